@@ -36,6 +36,11 @@ pub const LARGE_READ_BUF_SIZE: usize = 16384;
 /// Optimized for encoding larger messages.
 pub const LARGE_WRITE_BUF_SIZE: usize = 16384;
 
+/// Largest buffer size [`BufferConfig::for_message_size`] picks (1MB)
+///
+/// Larger messages still work; they span several reads and writes.
+pub const MAX_HINTED_BUF_SIZE: usize = 1024 * 1024;
+
 /// Initial staging buffer capacity for decoder reassembly (256 bytes)
 ///
 /// Pre-allocated to avoid initial reallocation on fragmented frames.
@@ -83,6 +88,29 @@ impl BufferConfig {
         }
     }
 
+    /// Configuration sized for messages of about `expected` bytes
+    ///
+    /// Both buffers get the next power of two at or above `expected`, clamped
+    /// to [`SMALL_READ_BUF_SIZE`]..=[`MAX_HINTED_BUF_SIZE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monocoque_core::config::BufferConfig;
+    ///
+    /// let config = BufferConfig::for_message_size(10 * 1024);
+    /// assert_eq!(config.read_buf_size, 16 * 1024);
+    /// ```
+    #[must_use]
+    pub const fn for_message_size(expected: usize) -> Self {
+        let size = match expected.checked_next_power_of_two() {
+            Some(size) if size < SMALL_READ_BUF_SIZE => SMALL_READ_BUF_SIZE,
+            Some(size) if size <= MAX_HINTED_BUF_SIZE => size,
+            _ => MAX_HINTED_BUF_SIZE,
+        };
+        Self::custom(size, size)
+    }
+
     /// Custom buffer configuration
     #[must_use]
     pub const fn custom(read_buf_size: usize, write_buf_size: usize) -> Self {
@@ -92,3 +120,56 @@ impl BufferConfig {
         }
     }
 }
+
+/// Snapshot of a socket's userspace buffer footprint.
+///
+/// Returned by the `buffer_stats()` accessor on sockets. Capacities are the
+/// bytes currently allocated, not the bytes queued; compare them with the
+/// configured `write_buffer_size` to see whether a burst left a buffer
+/// oversized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BufferStats {
+    /// Allocated capacity of the batching send buffer (`send_buffered` / coalescing).
+    pub send_buffer_capacity: usize,
+    /// Allocated capacity of the per-message encode buffer used by `send()`.
+    pub write_buffer_capacity: usize,
+    /// Remaining capacity of the current read slab.
+    pub read_buffer_capacity: usize,
+    /// Exponentially weighted average of recent flush sizes, in bytes.
+    pub avg_flush_bytes: usize,
+    /// Bytes read off the wire and not yet decoded or handed out.
+    pub recv_queued_bytes: usize,
+    /// Allocated capacity of the decoder's reassembly buffer for fragmented frames.
+    pub staging_capacity: usize,
+    /// Frames received on the current connection and handed out as a view of
+    /// the read buffer, with no copy.
+    pub zero_copy_frames: u64,
+    /// Frames received on the current connection whose body spanned reads and
+    /// had to be copied. A high share next to `zero_copy_frames` means the
+    /// read buffer is smaller than the typical message.
+    pub copied_frames: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_message_size_rounds_up_to_a_power_of_two() {
+        let sizes = |expected| {
+            let config = BufferConfig::for_message_size(expected);
+            (config.read_buf_size, config.write_buf_size)
+        };
+        assert_eq!(sizes(100), (4096, 4096));
+        assert_eq!(sizes(10 * 1024), (16384, 16384));
+        assert_eq!(sizes(16384), (16384, 16384));
+        assert_eq!(
+            sizes(5 * 1024 * 1024),
+            (MAX_HINTED_BUF_SIZE, MAX_HINTED_BUF_SIZE)
+        );
+        assert_eq!(
+            sizes(usize::MAX),
+            (MAX_HINTED_BUF_SIZE, MAX_HINTED_BUF_SIZE)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 //! Endpoint abstraction for transport-agnostic socket addressing.
 //!
-//! Provides unified addressing for TCP and IPC transports with parsing support.
+//! Provides unified addressing for TCP, TLS and IPC transports with parsing
+//! support.
 
 use std::fmt;
 use std::net::SocketAddr;
@@ -12,6 +13,11 @@ use std::str::FromStr;
 pub enum Endpoint {
     /// TCP transport: `tcp://host:port`
     Tcp(SocketAddr),
+    /// TLS over TCP: `tls://host:port`, with the name the server certificate
+    /// is verified against (the host). The `host:port` is kept as written
+    /// and resolved when connecting, so parsing never blocks on DNS.
+    #[cfg(feature = "tls")]
+    Tls(String, rustls::pki_types::ServerName<'static>),
     /// IPC transport (Unix domain socket): `ipc:///path/to/socket`
     #[cfg(unix)]
     Ipc(PathBuf),
@@ -25,6 +31,7 @@ impl Endpoint {
     /// Supported formats:
     /// - `tcp://127.0.0.1:5555`
     /// - `tcp://[::1]:5555` (IPv6)
+    /// - `tls://example.com:443` (`tls` feature)
     /// - `ipc:///tmp/socket.sock` (Unix only)
     /// - `inproc://name`
     ///
@@ -49,12 +56,41 @@ impl Endpoint {
         s.parse()
     }
 
+    /// The transport scheme: `"tcp"`, `"tls"`, `"ipc"` or `"inproc"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monocoque_core::endpoint::Endpoint;
+    ///
+    /// let endpoint = Endpoint::parse("inproc://jobs").unwrap();
+    /// assert_eq!(endpoint.scheme(), "inproc");
+    /// ```
+    #[must_use]
+    pub const fn scheme(&self) -> &'static str {
+        match self {
+            Self::Tcp(_) => "tcp",
+            #[cfg(feature = "tls")]
+            Self::Tls(..) => "tls",
+            #[cfg(unix)]
+            Self::Ipc(_) => "ipc",
+            Self::Inproc(_) => "inproc",
+        }
+    }
+
     /// Returns true if this is a TCP endpoint.
     #[must_use]
     pub const fn is_tcp(&self) -> bool {
         matches!(self, Self::Tcp(_))
     }
 
+    /// Returns true if this is a TLS endpoint.
+    #[cfg(feature = "tls")]
+    #[must_use]
+    pub const fn is_tls(&self) -> bool {
+        matches!(self, Self::Tls(..))
+    }
+
     /// Returns true if this is an IPC endpoint.
     #[cfg(unix)]
     #[must_use]
@@ -78,6 +114,25 @@ impl FromStr for Endpoint {
                 .parse::<SocketAddr>()
                 .map_err(|_| EndpointError::InvalidTcpAddress(addr.to_string()))?;
             Ok(Self::Tcp(socket_addr))
+        } else if let Some(addr) = s.strip_prefix("tls://") {
+            #[cfg(feature = "tls")]
+            {
+                let invalid = || EndpointError::InvalidTlsAddress(addr.to_string());
+                let (host, port) = addr.rsplit_once(':').ok_or_else(invalid)?;
+                port.parse::<u16>().map_err(|_| invalid())?;
+                let host = host
+                    .strip_prefix('[')
+                    .and_then(|h| h.strip_suffix(']'))
+                    .unwrap_or(host);
+                let name = rustls::pki_types::ServerName::try_from(host.to_string())
+                    .map_err(|_| invalid())?;
+                Ok(Self::Tls(addr.to_string(), name))
+            }
+            #[cfg(not(feature = "tls"))]
+            {
+                let _ = addr;
+                Err(EndpointError::TlsNotSupported)
+            }
         } else if let Some(path) = s.strip_prefix("ipc://") {
             #[cfg(unix)]
             {
@@ -104,13 +159,18 @@ impl FromStr for Endpoint {
     }
 }
 
+/// Formats the canonical ZeroMQ endpoint string, which [`Endpoint::parse`]
+/// reads back to the same endpoint.
 impl fmt::Display for Endpoint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://", self.scheme())?;
         match self {
-            Self::Tcp(addr) => write!(f, "tcp://{addr}"),
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            #[cfg(feature = "tls")]
+            Self::Tls(addr, _) => f.write_str(addr),
             #[cfg(unix)]
-            Self::Ipc(path) => write!(f, "ipc://{}", path.display()),
-            Self::Inproc(name) => write!(f, "inproc://{name}"),
+            Self::Ipc(path) => write!(f, "{}", path.display()),
+            Self::Inproc(name) => f.write_str(name),
         }
     }
 }
@@ -118,12 +178,18 @@ impl fmt::Display for Endpoint {
 /// Errors that can occur when parsing or using endpoints.
 #[derive(Debug, thiserror::Error)]
 pub enum EndpointError {
-    #[error("Invalid scheme in endpoint: {0} (expected tcp://, ipc://, or inproc://)")]
+    #[error("Invalid scheme in endpoint: {0} (expected tcp://, tls://, ipc://, or inproc://)")]
     InvalidScheme(String),
 
     #[error("Invalid TCP address: {0}")]
     InvalidTcpAddress(String),
 
+    #[error("Invalid TLS address: {0} (expected host:port)")]
+    InvalidTlsAddress(String),
+
+    #[error("TLS transport requires the `tls` feature")]
+    TlsNotSupported,
+
     #[error("Invalid inproc name: {0}")]
     InvalidInprocName(String),
 
@@ -178,6 +244,43 @@ mod tests {
         assert!(matches!(result, Err(EndpointError::InvalidTcpAddress(_))));
     }
 
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_parse_tls() {
+        use rustls::pki_types::ServerName;
+
+        let endpoint = Endpoint::parse("tls://example.com:443").unwrap();
+        assert_eq!(
+            endpoint,
+            Endpoint::Tls(
+                "example.com:443".to_string(),
+                ServerName::try_from("example.com").unwrap()
+            )
+        );
+        assert_eq!(endpoint.to_string(), "tls://example.com:443");
+
+        let endpoint = Endpoint::parse("tls://[::1]:5555").unwrap();
+        assert!(matches!(
+            endpoint,
+            Endpoint::Tls(_, ServerName::IpAddress(_))
+        ));
+
+        for invalid in ["tls://example.com", "tls://example.com:https", "tls://:443"] {
+            let result = Endpoint::parse(invalid);
+            assert!(
+                matches!(result, Err(EndpointError::InvalidTlsAddress(_))),
+                "{invalid}: {result:?}"
+            );
+        }
+    }
+
+    #[cfg(not(feature = "tls"))]
+    #[test]
+    fn test_tls_requires_feature() {
+        let result = Endpoint::parse("tls://example.com:443");
+        assert!(matches!(result, Err(EndpointError::TlsNotSupported)));
+    }
+
     #[test]
     fn test_parse_inproc() {
         let endpoint = Endpoint::parse("inproc://my-endpoint").unwrap();
@@ -185,6 +288,69 @@ mod tests {
         assert_eq!(endpoint.to_string(), "inproc://my-endpoint");
     }
 
+    fn random_name(rng: &mut impl rand::Rng) -> String {
+        const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-_.";
+        let len = rng.gen_range(1..16);
+        (0..len)
+            .map(|_| char::from(CHARS[rng.gen_range(0..CHARS.len())]))
+            .collect()
+    }
+
+    /// A random endpoint of every transport compiled in.
+    fn random_endpoint(rng: &mut impl rand::Rng) -> Endpoint {
+        use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV6};
+
+        match rng.gen_range(0..5) {
+            0 => Endpoint::Tcp(SocketAddr::from((
+                Ipv4Addr::from(rng.r#gen::<u32>()),
+                rng.r#gen::<u16>(),
+            ))),
+            1 => Endpoint::Tcp(SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(rng.r#gen::<u128>()),
+                rng.r#gen(),
+                0,
+                rng.gen_range(0..4),
+            ))),
+            #[cfg(feature = "tls")]
+            2 => Endpoint::parse(&format!(
+                "tls://host{}.example:{}",
+                rng.r#gen::<u32>(),
+                rng.r#gen::<u16>()
+            ))
+            .unwrap(),
+            #[cfg(unix)]
+            3 => Endpoint::Ipc(PathBuf::from(format!(
+                "/tmp/{}/{}.sock",
+                random_name(rng),
+                random_name(rng)
+            ))),
+            _ => Endpoint::Inproc(random_name(rng)),
+        }
+    }
+
+    #[test]
+    fn display_roundtrips_through_parse() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let endpoint = random_endpoint(&mut rng);
+            let text = endpoint.to_string();
+            assert!(
+                text.starts_with(&format!("{}://", endpoint.scheme())),
+                "{text}"
+            );
+            assert_eq!(text.parse::<Endpoint>().unwrap(), endpoint, "{text}");
+        }
+    }
+
+    #[test]
+    fn endpoints_key_a_hash_map() {
+        let mut seen = std::collections::HashMap::new();
+        seen.insert(Endpoint::parse("tcp://127.0.0.1:5555").unwrap(), 1);
+        seen.insert(Endpoint::parse("inproc://jobs").unwrap(), 2);
+        assert_eq!(seen[&"tcp://127.0.0.1:5555".parse().unwrap()], 1);
+        assert_eq!(seen[&Endpoint::Inproc("jobs".into())], 2);
+    }
+
     #[test]
     fn test_invalid_inproc_empty() {
         let result = Endpoint::parse("inproc://");
@@ -4,60 +4,167 @@
 use std::io;
 use thiserror::Error;
 
-/// Main error type for Monocoque operations
+/// Error from a socket's non-blocking `try_send`.
+///
+/// `Full` and `WouldBlock` hand the message back untouched, so the caller can
+/// retry it later or drop it on purpose.
 #[derive(Error, Debug)]
-pub enum MonocoqueError {
-    /// IO error during socket operations
-    #[error("IO error: {0}")]
-    Io(#[from] io::Error),
-
-    /// Protocol error during ZMTP handshake or framing
-    #[error("Protocol error: {0}")]
-    Protocol(String),
-
-    /// Handshake timeout
-    #[error("Handshake timeout after {0:?}")]
-    HandshakeTimeout(std::time::Duration),
-
-    /// Invalid greeting received
-    #[error("Invalid greeting: {0}")]
-    InvalidGreeting(String),
-
-    /// Invalid frame format
-    #[error("Invalid frame: {0}")]
-    InvalidFrame(String),
-
-    /// Socket closed
-    #[error("Socket closed")]
-    SocketClosed,
-
-    /// Channel send error
-    #[error("Channel send error")]
-    ChannelSend,
-
-    /// Channel receive error
-    #[error("Channel receive error")]
-    ChannelRecv,
-
-    /// Peer disconnected
-    #[error("Peer disconnected: {0}")]
-    PeerDisconnected(String),
-
-    /// Invalid routing ID
-    #[error("Invalid routing ID")]
-    InvalidRoutingId,
-
-    /// Message too large
-    #[error("Message too large: {size} bytes (max: {max})")]
-    MessageTooLarge { size: usize, max: usize },
-
-    /// Subscription error
-    #[error("Subscription error: {0}")]
-    Subscription(String),
+pub enum TrySendError {
+    /// The send high water mark is reached; flush before queueing more.
+    #[error("send high water mark reached")]
+    Full(Vec<bytes::Bytes>),
+
+    /// The socket has no live connection to queue the message on.
+    #[error("socket cannot take the message without waiting")]
+    WouldBlock(Vec<bytes::Bytes>),
+
+    /// The socket failed; the message was not queued.
+    #[error(transparent)]
+    Io(io::Error),
+}
+
+impl TrySendError {
+    /// The message handed back, if it was not consumed.
+    #[must_use]
+    pub fn into_message(self) -> Option<Vec<bytes::Bytes>> {
+        match self {
+            Self::Full(msg) | Self::WouldBlock(msg) => Some(msg),
+            Self::Io(_) => None,
+        }
+    }
+}
+
+impl From<TrySendError> for io::Error {
+    fn from(err: TrySendError) -> Self {
+        match err {
+            TrySendError::Io(err) => err,
+            err => Self::new(io::ErrorKind::WouldBlock, err.to_string()),
+        }
+    }
+}
+
+/// Error returned by the public socket API's `connect`, `bind`, `send` and
+/// `recv` entry points.
+///
+/// Sorts failures into the cases callers handle differently. Every variant
+/// keeps the `io::Error` it was raised as, so [`kind`](Self::kind), the
+/// message and the wrapped source error are unchanged; only the variant adds
+/// information.
+///
+/// Converting from a bare `io::Error` classifies by kind: `TimedOut`,
+/// `NotConnected`, `PermissionDenied` (rejected credentials) and
+/// `InvalidData` (a protocol violation) get their own variants, and
+/// everything else becomes [`Error::Io`]. The sockets classify their own
+/// errors more precisely, since they know which came from the handshake or
+/// from a size limit.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Any other I/O failure, such as a reset or refused connection.
+    #[error(transparent)]
+    Io(io::Error),
+
+    /// The ZMTP handshake failed, or the peer rejected the connection
+    /// during it.
+    #[error(transparent)]
+    Handshake(io::Error),
+
+    /// The peer violated the ZMTP protocol after the handshake.
+    #[error(transparent)]
+    Protocol(io::Error),
+
+    /// A frame or multipart message exceeded `max_msg_size` or
+    /// `max_multipart_size`.
+    #[error(transparent)]
+    MessageTooLarge(io::Error),
+
+    /// PLAIN or CURVE authentication failed.
+    #[error(transparent)]
+    Auth(io::Error),
+
+    /// A configured timeout elapsed.
+    #[error(transparent)]
+    Timeout(io::Error),
+
+    /// The socket has no connection to use.
+    #[error(transparent)]
+    NotConnected(io::Error),
+}
+
+impl Error {
+    /// The `io::ErrorKind` of the underlying error.
+    #[must_use]
+    pub fn kind(&self) -> io::ErrorKind {
+        self.io_error().kind()
+    }
+
+    /// The underlying `io::Error`.
+    #[must_use]
+    pub const fn io_error(&self) -> &io::Error {
+        match self {
+            Self::Io(err)
+            | Self::Handshake(err)
+            | Self::Protocol(err)
+            | Self::MessageTooLarge(err)
+            | Self::Auth(err)
+            | Self::Timeout(err)
+            | Self::NotConnected(err) => err,
+        }
+    }
+
+    /// Unwrap the underlying `io::Error`.
+    #[must_use]
+    pub fn into_io(self) -> io::Error {
+        match self {
+            Self::Io(err)
+            | Self::Handshake(err)
+            | Self::Protocol(err)
+            | Self::MessageTooLarge(err)
+            | Self::Auth(err)
+            | Self::Timeout(err)
+            | Self::NotConnected(err) => err,
+        }
+    }
+
+    /// Replace the underlying `io::Error`, keeping the variant.
+    fn map_io(self, f: impl FnOnce(io::Error) -> io::Error) -> Self {
+        match self {
+            Self::Io(err) => Self::Io(f(err)),
+            Self::Handshake(err) => Self::Handshake(f(err)),
+            Self::Protocol(err) => Self::Protocol(f(err)),
+            Self::MessageTooLarge(err) => Self::MessageTooLarge(f(err)),
+            Self::Auth(err) => Self::Auth(f(err)),
+            Self::Timeout(err) => Self::Timeout(f(err)),
+            Self::NotConnected(err) => Self::NotConnected(f(err)),
+        }
+    }
+
+    /// Prefix the message with `ctx`, keeping the variant and kind.
+    fn with_prefix(self, ctx: &str) -> Self {
+        self.map_io(|err| io::Error::new(err.kind(), format!("{ctx}: {err}")))
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::TimedOut => Self::Timeout(err),
+            io::ErrorKind::NotConnected => Self::NotConnected(err),
+            io::ErrorKind::PermissionDenied => Self::Auth(err),
+            io::ErrorKind::InvalidData => Self::Protocol(err),
+            _ => Self::Io(err),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        err.into_io()
+    }
 }
 
 /// Result type alias for Monocoque operations
-pub type Result<T> = std::result::Result<T, MonocoqueError>;
+pub type Result<T> = std::result::Result<T, Error>;
 
 /// Extension trait for adding context to results
 pub trait ResultExt<T> {
@@ -72,75 +179,177 @@ pub trait ResultExt<T> {
 
 impl<T> ResultExt<T> for Result<T> {
     fn context(self, context: impl Into<String>) -> Self {
-        self.map_err(|e| {
-            let ctx = context.into();
-            match e {
-                MonocoqueError::Io(io_err) => {
-                    MonocoqueError::Io(io::Error::new(io_err.kind(), format!("{ctx}: {io_err}")))
-                }
-                MonocoqueError::Protocol(msg) => MonocoqueError::Protocol(format!("{ctx}: {msg}")),
-                other => other,
-            }
-        })
+        self.map_err(|e| e.with_prefix(&context.into()))
     }
 
     fn with_context<F>(self, f: F) -> Self
     where
         F: FnOnce() -> String,
     {
-        self.map_err(|e| {
-            let ctx = f();
-            match e {
-                MonocoqueError::Io(io_err) => {
-                    MonocoqueError::Io(io::Error::new(io_err.kind(), format!("{ctx}: {io_err}")))
-                }
-                MonocoqueError::Protocol(msg) => MonocoqueError::Protocol(format!("{ctx}: {msg}")),
-                other => other,
-            }
-        })
+        self.map_err(|e| e.with_prefix(&f()))
     }
 }
 
-impl MonocoqueError {
-    /// Create a protocol error with a message
-    pub fn protocol(msg: impl Into<String>) -> Self {
-        Self::Protocol(msg.into())
-    }
+#[allow(deprecated)]
+pub use legacy::MonocoqueError;
+
+/// The deprecated [`MonocoqueError`], kept apart so its own derives and
+/// methods do not trip the deprecation lint.
+#[allow(deprecated)]
+mod legacy {
+    use std::io;
+    use thiserror::Error;
+
+    /// Former error type for Monocoque operations.
+    ///
+    /// Nothing in the socket API returns it; use [`Error`](enum@super::Error),
+    /// which the public `connect`/`bind`/`send`/`recv` entry points and
+    /// [`Result`](super::Result) share.
+    #[deprecated(note = "use `monocoque_core::error::Error`")]
+    #[derive(Error, Debug)]
+    pub enum MonocoqueError {
+        /// IO error during socket operations
+        #[error("IO error: {0}")]
+        Io(#[from] io::Error),
+
+        /// Protocol error during ZMTP handshake or framing
+        #[error("Protocol error: {0}")]
+        Protocol(String),
+
+        /// Handshake timeout
+        #[error("Handshake timeout after {0:?}")]
+        HandshakeTimeout(std::time::Duration),
+
+        /// Invalid greeting received
+        #[error("Invalid greeting: {0}")]
+        InvalidGreeting(String),
+
+        /// Invalid frame format
+        #[error("Invalid frame: {0}")]
+        InvalidFrame(String),
+
+        /// Socket closed
+        #[error("Socket closed")]
+        SocketClosed,
+
+        /// Channel send error
+        #[error("Channel send error")]
+        ChannelSend,
+
+        /// Channel receive error
+        #[error("Channel receive error")]
+        ChannelRecv,
+
+        /// Peer disconnected
+        #[error("Peer disconnected: {0}")]
+        PeerDisconnected(String),
 
-    /// Create an invalid greeting error
-    pub fn invalid_greeting(msg: impl Into<String>) -> Self {
-        Self::InvalidGreeting(msg.into())
+        /// Invalid routing ID
+        #[error("Invalid routing ID")]
+        InvalidRoutingId,
+
+        /// Message too large
+        #[error("Message too large: {size} bytes (max: {max})")]
+        MessageTooLarge { size: usize, max: usize },
+
+        /// Subscription error
+        #[error("Subscription error: {0}")]
+        Subscription(String),
     }
 
-    /// Create an invalid frame error
-    pub fn invalid_frame(msg: impl Into<String>) -> Self {
-        Self::InvalidFrame(msg.into())
+    impl MonocoqueError {
+        /// Create a protocol error with a message
+        pub fn protocol(msg: impl Into<String>) -> Self {
+            Self::Protocol(msg.into())
+        }
+
+        /// Create an invalid greeting error
+        pub fn invalid_greeting(msg: impl Into<String>) -> Self {
+            Self::InvalidGreeting(msg.into())
+        }
+
+        /// Create an invalid frame error
+        pub fn invalid_frame(msg: impl Into<String>) -> Self {
+            Self::InvalidFrame(msg.into())
+        }
+
+        /// Create a peer disconnected error
+        pub fn peer_disconnected(peer_id: impl Into<String>) -> Self {
+            Self::PeerDisconnected(peer_id.into())
+        }
+
+        /// Check if this error is recoverable
+        #[must_use]
+        pub fn is_recoverable(&self) -> bool {
+            match self {
+                Self::Io(e) => matches!(
+                    e.kind(),
+                    io::ErrorKind::Interrupted
+                        | io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                ),
+                Self::HandshakeTimeout(_) | Self::ChannelSend | Self::ChannelRecv => false,
+                _ => false,
+            }
+        }
+
+        /// Check if this is a connection error
+        #[must_use]
+        pub const fn is_connection_error(&self) -> bool {
+            matches!(
+                self,
+                Self::SocketClosed | Self::PeerDisconnected(_) | Self::HandshakeTimeout(_)
+            )
+        }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    /// Create a peer disconnected error
-    pub fn peer_disconnected(peer_id: impl Into<String>) -> Self {
-        Self::PeerDisconnected(peer_id.into())
+    #[test]
+    fn io_errors_are_classified_by_kind() {
+        let classify = |kind| Error::from(io::Error::new(kind, "boom"));
+        assert!(matches!(
+            classify(io::ErrorKind::TimedOut),
+            Error::Timeout(_)
+        ));
+        assert!(matches!(
+            classify(io::ErrorKind::NotConnected),
+            Error::NotConnected(_)
+        ));
+        assert!(matches!(
+            classify(io::ErrorKind::PermissionDenied),
+            Error::Auth(_)
+        ));
+        assert!(matches!(
+            classify(io::ErrorKind::InvalidData),
+            Error::Protocol(_)
+        ));
+        assert!(matches!(
+            classify(io::ErrorKind::ConnectionReset),
+            Error::Io(_)
+        ));
     }
 
-    /// Check if this error is recoverable
-    #[must_use]
-    pub fn is_recoverable(&self) -> bool {
-        match self {
-            Self::Io(e) => matches!(
-                e.kind(),
-                io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
-            ),
-            Self::HandshakeTimeout(_) | Self::ChannelSend | Self::ChannelRecv => false,
-            _ => false,
-        }
+    #[test]
+    fn context_keeps_variant_and_kind() {
+        let err: Result<()> = Err(Error::from(io::Error::new(io::ErrorKind::TimedOut, "slow")));
+        let err = err.context("connecting").unwrap_err();
+        assert!(matches!(err, Error::Timeout(_)));
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(err.to_string(), "connecting: slow");
     }
 
-    /// Check if this is a connection error
-    #[must_use]
-    pub const fn is_connection_error(&self) -> bool {
-        matches!(
-            self,
-            Self::SocketClosed | Self::PeerDisconnected(_) | Self::HandshakeTimeout(_)
-        )
+    #[test]
+    fn conversion_keeps_the_io_error() {
+        let err = Error::from(io::Error::new(io::ErrorKind::TimedOut, "too slow"));
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(err.to_string(), "too slow");
+
+        let back = io::Error::from(err);
+        assert_eq!(back.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(back.to_string(), "too slow");
     }
 }
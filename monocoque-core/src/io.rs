@@ -47,10 +47,13 @@ pub const COPY_OUT_THRESHOLD: usize = 4 * 1024;
 /// remaining tail in `stash` to hand out on the next call.
 ///
 /// This replaces the old bump-pointer read arena: successive reads carve
-/// `read_size` chunks off one `READ_SLAB_SIZE` allocation until it is used up,
-/// then a fresh slab is allocated. Freezing a returned buffer shares that
-/// slab's allocation (via `bytes` refcounting), so a lagging consumer pins the
-/// slab exactly as the arena page did.
+/// `read_size` chunks off one `READ_SLAB_SIZE` allocation until it is used up.
+/// Freezing a returned buffer shares that slab's allocation (via `bytes`
+/// refcounting), so a lagging consumer pins the slab exactly as the arena page
+/// did. Once every buffer and frame carved from a used-up slab has been
+/// dropped, `stash` is its only handle and the slab is recycled in place;
+/// otherwise a fresh slab is allocated. A receive loop that drops its frames
+/// before the slab runs out therefore reads through one allocation forever.
 ///
 /// # Safety
 ///
@@ -61,7 +64,11 @@ pub const COPY_OUT_THRESHOLD: usize = 4 * 1024;
 /// inspecting, or otherwise exposing its contents.
 pub unsafe fn take_read_buffer(stash: &mut BytesMut, read_size: usize) -> BytesMut {
     if stash.capacity() < read_size {
-        *stash = BytesMut::with_capacity(read_size.max(READ_SLAB_SIZE));
+        // `stash` is empty here (every previous call split off its whole
+        // length), so `reserve` reclaims the slab from its start when no
+        // other handle to it is alive and allocates a fresh one otherwise.
+        stash.clear();
+        stash.reserve(read_size.max(READ_SLAB_SIZE));
     }
     if stash.len() < read_size {
         // SAFETY: `read_size` is within capacity per the check above. The
@@ -205,6 +212,28 @@ mod tests {
         assert!(stash.capacity() >= READ_SLAB_SIZE - 256);
     }
 
+    #[test]
+    fn take_read_buffer_recycles_a_released_slab() {
+        let mut stash = BytesMut::new();
+        // SAFETY: bookkeeping-only test; contents are never inspected.
+        let first = unsafe { take_read_buffer(&mut stash, READ_SLAB_SIZE) };
+        let slab_ptr = first.as_ptr();
+
+        // A frame still pins the spent slab: the next read needs a new one.
+        let pinned = first.freeze();
+        // SAFETY: bookkeeping-only test.
+        let second = unsafe { take_read_buffer(&mut stash, READ_SLAB_SIZE) };
+        assert_ne!(second.as_ptr(), slab_ptr);
+        drop(pinned);
+
+        // Released: the slab behind `second` comes back once it is spent.
+        let second_ptr = second.as_ptr();
+        drop(second);
+        // SAFETY: bookkeeping-only test.
+        let third = unsafe { take_read_buffer(&mut stash, READ_SLAB_SIZE) };
+        assert_eq!(third.as_ptr(), second_ptr);
+    }
+
     #[test]
     fn take_read_buffer_reuses_one_slab_across_reads() {
         // Successive sub-slab reads carve from the same allocation, the way the
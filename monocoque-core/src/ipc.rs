@@ -9,6 +9,8 @@ use crate::rt::{UnixListener, UnixStream};
 use std::os::unix::fs::FileTypeExt;
 #[cfg(unix)]
 use std::path::{Path, PathBuf};
+#[cfg(unix)]
+use tracing::warn;
 
 /// A bound Unix domain socket listener that unlinks its socket file on drop.
 ///
@@ -19,11 +21,15 @@ use std::path::{Path, PathBuf};
 ///
 /// It [`Deref`](std::ops::Deref)s to the underlying [`UnixListener`], so it can be used anywhere
 /// a `&UnixListener` is expected (e.g. [`accept`]).
+///
+/// Call [`keep_on_drop`](Self::keep_on_drop) when the socket file must
+/// outlive this listener, for example when another process hands it on.
 #[cfg(unix)]
 #[derive(Debug)]
 pub struct IpcListener {
     listener: UnixListener,
     path: PathBuf,
+    unlink_on_drop: bool,
 }
 
 #[cfg(unix)]
@@ -39,6 +45,24 @@ impl IpcListener {
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    /// Leave the socket file in place when this listener is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monocoque_core::ipc;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let listener = ipc::bind("/run/shared.sock").await?.keep_on_drop();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn keep_on_drop(mut self) -> Self {
+        self.unlink_on_drop = false;
+        self
+    }
 }
 
 #[cfg(unix)]
@@ -53,13 +77,28 @@ impl std::ops::Deref for IpcListener {
 #[cfg(unix)]
 impl Drop for IpcListener {
     fn drop(&mut self) {
+        if !self.unlink_on_drop {
+            return;
+        }
         // Only remove the node if it is still the socket we bound. This avoids
         // deleting a regular file that raced into the path, and is a no-op if
         // the socket was already unlinked.
-        if let Ok(metadata) = std::fs::symlink_metadata(&self.path)
-            && metadata.file_type().is_socket()
-        {
-            let _ = std::fs::remove_file(&self.path);
+        let result = std::fs::symlink_metadata(&self.path).and_then(|metadata| {
+            if metadata.file_type().is_socket() {
+                std::fs::remove_file(&self.path)
+            } else {
+                Ok(())
+            }
+        });
+        match result {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                warn!(
+                    "[IPC] Could not remove socket file {}: {}",
+                    self.path.display(),
+                    e
+                );
+            }
+            _ => {}
         }
     }
 }
@@ -114,6 +153,7 @@ pub async fn bind<P: AsRef<Path>>(path: P) -> std::io::Result<IpcListener> {
     Ok(IpcListener {
         listener,
         path: path_ref.to_path_buf(),
+        unlink_on_drop: true,
     })
 }
 
@@ -187,6 +227,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rebind_after_drop_reuses_the_path() {
+        crate::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(rebind_after_drop_reuses_the_path_impl());
+    }
+
+    async fn rebind_after_drop_reuses_the_path_impl() {
+        let path =
+            std::env::temp_dir().join(format!("monocoque-ipc-rebind-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let first = bind(&path).await.unwrap();
+        drop(first);
+        let second = bind(&path).await.unwrap();
+        let client = connect(&path).await.unwrap();
+        let server = accept(&second).await.unwrap();
+        assert!(server.local_addr().is_ok());
+
+        drop((client, server));
+        drop(second);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn keep_on_drop_leaves_the_socket_file() {
+        crate::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(keep_on_drop_leaves_the_socket_file_impl());
+    }
+
+    async fn keep_on_drop_leaves_the_socket_file_impl() {
+        let path =
+            std::env::temp_dir().join(format!("monocoque-ipc-keep-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let listener = bind(&path).await.unwrap().keep_on_drop();
+        drop(listener);
+        assert!(path.exists(), "keep_on_drop must leave the socket file");
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn drop_leaves_non_socket_at_path_untouched() {
         crate::rt::LocalRuntime::new()
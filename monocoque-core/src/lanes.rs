@@ -0,0 +1,185 @@
+//! Two inbound lanes for a driver task: control ahead of data.
+//!
+//! A driver task that takes every command from one FIFO channel serves a PONG
+//! reply or a close request only after all the data queued before it, so a
+//! busy connection can miss its heartbeat deadline or ignore a close for as
+//! long as its backlog takes to drain. [`PriorityLanes`] gives the driver a
+//! small bounded control lane that is drained before the data lane.
+//!
+//! Control does not get unlimited precedence: while data is waiting, at most
+//! `control_burst` control items are served before the next data item, so a
+//! flood of control traffic (a peer spamming PINGs) cannot stall data either.
+//! Each lane stays FIFO; only the interleaving between them changes.
+
+use flume::{Receiver, Sender, TryRecvError};
+
+/// Capacity of a control lane made by [`control_lane`].
+///
+/// Control items are small and answered quickly, so a full lane means the
+/// driver is stuck or the peer is flooding; senders should drop or fall back
+/// rather than wait.
+pub const CONTROL_LANE_CAPACITY: usize = 64;
+
+/// Control items served back to back while data is waiting, by default.
+pub const DEFAULT_CONTROL_BURST: usize = 8;
+
+/// A bounded control lane ([`CONTROL_LANE_CAPACITY`]).
+#[must_use]
+pub fn control_lane<T>() -> (Sender<T>, Receiver<T>) {
+    flume::bounded(CONTROL_LANE_CAPACITY)
+}
+
+/// Receiving side of a driver's control and data lanes.
+///
+/// [`recv`](Self::recv) returns the next control item if there is one (within
+/// the burst limit), otherwise the next data item, otherwise waits for
+/// whichever lane delivers first. It returns `None` once the data lane is
+/// disconnected and drained: the data senders own the driver's lifetime, and
+/// control senders (a peer reader queueing PONGs, say) do not keep it alive.
+#[derive(Debug)]
+pub struct PriorityLanes<T> {
+    control: Receiver<T>,
+    data: Receiver<T>,
+    control_burst: usize,
+    /// Control items served since the last data item while data was waiting.
+    streak: usize,
+}
+
+impl<T> PriorityLanes<T> {
+    /// Lanes over `control` and `data`, with [`DEFAULT_CONTROL_BURST`].
+    #[must_use]
+    pub const fn new(control: Receiver<T>, data: Receiver<T>) -> Self {
+        Self {
+            control,
+            data,
+            control_burst: DEFAULT_CONTROL_BURST,
+            streak: 0,
+        }
+    }
+
+    /// Lanes whose control lane has no sender, so only `data` delivers.
+    #[must_use]
+    pub fn data_only(data: Receiver<T>) -> Self {
+        let (_, control) = flume::bounded(0);
+        Self::new(control, data)
+    }
+
+    /// Serve at most `burst` control items in a row while data is waiting.
+    /// Zero puts waiting data ahead of control.
+    #[must_use]
+    pub const fn with_control_burst(mut self, burst: usize) -> Self {
+        self.control_burst = burst;
+        self
+    }
+
+    /// Take the next item without waiting.
+    pub fn try_recv(&mut self) -> Option<T> {
+        if self.streak < self.control_burst
+            && let Ok(item) = self.control.try_recv()
+        {
+            if !self.data.is_empty() {
+                self.streak += 1;
+            }
+            return Some(item);
+        }
+        match self.data.try_recv() {
+            Ok(item) => {
+                self.streak = 0;
+                Some(item)
+            }
+            // No data is waiting, so control cannot starve it.
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => self.control.try_recv().ok(),
+        }
+    }
+
+    /// Wait for the next item; `None` once the data lane is closed and empty.
+    pub async fn recv(&mut self) -> Option<T> {
+        use futures::{FutureExt, select_biased};
+
+        loop {
+            if let Some(item) = self.try_recv() {
+                return Some(item);
+            }
+            if self.data.is_disconnected() {
+                return None;
+            }
+            if self.control.is_disconnected() {
+                let item = self.data.recv_async().await.ok();
+                self.streak = 0;
+                return item;
+            }
+            select_biased! {
+                item = self.control.recv_async().fuse() => {
+                    if let Ok(item) = item {
+                        return Some(item);
+                    }
+                }
+                item = self.data.recv_async().fuse() => {
+                    if let Ok(item) = item {
+                        self.streak = 0;
+                        return Some(item);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Items waiting in the data lane.
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_is_served_first_within_the_burst() {
+        let (control_tx, control_rx) = control_lane();
+        let (data_tx, data_rx) = flume::unbounded();
+        let mut lanes = PriorityLanes::new(control_rx, data_rx).with_control_burst(2);
+
+        for i in 0..3 {
+            data_tx.send(("data", i)).unwrap();
+        }
+        for i in 0..5 {
+            control_tx.send(("control", i)).unwrap();
+        }
+
+        let order: Vec<_> = std::iter::from_fn(|| lanes.try_recv()).collect();
+        assert_eq!(
+            order,
+            vec![
+                ("control", 0),
+                ("control", 1),
+                ("data", 0),
+                ("control", 2),
+                ("control", 3),
+                ("data", 1),
+                ("control", 4),
+                ("data", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn recv_ends_once_the_data_lane_closes() {
+        crate::rt::LocalRuntime::new().unwrap().block_on(async {
+            let (control_tx, control_rx) = control_lane();
+            let (data_tx, data_rx) = flume::unbounded();
+            let mut lanes = PriorityLanes::new(control_rx, data_rx);
+
+            data_tx.send(1).unwrap();
+            control_tx.send(2).unwrap();
+            drop(data_tx);
+            assert_eq!(lanes.recv().await, Some(2));
+            assert_eq!(lanes.recv().await, Some(1));
+
+            // A live control sender does not keep the driver going.
+            assert_eq!(lanes.recv().await, None);
+            drop(control_tx);
+        });
+    }
+}
@@ -5,6 +5,7 @@
 //! - Shared owned-buffer I/O helpers for the runtime backends (`io`)
 //! - TCP utilities for high-performance networking (`tcp`)
 //! - ROUTER hub + peer map (`router`)
+//! - Control-before-data inbound lanes for driver tasks (`lanes`)
 //! - PUB/SUB core (subscription index + hub) (`pubsub`)
 //! - Byte-based backpressure (`backpressure`)
 //! - Error types (`error`)
@@ -39,6 +40,7 @@ pub mod endpoint;
 pub mod error;
 pub mod inproc;
 pub mod io;
+pub mod lanes;
 pub mod message;
 pub mod message_builder;
 pub mod monitor;
@@ -50,14 +52,19 @@ pub mod rt;
 pub mod socket_type;
 pub mod subscription;
 pub mod tcp;
+pub mod testing;
 pub mod timeout;
+#[cfg(feature = "tls")]
+pub mod tls;
 
 #[cfg(unix)]
 pub mod ipc;
 
 pub mod pubsub {
+    pub mod cache;
     pub mod hub;
     pub mod index;
+    pub mod shared;
 }
 
 // Optional: a small prelude to make downstream crates ergonomic.
@@ -66,13 +73,18 @@ pub mod prelude {
     pub use crate::backpressure::{BytePermits, NoOpPermits, Permit, SemaphorePermits};
     pub use crate::buffer::SegmentedBuffer;
     pub use crate::endpoint::Endpoint;
-    pub use crate::message_builder::Message;
-    pub use crate::monitor::{SocketEvent, SocketMonitor};
+    pub use crate::message_builder::{Message, MessageBuilder};
+    pub use crate::monitor::{MonitoredEvent, SocketEvent, SocketMonitor};
     pub use crate::options::SocketOptions;
     pub use crate::poison::PoisonGuard;
+    pub use crate::pubsub::cache::LastValueCache;
     pub use crate::pubsub::hub::{PubSubCmd, PubSubEvent, PubSubHub};
-    pub use crate::pubsub::index::{PeerKey, SubscriptionIndex};
-    pub use crate::reconnect::{ReconnectError, ReconnectState};
+    pub use crate::pubsub::index::{PeerKey, SubscriptionIndex, WildcardEntry};
+    pub use crate::pubsub::shared::SharedSubscriptionIndex;
+    pub use crate::reconnect::{
+        ConnectAttempt, ConnectHistory, ConnectOutcome, ConstantPolicy, ExponentialPolicy,
+        FibonacciPolicy, ReconnectError, ReconnectPolicy, ReconnectState,
+    };
     pub use crate::router::{HubEvent, PeerCmd, RouterBehavior, RouterCmd, RouterHub};
     pub use crate::socket_type::SocketType;
     pub use crate::tcp::{configure_tcp_keepalive, enable_tcp_nodelay};
@@ -4,9 +4,14 @@
 //! automatic frame handling and type conversions.
 
 use bytes::Bytes;
+use smallvec::SmallVec;
 
 const MAX_PREALLOCATED_FRAMES: usize = 1024;
 
+/// Inline frame storage for [`MessageBuilder`]; 1-4 frame messages (the
+/// common envelope shapes) never touch the heap for the frame list itself.
+pub type InlineFrames = SmallVec<[Bytes; 4]>;
+
 /// Builder for constructing `ZeroMQ` multipart messages.
 ///
 /// Provides a fluent API for adding frames to a message with automatic
@@ -66,6 +71,27 @@ impl Message {
         Self { frames: Vec::new() }
     }
 
+    /// Start a [`MessageBuilder`] for assembling frames without per-frame
+    /// allocations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monocoque_core::message_builder::Message;
+    /// use bytes::Bytes;
+    ///
+    /// let frames = Message::builder()
+    ///     .frame_static(b"orders.eu")
+    ///     .frame(Bytes::from(vec![1, 2, 3]))
+    ///     .frame_copy(&[4, 5, 6])
+    ///     .build();
+    /// assert_eq!(frames.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn builder() -> MessageBuilder {
+        MessageBuilder::new()
+    }
+
     /// Create a message with pre-allocated capacity.
     ///
     /// Useful when you know the number of frames in advance to avoid reallocations.
@@ -297,6 +323,108 @@ impl Message {
     }
 }
 
+/// Allocation-conscious builder for multipart messages.
+///
+/// Unlike [`Message`], which converts every pushed value through `Into<Bytes>`,
+/// the builder makes the cost of each frame explicit:
+///
+/// - [`frame_static`](Self::frame_static) wraps a `&'static [u8]` with
+///   `Bytes::from_static`: no allocation, no copy.
+/// - [`frame`](Self::frame) takes an existing `Bytes` as-is (refcount only).
+/// - [`frame_copy`](Self::frame_copy) copies a borrowed slice into a new
+///   allocation, for data that does not outlive the call.
+///
+/// Frames are held inline for messages of up to four parts. The ZMTP MORE
+/// flag of each frame follows from its position (set on all but the last),
+/// which [`frames_with_more`](Self::frames_with_more) exposes.
+#[derive(Debug, Clone, Default)]
+pub struct MessageBuilder {
+    frames: InlineFrames,
+}
+
+impl MessageBuilder {
+    /// Create an empty builder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            frames: SmallVec::new(),
+        }
+    }
+
+    /// Append a frame backed by static data, without allocating or copying.
+    #[must_use]
+    pub fn frame_static(mut self, data: &'static [u8]) -> Self {
+        self.frames.push(Bytes::from_static(data));
+        self
+    }
+
+    /// Append an existing `Bytes` frame (O(1), shares its buffer).
+    #[must_use]
+    pub fn frame(mut self, data: Bytes) -> Self {
+        self.frames.push(data);
+        self
+    }
+
+    /// Append a frame by copying a borrowed slice.
+    #[must_use]
+    pub fn frame_copy(mut self, data: &[u8]) -> Self {
+        self.frames.push(Bytes::copy_from_slice(data));
+        self
+    }
+
+    /// Append an empty delimiter frame (as used in ROUTER/REQ envelopes).
+    #[must_use]
+    pub fn delimiter(mut self) -> Self {
+        self.frames.push(Bytes::new());
+        self
+    }
+
+    /// Get the number of frames added so far.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if no frames have been added.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Iterate the frames paired with the MORE flag each will carry on the wire.
+    pub fn frames_with_more(&self) -> impl Iterator<Item = (&Bytes, bool)> {
+        let last = self.frames.len().saturating_sub(1);
+        self.frames
+            .iter()
+            .enumerate()
+            .map(move |(i, frame)| (frame, i < last))
+    }
+
+    /// Finish the message as the `Vec<Bytes>` that sockets accept.
+    #[must_use]
+    pub fn build(self) -> Vec<Bytes> {
+        self.frames.into_vec()
+    }
+
+    /// Finish the message keeping the inline frame storage.
+    #[must_use]
+    pub fn build_inline(self) -> InlineFrames {
+        self.frames
+    }
+}
+
+impl From<MessageBuilder> for Vec<Bytes> {
+    fn from(builder: MessageBuilder) -> Self {
+        builder.build()
+    }
+}
+
+impl From<MessageBuilder> for Message {
+    fn from(builder: MessageBuilder) -> Self {
+        Self::from_frames(builder.build())
+    }
+}
+
 impl From<Vec<Bytes>> for Message {
     fn from(frames: Vec<Bytes>) -> Self {
         Self::from_frames(frames)
@@ -376,6 +504,24 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_builder_frame_static_does_not_copy() {
+        static PAYLOAD: &[u8] = b"static payload";
+        let frames = Message::builder().frame_static(PAYLOAD).build_inline();
+        assert_eq!(frames[0].as_ptr(), PAYLOAD.as_ptr());
+        assert!(!frames.spilled());
+    }
+
+    #[test]
+    fn test_builder_frames_with_more() {
+        let builder = Message::builder()
+            .frame_static(b"id")
+            .delimiter()
+            .frame_copy(b"body");
+        let flags: Vec<bool> = builder.frames_with_more().map(|(_, more)| more).collect();
+        assert_eq!(flags, [true, true, false]);
+    }
+
     #[test]
     fn test_from_frames() {
         let frames = vec![Bytes::from_static(b"a"), Bytes::from_static(b"b")];
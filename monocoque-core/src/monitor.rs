@@ -1,10 +1,15 @@
 //! Socket event monitoring.
 //!
 //! Provides event streams for tracking socket lifecycle events like
-//! connections, disconnections, and errors.
+//! connections, disconnections, and errors. Every event is delivered inside a
+//! [`MonitoredEvent`] envelope stamped with the time it was emitted and, where
+//! one applies, the remote peer.
 
 use crate::endpoint::Endpoint;
+use bytes::Bytes;
+use std::collections::HashMap;
 use std::fmt;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 /// Socket lifecycle events.
 #[derive(Debug, Clone)]
@@ -29,6 +34,59 @@ pub enum SocketEvent {
 
     /// Socket accepted a new incoming connection.
     Accepted(Endpoint),
+
+    /// A reconnection attempt to a lost peer is starting.
+    ConnectRetried {
+        /// Endpoint being reconnected to.
+        endpoint: Endpoint,
+        /// 1-based attempt number since the connection was lost.
+        attempt: u32,
+    },
+
+    /// Socket was closed by the application; carries its last endpoint.
+    Closed(Endpoint),
+
+    /// Buffered outgoing messages reached `send_hwm`; further buffered sends
+    /// are refused until a flush. Emitted once per crossing, not per refusal.
+    SendHwmReached {
+        /// Messages buffered when the limit was observed.
+        buffered: usize,
+    },
+
+    /// Buffered outgoing messages dropped back below `send_hwm` after a
+    /// [`SendHwmReached`](Self::SendHwmReached).
+    SendHwmCleared,
+}
+
+impl SocketEvent {
+    /// The remote peer this event concerns, if it has one.
+    ///
+    /// `Bound`, `Listening` and `Closed` describe the socket's own endpoint and
+    /// return `None`.
+    #[must_use]
+    pub const fn peer(&self) -> Option<&Endpoint> {
+        match self {
+            Self::Connected(ep) | Self::Disconnected(ep) | Self::Accepted(ep) => Some(ep),
+            Self::ConnectFailed { endpoint, .. } | Self::ConnectRetried { endpoint, .. } => {
+                Some(endpoint)
+            }
+            Self::Bound(_)
+            | Self::Listening(_)
+            | Self::BindFailed { .. }
+            | Self::Closed(_)
+            | Self::SendHwmReached { .. }
+            | Self::SendHwmCleared => None,
+        }
+    }
+
+    /// The reconnect attempt number, for retry events.
+    #[must_use]
+    pub const fn attempt(&self) -> Option<u32> {
+        match self {
+            Self::ConnectRetried { attempt, .. } => Some(*attempt),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for SocketEvent {
@@ -45,19 +103,98 @@ impl fmt::Display for SocketEvent {
             }
             Self::Listening(ep) => write!(f, "Listening on {ep}"),
             Self::Accepted(ep) => write!(f, "Accepted connection from {ep}"),
+            Self::ConnectRetried { endpoint, attempt } => {
+                write!(f, "Reconnecting to {endpoint} (attempt {attempt})")
+            }
+            Self::Closed(ep) => write!(f, "Closed {ep}"),
+            Self::SendHwmReached { buffered } => {
+                write!(f, "Send high water mark reached ({buffered} buffered)")
+            }
+            Self::SendHwmCleared => write!(f, "Send high water mark cleared"),
+        }
+    }
+}
+
+/// A [`SocketEvent`] stamped with when it happened and who it concerns.
+///
+/// `timestamp` is monotonic and is what to use for ordering and measuring the
+/// gap between events; `wall_time` is the matching wall-clock reading for logs.
+#[derive(Debug, Clone)]
+pub struct MonitoredEvent {
+    /// Monotonic time at which the event was emitted.
+    pub timestamp: Instant,
+    /// Wall-clock time at which the event was emitted.
+    pub wall_time: SystemTime,
+    /// Remote peer (TCP address or IPC path) the event concerns, if any.
+    pub peer: Option<Endpoint>,
+    /// Properties from the peer's handshake (READY metadata, plus `User-Id`
+    /// when ZAP authenticated it). Filled in for `Connected` events from
+    /// sockets that know them, empty otherwise.
+    pub peer_metadata: HashMap<String, Bytes>,
+    /// The lifecycle event itself.
+    pub event: SocketEvent,
+}
+
+impl MonitoredEvent {
+    /// Stamp `event` with the current time and its peer.
+    #[must_use]
+    pub fn new(event: SocketEvent) -> Self {
+        Self {
+            timestamp: Instant::now(),
+            wall_time: SystemTime::now(),
+            peer: event.peer().cloned(),
+            peer_metadata: HashMap::new(),
+            event,
         }
     }
+
+    /// Attach the peer's handshake properties.
+    #[must_use]
+    pub fn with_peer_metadata(
+        mut self,
+        metadata: impl IntoIterator<Item = (String, Bytes)>,
+    ) -> Self {
+        self.peer_metadata = metadata.into_iter().collect();
+        self
+    }
+
+    /// The reconnect attempt number, for retry events.
+    #[must_use]
+    pub const fn attempt(&self) -> Option<u32> {
+        self.event.attempt()
+    }
+}
+
+impl fmt::Display for MonitoredEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since_epoch = self
+            .wall_time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        write!(
+            f,
+            "[{}.{:03}] {}",
+            since_epoch.as_secs(),
+            since_epoch.subsec_millis(),
+            self.event
+        )?;
+        if let Some(peer) = &self.peer {
+            write!(f, " (peer {peer})")?;
+        }
+        Ok(())
+    }
 }
 
 /// Handle for receiving socket events.
 ///
-/// This is a channel receiver that provides a stream of socket lifecycle events.
-pub type SocketMonitor = flume::Receiver<SocketEvent>;
+/// This is a channel receiver that provides a stream of timestamped socket
+/// lifecycle events.
+pub type SocketMonitor = flume::Receiver<MonitoredEvent>;
 
 /// Internal sender for socket events.
 ///
 /// This is exposed publicly to allow socket implementations to emit events.
-pub type SocketEventSender = flume::Sender<SocketEvent>;
+pub type SocketEventSender = flume::Sender<MonitoredEvent>;
 
 /// Bound on the number of undrained monitor events held in the channel.
 ///
@@ -78,13 +215,53 @@ pub fn create_monitor() -> (SocketEventSender, SocketMonitor) {
     flume::bounded(MONITOR_CHANNEL_CAP)
 }
 
+/// Debounces send high-water-mark transitions into monitor events.
+///
+/// A socket sitting at its limit is observed on every refused send; only the
+/// transitions are worth an event. [`observe`](Self::observe) returns
+/// [`SocketEvent::SendHwmReached`] when the buffer first reaches the limit and
+/// [`SocketEvent::SendHwmCleared`] when it next drops below, and `None` for
+/// every observation in between.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HwmWatch {
+    reached: bool,
+}
+
+impl HwmWatch {
+    /// Record that `buffered` messages are queued against a limit of `hwm`
+    /// (zero means unlimited), returning the event for a transition.
+    #[must_use]
+    pub const fn observe(&mut self, buffered: usize, hwm: usize) -> Option<SocketEvent> {
+        let at_limit = hwm != 0 && buffered >= hwm;
+        if at_limit == self.reached {
+            return None;
+        }
+        self.reached = at_limit;
+        Some(if at_limit {
+            SocketEvent::SendHwmReached { buffered }
+        } else {
+            SocketEvent::SendHwmCleared
+        })
+    }
+}
+
 /// Emit a monitor event without ever blocking the socket path.
 ///
+/// The event is stamped into a [`MonitoredEvent`] at the moment of the call.
 /// Uses a non-blocking send: if the monitor is full (the application is not
 /// draining it) or the receiver has been dropped, the event is discarded rather
 /// than stalling the socket operation that produced it.
 pub fn emit(sender: &SocketEventSender, event: SocketEvent) {
-    let _ = sender.try_send(event);
+    let _ = sender.try_send(MonitoredEvent::new(event));
+}
+
+/// [`emit`] with the peer's handshake properties attached, for `Connected`.
+pub fn emit_with_metadata(
+    sender: &SocketEventSender,
+    event: SocketEvent,
+    metadata: impl IntoIterator<Item = (String, Bytes)>,
+) {
+    let _ = sender.try_send(MonitoredEvent::new(event).with_peer_metadata(metadata));
 }
 
 #[cfg(test)]
@@ -106,7 +283,77 @@ mod tests {
         emit(&sender, SocketEvent::Connected(Endpoint::Tcp(addr)));
 
         let event = receiver.recv().unwrap();
-        assert!(matches!(event, SocketEvent::Connected(_)));
+        assert!(matches!(event.event, SocketEvent::Connected(_)));
+        assert_eq!(event.peer, Some(Endpoint::Tcp(addr)));
+    }
+
+    #[test]
+    fn emit_with_metadata_attaches_peer_properties() {
+        let (sender, receiver) = create_monitor();
+        let addr: SocketAddr = "127.0.0.1:5555".parse().unwrap();
+        emit_with_metadata(
+            &sender,
+            SocketEvent::Connected(Endpoint::Tcp(addr)),
+            [("User-Id".to_owned(), Bytes::from_static(b"alice"))],
+        );
+
+        let event = receiver.recv().unwrap();
+        assert_eq!(event.peer_metadata["User-Id"], Bytes::from_static(b"alice"));
+        assert!(
+            MonitoredEvent::new(SocketEvent::Bound(Endpoint::Tcp(addr)))
+                .peer_metadata
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_monitored_event_peer_and_attempt() {
+        let addr: SocketAddr = "127.0.0.1:5555".parse().unwrap();
+
+        let bound = MonitoredEvent::new(SocketEvent::Bound(Endpoint::Tcp(addr)));
+        assert!(bound.peer.is_none());
+        assert!(bound.attempt().is_none());
+
+        let retry = MonitoredEvent::new(SocketEvent::ConnectRetried {
+            endpoint: Endpoint::Tcp(addr),
+            attempt: 3,
+        });
+        assert_eq!(retry.peer, Some(Endpoint::Tcp(addr)));
+        assert_eq!(retry.attempt(), Some(3));
+    }
+
+    #[test]
+    fn test_monitored_event_display_includes_time_and_peer() {
+        let addr: SocketAddr = "127.0.0.1:5555".parse().unwrap();
+        let mut event = MonitoredEvent::new(SocketEvent::ConnectRetried {
+            endpoint: Endpoint::Tcp(addr),
+            attempt: 2,
+        });
+        event.wall_time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_042);
+
+        assert_eq!(
+            event.to_string(),
+            "[1700000000.042] Reconnecting to tcp://127.0.0.1:5555 (attempt 2) \
+             (peer tcp://127.0.0.1:5555)"
+        );
+    }
+
+    #[test]
+    fn hwm_watch_reports_transitions_only() {
+        let mut watch = HwmWatch::default();
+        assert!(watch.observe(9, 10).is_none());
+        assert!(matches!(
+            watch.observe(10, 10),
+            Some(SocketEvent::SendHwmReached { buffered: 10 })
+        ));
+        assert!(watch.observe(10, 10).is_none());
+        assert!(matches!(
+            watch.observe(0, 10),
+            Some(SocketEvent::SendHwmCleared)
+        ));
+        assert!(watch.observe(0, 10).is_none());
+        // A zero limit is unlimited and never reached.
+        assert!(watch.observe(usize::MAX, 0).is_none());
     }
 
     #[test]
@@ -3,7 +3,9 @@
 //! This module provides configuration options for `ZeroMQ` sockets, similar to
 //! libzmq's socket options (`zmq_setsockopt/zmq_getsockopt`).
 
-use std::{fmt, time::Duration};
+use std::{collections::HashMap, fmt, time::Duration};
+
+use tracing::warn;
 
 /// Socket configuration options.
 ///
@@ -48,7 +50,8 @@ pub struct SocketOptions {
     ///
     /// Maximum time to wait for a receive operation.
     /// - `None`: Block indefinitely (default)
-    /// - `Some(Duration::ZERO)`: Non-blocking (return immediately with EAGAIN)
+    /// - `Some(Duration::ZERO)`: Non-blocking: return a message that is
+    ///   already buffered or has arrived, EAGAIN (`WouldBlock`) otherwise
     /// - `Some(duration)`: Wait up to duration before returning EAGAIN
     pub recv_timeout: Option<Duration>,
 
@@ -56,7 +59,12 @@ pub struct SocketOptions {
     ///
     /// Maximum time to wait for a send operation.
     /// - `None`: Block indefinitely (default)
-    /// - `Some(Duration::ZERO)`: Non-blocking (return immediately with EAGAIN)
+    /// - `Some(Duration::ZERO)`: Non-blocking: write what the kernel accepts
+    ///   right away, EAGAIN (`WouldBlock`) if it takes none of the message. A
+    ///   message that is partly accepted counts as sent; its rest is queued
+    ///   and goes out ahead of the next send. A write the runtime cannot
+    ///   finish at once is abandoned and poisons the socket, since on io_uring
+    ///   it may still complete
     /// - `Some(duration)`: Wait up to duration before returning EAGAIN
     pub send_timeout: Option<Duration>,
 
@@ -109,10 +117,19 @@ pub struct SocketOptions {
     /// - Default: 1000 messages
     pub send_hwm: usize,
 
+    /// What `send()` does when `send_hwm` messages are already held back.
+    ///
+    /// Only messages the socket keeps in memory count against the mark:
+    /// those held by `coalesce_window` or queued with `send_buffered()`.
+    /// - Default: [`HwmPolicy::Block`]
+    pub hwm_policy: HwmPolicy,
+
     /// Enable immediate connect mode (`ZMQ_IMMEDIATE`)
     ///
     /// - `false` (default): Queue messages while connecting
-    /// - `true`: Report error if no connection established
+    /// - `true`: Never queue while there is no connection. A send that finds
+    ///   the socket disconnected redials once, without waiting, and fails
+    ///   with `NotConnected` if that fails or a backoff is still pending
     pub immediate: bool,
 
     /// Maximum message size (`ZMQ_MAXMSGSIZE`)
@@ -122,6 +139,28 @@ pub struct SocketOptions {
     /// - `Some(size)`: Reject messages larger than size
     pub max_msg_size: Option<usize>,
 
+    /// Streaming threshold for large data frames.
+    ///
+    /// A data frame whose declared body exceeds this many bytes is not
+    /// buffered: `recv()` reports it with a `StreamingRequired` error and the
+    /// payload is read chunk by chunk through `recv_frame_streaming()`.
+    /// Streamed frames are not held to the decoder's implicit 64 MB cap, only
+    /// to an explicit `max_msg_size`. CURVE frames are always buffered.
+    /// - `None`: Every frame is buffered (default)
+    /// - `Some(size)`: Stream frames larger than size
+    pub stream_threshold: Option<usize>,
+
+    /// Maximum total size of a multipart message.
+    ///
+    /// The body lengths of a message's data frames are added up as their
+    /// headers arrive, and the connection is dropped as soon as the sum would
+    /// exceed this, before the offending frame is buffered. Complements
+    /// `max_msg_size`, which bounds each frame on its own. Streamed frames
+    /// (see `stream_threshold`) are never buffered and do not count.
+    /// - `None`: No limit (default)
+    /// - `Some(size)`: Drop peers whose messages grow larger than size
+    pub max_multipart_size: Option<usize>,
+
     /// Socket identity / routing ID (`ZMQ_ROUTING_ID` / `ZMQ_IDENTITY`)
     ///
     /// Identity for ROUTER addressing. If None, a random UUID is generated.
@@ -184,6 +223,9 @@ pub struct SocketOptions {
     ///
     /// - `false` (default): Queue all messages
     /// - `true`: Keep only last message (overwrite queue)
+    ///
+    /// Honored by SUB and DEALER receives: of the messages already received
+    /// when `recv()` runs, only the newest is returned, whole.
     pub conflate: bool,
 
     /// TCP keepalive (`ZMQ_TCP_KEEPALIVE`)
@@ -226,6 +268,9 @@ pub struct SocketOptions {
     /// Allow multiple outstanding requests without strict alternation.
     /// - `false` (default): Strict send-recv-send-recv pattern
     /// - `true`: Allow send-send-recv-recv pattern
+    ///
+    /// With `req_correlate` as well, up to `send_hwm` unanswered requests are
+    /// tracked; beyond that the oldest is forgotten.
     pub req_relaxed: bool,
 
     /// Multicast rate in kilobits per second (`ZMQ_RATE`)
@@ -260,6 +305,16 @@ pub struct SocketOptions {
     /// - `false` (default): a single listener per address.
     pub reuse_port: bool,
 
+    /// Bind listeners with `SO_REUSEADDR` (Unix only).
+    ///
+    /// Lets a listener rebind a port whose previous connections are still in
+    /// `TIME_WAIT`, so a restarted server (or the next test) does not fail with
+    /// `AddrInUse`. It does not let two live listeners share a port; that is
+    /// [`SocketOptions::reuse_port`]. Windows is left alone because its
+    /// `SO_REUSEADDR` lets another process steal a bound port.
+    /// - `true` (default): matches libzmq and the std listener on Unix.
+    pub so_reuseaddr: bool,
+
     /// Multicast TTL (`ZMQ_MULTICAST_HOPS`)
     ///
     /// Time-to-live for multicast packets.
@@ -343,6 +398,26 @@ pub struct SocketOptions {
     /// - Default: "" (global domain)
     pub zap_domain: String,
 
+    /// Application metadata sent in the handshake (`ZMQ_METADATA`)
+    ///
+    /// Extra properties such as `X-Region` added to the READY (or INITIATE)
+    /// command after `Socket-Type` and `Identity`. Those two names are owned
+    /// by the handshake, so entries using them (in any case) are not sent.
+    /// - Default: empty
+    pub metadata: HashMap<String, bytes::Bytes>,
+
+    /// Answer HTTP requests on a ZMTP port with `400 Bad Request`
+    ///
+    /// A connection whose first bytes are not the ZMTP signature is closed
+    /// during the greeting. With this set, one that starts with an HTTP
+    /// request line is first told, in plain text, that the port speaks ZMTP,
+    /// which helps whoever pointed `curl` at it. The socket then waits for
+    /// the peer's signature before sending its own greeting, so set it on
+    /// bound sockets only: two peers that both wait would stall until
+    /// `handshake_timeout`.
+    /// - Default: false
+    pub friendly_http_reject: bool,
+
     /// Subscriptions (`ZMQ_SUBSCRIBE`)
     ///
     /// Subscription filters for SUB/XSUB sockets.
@@ -366,6 +441,44 @@ pub struct SocketOptions {
     /// - `Some(n)`: Give up and return `NotConnected` after n attempts
     pub max_reconnect_attempts: Option<u32>,
 
+    /// Replay unflushed messages after a reconnect.
+    ///
+    /// A DEALER keeps the messages queued with `send_buffered()`/`send_batch()`
+    /// until a flush completes, and re-queues them on the new connection after
+    /// a reconnect. Some of them may already have reached the peer before the
+    /// old connection failed, so pair this with `dedupe_window` unless the
+    /// application tolerates duplicates.
+    ///
+    /// - Default: false (a failed flush loses the batch)
+    pub resend_on_reconnect: bool,
+
+    /// Reconnect a poisoned TCP socket on its next `send`/`recv`.
+    ///
+    /// A socket whose I/O was cancelled mid-operation is poisoned and
+    /// normally fails every later call with `BrokenPipe` until it is
+    /// reconnected by hand. With this set, the next `send` or `recv` first
+    /// redials the socket's endpoint and performs a fresh handshake. Sockets
+    /// made with `from_tcp` remember their peer address for this. A socket
+    /// with no stored endpoint (IPC, TLS, or a stream whose peer address is
+    /// unknown) still returns `BrokenPipe`, as does a failed reconnect.
+    ///
+    /// - Default: false
+    pub auto_recover: bool,
+
+    /// Sequence-stamp messages and drop duplicates within this window.
+    ///
+    /// When non-zero, DEALER and ROUTER sockets advertise the `X-Sequence`
+    /// property in their READY command. On a connection where both peers
+    /// advertised it, every message carries a leading sequence frame, and the
+    /// receiver silently drops any message whose sequence number it has
+    /// already seen among the last `dedupe_window` from the same sending
+    /// socket, counting it in `duplicates_dropped()`. Without negotiation no
+    /// sequence frame is sent. NULL and PLAIN only; CURVE peers never
+    /// negotiate it.
+    ///
+    /// - Default: 0 (disabled)
+    pub dedupe_window: usize,
+
     /// ZMTP heartbeat interval (`ZMQ_HEARTBEAT_IVL` = 75)
     ///
     /// How often to send PING heartbeat commands on an otherwise idle connection.
@@ -383,7 +496,8 @@ pub struct SocketOptions {
 
     /// ZMTP heartbeat timeout (`ZMQ_HEARTBEAT_TIMEOUT` = 77)
     ///
-    /// How long to wait for a PONG reply before considering the connection dead.
+    /// How long to wait after a PING for any traffic (normally the PONG)
+    /// before considering the connection dead and dropping it.
     /// - `None`: Use `heartbeat_ivl` (default)
     /// - `Some(dur)`: Custom timeout (recommended: 2-5x `heartbeat_ivl`)
     pub heartbeat_timeout: Option<Duration>,
@@ -437,6 +551,21 @@ pub struct SocketOptions {
     /// - Default: 65536 (64 KB) - one typical TCP segment on loopback
     pub write_coalesce_threshold: usize,
 
+    /// Time-bounded write coalescing, a user-space take on Nagle's algorithm.
+    ///
+    /// When set, `send()` encodes messages into the send buffer and writes
+    /// them only once this long has passed since the first of them was
+    /// buffered, or once `write_coalesce_threshold` bytes have accumulated.
+    /// Buffered messages are also written before the socket waits to receive
+    /// and by `flush()`; `close()` drains them according to `linger`.
+    ///
+    /// Elapsed time is checked when sending, so the tail of a burst waits for
+    /// the next send, receive, flush or close rather than a timer.
+    ///
+    /// - `None` (default): each `send()` writes immediately
+    /// - `Some(window)`: batch sends for up to `window`
+    pub coalesce_window: Option<Duration>,
+
     /// Frame-body size at or above which the send path switches to a vectored
     /// write (`writev`) instead of copying the body into the userspace send
     /// buffer.
@@ -456,6 +585,19 @@ pub struct SocketOptions {
     ///   copying the body into one contiguous buffer beats a two-segment
     ///   `writev`; tune for your hardware and message sizes
     pub vectored_write_threshold: usize,
+
+    /// Shrink factor for the userspace write buffers after a burst.
+    ///
+    /// The send and write buffers grow to the largest burst they have carried.
+    /// After each flush, a buffer whose capacity exceeds
+    /// `write_buffer_size × write_buffer_shrink_factor` is reallocated back to
+    /// `write_buffer_size` once the recent average flush size (an EWMA) has
+    /// dropped to a small fraction of that capacity, so a socket that once
+    /// batched a huge burst does not keep that memory forever.
+    ///
+    /// - Default: 4
+    /// - `0`: never shrink (buffers keep their high-water capacity)
+    pub write_buffer_shrink_factor: usize,
 }
 
 impl fmt::Debug for SocketOptions {
@@ -472,8 +614,11 @@ impl fmt::Debug for SocketOptions {
             .field("connect_timeout", &self.connect_timeout)
             .field("recv_hwm", &self.recv_hwm)
             .field("send_hwm", &self.send_hwm)
+            .field("hwm_policy", &self.hwm_policy)
             .field("immediate", &self.immediate)
             .field("max_msg_size", &self.max_msg_size)
+            .field("stream_threshold", &self.stream_threshold)
+            .field("max_multipart_size", &self.max_multipart_size)
             .field("routing_id", &self.routing_id)
             .field("connect_routing_id", &self.connect_routing_id)
             .field("router_mandatory", &self.router_mandatory)
@@ -495,6 +640,7 @@ impl fmt::Debug for SocketOptions {
             .field("sndbuf", &self.sndbuf)
             .field("rcvbuf", &self.rcvbuf)
             .field("reuse_port", &self.reuse_port)
+            .field("so_reuseaddr", &self.so_reuseaddr)
             .field("multicast_hops", &self.multicast_hops)
             .field("tos", &self.tos)
             .field("multicast_maxtpdu", &self.multicast_maxtpdu)
@@ -514,9 +660,14 @@ impl fmt::Debug for SocketOptions {
             )
             .field("curve_serverkey", &self.curve_serverkey)
             .field("zap_domain", &self.zap_domain)
+            .field("metadata", &self.metadata)
+            .field("friendly_http_reject", &self.friendly_http_reject)
             .field("subscriptions", &self.subscriptions)
             .field("unsubscriptions", &self.unsubscriptions)
             .field("max_reconnect_attempts", &self.max_reconnect_attempts)
+            .field("resend_on_reconnect", &self.resend_on_reconnect)
+            .field("auto_recover", &self.auto_recover)
+            .field("dedupe_window", &self.dedupe_window)
             .field("heartbeat_ivl", &self.heartbeat_ivl)
             .field("heartbeat_ttl", &self.heartbeat_ttl)
             .field("heartbeat_timeout", &self.heartbeat_timeout)
@@ -526,7 +677,12 @@ impl fmt::Debug for SocketOptions {
             .field("invert_matching", &self.invert_matching)
             .field("write_coalescing", &self.write_coalescing)
             .field("write_coalesce_threshold", &self.write_coalesce_threshold)
+            .field("coalesce_window", &self.coalesce_window)
             .field("vectored_write_threshold", &self.vectored_write_threshold)
+            .field(
+                "write_buffer_shrink_factor",
+                &self.write_buffer_shrink_factor,
+            )
             .finish()
     }
 }
@@ -543,8 +699,11 @@ impl Default for SocketOptions {
             connect_timeout: Duration::ZERO,   // Use OS default
             recv_hwm: 1000,
             send_hwm: 1000,
+            hwm_policy: HwmPolicy::Block,
             immediate: false,
-            max_msg_size: None,      // No limit
+            max_msg_size: None, // No limit
+            stream_threshold: None,
+            max_multipart_size: None,
             read_buffer_size: 8192,  // 8KB - balanced default
             write_buffer_size: 8192, // 8KB - balanced default
             routing_id: None,
@@ -568,6 +727,7 @@ impl Default for SocketOptions {
             sndbuf: 0, // OS default
             rcvbuf: 0, // OS default
             reuse_port: false,
+            so_reuseaddr: true,
             multicast_hops: 1,       // Local network only
             tos: 0,                  // Normal service
             multicast_maxtpdu: 1500, // Standard MTU
@@ -581,10 +741,15 @@ impl Default for SocketOptions {
             curve_publickey: None,
             curve_secretkey: None,
             curve_serverkey: None,
-            zap_domain: String::new(),    // Global domain
+            zap_domain: String::new(), // Global domain
+            metadata: HashMap::new(),
+            friendly_http_reject: false,
             subscriptions: Vec::new(),    // No subscriptions
             unsubscriptions: Vec::new(),  // No unsubscriptions
             max_reconnect_attempts: None, // Retry indefinitely
+            resend_on_reconnect: false,
+            auto_recover: false,
+            dedupe_window: 0,
             heartbeat_ivl: None,
             heartbeat_ttl: None,
             heartbeat_timeout: None,
@@ -594,7 +759,9 @@ impl Default for SocketOptions {
             invert_matching: false,
             write_coalescing: false,
             write_coalesce_threshold: 65536,
+            coalesce_window: None,
             vectored_write_threshold: 32768,
+            write_buffer_shrink_factor: 4,
         }
     }
 }
@@ -646,6 +813,96 @@ impl SocketOptions {
         }
     }
 
+    /// Create socket options from `ZMQ_*` environment variables.
+    ///
+    /// Starts from [`SocketOptions::default`] and overrides each field whose
+    /// variable is set:
+    ///
+    /// | Variable                    | Field                   |
+    /// |-----------------------------|-------------------------|
+    /// | `ZMQ_RECV_HWM`              | `recv_hwm`              |
+    /// | `ZMQ_SEND_HWM`              | `send_hwm`              |
+    /// | `ZMQ_RECV_TIMEOUT_MS`       | `recv_timeout`          |
+    /// | `ZMQ_SEND_TIMEOUT_MS`       | `send_timeout`          |
+    /// | `ZMQ_RECONNECT_IVL_MS`      | `reconnect_ivl`         |
+    /// | `ZMQ_RECONNECT_IVL_MAX_MS`  | `reconnect_ivl_max`     |
+    /// | `ZMQ_HANDSHAKE_TIMEOUT_MS`  | `handshake_timeout`     |
+    ///
+    /// Durations are whole milliseconds. As with `ZMQ_RCVTIMEO` and
+    /// `ZMQ_SNDTIMEO`, `-1` sets an infinite receive or send timeout. A value
+    /// that does not parse is logged with `tracing::warn!` and the default
+    /// is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monocoque_core::options::SocketOptions;
+    ///
+    /// let opts = SocketOptions::from_env().with_linger(None);
+    /// ```
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var_os(name))
+    }
+
+    /// [`Self::from_env`] over an arbitrary variable lookup.
+    fn from_vars(lookup: impl Fn(&str) -> Option<std::ffi::OsString>) -> Self {
+        let read = |name: &str| -> Option<String> {
+            let value = lookup(name)?;
+            let parsed = value.to_str().map(|v| v.trim().to_owned());
+            if parsed.is_none() {
+                warn!("{name} is not valid UTF-8, keeping the default");
+            }
+            parsed
+        };
+        let count = |name: &str| -> Option<usize> {
+            let value = read(name)?;
+            let parsed = value.parse().ok();
+            if parsed.is_none() {
+                warn!("{name}={value:?} is not a message count, keeping the default");
+            }
+            parsed
+        };
+        let parse_millis = |name: &str, value: &str| -> Option<Duration> {
+            let parsed = value.parse().ok().map(Duration::from_millis);
+            if parsed.is_none() {
+                warn!("{name}={value:?} is not a duration in milliseconds, keeping the default");
+            }
+            parsed
+        };
+        let millis = |name: &str| -> Option<Duration> { parse_millis(name, &read(name)?) };
+        let timeout = |name: &str| -> Option<Option<Duration>> {
+            match read(name)?.as_str() {
+                "-1" => Some(None),
+                value => parse_millis(name, value).map(Some),
+            }
+        };
+
+        let mut opts = Self::default();
+        if let Some(hwm) = count("ZMQ_RECV_HWM") {
+            opts.recv_hwm = hwm;
+        }
+        if let Some(hwm) = count("ZMQ_SEND_HWM") {
+            opts.send_hwm = hwm;
+        }
+        if let Some(timeout) = timeout("ZMQ_RECV_TIMEOUT_MS") {
+            opts.recv_timeout = timeout;
+        }
+        if let Some(timeout) = timeout("ZMQ_SEND_TIMEOUT_MS") {
+            opts.send_timeout = timeout;
+        }
+        if let Some(ivl) = millis("ZMQ_RECONNECT_IVL_MS") {
+            opts.reconnect_ivl = ivl;
+        }
+        if let Some(ivl) = millis("ZMQ_RECONNECT_IVL_MAX_MS") {
+            opts.reconnect_ivl_max = ivl;
+        }
+        if let Some(timeout) = millis("ZMQ_HANDSHAKE_TIMEOUT_MS") {
+            opts.handshake_timeout = timeout;
+        }
+        opts
+    }
+
     /// Set receive timeout.
     ///
     /// # Examples
@@ -672,11 +929,27 @@ impl SocketOptions {
     }
 
     /// Set handshake timeout.
+    ///
+    /// `Duration::ZERO` disables the deadline: the handshake waits for the
+    /// peer indefinitely, as with `ZMQ_HANDSHAKE_IVL = 0`.
     pub const fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
         self.handshake_timeout = timeout;
         self
     }
 
+    /// Deadline to enforce on the ZMTP handshake, or `None` when disabled.
+    ///
+    /// Maps a zero `handshake_timeout` to "no deadline" so it is never
+    /// mistaken for a non-blocking (immediate) timeout.
+    #[must_use]
+    pub const fn handshake_deadline(&self) -> Option<Duration> {
+        if self.handshake_timeout.is_zero() {
+            None
+        } else {
+            Some(self.handshake_timeout)
+        }
+    }
+
     /// Set linger timeout.
     pub const fn with_linger(mut self, linger: Option<Duration>) -> Self {
         self.linger = linger;
@@ -703,6 +976,27 @@ impl SocketOptions {
         self
     }
 
+    /// Replay unflushed messages after a reconnect; see
+    /// [`SocketOptions::resend_on_reconnect`].
+    pub const fn with_resend_on_reconnect(mut self, enabled: bool) -> Self {
+        self.resend_on_reconnect = enabled;
+        self
+    }
+
+    /// Reconnect a poisoned socket on its next operation; see
+    /// [`SocketOptions::auto_recover`].
+    pub const fn with_auto_recover(mut self, enabled: bool) -> Self {
+        self.auto_recover = enabled;
+        self
+    }
+
+    /// Sequence-stamp messages and drop duplicates among the last `window`
+    /// sequence numbers; see [`SocketOptions::dedupe_window`]. `0` disables.
+    pub const fn with_dedupe_window(mut self, window: usize) -> Self {
+        self.dedupe_window = window;
+        self
+    }
+
     /// Set connection timeout.
     pub const fn with_connect_timeout(mut self, timeout: Duration) -> Self {
         self.connect_timeout = timeout;
@@ -767,6 +1061,14 @@ impl SocketOptions {
         self
     }
 
+    /// Batch sends for up to `window` before writing them; see
+    /// [`SocketOptions::coalesce_window`]. `None` writes every send
+    /// immediately.
+    pub const fn with_coalesce_window(mut self, window: Option<Duration>) -> Self {
+        self.coalesce_window = window;
+        self
+    }
+
     /// Set the frame-body size at or above which the eager send path uses a
     /// vectored write (`writev`) instead of copying the body into the send
     /// buffer. See [`SocketOptions::vectored_write_threshold`]. Set to
@@ -776,6 +1078,14 @@ impl SocketOptions {
         self
     }
 
+    /// Set the factor above `write_buffer_size` at which an oversized write
+    /// buffer is shrunk after a burst. See
+    /// [`SocketOptions::write_buffer_shrink_factor`]. Set to `0` to disable.
+    pub const fn with_write_buffer_shrink_factor(mut self, factor: usize) -> Self {
+        self.write_buffer_shrink_factor = factor;
+        self
+    }
+
     /// Get the configured PLAIN password, if any.
     pub fn plain_password(&self) -> Option<&str> {
         self.plain_password.as_deref()
@@ -807,6 +1117,12 @@ impl SocketOptions {
         self
     }
 
+    /// Set what `send()` does once the send high water mark is reached.
+    pub const fn with_hwm_policy(mut self, policy: HwmPolicy) -> Self {
+        self.hwm_policy = policy;
+        self
+    }
+
     /// Enable or disable immediate mode.
     pub const fn with_immediate(mut self, immediate: bool) -> Self {
         self.immediate = immediate;
@@ -819,6 +1135,18 @@ impl SocketOptions {
         self
     }
 
+    /// Stream data frames larger than `size` instead of buffering them.
+    pub const fn with_stream_threshold(mut self, size: Option<usize>) -> Self {
+        self.stream_threshold = size;
+        self
+    }
+
+    /// Set the maximum total size of a multipart message.
+    pub const fn with_max_multipart_size(mut self, size: Option<usize>) -> Self {
+        self.max_multipart_size = size;
+        self
+    }
+
     /// Set read buffer size.
     ///
     /// # Examples
@@ -1033,6 +1361,13 @@ impl SocketOptions {
         self
     }
 
+    /// Bind listeners with `SO_REUSEADDR` (Unix only; on by default).
+    /// See [`SocketOptions::so_reuseaddr`].
+    pub const fn with_so_reuseaddr(mut self, enabled: bool) -> Self {
+        self.so_reuseaddr = enabled;
+        self
+    }
+
     /// Set multicast TTL/hops (`ZMQ_MULTICAST_HOPS`).
     pub const fn with_multicast_hops(mut self, hops: i32) -> Self {
         self.multicast_hops = hops;
@@ -1159,6 +1494,72 @@ impl SocketOptions {
         self
     }
 
+    /// Add a property to the metadata sent in the handshake (`ZMQ_METADATA`).
+    ///
+    /// The peer sees it alongside `Socket-Type` and `Identity`, which this
+    /// cannot override. Setting the same name again replaces the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monocoque_core::options::SocketOptions;
+    /// use bytes::Bytes;
+    ///
+    /// let opts = SocketOptions::new()
+    ///     .with_metadata_property("App-Version", Bytes::from_static(b"1.4.2"))
+    ///     .with_metadata_property("X-Region", Bytes::from_static(b"eu-west"));
+    /// ```
+    pub fn with_metadata_property(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<bytes::Bytes>,
+    ) -> Self {
+        self.metadata.insert(name.into(), value.into());
+        self
+    }
+
+    /// Add a property to the handshake metadata, checking its name first.
+    ///
+    /// Like [`with_metadata_property`](Self::with_metadata_property), but a
+    /// name that could not be sent is reported instead of being skipped at
+    /// handshake time. Properties follow `Socket-Type` and `Identity` in the
+    /// READY (or INITIATE) command, in name order.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidInput` if [`validate_metadata_property`](Self::validate_metadata_property)
+    /// rejects `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use monocoque_core::options::SocketOptions;
+    /// use bytes::Bytes;
+    ///
+    /// let opts = SocketOptions::new()
+    ///     .with_handshake_property("Service", Bytes::from_static(b"billing"))?
+    ///     .with_handshake_property("Version", Bytes::from_static(b"3"))?;
+    /// assert!(opts.clone().with_handshake_property("Identity", Bytes::new()).is_err());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_handshake_property(
+        self,
+        name: impl Into<String>,
+        value: impl Into<bytes::Bytes>,
+    ) -> std::io::Result<Self> {
+        let name = name.into();
+        Self::validate_metadata_property(&name)?;
+        Ok(self.with_metadata_property(name, value))
+    }
+
+    /// Answer HTTP probes with `400 Bad Request` before closing them.
+    ///
+    /// See [`SocketOptions::friendly_http_reject`].
+    pub const fn with_friendly_http_reject(mut self, enabled: bool) -> Self {
+        self.friendly_http_reject = enabled;
+        self
+    }
+
     /// Add a subscription filter for SUB/XSUB sockets (`ZMQ_SUBSCRIBE`).
     ///
     /// SUB sockets MUST subscribe to at least one topic to receive messages.
@@ -1265,6 +1666,33 @@ impl SocketOptions {
         Ok(())
     }
 
+    /// Validate the name of an application handshake property.
+    ///
+    /// Names are 1 to 255 bytes (the wire format's 1-byte length) and may
+    /// not be one the handshake writes itself: `Socket-Type`, `Identity` or
+    /// `X-Sequence`, compared case-insensitively.
+    pub fn validate_metadata_property(name: &str) -> std::io::Result<()> {
+        /// Properties the handshake fills in from the socket's own state.
+        const RESERVED: [&str; 3] = ["Socket-Type", "Identity", "X-Sequence"];
+
+        if name.is_empty() || name.len() > 255 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "metadata property name must be 1 to 255 bytes (got {})",
+                    name.len()
+                ),
+            ));
+        }
+        if let Some(reserved) = RESERVED.iter().find(|r| r.eq_ignore_ascii_case(name)) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("metadata property {reserved} is set by the handshake"),
+            ));
+        }
+        Ok(())
+    }
+
     /// Validate general routing ID (for DEALER, REQ, REP).
     ///
     /// Less strict than ROUTER identities - allows null prefix.
@@ -1298,10 +1726,443 @@ impl SocketOptions {
     }
 }
 
+/// Options read when a connection is established (transport setup,
+/// handshake, security): a change reaches the socket on its next reconnect.
+/// Options in neither list are consulted per operation and apply live.
+const CONNECTION_OPTIONS: &[&str] = &[
+    "read_buffer_size",
+    "handshake_timeout",
+    "connect_timeout",
+    "connect_routing_id",
+    "probe_router",
+    "tcp_keepalive",
+    "tcp_keepalive_cnt",
+    "tcp_keepalive_idle",
+    "tcp_keepalive_intvl",
+    "rate",
+    "recovery_ivl",
+    "sndbuf",
+    "rcvbuf",
+    "multicast_hops",
+    "tos",
+    "multicast_maxtpdu",
+    "plain_server",
+    "plain_username",
+    "plain_password",
+    "curve_server",
+    "curve_publickey",
+    "curve_secretkey",
+    "curve_serverkey",
+    "zap_domain",
+    "metadata",
+    "friendly_http_reject",
+];
+
+/// Options fixed when the socket is created: buffers and sequencing state
+/// sized from them, the identity peers route by, and listener settings
+/// applied at bind time. [`SocketOptions::update`] rejects changes to them.
+const CONSTRUCT_ONLY_OPTIONS: &[&str] = &[
+    "write_buffer_size",
+    "routing_id",
+    "reuse_port",
+    "so_reuseaddr",
+    "ipv6",
+    "bind_to_device",
+    "router_raw",
+    "dedupe_window",
+];
+
+/// When a changed option takes effect on a live socket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionTiming {
+    /// Read at each use: the next send, receive or timer sees the new value.
+    Live,
+    /// Read while a connection is set up: applies from the next reconnect.
+    NextReconnect,
+    /// Fixed at construction: create a new socket to change it.
+    ConstructOnly,
+}
+
+/// What `send()` does when the send high water mark is reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HwmPolicy {
+    /// Wait for the peer to drain held messages, bounded by `send_timeout`
+    /// (libzmq's behaviour for blocking sends).
+    ///
+    /// A wait that times out returns `TimedOut`. If the deadline cut off a
+    /// write in flight, the socket is also poisoned and the held messages,
+    /// already accepted by earlier sends, are discarded; see
+    /// `is_poisoned()` on the socket.
+    #[default]
+    Block,
+    /// Fail at once with `WouldBlock` and leave the message unsent.
+    Error,
+}
+
+/// A [`SocketOptions::update`] tried to change a construct-only option.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("option `{field}` is fixed when the socket is created and cannot be changed")]
+pub struct OptionUpdateError {
+    field: &'static str,
+}
+
+impl OptionUpdateError {
+    /// Name of the rejected `SocketOptions` field.
+    #[must_use]
+    pub const fn field(&self) -> &'static str {
+        self.field
+    }
+}
+
+impl From<OptionUpdateError> for std::io::Error {
+    fn from(err: OptionUpdateError) -> Self {
+        Self::new(std::io::ErrorKind::InvalidInput, err)
+    }
+}
+
+/// Generates [`OptionDiff`] and the field-wise `diff`/`merge` helpers from a
+/// single field list.
+///
+/// `merge` builds a `SocketOptions` literal from the list, so a field added to
+/// the struct but not listed here fails to compile instead of being silently
+/// skipped.
+macro_rules! socket_option_fields {
+    ($($variant:ident => $field:ident: $ty:ty,)*) => {
+        /// A single field that differs between two [`SocketOptions`].
+        ///
+        /// Produced by [`SocketOptions::diff`]. Secrets (`plain_password`,
+        /// `curve_secretkey`) are redacted in the `Debug` output.
+        #[derive(Clone, PartialEq, Eq)]
+        #[non_exhaustive]
+        pub enum OptionDiff {
+            $(
+                #[doc = concat!("`", stringify!($field), "` changed.")]
+                $variant {
+                    /// Value in the original options.
+                    old: $ty,
+                    /// Value in the updated options.
+                    new: $ty,
+                },
+            )*
+        }
+
+        impl OptionDiff {
+            /// Name of the `SocketOptions` field this diff refers to.
+            #[must_use]
+            pub const fn field(&self) -> &'static str {
+                match self {
+                    $(Self::$variant { .. } => stringify!($field),)*
+                }
+            }
+
+            /// Write the new value into `opts`.
+            pub fn apply(&self, opts: &mut SocketOptions) {
+                match self {
+                    $(Self::$variant { new, .. } => opts.$field = Clone::clone(new),)*
+                }
+            }
+        }
+
+        impl fmt::Debug for OptionDiff {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let secret = matches!(
+                    self,
+                    Self::PlainPassword { .. } | Self::CurveSecretkey { .. }
+                );
+                match self {
+                    $(
+                        Self::$variant { old, new } => {
+                            let mut s = f.debug_struct(stringify!($variant));
+                            if secret {
+                                s.field("old", &"[REDACTED]").field("new", &"[REDACTED]");
+                            } else {
+                                s.field("old", old).field("new", new);
+                            }
+                            s.finish()
+                        }
+                    )*
+                }
+            }
+        }
+
+        impl SocketOptions {
+            /// Names of every option field, in declaration order.
+            pub const FIELD_NAMES: &'static [&'static str] = &[$(stringify!($field),)*];
+
+            /// List every field whose value differs between `self` and `other`.
+            ///
+            /// `old` is taken from `self`, `new` from `other`. Use this to decide
+            /// whether a reconfiguration can be applied live or needs a
+            /// reconnect (see [`OptionDiff::requires_reconnect`]).
+            ///
+            /// # Example
+            ///
+            /// ```
+            /// use monocoque_core::options::{OptionDiff, SocketOptions};
+            ///
+            /// let current = SocketOptions::default();
+            /// let wanted = current.clone().with_recv_hwm(2000);
+            ///
+            /// assert_eq!(
+            ///     current.diff(&wanted),
+            ///     vec![OptionDiff::RecvHwm { old: 1000, new: 2000 }]
+            /// );
+            /// ```
+            #[must_use]
+            pub fn diff(&self, other: &Self) -> Vec<OptionDiff> {
+                let mut diffs = Vec::new();
+                $(
+                    if self.$field != other.$field {
+                        diffs.push(OptionDiff::$variant {
+                            old: Clone::clone(&self.$field),
+                            new: Clone::clone(&other.$field),
+                        });
+                    }
+                )*
+                diffs
+            }
+
+            /// Layer `override_` on top of `base`.
+            ///
+            /// Each field is taken from `override_` if it differs from the
+            /// default, otherwise from `base`. This is the natural shape for
+            /// applying environment-variable overrides to compiled-in settings;
+            /// note that an override cannot reset a field back to its default.
+            #[must_use]
+            pub fn merge(base: &Self, override_: &Self) -> Self {
+                let defaults = Self::default();
+                Self {
+                    $(
+                        $field: if override_.$field == defaults.$field {
+                            Clone::clone(&base.$field)
+                        } else {
+                            Clone::clone(&override_.$field)
+                        },
+                    )*
+                }
+            }
+        }
+    };
+}
+
+socket_option_fields! {
+    ReadBufferSize => read_buffer_size: usize,
+    WriteBufferSize => write_buffer_size: usize,
+    RecvTimeout => recv_timeout: Option<Duration>,
+    SendTimeout => send_timeout: Option<Duration>,
+    HandshakeTimeout => handshake_timeout: Duration,
+    Linger => linger: Option<Duration>,
+    ReconnectIvl => reconnect_ivl: Duration,
+    ReconnectIvlMax => reconnect_ivl_max: Duration,
+    ConnectTimeout => connect_timeout: Duration,
+    RecvHwm => recv_hwm: usize,
+    SendHwm => send_hwm: usize,
+    HwmPolicy => hwm_policy: HwmPolicy,
+    Immediate => immediate: bool,
+    MaxMsgSize => max_msg_size: Option<usize>,
+    StreamThreshold => stream_threshold: Option<usize>,
+    MaxMultipartSize => max_multipart_size: Option<usize>,
+    RoutingId => routing_id: Option<bytes::Bytes>,
+    ConnectRoutingId => connect_routing_id: Option<bytes::Bytes>,
+    RouterMandatory => router_mandatory: bool,
+    RouterHandover => router_handover: bool,
+    ProbeRouter => probe_router: bool,
+    XpubVerbose => xpub_verbose: bool,
+    XpubManual => xpub_manual: bool,
+    XpubWelcomeMsg => xpub_welcome_msg: Option<bytes::Bytes>,
+    XsubVerboseUnsubs => xsub_verbose_unsubs: bool,
+    Conflate => conflate: bool,
+    TcpKeepalive => tcp_keepalive: i32,
+    TcpKeepaliveCnt => tcp_keepalive_cnt: i32,
+    TcpKeepaliveIdle => tcp_keepalive_idle: i32,
+    TcpKeepaliveIntvl => tcp_keepalive_intvl: i32,
+    ReqCorrelate => req_correlate: bool,
+    ReqRelaxed => req_relaxed: bool,
+    Rate => rate: i32,
+    RecoveryIvl => recovery_ivl: Duration,
+    Sndbuf => sndbuf: i32,
+    Rcvbuf => rcvbuf: i32,
+    ReusePort => reuse_port: bool,
+    SoReuseaddr => so_reuseaddr: bool,
+    MulticastHops => multicast_hops: i32,
+    Tos => tos: i32,
+    MulticastMaxtpdu => multicast_maxtpdu: i32,
+    Ipv6 => ipv6: bool,
+    BindToDevice => bind_to_device: Option<String>,
+    PlainServer => plain_server: bool,
+    PlainUsername => plain_username: Option<String>,
+    PlainPassword => plain_password: Option<String>,
+    CurveServer => curve_server: bool,
+    CurvePublickey => curve_publickey: Option<[u8; 32]>,
+    CurveSecretkey => curve_secretkey: Option<[u8; 32]>,
+    CurveServerkey => curve_serverkey: Option<[u8; 32]>,
+    ZapDomain => zap_domain: String,
+    Metadata => metadata: HashMap<String, bytes::Bytes>,
+    FriendlyHttpReject => friendly_http_reject: bool,
+    Subscriptions => subscriptions: Vec<bytes::Bytes>,
+    Unsubscriptions => unsubscriptions: Vec<bytes::Bytes>,
+    MaxReconnectAttempts => max_reconnect_attempts: Option<u32>,
+    ResendOnReconnect => resend_on_reconnect: bool,
+    AutoRecover => auto_recover: bool,
+    DedupeWindow => dedupe_window: usize,
+    HeartbeatIvl => heartbeat_ivl: Option<Duration>,
+    HeartbeatTtl => heartbeat_ttl: Option<Duration>,
+    HeartbeatTimeout => heartbeat_timeout: Option<Duration>,
+    RouterRaw => router_raw: bool,
+    StreamNotify => stream_notify: bool,
+    XpubNodrop => xpub_nodrop: bool,
+    InvertMatching => invert_matching: bool,
+    WriteCoalescing => write_coalescing: bool,
+    WriteCoalesceThreshold => write_coalesce_threshold: usize,
+    CoalesceWindow => coalesce_window: Option<Duration>,
+    VectoredWriteThreshold => vectored_write_threshold: usize,
+    WriteBufferShrinkFactor => write_buffer_shrink_factor: usize,
+}
+
+impl OptionDiff {
+    /// Whether applying this change requires re-establishing the connection.
+    #[must_use]
+    pub fn requires_reconnect(&self) -> bool {
+        SocketOptions::is_connection_option(self.field())
+    }
+
+    /// When this change takes effect on a live socket.
+    #[must_use]
+    pub fn timing(&self) -> OptionTiming {
+        SocketOptions::timing(self.field())
+    }
+}
+
+impl SocketOptions {
+    /// Whether the named option only takes effect on a new connection.
+    ///
+    /// Connection options (buffer sizes, TCP tuning, handshake and security
+    /// settings, routing identity) are read while the connection is set up;
+    /// changing them requires a reconnect, or a new socket for the
+    /// construct-only ones. All other options are consulted per operation and
+    /// can be applied to a live socket. Unknown names return `false`.
+    #[must_use]
+    pub fn is_connection_option(field: &str) -> bool {
+        Self::timing(field) != OptionTiming::Live
+    }
+
+    /// When a change to the named option takes effect on a live socket.
+    /// Unknown names are reported as [`OptionTiming::Live`].
+    #[must_use]
+    pub fn timing(field: &str) -> OptionTiming {
+        if CONSTRUCT_ONLY_OPTIONS.contains(&field) {
+            OptionTiming::ConstructOnly
+        } else if CONNECTION_OPTIONS.contains(&field) {
+            OptionTiming::NextReconnect
+        } else {
+            OptionTiming::Live
+        }
+    }
+
+    /// Apply `f` to these options unless it changes a construct-only field.
+    ///
+    /// This is what the sockets' `update_options` runs. On success the
+    /// changed fields are returned; check [`OptionDiff::timing`] to see which
+    /// wait for a reconnect. If any change touches a
+    /// [`ConstructOnly`](OptionTiming::ConstructOnly) field, nothing is
+    /// applied and the first such field is reported.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] naming the construct-only field `f` changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use monocoque_core::options::{OptionTiming, SocketOptions};
+    /// use std::time::Duration;
+    ///
+    /// let mut opts = SocketOptions::default();
+    /// let applied = opts
+    ///     .update(|o| o.recv_timeout = Some(Duration::from_secs(1)))
+    ///     .unwrap();
+    /// assert_eq!(applied[0].timing(), OptionTiming::Live);
+    ///
+    /// let err = opts
+    ///     .update(|o| o.routing_id = Some(Bytes::from_static(b"other")))
+    ///     .unwrap_err();
+    /// assert_eq!(err.field(), "routing_id");
+    /// assert!(opts.routing_id.is_none());
+    /// ```
+    pub fn update(
+        &mut self,
+        f: impl FnOnce(&mut Self),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        let mut next = self.clone();
+        f(&mut next);
+        let diffs = self.diff(&next);
+        if let Some(diff) = diffs
+            .iter()
+            .find(|diff| diff.timing() == OptionTiming::ConstructOnly)
+        {
+            return Err(OptionUpdateError {
+                field: diff.field(),
+            });
+        }
+        *self = next;
+        Ok(diffs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn from_vars_applies_a_zmq_variable() {
+        let vars = [("ZMQ_RECV_HWM", "2000")];
+        let opts = SocketOptions::from_vars(|name| {
+            vars.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.into())
+        });
+
+        assert_eq!(opts.recv_hwm, 2000);
+        assert_eq!(opts.send_hwm, SocketOptions::default().send_hwm);
+    }
+
+    #[test]
+    fn from_vars_parses_durations_and_keeps_defaults_for_bad_values() {
+        let vars: HashMap<&str, &str> = [
+            ("ZMQ_SEND_HWM", "lots"),
+            ("ZMQ_RECV_TIMEOUT_MS", "250"),
+            ("ZMQ_SEND_TIMEOUT_MS", "-1"),
+            ("ZMQ_RECONNECT_IVL_MS", " 500 "),
+            ("ZMQ_HANDSHAKE_TIMEOUT_MS", "-5"),
+        ]
+        .into_iter()
+        .collect();
+        let opts = SocketOptions::from_vars(|name| vars.get(name).map(Into::into));
+        let defaults = SocketOptions::default();
+
+        assert_eq!(opts.send_hwm, defaults.send_hwm);
+        assert_eq!(opts.recv_timeout, Some(Duration::from_millis(250)));
+        assert_eq!(opts.send_timeout, None);
+        assert_eq!(opts.reconnect_ivl, Duration::from_millis(500));
+        assert_eq!(opts.reconnect_ivl_max, defaults.reconnect_ivl_max);
+        assert_eq!(opts.handshake_timeout, defaults.handshake_timeout);
+    }
+
+    #[test]
+    fn from_vars_looks_up_each_variable_once() {
+        let seen = std::cell::RefCell::new(Vec::new());
+        SocketOptions::from_vars(|name| {
+            seen.borrow_mut().push(name.to_owned());
+            Some("100".into())
+        });
+        let mut seen = seen.into_inner();
+        let total = seen.len();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), total, "a variable was read more than once");
+    }
+
     #[test]
     fn test_default_options() {
         let opts = SocketOptions::default();
@@ -1313,6 +2174,15 @@ mod tests {
         assert_eq!(opts.send_hwm, 1000);
     }
 
+    #[test]
+    fn test_zero_handshake_timeout_disables_deadline() {
+        let opts = SocketOptions::default();
+        assert_eq!(opts.handshake_deadline(), Some(Duration::from_secs(30)));
+
+        let opts = opts.with_handshake_timeout(Duration::ZERO);
+        assert_eq!(opts.handshake_deadline(), None);
+    }
+
     #[test]
     fn test_builder_pattern() {
         let opts = SocketOptions::new()
@@ -1470,4 +2340,236 @@ mod tests {
         assert_eq!(opts3.unsubscriptions.len(), 1);
         assert_eq!(opts3.unsubscriptions[0], bytes::Bytes::from("admin."));
     }
+
+    /// Every field set to a non-default value.
+    fn fully_customized() -> SocketOptions {
+        SocketOptions {
+            read_buffer_size: 4096,
+            write_buffer_size: 16384,
+            recv_timeout: Some(Duration::from_secs(1)),
+            send_timeout: Some(Duration::from_secs(2)),
+            handshake_timeout: Duration::from_secs(3),
+            linger: None,
+            reconnect_ivl: Duration::from_millis(50),
+            reconnect_ivl_max: Duration::from_secs(5),
+            connect_timeout: Duration::from_secs(4),
+            recv_hwm: 2000,
+            send_hwm: 3000,
+            hwm_policy: HwmPolicy::Error,
+            immediate: true,
+            max_msg_size: Some(1 << 20),
+            stream_threshold: Some(1 << 16),
+            max_multipart_size: Some(1 << 22),
+            routing_id: Some(bytes::Bytes::from_static(b"id")),
+            connect_routing_id: Some(bytes::Bytes::from_static(b"peer")),
+            router_mandatory: true,
+            router_handover: true,
+            probe_router: true,
+            xpub_verbose: true,
+            xpub_manual: true,
+            xpub_welcome_msg: Some(bytes::Bytes::from_static(b"hi")),
+            xsub_verbose_unsubs: true,
+            conflate: true,
+            tcp_keepalive: 1,
+            tcp_keepalive_cnt: 5,
+            tcp_keepalive_idle: 60,
+            tcp_keepalive_intvl: 10,
+            req_correlate: true,
+            req_relaxed: true,
+            rate: 200,
+            recovery_ivl: Duration::from_secs(20),
+            sndbuf: 65536,
+            rcvbuf: 65536,
+            reuse_port: true,
+            so_reuseaddr: false,
+            multicast_hops: 4,
+            tos: 0x10,
+            multicast_maxtpdu: 9000,
+            ipv6: true,
+            bind_to_device: Some("eth0".to_string()),
+            plain_server: true,
+            plain_username: Some("user".to_string()),
+            plain_password: Some("secret".to_string()),
+            curve_server: true,
+            curve_publickey: Some([1; 32]),
+            curve_secretkey: Some([2; 32]),
+            curve_serverkey: Some([3; 32]),
+            zap_domain: "global".to_string(),
+            metadata: HashMap::from([(
+                "X-Region".to_string(),
+                bytes::Bytes::from_static(b"eu-west"),
+            )]),
+            friendly_http_reject: true,
+            subscriptions: vec![bytes::Bytes::from_static(b"a")],
+            unsubscriptions: vec![bytes::Bytes::from_static(b"b")],
+            max_reconnect_attempts: Some(3),
+            resend_on_reconnect: true,
+            auto_recover: true,
+            dedupe_window: 64,
+            heartbeat_ivl: Some(Duration::from_secs(1)),
+            heartbeat_ttl: Some(Duration::from_secs(2)),
+            heartbeat_timeout: Some(Duration::from_secs(3)),
+            router_raw: true,
+            stream_notify: false,
+            xpub_nodrop: true,
+            invert_matching: true,
+            write_coalescing: true,
+            write_coalesce_threshold: 1024,
+            coalesce_window: Some(Duration::from_millis(1)),
+            vectored_write_threshold: 1024,
+            write_buffer_shrink_factor: 0,
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_every_field() {
+        let defaults = SocketOptions::default();
+        let custom = fully_customized();
+
+        let diffs = defaults.diff(&custom);
+        let fields: Vec<_> = diffs.iter().map(OptionDiff::field).collect();
+        assert_eq!(fields, SocketOptions::FIELD_NAMES);
+        assert!(custom.diff(&custom).is_empty());
+        assert!(diffs.contains(&OptionDiff::RecvHwm {
+            old: 1000,
+            new: 2000
+        }));
+    }
+
+    #[test]
+    fn test_diff_apply_round_trip() {
+        let custom = fully_customized();
+
+        let mut opts = SocketOptions::default();
+        for diff in SocketOptions::default().diff(&custom) {
+            diff.apply(&mut opts);
+        }
+        assert!(opts.diff(&custom).is_empty());
+
+        for diff in custom.diff(&SocketOptions::default()) {
+            diff.apply(&mut opts);
+        }
+        assert!(opts.diff(&SocketOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_merge_layers_non_default_overrides() {
+        let base = fully_customized();
+
+        // Overrides at the default keep every base value...
+        let merged = SocketOptions::merge(&base, &SocketOptions::default());
+        assert!(merged.diff(&base).is_empty());
+
+        // ...and a fully customized override wins everywhere.
+        let merged = SocketOptions::merge(&SocketOptions::default(), &base);
+        assert!(merged.diff(&base).is_empty());
+
+        // A partial override only touches the fields it sets.
+        let override_ = SocketOptions::new().with_send_hwm(42);
+        let merged = SocketOptions::merge(&base, &override_);
+        assert_eq!(
+            base.diff(&merged),
+            vec![OptionDiff::SendHwm { old: 3000, new: 42 }]
+        );
+    }
+
+    #[test]
+    fn test_connection_option_classification() {
+        assert!(SocketOptions::is_connection_option("curve_serverkey"));
+        assert!(SocketOptions::is_connection_option("tcp_keepalive"));
+        assert!(!SocketOptions::is_connection_option("recv_hwm"));
+        assert!(!SocketOptions::is_connection_option("send_timeout"));
+        assert!(!SocketOptions::is_connection_option("no_such_option"));
+
+        for name in CONNECTION_OPTIONS.iter().chain(CONSTRUCT_ONLY_OPTIONS) {
+            assert!(SocketOptions::FIELD_NAMES.contains(name), "unknown {name}");
+        }
+        for name in CONSTRUCT_ONLY_OPTIONS {
+            assert!(!CONNECTION_OPTIONS.contains(name), "{name} listed twice");
+        }
+        assert!(OptionDiff::Tos { old: 0, new: 1 }.requires_reconnect());
+        assert!(
+            !OptionDiff::Conflate {
+                old: false,
+                new: true
+            }
+            .requires_reconnect()
+        );
+    }
+
+    #[test]
+    fn test_option_diff_debug_redacts_secrets() {
+        let diff = OptionDiff::PlainPassword {
+            old: None,
+            new: Some("hunter2".to_string()),
+        };
+        let rendered = format!("{diff:?}");
+        assert!(!rendered.contains("hunter2"));
+        assert!(rendered.contains("REDACTED"));
+    }
+
+    #[test]
+    fn test_option_timing() {
+        assert_eq!(SocketOptions::timing("recv_timeout"), OptionTiming::Live);
+        assert_eq!(SocketOptions::timing("send_hwm"), OptionTiming::Live);
+        assert_eq!(SocketOptions::timing("heartbeat_ivl"), OptionTiming::Live);
+        assert_eq!(
+            SocketOptions::timing("curve_serverkey"),
+            OptionTiming::NextReconnect
+        );
+        assert_eq!(
+            SocketOptions::timing("routing_id"),
+            OptionTiming::ConstructOnly
+        );
+        assert!(SocketOptions::is_connection_option("routing_id"));
+    }
+
+    #[test]
+    fn test_update_rejects_construct_only_changes_atomically() {
+        let mut opts = SocketOptions::default();
+        let err = opts
+            .update(|o| {
+                o.send_hwm = 5;
+                o.write_buffer_size *= 2;
+            })
+            .unwrap_err();
+        assert_eq!(err.field(), "write_buffer_size");
+        assert_eq!(opts.send_hwm, 1000, "nothing is applied on rejection");
+
+        let io_err = std::io::Error::from(err);
+        assert_eq!(io_err.kind(), std::io::ErrorKind::InvalidInput);
+
+        // Setting a construct-only field to its current value is not a change.
+        let applied = opts
+            .update(|o| {
+                o.send_hwm = 5;
+                o.routing_id = None;
+                o.tos = 1;
+            })
+            .unwrap();
+        let timings: Vec<_> = applied.iter().map(OptionDiff::timing).collect();
+        assert_eq!(
+            timings,
+            vec![OptionTiming::Live, OptionTiming::NextReconnect]
+        );
+        assert_eq!(opts.send_hwm, 5);
+    }
+
+    #[test]
+    fn test_handshake_property_names_are_checked() {
+        let opts = SocketOptions::new()
+            .with_handshake_property("X-Service", bytes::Bytes::from_static(b"billing"))
+            .unwrap();
+        assert_eq!(opts.metadata["X-Service"], &b"billing"[..]);
+
+        for name in ["", "socket-type", "IDENTITY", "X-Sequence"] {
+            let err = opts
+                .clone()
+                .with_handshake_property(name, bytes::Bytes::new())
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput, "{name:?}");
+        }
+        assert!(SocketOptions::validate_metadata_property(&"n".repeat(255)).is_ok());
+        assert!(SocketOptions::validate_metadata_property(&"n".repeat(256)).is_err());
+    }
 }
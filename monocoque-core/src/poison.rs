@@ -50,6 +50,25 @@
 //! }
 //! ```
 //!
+//! When the whole critical section is one future, [`PoisonGuard::with_io`]
+//! does the arming and disarming itself, so no await point inside it can be
+//! cancelled without leaving the flag set:
+//!
+//! ```rust
+//! use monocoque_core::poison::PoisonGuard;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let mut is_poisoned = false;
+//! PoisonGuard::with_io(&mut is_poisoned, || async {
+//!     // ... every await of the multipart write ...
+//!     Ok(())
+//! })
+//! .await?;
+//! assert!(!is_poisoned);
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! # When to Use
 //!
 //! Apply this to **every** function that performs non-atomic writes:
@@ -69,6 +88,13 @@
 //! 1. **Only disarm when the entire logical operation completes**
 //! 2. **Never manually reset `is_poisoned` after an error**
 //! 3. **Once poisoned, the connection must be dropped and reconnected**
+//! 4. **Clear the flag with [`PoisonGuard::recover`] only after the stream has
+//!    been replaced by a freshly handshaken one**
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
 /// A RAII guard that marks a connection as poisoned if dropped before disarmed.
 ///
@@ -119,6 +145,67 @@ impl<'a> PoisonGuard<'a> {
         // self is dropped here, but since we updated the reference,
         // the connection is now marked as healthy
     }
+
+    /// Clear the poison flag after a successful reconnect.
+    ///
+    /// Unlike [`disarm`](Self::disarm), this does not vouch for an I/O
+    /// operation: it declares that the stream the flag guarded is gone and
+    /// its replacement has completed its handshake. Calling it on the stream
+    /// that was poisoned hides the half-written frame the flag is reporting.
+    #[inline]
+    pub const fn recover(&mut self) {
+        *self.flag = false;
+    }
+
+    /// Run the I/O future built by `f` as one critical section.
+    ///
+    /// The flag is set when the returned future is first polled and cleared
+    /// when the I/O completes with `Ok`. If the future is dropped after being
+    /// polled but before it completes (a timeout, a losing `select!` arm),
+    /// the flag stays set. An `Err` also leaves it set, as a guard that is
+    /// dropped without [`disarm`](Self::disarm) would. A future that is never
+    /// polled does no I/O and leaves the flag untouched.
+    pub fn with_io<F, Fut, R>(flag: &'a mut bool, f: F) -> impl Future<Output = io::Result<R>> + 'a
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = io::Result<R>> + 'a,
+    {
+        CancelDetector {
+            flag,
+            io: Box::pin(f()),
+            armed: false,
+        }
+    }
+}
+
+/// Future behind [`PoisonGuard::with_io`]: arms the flag on first poll and
+/// disarms it only when the inner I/O finishes successfully.
+struct CancelDetector<'a, Fut> {
+    flag: &'a mut bool,
+    io: Pin<Box<Fut>>,
+    armed: bool,
+}
+
+impl<Fut, R> Future for CancelDetector<'_, Fut>
+where
+    Fut: Future<Output = io::Result<R>>,
+{
+    type Output = io::Result<R>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if !this.armed {
+            // Set up front rather than in Drop, so a future that is leaked
+            // mid-write still leaves the connection poisoned.
+            *this.flag = true;
+            this.armed = true;
+        }
+        let result = std::task::ready!(this.io.as_mut().poll(cx));
+        if result.is_ok() {
+            *this.flag = false;
+        }
+        Poll::Ready(result)
+    }
 }
 
 #[cfg(test)]
@@ -161,6 +248,64 @@ mod tests {
         assert!(!poisoned);
     }
 
+    #[test]
+    fn test_recover_clears_poison() {
+        let mut poisoned = true;
+        {
+            let mut guard = PoisonGuard::new(&mut poisoned);
+            // The poisoned stream has been replaced and re-handshaken.
+            guard.recover();
+        }
+        assert!(!poisoned);
+    }
+
+    /// A write that sends half its frame, then waits for the peer forever.
+    async fn half_write(sink: &mut Vec<u8>) -> io::Result<()> {
+        sink.extend_from_slice(b"\x01\x08half");
+        futures::future::pending::<()>().await;
+        sink.extend_from_slice(b"-written");
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_io_poisons_when_cancelled_mid_write() {
+        use futures::future::{Either, select};
+
+        let mut poisoned = false;
+        let mut sink = Vec::new();
+        futures::executor::block_on(async {
+            let write = PoisonGuard::with_io(&mut poisoned, || half_write(&mut sink));
+            // The timeout arm wins, dropping the write after its first poll.
+            let timeout = futures::future::ready(());
+            assert!(matches!(select(write, timeout).await, Either::Right(_)));
+        });
+        assert_eq!(sink, b"\x01\x08half");
+        assert!(poisoned, "cancelled mid-write must poison");
+    }
+
+    #[test]
+    fn test_with_io_outcomes() {
+        futures::executor::block_on(async {
+            let mut poisoned = false;
+            let n = PoisonGuard::with_io(&mut poisoned, || async { Ok(7) })
+                .await
+                .unwrap();
+            assert_eq!(n, 7);
+            assert!(!poisoned, "completed I/O must not poison");
+
+            let failed = PoisonGuard::with_io(&mut poisoned, || async {
+                Err::<(), _>(io::Error::from(io::ErrorKind::BrokenPipe))
+            })
+            .await;
+            assert!(failed.is_err());
+            assert!(poisoned, "failed I/O leaves the flag set");
+
+            let mut poisoned = false;
+            drop(PoisonGuard::with_io(&mut poisoned, || async { Ok(()) }));
+            assert!(!poisoned, "a future never polled does no I/O");
+        });
+    }
+
     #[test]
     fn test_early_drop() {
         let mut poisoned = false;
@@ -0,0 +1,158 @@
+//! Last-value cache for the PUB/SUB hub.
+//!
+//! Keeps the most recent message published on each topic so a subscriber
+//! that joins late is sent the current value straight away instead of
+//! waiting for the next update.
+//!
+//! Design:
+//! - Entries are keyed by the full topic frame and hold the published
+//!   message as the same `Arc` the fan-out shares, so caching costs no copy.
+//! - Recency is a monotonic tick per entry plus a `BTreeMap` from tick to
+//!   topic; the smallest tick is the least recently published topic and is
+//!   evicted once `max_entries` is exceeded. Upsert and evict are O(log n).
+//! - A new subscription scans the entries for topics starting with its
+//!   prefix. Subscriptions are rare next to publishes, so the scan stays off
+//!   the hot path.
+
+use bytes::Bytes;
+use hashbrown::HashMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Bounded map of topic -> last published message, evicting the least
+/// recently published topic when full.
+#[derive(Debug)]
+pub struct LastValueCache {
+    max_entries: usize,
+    /// Topic -> (tick of its last publish, message)
+    entries: HashMap<Bytes, (u64, Arc<Vec<Bytes>>)>,
+    /// Tick -> topic, oldest first
+    order: BTreeMap<u64, Bytes>,
+    next_tick: u64,
+}
+
+impl LastValueCache {
+    /// Cache at most `max_entries` topics. `0` caches nothing.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            next_tick: 0,
+        }
+    }
+
+    /// Maximum number of topics kept.
+    #[must_use]
+    pub const fn max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Number of topics currently cached.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record `msg` as the latest value of its topic (frame 0).
+    ///
+    /// Empty messages are ignored.
+    pub fn insert(&mut self, msg: Arc<Vec<Bytes>>) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let Some(topic) = msg.first().cloned() else {
+            return;
+        };
+
+        let tick = self.next_tick;
+        self.next_tick += 1;
+        if let Some((old_tick, _)) = self.entries.insert(topic.clone(), (tick, msg)) {
+            self.order.remove(&old_tick);
+        }
+        self.order.insert(tick, topic);
+
+        while self.entries.len() > self.max_entries {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// The last value published on exactly `topic`.
+    #[must_use]
+    pub fn get(&self, topic: &[u8]) -> Option<&Arc<Vec<Bytes>>> {
+        self.entries.get(topic).map(|(_, msg)| msg)
+    }
+
+    /// Cached messages whose topic starts with `prefix`, oldest first.
+    pub fn matching<'a>(&'a self, prefix: &'a [u8]) -> impl Iterator<Item = &'a Arc<Vec<Bytes>>> {
+        self.order
+            .values()
+            .filter(move |topic| topic.starts_with(prefix))
+            .filter_map(|topic| self.get(topic))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(topic: &str, body: &str) -> Arc<Vec<Bytes>> {
+        Arc::new(vec![
+            Bytes::copy_from_slice(topic.as_bytes()),
+            Bytes::copy_from_slice(body.as_bytes()),
+        ])
+    }
+
+    #[test]
+    fn keeps_latest_value_per_topic() {
+        let mut cache = LastValueCache::new(8);
+        cache.insert(msg("price", "1"));
+        cache.insert(msg("price", "2"));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(b"price").unwrap()[1], Bytes::from_static(b"2"));
+    }
+
+    #[test]
+    fn evicts_least_recently_published_topic() {
+        let mut cache = LastValueCache::new(2);
+        cache.insert(msg("a", "1"));
+        cache.insert(msg("b", "1"));
+        // Refreshing `a` makes `b` the oldest.
+        cache.insert(msg("a", "2"));
+        cache.insert(msg("c", "1"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(b"b").is_none());
+        assert!(cache.get(b"a").is_some());
+        assert!(cache.get(b"c").is_some());
+    }
+
+    #[test]
+    fn matching_filters_by_prefix_in_publish_order() {
+        let mut cache = LastValueCache::new(8);
+        cache.insert(msg("weather.paris", "rain"));
+        cache.insert(msg("stocks.aapl", "100"));
+        cache.insert(msg("weather.london", "sun"));
+
+        let topics: Vec<_> = cache.matching(b"weather.").map(|m| m[0].clone()).collect();
+        assert_eq!(topics, vec!["weather.paris", "weather.london"]);
+        assert_eq!(cache.matching(b"").count(), 3);
+    }
+
+    #[test]
+    fn zero_capacity_caches_nothing() {
+        let mut cache = LastValueCache::new(0);
+        cache.insert(msg("a", "1"));
+        assert!(cache.is_empty());
+    }
+}
@@ -5,12 +5,18 @@
 //! - Track active peers with an Epoch to avoid ghost-peer races.
 //! - Apply SUB / UNSUB commands to the `SubscriptionIndex`.
 //! - Fan out published messages to matching peers (zero-copy via Bytes).
+//! - Optionally keep the last message per topic and replay it to new
+//!   subscribers (see [`PubSubHub::with_last_value_cache`]).
 //!
 //! Concurrency model:
 //! - Single-threaded async task.
 //! - Uses `flume::select`! for runtime-agnostic multiplexing.
+//! - Peer events (SUB / UNSUB included) are served ahead of queued publishes,
+//!   at most `DEFAULT_CONTROL_BURST` in a row while publishes wait.
 //! - No locks on the hot publish path.
 
+use crate::lanes::{DEFAULT_CONTROL_BURST, PriorityLanes};
+use crate::pubsub::cache::LastValueCache;
 use crate::pubsub::index::{PeerKey, SubscriptionIndex};
 use crate::router::PeerCmd;
 
@@ -34,7 +40,8 @@ type RidMap<V> = StdHashMap<Bytes, V, RandomState>;
 pub enum PubSubCmd {
     /// Publish a message (frame 0 is topic)
     Publish(Vec<Bytes>),
-    /// Close all peers
+    /// Close all peers. Sent on the hub's control lane (see
+    /// [`PubSubHub::with_control`]) it overtakes queued publishes.
     Close,
 }
 
@@ -90,6 +97,12 @@ pub struct PubSubHub {
 
     /// Messages from user (publish path)
     user_tx_rx: Receiver<PubSubCmd>,
+
+    /// Commands from user that overtake queued publishes
+    user_control_rx: Option<Receiver<PubSubCmd>>,
+
+    /// Last message per topic, replayed on subscribe
+    last_values: Option<LastValueCache>,
 }
 
 impl PubSubHub {
@@ -103,27 +116,65 @@ impl PubSubHub {
             next_key: 1, // reserve 0
             hub_rx,
             user_tx_rx,
+            user_control_rx: None,
+            last_values: None,
         }
     }
 
+    /// Take commands from `control` ahead of queued publishes, so a `Close`
+    /// takes effect without waiting for the backlog.
+    #[must_use]
+    pub fn with_control(mut self, control: Receiver<PubSubCmd>) -> Self {
+        self.user_control_rx = Some(control);
+        self
+    }
+
+    /// Remember the last message published on up to `max_entries` topics
+    /// and send the matching ones to a peer as soon as it subscribes.
+    ///
+    /// Once full, the least recently published topic is dropped.
+    #[must_use]
+    pub fn with_last_value_cache(mut self, max_entries: usize) -> Self {
+        self.last_values = Some(LastValueCache::new(max_entries));
+        self
+    }
+
     /// Main event loop.
     pub async fn run(mut self) {
         use futures::FutureExt;
-        use futures::select;
-
+        use futures::select_biased;
+
+        let mut user = match self.user_control_rx.take() {
+            Some(control) => PriorityLanes::new(control, self.user_tx_rx.clone()),
+            None => PriorityLanes::data_only(self.user_tx_rx.clone()),
+        };
+        // Peer events served since the last user command.
+        let mut events = 0usize;
         loop {
-            // Use futures::select! for runtime-agnostic multiplexing
-            select! {
+            if events >= DEFAULT_CONTROL_BURST
+                && let Some(cmd) = user.try_recv()
+            {
+                events = 0;
+                self.on_user_cmd(cmd);
+                continue;
+            }
+            select_biased! {
                 msg = self.hub_rx.recv_async().fuse() => {
                     match msg {
-                        Ok(ev) => self.on_hub_event(ev),
+                        Ok(ev) => {
+                            events += 1;
+                            self.on_hub_event(ev);
+                        }
                         Err(_) => break, // shutdown
                     }
                 }
-                msg = self.user_tx_rx.recv_async().fuse() => {
+                msg = user.recv().fuse() => {
                     match msg {
-                        Ok(cmd) => self.on_user_cmd(cmd),
-                        Err(_) => break, // shutdown
+                        Some(cmd) => {
+                            events = 0;
+                            self.on_user_cmd(cmd);
+                        }
+                        None => break, // shutdown
                     }
                 }
             }
@@ -166,8 +217,13 @@ impl PubSubHub {
 
             PubSubEvent::Subscribe { routing_id, prefix } => {
                 if let Some(&key) = self.rid_to_key.get(&routing_id)
-                    && self.peers.contains_key(&key)
+                    && let Some((_, tx)) = self.peers.get(&key)
                 {
+                    if let Some(cache) = &self.last_values {
+                        for msg in cache.matching(&prefix) {
+                            let _ = tx.send(PeerCmd::SendBody(Arc::clone(msg)));
+                        }
+                    }
                     self.index.subscribe(key, prefix);
                 }
             }
@@ -197,21 +253,22 @@ impl PubSubHub {
     /// ZMQ convention:
     /// - Frame 0 is the topic
     fn publish(&mut self, parts: Vec<Bytes>) {
-        if parts.is_empty() || self.index.is_empty() {
+        if parts.is_empty() {
             return;
         }
-
-        let topic = &parts[0];
-        let keys = self.index.match_topic(topic);
-
-        if keys.is_empty() {
+        if self.index.is_empty() && self.last_values.is_none() {
             return;
         }
 
+        let keys = self.index.match_topic(&parts[0]);
+
         // Zero-copy fan-out: share one allocation across all matching peers via
         // Arc instead of cloning a fresh Vec<Bytes> per peer. Each peer gets an
         // Arc refcount bump; the frames themselves are never re-copied.
         let msg = Arc::new(parts);
+        if let Some(cache) = &mut self.last_values {
+            cache.insert(Arc::clone(&msg));
+        }
         for key in keys {
             if let Some((_, tx)) = self.peers.get(&key) {
                 let _ = tx.send(PeerCmd::SendBody(Arc::clone(&msg)));
@@ -339,6 +396,95 @@ mod tests {
         });
     }
 
+    #[test]
+    fn subscription_is_applied_ahead_of_queued_publishes() {
+        crate::rt::LocalRuntime::new().unwrap().block_on(async {
+            let (hub_tx, hub_rx) = flume::unbounded::<PubSubEvent>();
+            let (user_tx, user_rx) = flume::unbounded::<PubSubCmd>();
+
+            // The publishes are queued before the subscriber shows up, but
+            // the hub serves peer events first.
+            for i in 0..100 {
+                user_tx
+                    .send(PubSubCmd::Publish(vec![b("t"), b(&i.to_string())]))
+                    .unwrap();
+            }
+            let (peer_tx, peer_rx) = flume::unbounded::<PeerCmd>();
+            hub_tx
+                .send(PubSubEvent::PeerUp {
+                    routing_id: b("sub1"),
+                    epoch: 1,
+                    tx: peer_tx,
+                })
+                .unwrap();
+            hub_tx
+                .send(PubSubEvent::Subscribe {
+                    routing_id: b("sub1"),
+                    prefix: b("t"),
+                })
+                .unwrap();
+            let handle = crate::rt::spawn(PubSubHub::new(hub_rx, user_rx).run());
+
+            for i in 0..100 {
+                let got = recv_arc(&peer_rx).await.expect("queued publish delivered");
+                assert_eq!(*got, vec![b("t"), b(&i.to_string())]);
+            }
+
+            drop(hub_tx);
+            drop(user_tx);
+            crate::rt::join(handle).await;
+        });
+    }
+
+    #[test]
+    fn late_subscriber_first_receives_last_cached_value() {
+        crate::rt::LocalRuntime::new().unwrap().block_on(async {
+            let (hub_tx, hub_rx) = flume::unbounded::<PubSubEvent>();
+            let (user_tx, user_rx) = flume::unbounded::<PubSubCmd>();
+            let hub = PubSubHub::new(hub_rx, user_rx).with_last_value_cache(16);
+            let handle = crate::rt::spawn(hub.run());
+
+            for i in 1..=5 {
+                user_tx
+                    .send(PubSubCmd::Publish(vec![b("price"), b(&i.to_string())]))
+                    .unwrap();
+            }
+            crate::rt::sleep(Duration::from_millis(30)).await;
+
+            let (peer_tx, peer_rx) = flume::unbounded::<PeerCmd>();
+            hub_tx
+                .send(PubSubEvent::PeerUp {
+                    routing_id: b("late"),
+                    epoch: 1,
+                    tx: peer_tx,
+                })
+                .unwrap();
+            hub_tx
+                .send(PubSubEvent::Subscribe {
+                    routing_id: b("late"),
+                    prefix: b("price"),
+                })
+                .unwrap();
+
+            let got = recv_arc(&peer_rx).await.expect("cached value delivered");
+            assert_eq!(*got, vec![b("price"), b("5")]);
+            assert!(
+                expect_no_body(&peer_rx).await,
+                "only the latest value is replayed"
+            );
+
+            user_tx
+                .send(PubSubCmd::Publish(vec![b("price"), b("6")]))
+                .unwrap();
+            let got = recv_arc(&peer_rx).await.expect("live update delivered");
+            assert_eq!(*got, vec![b("price"), b("6")]);
+
+            drop(hub_tx);
+            drop(user_tx);
+            crate::rt::join(handle).await;
+        });
+    }
+
     #[test]
     fn peer_down_with_stale_epoch_is_ignored() {
         crate::rt::LocalRuntime::new().unwrap().block_on(async {
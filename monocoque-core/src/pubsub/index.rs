@@ -1,12 +1,25 @@
-//! PUB/SUB Subscription Index (Phase 3: Sorted Prefix Table)
+//! PUB/SUB Subscription Index (byte trie)
 //!
 //! Design:
-//! - Keep subscriptions in a Vec sorted lexicographically by `prefix`.
-//! - subscribe/unsubscribe: O(log N) search + O(N) insert/remove shift (N ~ < 10k typical).
-//! - `match_topic` hot-path: cache-friendly forward scan with early-exit when prefix > topic.
-//! - Returns `SmallVec` of `PeerKeys` to avoid heap alloc in common cases.
-//! - Dedups results because peers may subscribe to overlapping prefixes.
+//! - One trie node per prefix byte, stored in a flat arena (`Vec<Node>`) and
+//!   addressed by `u32` so the hot structure stays cache-dense.
+//! - Each node holds the peers subscribed to exactly the prefix spelled by
+//!   the path from the root; the root holds empty-prefix ("all") subscribers.
+//! - `match_peers` hot-path: walk the topic once from the root, yielding the
+//!   peers of every node on the path. O(topic_len + matches), independent of
+//!   the number of subscriptions.
+//! - subscribe/unsubscribe: O(prefix_len); nodes left with neither peers nor
+//!   children are pruned and recycled through a free list.
+//! - Subscriptions are reference-counted per (peer, prefix) as in libzmq: a
+//!   peer that subscribes to a prefix N times stays subscribed until it has
+//!   unsubscribed N times. Counts sit beside the peer lists so matching still
+//!   walks plain `PeerKey` slices.
+//! - `match_topic` returns a deduplicated `SmallVec` of `PeerKeys` to avoid
+//!   heap alloc in common cases (peers may subscribe to overlapping prefixes).
+//! - Wildcard patterns cannot be walked as a trie path, so they sit beside it
+//!   in a short list of `WildcardEntry`s, each tested against the topic.
 
+use crate::subscription::WildcardPattern;
 use bytes::Bytes;
 use smallvec::SmallVec;
 
@@ -14,109 +27,324 @@ use smallvec::SmallVec;
 /// (Avoids storing Bytes/Senders directly in the hot structure.)
 pub type PeerKey = u64;
 
-#[derive(Debug, Clone)]
-struct Subscription {
-    prefix: Bytes,
+/// Arena index of the root node (the empty prefix).
+const ROOT: u32 = 0;
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Outgoing edges, sorted by byte for binary search.
+    children: SmallVec<[(u8, u32); 2]>,
     /// Inline up to 4 peers without heap allocation (common low fanout).
     peers: SmallVec<[PeerKey; 4]>,
+    /// Subscription count of each peer in `peers`, at the same position.
+    counts: SmallVec<[u32; 4]>,
+}
+
+impl Node {
+    fn child(&self, byte: u8) -> Option<u32> {
+        self.children
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .ok()
+            .map(|i| self.children[i].1)
+    }
+
+    fn is_dead(&self) -> bool {
+        self.peers.is_empty() && self.children.is_empty()
+    }
+}
+
+/// Count one more subscription of `peer`; returns whether it is the peer's
+/// first.
+fn add_ref(
+    peers: &mut SmallVec<[PeerKey; 4]>,
+    counts: &mut SmallVec<[u32; 4]>,
+    peer: PeerKey,
+) -> bool {
+    if let Some(pos) = peers.iter().position(|p| *p == peer) {
+        counts[pos] = counts[pos].saturating_add(1);
+        false
+    } else {
+        peers.push(peer);
+        counts.push(1);
+        true
+    }
+}
+
+/// Drop one subscription of `peer`; returns whether that was its last.
+fn release_ref(
+    peers: &mut SmallVec<[PeerKey; 4]>,
+    counts: &mut SmallVec<[u32; 4]>,
+    peer: PeerKey,
+) -> bool {
+    let Some(pos) = peers.iter().position(|p| *p == peer) else {
+        return false;
+    };
+    counts[pos] -= 1;
+    if counts[pos] > 0 {
+        return false;
+    }
+    peers.swap_remove(pos);
+    counts.swap_remove(pos);
+    true
+}
+
+/// Drop every subscription of `peer`; returns whether it had any.
+fn remove_all(
+    peers: &mut SmallVec<[PeerKey; 4]>,
+    counts: &mut SmallVec<[u32; 4]>,
+    peer: PeerKey,
+) -> bool {
+    let Some(pos) = peers.iter().position(|p| *p == peer) else {
+        return false;
+    };
+    peers.swap_remove(pos);
+    counts.swap_remove(pos);
+    true
+}
+
+/// Peers subscribed to one wildcard pattern.
+#[derive(Debug, Clone)]
+pub struct WildcardEntry {
+    pattern: WildcardPattern,
+    peers: SmallVec<[PeerKey; 4]>,
+    /// Subscription count of each peer in `peers`, at the same position.
+    counts: SmallVec<[u32; 4]>,
+}
+
+impl WildcardEntry {
+    /// The subscribed pattern.
+    #[must_use]
+    pub const fn pattern(&self) -> &WildcardPattern {
+        &self.pattern
+    }
+
+    /// Peers subscribed to the pattern.
+    #[must_use]
+    pub fn peers(&self) -> &[PeerKey] {
+        &self.peers
+    }
 }
 
 #[derive(Debug, Default)]
 pub struct SubscriptionIndex {
-    subs: Vec<Subscription>,
+    /// Node arena; `nodes[ROOT]` exists whenever any prefix is subscribed.
+    nodes: Vec<Node>,
+    /// Arena slots released by pruning, reused before growing `nodes`.
+    free: Vec<u32>,
+    /// Number of distinct prefixes with at least one peer.
+    prefixes: usize,
+    /// Wildcard subscriptions; entries are dropped once they have no peers.
+    wildcards: Vec<WildcardEntry>,
 }
 
 impl SubscriptionIndex {
     #[must_use]
     pub const fn new() -> Self {
-        Self { subs: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            prefixes: 0,
+            wildcards: Vec::new(),
+        }
     }
 
     #[inline]
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.subs.is_empty()
+    pub const fn is_empty(&self) -> bool {
+        self.prefixes == 0 && self.wildcards.is_empty()
+    }
+
+    /// Wildcard subscriptions currently held.
+    #[must_use]
+    pub fn wildcards(&self) -> &[WildcardEntry] {
+        &self.wildcards
+    }
+
+    /// Adds a subscription for `peer` to the wildcard `pattern`.
+    ///
+    /// Repeated subscriptions are counted, like prefix subscriptions.
+    ///
+    /// Complexity: O(patterns); wildcard subscriptions are expected to be few.
+    pub fn subscribe_pattern(&mut self, peer: PeerKey, pattern: WildcardPattern) {
+        match self.wildcards.iter_mut().find(|e| e.pattern == pattern) {
+            Some(entry) => {
+                add_ref(&mut entry.peers, &mut entry.counts, peer);
+            }
+            None => self.wildcards.push(WildcardEntry {
+                pattern,
+                peers: smallvec::smallvec![peer],
+                counts: smallvec::smallvec![1],
+            }),
+        }
+    }
+
+    /// Removes one subscription for `peer` from the wildcard `pattern`.
+    pub fn unsubscribe_pattern(&mut self, peer: PeerKey, pattern: &WildcardPattern) {
+        let Some(i) = self.wildcards.iter().position(|e| &e.pattern == pattern) else {
+            return;
+        };
+        let entry = &mut self.wildcards[i];
+        if release_ref(&mut entry.peers, &mut entry.counts, peer) && entry.peers.is_empty() {
+            self.wildcards.swap_remove(i);
+        }
     }
 
     /// Adds a subscription for `peer` to `prefix`.
     ///
-    /// Complexity:
-    /// - O(log N) to find
-    /// - O(N) for insertion shift in the vec (acceptable for typical ZMQ sizes)
+    /// Subscribing again to the same prefix raises the peer's count for it;
+    /// it takes as many [`unsubscribe`](Self::unsubscribe) calls to remove.
+    ///
+    /// Complexity: O(prefix_len), plus a binary search over each node's edges.
     pub fn subscribe(&mut self, peer: PeerKey, prefix: Bytes) {
-        match self.subs.binary_search_by(|s| s.prefix.cmp(&prefix)) {
-            Ok(idx) => {
-                let peers = &mut self.subs[idx].peers;
-                if !peers.contains(&peer) {
-                    peers.push(peer);
-                }
-            }
-            Err(idx) => {
-                let mut peers = SmallVec::<[PeerKey; 4]>::new();
-                peers.push(peer);
-                self.subs.insert(idx, Subscription { prefix, peers });
-            }
+        if self.nodes.is_empty() {
+            self.nodes.push(Node::default());
+        }
+
+        let mut node = ROOT;
+        for &byte in &prefix {
+            node = if let Some(next) = self.nodes[node as usize].child(byte) {
+                next
+            } else {
+                let next = self.alloc();
+                let children = &mut self.nodes[node as usize].children;
+                let pos = children.partition_point(|&(b, _)| b < byte);
+                children.insert(pos, (byte, next));
+                next
+            };
+        }
+
+        let Node { peers, counts, .. } = &mut self.nodes[node as usize];
+        let was_empty = peers.is_empty();
+        if add_ref(peers, counts, peer) && was_empty {
+            self.prefixes += 1;
         }
     }
 
-    /// Removes a subscription for `peer` from `prefix`.
+    /// Removes one subscription for `peer` from `prefix`; the peer stays
+    /// subscribed while it has subscribed more times than it unsubscribed.
     pub fn unsubscribe(&mut self, peer: PeerKey, prefix: &Bytes) {
-        if let Ok(idx) = self.subs.binary_search_by(|s| s.prefix.cmp(prefix)) {
-            let peers = &mut self.subs[idx].peers;
-            if let Some(pos) = peers.iter().position(|p| *p == peer) {
-                peers.swap_remove(pos);
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        // Remember the path so emptied nodes can be pruned bottom-up.
+        let mut path: SmallVec<[(u32, u8); 32]> = SmallVec::new();
+        let mut node = ROOT;
+        for &byte in prefix {
+            let Some(next) = self.nodes[node as usize].child(byte) else {
+                return;
+            };
+            path.push((node, byte));
+            node = next;
+        }
+
+        let Node { peers, counts, .. } = &mut self.nodes[node as usize];
+        if !release_ref(peers, counts, peer) || !peers.is_empty() {
+            return;
+        }
+        self.prefixes -= 1;
+
+        if self.prefixes == 0 {
+            self.clear();
+            return;
+        }
+        while let Some((parent, byte)) = path.pop() {
+            if !self.nodes[node as usize].is_dead() {
+                break;
             }
-            if peers.is_empty() {
-                self.subs.remove(idx);
+            let children = &mut self.nodes[parent as usize].children;
+            if let Ok(i) = children.binary_search_by_key(&byte, |&(b, _)| b) {
+                children.remove(i);
             }
+            self.free.push(node);
+            node = parent;
         }
     }
 
-    /// Remove `peer` from every prefix (used on disconnect).
+    /// Remove `peer` from every prefix whatever its subscription counts (used
+    /// on disconnect).
     ///
-    /// Complexity: O(N) scan, acceptable on churn events.
+    /// Complexity: O(nodes) walk, acceptable on churn events.
     pub fn remove_peer_everywhere(&mut self, peer: PeerKey) {
-        let mut i = 0usize;
-        while i < self.subs.len() {
-            let peers = &mut self.subs[i].peers;
+        self.wildcards.retain_mut(|entry| {
+            remove_all(&mut entry.peers, &mut entry.counts, peer);
+            !entry.peers.is_empty()
+        });
+        if self.nodes.is_empty() {
+            return;
+        }
 
-            if let Some(pos) = peers.iter().position(|p| *p == peer) {
-                peers.swap_remove(pos);
+        // Pre-order walk; visiting it in reverse sees children before parents.
+        let mut order = Vec::with_capacity(self.nodes.len() - self.free.len());
+        let mut stack = vec![ROOT];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            let n = &mut self.nodes[node as usize];
+            if remove_all(&mut n.peers, &mut n.counts, peer) && n.peers.is_empty() {
+                self.prefixes -= 1;
             }
+            stack.extend(n.children.iter().map(|&(_, c)| c));
+        }
 
-            if peers.is_empty() {
-                self.subs.remove(i);
-            } else {
-                i += 1;
-            }
+        if self.prefixes == 0 {
+            self.clear();
+            return;
+        }
+        let (nodes, free) = (&mut self.nodes, &mut self.free);
+        for &node in order.iter().rev() {
+            let mut children = std::mem::take(&mut nodes[node as usize].children);
+            children.retain(|&mut (_, child)| {
+                let dead = nodes[child as usize].is_dead();
+                if dead {
+                    free.push(child);
+                }
+                !dead
+            });
+            nodes[node as usize].children = children;
         }
     }
 
+    /// Iterate over every peer whose subscribed prefix is a prefix of `topic`,
+    /// then every peer whose wildcard pattern matches `topic`.
+    ///
+    /// Walks the trie along `topic`, so the prefix part costs
+    /// O(topic_len + matches) regardless of how many subscriptions exist;
+    /// each wildcard pattern adds one NFA run. A peer subscribed to nested
+    /// prefixes (e.g. `A` and `AB`) is yielded once per matching
+    /// subscription; use [`Self::match_topic`] for a deduplicated set.
+    pub fn match_peers<'a>(&'a self, topic: &'a [u8]) -> impl Iterator<Item = PeerKey> + 'a {
+        let root = (!self.nodes.is_empty()).then_some(ROOT);
+        root.into_iter()
+            .chain(topic.iter().scan(ROOT, move |node, &byte| {
+                *node = self.nodes.get(*node as usize)?.child(byte)?;
+                Some(*node)
+            }))
+            .flat_map(move |node| self.nodes[node as usize].peers.iter().copied())
+            .chain(
+                self.wildcards
+                    .iter()
+                    .filter(move |entry| entry.pattern.matches(topic))
+                    .flat_map(|entry| entry.peers.iter().copied()),
+            )
+    }
+
+    /// Whether any subscription matches `topic`, a message's first frame.
+    ///
+    /// Same semantics as the SUB side (see
+    /// [`crate::subscription`](crate::subscription#matching-semantics)):
+    /// prefixes match when `topic` starts with them, the empty prefix matches
+    /// everything, and wildcard patterns must cover the whole topic.
+    #[must_use]
+    pub fn matches(&self, topic: &[u8]) -> bool {
+        self.match_peers(topic).next().is_some()
+    }
+
     /// Match a topic against all subscriptions.
     ///
     /// Returns a deduplicated list of `PeerKeys`.
-    ///
-    /// Hot path characteristics:
-    /// - Forward scan over sorted prefixes.
-    /// - Early exit when prefix > topic lexicographically (cannot be a prefix).
-    /// - Starts-with check for actual prefix match.
     #[must_use]
     pub fn match_topic(&self, topic: &[u8]) -> SmallVec<[PeerKey; 16]> {
-        let mut out: SmallVec<[PeerKey; 16]> = SmallVec::new();
-
-        for sub in &self.subs {
-            let p = sub.prefix.as_ref();
-
-            // If prefix > topic, it cannot be a prefix of topic.
-            // e.g. prefix="apply" > topic="apple" => stop.
-            if p > topic {
-                break;
-            }
-
-            if topic.starts_with(p) {
-                out.extend_from_slice(&sub.peers);
-            }
-        }
+        let mut out: SmallVec<[PeerKey; 16]> = self.match_peers(topic).collect();
 
         // Dedup if needed (peer might have subscribed to nested prefixes).
         if out.len() > 1 {
@@ -126,6 +354,22 @@ impl SubscriptionIndex {
 
         out
     }
+
+    /// Take a node slot from the free list, or grow the arena.
+    fn alloc(&mut self) -> u32 {
+        if let Some(slot) = self.free.pop() {
+            return slot;
+        }
+        let slot = u32::try_from(self.nodes.len()).expect("subscription trie exceeds u32 nodes");
+        self.nodes.push(Node::default());
+        slot
+    }
+
+    /// Drop every node once the last subscription is gone.
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+    }
 }
 
 #[cfg(test)]
@@ -158,6 +402,34 @@ mod tests {
         assert_eq!(m.as_slice(), &[7]);
     }
 
+    #[test]
+    fn duplicate_subscriptions_need_as_many_unsubscribes() {
+        let mut idx = SubscriptionIndex::new();
+        let a = Bytes::from_static(b"A");
+
+        idx.subscribe(1, a.clone());
+        idx.subscribe(1, a.clone());
+        idx.subscribe(2, a.clone());
+
+        idx.unsubscribe(1, &a);
+        assert_eq!(idx.match_topic(b"A").as_slice(), &[1, 2]);
+        idx.unsubscribe(1, &a);
+        assert_eq!(idx.match_topic(b"A").as_slice(), &[2]);
+
+        // Disconnect drops every count at once.
+        idx.subscribe(2, a.clone());
+        idx.remove_peer_everywhere(2);
+        assert!(idx.is_empty());
+
+        let pattern = WildcardPattern::new("A*");
+        idx.subscribe_pattern(3, pattern.clone());
+        idx.subscribe_pattern(3, pattern.clone());
+        idx.unsubscribe_pattern(3, &pattern);
+        assert_eq!(idx.match_topic(b"AB").as_slice(), &[3]);
+        idx.unsubscribe_pattern(3, &pattern);
+        assert!(idx.is_empty());
+    }
+
     #[test]
     fn remove_peer_everywhere_cleans_empty_entries() {
         let mut idx = SubscriptionIndex::new();
@@ -171,4 +443,135 @@ mod tests {
         let m = idx.match_topic(b"ABCD");
         assert_eq!(m.as_slice(), &[2]);
     }
+
+    #[test]
+    fn unsubscribe_prunes_and_keeps_siblings() {
+        let mut idx = SubscriptionIndex::new();
+
+        idx.subscribe(1, Bytes::from_static(b"ABC"));
+        idx.subscribe(2, Bytes::from_static(b"AB"));
+        idx.subscribe(3, Bytes::from_static(b"ABD"));
+
+        idx.unsubscribe(1, &Bytes::from_static(b"ABC"));
+        assert_eq!(idx.match_topic(b"ABCD").as_slice(), &[2]);
+        assert_eq!(idx.match_topic(b"ABD").as_slice(), &[2, 3]);
+
+        // Unknown prefix / peer are no-ops.
+        idx.unsubscribe(9, &Bytes::from_static(b"AB"));
+        idx.unsubscribe(2, &Bytes::from_static(b"XYZ"));
+        assert_eq!(idx.match_topic(b"ABD").as_slice(), &[2, 3]);
+
+        idx.unsubscribe(2, &Bytes::from_static(b"AB"));
+        idx.unsubscribe(3, &Bytes::from_static(b"ABD"));
+        assert!(idx.is_empty());
+        assert!(idx.match_topic(b"ABD").is_empty());
+    }
+
+    #[test]
+    fn wildcard_entries_match_alongside_prefixes() {
+        let mut idx = SubscriptionIndex::new();
+
+        idx.subscribe(1, Bytes::from_static(b"weather."));
+        idx.subscribe_pattern(2, WildcardPattern::new("weather.*"));
+        idx.subscribe_pattern(3, WildcardPattern::new("sensor.?.*"));
+        idx.subscribe_pattern(4, WildcardPattern::new(""));
+        idx.subscribe_pattern(2, WildcardPattern::new("sensor.?.*"));
+
+        assert_eq!(idx.match_topic(b"weather.paris").as_slice(), &[1, 2]);
+        assert_eq!(idx.match_topic(b"sensor.3.temp").as_slice(), &[2, 3]);
+        assert!(idx.match_topic(b"sensor.33.temp").is_empty());
+        assert_eq!(idx.match_topic(b"").as_slice(), &[4]);
+
+        idx.unsubscribe_pattern(2, &WildcardPattern::new("weather.*"));
+        assert_eq!(idx.match_topic(b"weather.paris").as_slice(), &[1]);
+
+        idx.remove_peer_everywhere(2);
+        assert_eq!(idx.match_topic(b"sensor.3.temp").as_slice(), &[3]);
+
+        idx.unsubscribe(1, &Bytes::from_static(b"weather."));
+        idx.unsubscribe_pattern(3, &WildcardPattern::new("sensor.?.*"));
+        assert!(!idx.is_empty());
+        idx.unsubscribe_pattern(4, &WildcardPattern::new(""));
+        assert!(idx.is_empty());
+        assert!(idx.wildcards().is_empty());
+    }
+
+    #[test]
+    fn empty_prefix_matches_every_topic() {
+        let mut idx = SubscriptionIndex::new();
+
+        idx.subscribe(4, Bytes::new());
+        idx.subscribe(5, Bytes::from_static(b"X"));
+
+        assert_eq!(idx.match_topic(b"").as_slice(), &[4]);
+        assert_eq!(idx.match_topic(b"XY").as_slice(), &[4, 5]);
+    }
+
+    /// 10k subscriptions over a small alphabet so prefixes share paths; the
+    /// trie must agree with a linear scan on random topics, before and after
+    /// churn.
+    #[test]
+    fn matches_naive_scan_with_10k_subscriptions() {
+        // xorshift64*: deterministic, no extra dev-dependency.
+        let mut state = 0x9E37_79B9_7F4A_7C15_u64;
+        let mut next = move || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        };
+        let mut random_bytes = |max_len: u64| -> Vec<u8> {
+            let len = next() % (max_len + 1);
+            (0..len).map(|_| b'a' + (next() % 4) as u8).collect()
+        };
+
+        let mut idx = SubscriptionIndex::new();
+        // One entry per subscribe, duplicates included, so each entry stands
+        // for one reference the index must count.
+        let mut naive: Vec<(Vec<u8>, PeerKey)> = Vec::new();
+        for i in 0..10_000u64 {
+            let prefix = random_bytes(8);
+            let peer = i % 500;
+            idx.subscribe(peer, Bytes::from(prefix.clone()));
+            naive.push((prefix, peer));
+        }
+
+        let naive_match = |naive: &[(Vec<u8>, PeerKey)], topic: &[u8]| {
+            let mut out: Vec<PeerKey> = naive
+                .iter()
+                .filter(|(prefix, _)| topic.starts_with(prefix))
+                .map(|&(_, peer)| peer)
+                .collect();
+            out.sort_unstable();
+            out.dedup();
+            out
+        };
+
+        let topics: Vec<Vec<u8>> = (0..1_000).map(|_| random_bytes(12)).collect();
+        for topic in &topics {
+            assert_eq!(
+                idx.match_topic(topic).as_slice(),
+                naive_match(&naive, topic).as_slice()
+            );
+        }
+
+        // Churn: drop some peers entirely and some individual subscriptions.
+        for peer in (0..500).step_by(7) {
+            idx.remove_peer_everywhere(peer);
+            naive.retain(|&(_, p)| p != peer);
+        }
+        for (prefix, peer) in std::mem::take(&mut naive) {
+            if prefix.len() % 3 == 0 {
+                idx.unsubscribe(peer, &Bytes::from(prefix));
+            } else {
+                naive.push((prefix, peer));
+            }
+        }
+        for topic in &topics {
+            assert_eq!(
+                idx.match_topic(topic).as_slice(),
+                naive_match(&naive, topic).as_slice()
+            );
+        }
+    }
 }
@@ -0,0 +1,116 @@
+//! Shared, application-visible subscription index.
+//!
+//! A PUB or XPUB socket built with a [`SharedSubscriptionIndex`] mirrors every
+//! subscription it processes into this index, keyed by the socket's
+//! subscriber id. Application code (last-value caches, forwarders, metrics)
+//! holds a clone of the handle and queries the same authoritative view the
+//! fan-out uses.
+//!
+//! Concurrency model:
+//! - Writers are the sockets' subscription readers, which only run when a
+//!   SUBSCRIBE/CANCEL arrives or a subscriber disconnects.
+//! - Readers take a short `RwLock` read; publishing never touches the index,
+//!   so queries cannot stall the broadcast path.
+//! - `generation` is bumped (Release) after every mutation so callers can
+//!   cache derived state and re-query only when it advances.
+
+use crate::pubsub::index::{PeerKey, SubscriptionIndex};
+
+use bytes::Bytes;
+use parking_lot::RwLock;
+use smallvec::SmallVec;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[derive(Debug, Default)]
+struct Inner {
+    index: RwLock<SubscriptionIndex>,
+    generation: AtomicU64,
+}
+
+/// Cloneable handle to a [`SubscriptionIndex`] shared between a socket and
+/// the application.
+///
+/// Attach one handle to at most one socket: peer keys are the socket's own
+/// subscriber ids and would collide across sockets.
+#[derive(Debug, Clone, Default)]
+pub struct SharedSubscriptionIndex {
+    inner: Arc<Inner>,
+}
+
+impl SharedSubscriptionIndex {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether any subscriber's prefix matches `topic`.
+    #[must_use]
+    pub fn matches(&self, topic: &[u8]) -> bool {
+        self.inner.index.read().matches(topic)
+    }
+
+    /// Deduplicated peers whose prefixes match `topic`.
+    #[must_use]
+    pub fn match_topic(&self, topic: &[u8]) -> SmallVec<[PeerKey; 16]> {
+        self.inner.index.read().match_topic(topic)
+    }
+
+    /// Whether no subscriptions are recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.index.read().is_empty()
+    }
+
+    /// Number of mutations applied so far.
+    #[must_use]
+    pub fn generation(&self) -> u64 {
+        self.inner.generation.load(Ordering::Acquire)
+    }
+
+    /// Record a subscription for `peer`.
+    pub fn subscribe(&self, peer: PeerKey, prefix: Bytes) {
+        self.update(|idx| idx.subscribe(peer, prefix));
+    }
+
+    /// Drop a subscription for `peer`.
+    pub fn unsubscribe(&self, peer: PeerKey, prefix: &Bytes) {
+        self.update(|idx| idx.unsubscribe(peer, prefix));
+    }
+
+    /// Drop every subscription held by `peer` (used on disconnect).
+    pub fn remove_peer(&self, peer: PeerKey) {
+        self.update(|idx| idx.remove_peer_everywhere(peer));
+    }
+
+    /// Apply a mutation under the write lock and publish a new generation.
+    fn update(&self, f: impl FnOnce(&mut SubscriptionIndex)) {
+        f(&mut self.inner.index.write());
+        self.inner.generation.fetch_add(1, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_state() {
+        let index = SharedSubscriptionIndex::new();
+        let view = index.clone();
+        assert!(!view.matches(b"weather.paris"));
+
+        index.subscribe(1, Bytes::from_static(b"weather."));
+        index.subscribe(2, Bytes::from_static(b"weather.paris"));
+        assert!(view.matches(b"weather.paris"));
+        assert_eq!(view.match_topic(b"weather.paris").as_slice(), &[1, 2]);
+        assert_eq!(view.generation(), 2);
+
+        index.unsubscribe(1, &Bytes::from_static(b"weather."));
+        assert!(!view.matches(b"weather.rome"));
+
+        index.remove_peer(2);
+        assert!(view.is_empty());
+        assert_eq!(view.generation(), 4);
+    }
+}
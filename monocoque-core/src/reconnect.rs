@@ -1,15 +1,174 @@
-//! Reconnection utilities with exponential backoff support.
+//! Reconnection utilities with pluggable backoff.
 //!
-//! This module provides utilities for managing socket reconnection with
-//! exponential backoff, following libzmq patterns.
+//! [`ReconnectState`] counts attempts and asks a [`ReconnectPolicy`] how long
+//! to wait before each one. Built from [`SocketOptions`] it uses libzmq's
+//! exponential backoff ([`ExponentialPolicy`]); [`ConstantPolicy`],
+//! [`FibonacciPolicy`] or any custom policy can be supplied instead, and
+//! [`ReconnectState::with_jitter`] spreads the delays of whichever is used.
+//!
+//! [`ConnectHistory`] keeps the outcome of the latest connection attempts for
+//! diagnostics.
 
+use crate::endpoint::Endpoint;
 use crate::options::SocketOptions;
-use std::time::Duration;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, SystemTime};
+
+/// A backoff strategy: the delay before each reconnection attempt.
+///
+/// `next_delay` is called once per attempt and `reset` after a successful
+/// connection, so a policy may keep whatever state it needs between calls.
+pub trait ReconnectPolicy: Send + 'static {
+    /// Delay before the next attempt, advancing the policy.
+    fn next_delay(&mut self) -> Duration;
+
+    /// Start over from the first delay.
+    fn reset(&mut self);
+}
+
+/// Wait the same interval before every attempt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConstantPolicy {
+    /// Delay before each attempt.
+    pub interval: Duration,
+}
+
+impl ReconnectPolicy for ConstantPolicy {
+    fn next_delay(&mut self) -> Duration {
+        self.interval
+    }
+
+    fn reset(&mut self) {}
+}
+
+/// Multiply the delay by `multiplier` after each attempt, up to `max`.
+///
+/// As with libzmq's `ZMQ_RECONNECT_IVL_MAX`, a `max` of zero or one not above
+/// `base` disables growth and every attempt waits `base`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExponentialPolicy {
+    /// Delay before the first attempt.
+    pub base: Duration,
+    /// Largest delay once backoff has grown.
+    pub max: Duration,
+    /// Growth factor between attempts; 2.0 doubles the delay.
+    pub multiplier: f64,
+    attempt: u32,
+}
+
+impl ExponentialPolicy {
+    /// Create a policy starting at `base` and growing by `multiplier` up to `max`.
+    #[must_use]
+    pub const fn new(base: Duration, max: Duration, multiplier: f64) -> Self {
+        Self {
+            base,
+            max,
+            multiplier,
+            attempt: 0,
+        }
+    }
+}
+
+impl ReconnectPolicy for ExponentialPolicy {
+    fn next_delay(&mut self) -> Duration {
+        let attempt = self.attempt;
+        self.attempt = self.attempt.saturating_add(1);
+        if attempt == 0 || self.max.is_zero() || self.max <= self.base {
+            return self.base;
+        }
+        // Grown in f64 nanoseconds, which also saturates instead of
+        // overflowing `Duration` for huge attempt counts.
+        let exponent = attempt.min(i32::MAX as u32) as i32;
+        let nanos = self.base.as_nanos() as f64 * self.multiplier.powi(exponent);
+        if nanos >= self.max.as_nanos() as f64 {
+            self.max
+        } else {
+            Duration::from_nanos(nanos.round() as u64)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+/// Grow the delay along the Fibonacci sequence (`base`, `base`, `2·base`,
+/// `3·base`, `5·base`, ...), up to `max`.
+///
+/// Gentler than doubling early on. A `max` of zero or one not above `base`
+/// holds every attempt at `base`, like [`ExponentialPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FibonacciPolicy {
+    /// Delay before the first two attempts.
+    pub base: Duration,
+    /// Largest delay once backoff has grown.
+    pub max: Duration,
+    /// Current and next Fibonacci multipliers of `base`.
+    terms: (u32, u32),
+}
+
+impl FibonacciPolicy {
+    /// Create a policy starting at `base` and growing up to `max`.
+    #[must_use]
+    pub const fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            terms: (1, 1),
+        }
+    }
+}
+
+impl ReconnectPolicy for FibonacciPolicy {
+    fn next_delay(&mut self) -> Duration {
+        if self.max.is_zero() || self.max <= self.base {
+            return self.base;
+        }
+        let (current, next) = self.terms;
+        let delay = self
+            .base
+            .checked_mul(current)
+            .map_or(self.max, |d| d.min(self.max));
+        // Once the cap is reached the sequence stops growing.
+        if delay < self.max {
+            self.terms = (next, current.saturating_add(next));
+        }
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.terms = (1, 1);
+    }
+}
+
+/// Scales another policy's delays by a uniform random factor in
+/// `[1 - factor, 1]`.
+struct Jittered {
+    inner: Box<dyn ReconnectPolicy>,
+    factor: f64,
+}
+
+impl ReconnectPolicy for Jittered {
+    fn next_delay(&mut self) -> Duration {
+        let delay = self.inner.next_delay();
+        if self.factor <= 0.0 || delay.is_zero() {
+            return delay;
+        }
+        let scale = 1.0 - rand::thread_rng().gen_range(0.0..=self.factor);
+        delay.mul_f64(scale)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
 
 /// Reconnection state tracker for managing connection attempts and backoff.
 ///
-/// This helper tracks the number of reconnection attempts and calculates
-/// the appropriate backoff delay using exponential backoff.
+/// This helper tracks the number of reconnection attempts and asks its
+/// [`ReconnectPolicy`] for the delay before each one.
 ///
 /// # Example
 ///
@@ -35,66 +194,93 @@ use std::time::Duration;
 /// reconnect.reset();
 /// assert_eq!(reconnect.next_delay(), Duration::from_millis(100));
 /// ```
-#[derive(Debug, Clone)]
+///
+/// Any other policy can be plugged in:
+///
+/// ```rust
+/// use monocoque_core::reconnect::{FibonacciPolicy, ReconnectState};
+/// use std::time::Duration;
+///
+/// let policy = FibonacciPolicy::new(Duration::from_millis(100), Duration::from_secs(5));
+/// let mut reconnect = ReconnectState::new_with_policy(Box::new(policy)).with_jitter(0.2);
+///
+/// // Jitter only ever shortens the delay, by at most 20%.
+/// let first = reconnect.next_delay();
+/// assert!(first >= Duration::from_millis(80) && first <= Duration::from_millis(100));
+/// ```
 pub struct ReconnectState {
-    /// Base reconnection interval
+    /// Strategy producing each delay
+    policy: Box<dyn ReconnectPolicy>,
+    /// Base reconnection interval (zero for a custom policy)
     base_interval: Duration,
-    /// Maximum reconnection interval
+    /// Maximum reconnection interval (zero for a custom policy)
     max_interval: Duration,
     /// Current reconnection attempt (0 = first attempt)
     attempt: u32,
-    /// Current backoff interval
+    /// Delay returned by the latest attempt
     current_interval: Duration,
 }
 
 impl ReconnectState {
     /// Create a new reconnection state tracker from socket options.
-    pub const fn new(options: &SocketOptions) -> Self {
+    ///
+    /// Uses an [`ExponentialPolicy`] doubling from `reconnect_ivl` up to
+    /// `reconnect_ivl_max`.
+    pub fn new(options: &SocketOptions) -> Self {
+        let policy = ExponentialPolicy::new(options.reconnect_ivl, options.reconnect_ivl_max, 2.0);
         Self {
             base_interval: options.reconnect_ivl,
             max_interval: options.reconnect_ivl_max,
-            attempt: 0,
             current_interval: options.reconnect_ivl,
+            ..Self::new_with_policy(Box::new(policy))
         }
     }
 
-    /// Get the delay for the next reconnection attempt.
+    /// Create a reconnection state tracker driven by `policy`.
+    pub fn new_with_policy(policy: Box<dyn ReconnectPolicy>) -> Self {
+        Self {
+            policy,
+            base_interval: Duration::ZERO,
+            max_interval: Duration::ZERO,
+            attempt: 0,
+            current_interval: Duration::ZERO,
+        }
+    }
+
+    /// Randomise each delay by up to `factor` of itself.
     ///
-    /// This calculates the exponential backoff delay based on the number
-    /// of previous attempts. The delay doubles with each attempt until
-    /// it reaches `reconnect_ivl_max`.
+    /// Every delay is scaled by a uniform factor in `[1 - factor, 1]`, so a
+    /// fleet of clients that lost the same server does not reconnect in
+    /// lockstep, and no delay exceeds what the policy asked for. `factor` is
+    /// clamped to `0.0..=1.0`.
+    #[must_use]
+    pub fn with_jitter(mut self, factor: f64) -> Self {
+        let inner = std::mem::replace(&mut self.policy, Box::new(ConstantPolicy::default()));
+        self.policy = Box::new(Jittered {
+            inner,
+            factor: factor.clamp(0.0, 1.0),
+        });
+        self
+    }
+
+    /// Get the delay for the next reconnection attempt.
     ///
     /// # Returns
     ///
     /// The duration to wait before the next reconnection attempt.
     pub fn next_delay(&mut self) -> Duration {
-        let delay = self.current_interval;
-
         self.attempt += 1;
-
-        // libzmq semantics: reconnect_ivl_max == 0 disables exponential backoff
-        // and reconnects at the constant base interval. A max below the base is
-        // likewise treated as "hold at base" (there is no room to grow). Only
-        // when max > base do we apply exponential backoff capped at max.
-        if self.max_interval.is_zero() || self.max_interval <= self.base_interval {
-            self.current_interval = self.base_interval;
-        } else {
-            self.current_interval = self
-                .base_interval
-                .checked_mul(1_u32 << self.attempt.min(10))
-                .unwrap_or(self.max_interval)
-                .min(self.max_interval);
-        }
-
-        delay
+        self.current_interval = self.policy.next_delay();
+        self.current_interval
     }
 
     /// Reset the reconnection state after a successful connection.
     ///
-    /// This resets the attempt counter and interval back to the base values.
+    /// This resets the attempt counter and the policy back to the first delay.
     pub fn reset(&mut self) {
         self.attempt = 0;
         self.current_interval = self.base_interval;
+        self.policy.reset();
     }
 
     /// Get the current attempt number.
@@ -105,6 +291,8 @@ impl ReconnectState {
     }
 
     /// Get the base reconnection interval.
+    ///
+    /// Zero for a state created with [`new_with_policy`](Self::new_with_policy).
     #[inline]
     #[must_use]
     pub const fn base_interval(&self) -> Duration {
@@ -112,13 +300,16 @@ impl ReconnectState {
     }
 
     /// Get the maximum reconnection interval.
+    ///
+    /// Zero for a state created with [`new_with_policy`](Self::new_with_policy).
     #[inline]
     #[must_use]
     pub const fn max_interval(&self) -> Duration {
         self.max_interval
     }
 
-    /// Get the current reconnection interval.
+    /// Get the delay returned by the latest attempt (the base interval before
+    /// the first one).
     #[inline]
     #[must_use]
     pub const fn current_interval(&self) -> Duration {
@@ -126,6 +317,131 @@ impl ReconnectState {
     }
 }
 
+impl std::fmt::Debug for ReconnectState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReconnectState")
+            .field("base_interval", &self.base_interval)
+            .field("max_interval", &self.max_interval)
+            .field("attempt", &self.attempt)
+            .field("current_interval", &self.current_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// How a connection attempt ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectOutcome {
+    /// Connected and completed the handshake in the given time.
+    Ok(Duration),
+    /// The dial or the handshake failed.
+    Err {
+        /// Kind of the error returned by the attempt
+        kind: io::ErrorKind,
+        /// The error's message
+        message: String,
+    },
+}
+
+impl ConnectOutcome {
+    /// The outcome of an attempt that failed with `err`.
+    #[must_use]
+    pub fn from_error(err: &io::Error) -> Self {
+        Self::Err {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Whether the attempt connected.
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok(_))
+    }
+}
+
+/// One connection attempt recorded in a [`ConnectHistory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectAttempt {
+    /// Endpoint that was dialed
+    pub endpoint: Endpoint,
+    /// Wall-clock time the attempt started
+    pub started_at: SystemTime,
+    /// How the attempt ended
+    pub outcome: ConnectOutcome,
+    /// 0 for the initial connect, then the reconnection attempt number
+    pub attempt_number: u32,
+}
+
+/// Bounded log of the latest connection attempts, oldest first.
+///
+/// Once full, recording an attempt drops the oldest one, so memory stays at
+/// `capacity` entries however long a socket keeps reconnecting.
+#[derive(Debug, Clone)]
+pub struct ConnectHistory {
+    attempts: VecDeque<ConnectAttempt>,
+    capacity: usize,
+}
+
+impl ConnectHistory {
+    /// Number of attempts a socket keeps by default.
+    pub const DEFAULT_CAPACITY: usize = 16;
+
+    /// Keep at most `capacity` attempts.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            attempts: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Record `attempt`, dropping the oldest entry when full.
+    pub fn record(&mut self, attempt: ConnectAttempt) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.attempts.len() == self.capacity {
+            self.attempts.pop_front();
+        }
+        self.attempts.push_back(attempt);
+    }
+
+    /// Recorded attempts, oldest first.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &ConnectAttempt> + '_ {
+        self.attempts.iter()
+    }
+
+    /// The most recent attempt.
+    #[must_use]
+    pub fn last(&self) -> Option<&ConnectAttempt> {
+        self.attempts.back()
+    }
+
+    /// Number of attempts recorded.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.attempts.len()
+    }
+
+    /// Whether no attempt has been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.attempts.is_empty()
+    }
+
+    /// Maximum number of attempts kept.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl Default for ConnectHistory {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
 /// Error type for reconnection operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReconnectError {
@@ -159,6 +475,28 @@ impl std::error::Error for ReconnectError {}
 mod tests {
     use super::*;
 
+    fn attempt(n: u32) -> ConnectAttempt {
+        ConnectAttempt {
+            endpoint: Endpoint::Tcp("127.0.0.1:5555".parse().unwrap()),
+            started_at: SystemTime::now(),
+            outcome: ConnectOutcome::Ok(Duration::ZERO),
+            attempt_number: n,
+        }
+    }
+
+    #[test]
+    fn connect_history_keeps_latest_attempts() {
+        let mut history = ConnectHistory::new(3);
+        for n in 0..5 {
+            history.record(attempt(n));
+        }
+
+        assert_eq!(history.len(), 3);
+        let numbers: Vec<_> = history.iter().map(|a| a.attempt_number).collect();
+        assert_eq!(numbers, vec![2, 3, 4]);
+        assert_eq!(history.last().unwrap().attempt_number, 4);
+    }
+
     #[test]
     fn test_exponential_backoff() {
         let options = SocketOptions::default()
@@ -284,4 +622,103 @@ mod tests {
         assert_eq!(state.current_interval(), Duration::from_millis(250));
         assert_eq!(state.attempt(), 0);
     }
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_millis(v)).collect()
+    }
+
+    fn ten_delays(state: &mut ReconnectState) -> Vec<Duration> {
+        (0..10).map(|_| state.next_delay()).collect()
+    }
+
+    #[test]
+    fn test_constant_policy_sequence() {
+        let policy = ConstantPolicy {
+            interval: Duration::from_millis(250),
+        };
+        let mut state = ReconnectState::new_with_policy(Box::new(policy));
+
+        assert_eq!(ten_delays(&mut state), ms(&[250; 10]));
+        assert_eq!(state.attempt(), 10);
+    }
+
+    #[test]
+    fn test_exponential_policy_sequence() {
+        let policy =
+            ExponentialPolicy::new(Duration::from_millis(100), Duration::from_secs(2), 1.5);
+        let mut state = ReconnectState::new_with_policy(Box::new(policy));
+
+        assert_eq!(
+            ten_delays(&mut state),
+            [
+                100_000_000,
+                150_000_000,
+                225_000_000,
+                337_500_000,
+                506_250_000,
+                759_375_000,
+                1_139_062_500,
+                1_708_593_750,
+                2_000_000_000,
+                2_000_000_000,
+            ]
+            .map(Duration::from_nanos)
+        );
+    }
+
+    #[test]
+    fn test_options_build_doubling_exponential_policy() {
+        let options = SocketOptions::default()
+            .with_reconnect_ivl(Duration::from_millis(10))
+            .with_reconnect_ivl_max(Duration::from_secs(3));
+        let mut state = ReconnectState::new(&options);
+
+        assert_eq!(
+            ten_delays(&mut state),
+            ms(&[10, 20, 40, 80, 160, 320, 640, 1280, 2560, 3000])
+        );
+    }
+
+    #[test]
+    fn test_fibonacci_policy_sequence() {
+        let policy = FibonacciPolicy::new(Duration::from_millis(100), Duration::from_millis(2_500));
+        let mut state = ReconnectState::new_with_policy(Box::new(policy));
+
+        assert_eq!(
+            ten_delays(&mut state),
+            ms(&[100, 100, 200, 300, 500, 800, 1300, 2100, 2500, 2500])
+        );
+
+        state.reset();
+        assert_eq!(state.attempt(), 0);
+        assert_eq!(ten_delays(&mut state)[..4], ms(&[100, 100, 200, 300]));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_factor_of_policy_delay() {
+        let options = SocketOptions::default()
+            .with_reconnect_ivl(Duration::from_millis(100))
+            .with_reconnect_ivl_max(Duration::from_mins(1));
+        let mut state = ReconnectState::new(&options).with_jitter(0.25);
+
+        let expected = ms(&[100, 200, 400, 800, 1600, 3200, 6400, 12800, 25600, 51200]);
+        for (delay, full) in ten_delays(&mut state).into_iter().zip(expected) {
+            assert!(
+                delay <= full && delay >= full.mul_f64(0.75),
+                "jittered {delay:?} outside [{:?}, {full:?}]",
+                full.mul_f64(0.75)
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_jitter_keeps_policy_delays() {
+        let policy = FibonacciPolicy::new(Duration::from_millis(100), Duration::from_secs(1));
+        let mut state = ReconnectState::new_with_policy(Box::new(policy)).with_jitter(0.0);
+
+        assert_eq!(
+            ten_delays(&mut state),
+            ms(&[100, 100, 200, 300, 500, 800, 1000, 1000, 1000, 1000])
+        );
+    }
 }
@@ -6,11 +6,14 @@
 //! - Envelope normalization:
 //!   - inbound (actor->user) is normalized elsewhere to [ID, Empty, Body...]
 //!   - outbound (user->hub) accepts [ID, (Empty), Body...] in Standard mode
-//! - Load balancer mode: round-robin dispatch when no explicit routing id is used
+//! - Load balancer mode: dispatch by [`RoutingPolicy`] when no explicit routing
+//!   id is used (also available in Standard mode through `SendAny`)
 //! - "Ghost peer" self-heal: stale IDs removed from rr list when detected
 
+use crate::lanes::PriorityLanes;
 use bytes::Bytes;
 use flume::{Receiver, Sender};
+use rand::Rng;
 use std::collections::HashMap;
 use std::collections::hash_map::RandomState;
 use std::sync::Arc;
@@ -27,7 +30,14 @@ type PeerMap<V> = HashMap<Bytes, V, RandomState>;
 pub enum RouterCmd {
     /// Send a message (with routing envelope in Standard mode, or body-only in LB mode)
     SendMessage(Vec<Bytes>),
-    /// Close all peers
+    /// Send a body-only message to a peer chosen by the hub's
+    /// [`RoutingPolicy`], whatever the [`RouterBehavior`].
+    SendAny(Vec<Bytes>),
+    /// Signal the sender once every command queued before this one has been
+    /// routed to its peer's queue.
+    Barrier(Sender<()>),
+    /// Close all peers. Sent on the hub's control lane (see
+    /// [`RouterHub::with_control`]) it overtakes queued messages.
     Close,
 }
 
@@ -40,6 +50,9 @@ pub enum PeerCmd {
     /// matching peer the same allocation instead of cloning a fresh
     /// `Vec<Bytes>` per peer.
     SendBody(Arc<Vec<Bytes>>),
+    /// Write an already-encoded command frame (a PONG, say) as-is. Sent on
+    /// the peer's control lane so it is not held up behind queued bodies.
+    SendCommand(Bytes),
     Close,
 }
 
@@ -49,6 +62,9 @@ pub enum HubEvent {
     PeerUp {
         routing_id: Bytes, // Owned + stable
         tx: Sender<PeerCmd>,
+        /// The peer's control lane, if it has one; `Close` goes there so it
+        /// is not queued behind undelivered bodies.
+        control: Option<Sender<PeerCmd>>,
     },
     PeerDown {
         routing_id: Bytes,
@@ -67,21 +83,56 @@ pub enum RouterBehavior {
     LoadBalancer,
 }
 
+/// How the hub picks a peer for a message that names none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoutingPolicy {
+    /// Each peer in turn, in the order they connected.
+    #[default]
+    RoundRobin,
+    /// The peer with the fewest messages dispatched to it and not yet taken
+    /// by its writer, so slow peers are given less work. Ties go to the next
+    /// peer in round-robin order.
+    LeastConnections,
+    /// A peer picked uniformly at random.
+    Random,
+}
+
+/// A registered peer's command lanes.
+struct PeerLink {
+    data: Sender<PeerCmd>,
+    control: Option<Sender<PeerCmd>>,
+}
+
+impl PeerLink {
+    /// Close the peer ahead of its queued bodies when it has a control lane.
+    fn close(&self) {
+        let sent = self
+            .control
+            .as_ref()
+            .is_some_and(|control| control.try_send(PeerCmd::Close).is_ok());
+        if !sent {
+            let _ = self.data.send(PeerCmd::Close);
+        }
+    }
+}
+
 /// The Router Supervisor.
 ///
 /// This runs once per ROUTER socket (listener), and coordinates N peers.
 pub struct RouterHub {
     // routing table (keyed by attacker-controlled identity; seeded hasher)
-    peers: PeerMap<Sender<PeerCmd>>,
+    peers: PeerMap<PeerLink>,
 
     // LB rotation list (routing IDs)
     lb_list: Vec<Bytes>,
     lb_cursor: usize,
     behavior: RouterBehavior,
+    policy: RoutingPolicy,
 
     // channels
     hub_rx: Receiver<HubEvent>,
     user_tx_rx: Receiver<RouterCmd>,
+    user_control_rx: Option<Receiver<RouterCmd>>,
 }
 
 impl RouterHub {
@@ -96,15 +147,70 @@ impl RouterHub {
             lb_list: Vec::new(),
             lb_cursor: 0,
             behavior,
+            policy: RoutingPolicy::RoundRobin,
             hub_rx,
             user_tx_rx,
+            user_control_rx: None,
         }
     }
 
+    /// Take commands from `control` ahead of those queued on the data
+    /// channel, so a `Close` takes effect without waiting for the backlog.
+    #[must_use]
+    pub fn with_control(mut self, control: Receiver<RouterCmd>) -> Self {
+        self.user_control_rx = Some(control);
+        self
+    }
+
+    /// Pick peers for messages without a routing id by `policy` instead of
+    /// round robin.
+    #[must_use]
+    pub const fn with_policy(mut self, policy: RoutingPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Number of registered peers.
+    #[must_use]
+    pub fn peer_count(&self) -> usize {
+        self.peers.len()
+    }
+
+    /// Routing ids of the registered peers, in the order they connected.
+    #[must_use]
+    pub fn peer_ids(&self) -> Vec<Bytes> {
+        self.lb_list
+            .iter()
+            .filter(|id| self.peers.contains_key(*id))
+            .cloned()
+            .collect()
+    }
+
+    /// Send the body-only `msg` to a peer chosen by the routing policy,
+    /// returning its routing id; `None` (and the message is dropped) when no
+    /// peer is registered.
+    ///
+    /// This is what a running hub does for [`RouterCmd::SendAny`], and for
+    /// every message in [`RouterBehavior::LoadBalancer`] mode.
+    pub fn send_any(&mut self, msg: Vec<Bytes>) -> Option<Bytes> {
+        let id = match self.policy {
+            RoutingPolicy::RoundRobin => self.pick_rr_peer(),
+            RoutingPolicy::LeastConnections => self.pick_least_loaded_peer(),
+            RoutingPolicy::Random => self.pick_random_peer(),
+        }?;
+        let peer = self.peers.get(&id)?;
+        let _ = peer.data.send(PeerCmd::SendBody(Arc::new(msg)));
+        Some(id)
+    }
+
     pub async fn run(mut self) {
         use futures::FutureExt;
         use futures::select;
 
+        let mut user = match self.user_control_rx.take() {
+            Some(control) => PriorityLanes::new(control, self.user_tx_rx.clone()),
+            None => PriorityLanes::data_only(self.user_tx_rx.clone()),
+        };
         loop {
             // Use futures::select! for runtime-agnostic multiplexing
             select! {
@@ -114,24 +220,30 @@ impl RouterHub {
                         Err(_) => break, // channel closed
                     }
                 }
-                msg = self.user_tx_rx.recv_async().fuse() => {
+                msg = user.recv().fuse() => {
                     match msg {
-                        Ok(cmd) => self.handle_user_cmd(cmd),
-                        Err(_) => break, // channel closed
+                        Some(cmd) => self.handle_user_cmd(cmd),
+                        None => break, // channels closed
                     }
                 }
             }
         }
 
         // Best-effort: close all peers on hub shutdown.
-        for tx in self.peers.values() {
-            let _ = tx.send(PeerCmd::Close);
+        for peer in self.peers.values() {
+            peer.close();
         }
     }
 
-    fn handle_peer_event(&mut self, event: HubEvent) {
+    /// Apply a peer lifecycle event, as [`run`](Self::run) does for each one
+    /// arriving on the hub channel. For driving a hub directly.
+    pub fn handle_peer_event(&mut self, event: HubEvent) {
         match event {
-            HubEvent::PeerUp { routing_id, tx } => {
+            HubEvent::PeerUp {
+                routing_id,
+                tx,
+                control,
+            } => {
                 // Strict dedup: if ID exists, remove it from lb_list first to prevent drift.
                 if self.peers.contains_key(&routing_id)
                     && let Some(pos) = self.lb_list.iter().position(|x| x == &routing_id)
@@ -144,7 +256,8 @@ impl RouterHub {
 
                 // Move routing_id into lb_list, clone for peers map
                 self.lb_list.push(routing_id.clone());
-                self.peers.insert(routing_id, tx);
+                self.peers
+                    .insert(routing_id, PeerLink { data: tx, control });
             }
 
             HubEvent::PeerDown { routing_id } => {
@@ -164,10 +277,16 @@ impl RouterHub {
     fn handle_user_cmd(&mut self, cmd: RouterCmd) {
         match cmd {
             RouterCmd::SendMessage(parts) => self.route_outbound(parts),
+            RouterCmd::SendAny(parts) => {
+                self.send_any(parts);
+            }
+            RouterCmd::Barrier(done) => {
+                let _ = done.send(());
+            }
             RouterCmd::Close => {
                 // broadcast close to peers
-                for tx in self.peers.values() {
-                    let _ = tx.send(PeerCmd::Close);
+                for peer in self.peers.values() {
+                    peer.close();
                 }
             }
         }
@@ -205,6 +324,42 @@ impl RouterHub {
         None
     }
 
+    /// The peer whose data channel holds the fewest undelivered messages,
+    /// scanning from the round-robin cursor so ties rotate.
+    fn pick_least_loaded_peer(&mut self) -> Option<Bytes> {
+        let len = self.lb_list.len();
+        let mut best: Option<(usize, usize)> = None;
+        for step in 0..len {
+            let pos = (self.lb_cursor + step) % len;
+            let Some(peer) = self.peers.get(&self.lb_list[pos]) else {
+                continue;
+            };
+            let pending = peer.data.len();
+            if best.is_none_or(|(_, least)| pending < least) {
+                best = Some((pos, pending));
+            }
+        }
+        let Some((pos, _)) = best else {
+            // Only stale entries (or none): let round robin repair the list.
+            return self.pick_rr_peer();
+        };
+        self.lb_cursor = (pos + 1) % len;
+        Some(self.lb_list[pos].clone())
+    }
+
+    /// A uniformly random registered peer.
+    fn pick_random_peer(&mut self) -> Option<Bytes> {
+        if self.lb_list.is_empty() {
+            return None;
+        }
+        let id = &self.lb_list[rand::thread_rng().gen_range(0..self.lb_list.len())];
+        if self.peers.contains_key(id) {
+            Some(id.clone())
+        } else {
+            self.pick_rr_peer()
+        }
+    }
+
     fn route_outbound(&mut self, mut parts: Vec<Bytes>) {
         if parts.is_empty() {
             return;
@@ -221,22 +376,17 @@ impl RouterHub {
                     parts.remove(0);
                 }
 
-                if let Some(tx) = self.peers.get(&target_id) {
-                    let _ = tx.send(PeerCmd::SendBody(Arc::new(parts)));
+                if let Some(peer) = self.peers.get(&target_id) {
+                    let _ = peer.data.send(PeerCmd::SendBody(Arc::new(parts)));
                 } else {
                     // ZMQ behavior: silently drop if unknown id
                 }
             }
 
             RouterBehavior::LoadBalancer => {
-                // Expect: [Body...]
-                if let Some(id) = self.pick_rr_peer() {
-                    if let Some(tx) = self.peers.get(&id) {
-                        let _ = tx.send(PeerCmd::SendBody(Arc::new(parts)));
-                    }
-                } else {
-                    // No peers available: drop for now (backpressure elsewhere)
-                }
+                // Expect: [Body...]. With no peers available the message is
+                // dropped for now (backpressure elsewhere).
+                self.send_any(parts);
             }
         }
     }
@@ -281,6 +431,7 @@ mod tests {
                 .send(HubEvent::PeerUp {
                     routing_id: b("A"),
                     tx: peer_a_tx,
+                    control: None,
                 })
                 .unwrap();
             // Let the hub register the peer before routing to it.
@@ -325,12 +476,14 @@ mod tests {
                 .send(HubEvent::PeerUp {
                     routing_id: b("A"),
                     tx: peer_a_tx,
+                    control: None,
                 })
                 .unwrap();
             hub_tx
                 .send(HubEvent::PeerUp {
                     routing_id: b("B"),
                     tx: peer_b_tx,
+                    control: None,
                 })
                 .unwrap();
             crate::rt::sleep(Duration::from_millis(30)).await;
@@ -355,6 +508,137 @@ mod tests {
         });
     }
 
+    /// Register peers on a hub that is driven directly, not through `run`.
+    fn direct_hub(policy: RoutingPolicy, peers: usize) -> (RouterHub, Vec<Receiver<PeerCmd>>) {
+        let (_, hub_rx) = flume::unbounded::<HubEvent>();
+        let (_, user_rx) = flume::unbounded::<RouterCmd>();
+        let mut hub = RouterHub::new(hub_rx, user_rx, RouterBehavior::Standard).with_policy(policy);
+        let receivers = (0..peers)
+            .map(|i| {
+                let (tx, rx) = flume::unbounded();
+                hub.handle_peer_event(HubEvent::PeerUp {
+                    routing_id: b(&format!("w{i}")),
+                    tx,
+                    control: None,
+                });
+                rx
+            })
+            .collect();
+        (hub, receivers)
+    }
+
+    #[test]
+    fn send_any_follows_the_routing_policy() {
+        let (mut hub, _workers) = direct_hub(RoutingPolicy::RoundRobin, 3);
+        assert_eq!(hub.peer_count(), 3);
+        assert_eq!(hub.peer_ids(), vec![b("w0"), b("w1"), b("w2")]);
+        let picked: Vec<_> = (0..4).filter_map(|_| hub.send_any(vec![b("m")])).collect();
+        assert_eq!(picked, vec![b("w0"), b("w1"), b("w2"), b("w0")]);
+
+        // Nothing is drained, so the emptier queue always wins.
+        let (mut hub, workers) = direct_hub(RoutingPolicy::LeastConnections, 2);
+        assert_eq!(hub.send_any(vec![b("m")]), Some(b("w0")));
+        assert_eq!(hub.send_any(vec![b("m")]), Some(b("w1")));
+        workers[1].try_recv().unwrap();
+        assert_eq!(hub.send_any(vec![b("m")]), Some(b("w1")));
+
+        let (mut hub, workers) = direct_hub(RoutingPolicy::Random, 3);
+        for _ in 0..30 {
+            let id = hub.send_any(vec![b("m")]).unwrap();
+            assert!(hub.peer_ids().contains(&id));
+        }
+        assert_eq!(workers.iter().map(Receiver::len).sum::<usize>(), 30);
+
+        let (mut hub, _) = direct_hub(RoutingPolicy::LeastConnections, 0);
+        assert_eq!(hub.send_any(vec![b("m")]), None);
+    }
+
+    /// Dispatch one message per tick to 4 workers that take 1, 2, 4 and 8
+    /// ticks per message; returns the ticks until all are processed and the
+    /// deepest backlog any worker had.
+    fn simulate(policy: RoutingPolicy, messages: usize) -> (usize, usize) {
+        const COST: [usize; 4] = [1, 2, 4, 8];
+        let (mut hub, workers) = direct_hub(policy, COST.len());
+        let mut busy_until = [0usize; 4];
+        let (mut sent, mut done, mut deepest) = (0, 0, 0);
+        let mut tick = 0;
+        while done < messages {
+            if sent < messages {
+                hub.send_any(vec![b("job")]).unwrap();
+                sent += 1;
+            }
+            for (i, rx) in workers.iter().enumerate() {
+                deepest = deepest.max(rx.len());
+                if tick >= busy_until[i] && rx.try_recv().is_ok() {
+                    busy_until[i] = tick + COST[i];
+                    done += 1;
+                }
+            }
+            tick += 1;
+        }
+        (tick, deepest)
+    }
+
+    #[test]
+    fn least_connections_spreads_load_by_worker_speed() {
+        let (rr_ticks, rr_deepest) = simulate(RoutingPolicy::RoundRobin, 10_000);
+        let (lc_ticks, lc_deepest) = simulate(RoutingPolicy::LeastConnections, 10_000);
+        // Round robin gives the slowest worker a quarter of the work (2500
+        // messages at 8 ticks each); least connections keeps every worker's
+        // backlog short.
+        assert!(rr_ticks > 19_900, "round robin took {rr_ticks} ticks");
+        assert!(lc_ticks < 10_100, "least connections took {lc_ticks} ticks");
+        assert!(
+            lc_deepest * 100 < rr_deepest,
+            "backlogs: least connections {lc_deepest}, round robin {rr_deepest}"
+        );
+    }
+
+    #[test]
+    fn close_on_the_control_lane_overtakes_queued_messages() {
+        crate::rt::LocalRuntime::new().unwrap().block_on(async {
+            let (hub_tx, hub_rx) = flume::unbounded::<HubEvent>();
+            let (user_tx, user_rx) = flume::unbounded::<RouterCmd>();
+            let (control_tx, control_rx) = crate::lanes::control_lane();
+            let hub =
+                RouterHub::new(hub_rx, user_rx, RouterBehavior::Standard).with_control(control_rx);
+            let handle = crate::rt::spawn(hub.run());
+
+            // No peer control lane, so the peer's one channel shows the order
+            // in which the hub acted.
+            let (peer_tx, peer_rx) = flume::unbounded::<PeerCmd>();
+            hub_tx
+                .send(HubEvent::PeerUp {
+                    routing_id: b("A"),
+                    tx: peer_tx,
+                    control: None,
+                })
+                .unwrap();
+            crate::rt::sleep(Duration::from_millis(30)).await;
+
+            // Queue a backlog, then Close, without yielding to the hub.
+            for _ in 0..1000 {
+                user_tx
+                    .send(RouterCmd::SendMessage(vec![b("A"), b("bulk")]))
+                    .unwrap();
+            }
+            control_tx.send(RouterCmd::Close).unwrap();
+
+            let first = crate::rt::timeout(Duration::from_secs(1), peer_rx.recv_async())
+                .await
+                .expect("nothing delivered")
+                .unwrap();
+            assert!(
+                matches!(first, PeerCmd::Close),
+                "Close waited for the backlog"
+            );
+
+            drop(hub_tx);
+            drop(user_tx);
+            crate::rt::join(handle).await;
+        });
+    }
+
     #[test]
     fn peer_down_stops_delivery() {
         crate::rt::LocalRuntime::new().unwrap().block_on(async {
@@ -368,6 +652,7 @@ mod tests {
                 .send(HubEvent::PeerUp {
                     routing_id: b("A"),
                     tx: peer_a_tx,
+                    control: None,
                 })
                 .unwrap();
             crate::rt::sleep(Duration::from_millis(30)).await;
@@ -28,6 +28,24 @@ pub fn bind_reuseport(addr: std::net::SocketAddr) -> std::io::Result<TcpListener
     TcpListener::from_std(crate::tcp::reuseport_listener(addr)?)
 }
 
+/// Bind a TCP listener honoring the listener options in `options`
+/// (`so_reuseaddr`, `reuse_port`). See [`crate::tcp::bind_listener`].
+///
+/// # Errors
+///
+/// Returns an error if `addr` does not resolve or no resolved address binds.
+pub async fn bind_listener<A: ToSocketAddrs>(
+    addr: A,
+    options: &crate::options::SocketOptions,
+) -> std::io::Result<TcpListener> {
+    let addrs = addr.to_socket_addrs_async().await?;
+    TcpListener::from_std(crate::tcp::bind_listener(
+        addrs,
+        options.so_reuseaddr,
+        options.reuse_port,
+    )?)
+}
+
 /// Handle to a spawned task. Kept alive keeps the task running; dropping it
 /// detaches under compio.
 pub type JoinHandle<T> = compio::runtime::JoinHandle<T>;
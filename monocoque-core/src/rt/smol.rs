@@ -281,14 +281,10 @@ pub struct TcpListener {
 }
 
 impl TcpListener {
-    /// Bind a listening socket to `addr`.
-    ///
-    /// Async to mirror the other backends; smol's bind is synchronous.
-    #[allow(clippy::unused_async)] // signature parity with the compio backend
+    /// Bind a listening socket to `addr` with the default listener options.
+    /// See [`bind_listener`].
     pub async fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
-        let target = resolve(addr)?;
-        let inner = Async::<std::net::TcpListener>::bind(target)?;
-        Ok(Self { inner })
+        bind_listener(addr, &crate::options::SocketOptions::default()).await
     }
 
     /// Adopt a `std::net::TcpListener`, attaching it to the async reactor.
@@ -320,6 +316,22 @@ pub fn bind_reuseport(addr: SocketAddr) -> io::Result<TcpListener> {
     TcpListener::from_std(crate::tcp::reuseport_listener(addr)?)
 }
 
+/// Bind a TCP listener honoring the listener options in `options`
+/// (`so_reuseaddr`, `reuse_port`). See [`crate::tcp::bind_listener`].
+///
+/// Async to mirror the other backends; resolution and bind are synchronous.
+#[allow(clippy::unused_async)] // signature parity with the compio backend
+pub async fn bind_listener<A: ToSocketAddrs>(
+    addr: A,
+    options: &crate::options::SocketOptions,
+) -> io::Result<TcpListener> {
+    TcpListener::from_std(crate::tcp::bind_listener(
+        addr.to_socket_addrs()?,
+        options.so_reuseaddr,
+        options.reuse_port,
+    )?)
+}
+
 impl AsyncRead for TcpStream {
     async fn read<B: IoBufMut>(&mut self, buf: B) -> BufResult<usize, B> {
         read_into(&self.inner, buf).await
@@ -269,10 +269,10 @@ pub struct TcpListener {
 }
 
 impl TcpListener {
-    /// Bind a listening socket to `addr`.
+    /// Bind a listening socket to `addr` with the default listener options.
+    /// See [`bind_listener`].
     pub async fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
-        let inner = tokio::net::TcpListener::bind(addr).await?;
-        Ok(Self { inner })
+        bind_listener(addr, &crate::options::SocketOptions::default()).await
     }
 
     /// Adopt a `std::net::TcpListener`, attaching it to the tokio runtime.
@@ -300,6 +300,20 @@ pub fn bind_reuseport(addr: SocketAddr) -> io::Result<TcpListener> {
     TcpListener::from_std(crate::tcp::reuseport_listener(addr)?)
 }
 
+/// Bind a TCP listener honoring the listener options in `options`
+/// (`so_reuseaddr`, `reuse_port`). See [`crate::tcp::bind_listener`].
+pub async fn bind_listener<A: ToSocketAddrs>(
+    addr: A,
+    options: &crate::options::SocketOptions,
+) -> io::Result<TcpListener> {
+    let addrs = tokio::net::lookup_host(addr).await?;
+    TcpListener::from_std(crate::tcp::bind_listener(
+        addrs,
+        options.so_reuseaddr,
+        options.reuse_port,
+    )?)
+}
+
 impl_compio_io!(read TcpStream);
 impl_compio_io!(write TcpStream);
 impl_compio_io!(raw_fd TcpStream);
@@ -72,23 +72,43 @@ impl SocketType {
     }
 
     /// Check if this socket type is compatible with the given peer type.
+    ///
+    /// Follows the pairings of the ZMTP socket specifications (RFC 28, 29,
+    /// 30 and 31); libzmq closes any other pairing during the handshake.
+    /// STREAM speaks raw TCP and pairs with no ZMTP socket.
     #[must_use]
     pub const fn is_compatible(&self, peer: Self) -> bool {
         matches!(
             (self, peer),
             (Self::Pair, Self::Pair)
-                | (Self::Pub, Self::Sub)
-                | (Self::Sub, Self::Pub)
+                | (Self::Pub | Self::XPub, Self::Sub | Self::XSub)
+                | (Self::Sub | Self::XSub, Self::Pub | Self::XPub)
                 | (Self::Req | Self::Dealer, Self::Rep)
                 | (Self::Rep | Self::Router, Self::Req)
                 | (Self::Req | Self::Dealer | Self::Router, Self::Router)
                 | (Self::Rep | Self::Router | Self::Dealer, Self::Dealer)
                 | (Self::Push, Self::Pull)
                 | (Self::Pull, Self::Push)
-                | (Self::XPub, Self::XSub)
-                | (Self::XSub, Self::XPub)
         )
     }
+
+    /// Whether an application can send messages on this socket type.
+    ///
+    /// SUB and PULL are receive-only. XSUB sends, both subscriptions and
+    /// ordinary messages upstream.
+    #[must_use]
+    pub const fn can_send(&self) -> bool {
+        !matches!(self, Self::Sub | Self::Pull)
+    }
+
+    /// Whether an application can receive messages on this socket type.
+    ///
+    /// PUB and PUSH are send-only. XPUB receives the subscriptions of its
+    /// peers.
+    #[must_use]
+    pub const fn can_recv(&self) -> bool {
+        !matches!(self, Self::Pub | Self::Push)
+    }
 }
 
 impl fmt::Display for SocketType {
@@ -108,18 +128,64 @@ mod tests {
         assert_eq!(SocketType::Pub.to_string(), "PUB");
     }
 
+    const ALL: [SocketType; 12] = [
+        SocketType::Pair,
+        SocketType::Pub,
+        SocketType::Sub,
+        SocketType::Req,
+        SocketType::Rep,
+        SocketType::Dealer,
+        SocketType::Router,
+        SocketType::Pull,
+        SocketType::Push,
+        SocketType::XPub,
+        SocketType::XSub,
+        SocketType::Stream,
+    ];
+
+    /// Every valid pairing, one direction each.
+    const COMPATIBLE: [(SocketType, SocketType); 12] = [
+        (SocketType::Pair, SocketType::Pair),
+        (SocketType::Pub, SocketType::Sub),
+        (SocketType::Pub, SocketType::XSub),
+        (SocketType::XPub, SocketType::Sub),
+        (SocketType::XPub, SocketType::XSub),
+        (SocketType::Req, SocketType::Rep),
+        (SocketType::Req, SocketType::Router),
+        (SocketType::Dealer, SocketType::Rep),
+        (SocketType::Dealer, SocketType::Router),
+        (SocketType::Dealer, SocketType::Dealer),
+        (SocketType::Router, SocketType::Router),
+        (SocketType::Push, SocketType::Pull),
+    ];
+
     #[test]
-    fn test_socket_compatibility() {
-        assert!(SocketType::Req.is_compatible(SocketType::Rep));
-        assert!(SocketType::Rep.is_compatible(SocketType::Req));
-        assert!(SocketType::Dealer.is_compatible(SocketType::Router));
-        assert!(SocketType::Router.is_compatible(SocketType::Dealer));
-        assert!(SocketType::Push.is_compatible(SocketType::Pull));
-        assert!(SocketType::Pub.is_compatible(SocketType::Sub));
-        assert!(SocketType::XPub.is_compatible(SocketType::XSub));
-
-        // Incompatible pairs
-        assert!(!SocketType::Req.is_compatible(SocketType::Dealer));
-        assert!(!SocketType::Pub.is_compatible(SocketType::Pull));
+    fn test_socket_compatibility_matrix() {
+        for local in ALL {
+            for peer in ALL {
+                let expected = COMPATIBLE
+                    .iter()
+                    .any(|&(a, b)| (a, b) == (local, peer) || (b, a) == (local, peer));
+                assert_eq!(local.is_compatible(peer), expected, "{local} with {peer}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_send_and_recv_directions() {
+        let send_only = [SocketType::Pub, SocketType::Push];
+        let recv_only = [SocketType::Sub, SocketType::Pull];
+        for socket_type in ALL {
+            assert_eq!(
+                socket_type.can_send(),
+                !recv_only.contains(&socket_type),
+                "{socket_type}"
+            );
+            assert_eq!(
+                socket_type.can_recv(),
+                !send_only.contains(&socket_type),
+                "{socket_type}"
+            );
+        }
     }
 }
@@ -2,10 +2,56 @@
 //!
 //! This provides a more efficient subscription matching mechanism than linear
 //! scanning, especially for large numbers of subscriptions.
+//!
+//! # Matching semantics
+//!
+//! Every matcher here, and the publisher-side `SubscriptionIndex`, compares a
+//! subscription against the message's first frame only (see
+//! [`message_topic`]); later frames never take part, and a message with no
+//! frames matches nothing. A prefix subscription matches when the first frame
+//! starts with it, so the empty prefix matches every message. An exact
+//! subscription ([`TopicMatch::Exact`]) matches only a first frame equal to it.
 
 use bytes::{Bytes, BytesMut};
+use smallvec::SmallVec;
 use std::collections::BTreeSet;
 
+/// The part of a message subscriptions are matched against: its first frame.
+///
+/// `None` for a message with no frames, which no subscription matches.
+#[must_use]
+pub fn message_topic(msg: &[Bytes]) -> Option<&[u8]> {
+    msg.first().map(|frame| &frame[..])
+}
+
+/// How one subscription is compared against a message's topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TopicMatch {
+    /// The topic starts with the subscription, as in libzmq. The empty
+    /// subscription matches every topic.
+    Prefix,
+    /// The topic equals the subscription. Publishers only know prefixes, so a
+    /// SUB subscribes to the prefix on the wire and checks equality itself.
+    Exact,
+}
+
+impl TopicMatch {
+    /// Whether `topic` (a message's first frame) matches `subscription`.
+    #[must_use]
+    pub fn matches_topic(self, subscription: &[u8], topic: &[u8]) -> bool {
+        match self {
+            Self::Prefix => topic.starts_with(subscription),
+            Self::Exact => topic == subscription,
+        }
+    }
+
+    /// Whether the multipart message `msg` matches `subscription`.
+    #[must_use]
+    pub fn matches(self, subscription: &[u8], msg: &[Bytes]) -> bool {
+        message_topic(msg).is_some_and(|topic| self.matches_topic(subscription, topic))
+    }
+}
+
 /// A subscription entry with topic prefix
 #[derive(Debug, Clone)]
 pub struct Subscription {
@@ -23,13 +69,7 @@ impl Subscription {
     /// Check if this subscription matches a given topic
     #[must_use]
     pub fn matches(&self, topic: &[u8]) -> bool {
-        // Empty prefix matches everything
-        if self.prefix.is_empty() {
-            return true;
-        }
-
-        // Check if topic starts with prefix
-        topic.len() >= self.prefix.len() && topic[..self.prefix.len()] == self.prefix[..]
+        TopicMatch::Prefix.matches_topic(&self.prefix, topic)
     }
 }
 
@@ -109,9 +149,291 @@ impl SubscriptionTrie {
 pub fn topic_matches_prefixes(topic: &[u8], prefixes: &[Bytes]) -> bool {
     prefixes
         .iter()
-        .any(|prefix| prefix.is_empty() || topic.starts_with(prefix))
+        .any(|prefix| TopicMatch::Prefix.matches_topic(prefix, topic))
 }
 
+/// The receive-side filter of a SUB socket: prefix, exact and wildcard
+/// subscriptions, each matched against the first frame only.
+///
+/// Publishers only filter by prefix, so exact and wildcard subscriptions are
+/// subscribed on the wire by a prefix of their own and checked here. A filter
+/// with no subscriptions at all accepts every message.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionFilter {
+    prefixes: Vec<Bytes>,
+    exact: Vec<Bytes>,
+    patterns: Vec<WildcardPattern>,
+}
+
+impl SubscriptionFilter {
+    /// Create a filter with no subscriptions.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            prefixes: Vec::new(),
+            exact: Vec::new(),
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Whether no subscriptions of any kind are held.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty() && self.exact.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Prefix subscriptions, sorted.
+    #[must_use]
+    pub fn prefixes(&self) -> &[Bytes] {
+        &self.prefixes
+    }
+
+    /// Exact-topic subscriptions.
+    #[must_use]
+    pub fn exact(&self) -> &[Bytes] {
+        &self.exact
+    }
+
+    /// Wildcard subscriptions.
+    #[must_use]
+    pub fn patterns(&self) -> &[WildcardPattern] {
+        &self.patterns
+    }
+
+    /// Add a prefix subscription; `false` if it was already held.
+    pub fn add_prefix(&mut self, prefix: Bytes) -> bool {
+        match self.prefixes.binary_search(&prefix) {
+            Ok(_) => false,
+            Err(pos) => {
+                self.prefixes.insert(pos, prefix);
+                true
+            }
+        }
+    }
+
+    /// Remove a prefix subscription; `false` if it was not held.
+    pub fn remove_prefix(&mut self, prefix: &[u8]) -> bool {
+        remove_first(&mut self.prefixes, |p| p == prefix)
+    }
+
+    /// Add an exact-topic subscription; `false` if it was already held.
+    pub fn add_exact(&mut self, topic: Bytes) -> bool {
+        if self.exact.contains(&topic) {
+            return false;
+        }
+        self.exact.push(topic);
+        true
+    }
+
+    /// Remove an exact-topic subscription; `false` if it was not held.
+    pub fn remove_exact(&mut self, topic: &[u8]) -> bool {
+        remove_first(&mut self.exact, |t| t == topic)
+    }
+
+    /// Add a wildcard subscription; `false` if it was already held.
+    pub fn add_pattern(&mut self, pattern: WildcardPattern) -> bool {
+        if self.patterns.contains(&pattern) {
+            return false;
+        }
+        self.patterns.push(pattern);
+        true
+    }
+
+    /// Remove a wildcard subscription; `false` if it was not held.
+    pub fn remove_pattern(&mut self, pattern: &WildcardPattern) -> bool {
+        remove_first(&mut self.patterns, |p| p == pattern)
+    }
+
+    /// Whether an exact or wildcard subscription relies on `prefix` being
+    /// subscribed on the wire.
+    #[must_use]
+    pub fn held_locally(&self, prefix: &[u8]) -> bool {
+        self.exact.iter().any(|t| t == prefix)
+            || self.patterns.iter().any(|p| p.literal_prefix() == prefix)
+    }
+
+    /// Whether any subscription needs `prefix` subscribed on the wire.
+    #[must_use]
+    pub fn uses_prefix(&self, prefix: &[u8]) -> bool {
+        self.prefixes.iter().any(|p| p == prefix) || self.held_locally(prefix)
+    }
+
+    /// Every prefix the publisher must be subscribed to, each once.
+    #[must_use]
+    pub fn wire_prefixes(&self) -> Vec<Bytes> {
+        let mut out = self.prefixes.clone();
+        let local = self.exact.iter().cloned().chain(
+            self.patterns
+                .iter()
+                .map(|p| Bytes::copy_from_slice(p.literal_prefix())),
+        );
+        for prefix in local {
+            if !out.contains(&prefix) {
+                out.push(prefix);
+            }
+        }
+        out
+    }
+
+    /// Whether a message whose first frame is `topic` passes the filter.
+    ///
+    /// Prefixes are checked first as the cheap common case.
+    #[must_use]
+    pub fn accepts_topic(&self, topic: &[u8]) -> bool {
+        self.is_empty()
+            || topic_matches_prefixes(topic, &self.prefixes)
+            || self
+                .exact
+                .iter()
+                .any(|t| TopicMatch::Exact.matches_topic(t, topic))
+            || self.patterns.iter().any(|p| p.matches(topic))
+    }
+
+    /// Whether the multipart message `msg` passes the filter.
+    #[must_use]
+    pub fn accepts(&self, msg: &[Bytes]) -> bool {
+        message_topic(msg).is_some_and(|topic| self.accepts_topic(topic))
+    }
+}
+
+/// Remove the first element matching `pred`, reporting whether one was found.
+fn remove_first<T>(items: &mut Vec<T>, pred: impl Fn(&T) -> bool) -> bool {
+    items
+        .iter()
+        .position(pred)
+        .map(|pos| items.remove(pos))
+        .is_some()
+}
+
+/// A glob-style topic pattern: `*` matches any run of bytes (including none)
+/// and `?` matches exactly one byte. Every other byte matches itself.
+///
+/// Unlike a prefix subscription the pattern must cover the whole topic, so
+/// `weather.*` matches `weather.london` but `sensor.?` does not match
+/// `sensor.12`. The empty pattern therefore matches only the empty topic;
+/// subscribe to the empty prefix to receive everything.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct WildcardPattern(String);
+
+impl WildcardPattern {
+    /// Create a pattern from its textual form.
+    #[must_use]
+    pub fn new(pattern: impl Into<String>) -> Self {
+        Self(pattern.into())
+    }
+
+    /// The pattern as written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The bytes before the first wildcard.
+    ///
+    /// Every matching topic starts with this prefix, so it is what gets sent
+    /// to the publisher as a regular subscription.
+    #[must_use]
+    pub fn literal_prefix(&self) -> &[u8] {
+        let bytes = self.0.as_bytes();
+        let end = bytes
+            .iter()
+            .position(|&b| b == b'*' || b == b'?')
+            .unwrap_or(bytes.len());
+        &bytes[..end]
+    }
+
+    /// Check whether `topic` matches the whole pattern.
+    ///
+    /// Simulates the pattern's NFA one topic byte at a time, tracking the set
+    /// of live pattern positions, so the cost is O(topic_len × pattern_len)
+    /// with no backtracking.
+    #[must_use]
+    pub fn matches(&self, topic: &[u8]) -> bool {
+        let pattern = self.0.as_bytes();
+        let mut current = StateSet::new(pattern.len() + 1);
+        current.enter(pattern, 0);
+        for &byte in topic {
+            let mut next = StateSet::new(pattern.len() + 1);
+            for state in current.iter() {
+                match pattern.get(state) {
+                    Some(b'*') => next.enter(pattern, state),
+                    Some(&b) if b == b'?' || b == byte => next.enter(pattern, state + 1),
+                    _ => {}
+                }
+            }
+            if next.is_empty() {
+                return false;
+            }
+            current = next;
+        }
+        current.contains(pattern.len())
+    }
+}
+
+impl From<&str> for WildcardPattern {
+    fn from(pattern: &str) -> Self {
+        Self::new(pattern)
+    }
+}
+
+impl From<String> for WildcardPattern {
+    fn from(pattern: String) -> Self {
+        Self(pattern)
+    }
+}
+
+impl std::fmt::Display for WildcardPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Set of live NFA states (pattern positions) for [`WildcardPattern::matches`].
+struct StateSet {
+    words: SmallVec<[u64; 2]>,
+}
+
+impl StateSet {
+    fn new(states: usize) -> Self {
+        Self {
+            words: smallvec::smallvec![0; states.div_ceil(64)],
+        }
+    }
+
+    /// Mark `state` live, along with the states reachable from it by letting
+    /// `*` match nothing.
+    fn enter(&mut self, pattern: &[u8], mut state: usize) {
+        loop {
+            self.words[state / 64] |= 1 << (state % 64);
+            if pattern.get(state) != Some(&b'*') {
+                break;
+            }
+            state += 1;
+        }
+    }
+
+    fn contains(&self, state: usize) -> bool {
+        self.words[state / 64] & (1 << (state % 64)) != 0
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| i * 64 + bit)
+        })
+    }
+}
+
+/// ZMTP 3.1 `SUBSCRIBE` command name (length-prefixed).
+const SUBSCRIBE_CMD: &[u8] = b"\x09SUBSCRIBE";
+/// ZMTP 3.1 `CANCEL` command name (length-prefixed).
+const CANCEL_CMD: &[u8] = b"\x06CANCEL";
+
 /// Subscription event for XPUB socket
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SubscriptionEvent {
@@ -154,6 +476,21 @@ impl SubscriptionEvent {
         }
     }
 
+    /// Create a subscription event from a ZMTP 3.1 `SUBSCRIBE`/`CANCEL` command body.
+    ///
+    /// Format: `\x09SUBSCRIBE` or `\x06CANCEL` followed by the topic prefix.
+    /// Peers that negotiated ZMTP 3.1 send subscriptions this way instead of
+    /// as `0x01`/`0x00`-prefixed messages.
+    #[must_use]
+    pub fn from_command(body: &Bytes) -> Option<Self> {
+        if let Some(prefix) = body.strip_prefix(SUBSCRIBE_CMD) {
+            Some(Self::Subscribe(body.slice_ref(prefix)))
+        } else {
+            body.strip_prefix(CANCEL_CMD)
+                .map(|prefix| Self::Unsubscribe(body.slice_ref(prefix)))
+        }
+    }
+
     /// Encode this event as a ZMTP subscription message
     #[must_use]
     pub fn to_message(&self) -> Bytes {
@@ -168,6 +505,24 @@ impl SubscriptionEvent {
         msg.freeze()
     }
 
+    /// Encode this event as a ZMTP 3.1 `SUBSCRIBE`/`CANCEL` command body,
+    /// the inverse of [`from_command`](Self::from_command).
+    ///
+    /// Only send this to peers that negotiated ZMTP 3.1; 3.0 peers expect
+    /// [`to_message`](Self::to_message).
+    #[must_use]
+    pub fn to_command(&self) -> Bytes {
+        let (name, prefix) = match self {
+            Self::Subscribe(p) => (SUBSCRIBE_CMD, p),
+            Self::Unsubscribe(p) => (CANCEL_CMD, p),
+        };
+
+        let mut body = BytesMut::with_capacity(name.len() + prefix.len());
+        body.extend_from_slice(name);
+        body.extend_from_slice(prefix);
+        body.freeze()
+    }
+
     /// Get the topic prefix
     #[must_use]
     pub const fn prefix(&self) -> &Bytes {
@@ -266,6 +621,50 @@ mod tests {
         assert!(trie.matches(b"ab"));
     }
 
+    #[test]
+    fn test_wildcard_star_matches_any_suffix() {
+        let pattern = WildcardPattern::new("weather.*");
+
+        assert!(pattern.matches(b"weather."));
+        assert!(pattern.matches(b"weather.london"));
+        assert!(pattern.matches(b"weather.eu.paris"));
+        assert!(!pattern.matches(b"weather"));
+        assert!(!pattern.matches(b"sports.weather.x"));
+        assert_eq!(pattern.literal_prefix(), b"weather.");
+    }
+
+    #[test]
+    fn test_wildcard_question_mark_matches_one_byte() {
+        let pattern = WildcardPattern::new("sensor.?.*");
+
+        assert!(pattern.matches(b"sensor.1."));
+        assert!(pattern.matches(b"sensor.7.temperature"));
+        assert!(!pattern.matches(b"sensor.12.temperature"));
+        assert!(!pattern.matches(b"sensor..temperature"));
+        assert_eq!(pattern.literal_prefix(), b"sensor.");
+    }
+
+    #[test]
+    fn test_wildcard_empty_pattern_matches_only_empty_topic() {
+        let pattern = WildcardPattern::new("");
+
+        assert!(pattern.matches(b""));
+        assert!(!pattern.matches(b"anything"));
+        assert!(pattern.literal_prefix().is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_long_pattern_and_repeated_stars() {
+        // Spans more than one word of NFA state and needs several `*` to
+        // line up; a backtracking matcher would revisit these positions.
+        let pattern = WildcardPattern::new(format!("{}*a*a*b", "x".repeat(70)));
+        let mut topic = "x".repeat(70).into_bytes();
+        topic.extend_from_slice(&[b'a'; 200]);
+        assert!(!pattern.matches(&topic));
+        topic.push(b'b');
+        assert!(pattern.matches(&topic));
+    }
+
     #[test]
     fn test_subscription_event() {
         let sub = SubscriptionEvent::Subscribe(Bytes::from_static(b"topic"));
@@ -289,4 +688,223 @@ mod tests {
         let parsed = SubscriptionEvent::from_message(&msg).unwrap();
         assert_eq!(parsed, unsub);
     }
+
+    #[test]
+    fn test_subscription_event_from_command() {
+        let sub = SubscriptionEvent::from_command(&Bytes::from_static(b"\x09SUBSCRIBEtopic"));
+        assert_eq!(
+            sub,
+            Some(SubscriptionEvent::Subscribe(Bytes::from_static(b"topic")))
+        );
+
+        let cancel = SubscriptionEvent::from_command(&Bytes::from_static(b"\x06CANCELtopic"));
+        assert_eq!(
+            cancel,
+            Some(SubscriptionEvent::Unsubscribe(Bytes::from_static(b"topic")))
+        );
+
+        let all = SubscriptionEvent::from_command(&Bytes::from_static(b"\x09SUBSCRIBE"));
+        assert_eq!(all, Some(SubscriptionEvent::Subscribe(Bytes::new())));
+
+        assert_eq!(
+            SubscriptionEvent::from_command(&Bytes::from_static(b"\x04PING")),
+            None
+        );
+
+        for event in [sub.unwrap(), cancel.unwrap(), all.unwrap()] {
+            assert_eq!(
+                SubscriptionEvent::from_command(&event.to_command()),
+                Some(event)
+            );
+        }
+    }
+
+    #[test]
+    fn test_data_frame_and_command_forms_stay_separate() {
+        // ZMTP 3.0 peers subscribe with a data frame whose first byte is the
+        // flag; only the ZMTP 3.1 command form carries a name.
+        assert_eq!(
+            SubscriptionEvent::from_bytes(Bytes::from_static(b"\x01weather")),
+            Some(SubscriptionEvent::Subscribe(Bytes::from_static(b"weather")))
+        );
+        assert_eq!(
+            SubscriptionEvent::from_bytes(Bytes::from_static(b"\x00weather")),
+            Some(SubscriptionEvent::Unsubscribe(Bytes::from_static(
+                b"weather"
+            )))
+        );
+        assert_eq!(
+            SubscriptionEvent::from_command(&Bytes::from_static(b"\x01weather")),
+            None
+        );
+        // A command body is never mistaken for a flagged data frame, even one
+        // whose name happens to be three bytes long.
+        assert_eq!(
+            SubscriptionEvent::from_bytes(Bytes::from_static(b"\x05READY")),
+            None
+        );
+        assert_eq!(
+            SubscriptionEvent::from_command(&Bytes::from_static(b"\x03SUBweather")),
+            None
+        );
+    }
+
+    type MatchCase = (&'static [u8], TopicMatch, &'static [&'static [u8]], bool);
+
+    /// The matching rules, one row per case: subscription, how it matches,
+    /// message frames, and whether the message is delivered. The user guide
+    /// renders this table verbatim (see `matching_semantics_table_is_in_user_guide`).
+    const CASES: &[MatchCase] = &[
+        (b"weather", TopicMatch::Prefix, &[b"weather"], true),
+        (b"weather", TopicMatch::Prefix, &[b"weather.paris"], true),
+        (b"weather", TopicMatch::Prefix, &[b"weathe"], false),
+        (
+            b"weather",
+            TopicMatch::Prefix,
+            &[b"sports", b"weather"],
+            false,
+        ),
+        (b"weather", TopicMatch::Prefix, &[b"weather", b"72F"], true),
+        (b"", TopicMatch::Prefix, &[b"anything"], true),
+        (b"", TopicMatch::Prefix, &[b""], true),
+        (b"", TopicMatch::Prefix, &[], false),
+        (b"weather", TopicMatch::Exact, &[b"weather"], true),
+        (b"weather", TopicMatch::Exact, &[b"weather.paris"], false),
+        (b"weather", TopicMatch::Exact, &[b"weather", b"72F"], true),
+        (b"", TopicMatch::Exact, &[b""], true),
+        (b"", TopicMatch::Exact, &[b"anything"], false),
+    ];
+
+    fn filter_with(subscription: &[u8], kind: TopicMatch) -> SubscriptionFilter {
+        let mut filter = SubscriptionFilter::new();
+        let subscription = Bytes::copy_from_slice(subscription);
+        match kind {
+            TopicMatch::Prefix => filter.add_prefix(subscription),
+            TopicMatch::Exact => filter.add_exact(subscription),
+        };
+        filter
+    }
+
+    fn frames(msg: &[&[u8]]) -> Vec<Bytes> {
+        msg.iter().map(|f| Bytes::copy_from_slice(f)).collect()
+    }
+
+    #[test]
+    fn matching_semantics_cases() {
+        for &(subscription, kind, msg, expected) in CASES {
+            let msg = frames(msg);
+            let case = format!("{subscription:?} {kind:?} {msg:?}");
+            assert_eq!(kind.matches(subscription, &msg), expected, "{case}");
+            assert_eq!(
+                filter_with(subscription, kind).accepts(&msg),
+                expected,
+                "{case}"
+            );
+            if kind == TopicMatch::Prefix {
+                let mut index = crate::pubsub::index::SubscriptionIndex::default();
+                index.subscribe(1, Bytes::copy_from_slice(subscription));
+                let topic_matches = message_topic(&msg).is_some_and(|t| index.matches(t));
+                assert_eq!(topic_matches, expected, "{case}");
+            }
+        }
+    }
+
+    #[test]
+    fn filter_and_index_match_brute_force_reference() {
+        // xorshift64*: deterministic, no extra dev-dependency.
+        let mut state = 0x2545_F491_4F6C_DD1D_u64;
+        let mut next = move || {
+            state ^= state >> 12;
+            state ^= state << 25;
+            state ^= state >> 27;
+            state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+        };
+        // A three-letter alphabet keeps prefix collisions frequent.
+        let mut random_bytes = |max_len: u64| -> Vec<u8> {
+            let len = next() % (max_len + 1);
+            (0..len).map(|_| b"abc"[(next() % 3) as usize]).collect()
+        };
+
+        for _ in 0..500 {
+            let prefixes: Vec<Vec<u8>> = (0..3).map(|_| random_bytes(3)).collect();
+            let exact: Vec<Vec<u8>> = (0..2).map(|_| random_bytes(4)).collect();
+            let mut filter = SubscriptionFilter::new();
+            let mut index = crate::pubsub::index::SubscriptionIndex::default();
+            for p in &prefixes {
+                filter.add_prefix(Bytes::copy_from_slice(p));
+                index.subscribe(7, Bytes::copy_from_slice(p));
+            }
+            for e in &exact {
+                filter.add_exact(Bytes::copy_from_slice(e));
+            }
+
+            for _ in 0..20 {
+                let frame_count = random_bytes(3).len();
+                let msg: Vec<Bytes> = (0..frame_count).map(|_| random_bytes(5).into()).collect();
+
+                // Reference: compare every subscription to frame 0 by hand.
+                let (by_prefix, by_exact) = msg.first().map_or((false, false), |first| {
+                    (
+                        prefixes
+                            .iter()
+                            .any(|p| first.len() >= p.len() && first[..p.len()] == p[..]),
+                        exact.iter().any(|e| first[..] == e[..]),
+                    )
+                });
+                assert_eq!(filter.accepts(&msg), by_prefix || by_exact, "{msg:?}");
+                let index_matches = message_topic(&msg).is_some_and(|t| index.matches(t));
+                assert_eq!(index_matches, by_prefix, "{msg:?}");
+            }
+        }
+    }
+
+    /// Render [`CASES`] as the markdown table the user guide embeds.
+    fn render_cases() -> String {
+        use std::fmt::Write;
+
+        let show = |bytes: &[u8]| format!("`\"{}\"`", String::from_utf8_lossy(bytes));
+        let mut out = String::from(
+            "| Subscription | Kind | Message frames | Delivered |\n|---|---|---|---|\n",
+        );
+        for &(subscription, kind, msg, expected) in CASES {
+            let kind = match kind {
+                TopicMatch::Prefix => "`subscribe`",
+                TopicMatch::Exact => "`subscribe_exact`",
+            };
+            let msg = if msg.is_empty() {
+                "(none)".to_string()
+            } else {
+                msg.iter().map(|f| show(f)).collect::<Vec<_>>().join(", ")
+            };
+            let expected = if expected { "yes" } else { "no" };
+            writeln!(
+                out,
+                "| {} | {kind} | {msg} | {expected} |",
+                show(subscription)
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    #[test]
+    fn matching_semantics_table_is_in_user_guide() {
+        const START: &str = "<!-- matching-semantics:start -->\n";
+        const END: &str = "<!-- matching-semantics:end -->";
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../docs/USER_GUIDE.md");
+        let guide = std::fs::read_to_string(path).unwrap();
+        let start = guide.find(START).expect("missing start marker") + START.len();
+        let end = guide.find(END).expect("missing end marker");
+        let table = render_cases();
+        if guide[start..end] != table && std::env::var_os("MONOCOQUE_BLESS_DOCS").is_some() {
+            let updated = format!("{}{table}{}", &guide[..start], &guide[end..]);
+            std::fs::write(path, updated).unwrap();
+            return;
+        }
+        assert_eq!(
+            guide[start..end],
+            table,
+            "docs/USER_GUIDE.md is stale; rerun with MONOCOQUE_BLESS_DOCS=1"
+        );
+    }
 }
@@ -67,48 +67,80 @@ pub fn enable_tcp_nodelay<S>(_stream: &S) -> io::Result<()> {
     Ok(())
 }
 
-/// Build a listening `std::net::TcpListener` bound to `addr` with `SO_REUSEPORT`
-/// (and `SO_REUSEADDR`) set before the bind.
-///
-/// `SO_REUSEPORT` must be set on the socket before `bind`, so this constructs
-/// the socket via socket2, sets the option, then binds and listens. Multiple
-/// listeners on the same address can then coexist with in-kernel load balancing
-/// across them, which is the path to scaling accept in high-connection ROUTER,
-/// PULL, and PUB. Each runtime backend adopts the returned std listener via its
-/// own `from_std`.
+/// Build a listening `std::net::TcpListener` bound to the first of `addrs`
+/// that accepts a bind.
+///
+/// The socket is constructed via socket2 so `SO_REUSEADDR` and `SO_REUSEPORT`
+/// are applied before the bind, which is the only point at which they take
+/// effect. `reuse_addr` lets the port be rebound while old connections linger
+/// in `TIME_WAIT`; it is ignored outside Unix, where `SO_REUSEADDR` would let
+/// another socket bind over a live listener. `reuse_port` lets several live
+/// listeners share the address with in-kernel load balancing across them,
+/// which is the path to scaling accept in high-connection ROUTER, PULL, and
+/// PUB. Each runtime backend adopts the returned std listener via its own
+/// `from_std`.
 ///
 /// # Errors
 ///
-/// Returns an error if the socket cannot be created, the option set, or the
-/// address bound.
-#[cfg(unix)]
-pub fn reuseport_listener(addr: std::net::SocketAddr) -> io::Result<std::net::TcpListener> {
+/// Returns the last bind error if no address could be bound, `InvalidInput`
+/// if `addrs` is empty, and `Unsupported` if `reuse_port` is requested off
+/// Unix.
+pub fn bind_listener(
+    addrs: impl IntoIterator<Item = std::net::SocketAddr>,
+    reuse_addr: bool,
+    reuse_port: bool,
+) -> io::Result<std::net::TcpListener> {
+    let mut last_err = None;
+    for addr in addrs {
+        match bind_one(addr, reuse_addr, reuse_port) {
+            Ok(listener) => return Ok(listener),
+            Err(e) => last_err = Some(e),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "no socket address resolved")
+    }))
+}
+
+fn bind_one(
+    addr: std::net::SocketAddr,
+    reuse_addr: bool,
+    reuse_port: bool,
+) -> io::Result<std::net::TcpListener> {
     use socket2::{Domain, Protocol, Socket, Type};
 
-    let domain = if addr.is_ipv4() {
-        Domain::IPV4
-    } else {
-        Domain::IPV6
-    };
-    let sock = Socket::new(domain, Type::STREAM, Some(Protocol::TCP))?;
-    sock.set_reuse_address(true)?;
-    sock.set_reuse_port(true)?;
+    let sock = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    #[cfg(unix)]
+    {
+        sock.set_reuse_address(reuse_addr)?;
+        if reuse_port {
+            sock.set_reuse_port(true)?;
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = reuse_addr;
+        if reuse_port {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "SO_REUSEPORT is only supported on Unix",
+            ));
+        }
+    }
     sock.bind(&addr.into())?;
     sock.listen(1024)?;
     Ok(sock.into())
 }
 
-/// `SO_REUSEPORT` is a Unix (Linux/BSD) socket option; unsupported elsewhere.
+/// Build a listening `std::net::TcpListener` bound to `addr` with `SO_REUSEPORT`
+/// (and `SO_REUSEADDR`) set before the bind. See [`bind_listener`].
 ///
 /// # Errors
 ///
-/// Always returns `Unsupported` on non-Unix platforms.
-#[cfg(not(unix))]
-pub fn reuseport_listener(_addr: std::net::SocketAddr) -> io::Result<std::net::TcpListener> {
-    Err(io::Error::new(
-        io::ErrorKind::Unsupported,
-        "SO_REUSEPORT is only supported on Unix",
-    ))
+/// Returns an error if the socket cannot be created, the option set, or the
+/// address bound; `Unsupported` on non-Unix platforms.
+pub fn reuseport_listener(addr: std::net::SocketAddr) -> io::Result<std::net::TcpListener> {
+    bind_listener([addr], true, true)
 }
 
 /// Configure the OS-level socket send/receive buffer sizes (`SO_SNDBUF` /
@@ -0,0 +1,54 @@
+//! Helpers for tests that need a concrete TCP port.
+//!
+//! Binding to port 0 and reading the port back is the usual way to get a free
+//! port, but a test that needs the number *before* binding (to hand it to a
+//! peer, or to rebind it after a restart) has to release the probe listener
+//! first, and anything else on the host may grab the port in between.
+//! [`reserve_port`] keeps the probe listener open until the caller is about to
+//! bind, which shrinks that window to a single call.
+
+use std::io;
+use std::net::{SocketAddr, TcpListener};
+
+/// A loopback port held open by a probe listener until [`release`](Self::release).
+#[derive(Debug)]
+pub struct ReservedPort {
+    listener: TcpListener,
+    addr: SocketAddr,
+}
+
+impl ReservedPort {
+    /// The reserved port number.
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// The reserved loopback address.
+    #[must_use]
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Close the probe listener and return the address, ready to bind.
+    ///
+    /// Call this immediately before binding the real listener. The probe
+    /// never accepted a connection, so the port is not left in `TIME_WAIT`.
+    #[must_use]
+    pub fn release(self) -> SocketAddr {
+        drop(self.listener);
+        self.addr
+    }
+}
+
+/// Bind `127.0.0.1:0` and hold the port the OS picked until the caller
+/// releases it.
+///
+/// # Errors
+///
+/// Returns an error if no loopback port can be bound.
+pub fn reserve_port() -> io::Result<ReservedPort> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    Ok(ReservedPort { listener, addr })
+}
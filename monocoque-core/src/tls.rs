@@ -0,0 +1,458 @@
+//! TLS streams for the socket stack, built on rustls.
+//!
+//! Sockets are generic over their stream, so TLS is a stream wrapper:
+//! [`TlsStream`] runs rustls over any [`AsyncRead`] + [`AsyncWrite`] stream
+//! and is itself one, so a socket constructed over it runs its ZMTP
+//! handshake inside the TLS session. [`client`] and [`server`] perform the
+//! TLS handshake; [`wrap_tcp`] is the common case of a client over a
+//! [`TcpStream`](crate::rt::TcpStream).
+//!
+//! Certificates are verified against the platform's root store by default
+//! ([`TlsConfig::default`]); [`TlsConfig::with_roots`] pins a custom set.
+//! Only the `ring` crypto provider is compiled in. rustls itself is
+//! re-exported as [`rustls`] for building custom configurations.
+//!
+//! Requires the `tls` feature. Reads fill the caller's buffer in place, the
+//! one `unsafe` step (marking the bytes initialized), so like `io` this
+//! module opts back into `unsafe_code` that the crate otherwise denies.
+#![allow(unsafe_code)]
+
+use compio_buf::{BufResult, IoBuf, IoBufMut};
+use compio_io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{
+    ClientConfig, ClientConnection, Connection, RootCertStore, ServerConfig, ServerConnection,
+};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::debug;
+
+use crate::poison::PoisonGuard;
+use crate::rt::TcpStream;
+
+pub use rustls;
+
+/// Ciphertext read from the underlying stream per I/O operation; one
+/// maximum-size TLS record plus header.
+const TLS_READ_SIZE: usize = 16 * 1024 + 256;
+
+/// TLS failures, carried inside the `io::Error` returned by this module.
+///
+/// Recover it with `err.get_ref().and_then(|e| e.downcast_ref::<TlsError>())`.
+#[derive(Debug, Error)]
+pub enum TlsError {
+    /// The name to verify the server certificate against is not a valid DNS
+    /// name or IP address.
+    #[error("invalid TLS server name {0:?}")]
+    InvalidServerName(String),
+
+    /// The peer's certificate failed verification: untrusted issuer, wrong
+    /// name, expired, and so on.
+    #[error("TLS certificate rejected: {0}")]
+    CertificateRejected(rustls::CertificateError),
+
+    /// The certificate or key handed to a configuration was unusable.
+    #[error("invalid TLS configuration: {0}")]
+    Config(rustls::Error),
+
+    /// Any other TLS failure, including a peer alert rejecting our
+    /// certificate.
+    #[error("TLS error: {0}")]
+    Protocol(rustls::Error),
+}
+
+impl From<TlsError> for io::Error {
+    fn from(err: TlsError) -> Self {
+        let kind = match err {
+            TlsError::InvalidServerName(_) | TlsError::Config(_) => io::ErrorKind::InvalidInput,
+            TlsError::CertificateRejected(_) | TlsError::Protocol(_) => io::ErrorKind::InvalidData,
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<rustls::Error> for TlsError {
+    fn from(err: rustls::Error) -> Self {
+        match err {
+            rustls::Error::InvalidCertificate(e) => Self::CertificateRejected(e),
+            e => Self::Protocol(e),
+        }
+    }
+}
+
+/// Client-side TLS settings.
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    config: Arc<ClientConfig>,
+    server_name: Option<String>,
+}
+
+impl TlsConfig {
+    /// Use a prepared rustls client configuration.
+    pub const fn new(config: Arc<ClientConfig>) -> Self {
+        Self {
+            config,
+            server_name: None,
+        }
+    }
+
+    /// Trust servers whose certificate chains to one of the platform's root
+    /// certificates, as [`default`](Self::default) does.
+    ///
+    /// Certificates the platform store holds but rustls cannot parse are
+    /// skipped. If the store cannot be read at all, the config trusts
+    /// nothing and every handshake fails with
+    /// [`TlsError::CertificateRejected`].
+    pub fn with_platform_roots() -> Self {
+        let found = rustls_native_certs::load_native_certs();
+        for err in &found.errors {
+            debug!("[TLS] Platform root certificates: {}", err);
+        }
+        let mut roots = RootCertStore::empty();
+        let (added, skipped) = roots.add_parsable_certificates(found.certs);
+        debug!(
+            "[TLS] Loaded {} platform root certificates ({} skipped)",
+            added, skipped
+        );
+        Self::with_roots(roots)
+    }
+
+    /// Trust servers whose certificate chains to one of `roots`, with
+    /// rustls' default protocol versions and no client certificate.
+    pub fn with_roots(roots: RootCertStore) -> Self {
+        let config =
+            ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .expect("ring supports the default protocol versions")
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+        Self::new(Arc::new(config))
+    }
+
+    /// Verify the server certificate against `name` instead of the host in
+    /// the endpoint, e.g. when connecting to an IP address.
+    #[must_use]
+    pub fn with_server_name(mut self, name: impl Into<String>) -> Self {
+        self.server_name = Some(name.into());
+        self
+    }
+}
+
+impl Default for TlsConfig {
+    /// Verify servers against the platform's root certificates.
+    fn default() -> Self {
+        Self::with_platform_roots()
+    }
+}
+
+/// Server-side TLS settings.
+#[derive(Debug, Clone)]
+pub struct TlsAcceptorConfig {
+    config: Arc<ServerConfig>,
+}
+
+impl TlsAcceptorConfig {
+    /// Use a prepared rustls server configuration.
+    pub const fn new(config: Arc<ServerConfig>) -> Self {
+        Self { config }
+    }
+
+    /// Present `chain` (leaf first) signed by `key`, with rustls' default
+    /// protocol versions and no client certificate verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` wrapping [`TlsError::Config`] if the key is
+    /// unusable or does not match the certificate.
+    pub fn with_single_cert(
+        chain: Vec<CertificateDer<'static>>,
+        key: PrivateKeyDer<'static>,
+    ) -> io::Result<Self> {
+        let config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .expect("ring supports the default protocol versions")
+                .with_no_client_auth()
+                .with_single_cert(chain, key)
+                .map_err(TlsError::Config)?;
+        Ok(Self::new(Arc::new(config)))
+    }
+}
+
+/// A stream carrying TLS over `S`, usable wherever a socket takes a stream.
+///
+/// Created by [`client`], [`server`] or [`wrap_tcp`], always with the TLS
+/// handshake already complete.
+pub struct TlsStream<S> {
+    io: S,
+    conn: Connection,
+    /// Ciphertext read from `io`, reused across reads.
+    incoming: Vec<u8>,
+    /// Ciphertext waiting to be written to `io`, reused across writes.
+    outgoing: Vec<u8>,
+    /// `io` reached EOF.
+    eof: bool,
+    /// A write to `io` was dropped mid-flight, so records rustls had already
+    /// handed over may be lost and the session cannot continue.
+    poisoned: bool,
+}
+
+impl<S> TlsStream<S> {
+    /// The underlying stream.
+    pub const fn get_ref(&self) -> &S {
+        &self.io
+    }
+
+    /// Certificates the peer presented, leaf first.
+    pub fn peer_certificates(&self) -> Option<&[CertificateDer<'static>]> {
+        self.conn.peer_certificates()
+    }
+}
+
+impl<S> TlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn new(io: S, conn: Connection) -> Self {
+        Self {
+            io,
+            conn,
+            incoming: Vec::with_capacity(TLS_READ_SIZE),
+            outgoing: Vec::new(),
+            eof: false,
+            poisoned: false,
+        }
+    }
+
+    /// Exchange handshake records until the session is established.
+    async fn handshake(&mut self) -> io::Result<()> {
+        while self.conn.is_handshaking() {
+            self.write_tls().await?;
+            if self.conn.is_handshaking() && self.conn.wants_read() && !self.read_tls().await? {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "peer closed the connection during the TLS handshake",
+                ));
+            }
+        }
+        // The last flight (the client's Finished) is still queued.
+        self.write_tls().await
+    }
+
+    /// Read one batch of ciphertext from `io` and process it. Returns
+    /// `false` at EOF.
+    async fn read_tls(&mut self) -> io::Result<bool> {
+        self.check_poisoned()?;
+        let mut buf = std::mem::take(&mut self.incoming);
+        buf.clear();
+        // A read dropped mid-flight (a recv timeout or heartbeat wake) takes
+        // the buffer with it; reading into the empty one left behind would
+        // return 0 and look like EOF.
+        buf.reserve(TLS_READ_SIZE);
+        let BufResult(result, buf) = self.io.read(buf).await;
+        self.incoming = buf;
+        if result? == 0 {
+            self.eof = true;
+            return Ok(false);
+        }
+
+        let mut data = &self.incoming[..];
+        let mut failure = None;
+        while !data.is_empty() {
+            self.conn.read_tls(&mut data)?;
+            if let Err(e) = self.conn.process_new_packets() {
+                failure = Some(e);
+                break;
+            }
+        }
+        if let Some(e) = failure {
+            // Best effort: tell the peer why before giving up.
+            let _ = self.write_tls().await;
+            return Err(TlsError::from(e).into());
+        }
+        // Processing can queue records of its own (alerts, tickets, key
+        // updates).
+        self.write_tls().await?;
+        Ok(true)
+    }
+
+    fn check_poisoned(&self) -> io::Result<()> {
+        if self.poisoned {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "TLS stream poisoned by a cancelled write",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Write every record rustls has queued.
+    ///
+    /// Records leave rustls before they reach `io`, so a write that is
+    /// dropped or fails part-way poisons the stream: every later read or
+    /// write fails with `BrokenPipe`.
+    async fn write_tls(&mut self) -> io::Result<()> {
+        self.check_poisoned()?;
+        while self.conn.wants_write() {
+            let mut buf = std::mem::take(&mut self.outgoing);
+            buf.clear();
+            self.conn.write_tls(&mut buf)?;
+            let guard = PoisonGuard::new(&mut self.poisoned);
+            let BufResult(result, buf) = self.io.write_all(buf).await;
+            self.outgoing = buf;
+            result?;
+            guard.disarm();
+        }
+        Ok(())
+    }
+}
+
+impl<S> AsyncRead for TlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    async fn read<B: IoBufMut>(&mut self, mut buf: B) -> BufResult<usize, B> {
+        if buf.buf_capacity() == 0 {
+            return BufResult(Ok(0), buf);
+        }
+        loop {
+            match self.conn.reader().read(buf.ensure_init()) {
+                Ok(n) => {
+                    // SAFETY: `ensure_init` initialized the whole buffer.
+                    unsafe { buf.advance_to(n) };
+                    return BufResult(Ok(n), buf);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                // ZMTP peers close the TCP connection without close_notify.
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    return BufResult(Ok(0), buf);
+                }
+                Err(e) => return BufResult(Err(e), buf),
+            }
+            if self.eof {
+                return BufResult(Ok(0), buf);
+            }
+            if let Err(e) = self.read_tls().await {
+                return BufResult(Err(e), buf);
+            }
+        }
+    }
+}
+
+impl<S> AsyncWrite for TlsStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    async fn write<B: IoBuf>(&mut self, buf: B) -> BufResult<usize, B> {
+        let n = match self.conn.writer().write(buf.as_init()) {
+            Ok(n) => n,
+            Err(e) => return BufResult(Err(e), buf),
+        };
+        match self.write_tls().await {
+            Ok(()) => BufResult(Ok(n), buf),
+            Err(e) => BufResult(Err(e), buf),
+        }
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.write_tls().await?;
+        self.io.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.conn.send_close_notify();
+        self.write_tls().await?;
+        self.io.shutdown().await
+    }
+}
+
+/// Run a client TLS handshake over `stream`, verifying the server
+/// certificate against `server_name` (unless the config overrides it).
+///
+/// # Errors
+///
+/// Fails with a [`TlsError`] inside the `io::Error` if the name is invalid
+/// or the handshake fails, and with the stream's own error on I/O failure.
+pub async fn client<S>(stream: S, config: &TlsConfig, server_name: &str) -> io::Result<TlsStream<S>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let name = config.server_name.as_deref().unwrap_or(server_name);
+    let name = ServerName::try_from(name.to_owned())
+        .map_err(|_| TlsError::InvalidServerName(name.to_owned()))?;
+    client_handshake(stream, config, name).await
+}
+
+/// Run a client TLS handshake over a TCP connection, verifying the server
+/// certificate against `server_name` (unless the config overrides it).
+///
+/// The result is a stream for any socket's `with_options` constructor, e.g.
+/// with the name from an [`Endpoint::Tls`](crate::endpoint::Endpoint::Tls).
+///
+/// # Errors
+///
+/// As for [`client`].
+pub async fn wrap_tcp(
+    stream: TcpStream,
+    server_name: &ServerName<'static>,
+    config: &TlsConfig,
+) -> io::Result<TlsStream<TcpStream>> {
+    let name = match config.server_name.as_deref() {
+        Some(name) => ServerName::try_from(name.to_owned())
+            .map_err(|_| TlsError::InvalidServerName(name.to_owned()))?,
+        None => server_name.clone(),
+    };
+    client_handshake(stream, config, name).await
+}
+
+async fn client_handshake<S>(
+    stream: S,
+    config: &TlsConfig,
+    name: ServerName<'static>,
+) -> io::Result<TlsStream<S>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let conn = ClientConnection::new(Arc::clone(&config.config), name).map_err(TlsError::Config)?;
+    let mut stream = TlsStream::new(stream, conn.into());
+    stream.handshake().await?;
+    debug!("[TLS] Client handshake complete");
+    Ok(stream)
+}
+
+/// Run a server TLS handshake over `stream`.
+///
+/// # Errors
+///
+/// Fails with a [`TlsError`] inside the `io::Error` if the handshake fails,
+/// including when the client rejects the certificate.
+pub async fn server<S>(stream: S, config: &TlsAcceptorConfig) -> io::Result<TlsStream<S>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let conn = ServerConnection::new(Arc::clone(&config.config)).map_err(TlsError::Config)?;
+    let mut stream = TlsStream::new(stream, conn.into());
+    stream.handshake().await?;
+    debug!("[TLS] Server handshake complete");
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_socket_stream<S: AsyncRead + AsyncWrite + Unpin + 'static>() {}
+
+    #[test]
+    fn tls_stream_is_a_socket_stream() {
+        assert_socket_stream::<TlsStream<TcpStream>>();
+    }
+
+    #[test]
+    fn invalid_server_name_is_input_error() {
+        let err: io::Error = TlsError::InvalidServerName("bad name".into()).into();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(err.get_ref().unwrap().downcast_ref::<TlsError>().is_some());
+    }
+}
@@ -0,0 +1,49 @@
+//! Rebinding a port whose last connection is still in `TIME_WAIT`.
+//!
+//! Each round accepts one connection and closes the server side first, which
+//! is what leaves the listener's port in `TIME_WAIT`. With `so_reuseaddr` (the
+//! default) the next round binds the same port straight away.
+
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, LocalRuntime, TcpStream};
+use monocoque_core::testing::reserve_port;
+use std::net::SocketAddr;
+
+const ROUNDS: usize = 50;
+
+/// Bind `addr`, serve one connection, and close the server side first.
+async fn serve_once(addr: SocketAddr, options: &SocketOptions) -> std::io::Result<()> {
+    let listener = rt::bind_listener(addr, options).await?;
+    let client = TcpStream::connect(addr).await?;
+    let (accepted, _) = listener.accept().await?;
+    drop(accepted);
+    drop(client);
+    Ok(())
+}
+
+#[test]
+fn same_port_rebinds_fifty_times() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let addr = reserve_port().unwrap().release();
+        let options = SocketOptions::default();
+        for round in 0..ROUNDS {
+            serve_once(addr, &options)
+                .await
+                .unwrap_or_else(|e| panic!("round {round}: {e}"));
+        }
+    });
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn rebind_without_so_reuseaddr_hits_time_wait() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let addr = reserve_port().unwrap().release();
+        serve_once(addr, &SocketOptions::default()).await.unwrap();
+
+        let err = rt::bind_listener(addr, &SocketOptions::default().with_so_reuseaddr(false))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+    });
+}
@@ -1,7 +1,7 @@
 //! Integration tests for Message builder API
 
 use bytes::Bytes;
-use monocoque_core::message_builder::Message;
+use monocoque_core::message_builder::{Message, MessageBuilder};
 
 #[test]
 fn test_message_builder_basic() {
@@ -95,3 +95,47 @@ fn test_message_builder_chaining() {
 
     assert_eq!(msg.len(), 5);
 }
+
+#[test]
+fn test_builder_frame_static_shares_original_pointer() {
+    static TOPIC: &[u8] = b"weather.eu";
+    static BODY: &[u8] = b"sunny";
+
+    let frames = Message::builder()
+        .frame_static(TOPIC)
+        .frame_static(BODY)
+        .build();
+
+    // Bytes::from_static keeps pointing at the original static data.
+    assert_eq!(frames[0].as_ptr(), TOPIC.as_ptr());
+    assert_eq!(frames[1].as_ptr(), BODY.as_ptr());
+    assert_eq!(frames[0], Bytes::from_static(TOPIC));
+}
+
+#[test]
+fn test_builder_frame_and_frame_copy() {
+    let shared = Bytes::from(vec![7u8; 16]);
+    let scratch = [1u8, 2, 3];
+
+    let frames = Message::builder()
+        .frame(shared.clone())
+        .frame_copy(&scratch)
+        .build();
+
+    // `frame` shares the buffer, `frame_copy` owns a fresh allocation.
+    assert_eq!(frames[0].as_ptr(), shared.as_ptr());
+    assert_ne!(frames[1].as_ptr(), scratch.as_ptr());
+    assert_eq!(&frames[1][..], &scratch[..]);
+}
+
+#[test]
+fn test_builder_conversions() {
+    let builder = MessageBuilder::new().frame_static(b"a").delimiter();
+    assert_eq!(builder.len(), 2);
+
+    let msg: Message = builder.clone().into();
+    assert_eq!(msg.len(), 2);
+
+    let frames: Vec<Bytes> = builder.into();
+    assert_eq!(frames, vec![Bytes::from_static(b"a"), Bytes::new()]);
+}
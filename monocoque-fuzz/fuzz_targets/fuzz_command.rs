@@ -1,20 +1,37 @@
 #![no_main]
 
-//! Fuzz the ZMTP READY command parser.
+//! Fuzz the ZMTP command parsers.
 //!
 //! `parse_ready_command` decodes the READY command body a peer sends during the
 //! handshake: a length-prefixed command name followed by repeated
 //! (name-len, name, 4-byte value-len, value) properties. Every length field is
 //! attacker-controlled, so the parser must reject truncated, oversized, or
 //! malformed property lists without panicking or over-reading; only `Ok`/`Err`.
+//!
+//! After the handshake, every command frame a socket receives goes through
+//! `parse_command` (and XPUB/PUB through the SUBSCRIBE/CANCEL parsers), so the
+//! same bytes are fed to those as well.
 
 use bytes::Bytes;
 use libfuzzer_sys::fuzz_target;
+use monocoque_core::subscription::SubscriptionEvent;
+use monocoque_zmtp::codec::{ZmtpFrame, parse_command};
 use monocoque_zmtp::parse_ready_command;
+use monocoque_zmtp::security::protocol::{parse_error_reason, peer_error};
 
 fuzz_target!(|data: &[u8]| {
     // Raw arbitrary bytes as a command body.
-    let _ = parse_ready_command(&Bytes::copy_from_slice(data));
+    let body = Bytes::copy_from_slice(data);
+    let _ = parse_ready_command(&body);
+    let _ = monocoque_zmtp::parse_ready_metadata(&body);
+    let _ = parse_error_reason(data);
+    let _ = peer_error(data);
+    let _ = SubscriptionEvent::from_command(&body);
+    let _ = SubscriptionEvent::from_bytes(body.clone());
+    let _ = parse_command(&ZmtpFrame {
+        flags: 0x04,
+        payload: body,
+    });
 
     // Also feed a well-formed "READY" prefix so the property loop past the
     // command name is exercised rather than being rejected up front.
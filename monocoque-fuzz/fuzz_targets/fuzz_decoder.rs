@@ -1,56 +1,76 @@
 #![no_main]
 
+//! Feed arbitrary bytes, split into arbitrary reads, into `ZmtpDecoder` and
+//! `ZmtpSession::on_bytes`, the two entry points a bound ROUTER exposes to
+//! an unauthenticated peer.
+//!
+//! Input layout:
+//! - byte 0: read size (1..=256) the rest is split into
+//! - byte 1: decoder limits (bit 0: 64-byte `max_msg_size`, bit 1: 32-byte
+//!   stream threshold, bit 2: 128-byte `max_multipart_size`)
+//! - rest: the wire bytes
+
+use bytes::Bytes;
 use libfuzzer_sys::fuzz_target;
+use monocoque_core::buffer::SegmentedBuffer;
+use monocoque_zmtp::codec::{ZmtpDecoder, ZmtpError};
+use monocoque_zmtp::session::{SocketType, ZmtpSession};
 
 fuzz_target!(|data: &[u8]| {
-    // Test ZMTP greeting parsing - the first 64 bytes of the handshake
-    if data.len() >= 64 {
-        // Try to parse a greeting
-        let _ = parse_greeting(data);
-    }
-
-    // Test command parsing
-    if data.len() >= 2 {
-        let _ = parse_command(data);
-    }
-});
+    let [read_size, limits, wire @ ..] = data else {
+        return;
+    };
+    let read_size = usize::from(*read_size) + 1;
 
-// Simple greeting parser to test - based on ZMTP spec
-fn parse_greeting(data: &[u8]) -> Result<(), &'static str> {
-    if data.len() < 64 {
-        return Err("Too short");
+    let mut decoder = ZmtpDecoder::new();
+    if limits & 0x01 != 0 {
+        decoder.set_max_body_len(Some(64));
     }
-
-    // Check signature
-    if data[0] != 0xff || data[9] != 0x7f {
-        return Err("Invalid signature");
+    if limits & 0x02 != 0 {
+        decoder.set_stream_threshold(Some(32));
     }
-
-    // Check protocol version
-    let major = data[10];
-    let minor = data[11];
-    if major != 3 || (minor != 0 && minor != 1) {
-        return Err("Unsupported version");
+    if limits & 0x04 != 0 {
+        decoder.set_max_multipart_size(Some(128));
     }
 
-    Ok(())
-}
-
-// Simple command parser
-fn parse_command(data: &[u8]) -> Result<(), &'static str> {
-    if data.len() < 2 {
-        return Err("Too short");
-    }
+    let mut handshaking = ZmtpSession::new(SocketType::Router);
+    let mut active = ZmtpSession::new_active(SocketType::Router);
 
-    let flags = data[0];
-    let _size = data[1];
+    let mut buf = SegmentedBuffer::new();
+    let mut failed = false;
+    for chunk in wire.chunks(read_size) {
+        let chunk = Bytes::copy_from_slice(chunk);
+        let _ = handshaking.on_bytes(chunk.clone());
+        let _ = active.on_bytes(chunk.clone());
 
-    // Check if it's a long command
-    if (flags & 0x02) != 0 {
-        if data.len() < 9 {
-            return Err("Long command too short");
+        if failed {
+            continue;
+        }
+        buf.push(chunk);
+        // Every decoded frame consumes at least its 2-byte header, so the
+        // loop must end within this many steps; more means it spins.
+        let mut budget = buf.len() / 2 + 2;
+        loop {
+            assert!(budget > 0, "decoder made no progress");
+            budget -= 1;
+            match decoder.decode(&mut buf) {
+                Ok(Some(_)) => {}
+                Ok(None) => break,
+                Err(ZmtpError::StreamingRequired { .. }) => {
+                    match decoder.begin_stream(&mut buf) {
+                        Ok(Some(_)) => while decoder.take_stream_chunk(&mut buf).is_some() {},
+                        Ok(None) => panic!("StreamingRequired frame could not be streamed"),
+                        Err(_) => {
+                            failed = true;
+                            break;
+                        }
+                    }
+                }
+                Err(_) => {
+                    failed = true;
+                    break;
+                }
+            }
         }
     }
-
-    Ok(())
-}
+});
@@ -11,22 +11,28 @@
 //! - **Protocol safety**: PoisonGuard integration for cancellation safety
 //! - **Reconnection support**: Optional endpoint storage and backoff logic
 
-use bytes::{BufMut, Bytes, BytesMut};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use compio_io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use monocoque_core::buffer::SegmentedBuffer;
+use monocoque_core::config::BufferStats;
 use monocoque_core::endpoint::Endpoint;
+use monocoque_core::error::TrySendError;
 use monocoque_core::io::take_read_buffer;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{HwmPolicy, OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::poison::PoisonGuard;
-use monocoque_core::reconnect::ReconnectState;
+use monocoque_core::reconnect::{ConnectAttempt, ConnectHistory, ConnectOutcome, ReconnectState};
 use monocoque_core::rt::TcpStream;
+use monocoque_core::subscription::SubscriptionEvent;
 use std::fmt;
 use std::io;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{debug, trace, warn};
 
-use crate::codec::ZmtpDecoder;
-use crate::handshake::perform_handshake_with_options;
+use crate::codec::{Command, ZmtpDecoder, ZmtpError, parse_command};
+use crate::diagnostics::DiagnosticsSnapshot;
+use crate::greeting::ZmtpVersion;
+use crate::handshake::{PeerMetadata, handshake_error, perform_handshake_with_options};
+use crate::sequence::Sequencing;
 use crate::session::SocketType;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -60,21 +66,19 @@ pub fn build_ping_frame(ttl_tenths: u16) -> Bytes {
 /// Wire format:
 /// - `0x04` flag byte (COMMAND, short frame)
 /// - 1-byte body length
-/// - Body: `\x04PONG`
-pub fn build_pong_frame() -> Bytes {
-    let body = Bytes::from_static(PONG_CMD);
+/// - Body: `\x04PONG` followed by the PING's context (0-16 bytes), echoed
+///   back unchanged as RFC 37 requires
+pub fn build_pong_frame(context: &[u8]) -> Bytes {
+    let mut body = BytesMut::with_capacity(PONG_CMD.len() + context.len());
+    body.extend_from_slice(PONG_CMD);
+    body.extend_from_slice(context);
+    let body = body.freeze();
     crate::utils::encode_frame(crate::utils::FLAG_COMMAND, &body)
 }
 
-/// Return `true` if the decoded command payload begins with the PING name.
-pub fn is_ping_payload(payload: &[u8]) -> bool {
-    payload.starts_with(PING_CMD) && payload.len().saturating_sub(PING_CMD.len()) <= 18
-}
-
-/// Return `true` if the decoded command payload begins with the PONG name.
-pub fn is_pong_payload(payload: &[u8]) -> bool {
-    payload.starts_with(PONG_CMD) && payload.len().saturating_sub(PONG_CMD.len()) <= 16
-}
+/// Dials a stored endpoint again for [`SocketBase::try_reconnect`].
+pub type Redial<S> =
+    fn(Endpoint, SocketOptions) -> futures::future::LocalBoxFuture<'static, io::Result<S>>;
 
 /// Base socket infrastructure shared by all ZMQ socket types.
 ///
@@ -106,6 +110,18 @@ where
     /// Reconnection state tracker (exponential backoff)
     pub(crate) reconnect: Option<ReconnectState>,
 
+    /// With `immediate`, when the backoff after a failed redial ends; the
+    /// next attempt is not made before then.
+    pub(crate) redial_after: Option<Instant>,
+
+    /// Opens a fresh stream to `endpoint`; `None` for stream types that
+    /// cannot be dialed again.
+    pub(crate) redial: Option<Redial<S>>,
+
+    /// Whether `recv`/`send` reconnect on their own after the connection is
+    /// lost (sockets created with `connect_with_reconnect`).
+    pub(crate) auto_reconnect: bool,
+
     /// ZMTP frame decoder
     pub(crate) decoder: ZmtpDecoder,
 
@@ -118,25 +134,41 @@ where
     /// Reusable write buffer for outgoing data
     pub(crate) write_buf: BytesMut,
 
-    /// Reusable iovec scratch for vectored writes (header/body entries),
-    /// kept across calls so `send_vectored` allocates nothing on the hot path.
+    /// Reusable iovec scratch for vectored writes, kept across calls so
+    /// `write_vectored` allocates nothing on the hot path.
     pub(crate) iov: Vec<Bytes>,
 
+    /// Reusable scratch where `send_vectored` lays out each frame's header and
+    /// body before handing them to `write_vectored`.
+    frame_iov: Vec<Bytes>,
+
     /// Send buffer for message batching
     pub(crate) send_buffer: BytesMut,
 
     /// Socket options (timeouts, limits, identity, buffer sizes)
     pub(crate) options: SocketOptions,
 
-    /// Last connected/bound endpoint
+    /// Last connected/bound endpoint, in its canonical `Display` form
     pub(crate) last_endpoint: Option<String>,
 
     /// Connection health flag (true if I/O was cancelled mid-operation)
     pub(crate) is_poisoned: bool,
 
-    /// Number of messages currently buffered (for HWM enforcement)
+    /// Number of messages currently buffered (for HWM enforcement), whether
+    /// queued by `send_buffered` or held by `coalesce_window`
     pub(crate) buffered_messages: usize,
 
+    /// When the first message of the current `coalesce_window` batch was
+    /// buffered; `None` while no windowed send is waiting in `send_buffer`.
+    pub(crate) coalesce_started: Option<Instant>,
+
+    /// Sequence stamps and duplicate filter for the `dedupe_window` option;
+    /// `None` when it is off.
+    pub(crate) sequencing: Option<Box<Sequencing>>,
+
+    /// The current connection negotiated sequence frames.
+    pub(crate) sequenced: bool,
+
     // ── Heartbeat state (ZMTP PING/PONG, RFC 23) ──────────────────────────
     //
     // Heartbeating keeps idle connections alive and detects dead peers.
@@ -148,8 +180,8 @@ where
     //      (defaults to `heartbeat_ivl` when not set).  If it does not,
     //      `awaiting_pong` stays true and the next check can close the conn.
     //
-    // Stub note: PING sending is wired into `check_heartbeat()`.
-    // Timeout-based disconnection is noted below and left for a future PR.
+    //   4. `read_raw` wakes at each of these deadlines, so an idle
+    //      connection still sends PINGs and a silent peer is dropped.
     /// Instant of the last received frame on this connection.
     ///
     /// `None` before the first frame is received after the handshake, or
@@ -169,6 +201,41 @@ where
 
     /// Post-handshake CURVE cipher, if CURVE security is active.
     pub(crate) curve_cipher: Option<crate::security::curve::CurveMessageCipher>,
+
+    /// ZMTP revision negotiated during the handshake.
+    ///
+    /// Heartbeat PINGs are only sent when the peer speaks 3.1; a 3.0 peer
+    /// does not know the command.
+    pub(crate) zmtp_version: ZmtpVersion,
+
+    /// Properties the peer sent in its READY during the latest handshake.
+    pub(crate) peer_metadata: PeerMetadata,
+
+    /// Why the latest connection ended; kept across reconnects.
+    pub(crate) close_reason: Option<CloseReason>,
+
+    /// Outcome of the latest connect and reconnect attempts.
+    pub(crate) connect_history: ConnectHistory,
+
+    /// Exponentially weighted average of recent flush sizes (bytes).
+    ///
+    /// Drives the post-flush shrink of `send_buffer` / `write_buf`: a buffer
+    /// is only released back to `write_buffer_size` once recent flushes are
+    /// small relative to its capacity, so a steady stream of large writes
+    /// keeps its allocation while a one-off burst does not.
+    pub(crate) avg_flush_bytes: usize,
+}
+
+/// Build a frame decoder honoring the size options in `options`.
+fn decoder_for(options: &SocketOptions) -> ZmtpDecoder {
+    let mut decoder = ZmtpDecoder::with_size_limits(options);
+    decoder.set_stream_threshold(options.stream_threshold);
+    decoder
+}
+
+/// Sequencing state for the `dedupe_window` option, if it is on.
+fn sequencing_for(options: &SocketOptions) -> Option<Box<Sequencing>> {
+    (options.dedupe_window > 0).then(|| Box::new(Sequencing::new(options.dedupe_window)))
 }
 
 pub fn append_zmtp_cmd_frame(buf: &mut BytesMut, body: &[u8]) {
@@ -193,6 +260,173 @@ pub enum FrameResult {
     NeedMore,
 }
 
+/// Why the latest connection of a socket ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The peer closed its side in order: the read returned end-of-file.
+    Eof,
+    /// The connection was reset or broken (`ECONNRESET`, `ECONNABORTED`,
+    /// `EPIPE`).
+    Reset,
+    /// The peer stopped answering heartbeat PINGs.
+    Timeout,
+    /// The peer sent a malformed frame, or an ERROR command.
+    ProtocolError,
+}
+
+/// Whether `err` reports a reset or broken connection ([`CloseReason::Reset`]).
+fn is_reset(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe
+    )
+}
+
+/// Weight of the newest sample in the flush-size EWMA, as a right shift:
+/// each flush moves the average 1/8 of the way towards its own size.
+const FLUSH_EWMA_SHIFT: u32 = 3;
+
+/// Why [`write_all_within`] or [`write_all_nonblocking`] stopped before
+/// writing everything.
+enum WriteStop {
+    /// Gave up between writes, with no write in flight: the deadline passed,
+    /// or in non-blocking mode a short write showed the stream is full.
+    Incomplete,
+    /// The deadline passed while a write was in flight and the write was
+    /// dropped. On a completion-based backend (io_uring) the kernel may still
+    /// have sent some of its bytes, so the stream's position is unknown.
+    Cancelled,
+    /// The stream reported an error.
+    Failed(io::Error),
+}
+
+/// Write all of `data` to `stream` within `dur`, reporting how many bytes the
+/// stream is known to have accepted when it stops early.
+async fn write_all_within<S>(
+    stream: &mut S,
+    data: &Bytes,
+    dur: Duration,
+) -> (usize, Result<(), WriteStop>)
+where
+    S: AsyncWrite + Unpin,
+{
+    use compio_buf::BufResult;
+    use monocoque_core::rt::timeout;
+
+    let deadline = Instant::now() + dur;
+    let mut written = 0;
+    while written < data.len() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return (written, Err(WriteStop::Incomplete));
+        }
+        match timeout(remaining, stream.write(data.slice(written..))).await {
+            Ok(BufResult(Ok(0), _)) => {
+                return (
+                    written,
+                    Err(WriteStop::Failed(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "stream accepted no bytes",
+                    ))),
+                );
+            }
+            Ok(BufResult(Ok(n), _)) => written += n,
+            Ok(BufResult(Err(e), _)) if e.kind() == io::ErrorKind::Interrupted => {}
+            Ok(BufResult(Err(e), _)) => return (written, Err(WriteStop::Failed(e))),
+            Err(_) => return (written, Err(WriteStop::Cancelled)),
+        }
+    }
+    (written, Ok(()))
+}
+
+/// Poll `fut` for one turn of the runtime: once, then once more after
+/// yielding, so a completion-based driver gets to submit the operation and
+/// reap what the kernel finished inline. `None` if it is still pending.
+async fn within_one_turn<F: std::future::Future>(fut: F) -> Option<F::Output> {
+    use std::task::Poll;
+
+    let mut fut = std::pin::pin!(fut);
+    let mut yielded = false;
+    std::future::poll_fn(|cx| match fut.as_mut().poll(cx) {
+        Poll::Ready(out) => Poll::Ready(Some(out)),
+        Poll::Pending if yielded => Poll::Ready(None),
+        Poll::Pending => {
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+    .await
+}
+
+/// Write as much of `data` to `stream` as it takes without waiting,
+/// reporting how many bytes it is known to have accepted.
+///
+/// Each write gets one runtime turn. A short write means the stream is full,
+/// so the loop stops there ([`WriteStop::Incomplete`]) rather than issue a
+/// write that would wait. A write that does not finish within its turn is
+/// dropped ([`WriteStop::Cancelled`]).
+async fn write_all_nonblocking<S>(stream: &mut S, data: &Bytes) -> (usize, Result<(), WriteStop>)
+where
+    S: AsyncWrite + Unpin,
+{
+    use compio_buf::BufResult;
+
+    let mut written = 0;
+    while written < data.len() {
+        match within_one_turn(stream.write(data.slice(written..))).await {
+            None => return (written, Err(WriteStop::Cancelled)),
+            Some(BufResult(Ok(0), _)) => {
+                return (
+                    written,
+                    Err(WriteStop::Failed(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "stream accepted no bytes",
+                    ))),
+                );
+            }
+            Some(BufResult(Ok(n), _)) => {
+                written += n;
+                if written < data.len() {
+                    return (written, Err(WriteStop::Incomplete));
+                }
+            }
+            Some(BufResult(Err(e), _)) if e.kind() == io::ErrorKind::Interrupted => {}
+            Some(BufResult(Err(e), _)) => return (written, Err(WriteStop::Failed(e))),
+        }
+    }
+    (written, Ok(()))
+}
+
+fn would_block() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::WouldBlock,
+        "Socket is in non-blocking mode and cannot send immediately",
+    )
+}
+
+/// Reallocate an idle write buffer back to `base` bytes if a past burst left
+/// it oversized.
+///
+/// The buffer is shrunk only when its capacity exceeds `base × factor` and the
+/// recent average flush would also fit `factor` times over, i.e. the capacity
+/// is no longer earning its keep. A `factor` of 0 disables shrinking.
+fn shrink_if_oversized(buf: &mut BytesMut, base: usize, factor: usize, avg_flush: usize) {
+    if factor == 0 || !buf.is_empty() {
+        return;
+    }
+    let cap = buf.capacity();
+    if cap > base.saturating_mul(factor) && avg_flush.saturating_mul(factor) <= cap {
+        trace!(
+            "[SocketBase] Shrinking write buffer {} -> {} bytes (avg flush {})",
+            cap, base, avg_flush
+        );
+        *buf = BytesMut::with_capacity(base);
+    }
+}
+
 /// Apply equal jitter to a reconnect backoff delay, spreading the actual sleep
 /// uniformly across `[delay/2, delay]`.
 ///
@@ -223,15 +457,15 @@ where
     /// Buffer sizes are taken from `options.read_buffer_size` and `options.write_buffer_size`.
     pub fn new(stream: S, _socket_type: SocketType, options: SocketOptions) -> Self {
         let write_capacity = options.write_buffer_size;
-        let decoder = if let Some(max) = options.max_msg_size {
-            ZmtpDecoder::with_max_frame_size(max)
-        } else {
-            ZmtpDecoder::new()
-        };
+        let decoder = decoder_for(&options);
+        let sequencing = sequencing_for(&options);
         Self {
             stream: Some(stream),
             endpoint: None,
             reconnect: None,
+            redial_after: None,
+            redial: None,
+            auto_reconnect: false,
             decoder,
             recv: SegmentedBuffer::new(),
             // Lazily allocated on the first read (matches the old arena, which
@@ -239,16 +473,25 @@ where
             read_buf: BytesMut::new(),
             write_buf: BytesMut::with_capacity(write_capacity),
             iov: Vec::new(),
+            frame_iov: Vec::new(),
             send_buffer: BytesMut::with_capacity(write_capacity),
             options,
             last_endpoint: None,
             is_poisoned: false,
             buffered_messages: 0,
+            coalesce_started: None,
+            sequencing,
+            sequenced: false,
             // Heartbeat fields  -  initialised to idle state
             last_recv_instant: None,
             ping_sent_at: None,
             awaiting_pong: false,
             curve_cipher: None,
+            zmtp_version: ZmtpVersion::LOCAL,
+            peer_metadata: PeerMetadata::new(),
+            close_reason: None,
+            connect_history: ConnectHistory::default(),
+            avg_flush_bytes: 0,
         }
     }
 
@@ -266,15 +509,15 @@ where
     ) -> Self {
         let endpoint_str = endpoint.to_string();
         let write_capacity = options.write_buffer_size;
-        let decoder = if let Some(max) = options.max_msg_size {
-            ZmtpDecoder::with_max_frame_size(max)
-        } else {
-            ZmtpDecoder::new()
-        };
+        let decoder = decoder_for(&options);
+        let sequencing = sequencing_for(&options);
         Self {
             stream: Some(stream),
             endpoint: Some(endpoint),
             reconnect: Some(ReconnectState::new(&options)),
+            redial_after: None,
+            redial: None,
+            auto_reconnect: false,
             decoder,
             recv: SegmentedBuffer::new(),
             // Lazily allocated on the first read (matches the old arena, which
@@ -282,16 +525,25 @@ where
             read_buf: BytesMut::new(),
             write_buf: BytesMut::with_capacity(write_capacity),
             iov: Vec::new(),
+            frame_iov: Vec::new(),
             send_buffer: BytesMut::with_capacity(write_capacity),
             options,
             last_endpoint: Some(endpoint_str),
             is_poisoned: false,
             buffered_messages: 0,
+            coalesce_started: None,
+            sequencing,
+            sequenced: false,
             // Heartbeat fields  -  initialised to idle state
             last_recv_instant: None,
             ping_sent_at: None,
             awaiting_pong: false,
             curve_cipher: None,
+            zmtp_version: ZmtpVersion::LOCAL,
+            peer_metadata: PeerMetadata::new(),
+            close_reason: None,
+            connect_history: ConnectHistory::default(),
+            avg_flush_bytes: 0,
         }
     }
 
@@ -301,12 +553,174 @@ where
         self.stream.is_some()
     }
 
+    /// Properties the peer sent in its READY command.
+    ///
+    /// Refreshed by every reconnect; empty for a socket that never completed
+    /// a handshake itself.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &PeerMetadata {
+        &self.peer_metadata
+    }
+
+    /// ZMTP revision agreed with the peer during the latest handshake.
+    ///
+    /// The lower of both sides' revisions; [`ZmtpVersion::LOCAL`] before the
+    /// first handshake.
+    #[inline]
+    pub const fn negotiated_version(&self) -> ZmtpVersion {
+        self.zmtp_version
+    }
+
+    /// Why the latest connection ended, or `None` while the first one is
+    /// still open.
+    ///
+    /// Not cleared by a reconnect, so it still explains the previous
+    /// connection once a new one is up.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<CloseReason> {
+        self.close_reason
+    }
+
+    /// The latest connection attempts, oldest first.
+    ///
+    /// Holds the initial connect and every reconnect attempt since, up to
+    /// [`ConnectHistory::DEFAULT_CAPACITY`] entries.
+    #[inline]
+    pub const fn connect_history(&self) -> &ConnectHistory {
+        &self.connect_history
+    }
+
+    /// Record the initial connect, which began at `started` and has just
+    /// completed its handshake.
+    pub(crate) fn record_initial_connect(&mut self, started_at: SystemTime, started: Instant) {
+        if let Some(endpoint) = self.endpoint.clone() {
+            self.connect_history.record(ConnectAttempt {
+                endpoint,
+                started_at,
+                outcome: ConnectOutcome::Ok(started.elapsed()),
+                attempt_number: 0,
+            });
+        }
+    }
+
+    /// Human-readable dump of the socket's state for diagnostics: endpoint,
+    /// connection state, options that differ from the defaults, buffer usage
+    /// and connection history.
+    ///
+    /// The format is meant for logs and may change between releases.
+    pub fn diagnostics(&self) -> String {
+        use fmt::Write as _;
+
+        let mut out = String::new();
+        let state = match (self.is_connected(), self.is_poisoned) {
+            (_, true) => "poisoned",
+            (true, false) => "connected",
+            (false, false) => "disconnected",
+        };
+        let _ = writeln!(
+            out,
+            "endpoint: {}",
+            self.last_endpoint.as_deref().unwrap_or("-")
+        );
+        let _ = writeln!(
+            out,
+            "state: {state} (zmtp {:?}, reconnect attempt {})",
+            self.zmtp_version,
+            self.reconnect_attempt()
+        );
+        let _ = writeln!(out, "options (non-default):");
+        for diff in SocketOptions::default().diff(&self.options) {
+            let _ = writeln!(out, "  {diff:?}");
+        }
+        let _ = writeln!(
+            out,
+            "buffered: {} messages, {} bytes; {:?}",
+            self.buffered_messages,
+            self.buffered_bytes(),
+            self.buffer_stats()
+        );
+        let _ = writeln!(out, "connect history:");
+        for attempt in self.connect_history.iter() {
+            let started_ms = attempt
+                .started_at
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis());
+            let _ = match &attempt.outcome {
+                ConnectOutcome::Ok(took) => writeln!(
+                    out,
+                    "  #{} {} at {started_ms}ms: ok in {took:?}",
+                    attempt.attempt_number, attempt.endpoint
+                ),
+                ConnectOutcome::Err { kind, message } => writeln!(
+                    out,
+                    "  #{} {} at {started_ms}ms: {kind:?}: {message}",
+                    attempt.attempt_number, attempt.endpoint
+                ),
+            };
+        }
+        out
+    }
+
+    /// Snapshot of everything [`diagnostics`](Self::diagnostics) reports, as
+    /// structured data.
+    pub fn snapshot(&self, socket_type: SocketType) -> DiagnosticsSnapshot {
+        let state = match (self.is_connected(), self.is_poisoned) {
+            (_, true) => "poisoned",
+            (true, false) => "connected",
+            (false, false) => "disconnected",
+        };
+        DiagnosticsSnapshot {
+            schema_version: crate::diagnostics::SCHEMA_VERSION,
+            socket_type: socket_type.as_str().to_owned(),
+            endpoint: self.last_endpoint.clone(),
+            state: state.to_owned(),
+            zmtp_version: self.zmtp_version.to_string(),
+            reconnect_attempt: self.reconnect_attempt(),
+            options: SocketOptions::default()
+                .diff(&self.options)
+                .iter()
+                .map(|diff| format!("{diff:?}"))
+                .collect(),
+            buffered_messages: self.buffered_messages,
+            buffered_bytes: self.buffered_bytes(),
+            buffers: self.buffer_stats().into(),
+            duplicates_dropped: self.duplicates_dropped(),
+            protocol_violations: crate::codec::protocol_violations(),
+            connect_history: self.connect_history.iter().map(Into::into).collect(),
+            peers: Vec::new(),
+            queues: Vec::new(),
+            hwm_dropped: 0,
+        }
+    }
+
+    /// Number of reconnection attempts made since the connection was lost.
+    ///
+    /// Returns 0 when connected or when reconnection is not configured.
+    #[inline]
+    pub const fn reconnect_attempt(&self) -> u32 {
+        match &self.reconnect {
+            Some(reconnect) => reconnect.attempt(),
+            None => 0,
+        }
+    }
+
     /// Check if the socket is poisoned (I/O was cancelled mid-operation).
     #[inline]
     pub const fn is_poisoned(&self) -> bool {
         self.is_poisoned
     }
 
+    /// Whether the next `send`/`recv` should reconnect before doing any I/O:
+    /// the socket is poisoned, `auto_recover` is set, and it has an endpoint
+    /// it can redial. Without one the operation fails with `BrokenPipe`.
+    #[inline]
+    pub(crate) const fn should_auto_recover(&self) -> bool {
+        self.is_poisoned
+            && self.options.auto_recover
+            && self.endpoint.is_some()
+            && self.redial.is_some()
+    }
+
     /// Get the number of buffered messages.
     #[inline]
     pub const fn buffered_messages(&self) -> usize {
@@ -319,10 +733,41 @@ where
         self.send_buffer.len()
     }
 
-    /// Update live socket options and keep derived decoder state in sync.
-    pub(crate) fn set_options(&mut self, options: SocketOptions) {
-        self.decoder.set_max_body_len(options.max_msg_size);
-        self.options = options;
+    /// Snapshot the current userspace buffer capacities and the decoder's
+    /// zero-copy and copied frame counts.
+    pub fn buffer_stats(&self) -> BufferStats {
+        BufferStats {
+            send_buffer_capacity: self.send_buffer.capacity(),
+            write_buffer_capacity: self.write_buf.capacity(),
+            read_buffer_capacity: self.read_buf.capacity(),
+            avg_flush_bytes: self.avg_flush_bytes,
+            recv_queued_bytes: self.recv.len(),
+            staging_capacity: self.decoder.staging_capacity(),
+            zero_copy_frames: self.decoder.zero_copy_frames(),
+            copied_frames: self.decoder.copied_frames(),
+        }
+    }
+
+    /// Fold a completed flush of `n` bytes into the flush-size average.
+    #[inline]
+    fn record_flush(&mut self, n: usize) {
+        self.avg_flush_bytes = self.avg_flush_bytes - (self.avg_flush_bytes >> FLUSH_EWMA_SHIFT)
+            + (n >> FLUSH_EWMA_SHIFT);
+    }
+
+    /// Update live socket options (see [`SocketOptions::update`]) and keep
+    /// the decoder's cached frame limits in sync.
+    pub(crate) fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        let diffs = self.options.update(f)?;
+        self.decoder.set_max_body_len(self.options.max_msg_size);
+        self.decoder
+            .set_max_multipart_size(self.options.max_multipart_size);
+        self.decoder
+            .set_stream_threshold(self.options.stream_threshold);
+        Ok(diffs)
     }
 
     /// Check if send HWM has been reached.
@@ -331,6 +776,72 @@ where
         self.options.send_hwm != 0 && self.buffered_messages >= self.options.send_hwm
     }
 
+    /// Refuse to queue a message while disconnected when `immediate` is set.
+    ///
+    /// Without `immediate` a message sent between connections waits in the
+    /// send buffer, or in a reconnect loop, for the next one. With it the
+    /// send fails with `NotConnected`, so nothing piles up for a dead peer.
+    pub(crate) fn check_immediate(&self) -> io::Result<()> {
+        if self.options.immediate && self.stream.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "No connection established (immediate mode)",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Refuse a send's redial while the backoff after a failed one is still
+    /// running, when `immediate` is set.
+    ///
+    /// A send on an `immediate` socket redials straight away, but fails with
+    /// `NotConnected` rather than sit out the backoff holding its message.
+    pub(crate) fn check_redial_backoff(&self) -> io::Result<()> {
+        if self.options.immediate && self.redial_after.is_some_and(|at| Instant::now() < at) {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "No connection established (immediate mode): reconnect backing off",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Report a send's failed redial as `NotConnected` when `immediate` is
+    /// set; errors another attempt cannot fix (no endpoint, invalid routing
+    /// id) and sockets without `immediate` keep the original error.
+    pub(crate) fn redial_error(&self, err: io::Error) -> io::Error {
+        if !self.options.immediate
+            || matches!(
+                err.kind(),
+                io::ErrorKind::Unsupported | io::ErrorKind::InvalidInput
+            )
+        {
+            return err;
+        }
+        io::Error::new(
+            io::ErrorKind::NotConnected,
+            format!("No connection established (immediate mode): {err}"),
+        )
+    }
+
+    /// Check whether `try_send` may queue `msg` now, handing it back inside
+    /// the error if not.
+    pub(crate) fn try_send_ready(&self, msg: Vec<Bytes>) -> Result<Vec<Bytes>, TrySendError> {
+        if self.is_poisoned {
+            return Err(TrySendError::Io(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "Socket poisoned by cancelled I/O - reconnect required",
+            )));
+        }
+        if self.hwm_reached() {
+            return Err(TrySendError::Full(msg));
+        }
+        if self.stream.is_none() {
+            return Err(TrySendError::WouldBlock(msg));
+        }
+        Ok(msg)
+    }
+
     /// Get the endpoint this socket is connected/bound to, if any.
     ///
     /// Returns `None` if the socket was created from a raw stream without
@@ -344,7 +855,10 @@ where
         self.endpoint.as_ref()
     }
 
-    /// Get the last endpoint as a string.
+    /// Get the last endpoint as a string, such as `tcp://127.0.0.1:5555`.
+    ///
+    /// This is the endpoint's [`Display`](std::fmt::Display) form, rendered
+    /// once when it is stored.
     ///
     /// # ZeroMQ Compatibility
     ///
@@ -410,13 +924,34 @@ where
     /// Record that a frame was received right now.
     ///
     /// Call this every time a complete ZMTP frame is read from the wire so that
-    /// the heartbeat idle timer is reset.  When heartbeating is disabled
+    /// the heartbeat idle timer is reset. Any traffic also settles an
+    /// outstanding PING, as in libzmq.  When heartbeating is disabled
     /// (`options.heartbeat_ivl` is `None`) this is a no-op.
     #[inline]
     pub fn note_recv(&mut self) {
         if self.options.heartbeat_ivl.is_some() {
             self.last_recv_instant = Some(Instant::now());
+            self.awaiting_pong = false;
+            self.ping_sent_at = None;
+        }
+    }
+
+    /// When [`check_heartbeat`](Self::check_heartbeat) next has work to do:
+    /// the PONG deadline while a PING is outstanding, otherwise the moment
+    /// the connection will have been idle for `heartbeat_ivl`.
+    ///
+    /// `None` when heartbeating is disabled or the peer speaks ZMTP 3.0.
+    fn next_heartbeat_at(&self) -> Option<Instant> {
+        let ivl = self.options.heartbeat_ivl?;
+        if !self.zmtp_version.has_v31_commands() {
+            return None;
         }
+        Some(match self.ping_sent_at.filter(|_| self.awaiting_pong) {
+            Some(ping_at) => ping_at + self.options.heartbeat_timeout.unwrap_or(ivl),
+            None => self
+                .last_recv_instant
+                .map_or_else(Instant::now, |at| at + ivl),
+        })
     }
 
     /// Record that a PONG was received, clearing the outstanding-PING flag.
@@ -432,6 +967,18 @@ where
         }
     }
 
+    /// Count body bytes of a streamed frame as proof the peer is alive.
+    ///
+    /// A PONG queued behind a long frame cannot arrive until the frame ends,
+    /// so an outstanding PING is settled by the frame's own traffic instead
+    /// of timing out mid-transfer (libzmq likewise cancels its heartbeat
+    /// timeout on any inbound traffic).
+    #[inline]
+    pub(crate) fn note_stream_progress(&mut self) {
+        self.awaiting_pong = false;
+        self.ping_sent_at = None;
+    }
+
     /// Check whether a PING should be sent and whether a pending PONG has
     /// timed out.
     ///
@@ -442,13 +989,17 @@ where
     /// # Return value
     ///
     /// - `Ok(true)`   -  a PING was appended to `send_buffer`; caller must flush.
-    /// - `Ok(false)`  -  nothing to do or heartbeat is disabled.
+    /// - `Ok(false)`  -  nothing to do, heartbeat is disabled, or the peer only
+    ///   speaks ZMTP 3.0 (which has no PING command).
     /// - `Err(e)`     -  a pending PONG timed out; connection should be closed.
     ///   Callers propagate this with `?` so the error surfaces to the application.
     pub fn check_heartbeat(&mut self) -> io::Result<bool> {
         let Some(ivl) = self.options.heartbeat_ivl else {
             return Ok(false);
         };
+        if !self.zmtp_version.has_v31_commands() {
+            return Ok(false);
+        }
 
         let now = Instant::now();
 
@@ -456,13 +1007,14 @@ where
         if self.awaiting_pong {
             if let Some(ping_at) = self.ping_sent_at {
                 let timeout = self.options.heartbeat_timeout.unwrap_or(ivl); // default to ivl when not set
-                if now.duration_since(ping_at) > timeout {
+                if now.duration_since(ping_at) >= timeout {
                     warn!(
                         "[SocketBase] Heartbeat PONG not received within {:?}  -  peer considered dead",
                         timeout
                     );
                     // Mark disconnected
                     self.stream = None;
+                    self.close_reason = Some(CloseReason::Timeout);
                     self.awaiting_pong = false;
                     self.ping_sent_at = None;
                     return Err(io::Error::new(
@@ -515,6 +1067,10 @@ where
     /// - `Err(e)` on I/O error
     ///
     /// On EOF, sets `stream = None` to mark disconnection.
+    ///
+    /// With heartbeating enabled the read also wakes to send PINGs while the
+    /// connection is idle, and fails with `TimedOut` (dropping the stream)
+    /// when the peer stays silent past `heartbeat_timeout` after a PING.
     pub(crate) async fn read_raw(&mut self) -> io::Result<usize> {
         // Ensure we're connected
         if self.stream.is_none() {
@@ -524,16 +1080,43 @@ where
             ));
         }
 
-        // Read from stream
-        use compio_buf::BufResult;
+        // A reply may depend on sends still held by the coalescing window.
+        if self.coalesce_started.is_some() {
+            self.flush_coalesced().await?;
+        }
 
-        // Reject the non-blocking / zero-timeout case before taking a buffer.
-        if matches!(self.options.recv_timeout, Some(dur) if dur.is_zero()) {
-            return Err(io::Error::new(
-                io::ErrorKind::WouldBlock,
-                "Socket is in non-blocking mode and no data is available",
-            ));
+        let recv_timeout = self.options.recv_timeout;
+        let recv_deadline = recv_timeout
+            .filter(|dur| !dur.is_zero())
+            .map(|dur| Instant::now() + dur);
+        loop {
+            let heartbeat_at = self.next_heartbeat_at();
+            let wake_at = match (recv_deadline, heartbeat_at) {
+                (Some(recv), Some(heartbeat)) => Some(recv.min(heartbeat)),
+                (recv, heartbeat) => recv.or(heartbeat),
+            };
+            if let Some(n) = self.read_raw_until(wake_at).await? {
+                return Ok(n);
+            }
+            if recv_deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!(
+                        "Receive operation timed out after {:?}",
+                        recv_timeout.unwrap_or_default()
+                    ),
+                ));
+            }
+            if self.check_heartbeat()? {
+                self.flush_send_buffer().await?;
+            }
         }
+    }
+
+    /// One read for [`read_raw`](Self::read_raw), giving up with `Ok(None)`
+    /// at `wake_at`.
+    async fn read_raw_until(&mut self, wake_at: Option<Instant>) -> io::Result<Option<usize>> {
+        use compio_buf::BufResult;
 
         // SAFETY: `buf` is passed straight to `read` below; on every path that
         // exposes bytes it is first truncated to `n`, and the error/EOF paths
@@ -546,30 +1129,46 @@ where
             .as_mut()
             .expect("BUG: stream must be Some  -  checked is_none() above");
 
-        // Apply recv timeout
-        let BufResult(result, mut buf) = match self.options.recv_timeout {
-            None => AsyncRead::read(stream, buf).await,
-            Some(dur) => {
-                use monocoque_core::rt::timeout;
-                match timeout(dur, AsyncRead::read(stream, buf)).await {
-                    Ok(result) => result,
-                    Err(_) => {
+        // Non-blocking mode still reads whatever has already arrived; it only
+        // gives up if the read cannot complete within one turn of the runtime.
+        let BufResult(result, mut buf) = match (self.options.recv_timeout, wake_at) {
+            (Some(dur), _) if dur.is_zero() => {
+                match within_one_turn(AsyncRead::read(stream, buf)).await {
+                    Some(result) => result,
+                    None => {
                         return Err(io::Error::new(
-                            io::ErrorKind::TimedOut,
-                            format!("Receive operation timed out after {:?}", dur),
+                            io::ErrorKind::WouldBlock,
+                            "Socket is in non-blocking mode and no data is available",
                         ));
                     }
                 }
             }
+            (_, None) => AsyncRead::read(stream, buf).await,
+            (_, Some(wake_at)) => {
+                use monocoque_core::rt::timeout;
+                let dur = wake_at.saturating_duration_since(Instant::now());
+                match timeout(dur, AsyncRead::read(stream, buf)).await {
+                    Ok(result) => result,
+                    Err(_) => return Ok(None),
+                }
+            }
         };
 
-        let n = result?;
+        let n = result.inspect_err(|e| {
+            if is_reset(e) {
+                // Reset - the stream is as dead as after EOF
+                trace!("[SocketBase] Connection reset by peer");
+                self.stream = None;
+                self.close_reason = Some(CloseReason::Reset);
+            }
+        })?;
 
         if n == 0 {
             // EOF - mark stream as disconnected
             trace!("[SocketBase] Connection closed (EOF)");
             self.stream = None;
-            return Ok(0);
+            self.close_reason = Some(CloseReason::Eof);
+            return Ok(Some(0));
         }
 
         // Push bytes into recv buffer (trim to what was actually read).
@@ -587,7 +1186,7 @@ where
         // Update heartbeat idle timer: data was received so we are not idle
         self.note_recv();
 
-        Ok(n)
+        Ok(Some(n))
     }
 
     /// Write buffered data from `send_buffer` to the stream.
@@ -598,6 +1197,28 @@ where
     /// Returns `Ok(())` on success, `Err(e)` on failure. On write failure,
     /// sets `stream = None` to mark disconnection.
     pub(crate) async fn flush_send_buffer(&mut self) -> io::Result<()> {
+        self.flush_send_buffer_within(self.options.send_timeout)
+            .await
+    }
+
+    /// Flush `send_buffer`, bounding the write by `timeout` instead of the
+    /// configured `send_timeout`.
+    ///
+    /// If the deadline passes between writes, whatever the stream has not yet
+    /// accepted is left at the front of `send_buffer` and the socket stays
+    /// usable, so a later flush resumes exactly where this one stopped. If it
+    /// passes with a write in flight, that write is dropped, and on io_uring
+    /// the kernel may still complete it; the socket is then poisoned rather
+    /// than risk repeating bytes the peer already has.
+    ///
+    /// A zero `timeout` writes only what the stream takes straight away and
+    /// returns `WouldBlock` for the rest: after a short write the unsent tail
+    /// stays queued, while a write that could not finish within one runtime
+    /// turn is dropped and poisons the socket as above.
+    pub(crate) async fn flush_send_buffer_within(
+        &mut self,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
         if self.send_buffer.is_empty() {
             return Ok(());
         }
@@ -616,42 +1237,84 @@ where
             .as_mut()
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "Socket not connected"))?;
 
-        if self.options.send_timeout.is_some_and(|dur| dur.is_zero()) {
-            return Err(io::Error::new(
-                io::ErrorKind::WouldBlock,
-                "Socket is in non-blocking mode and cannot flush immediately",
-            ));
-        }
-
         trace!("[SocketBase] Flushing {} bytes", self.send_buffer.len());
 
         // Arm poison guard
         let guard = PoisonGuard::new(&mut self.is_poisoned);
 
+        // Hand the buffer itself to the write and take it back afterwards, so
+        // its allocation is reused by the next batch instead of being frozen
+        // away and regrown.
         use compio_buf::BufResult;
-        let buf = self.send_buffer.split().freeze();
+        let buf = std::mem::take(&mut self.send_buffer);
+        let flushed = buf.len();
 
-        // Apply send timeout
-        let BufResult(result, _) = match self.options.send_timeout {
-            None => stream.write_all(buf).await,
+        let write_result = match timeout {
+            None => {
+                let BufResult(result, mut buf) = stream.write_all(buf).await;
+                buf.clear();
+                self.send_buffer = buf;
+                result
+            }
             Some(dur) => {
-                use monocoque_core::rt::timeout;
-                match timeout(dur, stream.write_all(buf)).await {
-                    Ok(result) => result,
-                    Err(_) => {
-                        return Err(io::Error::new(
+                // Written in pieces so the progress survives stopping between
+                // writes: the frozen buffer is shared with each write, and
+                // the unaccepted tail goes back into `send_buffer`.
+                let data = buf.freeze();
+                let (written, result) = if dur.is_zero() {
+                    write_all_nonblocking(stream, &data).await
+                } else {
+                    write_all_within(stream, &data, dur).await
+                };
+                let mut buf = data
+                    .try_into_mut()
+                    .unwrap_or_else(|data| BytesMut::from(&data[..]));
+                let stopped = || {
+                    if dur.is_zero() {
+                        would_block()
+                    } else {
+                        io::Error::new(
                             io::ErrorKind::TimedOut,
                             format!("Flush operation timed out after {:?}", dur),
-                        ));
+                        )
+                    }
+                };
+                match result {
+                    Ok(()) => {
+                        buf.clear();
+                        self.send_buffer = buf;
+                        Ok(())
+                    }
+                    Err(WriteStop::Incomplete) => {
+                        buf.advance(written);
+                        self.send_buffer = buf;
+                        guard.disarm();
+                        return Err(stopped());
+                    }
+                    Err(WriteStop::Cancelled) => {
+                        // The dropped write may yet reach the peer, so
+                        // resending the tail could repeat bytes: the guard
+                        // stays armed and only a reconnect clears it.
+                        buf.clear();
+                        self.send_buffer = buf;
+                        self.buffered_messages = 0;
+                        self.coalesce_started = None;
+                        return Err(stopped());
+                    }
+                    Err(WriteStop::Failed(e)) => {
+                        buf.clear();
+                        self.send_buffer = buf;
+                        Err(e)
                     }
                 }
             }
         };
 
-        let write_result = result;
-
         // If write failed, mark stream as disconnected
-        if write_result.is_err() {
+        if let Err(e) = &write_result {
+            if is_reset(e) {
+                self.close_reason = Some(CloseReason::Reset);
+            }
             self.stream = None;
         }
 
@@ -660,16 +1323,89 @@ where
         // Success - disarm guard and reset counter
         guard.disarm();
         self.buffered_messages = 0;
+        self.coalesce_started = None;
+
+        self.record_flush(flushed);
+        shrink_if_oversized(
+            &mut self.send_buffer,
+            self.options.write_buffer_size,
+            self.options.write_buffer_shrink_factor,
+            self.avg_flush_bytes,
+        );
 
         trace!("[SocketBase] Flush completed");
         Ok(())
     }
 
+    /// Encode a subscription change as the frame this peer expects.
+    ///
+    /// A peer that negotiated ZMTP 3.1 gets a `SUBSCRIBE`/`CANCEL` command
+    /// (RFC 37); a 3.0 peer gets the legacy data frame whose first byte is
+    /// `0x01`/`0x00`. Under CURVE the legacy frame is sent, encrypted, since
+    /// our MESSAGE boxes carry data frames only; every peer accepts it.
+    pub(crate) fn encode_subscription(
+        &mut self,
+        event: &SubscriptionEvent,
+    ) -> io::Result<BytesMut> {
+        let mut wire = BytesMut::with_capacity(event.prefix().len() + 19);
+        if let Some(ref mut cipher) = self.curve_cipher {
+            let body = cipher
+                .encrypt_frame(&event.to_message(), false)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            append_zmtp_cmd_frame(&mut wire, &body);
+        } else if self.zmtp_version.has_v31_commands() {
+            append_zmtp_cmd_frame(&mut wire, &event.to_command());
+        } else {
+            crate::codec::encode_multipart(&[event.to_message()], &mut wire);
+        }
+        Ok(wire)
+    }
+
+    /// Send a complete control frame (subscription, command) through
+    /// `send_buffer`, bounded by `timeout`.
+    ///
+    /// Anything already buffered is flushed first, so the frame keeps its place
+    /// in the stream. The frame is all-or-nothing: if the flush fails before
+    /// any of its bytes reach the stream it is withdrawn from the buffer and the
+    /// error returned; once part of it is on the wire it is committed, and any
+    /// unwritten remainder stays queued for the next flush. A timeout that
+    /// poisons the socket is returned as an error either way.
+    pub(crate) async fn send_command(
+        &mut self,
+        frame: &[u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        self.send_buffer.extend_from_slice(frame);
+        match self.flush_send_buffer_within(timeout).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                let pending = self.send_buffer.len();
+                if pending >= frame.len() {
+                    self.send_buffer.truncate(pending - frame.len());
+                    Err(e)
+                } else if e.kind() == io::ErrorKind::TimedOut && !self.is_poisoned {
+                    Ok(())
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
     /// Write the contents of write_buf directly to the stream.
     ///
     /// This is used when the caller has already encoded data into write_buf
     /// and wants to send it without additional copying. Applies send_timeout
-    /// from options and uses PoisonGuard for cancellation safety.
+    /// from options and uses PoisonGuard for cancellation safety. Anything
+    /// still queued in `send_buffer` is flushed first, so the message keeps
+    /// its place in the stream.
+    ///
+    /// A zero send_timeout never waits for the stream. If the queue ahead
+    /// cannot be flushed, or the stream takes none of the message, the result
+    /// is `WouldBlock` and the message is not sent. Once part of it is
+    /// written the message is committed: the rest is queued in `send_buffer`
+    /// for the next flush and the send succeeds. A write dropped unfinished
+    /// poisons the socket, as in [`flush_send_buffer_within`](Self::flush_send_buffer_within).
     pub(crate) async fn write_from_buf(&mut self) -> io::Result<()> {
         // Check health
         if self.is_poisoned {
@@ -680,32 +1416,66 @@ where
         }
 
         // Ensure we have a connected stream
-        let stream = self
-            .stream
-            .as_mut()
-            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "Socket not connected"))?;
-
-        if self.options.send_timeout.is_some_and(|dur| dur.is_zero()) {
+        if self.stream.is_none() {
             return Err(io::Error::new(
-                io::ErrorKind::WouldBlock,
-                "Socket is in non-blocking mode and cannot send immediately",
+                io::ErrorKind::NotConnected,
+                "Socket not connected",
             ));
         }
 
+        // A tail left by a timed or non-blocking flush must reach the peer
+        // before this message does.
+        if !self.send_buffer.is_empty() {
+            self.flush_send_buffer().await?;
+        }
+
+        let stream = self
+            .stream
+            .as_mut()
+            .expect("BUG: stream must be Some  -  checked is_none() above");
+
         // Arm poison guard
         let guard = PoisonGuard::new(&mut self.is_poisoned);
 
-        // Send write_buf contents
-        let buf = self.write_buf.split().freeze();
+        // Send write_buf contents, reclaiming the buffer afterwards so the
+        // next encode reuses its allocation.
+        let buf = std::mem::take(&mut self.write_buf);
+        let written = buf.len();
 
         use compio_buf::BufResult;
 
         // Apply send timeout from options
-        let BufResult(result, _) = match self.options.send_timeout {
+        let BufResult(result, mut buf) = match self.options.send_timeout {
             None => {
                 // Blocking mode - no timeout
                 stream.write_all(buf).await
             }
+            Some(dur) if dur.is_zero() => {
+                // Non-blocking mode - write what the stream takes right away
+                let data = buf.freeze();
+                let (sent, result) = write_all_nonblocking(stream, &data).await;
+                let mut buf = data
+                    .try_into_mut()
+                    .unwrap_or_else(|data| BytesMut::from(&data[..]));
+                match result {
+                    Ok(()) => BufResult(Ok(()), buf),
+                    Err(WriteStop::Incomplete) => {
+                        // `send_buffer` was flushed above, so the tail goes
+                        // out before anything sent after this message.
+                        self.send_buffer.extend_from_slice(&buf[sent..]);
+                        buf.clear();
+                        self.write_buf = buf;
+                        guard.disarm();
+                        return Ok(());
+                    }
+                    Err(WriteStop::Cancelled) => {
+                        buf.clear();
+                        self.write_buf = buf;
+                        return Err(would_block());
+                    }
+                    Err(WriteStop::Failed(e)) => BufResult(Err(e), buf),
+                }
+            }
             Some(dur) => {
                 // Timed mode - apply timeout
                 use monocoque_core::rt::timeout;
@@ -721,14 +1491,28 @@ where
             }
         };
 
+        buf.clear();
+        self.write_buf = buf;
+
         // Mark disconnected on error
-        if result.is_err() {
+        if let Err(e) = &result {
+            if is_reset(e) {
+                self.close_reason = Some(CloseReason::Reset);
+            }
             self.stream = None;
         }
 
         result?;
 
         guard.disarm();
+
+        self.record_flush(written);
+        shrink_if_oversized(
+            &mut self.write_buf,
+            self.options.write_buffer_size,
+            self.options.write_buffer_shrink_factor,
+            self.avg_flush_bytes,
+        );
         Ok(())
     }
 
@@ -740,10 +1524,11 @@ where
     /// frames stay on the copy path, where a single contiguous `write` beats the
     /// per-iovec bookkeeping. CURVE-encrypted connections never qualify: the
     /// cipher must transform each body into a fresh buffer regardless, so there
-    /// is no copy to save.
+    /// is no copy to save. Neither do non-blocking sends, which go through the
+    /// copy path so an unsent tail can be queued in `send_buffer`.
     #[inline]
     pub(crate) fn should_vectored_write(&self, msg: &[Bytes]) -> bool {
-        if self.curve_cipher.is_some() {
+        if self.curve_cipher.is_some() || self.options.is_send_nonblocking() {
             return false;
         }
         let threshold = self.options.vectored_write_threshold;
@@ -753,75 +1538,106 @@ where
     /// Send a multipart message using a vectored write, without copying any
     /// frame body into the userspace send buffer.
     ///
-    /// Each frame contributes two iovec entries: a freshly built header (2 or 9
-    /// bytes) and the frame body itself, an O(1) `Bytes::clone` with no data
-    /// copy. The whole iovec list is handed to `write_vectored_all`, so the
-    /// bodies travel straight to the kernel.
+    /// The message is laid out as a [`FramedMessage`](crate::codec::FramedMessage):
+    /// each frame contributes two iovec entries, its header (2 or 9 bytes) and
+    /// the frame body itself, an O(1) `Bytes::clone` with no data copy. The
+    /// list goes to [`write_vectored`](Self::write_vectored), so the bodies
+    /// travel straight to the kernel.
     ///
-    /// Anything already pending in `send_buffer` is flushed first to preserve
-    /// wire ordering. Uses `PoisonGuard` for cancellation safety and applies
-    /// `send_timeout`, mirroring [`flush_send_buffer`](Self::flush_send_buffer).
-    /// On write failure the stream is dropped (`stream = None`) to mark
-    /// disconnection.
+    /// The entries are owned `Bytes` rather than borrowed `IoSlice`s: the
+    /// `io_uring` backend holds the buffers until the write completes, which
+    /// a borrow cannot outlive if the send is cancelled.
     pub(crate) async fn send_vectored(&mut self, msg: &[Bytes]) -> io::Result<()> {
-        use crate::codec::write_frame_header;
-
         if msg.is_empty() {
             return Ok(());
         }
+        self.send_framed(&crate::codec::FramedMessage::new(msg))
+            .await
+    }
 
-        // Preserve ordering: flush anything already buffered before this frame.
-        if !self.send_buffer.is_empty() {
-            self.flush_send_buffer().await?;
+    /// Write a message laid out by [`FramedMessage`](crate::codec::FramedMessage)
+    /// with one vectored write.
+    ///
+    /// The headers are copied contiguously into the reused `write_buf` and
+    /// sliced back out (O(1), sharing its allocation), and the frame list is
+    /// reused across calls via `self.frame_iov`, so a message of up to eight
+    /// frames costs no heap allocation.
+    pub(crate) async fn send_framed(
+        &mut self,
+        framed: &crate::codec::FramedMessage<'_>,
+    ) -> io::Result<()> {
+        self.write_buf.clear();
+        for header in &framed.headers {
+            self.write_buf.extend_from_slice(header.as_bytes());
         }
+        let mut headers = self.write_buf.split().freeze();
 
-        if self.is_poisoned {
-            return Err(io::Error::new(
-                io::ErrorKind::BrokenPipe,
-                "Socket poisoned by cancelled I/O - reconnect required",
-            ));
+        let mut frames = std::mem::take(&mut self.frame_iov);
+        frames.reserve(framed.headers.len() * 2);
+        for (header, payload) in framed.headers.iter().zip(&framed.payloads) {
+            frames.push(headers.split_to(header.as_bytes().len()));
+            frames.push(Bytes::clone(payload));
         }
 
-        if self.options.send_timeout.is_some_and(|dur| dur.is_zero()) {
-            return Err(io::Error::new(
-                io::ErrorKind::WouldBlock,
-                "Socket is in non-blocking mode and cannot send immediately",
-            ));
+        let result = self.write_vectored(&frames).await;
+        frames.clear();
+        self.frame_iov = frames;
+        result
+    }
+
+    /// Write preformed buffers (encoded frames, or any wire bytes) with one
+    /// vectored write, without consolidating them into `write_buf`.
+    ///
+    /// Anything already pending in `send_buffer` is flushed first to preserve
+    /// wire ordering. Uses `PoisonGuard` for cancellation safety and applies
+    /// `send_timeout`, mirroring [`flush_send_buffer`](Self::flush_send_buffer).
+    /// A zero `send_timeout` falls back to copying `bufs` into `write_buf` for
+    /// [`write_from_buf`](Self::write_from_buf)'s all-or-nothing write, which a
+    /// vectored write cannot offer. On write failure the stream is dropped
+    /// (`stream = None`) to mark disconnection.
+    pub(crate) async fn write_vectored(&mut self, bufs: &[Bytes]) -> io::Result<()> {
+        if bufs.is_empty() {
+            return Ok(());
         }
 
-        // Build all frame headers contiguously in the reused write_buf, then
-        // slice each one back out (O(1), sharing write_buf's allocation). The
-        // iovec list is reused across calls via `self.iov`, so the hot path
-        // performs no per-message heap allocation.
-        let last = msg.len() - 1;
-        self.write_buf.clear();
-        for (i, frame) in msg.iter().enumerate() {
-            write_frame_header(&mut self.write_buf, frame.len(), i < last);
+        // Preserve ordering: flush anything already buffered before this write.
+        if !self.send_buffer.is_empty() {
+            self.flush_send_buffer().await?;
         }
-        let mut headers = self.write_buf.split().freeze();
 
-        let mut iovecs = std::mem::take(&mut self.iov);
-        iovecs.clear();
-        iovecs.reserve(msg.len() * 2);
-        for frame in msg {
-            let hlen = if frame.len() >= 256 { 9 } else { 2 };
-            iovecs.push(headers.split_to(hlen));
-            iovecs.push(frame.clone());
+        if self.is_poisoned {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "Socket poisoned by cancelled I/O - reconnect required",
+            ));
+        }
+
+        if self.options.is_send_nonblocking() {
+            self.write_buf.clear();
+            for buf in bufs {
+                self.write_buf.extend_from_slice(buf);
+            }
+            return self.write_from_buf().await;
         }
 
         let Some(stream) = self.stream.as_mut() else {
-            self.iov = iovecs; // keep the scratch capacity
             return Err(io::Error::new(
                 io::ErrorKind::NotConnected,
                 "Socket not connected",
             ));
         };
 
+        // The iovec list is reused across calls via `self.iov`; handing it
+        // the caller's buffers only bumps their reference counts.
+        let mut iovecs = std::mem::take(&mut self.iov);
+        iovecs.clear();
+        iovecs.extend_from_slice(bufs);
+
         // Arm poison guard for cancellation safety.
         let guard = PoisonGuard::new(&mut self.is_poisoned);
 
         use compio_buf::BufResult;
-        let BufResult(result, returned) = match self.options.send_timeout {
+        let BufResult(result, mut returned) = match self.options.send_timeout {
             None => stream.write_vectored_all(iovecs).await,
             Some(dur) => {
                 use monocoque_core::rt::timeout;
@@ -838,9 +1654,13 @@ where
         };
 
         // Reclaim the iovec allocation for the next call.
+        returned.clear();
         self.iov = returned;
 
-        if result.is_err() {
+        if let Err(e) = &result {
+            if is_reset(e) {
+                self.close_reason = Some(CloseReason::Reset);
+            }
             self.stream = None;
         }
         result?;
@@ -870,13 +1690,18 @@ where
                     // Linger 0: discard buffered data.
                     self.send_buffer.clear();
                     self.buffered_messages = 0;
+                    self.coalesce_started = None;
                 }
                 Some(dur) => {
                     use monocoque_core::rt::timeout;
                     // Flush within the linger window; close anyway on timeout.
                     match timeout(dur, self.flush_send_buffer()).await {
-                        Ok(Ok(())) | Err(_) => {}
+                        Ok(Ok(())) => {}
                         Ok(Err(e)) => return Err(e),
+                        Err(_) => warn!(
+                            "[SocketBase] Linger expired with {} bytes unsent",
+                            self.send_buffer.len()
+                        ),
                     }
                 }
                 None => {
@@ -913,18 +1738,8 @@ where
 
     /// Encode a multipart message into `send_buffer`, encrypting if CURVE is active.
     pub fn encode_message_to_send_buf(&mut self, msg: &[Bytes]) -> io::Result<()> {
-        use crate::codec::encode_multipart;
-        if let Some(ref mut cipher) = self.curve_cipher {
-            let last = msg.len().saturating_sub(1);
-            for (i, frame) in msg.iter().enumerate() {
-                let body = cipher
-                    .encrypt_frame(frame, i < last)
-                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-                append_zmtp_cmd_frame(&mut self.send_buffer, &body);
-            }
-        } else {
-            encode_multipart(msg, &mut self.send_buffer);
-        }
+        self.check_immediate()?;
+        self.append_to_send_buf(msg)?;
         self.buffered_messages += 1;
         Ok(())
     }
@@ -936,6 +1751,147 @@ where
     /// `send_buffered` / `flush` batch API).  Callers must call `flush_send_buffer`
     /// after the last message in a burst.
     pub(crate) async fn send_coalesced(&mut self, msg: &[Bytes]) -> io::Result<()> {
+        self.check_immediate()?;
+        self.append_to_send_buf(msg)?;
+        if self.send_buffer.len() >= self.options.write_coalesce_threshold {
+            self.flush_send_buffer().await?;
+        }
+        Ok(())
+    }
+
+    /// Take the sequence number for the next outgoing message; 0 when the
+    /// `dedupe_window` option is off.
+    pub(crate) fn next_sequence(&mut self) -> u64 {
+        self.sequencing.as_mut().map_or(0, |s| s.next_sequence())
+    }
+
+    /// `msg` behind the sequence frame for `seq`, if this connection
+    /// negotiated sequence frames.
+    fn stamped(&self, seq: u64, msg: &[Bytes]) -> Option<Vec<Bytes>> {
+        let sequencing = self.sequencing.as_ref().filter(|_| self.sequenced)?;
+        let mut stamped = Vec::with_capacity(msg.len() + 1);
+        stamped.push(sequencing.stamp(seq));
+        stamped.extend_from_slice(msg);
+        Some(stamped)
+    }
+
+    /// [`send_message`](Self::send_message) with the sequence frame for `seq`
+    /// in front when negotiated.
+    pub(crate) async fn send_sequenced(&mut self, seq: u64, msg: &[Bytes]) -> io::Result<()> {
+        match self.stamped(seq, msg) {
+            Some(stamped) => self.send_message(&stamped).await,
+            None => self.send_message(msg).await,
+        }
+    }
+
+    /// [`encode_message_to_send_buf`](Self::encode_message_to_send_buf) with
+    /// the sequence frame for `seq` in front when negotiated.
+    pub(crate) fn buffer_sequenced(&mut self, seq: u64, msg: &[Bytes]) -> io::Result<()> {
+        match self.stamped(seq, msg) {
+            Some(stamped) => self.encode_message_to_send_buf(&stamped),
+            None => self.encode_message_to_send_buf(msg),
+        }
+    }
+
+    /// Strip the sequence frame from a received message and report whether
+    /// to deliver it; duplicates return `Ok(false)`. Without negotiated
+    /// sequence frames every message is delivered untouched.
+    pub(crate) fn admit(&mut self, msg: &mut Vec<Bytes>) -> io::Result<bool> {
+        match self.sequencing.as_mut() {
+            Some(sequencing) if self.sequenced => sequencing.admit(msg),
+            _ => Ok(true),
+        }
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` filter.
+    pub const fn duplicates_dropped(&self) -> u64 {
+        match &self.sequencing {
+            Some(sequencing) => sequencing.duplicates(),
+            None => 0,
+        }
+    }
+
+    /// Encode and write `msg`, holding it for the `coalesce_window` option
+    /// when one is set.
+    ///
+    /// Without a window this is the eager path: the message is written before
+    /// returning, vectored once a frame reaches `vectored_write_threshold`. With one, it joins `send_buffer`, which is written once the
+    /// window has passed since the batch started or the buffer reaches
+    /// `write_coalesce_threshold`.
+    ///
+    /// Held messages count against `send_hwm`. Once the mark is reached,
+    /// [`HwmPolicy::Block`] waits for the held batch to be written (bounded
+    /// by `send_timeout`) before taking `msg`, and [`HwmPolicy::Error`]
+    /// returns `WouldBlock`; either way a failed send leaves `msg` unqueued.
+    ///
+    /// A Block wait that times out returns `TimedOut`. If the deadline passed
+    /// between writes, the unwritten part of the held batch stays queued. If
+    /// it passed with a write in flight, the socket is poisoned and **the held
+    /// batch is discarded**, although earlier sends accepted it with `Ok(())`:
+    /// the dropped write may still reach the peer, so resending could repeat
+    /// messages. Only a reconnect makes the socket usable again.
+    pub(crate) async fn send_message(&mut self, msg: &[Bytes]) -> io::Result<()> {
+        let Some(window) = self.options.coalesce_window else {
+            if self.should_vectored_write(msg) {
+                return self.send_vectored(msg).await;
+            }
+            self.encode_message_to_write_buf(msg)?;
+            return self.write_from_buf().await;
+        };
+        if self.stream.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "Socket not connected",
+            ));
+        }
+        if self.hwm_reached() {
+            match self.options.hwm_policy {
+                HwmPolicy::Block => self.flush_send_buffer().await?,
+                HwmPolicy::Error => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WouldBlock,
+                        format!(
+                            "Send high water mark reached ({} messages)",
+                            self.options.send_hwm
+                        ),
+                    ));
+                }
+            }
+        }
+        self.append_to_send_buf(msg)?;
+        self.buffered_messages += 1;
+        let started = *self.coalesce_started.get_or_insert_with(Instant::now);
+        if started.elapsed() >= window
+            || self.send_buffer.len() >= self.options.write_coalesce_threshold
+        {
+            self.flush_coalesced().await?;
+        }
+        Ok(())
+    }
+
+    /// Write the batch held by the coalescing window.
+    ///
+    /// The messages were already accepted, so a flush the stream cannot take
+    /// right now (`WouldBlock`, or `TimedOut` part-way) leaves them buffered
+    /// for the next attempt instead of failing the caller's operation. A
+    /// timeout that poisoned the socket dropped the batch and is returned.
+    pub(crate) async fn flush_coalesced(&mut self) -> io::Result<()> {
+        match self.flush_send_buffer().await {
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                ) && !self.is_poisoned =>
+            {
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Encode `msg` onto the end of `send_buffer`, encrypting if CURVE is
+    /// active, without counting it as a `send_buffered` message.
+    fn append_to_send_buf(&mut self, msg: &[Bytes]) -> io::Result<()> {
         use crate::codec::encode_multipart;
         if let Some(ref mut cipher) = self.curve_cipher {
             let last = msg.len().saturating_sub(1);
@@ -948,9 +1904,6 @@ where
         } else {
             encode_multipart(msg, &mut self.send_buffer);
         }
-        if self.send_buffer.len() >= self.options.write_coalesce_threshold {
-            self.flush_send_buffer().await?;
-        }
         Ok(())
     }
 
@@ -978,14 +1931,37 @@ where
         Ok(self.send_buffer.len() >= self.options.write_coalesce_threshold)
     }
 
+    /// Disconnect a peer that broke the framing rules, as libzmq does.
+    ///
+    /// The rest of the receive buffer cannot be trusted once a frame is
+    /// malformed, so it is discarded along with any partial decoder state.
+    /// Returns `err` so callers can surface it unchanged.
+    pub(crate) fn drop_violating_peer(&mut self, err: io::Error) -> io::Error {
+        warn!(
+            "[SocketBase] Dropping peer after protocol violation: {}",
+            err
+        );
+        self.stream = None;
+        self.close_reason = Some(CloseReason::ProtocolError);
+        self.recv = SegmentedBuffer::new();
+        self.decoder = decoder_for(&self.options);
+        err
+    }
+
     /// Decode the next frame from the receive buffer, handling CURVE decryption and PING/PONG.
+    ///
+    /// A malformed frame disconnects the peer and yields an `InvalidData`
+    /// error wrapping the [`ZmtpError`]. An ERROR command from the peer also
+    /// disconnects it and yields `ConnectionRefused` carrying its reason.
     pub fn process_frame(&mut self) -> io::Result<FrameResult> {
         use crate::security::curve::CurveMessageCipher;
-        match self
-            .decoder
-            .decode(&mut self.recv)
-            .map_err(io::Error::from)?
-        {
+        let decoded = match self.decoder.decode(&mut self.recv) {
+            Ok(decoded) => decoded,
+            // Not a violation: the frame waits for `recv_frame_streaming`.
+            Err(e @ ZmtpError::StreamingRequired { .. }) => return Err(io::Error::from(e)),
+            Err(e) => return Err(self.drop_violating_peer(io::Error::from(e))),
+        };
+        match decoded {
             None => Ok(FrameResult::NeedMore),
             Some(frame) => {
                 if frame.is_command() {
@@ -999,23 +1975,37 @@ where
                             })?;
                         return Ok(FrameResult::Data(more, payload));
                     }
-                    // PING/PONG
-                    if is_ping_payload(&frame.payload) {
-                        let pong = build_pong_frame();
-                        self.send_buffer.extend_from_slice(&pong);
-                    }
-                    if is_pong_payload(&frame.payload) {
-                        self.note_pong_received();
+                    match parse_command(&frame) {
+                        // ERROR: the peer is rejecting us and closing.
+                        Ok(Command::Error(reason)) => {
+                            let err = ZmtpError::PeerError {
+                                reason: String::from_utf8_lossy(&reason).into_owned(),
+                            };
+                            warn!("[SocketBase] Peer closed the connection: {}", err);
+                            self.stream = None;
+                            self.close_reason = Some(CloseReason::ProtocolError);
+                            self.recv = SegmentedBuffer::new();
+                            self.decoder = decoder_for(&self.options);
+                            return Err(io::Error::from(err));
+                        }
+                        Ok(Command::Ping { context, .. }) => {
+                            let pong = build_pong_frame(&context);
+                            self.send_buffer.extend_from_slice(&pong);
+                        }
+                        Ok(Command::Pong(_)) => self.note_pong_received(),
+                        Ok(_) => {}
+                        Err(e) => debug!("[SocketBase] Ignoring command: {}", e),
                     }
                     Ok(FrameResult::CommandHandled)
                 } else {
                     // Reject raw data frames when CURVE is negotiated - all application
                     // messages must arrive as CURVE MESSAGE command frames.
                     if self.curve_cipher.is_some() {
-                        return Err(io::Error::new(
+                        crate::codec::record_violation(ZmtpError::Protocol);
+                        return Err(self.drop_violating_peer(io::Error::new(
                             io::ErrorKind::InvalidData,
                             "unexpected plaintext data frame in CURVE mode",
-                        ));
+                        )));
                     }
                     Ok(FrameResult::Data(frame.more(), frame.payload))
                 }
@@ -1025,25 +2015,113 @@ where
 }
 
 impl SocketBase<TcpStream> {
+    /// Create a TCP SocketBase that can reconnect to `endpoint`.
+    ///
+    /// Like [`with_endpoint`](SocketBase::with_endpoint), plus the dialer
+    /// [`try_reconnect`](SocketBase::try_reconnect) uses to open a new
+    /// connection.
+    pub(crate) fn with_tcp_endpoint(
+        stream: TcpStream,
+        socket_type: SocketType,
+        endpoint: Endpoint,
+        options: SocketOptions,
+    ) -> Self {
+        let mut base = Self::with_endpoint(stream, socket_type, endpoint, options);
+        base.redial = Some(redial_tcp);
+        base
+    }
+
+    /// Remember the peer address of a stream handed to `from_tcp`, so a
+    /// socket with `auto_recover` set can redial it once poisoned.
+    ///
+    /// Does nothing unless `auto_recover` is set, if an endpoint is already
+    /// stored, or if the peer address is unavailable. Only meaningful for
+    /// streams this side dialed: an accepted stream's peer address is the
+    /// client's ephemeral port.
+    pub(crate) fn remember_peer_for_recovery(&mut self) {
+        if !self.options.auto_recover || self.endpoint.is_some() {
+            return;
+        }
+        let Some(addr) = self.stream.as_ref().and_then(|s| s.peer_addr().ok()) else {
+            return;
+        };
+        let endpoint = Endpoint::Tcp(addr);
+        self.last_endpoint = Some(endpoint.to_string());
+        self.endpoint = Some(endpoint);
+        self.reconnect = Some(ReconnectState::new(&self.options));
+        self.redial = Some(redial_tcp);
+    }
+}
+
+/// [`Redial`] for TCP endpoints.
+fn redial_tcp(
+    endpoint: Endpoint,
+    options: SocketOptions,
+) -> futures::future::LocalBoxFuture<'static, io::Result<TcpStream>> {
+    Box::pin(async move {
+        let stream = match endpoint {
+            Endpoint::Tcp(addr) => TcpStream::connect(addr).await?,
+            #[cfg(feature = "tls")]
+            Endpoint::Tls(..) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "TLS reconnection not supported for TcpStream base",
+                ));
+            }
+            #[cfg(unix)]
+            Endpoint::Ipc(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "IPC reconnection not supported for TcpStream base",
+                ));
+            }
+            Endpoint::Inproc(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Inproc reconnection not supported for TcpStream base",
+                ));
+            }
+        };
+        // Re-apply TCP tuning to the fresh socket. The original connect set
+        // TCP_NODELAY (and keepalive), but a reconnect is a brand-new fd that
+        // starts with kernel defaults (Nagle on), so without this the socket
+        // would silently run with Nagle enabled after any reconnect. This is a
+        // one-time setsockopt at reconnect, off the send/recv hot path.
+        crate::utils::configure_tcp_stream(&stream, &options, "RECONNECT")?;
+        Ok(stream)
+    })
+}
+
+impl<S> SocketBase<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
     /// Try to reconnect to the stored endpoint.
     ///
     /// This method:
     /// 1. Checks if endpoint is configured
     /// 2. Applies exponential backoff delay
-    /// 3. Attempts new TCP connection
+    /// 3. Attempts new connection
     /// 4. Performs ZMTP handshake
     /// 5. Resets socket state on success
     ///
-    /// Returns `Ok(())` on successful reconnection, `Err(e)` otherwise.
-    pub(crate) async fn try_reconnect(&mut self, socket_type: SocketType) -> io::Result<()> {
+    /// A poisoned socket stays poisoned through steps 1-4: a failed attempt
+    /// leaves `is_poisoned` as it was. Only once the new stream is installed
+    /// is the flag cleared, and it must be cleared with
+    /// [`PoisonGuard::recover`] after the stream is replaced, never before.
+    ///
+    /// Returns the identity the peer announced in the new handshake, if any.
+    pub(crate) async fn try_reconnect(
+        &mut self,
+        socket_type: SocketType,
+    ) -> io::Result<Option<Bytes>> {
         // Can only reconnect if we have an endpoint
-        let endpoint = self.endpoint.as_ref().ok_or_else(|| {
-            io::Error::new(
+        let (Some(endpoint), Some(redial)) = (self.endpoint.clone(), self.redial) else {
+            return Err(io::Error::new(
                 io::ErrorKind::Unsupported,
                 "Socket was not created with connect() - no endpoint stored for reconnection",
-            )
-        })?;
-
+            ));
+        };
         // Apply the backoff delay if we have reconnection state. This is an
         // async sleep that yields the executor, so a reconnecting socket does
         // not stall other sockets colocated on the same single-threaded runtime.
@@ -1051,7 +2129,15 @@ impl SocketBase<TcpStream> {
         // 0.10 left residual timer state after handshake timeouts that hung
         // rt::sleep; compio 0.19 fixed that, verified by
         // tests/sleep_after_timeout_probe.rs.)
-        if let Some(reconnect) = &mut self.reconnect {
+        //
+        // With `immediate` the backoff runs after a failed attempt instead of
+        // before each one, so a send can redial at once after a disconnect
+        // and otherwise fails fast (see `check_redial_backoff`).
+        if self.options.immediate {
+            if let Some(at) = self.redial_after {
+                monocoque_core::rt::sleep(at.saturating_duration_since(Instant::now())).await;
+            }
+        } else if let Some(reconnect) = &mut self.reconnect {
             let base_delay = reconnect.next_delay();
             let delay = jittered_backoff(base_delay);
             debug!(
@@ -1062,49 +2148,61 @@ impl SocketBase<TcpStream> {
             monocoque_core::rt::sleep(delay).await;
         }
 
-        // Attempt connection based on endpoint type
-        let mut new_stream = match endpoint {
-            Endpoint::Tcp(addr) => TcpStream::connect(addr).await?,
-            #[cfg(unix)]
-            Endpoint::Ipc(_) => {
-                return Err(io::Error::new(
-                    io::ErrorKind::Unsupported,
-                    "IPC reconnection not supported for TcpStream base",
-                ));
-            }
-            Endpoint::Inproc(_) => {
-                return Err(io::Error::new(
-                    io::ErrorKind::Unsupported,
-                    "Inproc reconnection not supported for TcpStream base",
-                ));
-            }
-        };
+        let attempt_number = self.reconnect_attempt();
+        let started_at = SystemTime::now();
+        let started = Instant::now();
+        let dialed = async {
+            let mut new_stream = redial(endpoint.clone(), self.options.clone()).await?;
 
-        // Re-apply TCP tuning to the fresh socket. The original connect set
-        // TCP_NODELAY (and keepalive), but a reconnect is a brand-new fd that
-        // starts with kernel defaults (Nagle on), so without this the socket
-        // would silently run with Nagle enabled after any reconnect. This is a
-        // one-time setsockopt at reconnect, off the send/recv hot path.
-        crate::utils::configure_tcp_stream(&new_stream, &self.options, "RECONNECT")?;
-
-        // Perform handshake  -  preserve routing identity from options
-        let hr = perform_handshake_with_options(
-            &mut new_stream,
-            socket_type,
-            self.options.routing_id.as_deref(),
-            Some(self.options.handshake_timeout),
-            &self.options,
-        )
-        .await
-        .map_err(|e| io::Error::other(format!("Handshake failed during reconnect: {}", e)))?;
+            // Perform handshake  -  preserve routing identity from options
+            let hr = perform_handshake_with_options(
+                &mut new_stream,
+                socket_type,
+                self.options.routing_id.as_deref(),
+                self.options.handshake_deadline(),
+                &self.options,
+            )
+            .await
+            .map_err(|e| match e {
+                ZmtpError::AuthenticationFailed | ZmtpError::PeerError { .. } => handshake_error(e),
+                e => io::Error::other(format!("Handshake failed during reconnect: {}", e)),
+            })?;
+            Ok::<_, io::Error>((new_stream, hr))
+        }
+        .await;
+        self.connect_history.record(ConnectAttempt {
+            endpoint,
+            started_at,
+            outcome: match &dialed {
+                Ok(_) => ConnectOutcome::Ok(started.elapsed()),
+                Err(e) => ConnectOutcome::from_error(e),
+            },
+            attempt_number,
+        });
+        if self.options.immediate {
+            self.redial_after = match (&dialed, &mut self.reconnect) {
+                (Err(_), Some(reconnect)) => {
+                    Some(Instant::now() + jittered_backoff(reconnect.next_delay()))
+                }
+                _ => None,
+            };
+        }
+        let (new_stream, hr) = dialed?;
 
-        // Success! Update socket state
+        // Success! Update socket state. The poison flag belonged to the old
+        // stream: it is cleared through `recover()` only now that the stream
+        // has been replaced by one that completed its handshake, never before.
         self.curve_cipher = hr.curve_cipher;
+        self.zmtp_version = hr.version;
+        self.sequenced = hr.sequenced;
+        self.peer_metadata = hr.peer_metadata;
         self.stream = Some(new_stream);
-        self.is_poisoned = false;
+        PoisonGuard::new(&mut self.is_poisoned).recover();
         self.recv = SegmentedBuffer::new();
+        self.decoder = decoder_for(&self.options);
         self.send_buffer.clear();
         self.buffered_messages = 0;
+        self.coalesce_started = None;
 
         // Reset heartbeat state for the fresh connection
         self.last_recv_instant = None;
@@ -1117,7 +2215,92 @@ impl SocketBase<TcpStream> {
         }
 
         debug!("[SocketBase] Reconnection successful");
-        Ok(())
+        Ok(hr.peer_identity)
+    }
+
+    /// Reconnect a poisoned socket before a `send`/`recv` when
+    /// [`should_auto_recover`](Self::should_auto_recover) holds.
+    ///
+    /// A failed attempt is only logged: the socket stays poisoned, so the
+    /// operation that follows still fails with `BrokenPipe`.
+    pub(crate) async fn recover_if_poisoned(&mut self, socket_type: SocketType) {
+        if !self.should_auto_recover() {
+            return;
+        }
+        // Boxed: the handshake may call back into a socket send (ZAP).
+        match Box::pin(self.try_reconnect(socket_type)).await {
+            Ok(_) => debug!("[SocketBase] Recovered poisoned socket by reconnecting"),
+            Err(e) => debug!("[SocketBase] Automatic recovery failed: {}", e),
+        }
+    }
+
+    /// Reconnect, retrying failed attempts with backoff.
+    ///
+    /// `attempts` counts attempts across calls so a caller looping over
+    /// reconnect/receive cycles shares one `max_reconnect_attempts` budget;
+    /// once it is spent this returns `NotConnected`. Errors that another
+    /// attempt cannot fix (no endpoint, invalid routing id) return at once.
+    pub(crate) async fn reconnect_within_budget(
+        &mut self,
+        socket_type: SocketType,
+        attempts: &mut u32,
+    ) -> io::Result<Option<Bytes>> {
+        loop {
+            if let Some(limit) = self.options.max_reconnect_attempts
+                && *attempts >= limit
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotConnected,
+                    format!("Max {} reconnection attempts exceeded", limit),
+                ));
+            }
+            *attempts += 1;
+            match self.try_reconnect(socket_type).await {
+                Ok(identity) => return Ok(identity),
+                Err(e)
+                    if matches!(
+                        e.kind(),
+                        io::ErrorKind::Unsupported | io::ErrorKind::InvalidInput
+                    ) =>
+                {
+                    return Err(e);
+                }
+                Err(e) => debug!(
+                    "[SocketBase] Reconnection attempt {} failed: {}",
+                    attempts, e
+                ),
+            }
+        }
+    }
+
+    /// Whether `err` from a receive or send means the connection is gone and
+    /// a reconnect should follow.
+    pub(crate) fn lost_connection(&self, err: &io::Error) -> bool {
+        self.stream.is_none()
+            || matches!(
+                err.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+            )
+    }
+}
+
+impl<S> Drop for SocketBase<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn drop(&mut self) {
+        // `close()` drops the stream once linger is honored, so anything still
+        // buffered on a live stream here is lost without the caller asking.
+        if self.stream.is_some() && !self.send_buffer.is_empty() {
+            warn!(
+                "[SocketBase] Dropped with {} unsent bytes ({} messages); call close() to flush within linger",
+                self.send_buffer.len(),
+                self.buffered_messages
+            );
+        }
     }
 }
 
@@ -1165,6 +2348,63 @@ mod tests {
         }
     }
 
+    /// Stream whose reads end the connection: `Ok(0)`, or the given error.
+    struct ClosingStream(Option<io::ErrorKind>);
+
+    impl AsyncRead for ClosingStream {
+        async fn read<B: IoBufMut>(&mut self, buf: B) -> BufResult<usize, B> {
+            match self.0 {
+                Some(kind) => BufResult(Err(io::Error::new(kind, "closing stream")), buf),
+                None => BufResult(Ok(0), buf),
+            }
+        }
+    }
+
+    impl AsyncWrite for ClosingStream {
+        async fn write<B: IoBuf>(&mut self, buf: B) -> BufResult<usize, B> {
+            BufResult(Err(io::ErrorKind::BrokenPipe.into()), buf)
+        }
+
+        async fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn close_reason_tells_eof_from_reset() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = ClosingStream(Some(io::ErrorKind::ConnectionReset));
+                let mut base =
+                    SocketBase::new(stream, SocketType::Dealer, SocketOptions::default());
+                assert_eq!(base.last_close_reason(), None);
+                let err = base.read_raw().await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+                assert_eq!(base.last_close_reason(), Some(CloseReason::Reset));
+                assert!(!base.is_connected());
+
+                let stream = ClosingStream(None);
+                let mut base =
+                    SocketBase::new(stream, SocketType::Dealer, SocketOptions::default());
+                assert_eq!(base.read_raw().await.unwrap(), 0);
+                assert_eq!(base.last_close_reason(), Some(CloseReason::Eof));
+                assert!(!base.is_connected());
+
+                // A broken pipe on write is a reset too.
+                let stream = ClosingStream(None);
+                let mut base =
+                    SocketBase::new(stream, SocketType::Dealer, SocketOptions::default());
+                base.send_buffer.extend_from_slice(PAYLOAD);
+                base.flush_send_buffer().await.unwrap_err();
+                assert_eq!(base.last_close_reason(), Some(CloseReason::Reset));
+            });
+    }
+
     const PAYLOAD: &[u8] = b"abcdef";
 
     #[derive(Clone, Debug, Default)]
@@ -1192,6 +2432,13 @@ mod tests {
     enum WriteStep {
         Bytes(usize),
         Error(io::ErrorKind),
+        /// Never complete, like a peer that has stopped reading.
+        Stall,
+        /// Accept everything after a pause, like a slow reader.
+        Delay(std::time::Duration),
+        /// Accept `n` bytes after blocking the thread for the pause, so the
+        /// write completes past a deadline without being dropped.
+        Slow(usize, std::time::Duration),
     }
 
     #[derive(Debug)]
@@ -1230,6 +2477,19 @@ mod tests {
                 Some(WriteStep::Error(kind)) => {
                     BufResult(Err(io::Error::new(kind, "scripted write error")), buf)
                 }
+                Some(WriteStep::Stall) => std::future::pending().await,
+                Some(WriteStep::Slow(n, pause)) => {
+                    std::thread::sleep(pause);
+                    let n = n.min(buf.buf_len());
+                    self.log.push(&buf.as_init()[..n]);
+                    BufResult(Ok(n), buf)
+                }
+                Some(WriteStep::Delay(pause)) => {
+                    monocoque_core::rt::sleep(pause).await;
+                    let n = buf.buf_len();
+                    self.log.push(buf.as_init());
+                    BufResult(Ok(n), buf)
+                }
                 None => {
                     let n = buf.buf_len();
                     self.log.push(buf.as_init());
@@ -1292,12 +2552,6 @@ mod tests {
         }
     }
 
-    #[derive(Clone, Copy, Debug)]
-    enum StreamState {
-        Connected,
-        Disconnected,
-    }
-
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     enum WriteStatus {
         Ok,
@@ -1350,6 +2604,9 @@ mod tests {
                         written,
                     };
                 }
+                WriteStep::Stall | WriteStep::Delay(_) | WriteStep::Slow(..) => {
+                    unreachable!("stalls and delays are not part of write_all scripts")
+                }
             }
         }
 
@@ -1431,27 +2688,33 @@ mod tests {
 
     async fn assert_pre_io_error_preserves_buffer(
         path: WritePath,
+        steps: impl IntoIterator<Item = WriteStep>,
         options: SocketOptions,
-        stream_state: StreamState,
         expected_kind: io::ErrorKind,
     ) {
-        let (mut base, log) = socket_with_payload(path, [], options);
-        if matches!(stream_state, StreamState::Disconnected) {
-            base.stream = None;
-        }
+        let (mut base, log) = socket_with_payload(path, steps, options);
+        base.stream = None;
 
         let err = path.write(&mut base).await.unwrap_err();
 
         assert_eq!(err.kind(), expected_kind);
         assert!(log.is_empty());
-        match stream_state {
-            StreamState::Connected => assert!(base.stream.is_some()),
-            StreamState::Disconnected => assert!(base.stream.is_none()),
-        }
+        assert!(base.stream.is_none());
         path.assert_payload_buffered(&base);
         assert!(!base.is_poisoned());
     }
 
+    async fn assert_nonblocking_write_completes(path: WritePath, steps: Vec<WriteStep>) {
+        let (mut base, log) = socket_with_payload(path, steps, nonblocking_options());
+
+        path.write(&mut base).await.unwrap();
+
+        assert_eq!(log.bytes(), PAYLOAD);
+        assert!(base.stream.is_some());
+        path.assert_drained(&base);
+        assert!(!base.is_poisoned());
+    }
+
     fn write_step_scripts(max_len: usize) -> Vec<Vec<WriteStep>> {
         const STEPS: &[WriteStep] = &[
             WriteStep::Bytes(0),
@@ -1604,7 +2867,7 @@ mod tests {
 
     #[test]
     fn test_build_pong_frame_structure() {
-        let frame = build_pong_frame();
+        let frame = build_pong_frame(&[]);
         // Byte 0: COMMAND flag (0x04)
         assert_eq!(frame[0], 0x04, "PONG frame must have COMMAND flag");
         // Byte 1: body length = 5 (\x04PONG = 1-byte length prefix + "PONG")
@@ -1613,30 +2876,135 @@ mod tests {
         assert_eq!(&frame[2..7], b"\x04PONG", "PONG body must be \\x04PONG");
     }
 
-    #[test]
-    fn test_is_ping_payload() {
-        assert!(is_ping_payload(b"\x04PING\x00\x0A"));
-        assert!(!is_ping_payload(b"\x04PONG"));
-        assert!(!is_ping_payload(b"\x05READY"));
-        assert!(!is_ping_payload(b""));
+    fn heartbeat(body: &'static [u8]) -> crate::codec::Result<Command> {
+        parse_command(&crate::codec::ZmtpFrame {
+            flags: crate::utils::FLAG_COMMAND,
+            payload: Bytes::from_static(body),
+        })
     }
 
     #[test]
-    fn test_is_ping_payload_rejects_context_over_16_octets() {
-        assert!(!is_ping_payload(b"\x04PING\x00\x0A12345678901234567"));
-    }
+    fn test_pong_echoes_ping_context() {
+        let Ok(Command::Ping { ttl, context }) = heartbeat(b"\x04PING\x00\x0Actx-42") else {
+            panic!("not a PING");
+        };
+        assert_eq!(ttl, 10);
+        assert_eq!(context, &b"ctx-42"[..]);
+        assert!(matches!(
+            heartbeat(b"\x04PING\x00\x0A"),
+            Ok(Command::Ping { context, .. }) if context.is_empty()
+        ));
 
-    #[test]
-    fn test_is_pong_payload() {
-        assert!(is_pong_payload(b"\x04PONG"));
-        assert!(!is_pong_payload(b"\x04PING\x00\x0A"));
-        assert!(!is_pong_payload(b"\x05READY"));
-        assert!(!is_pong_payload(b""));
+        let frame = build_pong_frame(&context);
+        assert_eq!(frame[1], 11, "PONG body is the name plus the context");
+        assert_eq!(&frame[2..], b"\x04PONGctx-42");
     }
 
     #[test]
-    fn test_is_pong_payload_rejects_context_over_16_octets() {
-        assert!(!is_pong_payload(b"\x04PONG12345678901234567"));
+    fn test_heartbeat_commands_are_told_apart() {
+        assert!(matches!(heartbeat(b"\x04PONG"), Ok(Command::Pong(_))));
+        assert!(matches!(heartbeat(b"\x05READY"), Ok(Command::Ready(_))));
+        // PING needs its TTL.
+        assert!(heartbeat(b"\x04PING").is_err());
+    }
+
+    #[test]
+    fn test_heartbeat_context_over_16_octets_is_rejected() {
+        assert!(heartbeat(b"\x04PING\x00\x0A12345678901234567").is_err());
+        assert!(heartbeat(b"\x04PONG12345678901234567").is_err());
+    }
+
+    fn snapshot_with_secrets() -> crate::DiagnosticsSnapshot {
+        let options = SocketOptions::default()
+            .with_plain_credentials("alice", "super-secret-password")
+            .with_curve_keypair([1; 32], [7; 32])
+            .with_recv_hwm(2000);
+        let base = SocketBase::with_endpoint(
+            ScriptedWriteStream::new([]),
+            SocketType::Dealer,
+            Endpoint::Tcp("127.0.0.1:5555".parse().unwrap()),
+            options,
+        );
+        base.snapshot(SocketType::Dealer)
+    }
+
+    #[test]
+    fn snapshot_redacts_secrets() {
+        let snapshot = snapshot_with_secrets();
+
+        assert_eq!(snapshot.schema_version, crate::diagnostics::SCHEMA_VERSION);
+        assert_eq!(snapshot.socket_type, "DEALER");
+        assert_eq!(snapshot.state, "connected");
+        assert!(snapshot.options.iter().any(|o| o.contains("RecvHwm")));
+        let dump = format!("{snapshot:?}");
+        assert!(!dump.contains("super-secret-password"), "{dump}");
+        assert!(!dump.contains("7, 7, 7"), "{dump}");
+        assert!(dump.contains("[REDACTED]"), "{dump}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshot_round_trips_through_json() {
+        let snapshot = snapshot_with_secrets();
+        let json = snapshot.to_json_pretty().unwrap();
+
+        assert!(!json.contains("super-secret-password"), "{json}");
+        assert!(json.contains("\"schema_version\": 1"), "{json}");
+        let back: crate::DiagnosticsSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, snapshot);
+    }
+
+    /// Every command body of 0..=8 bytes, with every leading byte and the
+    /// tail of each command name the dispatcher recognises, must come back
+    /// from `process_frame` and the command parsers as `Ok`/`Err`, never as a
+    /// slicing panic.
+    #[test]
+    fn short_command_bodies_never_panic() {
+        use crate::security::protocol::{parse_error_reason, parse_ready_command};
+        use monocoque_core::subscription::SubscriptionEvent;
+
+        const NAMES: &[&[u8]] = &[
+            b"\x04PING\x00\x0a",
+            b"\x04PONG",
+            b"\x05ERROR\x03",
+            b"\x05READY\x0b",
+            b"\x09SUBSCRIBE",
+            b"\x06CANCEL",
+            b"\x07MESSAGE",
+        ];
+        for len in 0..=8usize {
+            for lead in 0..=u8::MAX {
+                for name in NAMES {
+                    let mut body = Vec::with_capacity(len);
+                    body.extend(std::iter::once(lead).take(len));
+                    body.extend(name.iter().skip(1).take(len.saturating_sub(1)));
+                    body.resize(len, 0);
+                    let body = Bytes::from(body);
+
+                    let _ = parse_command(&crate::codec::ZmtpFrame {
+                        flags: crate::utils::FLAG_COMMAND,
+                        payload: body.clone(),
+                    });
+                    let _ = parse_error_reason(&body);
+                    let _ = parse_ready_command(&body);
+                    let _ = crate::parse_ready_command(&body);
+                    let _ = crate::parse_ready_metadata(&body);
+                    let _ = SubscriptionEvent::from_command(&body);
+                    let _ = SubscriptionEvent::from_bytes(body.clone());
+
+                    let mut base = SocketBase::new(
+                        ScriptedWriteStream::new([]),
+                        SocketType::Dealer,
+                        SocketOptions::default(),
+                    );
+                    base.recv.push(crate::utils::encode_frame(
+                        crate::utils::FLAG_COMMAND,
+                        &body,
+                    ));
+                    let _ = base.process_frame();
+                }
+            }
+        }
     }
 
     // ── Heartbeat state helpers ───────────────────────────────────────────────
@@ -1650,7 +3018,7 @@ mod tests {
         // Full integration is covered by the heartbeat field initialisation
         // verified in the constructor tests below.
         let _ = build_ping_frame(0); // smoke-test: no panic
-        let _ = build_pong_frame(); // smoke-test: no panic
+        let _ = build_pong_frame(&[]); // smoke-test: no panic
     }
 
     /// Verify that the PING frame's TTL is encoded as big-endian in tenths of
@@ -1673,6 +3041,84 @@ mod tests {
         assert_eq!(ttl_max, u16::MAX);
     }
 
+    // ── Write buffer capacity management ─────────────────────────────────────
+
+    const HUGE_BURST: usize = 4 * 1024 * 1024;
+
+    #[test]
+    fn test_send_buffer_returns_to_baseline_between_bursts() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_send_buffer_returns_to_baseline_between_bursts_impl());
+    }
+
+    async fn test_send_buffer_returns_to_baseline_between_bursts_impl() {
+        let options = SocketOptions::default();
+        let baseline = options.write_buffer_size;
+        let mut base = SocketBase::new(ScriptedWriteStream::new([]), SocketType::Dealer, options);
+        let huge = vec![0xAB; HUGE_BURST];
+
+        for _ in 0..5 {
+            base.send_buffer.extend_from_slice(&huge);
+            assert!(base.buffer_stats().send_buffer_capacity >= HUGE_BURST);
+            base.flush_send_buffer().await.unwrap();
+
+            for _ in 0..16 {
+                base.send_buffer.extend_from_slice(PAYLOAD);
+                base.flush_send_buffer().await.unwrap();
+            }
+
+            assert_eq!(base.buffer_stats().send_buffer_capacity, baseline);
+        }
+    }
+
+    #[test]
+    fn test_sustained_large_flushes_keep_capacity() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_sustained_large_flushes_keep_capacity_impl());
+    }
+
+    async fn test_sustained_large_flushes_keep_capacity_impl() {
+        let mut base = SocketBase::new(
+            ScriptedWriteStream::new([]),
+            SocketType::Dealer,
+            SocketOptions::default(),
+        );
+        let large = vec![0xCD; 256 * 1024];
+
+        for _ in 0..8 {
+            base.send_buffer.extend_from_slice(&large);
+            base.flush_send_buffer().await.unwrap();
+        }
+
+        // Once the average catches up with the burst size the buffer is no
+        // longer oversized for the traffic, so it stops being reallocated.
+        let stats = base.buffer_stats();
+        assert!(stats.send_buffer_capacity >= large.len());
+        assert!(stats.avg_flush_bytes > large.len() / 2);
+    }
+
+    #[test]
+    fn test_write_buf_shrink_disabled_with_zero_factor() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_write_buf_shrink_disabled_with_zero_factor_impl());
+    }
+
+    async fn test_write_buf_shrink_disabled_with_zero_factor_impl() {
+        let options = SocketOptions::default().with_write_buffer_shrink_factor(0);
+        let mut base = SocketBase::new(ScriptedWriteStream::new([]), SocketType::Dealer, options);
+
+        base.write_buf.extend_from_slice(&vec![0xEF; HUGE_BURST]);
+        base.write_from_buf().await.unwrap();
+        base.write_buf.extend_from_slice(PAYLOAD);
+        base.write_from_buf().await.unwrap();
+
+        assert!(base.write_buf.is_empty());
+        assert!(base.buffer_stats().write_buffer_capacity >= HUGE_BURST);
+    }
+
     #[test]
     fn test_write_from_buf_retries_short_writes_before_disarming() {
         monocoque_core::rt::LocalRuntime::new()
@@ -1684,6 +3130,65 @@ mod tests {
         assert_short_writes_complete(WritePath::WriteFromBuf, [2, 2]).await;
     }
 
+    #[test]
+    fn test_write_vectored_keeps_order_across_short_writes() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_write_vectored_keeps_order_across_short_writes_impl());
+    }
+
+    async fn test_write_vectored_keeps_order_across_short_writes_impl() {
+        let stream = ScriptedWriteStream::new(byte_steps([1, 2, 3]));
+        let log = stream.log();
+        let mut base = SocketBase::new(stream, SocketType::Dealer, SocketOptions::default());
+        base.send_buffer.extend_from_slice(b"queued|");
+        base.buffered_messages = 1;
+
+        let bufs = [
+            Bytes::from_static(b"head|"),
+            Bytes::new(),
+            Bytes::from_static(b"body"),
+        ];
+        base.write_vectored(&bufs).await.unwrap();
+
+        assert_eq!(log.bytes(), b"queued|head|body");
+        assert!(base.send_buffer.is_empty());
+        assert!(base.stream.is_some());
+        assert!(!base.is_poisoned());
+    }
+
+    #[test]
+    fn test_vectored_and_copied_sends_write_identical_bytes() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_vectored_and_copied_sends_write_identical_bytes_impl());
+    }
+
+    async fn test_vectored_and_copied_sends_write_identical_bytes_impl() {
+        // Short and long headers, an empty frame, and a 64 KiB body.
+        let msg = [
+            Bytes::from_static(b"id"),
+            Bytes::new(),
+            Bytes::from(vec![0x5A; 300]),
+            Bytes::from(vec![0xA5; 64 * 1024]),
+        ];
+        let msg = &msg;
+        let wire = |threshold| async move {
+            let stream = ScriptedWriteStream::new([]);
+            let log = stream.log();
+            let options = SocketOptions::default().with_vectored_write_threshold(threshold);
+            let mut base = SocketBase::new(stream, SocketType::Dealer, options);
+            assert_eq!(base.should_vectored_write(msg), threshold == 0);
+            base.send_message(msg).await.unwrap();
+            log.bytes()
+        };
+
+        let mut expected = BytesMut::new();
+        crate::codec::encode_multipart(msg, &mut expected);
+        assert_eq!(wire(usize::MAX).await, expected);
+        assert_eq!(wire(0).await, expected);
+    }
+
     #[test]
     fn test_flush_send_buffer_retries_short_writes_before_disarming() {
         monocoque_core::rt::LocalRuntime::new()
@@ -1830,20 +3335,68 @@ mod tests {
     }
 
     #[test]
-    fn test_nonblocking_write_from_buf_keeps_buffer_and_health() {
+    fn test_nonblocking_write_dropped_unfinished_poisons() {
         monocoque_core::rt::LocalRuntime::new()
             .unwrap()
-            .block_on(test_nonblocking_write_from_buf_keeps_buffer_and_health_impl());
+            .block_on(test_nonblocking_write_dropped_unfinished_poisons_impl());
     }
 
-    async fn test_nonblocking_write_from_buf_keeps_buffer_and_health_impl() {
-        assert_pre_io_error_preserves_buffer(
+    async fn test_nonblocking_write_dropped_unfinished_poisons_impl() {
+        for path in [WritePath::WriteFromBuf, WritePath::FlushSendBuffer] {
+            let (mut base, log) =
+                socket_with_payload(path, [WriteStep::Stall], nonblocking_options());
+
+            let err = path.write(&mut base).await.unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::WouldBlock, "{path:?}");
+            assert!(log.is_empty());
+            // The dropped write may still complete in the kernel.
+            assert!(base.is_poisoned(), "{path:?}");
+        }
+    }
+
+    #[test]
+    fn test_nonblocking_write_from_buf_writes_when_stream_ready() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_nonblocking_write_from_buf_writes_when_stream_ready_impl());
+    }
+
+    async fn test_nonblocking_write_from_buf_writes_when_stream_ready_impl() {
+        assert_nonblocking_write_completes(WritePath::WriteFromBuf, vec![]).await;
+    }
+
+    #[test]
+    fn test_nonblocking_write_from_buf_queues_tail_after_short_write() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_nonblocking_write_from_buf_queues_tail_after_short_write_impl());
+    }
+
+    async fn test_nonblocking_write_from_buf_queues_tail_after_short_write_impl() {
+        let (mut base, log) = socket_with_payload(
             WritePath::WriteFromBuf,
+            [WriteStep::Bytes(2), WriteStep::Bytes(1)],
             nonblocking_options(),
-            StreamState::Connected,
-            io::ErrorKind::WouldBlock,
-        )
-        .await;
+        );
+
+        // Part of the message is on the wire, so it is sent; the rest waits.
+        base.write_from_buf().await.unwrap();
+        assert_eq!(log.bytes(), &PAYLOAD[..2]);
+        assert_eq!(&base.send_buffer[..], &PAYLOAD[2..]);
+        assert!(!base.is_poisoned());
+
+        // The next message cannot overtake the tail; while the stream stays
+        // full it is refused, untouched.
+        base.write_buf.extend_from_slice(b"next");
+        let err = base.write_from_buf().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(&base.write_buf[..], b"next");
+        assert_eq!(&base.send_buffer[..], &PAYLOAD[3..]);
+        assert!(!base.is_poisoned());
+
+        base.write_from_buf().await.unwrap();
+        assert_eq!(log.bytes(), [PAYLOAD, b"next"].concat());
+        assert!(base.send_buffer.is_empty());
     }
 
     #[test]
@@ -1856,8 +3409,8 @@ mod tests {
     async fn test_write_from_buf_not_connected_does_not_poison_impl() {
         assert_pre_io_error_preserves_buffer(
             WritePath::WriteFromBuf,
+            [],
             SocketOptions::default(),
-            StreamState::Disconnected,
             io::ErrorKind::NotConnected,
         )
         .await;
@@ -1873,28 +3426,47 @@ mod tests {
     async fn test_write_from_buf_not_connected_takes_precedence_over_nonblocking_impl() {
         assert_pre_io_error_preserves_buffer(
             WritePath::WriteFromBuf,
+            [],
             nonblocking_options(),
-            StreamState::Disconnected,
             io::ErrorKind::NotConnected,
         )
         .await;
     }
 
     #[test]
-    fn test_nonblocking_flush_keeps_buffer_and_health() {
+    fn test_nonblocking_flush_keeps_tail_after_short_write() {
         monocoque_core::rt::LocalRuntime::new()
             .unwrap()
-            .block_on(test_nonblocking_flush_keeps_buffer_and_health_impl());
+            .block_on(test_nonblocking_flush_keeps_tail_after_short_write_impl());
     }
 
-    async fn test_nonblocking_flush_keeps_buffer_and_health_impl() {
-        assert_pre_io_error_preserves_buffer(
+    async fn test_nonblocking_flush_keeps_tail_after_short_write_impl() {
+        let (mut base, log) = socket_with_payload(
             WritePath::FlushSendBuffer,
+            [WriteStep::Bytes(2)],
             nonblocking_options(),
-            StreamState::Connected,
-            io::ErrorKind::WouldBlock,
-        )
-        .await;
+        );
+
+        let err = base.flush_send_buffer().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(&base.send_buffer[..], &PAYLOAD[2..]);
+        assert!(base.stream.is_some());
+        assert!(!base.is_poisoned());
+
+        base.flush_send_buffer().await.unwrap();
+        assert_eq!(log.bytes(), PAYLOAD);
+        WritePath::FlushSendBuffer.assert_drained(&base);
+    }
+
+    #[test]
+    fn test_nonblocking_flush_writes_when_stream_ready() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_nonblocking_flush_writes_when_stream_ready_impl());
+    }
+
+    async fn test_nonblocking_flush_writes_when_stream_ready_impl() {
+        assert_nonblocking_write_completes(WritePath::FlushSendBuffer, vec![]).await;
     }
 
     #[test]
@@ -1907,8 +3479,8 @@ mod tests {
     async fn test_flush_send_buffer_not_connected_keeps_buffer_and_health_impl() {
         assert_pre_io_error_preserves_buffer(
             WritePath::FlushSendBuffer,
+            [],
             SocketOptions::default(),
-            StreamState::Disconnected,
             io::ErrorKind::NotConnected,
         )
         .await;
@@ -1924,10 +3496,350 @@ mod tests {
     async fn test_flush_send_buffer_not_connected_takes_precedence_over_nonblocking_impl() {
         assert_pre_io_error_preserves_buffer(
             WritePath::FlushSendBuffer,
+            [],
             nonblocking_options(),
-            StreamState::Disconnected,
             io::ErrorKind::NotConnected,
         )
         .await;
     }
+
+    fn timed_options() -> SocketOptions {
+        SocketOptions {
+            send_timeout: Some(std::time::Duration::from_millis(20)),
+            ..SocketOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_timed_flush_keeps_unsent_tail_and_health() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_timed_flush_keeps_unsent_tail_and_health_impl());
+    }
+
+    async fn test_timed_flush_keeps_unsent_tail_and_health_impl() {
+        // The deadline passes while the first write runs, but that write
+        // completes, so no write is in flight when the flush gives up.
+        let (mut base, log) = socket_with_payload(
+            WritePath::FlushSendBuffer,
+            [WriteStep::Slow(2, std::time::Duration::from_millis(30))],
+            timed_options(),
+        );
+
+        let err = base.flush_send_buffer().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(&base.send_buffer[..], &PAYLOAD[2..]);
+        assert!(base.stream.is_some());
+        assert!(!base.is_poisoned());
+
+        // The stall has cleared: the next flush resumes where the last stopped.
+        base.flush_send_buffer().await.unwrap();
+        assert_eq!(log.bytes(), PAYLOAD);
+        WritePath::FlushSendBuffer.assert_drained(&base);
+    }
+
+    #[test]
+    fn test_timed_flush_poisons_when_a_write_is_dropped() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_timed_flush_poisons_when_a_write_is_dropped_impl());
+    }
+
+    async fn test_timed_flush_poisons_when_a_write_is_dropped_impl() {
+        let (mut base, log) = socket_with_payload(
+            WritePath::FlushSendBuffer,
+            [WriteStep::Bytes(2), WriteStep::Stall],
+            timed_options(),
+        );
+
+        let err = base.flush_send_buffer().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(base.is_poisoned());
+
+        // Nothing is resent over a stream whose position is unknown.
+        base.send_buffer.extend_from_slice(PAYLOAD);
+        let err = base.flush_send_buffer().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(log.bytes(), &PAYLOAD[..2]);
+    }
+
+    #[test]
+    fn test_send_command_fails_when_a_write_is_dropped() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_send_command_fails_when_a_write_is_dropped_impl());
+    }
+
+    async fn test_send_command_fails_when_a_write_is_dropped_impl() {
+        let (mut base, _log) = socket_with_payload(
+            WritePath::FlushSendBuffer,
+            [WriteStep::Bytes(PAYLOAD.len() + 1), WriteStep::Stall],
+            SocketOptions::default(),
+        );
+
+        let timeout = Some(std::time::Duration::from_millis(20));
+        let err = base.send_command(b"CMD", timeout).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(base.is_poisoned());
+    }
+
+    #[test]
+    fn test_send_command_withdrawn_when_nothing_written() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_send_command_withdrawn_when_nothing_written_impl());
+    }
+
+    async fn test_send_command_withdrawn_when_nothing_written_impl() {
+        let (mut base, log) = socket_with_payload(
+            WritePath::FlushSendBuffer,
+            [WriteStep::Slow(3, std::time::Duration::from_millis(30))],
+            SocketOptions::default(),
+        );
+
+        let timeout = Some(std::time::Duration::from_millis(20));
+        let err = base.send_command(b"CMD", timeout).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        // Only the pending data's unsent tail remains; the command is gone.
+        assert_eq!(&base.send_buffer[..], &PAYLOAD[3..]);
+        assert!(!base.is_poisoned());
+
+        base.send_command(b"CMD", timeout).await.unwrap();
+        assert_eq!(log.bytes(), [PAYLOAD, b"CMD"].concat());
+        assert!(base.send_buffer.is_empty());
+    }
+
+    #[test]
+    fn test_send_command_committed_once_partly_written() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_send_command_committed_once_partly_written_impl());
+    }
+
+    async fn test_send_command_committed_once_partly_written_impl() {
+        let (mut base, log) = socket_with_payload(
+            WritePath::FlushSendBuffer,
+            [WriteStep::Slow(
+                PAYLOAD.len() + 1,
+                std::time::Duration::from_millis(30),
+            )],
+            SocketOptions::default(),
+        );
+
+        let timeout = Some(std::time::Duration::from_millis(20));
+        base.send_command(b"CMD", timeout).await.unwrap();
+        assert_eq!(&base.send_buffer[..], b"MD");
+
+        base.flush_send_buffer().await.unwrap();
+        assert_eq!(log.bytes(), [PAYLOAD, b"CMD"].concat());
+    }
+
+    fn windowed_socket(
+        window: Option<std::time::Duration>,
+    ) -> (SocketBase<ScriptedWriteStream>, WriteLog) {
+        let stream = ScriptedWriteStream::new([]);
+        let log = stream.log();
+        let options = SocketOptions::default()
+            .with_coalesce_window(window)
+            .with_linger(None);
+        (SocketBase::new(stream, SocketType::Dealer, options), log)
+    }
+
+    fn hwm_socket(
+        steps: impl IntoIterator<Item = WriteStep>,
+        options: SocketOptions,
+    ) -> (SocketBase<ScriptedWriteStream>, WriteLog) {
+        let stream = ScriptedWriteStream::new(steps);
+        let log = stream.log();
+        let options = options
+            .with_coalesce_window(Some(std::time::Duration::from_secs(10)))
+            .with_send_hwm(2)
+            .with_linger(None);
+        (SocketBase::new(stream, SocketType::Dealer, options), log)
+    }
+
+    fn wire(msgs: &[&'static [u8]]) -> Vec<u8> {
+        let mut buf = BytesMut::new();
+        for msg in msgs {
+            crate::codec::encode_multipart(&[Bytes::from_static(msg)], &mut buf);
+        }
+        buf.to_vec()
+    }
+
+    #[test]
+    fn test_coalesce_window_batches_rapid_sends_into_one_write() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_coalesce_window_batches_rapid_sends_into_one_write_impl());
+    }
+
+    async fn test_coalesce_window_batches_rapid_sends_into_one_write_impl() {
+        let (mut base, log) = windowed_socket(Some(std::time::Duration::from_secs(10)));
+
+        for msg in [b"one", b"two", b"six"] {
+            base.send_message(&[Bytes::from_static(msg)]).await.unwrap();
+        }
+        assert!(log.is_empty());
+
+        // close() force-flushes the batch within linger.
+        base.close().await.unwrap();
+        assert_eq!(log.write_count(), 1);
+        assert_eq!(log.bytes(), wire(&[b"one", b"two", b"six"]));
+    }
+
+    #[test]
+    fn test_coalesce_window_writes_once_elapsed() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_coalesce_window_writes_once_elapsed_impl());
+    }
+
+    async fn test_coalesce_window_writes_once_elapsed_impl() {
+        let window = std::time::Duration::from_millis(20);
+        let (mut base, log) = windowed_socket(Some(window));
+
+        base.send_message(&[Bytes::from_static(b"a")])
+            .await
+            .unwrap();
+        base.send_message(&[Bytes::from_static(b"b")])
+            .await
+            .unwrap();
+        assert!(log.is_empty());
+
+        monocoque_core::rt::sleep(window).await;
+        base.send_message(&[Bytes::from_static(b"c")])
+            .await
+            .unwrap();
+        assert_eq!(log.write_count(), 1);
+        assert_eq!(log.bytes(), wire(&[b"a", b"b", b"c"]));
+        assert!(base.send_buffer.is_empty());
+        assert!(base.coalesce_started.is_none());
+    }
+
+    #[test]
+    fn test_send_at_hwm_waits_for_a_slow_peer() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_send_at_hwm_waits_for_a_slow_peer_impl());
+    }
+
+    async fn test_send_at_hwm_waits_for_a_slow_peer_impl() {
+        let pause = std::time::Duration::from_millis(50);
+        let (mut base, log) = hwm_socket([WriteStep::Delay(pause)], SocketOptions::default());
+
+        for msg in [b"a", b"b"] {
+            base.send_message(&[Bytes::from_static(msg)]).await.unwrap();
+        }
+        assert!(log.is_empty());
+
+        // The third message waits for the held two to drain instead of
+        // growing the buffer past the mark.
+        let started = Instant::now();
+        base.send_message(&[Bytes::from_static(b"c")])
+            .await
+            .unwrap();
+        assert!(started.elapsed() >= pause);
+        assert_eq!(log.bytes(), wire(&[b"a", b"b"]));
+        assert_eq!(base.buffered_messages(), 1);
+        assert_eq!(&base.send_buffer[..], &wire(&[b"c"])[..]);
+    }
+
+    #[test]
+    fn test_send_at_hwm_leaves_message_unqueued_on_failure() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_send_at_hwm_leaves_message_unqueued_on_failure_impl());
+    }
+
+    async fn test_send_at_hwm_leaves_message_unqueued_on_failure_impl() {
+        let cases = [
+            (
+                SocketOptions::default().with_hwm_policy(HwmPolicy::Error),
+                io::ErrorKind::WouldBlock,
+            ),
+            (timed_options(), io::ErrorKind::TimedOut),
+        ];
+        for (options, kind) in cases {
+            let (mut base, log) = hwm_socket([WriteStep::Stall], options);
+            for msg in [b"a", b"b"] {
+                base.send_message(&[Bytes::from_static(msg)]).await.unwrap();
+            }
+
+            let err = base
+                .send_message(&[Bytes::from_static(b"c")])
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), kind);
+            assert!(log.is_empty());
+            if kind == io::ErrorKind::TimedOut {
+                // The blocking flush dropped a write in flight.
+                assert!(base.is_poisoned());
+                assert!(base.send_buffer.is_empty());
+            } else {
+                assert_eq!(base.buffered_messages(), 2);
+                assert_eq!(&base.send_buffer[..], &wire(&[b"a", b"b"])[..]);
+                assert!(!base.is_poisoned());
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_timeout_keeps_or_discards_held_batch() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_block_timeout_keeps_or_discards_held_batch_impl());
+    }
+
+    async fn test_block_timeout_keeps_or_discards_held_batch_impl() {
+        let held = wire(&[b"a", b"b"]);
+
+        // The deadline passes during a write that completes: nothing is in
+        // flight, so the unwritten part of the held batch stays queued.
+        let slow = WriteStep::Slow(1, std::time::Duration::from_millis(40));
+        let (mut base, log) = hwm_socket([slow], timed_options());
+        for msg in [b"a", b"b"] {
+            base.send_message(&[Bytes::from_static(msg)]).await.unwrap();
+        }
+        let err = base
+            .send_message(&[Bytes::from_static(b"c")])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(!base.is_poisoned());
+        assert_eq!(base.buffered_messages(), 2);
+        assert_eq!(log.bytes(), held[..1]);
+        assert_eq!(&base.send_buffer[..], &held[1..]);
+
+        // The deadline drops a write in flight: the socket is poisoned and
+        // the held batch, accepted by the first two sends, is discarded.
+        let (mut base, _log) = hwm_socket([WriteStep::Stall], timed_options());
+        for msg in [b"a", b"b"] {
+            base.send_message(&[Bytes::from_static(msg)]).await.unwrap();
+        }
+        let err = base
+            .send_message(&[Bytes::from_static(b"c")])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(base.is_poisoned());
+        assert_eq!(base.buffered_messages(), 0);
+        assert!(base.send_buffer.is_empty());
+    }
+
+    #[test]
+    fn test_no_coalesce_window_writes_each_send() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(test_no_coalesce_window_writes_each_send_impl());
+    }
+
+    async fn test_no_coalesce_window_writes_each_send_impl() {
+        let (mut base, log) = windowed_socket(None);
+
+        for msg in [b"one", b"two", b"six"] {
+            base.send_message(&[Bytes::from_static(msg)]).await.unwrap();
+        }
+        assert_eq!(log.write_count(), 3);
+        assert_eq!(log.bytes(), wire(&[b"one", b"two", b"six"]));
+    }
 }
@@ -1,6 +1,10 @@
+use crate::greeting::ProbeKind;
 use bytes::{Buf, Bytes, BytesMut};
 use monocoque_core::buffer::SegmentedBuffer;
+use monocoque_core::options::SocketOptions;
+use smallvec::SmallVec;
 use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
 use thiserror::Error;
 
 use monocoque_core::config::STAGING_BUF_INITIAL_CAP;
@@ -17,16 +21,105 @@ pub enum ZmtpError {
     #[error("Protocol violation: frame size too large")]
     SizeTooLarge,
 
+    /// A frame header declared a body larger than `max_msg_size`. Raised
+    /// from the header alone, before any of the body is buffered.
+    #[error("Frame of {declared} bytes exceeds the {limit}-byte limit")]
+    FrameTooLarge { declared: u64, limit: usize },
+
+    /// The data frames of one multipart message add up to more than
+    /// `max_multipart_size`; `declared` counts the frame being refused.
+    #[error("Multipart message of {declared} bytes exceeds the {limit}-byte limit")]
+    MessageTooLarge { declared: u64, limit: usize },
+
     #[error("Protocol violation")]
     Protocol,
 
+    /// The peer did not open with the ZMTP signature; `kind` is what it
+    /// looked like instead.
+    #[error("Peer is not speaking ZMTP ({kind} probe)")]
+    NotZmtp { kind: ProbeKind },
+
+    /// The peer's greeting announces a revision older than ZMTP 3.0, whose
+    /// greeting is shorter and would never complete ours.
+    #[error("Peer speaks ZMTP revision {major}, older than the required 3.0")]
+    UnsupportedVersion { major: u8 },
+
+    /// The peer's 64-byte greeting is malformed.
+    #[error("Invalid greeting: {reason}")]
+    InvalidGreeting { reason: &'static str },
+
+    /// A command body does not follow the ZMTP command grammar.
+    #[error("Malformed {command} command: {reason}")]
+    MalformedCommand {
+        command: String,
+        reason: &'static str,
+    },
+
     #[error("Authentication failed")]
     AuthenticationFailed,
+
+    /// The peer's greeting names a different security mechanism than ours.
+    #[error("Security mechanism mismatch: local {local}, peer {peer}")]
+    MechanismMismatch { local: String, peer: String },
+
+    /// Both peers claim the server role for PLAIN or CURVE, which needs
+    /// exactly one server.
+    #[error("Both peers are {mechanism} servers")]
+    AsServerConflict { mechanism: String },
+
+    /// The peer sent a ZMTP ERROR command (for example a ZAP denial) and is
+    /// closing the connection.
+    #[error("Peer rejected the connection: {reason}")]
+    PeerError { reason: String },
+
+    /// A data frame is larger than `stream_threshold` and must be received
+    /// with `recv_frame_streaming()`. The frame stays queued and the
+    /// connection stays usable.
+    #[error("Frame of {len} bytes exceeds stream_threshold; receive it with recv_frame_streaming")]
+    StreamingRequired { len: u64 },
 }
 
 impl From<ZmtpError> for io::Error {
     fn from(err: ZmtpError) -> Self {
-        Self::new(io::ErrorKind::InvalidData, err)
+        let kind = match err {
+            ZmtpError::PeerError { .. } => io::ErrorKind::ConnectionRefused,
+            _ => io::ErrorKind::InvalidData,
+        };
+        Self::new(kind, err)
+    }
+}
+
+/// Classify an `io::Error` from a socket into the public
+/// [`Error`](monocoque_core::error::Error).
+///
+/// Errors wrapping a [`ZmtpError`] or a
+/// [`HandshakeFailed`](crate::handshake::HandshakeFailed) are sorted by what
+/// went wrong; any other error is classified by its kind.
+pub fn classify_error(err: io::Error) -> monocoque_core::error::Error {
+    use monocoque_core::error::Error;
+
+    type Class = fn(io::Error) -> Error;
+    let class = err.get_ref().and_then(|inner| -> Option<Class> {
+        if inner.is::<crate::handshake::HandshakeFailed>() {
+            return Some(Error::Handshake);
+        }
+        Some(match inner.downcast_ref::<ZmtpError>()? {
+            ZmtpError::AuthenticationFailed => Error::Auth,
+            ZmtpError::SizeTooLarge
+            | ZmtpError::FrameTooLarge { .. }
+            | ZmtpError::MessageTooLarge { .. } => Error::MessageTooLarge,
+            ZmtpError::NotZmtp { .. }
+            | ZmtpError::UnsupportedVersion { .. }
+            | ZmtpError::InvalidGreeting { .. }
+            | ZmtpError::MechanismMismatch { .. }
+            | ZmtpError::AsServerConflict { .. }
+            | ZmtpError::PeerError { .. } => Error::Handshake,
+            _ => Error::Protocol,
+        })
+    });
+    match class {
+        Some(class) => class(err),
+        None => Error::from(err),
     }
 }
 
@@ -40,6 +133,45 @@ impl From<io::Error> for ZmtpError {
 /// Result type alias for ZMTP operations
 pub type Result<T> = std::result::Result<T, ZmtpError>;
 
+static PROTOCOL_VIOLATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Number of peer protocol violations rejected by this process so far.
+///
+/// Counts malformed frames refused by [`ZmtpDecoder`] and greetings or READY
+/// commands refused during the handshake. Failed I/O and timeouts are not
+/// violations and are not counted. The counter is process-wide and only
+/// grows, so compare snapshots rather than absolute values.
+#[must_use]
+pub fn protocol_violations() -> u64 {
+    PROTOCOL_VIOLATIONS.load(Ordering::Relaxed)
+}
+
+/// Count a peer protocol violation and pass the error through.
+pub(crate) fn record_violation(err: ZmtpError) -> ZmtpError {
+    PROTOCOL_VIOLATIONS.fetch_add(1, Ordering::Relaxed);
+    err
+}
+
+static NON_ZMTP_PROBES: [AtomicU64; ProbeKind::ALL.len()] =
+    [const { AtomicU64::new(0) }; ProbeKind::ALL.len()];
+
+/// Number of connections of the given kind closed for not speaking ZMTP.
+///
+/// A connection whose first bytes are not the ZMTP signature is dropped as
+/// soon as those bytes arrive, before any security mechanism runs, and
+/// counted here by [`ProbeKind`] as well as in [`protocol_violations`].
+/// Process-wide and only grows, like [`protocol_violations`].
+#[must_use]
+pub fn non_zmtp_probes(kind: ProbeKind) -> u64 {
+    NON_ZMTP_PROBES[kind as usize].load(Ordering::Relaxed)
+}
+
+/// Count a non-ZMTP connection and return the error closing it.
+pub(crate) fn record_probe(kind: ProbeKind) -> ZmtpError {
+    NON_ZMTP_PROBES[kind as usize].fetch_add(1, Ordering::Relaxed);
+    record_violation(ZmtpError::NotZmtp { kind })
+}
+
 /// A decoded ZMTP frame
 #[derive(Debug, Clone)]
 pub struct ZmtpFrame {
@@ -59,13 +191,117 @@ impl ZmtpFrame {
     }
 }
 
+/// A ZMTP command, parsed from a command frame by [`parse_command`].
+///
+/// Payloads are slices of the frame, not copies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// ZMTP 3.1 `SUBSCRIBE`, carrying the topic prefix.
+    Subscribe(Bytes),
+    /// ZMTP 3.1 `CANCEL`, carrying the topic prefix.
+    Cancel(Bytes),
+    /// `PING`: the TTL in tenths of a second and the context to echo back.
+    Ping { ttl: u16, context: Bytes },
+    /// `PONG`, carrying the echoed context.
+    Pong(Bytes),
+    /// `READY`, carrying the encoded metadata properties.
+    Ready(Bytes),
+    /// `ERROR`, carrying the reason text.
+    Error(Bytes),
+    /// Any other command (such as CURVE `MESSAGE`): its name and the data
+    /// after it.
+    Unknown { name: Bytes, data: Bytes },
+}
+
+/// Longest PING context and PONG echo (RFC 37).
+const MAX_PING_CONTEXT: usize = 16;
+
+/// Parse a command frame into a [`Command`].
+///
+/// The body is read as RFC 23 frames it: a name-length byte, the name, then
+/// the command data. Names this module does not know come back as
+/// [`Command::Unknown`] so callers can skip them.
+///
+/// # Errors
+///
+/// [`ZmtpError::Protocol`] for a data frame, and
+/// [`ZmtpError::MalformedCommand`] when the name does not fit the body or a
+/// known command's data does not follow its grammar.
+pub fn parse_command(frame: &ZmtpFrame) -> Result<Command> {
+    if !frame.is_command() {
+        return Err(ZmtpError::Protocol);
+    }
+    let body = &frame.payload;
+    // Checked first: libzmq's PLAIN ERROR has a garbled name-length byte.
+    if let Some(reason) = crate::security::protocol::parse_error_reason(body) {
+        return Ok(Command::Error(body.slice_ref(reason)));
+    }
+    let malformed = |command: &[u8], reason| ZmtpError::MalformedCommand {
+        command: String::from_utf8_lossy(command).into_owned(),
+        reason,
+    };
+    let Some((&name_len, rest)) = body.split_first() else {
+        return Err(malformed(b"", "empty command frame"));
+    };
+    if name_len == 0 {
+        return Err(malformed(b"", "empty command name"));
+    }
+    let Some((name, data)) = rest.split_at_checked(usize::from(name_len)) else {
+        return Err(malformed(b"", "command name longer than the frame"));
+    };
+    let data = body.slice_ref(data);
+    match name {
+        b"SUBSCRIBE" => Ok(Command::Subscribe(data)),
+        b"CANCEL" => Ok(Command::Cancel(data)),
+        b"PING" => {
+            let Some(&[hi, lo]) = data.first_chunk::<2>() else {
+                return Err(malformed(name, "missing TTL"));
+            };
+            if data.len() - 2 > MAX_PING_CONTEXT {
+                return Err(malformed(name, "context longer than 16 bytes"));
+            }
+            Ok(Command::Ping {
+                ttl: u16::from_be_bytes([hi, lo]),
+                context: data.slice(2..),
+            })
+        }
+        b"PONG" if data.len() > MAX_PING_CONTEXT => {
+            Err(malformed(name, "context longer than 16 bytes"))
+        }
+        b"PONG" => Ok(Command::Pong(data)),
+        b"READY" => Ok(Command::Ready(data)),
+        b"ERROR" => Err(malformed(name, "reason length does not match")),
+        _ => Ok(Command::Unknown {
+            name: body.slice_ref(name),
+            data,
+        }),
+    }
+}
+
+/// Parsed header of the frame at the front of a buffer.
+struct ParsedHeader {
+    flags: u8,
+    header_len: usize,
+    body_len: u64,
+}
+
 /// Stateful ZMTP decoder
 ///
 /// Fast path:
-/// - Entire frame present → zero-copy slice
+/// - Entire frame present, body within one read segment → zero-copy slice
+/// - Entire frame present, body across segments → one copy
 ///
 /// Slow path:
 /// - Fragmented frame → reassemble into `BytesMut`
+///
+/// [`zero_copy_frames`](Self::zero_copy_frames) and
+/// [`copied_frames`](Self::copied_frames) count which path each frame took.
+///
+/// Streaming path:
+/// - Data frame above the stream threshold → [`ZmtpError::StreamingRequired`]
+///   from [`decode`](Self::decode); the body is then taken in chunks with
+///   [`begin_stream`](Self::begin_stream) and
+///   [`take_stream_chunk`](Self::take_stream_chunk)
 pub struct ZmtpDecoder {
     // Fragmentation state
     pending_flags: Option<u8>,
@@ -73,6 +309,21 @@ pub struct ZmtpDecoder {
     staging: BytesMut,
     /// Maximum allowed frame body size (enforcement of ZMQ_MAXMSGSIZE)
     max_frame_size: usize,
+    /// Explicit `max_msg_size`, the only cap applied to streamed frames.
+    explicit_max: Option<usize>,
+    /// Data frames larger than this are streamed instead of buffered.
+    stream_threshold: Option<usize>,
+    /// Body bytes of the streamed frame not yet taken.
+    stream_remaining: u64,
+    /// Cap on the summed data-frame bodies of one multipart message.
+    max_multipart_size: Option<usize>,
+    /// Body bytes of the current message's data frames so far, including the
+    /// frame being reassembled.
+    message_len: u64,
+    /// Frames returned as slices of a read segment.
+    zero_copy_frames: u64,
+    /// Frames copied because their body spanned read segments.
+    copied_frames: u64,
 }
 
 impl Default for ZmtpDecoder {
@@ -89,6 +340,13 @@ impl ZmtpDecoder {
             expected_body_len: 0,
             staging: BytesMut::with_capacity(STAGING_BUF_INITIAL_CAP),
             max_frame_size: 64 * 1024 * 1024, // 64MB default (generous but bounded)
+            explicit_max: None,
+            stream_threshold: None,
+            stream_remaining: 0,
+            max_multipart_size: None,
+            message_len: 0,
+            zero_copy_frames: 0,
+            copied_frames: 0,
         }
     }
 
@@ -96,17 +354,43 @@ impl ZmtpDecoder {
     #[must_use]
     pub fn with_max_frame_size(max_frame_size: usize) -> Self {
         Self {
-            pending_flags: None,
-            expected_body_len: 0,
-            staging: BytesMut::with_capacity(STAGING_BUF_INITIAL_CAP),
             max_frame_size,
+            explicit_max: Some(max_frame_size),
+            ..Self::new()
         }
     }
 
+    /// Create a decoder enforcing the `max_msg_size` and `max_multipart_size`
+    /// limits in `options`.
+    #[must_use]
+    pub fn with_size_limits(options: &SocketOptions) -> Self {
+        let mut decoder = options
+            .max_msg_size
+            .map_or_else(Self::new, Self::with_max_frame_size);
+        decoder.set_max_multipart_size(options.max_multipart_size);
+        decoder
+    }
+
     /// Update the maximum body length enforced by the decoder.
     #[inline]
     pub fn set_max_body_len(&mut self, max_body_len: Option<usize>) {
         self.max_frame_size = max_body_len.unwrap_or(64 * 1024 * 1024);
+        self.explicit_max = max_body_len;
+    }
+
+    /// Stream data frames whose body exceeds `threshold` bytes.
+    #[inline]
+    pub const fn set_stream_threshold(&mut self, threshold: Option<usize>) {
+        self.stream_threshold = threshold;
+    }
+
+    /// Limit the summed data-frame bodies of one multipart message.
+    ///
+    /// Checked against each frame header as it arrives, so a message that
+    /// would grow past `limit` is refused before the frame is buffered.
+    #[inline]
+    pub const fn set_max_multipart_size(&mut self, limit: Option<usize>) {
+        self.max_multipart_size = limit;
     }
 
     /// Check if more message frames are expected (partial multipart message).
@@ -120,7 +404,37 @@ impl ZmtpDecoder {
     #[inline]
     pub const fn has_more(&self) -> bool {
         // Decoder is expecting more data for current frame
-        self.pending_flags.is_some()
+        self.pending_flags.is_some() || self.stream_remaining > 0
+    }
+
+    /// Allocated capacity of the reassembly buffer for fragmented frames.
+    #[inline]
+    pub fn staging_capacity(&self) -> usize {
+        self.staging.capacity()
+    }
+
+    /// Body bytes of the streamed frame that have not been taken yet.
+    #[inline]
+    pub const fn stream_remaining(&self) -> u64 {
+        self.stream_remaining
+    }
+
+    /// Frames this decoder returned as a [`Bytes`] view of the read segment
+    /// their body arrived in, with no copy.
+    #[inline]
+    pub const fn zero_copy_frames(&self) -> u64 {
+        self.zero_copy_frames
+    }
+
+    /// Frames this decoder copied into a fresh buffer because their body
+    /// spanned two or more read segments.
+    ///
+    /// A high share next to [`zero_copy_frames`](Self::zero_copy_frames)
+    /// means reads are splitting messages, usually because the read buffer is
+    /// smaller than the typical message.
+    #[inline]
+    pub const fn copied_frames(&self) -> u64 {
+        self.copied_frames
     }
 
     /// Decode a single frame from `src`
@@ -128,8 +442,23 @@ impl ZmtpDecoder {
     /// Returns:
     /// - Ok(Some(frame)) → frame decoded
     /// - Ok(None) → need more data
+    /// - Err(StreamingRequired) → the next frame must be streamed; nothing
+    ///   was consumed
     /// - Err → protocol violation
+    ///
+    /// The unread rest of an abandoned streamed frame is discarded first.
     pub fn decode(&mut self, src: &mut SegmentedBuffer) -> Result<Option<ZmtpFrame>> {
+        // === Skip an abandoned streamed frame ===
+        if self.stream_remaining > 0 {
+            let skip =
+                usize::try_from(self.stream_remaining).map_or(src.len(), |r| r.min(src.len()));
+            src.advance(skip);
+            self.stream_remaining -= skip as u64;
+            if self.stream_remaining > 0 {
+                return Ok(None);
+            }
+        }
+
         // === Reassembly mode ===
         if let Some(flags) = self.pending_flags {
             let needed = self.expected_body_len - self.staging.len();
@@ -145,11 +474,145 @@ impl ZmtpDecoder {
             let payload = self.staging.split().freeze();
             self.pending_flags = None;
             self.expected_body_len = 0;
+            self.copied_frames += 1;
+
+            return Ok(Some(ZmtpFrame { flags, payload }));
+        }
+
+        let Some(ParsedHeader {
+            flags,
+            header_len,
+            body_len,
+        }) = Self::parse_header(src)?
+        else {
+            return Ok(None);
+        };
+
+        if self.must_stream(flags, body_len) {
+            return Err(ZmtpError::StreamingRequired { len: body_len });
+        }
+        let body_len = match usize::try_from(body_len) {
+            Ok(len) if len <= self.max_frame_size => len,
+            _ => {
+                return Err(record_violation(ZmtpError::FrameTooLarge {
+                    declared: body_len,
+                    limit: self.max_frame_size,
+                }));
+            }
+        };
+        if (flags & 0x04) == 0 {
+            self.admit_message_bytes(flags, body_len as u64)?;
+        }
 
+        let total_len = header_len + body_len;
+
+        // === Fast path: entire frame present ===
+        if src.len() >= total_len {
+            // Only the body decides: a header split from its body by a read
+            // boundary still leaves the body sliceable.
+            src.advance(header_len);
+            if src.front_chunk().len() >= body_len {
+                self.zero_copy_frames += 1;
+            } else {
+                self.copied_frames += 1;
+            }
+            let payload = src.take_bytes(body_len).expect("length checked above");
             return Ok(Some(ZmtpFrame { flags, payload }));
         }
 
-        // === Header parsing ===
+        // === Slow path: fragmentation ===
+        src.advance(header_len);
+        self.pending_flags = Some(flags);
+        self.expected_body_len = body_len;
+        self.staging.clear();
+
+        let available = src.len().min(body_len);
+        if let Some(bytes) = src.take_bytes(available) {
+            self.staging.extend_from_slice(&bytes);
+        }
+
+        Ok(None)
+    }
+
+    /// Consume the header of a frame [`decode`](Self::decode) reported as
+    /// [`ZmtpError::StreamingRequired`] and start streaming its body.
+    ///
+    /// Returns the MORE flag and declared body length, or `None` when the
+    /// front of `src` is not such a frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ZmtpError::FrameTooLarge`] when the frame exceeds an explicit
+    /// `max_msg_size`.
+    pub fn begin_stream(&mut self, src: &mut SegmentedBuffer) -> Result<Option<(bool, u64)>> {
+        if self.stream_remaining > 0 || self.pending_flags.is_some() {
+            return Ok(None);
+        }
+        let Some(header) = Self::parse_header(src)? else {
+            return Ok(None);
+        };
+        if !self.must_stream(header.flags, header.body_len) {
+            return Ok(None);
+        }
+        if let Some(max) = self.explicit_max
+            && header.body_len > max as u64
+        {
+            return Err(record_violation(ZmtpError::FrameTooLarge {
+                declared: header.body_len,
+                limit: max,
+            }));
+        }
+        src.advance(header.header_len);
+        self.stream_remaining = header.body_len;
+        if (header.flags & 0x01) == 0 {
+            self.message_len = 0;
+        }
+        Ok(Some(((header.flags & 0x01) != 0, header.body_len)))
+    }
+
+    /// Take the next chunk of the streamed frame body from `src`.
+    ///
+    /// Never copies: the chunk is a slice of the first buffered segment, so it
+    /// may be shorter than what is buffered. Returns `None` when `src` is
+    /// empty or the body is complete.
+    pub fn take_stream_chunk(&mut self, src: &mut SegmentedBuffer) -> Option<Bytes> {
+        let front = src.front_chunk().len();
+        let n = usize::try_from(self.stream_remaining).map_or(front, |r| r.min(front));
+        if n == 0 {
+            return None;
+        }
+        self.stream_remaining -= n as u64;
+        src.take_bytes(n)
+    }
+
+    /// Add a data frame's body to the running message size, refusing it when
+    /// the message would outgrow `max_multipart_size`. The running size
+    /// restarts after a frame without MORE.
+    fn admit_message_bytes(&mut self, flags: u8, body_len: u64) -> Result<()> {
+        let total = self.message_len.saturating_add(body_len);
+        if let Some(limit) = self.max_multipart_size
+            && total > limit as u64
+        {
+            return Err(record_violation(ZmtpError::MessageTooLarge {
+                declared: total,
+                limit,
+            }));
+        }
+        self.message_len = if (flags & 0x01) == 0 { 0 } else { total };
+        Ok(())
+    }
+
+    /// Whether a frame with these flags and length is streamed.
+    fn must_stream(&self, flags: u8, body_len: u64) -> bool {
+        (flags & 0x04) == 0
+            && self
+                .stream_threshold
+                .is_some_and(|threshold| body_len > threshold as u64)
+    }
+
+    /// Parse and validate the header at the front of `src` without consuming
+    /// it. Size limits are left to the caller.
+    fn parse_header(src: &SegmentedBuffer) -> Result<Option<ParsedHeader>> {
         if src.len() < 2 {
             return Ok(None);
         }
@@ -166,12 +629,12 @@ impl ZmtpDecoder {
         let flags = hdr[0];
 
         if (flags & 0x05) == 0x05 {
-            return Err(ZmtpError::Protocol);
+            return Err(record_violation(ZmtpError::Protocol));
         }
 
         // Reserved bits must be zero (bits 3-7)
         if (flags & 0xF8) != 0 {
-            return Err(ZmtpError::ReservedBits);
+            return Err(record_violation(ZmtpError::ReservedBits));
         }
 
         let is_long = (flags & 0x02) != 0;
@@ -181,7 +644,6 @@ impl ZmtpDecoder {
             return Ok(None);
         }
 
-        // === Body length ===
         let body_len = if is_long {
             if front.len() >= 9 {
                 hdr.copy_from_slice(&front[..9]);
@@ -193,42 +655,18 @@ impl ZmtpDecoder {
 
             // MSB must be zero in ZMTP 3.x
             if size > 0x7FFF_FFFF_FFFF_FFFF {
-                return Err(ZmtpError::SizeTooLarge);
+                return Err(record_violation(ZmtpError::SizeTooLarge));
             }
-
-            let body_len = size as usize;
-            if body_len > self.max_frame_size {
-                return Err(ZmtpError::SizeTooLarge);
-            }
-            body_len
+            size
         } else {
-            let body_len = hdr[1] as usize;
-            if body_len > self.max_frame_size {
-                return Err(ZmtpError::SizeTooLarge);
-            }
-            body_len
+            u64::from(hdr[1])
         };
 
-        let total_len = header_len + body_len;
-
-        // === Fast path: entire frame present ===
-        if src.len() >= total_len {
-            let payload = src.take_bytes_after_available(header_len, body_len);
-            return Ok(Some(ZmtpFrame { flags, payload }));
-        }
-
-        // === Slow path: fragmentation ===
-        src.advance(header_len);
-        self.pending_flags = Some(flags);
-        self.expected_body_len = body_len;
-        self.staging.clear();
-
-        let available = src.len().min(body_len);
-        if let Some(bytes) = src.take_bytes(available) {
-            self.staging.extend_from_slice(&bytes);
-        }
-
-        Ok(None)
+        Ok(Some(ParsedHeader {
+            flags,
+            header_len,
+            body_len,
+        }))
     }
 }
 
@@ -309,6 +747,94 @@ pub fn write_frame_header(buf: &mut BytesMut, body_len: usize, more: bool) -> us
     }
 }
 
+/// A ZMTP data-frame header: the flags byte and a 1- or 8-byte length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    bytes: [u8; 9],
+    len: u8,
+}
+
+impl FrameHeader {
+    /// Header for a body of `body_len` bytes, with the MORE flag if `more`.
+    /// Uses the same short/long rule as [`write_frame_header`].
+    #[must_use]
+    pub fn new(body_len: usize, more: bool) -> Self {
+        let mut bytes = [0u8; 9];
+        let is_long = body_len >= 256;
+        bytes[0] = u8::from(more) | if is_long { 0x02 } else { 0 };
+        let len = if is_long {
+            bytes[1..].copy_from_slice(&(body_len as u64).to_be_bytes());
+            9
+        } else {
+            bytes[1] = body_len as u8;
+            2
+        };
+        Self { bytes, len }
+    }
+
+    /// The encoded header, 2 or 9 bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..usize::from(self.len)]
+    }
+}
+
+/// A multipart message laid out for a vectored write: one header per frame,
+/// next to the caller's own payloads, so no body is copied.
+///
+/// [`as_iovec`](Self::as_iovec) borrows both as `IoSlice`s for writers such
+/// as `std::io::Write::write_vectored`. Up to eight frames fit inline without
+/// a heap allocation.
+#[derive(Debug, Clone)]
+pub struct FramedMessage<'a> {
+    /// Header of each frame, in order.
+    pub headers: SmallVec<[FrameHeader; 8]>,
+    /// Body of each frame, borrowed from the message.
+    pub payloads: SmallVec<[&'a Bytes; 8]>,
+}
+
+impl<'a> FramedMessage<'a> {
+    /// Lay out `msg`, setting MORE on every frame but the last.
+    #[must_use]
+    pub fn new(msg: &'a [Bytes]) -> Self {
+        let last = msg.len().saturating_sub(1);
+        Self {
+            headers: msg
+                .iter()
+                .enumerate()
+                .map(|(i, frame)| FrameHeader::new(frame.len(), i < last))
+                .collect(),
+            payloads: msg.iter().collect(),
+        }
+    }
+
+    /// Header and payload slices, alternating, in wire order.
+    #[must_use]
+    pub fn as_iovec(&self) -> SmallVec<[io::IoSlice<'_>; 16]> {
+        self.headers
+            .iter()
+            .zip(&self.payloads)
+            .flat_map(|(header, payload)| {
+                [
+                    io::IoSlice::new(header.as_bytes()),
+                    io::IoSlice::new(payload),
+                ]
+            })
+            .collect()
+    }
+
+    /// Total bytes the message occupies on the wire.
+    #[must_use]
+    pub fn wire_len(&self) -> usize {
+        self.headers
+            .iter()
+            .zip(&self.payloads)
+            .map(|(header, payload)| header.as_bytes().len() + payload.len())
+            .sum()
+    }
+}
+
 /// Encode a multipart message directly into a buffer.
 ///
 /// This is a zero-allocation helper for encoding messages without
@@ -386,6 +912,141 @@ mod tests {
         assert!(matches!(decoder.decode(&mut src), Err(ZmtpError::Protocol)));
     }
 
+    #[test]
+    fn frames_within_one_segment_are_sliced_and_straddling_ones_copied() {
+        let mut decoder = ZmtpDecoder::new();
+        let mut src = SegmentedBuffer::new();
+        let mut wire = vec![0x01, 0x04];
+        wire.extend_from_slice(b"abcd");
+        wire.extend_from_slice(&[0x00, 0x04]);
+        wire.extend_from_slice(b"efgh");
+        let segment = Bytes::from(wire);
+        let range = segment.as_ptr_range();
+
+        // Whole first frame plus the second frame's header and half its body.
+        src.push(segment.slice(..10));
+        let sliced = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(&sliced.payload[..], b"abcd");
+        assert!(range.contains(&sliced.payload.as_ptr()));
+        assert_eq!(
+            (decoder.zero_copy_frames(), decoder.copied_frames()),
+            (1, 0)
+        );
+
+        src.push(segment.slice(10..));
+        let joined = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(&joined.payload[..], b"efgh");
+        assert!(!range.contains(&joined.payload.as_ptr()));
+        assert_eq!(
+            (decoder.zero_copy_frames(), decoder.copied_frames()),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn body_after_a_segment_boundary_is_sliced() {
+        let mut decoder = ZmtpDecoder::new();
+        let mut src = SegmentedBuffer::new();
+        // The header ends one segment and the body fills the next.
+        let body = Bytes::from_static(b"payload");
+        src.push(Bytes::from_static(&[0x00, 0x07]));
+        src.push(body.clone());
+
+        let frame = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(frame.payload, body);
+        assert_eq!(frame.payload.as_ptr(), body.as_ptr());
+        assert_eq!(
+            (decoder.zero_copy_frames(), decoder.copied_frames()),
+            (1, 0)
+        );
+    }
+
+    /// Long data-frame header declaring `len` body bytes, with no body.
+    fn long_header(flags: u8, len: u64) -> Bytes {
+        let mut hdr = vec![flags | 0x02];
+        hdr.extend_from_slice(&len.to_be_bytes());
+        Bytes::from(hdr)
+    }
+
+    #[test]
+    fn oversized_header_is_refused_before_buffering() {
+        let mut decoder = ZmtpDecoder::with_max_frame_size(1 << 20);
+        let staging = decoder.staging_capacity();
+        let mut src = SegmentedBuffer::new();
+        // 8 GB declared, followed by the first few body bytes.
+        src.push(long_header(0x00, 8 << 30));
+        src.push(Bytes::from_static(b"payload"));
+
+        let err = decoder.decode(&mut src).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ZmtpError::FrameTooLarge {
+                    declared,
+                    limit: 1_048_576,
+                } if declared == 8 << 30
+            ),
+            "{err:?}"
+        );
+        // Nothing was consumed or reassembled, and no staging space reserved.
+        assert_eq!(src.len(), 9 + 7);
+        assert!(!decoder.has_more());
+        assert_eq!(decoder.staging_capacity(), staging);
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn multipart_total_is_limited_across_frames() {
+        let options = SocketOptions::default().with_max_multipart_size(Some(1000));
+        let mut decoder = ZmtpDecoder::with_size_limits(&options);
+        let mut src = SegmentedBuffer::new();
+
+        // Two 400-byte frames fit; a third pushes the message to 1200 bytes
+        // and is refused from its header alone.
+        for _ in 0..2 {
+            src.push(long_header(0x01, 400));
+            src.push(Bytes::from(vec![0u8; 400]));
+            assert_eq!(
+                decoder.decode(&mut src).unwrap().unwrap().payload.len(),
+                400
+            );
+        }
+        src.push(long_header(0x00, 400));
+        let err = decoder.decode(&mut src).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ZmtpError::MessageTooLarge {
+                    declared: 1200,
+                    limit: 1000
+                }
+            ),
+            "{err:?}"
+        );
+        assert_eq!(src.len(), 9);
+    }
+
+    #[test]
+    fn multipart_total_restarts_with_each_message() {
+        let mut decoder = ZmtpDecoder::new();
+        decoder.set_max_multipart_size(Some(1000));
+        let mut src = SegmentedBuffer::new();
+
+        // Several messages of 600 + 300 bytes: each fits on its own, and a
+        // PING command in the middle of one does not count towards it.
+        for _ in 0..3 {
+            src.push(long_header(0x01, 600));
+            src.push(Bytes::from(vec![0u8; 600]));
+            src.push(Bytes::from_static(b"\x04\x05\x04PING"));
+            src.push(long_header(0x00, 300));
+            src.push(Bytes::from(vec![0u8; 300]));
+            for _ in 0..3 {
+                decoder.decode(&mut src).unwrap().unwrap();
+            }
+        }
+        assert_eq!(src.len(), 0);
+    }
+
     #[test]
     fn encode_sets_long_flag_for_public_large_frame_payload() {
         let frame = ZmtpFrame {
@@ -449,4 +1110,147 @@ mod tests {
 
         assert_eq!(&buf[..], b"prefix");
     }
+
+    #[test]
+    fn framed_message_iovec_matches_encode_multipart() {
+        let msg = vec![
+            Bytes::from_static(b"id"),
+            Bytes::new(),
+            Bytes::from(vec![0x5A; 255]),
+            Bytes::from(vec![0xA5; 64 * 1024]),
+        ];
+        let mut expected = BytesMut::new();
+        encode_multipart(&msg, &mut expected);
+
+        let framed = FramedMessage::new(&msg);
+        let iovec = framed.as_iovec();
+        assert_eq!(iovec.len(), 2 * msg.len());
+        let wire: Vec<u8> = iovec.iter().flat_map(|s| s.iter().copied()).collect();
+        assert_eq!(wire, expected);
+        assert_eq!(framed.wire_len(), expected.len());
+        // Payloads are borrowed, not copied.
+        assert_eq!(iovec[7].as_ptr(), msg[3].as_ptr());
+        assert!(!framed.headers.spilled() && !framed.payloads.spilled());
+    }
+
+    fn command(body: &'static [u8]) -> Result<Command> {
+        parse_command(&ZmtpFrame {
+            flags: 0x04,
+            payload: Bytes::from_static(body),
+        })
+    }
+
+    #[test]
+    fn parse_command_reads_known_commands() {
+        assert_eq!(
+            command(b"\x09SUBSCRIBEweather").unwrap(),
+            Command::Subscribe(Bytes::from_static(b"weather"))
+        );
+        assert_eq!(
+            command(b"\x06CANCEL").unwrap(),
+            Command::Cancel(Bytes::new())
+        );
+        assert_eq!(
+            command(b"\x04PING\x01\x2cctx").unwrap(),
+            Command::Ping {
+                ttl: 300,
+                context: Bytes::from_static(b"ctx")
+            }
+        );
+        assert_eq!(
+            command(b"\x04PONGctx").unwrap(),
+            Command::Pong(Bytes::from_static(b"ctx"))
+        );
+        assert_eq!(
+            command(b"\x05READY\x0bSocket-Type\x00\x00\x00\x03PUB").unwrap(),
+            Command::Ready(Bytes::from_static(b"\x0bSocket-Type\x00\x00\x00\x03PUB"))
+        );
+        assert_eq!(
+            command(b"\x05ERROR\x06denied").unwrap(),
+            Command::Error(Bytes::from_static(b"denied"))
+        );
+        // libzmq 4.3 PLAIN servers garble the name-length byte of ERROR.
+        assert_eq!(
+            command(b"^RROR\x06denied").unwrap(),
+            Command::Error(Bytes::from_static(b"denied"))
+        );
+        assert_eq!(
+            command(b"\x07MESSAGE\x00data").unwrap(),
+            Command::Unknown {
+                name: Bytes::from_static(b"MESSAGE"),
+                data: Bytes::from_static(b"\x00data")
+            }
+        );
+    }
+
+    #[test]
+    fn parse_command_rejects_malformed_frames() {
+        // Data frames are not commands, whatever they contain.
+        let data = ZmtpFrame {
+            flags: 0x00,
+            payload: Bytes::from_static(b"\x09SUBSCRIBEweather"),
+        };
+        assert!(matches!(parse_command(&data), Err(ZmtpError::Protocol)));
+
+        for body in [
+            &b""[..],
+            b"\x00",
+            b"\x09SUBSCRIB",
+            b"\x04PING\x01",
+            b"\x04PING\x00\x0a01234567890123456",
+            b"\x04PONG01234567890123456",
+            b"\x05ERROR\x09short",
+        ] {
+            assert!(
+                matches!(
+                    parse_command(&ZmtpFrame {
+                        flags: 0x04,
+                        payload: Bytes::from_static(body),
+                    }),
+                    Err(ZmtpError::MalformedCommand { .. })
+                ),
+                "{body:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn classify_error_sorts_protocol_failures() {
+        use crate::handshake::handshake_error;
+        use monocoque_core::error::Error;
+
+        let too_large = ZmtpError::FrameTooLarge {
+            declared: 1024,
+            limit: 16,
+        };
+        assert!(matches!(
+            classify_error(too_large.into()),
+            Error::MessageTooLarge(_)
+        ));
+        assert!(matches!(
+            classify_error(ZmtpError::Protocol.into()),
+            Error::Protocol(_)
+        ));
+        assert!(matches!(
+            classify_error(handshake_error(ZmtpError::AuthenticationFailed)),
+            Error::Auth(_)
+        ));
+        assert!(matches!(
+            classify_error(handshake_error(ZmtpError::PeerError {
+                reason: "denied".into()
+            })),
+            Error::Handshake(_)
+        ));
+
+        // The same decoder error means a failed handshake when it is raised
+        // during one, and keeps its message.
+        let err = classify_error(handshake_error(ZmtpError::Protocol));
+        assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+        assert_eq!(err.to_string(), "Handshake failed: Protocol violation");
+
+        let reset = io::Error::from(io::ErrorKind::ConnectionReset);
+        assert!(matches!(classify_error(reset), Error::Io(_)));
+        let timed_out = io::Error::new(io::ErrorKind::TimedOut, "recv timed out");
+        assert!(matches!(classify_error(timed_out), Error::Timeout(_)));
+    }
 }
@@ -10,14 +10,20 @@
 
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::error::TrySendError;
+use monocoque_core::options::{HwmPolicy, OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use smallvec::SmallVec;
 use std::io;
 use std::time::Duration;
-use tracing::{debug, trace};
-
-use crate::{base::SocketBase, handshake::perform_handshake_with_options, session::SocketType};
+use tracing::{Instrument, debug, debug_span, trace};
+
+use crate::frame_reader::FrameReader;
+use crate::{
+    base::SocketBase,
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use monocoque_core::endpoint::Endpoint;
 
 /// Direct-stream DEALER socket with optional auto-reconnection support.
@@ -30,6 +36,9 @@ where
     /// Accumulated frames for current multipart message
     /// SmallVec avoids heap allocation for 1-4 frame messages (common case)
     frames: SmallVec<[Bytes; 4]>,
+    /// Messages buffered since the last successful flush, with their
+    /// sequence numbers, kept for the `resend_on_reconnect` option.
+    unflushed: Vec<(u64, Vec<Bytes>)>,
 }
 
 impl<S> DealerSocket<S>
@@ -92,17 +101,22 @@ where
     pub async fn with_options(mut stream: S, options: SocketOptions) -> io::Result<Self> {
         debug!("[DEALER] Creating new direct DEALER socket");
 
+        // Reject an invalid routing id before anything is sent
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+
         // Perform ZMTP handshake with timeout
         debug!("[DEALER] Performing ZMTP handshake...");
         let handshake_result = perform_handshake_with_options(
             &mut stream,
             SocketType::Dealer,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -114,9 +128,13 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Dealer, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
+        base.sequenced = handshake_result.sequenced;
         Ok(Self {
             base,
             frames: SmallVec::new(),
+            unflushed: Vec::new(),
         })
     }
 
@@ -124,7 +142,57 @@ where
 
     /// Receive a message.
     pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        let mut msg = Vec::new();
+        Ok(self.recv_into(&mut msg).await?.then_some(msg))
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        }))
+    }
+
+    /// Receive a message into `msg`, reusing its allocation.
+    ///
+    /// `msg` is cleared first and then filled with the message's frames.
+    /// Returns `Ok(false)` if the connection closed, leaving `msg` empty.
+    /// The frames themselves are shared as with [`recv`](Self::recv); only
+    /// the outer `Vec` is reused across calls.
+    ///
+    /// With the `conflate` option set, every complete message already
+    /// received is decoded and only the newest is returned; older ones are
+    /// dropped whole, never frame by frame.
+    pub async fn recv_into(&mut self, msg: &mut Vec<Bytes>) -> io::Result<bool> {
+        let span = debug_span!(
+            "recv_message",
+            socket_type = "DEALER",
+            frame_count = tracing::field::Empty
+        );
+        let received = self.recv_message(msg).instrument(span.clone()).await;
+        if matches!(received, Ok(true)) {
+            span.record("frame_count", msg.len());
+        }
+        received
+    }
+
+    async fn recv_message(&mut self, msg: &mut Vec<Bytes>) -> io::Result<bool> {
         trace!("[DEALER] Waiting for message");
+        self.recover_if_poisoned().await;
+        msg.clear();
 
         // Read from stream until we have a complete message
         loop {
@@ -139,21 +207,41 @@ where
                     }
                     crate::base::FrameResult::Data(more, payload) => {
                         self.frames.push(payload);
-                        if !more {
-                            let msg: Vec<Bytes> = self.frames.drain(..).collect();
+                        if more {
+                            continue;
+                        }
+                        if self.base.options.conflate {
+                            let mut next: Vec<Bytes> = self.frames.drain(..).collect();
+                            if self.base.admit(&mut next)? {
+                                // The newer message replaces the pending one.
+                                msg.clear();
+                                msg.append(&mut next);
+                            }
+                            continue;
+                        }
+                        msg.extend(self.frames.drain(..));
+                        if self.base.admit(msg)? {
                             trace!("[DEALER] Received {} frames", msg.len());
-                            return Ok(Some(msg));
+                            return Ok(true);
                         }
+                        trace!("[DEALER] Dropped a duplicate message");
+                        msg.clear();
                     }
                 }
             }
 
+            // Conflating: the buffer is drained, deliver the newest message.
+            if !msg.is_empty() {
+                trace!("[DEALER] Received {} frames (conflated)", msg.len());
+                return Ok(true);
+            }
+
             // Need more data - read raw bytes from stream
             let n = self.base.read_raw().await?;
             if n == 0 {
                 // EOF - connection closed
                 trace!("[DEALER] Connection closed");
-                return Ok(None);
+                return Ok(false);
             }
             if self.base.check_heartbeat()? {
                 self.base.flush_send_buffer().await?;
@@ -162,22 +250,53 @@ where
         }
     }
 
+    /// Receive the next frame as a [`FrameReader`], streaming frames larger
+    /// than the `stream_threshold` option instead of buffering them.
+    ///
+    /// Call this after `recv()` fails with
+    /// [`ZmtpError::StreamingRequired`](crate::codec::ZmtpError::StreamingRequired).
+    /// Frames of the interrupted message that `recv()` had already decoded
+    /// come first, then the large frame. Keep calling until a frame without
+    /// [`more`](FrameReader::more) ends the message.
+    ///
+    /// Returns `Ok(None)` when the connection closed between frames.
+    pub async fn recv_frame_streaming(&mut self) -> io::Result<Option<FrameReader<'_, S>>> {
+        if !self.frames.is_empty() {
+            return Ok(Some(FrameReader::buffered(
+                &mut self.base,
+                self.frames.remove(0),
+                true,
+            )));
+        }
+        self.base.recv_frame_streaming().await
+    }
+
     /// Send a message immediately.
     ///
     /// Encodes and sends the message in a single I/O operation.
     /// For high-throughput scenarios, consider using `send_buffered()` + `flush()`
     /// to batch multiple messages.
     pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        trace!("[DEALER] Sending {} frames", msg.len());
-
-        // Encode message into write_buf (with CURVE encryption if active)
-        self.base.encode_message_to_write_buf(&msg)?;
+        let span = debug_span!(
+            "send_message",
+            socket_type = "DEALER",
+            frame_count = msg.len(),
+            total_bytes = msg.iter().map(Bytes::len).sum::<usize>()
+        );
+        async {
+            trace!("[DEALER] Sending {} frames", msg.len());
+            self.recover_if_poisoned().await;
 
-        // Delegate to base for writing from write_buf
-        self.base.write_from_buf().await?;
+            // Encode and write (or hold for the coalescing window), with CURVE
+            // encryption if active
+            let seq = self.base.next_sequence();
+            self.base.send_sequenced(seq, &msg).await?;
 
-        trace!("[DEALER] Message sent successfully");
-        Ok(())
+            trace!("[DEALER] Message sent successfully");
+            Ok(())
+        }
+        .instrument(span)
+        .await
     }
 
     /// Send a message to the internal buffer without flushing.
@@ -229,8 +348,43 @@ where
         trace!("[DEALER] Buffering {} frames", msg.len());
 
         // Encode directly into send_buffer (with CURVE encryption if active)
-        self.base.encode_message_to_send_buf(&msg)?;
-        Ok(())
+        self.buffer_message(&msg)
+    }
+
+    /// Queue a message without ever suspending.
+    ///
+    /// Like [`send_buffered`](Self::send_buffered), the message is encoded
+    /// into the send buffer and goes out with the next
+    /// [`flush()`](Self::flush). No write is attempted here: on the
+    /// completion-based runtimes a write cannot finish without yielding to
+    /// the driver, so an event loop that must not suspend queues with
+    /// `try_send` and flushes from a task that may.
+    ///
+    /// # Errors
+    ///
+    /// - [`TrySendError::Full`] when `send_hwm` messages are already queued.
+    /// - [`TrySendError::WouldBlock`] while there is no connection, e.g.
+    ///   during a reconnect.
+    ///
+    /// Both hand the message back. [`TrySendError::Io`] reports a socket
+    /// that needs reconnecting or a message that could not be encoded.
+    pub fn try_send(&mut self, msg: Vec<Bytes>) -> Result<(), TrySendError> {
+        let msg = self.base.try_send_ready(msg)?;
+        trace!("[DEALER] Queueing {} frames", msg.len());
+        self.buffer_message(&msg).map_err(TrySendError::Io)
+    }
+
+    /// Encode `msg` into the send buffer, keeping a copy for
+    /// `resend_on_reconnect`.
+    fn buffer_message(&mut self, msg: &[Bytes]) -> io::Result<()> {
+        let seq = self.base.next_sequence();
+        if self.base.options.resend_on_reconnect {
+            if self.base.buffered_messages == 0 {
+                self.unflushed.clear();
+            }
+            self.unflushed.push((seq, msg.to_vec()));
+        }
+        self.base.buffer_sequenced(seq, msg)
     }
 
     /// Flush all buffered messages to the network.
@@ -239,6 +393,7 @@ where
     pub async fn flush(&mut self) -> io::Result<()> {
         trace!("[DEALER] Flushing {} bytes", self.base.send_buffer.len());
         self.base.flush_send_buffer().await?;
+        self.unflushed.clear();
         trace!("[DEALER] Flush completed");
         Ok(())
     }
@@ -250,9 +405,12 @@ where
     ///
     /// # High Water Mark (HWM)
     ///
-    /// This method checks HWM for each message. If HWM is reached,
-    /// it returns an error. Consider using `send_buffered()` + `flush()`
-    /// for more control over HWM handling.
+    /// Follows `hwm_policy` like [`send`](Self::send). With
+    /// [`HwmPolicy::Error`] a batch that would take the buffer past
+    /// `send_hwm` is refused with `WouldBlock` and nothing is queued. With
+    /// [`HwmPolicy::Block`] the queued messages are flushed whenever the
+    /// buffer reaches `send_hwm`, so the batch goes out in `send_hwm`-sized
+    /// writes.
     ///
     /// # Example
     /// ```rust,no_run
@@ -271,7 +429,8 @@ where
     pub async fn send_batch(&mut self, messages: &[Vec<Bytes>]) -> io::Result<()> {
         trace!("[DEALER] Batching {} messages", messages.len());
 
-        if self.base.options.send_hwm != 0
+        if self.base.options.hwm_policy == HwmPolicy::Error
+            && self.base.options.send_hwm != 0
             && self.base.buffered_messages.saturating_add(messages.len())
                 > self.base.options.send_hwm
         {
@@ -285,7 +444,11 @@ where
         }
 
         for msg in messages {
-            self.base.encode_message_to_send_buf(msg)?;
+            if self.base.hwm_reached() {
+                // HwmPolicy::Block: wait for the queued messages to go out.
+                self.flush().await?;
+            }
+            self.buffer_message(msg)?;
         }
 
         self.flush().await
@@ -297,6 +460,22 @@ where
         self.base.send_buffer.len()
     }
 
+    /// Get the number of currently buffered messages.
+    #[inline]
+    pub fn buffered_messages(&self) -> usize {
+        self.base.buffered_messages()
+    }
+
+    /// Get the current capacities of the socket's userspace buffers.
+    ///
+    /// Write buffers grow to fit the largest burst and are shrunk back to
+    /// `write_buffer_size` after flushes once traffic returns to normal; see
+    /// `SocketOptions::write_buffer_shrink_factor`.
+    #[inline]
+    pub fn buffer_stats(&self) -> monocoque_core::config::BufferStats {
+        self.base.buffer_stats()
+    }
+
     /// Close the socket gracefully, respecting the linger timeout.
     ///
     /// This method attempts to flush any buffered send data before closing.
@@ -320,54 +499,12 @@ where
     /// # }
     /// ```
     pub async fn close(mut self) -> io::Result<()> {
-        let linger = self.base.options.linger;
-
-        // If no data buffered, just drop the socket
-        if self.base.send_buffer.is_empty() {
-            trace!("[DEALER] No buffered data, closing immediately");
-            return Ok(());
-        }
-
         trace!(
             "[DEALER] Closing with {} bytes buffered, linger={:?}",
             self.base.send_buffer.len(),
-            linger
+            self.base.options.linger
         );
-
-        match linger {
-            Some(dur) if dur.is_zero() => {
-                // Linger = 0: discard buffered data immediately
-                debug!(
-                    "[DEALER] Linger=0, discarding {} bytes",
-                    self.base.send_buffer.len()
-                );
-                Ok(())
-            }
-            Some(dur) => {
-                // Linger = timeout: try to flush within timeout
-                use monocoque_core::rt::timeout;
-                match timeout(dur, self.flush()).await {
-                    Ok(Ok(())) => {
-                        debug!("[DEALER] Successfully flushed before close");
-                        Ok(())
-                    }
-                    Ok(Err(e)) => {
-                        debug!("[DEALER] Flush failed: {}", e);
-                        Err(e)
-                    }
-                    Err(_) => {
-                        debug!("[DEALER] Linger timeout expired, closing anyway");
-                        // Timeout expired, but we close gracefully anyway
-                        Ok(())
-                    }
-                }
-            }
-            None => {
-                // Linger = indefinite: block until flushed
-                debug!("[DEALER] Linger=indefinite, flushing all buffered data");
-                self.flush().await
-            }
-        }
+        self.base.close().await
     }
 
     /// Wait for the appropriate reconnection delay based on socket options.
@@ -424,42 +561,61 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// # use monocoque_zmtp::DealerSocket;
     /// # use std::time::Duration;
-    /// # async fn example(mut socket: DealerSocket) {
+    /// # fn example(mut socket: DealerSocket) -> Result<(), Box<dyn std::error::Error>> {
     /// // Change receive timeout dynamically
-    /// socket.options_mut().recv_timeout = Some(Duration::from_secs(10));
+    /// socket.update_options(|o| o.recv_timeout = Some(Duration::from_secs(10)))?;
+    /// # Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
     }
 
-    /// Set socket options (builder-style).
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// # use monocoque_zmtp::DealerSocket;
-    /// # use monocoque_core::options::SocketOptions;
     /// # use std::time::Duration;
-    /// # async fn example(mut socket: DealerSocket) {
-    /// socket.set_options(
-    ///     SocketOptions::default()
-    ///         .with_recv_timeout(Duration::from_secs(5))
-    ///         .with_send_timeout(Duration::from_secs(5))
-    /// );
+    /// # fn example(mut socket: DealerSocket) -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = socket
+    ///     .options()
+    ///     .clone()
+    ///     .with_recv_timeout(Duration::from_secs(5))
+    ///     .with_send_timeout(Duration::from_secs(5));
+    /// socket.set_options(options)?;
+    /// # Ok(())
     /// # }
     /// ```
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 
     /// Get the socket type.
@@ -472,6 +628,74 @@ where
         SocketType::Dealer
     }
 
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` option.
+    #[inline]
+    pub const fn duplicates_dropped(&self) -> u64 {
+        self.base.duplicates_dropped()
+    }
+
+    /// The latest connection attempts (initial connect and reconnects),
+    /// oldest first, with their timestamps and errors.
+    #[inline]
+    pub const fn connect_history(&self) -> &monocoque_core::reconnect::ConnectHistory {
+        self.base.connect_history()
+    }
+
+    /// Multi-line dump of the socket's endpoint, state, non-default options,
+    /// buffer usage and connection history, for logs and bug reports.
+    pub fn diagnostics(&self) -> String {
+        self.base.diagnostics()
+    }
+
+    /// The same state as [`diagnostics`](Self::diagnostics) as a structured
+    /// [`DiagnosticsSnapshot`](crate::DiagnosticsSnapshot), serializable with
+    /// the `serde` feature.
+    pub fn snapshot(&self) -> crate::DiagnosticsSnapshot {
+        self.base.snapshot(SocketType::Dealer)
+    }
+
+    /// Number of reconnection attempts made since the connection was lost.
+    #[inline]
+    pub const fn reconnect_attempt(&self) -> u32 {
+        self.base.reconnect_attempt()
+    }
+
     /// Get the endpoint this socket is connected/bound to, if available.
     ///
     /// Returns `None` if the socket was created from a raw stream.
@@ -519,6 +743,41 @@ where
     pub fn events(&self) -> u32 {
         self.base.events()
     }
+
+    /// Reconnect to the stored endpoint, re-buffering unflushed messages
+    /// when `resend_on_reconnect` is set.
+    async fn reconnect_replaying(&mut self) -> io::Result<()> {
+        let replay = if self.base.buffered_messages > 0 {
+            std::mem::take(&mut self.unflushed)
+        } else {
+            self.unflushed.clear();
+            Vec::new()
+        };
+        if let Err(e) = self.base.try_reconnect(SocketType::Dealer).await {
+            self.unflushed = replay;
+            return Err(e);
+        }
+        self.frames.clear();
+        for (seq, msg) in &replay {
+            self.base.buffer_sequenced(*seq, msg)?;
+        }
+        self.unflushed = replay;
+        Ok(())
+    }
+
+    /// Reconnect a poisoned socket before the next operation when
+    /// [`SocketOptions::auto_recover`] allows it. A failed attempt leaves the
+    /// socket poisoned, so the operation still fails with `BrokenPipe`.
+    async fn recover_if_poisoned(&mut self) {
+        if !self.base.should_auto_recover() {
+            return;
+        }
+        // Boxed: the handshake may call back into a DEALER (ZAP).
+        match Box::pin(self.reconnect_replaying()).await {
+            Ok(()) => debug!("[DEALER] Recovered poisoned socket by reconnecting"),
+            Err(e) => debug!("[DEALER] Automatic recovery failed: {}", e),
+        }
+    }
 }
 
 // Specialized implementation for TCP streams to enable TCP_NODELAY
@@ -582,6 +841,11 @@ impl DealerSocket<TcpStream> {
         addr: impl monocoque_core::rt::ToSocketAddrs,
         options: SocketOptions,
     ) -> io::Result<Self> {
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+        let started_at = std::time::SystemTime::now();
+        let started = std::time::Instant::now();
         let stream = TcpStream::connect(addr).await?;
         let peer_addr = stream.peer_addr()?;
         crate::utils::configure_tcp_stream(&stream, &options, "DEALER")?;
@@ -591,11 +855,11 @@ impl DealerSocket<TcpStream> {
             &mut stream,
             SocketType::Dealer,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -605,12 +869,21 @@ impl DealerSocket<TcpStream> {
         );
 
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
-        let mut base =
-            crate::base::SocketBase::with_endpoint(stream, SocketType::Dealer, endpoint, options);
+        let mut base = crate::base::SocketBase::with_tcp_endpoint(
+            stream,
+            SocketType::Dealer,
+            endpoint,
+            options,
+        );
+        base.record_initial_connect(started_at, started);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
+        base.sequenced = handshake_result.sequenced;
         Ok(Self {
             base,
             frames: smallvec::SmallVec::new(),
+            unflushed: Vec::new(),
         })
     }
 
@@ -665,33 +938,23 @@ impl DealerSocket<TcpStream> {
     ) -> io::Result<Self> {
         // Configure TCP optimizations
         crate::utils::configure_tcp_stream(&stream, &options, "DEALER")?;
-        Self::with_options(stream, options).await
+        let mut socket = Self::with_options(stream, options).await?;
+        socket.base.remember_peer_for_recovery();
+        Ok(socket)
     }
 
     /// Try to reconnect to the stored endpoint.
     ///
     /// Returns Ok(()) if reconnection succeeded, Err otherwise.
-    /// On success, resets the poisoned flag and reconnection state.
+    /// On success, resets the poisoned flag and reconnection state and drops
+    /// any partially received multipart message from the old connection.
+    ///
+    /// With the `resend_on_reconnect` option, messages buffered since the
+    /// last successful flush are buffered again on the new connection under
+    /// their original sequence numbers; call [`flush`](Self::flush) (or use
+    /// [`flush_with_reconnect`](Self::flush_with_reconnect)) to send them.
     pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.base.try_reconnect(SocketType::Dealer).await
-    }
-
-    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
-    #[inline]
-    pub fn is_poisoned(&self) -> bool {
-        self.base.is_poisoned()
-    }
-
-    /// Get the number of currently buffered messages.
-    #[inline]
-    pub fn buffered_messages(&self) -> usize {
-        self.base.buffered_messages()
-    }
-
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.base.is_connected()
+        self.reconnect_replaying().await
     }
 
     /// Receive a message with automatic reconnection on EOF or network error.
@@ -761,9 +1024,11 @@ impl DealerSocket<TcpStream> {
     pub async fn send_with_reconnect(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
         let max = self.base.options.max_reconnect_attempts;
         let mut attempts = 0u32;
+        let seq = self.base.next_sequence();
 
         loop {
             if self.base.stream.is_none() {
+                self.base.check_redial_backoff()?;
                 if let Some(limit) = max
                     && attempts >= limit
                 {
@@ -777,10 +1042,14 @@ impl DealerSocket<TcpStream> {
                     "[DEALER] Stream disconnected, reconnecting (attempt {})",
                     attempts
                 );
-                self.try_reconnect().await?;
+                self.try_reconnect()
+                    .await
+                    .map_err(|e| self.base.redial_error(e))?;
             }
 
-            match self.send(msg.clone()).await {
+            // A retry keeps the sequence number, so a receiver with a
+            // `dedupe_window` drops the copy if the first attempt arrived.
+            match self.base.send_sequenced(seq, &msg).await {
                 Ok(()) => return Ok(()),
                 Err(_) if self.base.stream.is_none() => {
                     // write_from_buf set stream = None → network error, retry
@@ -790,6 +1059,67 @@ impl DealerSocket<TcpStream> {
             }
         }
     }
+
+    /// Flush buffered messages with automatic reconnection on network error.
+    ///
+    /// With the `resend_on_reconnect` option, every message buffered since
+    /// the last successful flush is sent again on the new connection, so
+    /// none is lost to a connection that broke mid-flush. Messages the peer
+    /// already received arrive twice unless both sides set `dedupe_window`.
+    ///
+    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
+    pub async fn flush_with_reconnect(&mut self) -> io::Result<()> {
+        let max = self.base.options.max_reconnect_attempts;
+        let mut attempts = 0u32;
+
+        loop {
+            if self.base.stream.is_none() {
+                if let Some(limit) = max
+                    && attempts >= limit
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotConnected,
+                        format!("Max {} reconnection attempts exceeded", limit),
+                    ));
+                }
+                attempts += 1;
+                trace!(
+                    "[DEALER] Stream disconnected, reconnecting (attempt {})",
+                    attempts
+                );
+                self.try_reconnect().await?;
+            }
+
+            match self.flush().await {
+                Ok(()) => return Ok(()),
+                Err(_) if self.base.stream.is_none() => {
+                    debug!("[DEALER] Flush failed (stream lost), will reconnect");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+impl DealerSocket<crate::tls::TlsStream<TcpStream>> {
+    /// Connect to `endpoint` over TLS, then run the ZMTP handshake inside
+    /// the TLS session.
+    ///
+    /// The server certificate is verified against the endpoint's host (or
+    /// the name set with [`TlsConfig::with_server_name`](crate::tls::TlsConfig::with_server_name))
+    /// before any ZMTP bytes are sent; a rejected certificate fails with
+    /// [`TlsError::CertificateRejected`](crate::tls::TlsError::CertificateRejected).
+    /// The ZMTP mechanism (NULL, PLAIN or CURVE) comes from `options` as
+    /// usual, and `handshake_timeout` bounds each of the two handshakes.
+    pub async fn connect_tls(
+        endpoint: &str,
+        tls: &crate::tls::TlsConfig,
+        options: SocketOptions,
+    ) -> io::Result<Self> {
+        let stream = crate::tls::connect_with_options(endpoint, tls, &options).await?;
+        Self::with_options(stream, options).await
+    }
 }
 
 // Implement Socket trait for DealerSocket
@@ -844,6 +1174,7 @@ impl DealerSocket<InprocStream> {
         Ok(Self {
             base: SocketBase::new(stream, SocketType::Dealer, options),
             frames: SmallVec::new(),
+            unflushed: Vec::new(),
         })
     }
 
@@ -867,6 +1198,7 @@ impl DealerSocket<InprocStream> {
         Ok(Self {
             base: SocketBase::new(stream, SocketType::Dealer, options),
             frames: SmallVec::new(),
+            unflushed: Vec::new(),
         })
     }
 
@@ -915,6 +1247,492 @@ impl DealerSocket<InprocStream> {
         Ok(Self {
             base: SocketBase::new(stream, SocketType::Dealer, options),
             frames: SmallVec::new(),
+            unflushed: Vec::new(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BytesMut;
+    use compio_buf::{BufResult, IoBuf, IoBufMut};
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    /// In-memory stream handing out queued chunks; an empty queue is an idle
+    /// peer, so a read never completes.
+    #[derive(Clone, Default)]
+    struct QueuedStream(Rc<RefCell<VecDeque<Bytes>>>);
+
+    impl QueuedStream {
+        fn push(&self, msg: &[Bytes]) {
+            let mut buf = BytesMut::new();
+            crate::codec::encode_multipart(msg, &mut buf);
+            self.0.borrow_mut().push_back(buf.freeze());
+        }
+    }
+
+    impl AsyncRead for QueuedStream {
+        async fn read<B: IoBufMut>(&mut self, mut buf: B) -> BufResult<usize, B> {
+            let Some(mut chunk) = self.0.borrow_mut().pop_front() else {
+                return std::future::pending().await;
+            };
+            let n = chunk.len().min(buf.buf_capacity());
+            let rest = chunk.split_off(n);
+            if !rest.is_empty() {
+                self.0.borrow_mut().push_front(rest);
+            }
+            for (dst, src) in buf.as_uninit().iter_mut().zip(&chunk[..]) {
+                dst.write(*src);
+            }
+            // SAFETY: the first `n` bytes were just written.
+            unsafe { buf.set_len(n) };
+            BufResult(Ok(n), buf)
+        }
+    }
+
+    impl AsyncWrite for QueuedStream {
+        async fn write<B: IoBuf>(&mut self, buf: B) -> BufResult<usize, B> {
+            let len = buf.buf_len();
+            BufResult(Ok(len), buf)
+        }
+
+        async fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn nonblocking_dealer(stream: QueuedStream) -> DealerSocket<QueuedStream> {
+        let options = SocketOptions::default()
+            .with_recv_timeout(Duration::ZERO)
+            .with_send_timeout(Duration::ZERO);
+        DealerSocket {
+            base: SocketBase::new(stream, SocketType::Dealer, options),
+            frames: SmallVec::new(),
+            unflushed: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn nonblocking_recv_returns_arrived_messages() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                stream.push(&[Bytes::from_static(b"one")]);
+                stream.push(&[Bytes::from_static(b"two"), Bytes::from_static(b"parts")]);
+                let mut dealer = nonblocking_dealer(stream);
+
+                let msg = dealer.recv().await.unwrap().unwrap();
+                assert_eq!(msg, vec![Bytes::from_static(b"one")]);
+                let msg = dealer.recv().await.unwrap().unwrap();
+                assert_eq!(
+                    msg,
+                    vec![Bytes::from_static(b"two"), Bytes::from_static(b"parts")]
+                );
+
+                let err = dealer.recv().await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+                assert!(dealer.is_connected());
+            });
+    }
+
+    #[test]
+    fn recv_stream_yields_messages_then_the_error() {
+        use futures::StreamExt;
+
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                for i in 0..10 {
+                    stream.push(&[Bytes::from(format!("msg-{i}"))]);
+                }
+                let mut dealer = nonblocking_dealer(stream);
+
+                let mut messages = dealer.recv_stream();
+                for i in 0..10 {
+                    let msg = messages.next().await.unwrap().unwrap();
+                    assert_eq!(msg, vec![Bytes::from(format!("msg-{i}"))]);
+                }
+                // Nothing else has arrived: the zero recv timeout surfaces as
+                // an error item, after which the stream is over.
+                let err = messages.next().await.unwrap().unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+                assert!(messages.next().await.is_none());
+            });
+    }
+
+    #[test]
+    fn nonblocking_recv_drains_decoded_messages_first() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                let mut wire = BytesMut::new();
+                crate::codec::encode_multipart(&[Bytes::from_static(b"a")], &mut wire);
+                crate::codec::encode_multipart(&[Bytes::from_static(b"b")], &mut wire);
+                stream.0.borrow_mut().push_back(wire.freeze());
+                let mut dealer = nonblocking_dealer(stream.clone());
+
+                assert_eq!(
+                    dealer.recv().await.unwrap().unwrap(),
+                    vec![Bytes::from_static(b"a")]
+                );
+                // The second message came in with the first read; the stream
+                // itself has nothing left.
+                assert!(stream.0.borrow().is_empty());
+                assert_eq!(
+                    dealer.recv().await.unwrap().unwrap(),
+                    vec![Bytes::from_static(b"b")]
+                );
+            });
+    }
+
+    #[test]
+    fn nonblocking_recv_keeps_partial_message() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                let mut wire = BytesMut::new();
+                crate::codec::encode_multipart(
+                    &[Bytes::from_static(b"head"), Bytes::from_static(b"tail")],
+                    &mut wire,
+                );
+                let tail = wire.split_off(wire.len() - 3);
+                stream.0.borrow_mut().push_back(wire.freeze());
+                let mut dealer = nonblocking_dealer(stream.clone());
+
+                let err = dealer.recv().await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+                stream.0.borrow_mut().push_back(tail.freeze());
+                assert_eq!(
+                    dealer.recv().await.unwrap().unwrap(),
+                    vec![Bytes::from_static(b"head"), Bytes::from_static(b"tail")]
+                );
+            });
+    }
+
+    #[test]
+    fn recv_timeout_between_frames_resumes_the_same_message() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                let mut wire = BytesMut::new();
+                crate::codec::encode_multipart(
+                    &[Bytes::from_static(b"head"), Bytes::from_static(b"tail")],
+                    &mut wire,
+                );
+                let tail = wire.split_off(6);
+                stream.0.borrow_mut().push_back(wire.freeze());
+                let options = SocketOptions::default().with_recv_timeout(Duration::from_millis(20));
+                let mut dealer = DealerSocket {
+                    base: SocketBase::new(stream.clone(), SocketType::Dealer, options),
+                    frames: SmallVec::new(),
+                    unflushed: Vec::new(),
+                };
+
+                // The timeout fires after "head" was decoded.
+                let err = dealer.recv().await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+
+                // Dropping a recv future between frames keeps them as well.
+                let cancelled =
+                    monocoque_core::rt::timeout(Duration::from_millis(5), dealer.recv()).await;
+                assert!(cancelled.is_err());
+
+                stream.0.borrow_mut().push_back(tail.freeze());
+                let mut next = BytesMut::new();
+                crate::codec::encode_multipart(&[Bytes::from_static(b"next")], &mut next);
+                stream.0.borrow_mut().push_back(next.freeze());
+                assert_eq!(
+                    dealer.recv().await.unwrap().unwrap(),
+                    vec![Bytes::from_static(b"head"), Bytes::from_static(b"tail")]
+                );
+                assert_eq!(
+                    dealer.recv().await.unwrap().unwrap(),
+                    vec![Bytes::from_static(b"next")]
+                );
+            });
+    }
+
+    /// Stream that takes at most `CAP` bytes per write, like a socket whose
+    /// send buffer is nearly full, and keeps everything written.
+    #[derive(Clone, Default)]
+    struct CappedStream(Rc<RefCell<Vec<u8>>>);
+
+    impl CappedStream {
+        const CAP: usize = 7;
+    }
+
+    impl AsyncRead for CappedStream {
+        async fn read<B: IoBufMut>(&mut self, _buf: B) -> BufResult<usize, B> {
+            std::future::pending().await
+        }
+    }
+
+    impl AsyncWrite for CappedStream {
+        async fn write<B: IoBuf>(&mut self, buf: B) -> BufResult<usize, B> {
+            let n = buf.buf_len().min(Self::CAP);
+            self.0.borrow_mut().extend_from_slice(&buf.as_init()[..n]);
+            BufResult(Ok(n), buf)
+        }
+
+        async fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn send_completes_messages_across_short_writes() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = CappedStream::default();
+                let mut dealer = DealerSocket {
+                    base: SocketBase::new(
+                        stream.clone(),
+                        SocketType::Dealer,
+                        SocketOptions::default(),
+                    ),
+                    frames: SmallVec::new(),
+                    unflushed: Vec::new(),
+                };
+                // Small frames go through the copying path, the large one
+                // through the vectored path.
+                let small = vec![Bytes::from_static(b"id"), Bytes::from_static(b"body")];
+                let large = vec![Bytes::new(), Bytes::from(vec![0x5A; 100_000])];
+                dealer.send(small.clone()).await.unwrap();
+                dealer.send(large.clone()).await.unwrap();
+                dealer.send_buffered(small.clone()).unwrap();
+                dealer.flush().await.unwrap();
+
+                let mut src = monocoque_core::buffer::SegmentedBuffer::new();
+                src.push(Bytes::from(stream.0.take()));
+                let mut decoder = crate::codec::ZmtpDecoder::new();
+                let mut messages = vec![Vec::new()];
+                while let Some(frame) = decoder.decode(&mut src).unwrap() {
+                    let more = frame.more();
+                    messages.last_mut().unwrap().push(frame.payload);
+                    if !more {
+                        messages.push(Vec::new());
+                    }
+                }
+                assert!(src.is_empty());
+                assert_eq!(messages, [small.clone(), large, small, Vec::new()]);
+            });
+    }
+
+    #[test]
+    fn nonblocking_send_writes_when_stream_ready() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let mut dealer = nonblocking_dealer(QueuedStream::default());
+                dealer.send(vec![Bytes::from_static(b"x")]).await.unwrap();
+                dealer
+                    .send_buffered(vec![Bytes::from_static(b"y")])
+                    .unwrap();
+                dealer.flush().await.unwrap();
+                assert_eq!(dealer.buffered_bytes(), 0);
+            });
+    }
+
+    #[test]
+    fn try_send_hands_the_message_back_at_hwm() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let mut dealer = nonblocking_dealer(QueuedStream::default());
+                dealer.base.options.send_hwm = 2;
+                dealer.try_send(vec![Bytes::from_static(b"1")]).unwrap();
+                dealer.try_send(vec![Bytes::from_static(b"2")]).unwrap();
+
+                let msg = vec![Bytes::from_static(b"three"), Bytes::from_static(b"parts")];
+                let err = dealer.try_send(msg.clone()).unwrap_err();
+                assert!(matches!(err, TrySendError::Full(_)), "{err}");
+                assert_eq!(err.into_message(), Some(msg.clone()));
+                assert_eq!(dealer.buffered_messages(), 2);
+
+                dealer.flush().await.unwrap();
+                dealer.try_send(msg).unwrap();
+
+                dealer.base.stream = None;
+                let msg = vec![Bytes::from_static(b"offline")];
+                let err = dealer.try_send(msg.clone()).unwrap_err();
+                assert!(matches!(err, TrySendError::WouldBlock(_)), "{err}");
+                assert_eq!(err.into_message(), Some(msg));
+            });
+    }
+
+    #[test]
+    fn immediate_refuses_to_queue_while_disconnected() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let mut dealer = nonblocking_dealer(QueuedStream::default());
+                dealer.base.options.immediate = true;
+                dealer.base.stream = None;
+
+                let msg = vec![Bytes::from_static(b"dropped")];
+                let err = dealer.send_buffered(msg.clone()).unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+                let err = dealer.send(msg).await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+                assert_eq!(dealer.buffered_messages(), 0);
+                assert_eq!(dealer.buffered_bytes(), 0);
+            });
+    }
+
+    #[test]
+    fn without_immediate_messages_queue_while_disconnected() {
+        let mut dealer = nonblocking_dealer(QueuedStream::default());
+        dealer.base.stream = None;
+
+        dealer
+            .send_buffered(vec![Bytes::from_static(b"kept")])
+            .unwrap();
+        assert_eq!(dealer.buffered_messages(), 1);
+        assert!(dealer.buffered_bytes() > 0);
+    }
+
+    /// Names and fields of every span created while it is the default
+    /// subscriber.
+    #[derive(Clone, Default)]
+    struct SpanLog(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for SpanLog {
+        fn on_new_span(
+            &self,
+            attrs: &tracing::span::Attributes<'_>,
+            _id: &tracing::span::Id,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            self.0.lock().unwrap().push(format!(
+                "{} {:?}",
+                attrs.metadata().name(),
+                attrs.values()
+            ));
+        }
+    }
+
+    #[test]
+    fn send_runs_inside_a_send_message_span() {
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let log = SpanLog::default();
+        let subscriber = tracing_subscriber::registry().with(log.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            monocoque_core::rt::LocalRuntime::new()
+                .unwrap()
+                .block_on(async {
+                    let mut dealer = nonblocking_dealer(QueuedStream::default());
+                    dealer
+                        .send(vec![Bytes::from_static(b"ab"), Bytes::from_static(b"cde")])
+                        .await
+                        .unwrap();
+                });
+        });
+
+        let send = log
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|span| span.starts_with("send_message"))
+            .cloned()
+            .expect("send_message span");
+        for field in [
+            "socket_type: \"DEALER\"",
+            "frame_count: 2",
+            "total_bytes: 5",
+        ] {
+            assert!(send.contains(field), "{send} lacks {field}");
+        }
+    }
+
+    #[test]
+    fn recv_into_overwrites_reused_buffer() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                stream.push(&[Bytes::from_static(b"first"), Bytes::from_static(b"message")]);
+                stream.push(&[Bytes::from_static(b"second")]);
+                // An empty chunk reads as EOF.
+                stream.0.borrow_mut().push_back(Bytes::new());
+                let mut dealer = DealerSocket {
+                    base: SocketBase::new(stream, SocketType::Dealer, SocketOptions::default()),
+                    frames: SmallVec::new(),
+                    unflushed: Vec::new(),
+                };
+
+                let mut msg = Vec::new();
+                assert!(dealer.recv_into(&mut msg).await.unwrap());
+                assert_eq!(
+                    msg,
+                    vec![Bytes::from_static(b"first"), Bytes::from_static(b"message")]
+                );
+                let capacity = msg.capacity();
+
+                assert!(dealer.recv_into(&mut msg).await.unwrap());
+                assert_eq!(msg, vec![Bytes::from_static(b"second")]);
+                assert_eq!(msg.capacity(), capacity);
+
+                assert!(!dealer.recv_into(&mut msg).await.unwrap());
+                assert!(msg.is_empty());
+            });
+    }
+
+    #[test]
+    fn conflate_keeps_only_the_newest_message() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let stream = QueuedStream::default();
+                let mut chunk = BytesMut::new();
+                for msg in [
+                    &[Bytes::from_static(b"first"), Bytes::from_static(b"a")][..],
+                    &[Bytes::from_static(b"second")][..],
+                    &[Bytes::from_static(b"third"), Bytes::from_static(b"c")][..],
+                ] {
+                    crate::codec::encode_multipart(msg, &mut chunk);
+                }
+                stream.0.borrow_mut().push_back(chunk.freeze());
+                let mut dealer = DealerSocket {
+                    base: SocketBase::new(
+                        stream,
+                        SocketType::Dealer,
+                        SocketOptions::default().with_conflate(true),
+                    ),
+                    frames: SmallVec::new(),
+                    unflushed: Vec::new(),
+                };
+
+                let msg = dealer.recv().await.unwrap().expect("dealer EOF");
+                assert_eq!(
+                    msg,
+                    vec![Bytes::from_static(b"third"), Bytes::from_static(b"c")]
+                );
+                assert!(
+                    monocoque_core::rt::timeout(Duration::from_millis(50), dealer.recv())
+                        .await
+                        .is_err(),
+                    "a conflated message was delivered"
+                );
+            });
+    }
+}
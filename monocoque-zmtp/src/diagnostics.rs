@@ -0,0 +1,236 @@
+//! Point-in-time diagnostics snapshot of a socket, for support bundles.
+//!
+//! [`DiagnosticsSnapshot`] gathers what the socket accessors report one by
+//! one (state, non-default options, buffer usage, duplicate and protocol
+//! violation counters, connection history) into one plain value. Sockets that
+//! serve several peers (ROUTER, PUB) also list their peers and the depth of
+//! their internal queues. With the
+//! `serde` feature it derives `Serialize`/`Deserialize` and
+//! [`to_json_pretty`](DiagnosticsSnapshot::to_json_pretty) renders it.
+//!
+//! The layout is versioned by [`SCHEMA_VERSION`]: fields are only added
+//! within a version, and renaming or removing one bumps it.
+//!
+//! Secrets never enter a snapshot. Options are recorded through the
+//! redacting `Debug` of [`OptionDiff`](monocoque_core::options::OptionDiff),
+//! so PLAIN passwords and CURVE secret keys appear as `[REDACTED]`.
+//!
+//! # Example
+//!
+//! Dump a snapshot to a file whenever a trigger arrives, the way a SIGUSR1
+//! handler would (requires the `serde` feature):
+//!
+//! ```ignore
+//! use monocoque_zmtp::DealerSocket;
+//!
+//! # async fn example(mut socket: DealerSocket, dump: flume::Receiver<()>) -> std::io::Result<()> {
+//! while dump.recv_async().await.is_ok() {
+//!     let json = socket.snapshot().to_json_pretty()?;
+//!     std::fs::write("/tmp/monocoque-dealer.json", json)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use monocoque_core::config::BufferStats;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::reconnect::{ConnectAttempt, ConnectOutcome};
+use std::time::SystemTime;
+
+use crate::ZmtpVersion;
+use crate::session::SocketType;
+
+/// Version of the [`DiagnosticsSnapshot`] layout.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Everything a socket reports about itself at one instant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticsSnapshot {
+    /// Layout version, [`SCHEMA_VERSION`] when taken by this build
+    pub schema_version: u32,
+    /// `ZMQ_TYPE` name, such as `"DEALER"`
+    pub socket_type: String,
+    /// Last connected or bound endpoint
+    pub endpoint: Option<String>,
+    /// `"connected"`, `"disconnected"` or `"poisoned"`
+    pub state: String,
+    /// ZMTP revision negotiated by the latest handshake
+    pub zmtp_version: String,
+    /// Reconnection attempts since the connection was lost
+    pub reconnect_attempt: u32,
+    /// Options that differ from the defaults, secrets redacted
+    pub options: Vec<String>,
+    /// Messages buffered and not yet flushed
+    pub buffered_messages: usize,
+    /// Bytes buffered and not yet flushed
+    pub buffered_bytes: usize,
+    /// Allocated buffer capacities
+    pub buffers: BufferSnapshot,
+    /// Messages dropped by the `dedupe_window` option
+    pub duplicates_dropped: u64,
+    /// Protocol violations rejected by this process (all sockets)
+    pub protocol_violations: u64,
+    /// Latest connection attempts, oldest first
+    pub connect_history: Vec<ConnectRecord>,
+    /// Connected peers, for sockets that route to them by identity
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub peers: Vec<PeerRecord>,
+    /// Internal queues between the socket and its peers
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queues: Vec<QueueRecord>,
+    /// Messages dropped at a full queue (`send_hwm`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hwm_dropped: u64,
+}
+
+impl DiagnosticsSnapshot {
+    /// Snapshot of a socket that serves several peers, without the
+    /// single-connection fields (buffers, reconnects, history). The caller
+    /// fills in peers, queues and counters.
+    pub(crate) fn multi_peer(
+        socket_type: SocketType,
+        endpoint: Option<String>,
+        state: &str,
+        options: &SocketOptions,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            socket_type: socket_type.as_str().to_owned(),
+            endpoint,
+            state: state.to_owned(),
+            zmtp_version: ZmtpVersion::LOCAL.to_string(),
+            reconnect_attempt: 0,
+            options: SocketOptions::default()
+                .diff(options)
+                .iter()
+                .map(|diff| format!("{diff:?}"))
+                .collect(),
+            buffered_messages: 0,
+            buffered_bytes: 0,
+            buffers: BufferSnapshot::default(),
+            duplicates_dropped: 0,
+            protocol_violations: crate::codec::protocol_violations(),
+            connect_history: Vec::new(),
+            peers: Vec::new(),
+            queues: Vec::new(),
+            hwm_dropped: 0,
+        }
+    }
+}
+
+/// One connected peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PeerRecord {
+    /// Routing identity, escaped as a byte string
+    pub identity: String,
+    /// Who the peer authenticated as, if it did
+    pub principal: Option<String>,
+    /// Messages queued for the peer and not yet written
+    pub pending: usize,
+}
+
+/// One internal queue of a multi-peer socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QueueRecord {
+    /// What the queue carries, such as `"inbound"` or `"worker 0"`
+    pub name: String,
+    /// Messages waiting in the queue
+    pub depth: usize,
+    /// Peers served through the queue
+    pub peers: usize,
+}
+
+/// Serializable copy of [`BufferStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BufferSnapshot {
+    /// Allocated capacity of the batching send buffer
+    pub send_buffer_capacity: usize,
+    /// Allocated capacity of the per-message encode buffer
+    pub write_buffer_capacity: usize,
+    /// Remaining capacity of the current read slab
+    pub read_buffer_capacity: usize,
+    /// Weighted average of recent flush sizes, in bytes
+    pub avg_flush_bytes: usize,
+    /// Bytes read off the wire and not yet decoded or handed out
+    pub recv_queued_bytes: usize,
+    /// Allocated capacity of the decoder's reassembly buffer
+    pub staging_capacity: usize,
+    /// Frames received on the current connection without a copy
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zero_copy_frames: u64,
+    /// Frames received on the current connection that were copied because
+    /// their body spanned reads
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub copied_frames: u64,
+}
+
+impl From<BufferStats> for BufferSnapshot {
+    fn from(stats: BufferStats) -> Self {
+        Self {
+            send_buffer_capacity: stats.send_buffer_capacity,
+            write_buffer_capacity: stats.write_buffer_capacity,
+            read_buffer_capacity: stats.read_buffer_capacity,
+            avg_flush_bytes: stats.avg_flush_bytes,
+            recv_queued_bytes: stats.recv_queued_bytes,
+            staging_capacity: stats.staging_capacity,
+            zero_copy_frames: stats.zero_copy_frames,
+            copied_frames: stats.copied_frames,
+        }
+    }
+}
+
+/// Serializable copy of a [`ConnectAttempt`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectRecord {
+    /// Endpoint that was dialed
+    pub endpoint: String,
+    /// Start of the attempt, in milliseconds since the Unix epoch
+    pub started_at_unix_ms: u64,
+    /// 0 for the initial connect, then the reconnection attempt number
+    pub attempt_number: u32,
+    /// Time to connect and handshake, for a successful attempt
+    pub duration_us: Option<u64>,
+    /// Error kind and message, for a failed attempt
+    pub error: Option<String>,
+}
+
+impl From<&ConnectAttempt> for ConnectRecord {
+    fn from(attempt: &ConnectAttempt) -> Self {
+        let started_at_unix_ms = attempt
+            .started_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX));
+        let (duration_us, error) = match &attempt.outcome {
+            ConnectOutcome::Ok(took) => (
+                Some(u64::try_from(took.as_micros()).unwrap_or(u64::MAX)),
+                None,
+            ),
+            ConnectOutcome::Err { kind, message } => (None, Some(format!("{kind:?}: {message}"))),
+        };
+        Self {
+            endpoint: attempt.endpoint.to_string(),
+            started_at_unix_ms,
+            attempt_number: attempt.attempt_number,
+            duration_us,
+            error,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl DiagnosticsSnapshot {
+    /// Render the snapshot as indented JSON.
+    ///
+    /// # Errors
+    ///
+    /// Only fails if serialization itself fails, which a snapshot's plain
+    /// fields do not cause.
+    pub fn to_json_pretty(&self) -> std::io::Result<String> {
+        serde_json::to_string_pretty(self).map_err(std::io::Error::other)
+    }
+}
@@ -0,0 +1,184 @@
+//! Chunked receive for frames too large to buffer.
+//!
+//! With the `stream_threshold` option set, a data frame whose declared body
+//! exceeds the threshold is never reassembled in memory. `recv()` reports it
+//! with [`ZmtpError::StreamingRequired`](crate::codec::ZmtpError::StreamingRequired)
+//! and leaves it queued; `recv_frame_streaming()` then returns a
+//! [`FrameReader`] that hands the body out as it comes off the wire.
+//!
+//! Each chunk is a zero-copy slice of a read slab, and the socket only reads
+//! when the caller asks for the next chunk, so a slow consumer applies
+//! backpressure to the sender through TCP flow control. Every read keeps
+//! `recv_timeout` and the heartbeat running, so a long transfer does not look
+//! like a dead peer.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use monocoque_core::options::SocketOptions;
+//! use monocoque_zmtp::DealerSocket;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let options = SocketOptions::default().with_stream_threshold(Some(16 << 20));
+//! let mut socket = DealerSocket::connect_with_options("127.0.0.1:5555", options).await?;
+//!
+//! while let Some(mut frame) = socket.recv_frame_streaming().await? {
+//!     println!("frame of {} bytes", frame.total_len());
+//!     while let Some(chunk) = frame.next_chunk().await? {
+//!         // write `chunk` to disk, hash it, ...
+//!         drop(chunk);
+//!     }
+//!     if !frame.more() {
+//!         break;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::Bytes;
+use compio_io::{AsyncRead, AsyncWrite};
+use std::io;
+
+use crate::base::{FrameResult, SocketBase};
+
+/// One received frame, read chunk by chunk.
+///
+/// Frames at or below `stream_threshold` arrive whole and are returned as a
+/// single chunk. Dropping a reader before the last chunk discards the rest of
+/// the frame; the socket skips it before decoding the next frame.
+pub struct FrameReader<'a, S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    base: &'a mut SocketBase<S>,
+    /// Body of a frame that was decoded whole; `None` when streaming.
+    buffered: Option<Bytes>,
+    /// Whether the body is still on the wire.
+    streamed: bool,
+    total: u64,
+    more: bool,
+}
+
+impl<'a, S> FrameReader<'a, S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Wrap a frame that was decoded whole.
+    pub(crate) fn buffered(base: &'a mut SocketBase<S>, payload: Bytes, more: bool) -> Self {
+        Self {
+            base,
+            total: payload.len() as u64,
+            buffered: Some(payload),
+            streamed: false,
+            more,
+        }
+    }
+
+    /// Declared length of the frame body in bytes.
+    #[inline]
+    pub const fn total_len(&self) -> u64 {
+        self.total
+    }
+
+    /// Whether more frames of the same message follow this one.
+    #[inline]
+    pub const fn more(&self) -> bool {
+        self.more
+    }
+
+    /// Body bytes not yet returned by [`next_chunk`](Self::next_chunk).
+    pub fn remaining(&self) -> u64 {
+        if self.streamed {
+            self.base.decoder.stream_remaining()
+        } else {
+            self.buffered.as_ref().map_or(0, |p| p.len() as u64)
+        }
+    }
+
+    /// Buffer footprint of the underlying socket, e.g. to confirm a streamed
+    /// frame is not being reassembled.
+    pub fn buffer_stats(&self) -> monocoque_core::config::BufferStats {
+        self.base.buffer_stats()
+    }
+
+    /// Return the next chunk of the body, or `None` once it is complete.
+    ///
+    /// Reads from the socket only when nothing is buffered. A chunk is never
+    /// longer than one read, so its size follows `read_buffer_size`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnexpectedEof` if the connection closes mid-frame, and the
+    /// socket's usual read, timeout and heartbeat errors.
+    pub async fn next_chunk(&mut self) -> io::Result<Option<Bytes>> {
+        if !self.streamed {
+            return Ok(self.buffered.take());
+        }
+        let base = &mut *self.base;
+        loop {
+            if base.decoder.stream_remaining() == 0 {
+                return Ok(None);
+            }
+            if let Some(chunk) = base.decoder.take_stream_chunk(&mut base.recv) {
+                return Ok(Some(chunk));
+            }
+            if base.read_raw().await? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "connection closed in the middle of a streamed frame",
+                ));
+            }
+            base.note_stream_progress();
+            if base.check_heartbeat()? {
+                base.flush_send_buffer().await?;
+            }
+        }
+    }
+}
+
+impl<S> SocketBase<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Receive the next data frame as a [`FrameReader`].
+    ///
+    /// Frames above `stream_threshold` are streamed off the wire; smaller
+    /// ones are decoded whole. Command frames are handled as in `recv()`.
+    /// Returns `Ok(None)` on a clean EOF between frames.
+    pub(crate) async fn recv_frame_streaming(&mut self) -> io::Result<Option<FrameReader<'_, S>>> {
+        loop {
+            match self.decoder.begin_stream(&mut self.recv) {
+                Ok(Some((more, total))) => {
+                    return Ok(Some(FrameReader {
+                        base: self,
+                        buffered: None,
+                        streamed: true,
+                        total,
+                        more,
+                    }));
+                }
+                Ok(None) => {}
+                Err(e) => return Err(self.drop_violating_peer(io::Error::from(e))),
+            }
+            match self.process_frame()? {
+                FrameResult::Data(more, payload) => {
+                    return Ok(Some(FrameReader::buffered(self, payload, more)));
+                }
+                FrameResult::CommandHandled => {
+                    if !self.send_buffer.is_empty() {
+                        self.flush_send_buffer().await?;
+                    }
+                    continue;
+                }
+                FrameResult::NeedMore => {}
+            }
+            if self.read_raw().await? == 0 {
+                return Ok(None);
+            }
+            if self.check_heartbeat()? {
+                self.flush_send_buffer().await?;
+            }
+        }
+    }
+}
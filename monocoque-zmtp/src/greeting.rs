@@ -4,12 +4,162 @@ use bytes::Bytes;
 /// ZMTP Greeting is always exactly 64 bytes
 pub const GREETING_SIZE: usize = 64;
 
+/// The signature opening every greeting: `0xFF`, 8 padding bytes, `0x7F`.
+pub const SIGNATURE_SIZE: usize = 10;
+
 const SIGNATURE_HEAD: u8 = 0xFF;
 const SIGNATURE_TAIL: u8 = 0x7F;
 
+/// Whether `signature` (the first [`SIGNATURE_SIZE`] bytes from a peer) is
+/// the ZMTP signature.
+const fn is_signature(signature: &[u8; SIGNATURE_SIZE]) -> bool {
+    signature[0] == SIGNATURE_HEAD && signature[SIGNATURE_SIZE - 1] == SIGNATURE_TAIL
+}
+
+/// Reject a peer whose greeting, right after the signature, announces a
+/// major version below 3.
+///
+/// ZMTP 2.x greetings are shorter than 64 bytes, so this has to be checked as
+/// soon as the byte arrives: waiting for the full greeting would stall until
+/// the handshake timeout.
+pub const fn check_major_version(major: u8) -> Result<(), ZmtpError> {
+    if major < ZmtpVersion::V3_0.major {
+        return Err(ZmtpError::UnsupportedVersion { major });
+    }
+    Ok(())
+}
+
+/// What a connection that does not open with the ZMTP signature was trying
+/// to speak, judged from its first bytes.
+///
+/// Load balancer health checks, port scanners and people with `curl` all
+/// end up on ZMTP ports; the classification only serves logging and the
+/// per-kind counters in [`non_zmtp_probes`](crate::codec::non_zmtp_probes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProbeKind {
+    /// An HTTP/1.x request line or the HTTP/2 connection preface.
+    Http,
+    /// A TLS (or SSL 3.0) handshake record, such as a `ClientHello`.
+    Tls,
+    /// Anything else.
+    Unknown,
+}
+
+impl ProbeKind {
+    /// Every kind, in counter order.
+    pub const ALL: [Self; 3] = [Self::Http, Self::Tls, Self::Unknown];
+
+    /// What the peer that sent `prefix` is speaking, if not ZMTP.
+    ///
+    /// `None` until `prefix` holds a whole signature, and when it is the
+    /// ZMTP signature.
+    #[must_use]
+    pub fn detect(prefix: &[u8]) -> Option<Self> {
+        let signature = prefix.first_chunk::<SIGNATURE_SIZE>()?;
+        (!is_signature(signature)).then(|| Self::classify(prefix))
+    }
+
+    /// Classify a connection from its first bytes.
+    #[must_use]
+    pub fn classify(prefix: &[u8]) -> Self {
+        const HTTP_METHODS: [&[u8]; 10] = [
+            b"GET ",
+            b"HEAD ",
+            b"POST ",
+            b"PUT ",
+            b"DELETE ",
+            b"CONNECT ",
+            b"OPTIONS ",
+            b"TRACE ",
+            b"PATCH ",
+            b"PRI ",
+        ];
+        // Record type 22 (handshake), protocol version 3.0 (SSL 3.0) to 3.4.
+        if let [0x16, 0x03, 0x00..=0x04, ..] = prefix {
+            return Self::Tls;
+        }
+        if HTTP_METHODS.iter().any(|method| prefix.starts_with(method)) {
+            return Self::Http;
+        }
+        Self::Unknown
+    }
+
+    /// Lowercase name, as used in logs.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Http => "http",
+            Self::Tls => "tls",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl std::fmt::Display for ProbeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// ZMTP protocol version advertised in the greeting (bytes 10 and 11).
+///
+/// Both sides advertise their highest supported revision and then speak the
+/// lower of the two (RFC 23 §"Version Negotiation"). The frame layout is the
+/// same in 3.0 and 3.1: short frames (flags bit 1 clear, 1-byte length) and
+/// long frames (bit 1 set, 8-byte length) are both defined by ZMTP 3.0, and
+/// bit 0 is MORE in either revision. What 3.1 adds are commands: `PING`/`PONG`
+/// heartbeats and `SUBSCRIBE`/`CANCEL`, which must not be sent to a 3.0 peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ZmtpVersion {
+    /// Major revision (always 3 for the versions we speak).
+    pub major: u8,
+    /// Minor revision.
+    pub minor: u8,
+}
+
+impl ZmtpVersion {
+    /// ZMTP 3.0 (RFC 23), spoken by `ZeroMQ` 4.0 and 4.1.
+    pub const V3_0: Self = Self { major: 3, minor: 0 };
+    /// ZMTP 3.1 (RFC 37), spoken by `ZeroMQ` 4.2 and later.
+    pub const V3_1: Self = Self { major: 3, minor: 1 };
+    /// The highest revision this implementation advertises.
+    pub const LOCAL: Self = Self::V3_1;
+
+    /// Negotiate the revision to speak with a peer that advertised `peer`.
+    ///
+    /// Both sides use the lower minor revision; the major revision is always
+    /// ours since [`ZmtpGreeting::parse`] rejects anything below 3.
+    #[must_use]
+    pub const fn negotiate(self, peer: Self) -> Self {
+        let minor = if peer.minor < self.minor {
+            peer.minor
+        } else {
+            self.minor
+        };
+        Self {
+            major: self.major,
+            minor,
+        }
+    }
+
+    /// Whether ZMTP 3.1 commands (`PING`/`PONG`, `SUBSCRIBE`/`CANCEL`) may be sent.
+    #[must_use]
+    pub const fn has_v31_commands(self) -> bool {
+        self.major > 3 || (self.major == 3 && self.minor >= 1)
+    }
+}
+
+impl std::fmt::Display for ZmtpVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
 /// Parsed greeting information from peer.
 #[derive(Debug, Clone)]
 pub struct ZmtpGreeting {
+    /// Protocol version advertised by the peer
+    pub version: ZmtpVersion,
     /// Security mechanism advertised by the peer (e.g., "NULL", "PLAIN", "CURVE")
     pub mechanism: [u8; 20],
     /// Whether the peer is acting as server for the security mechanism
@@ -41,35 +191,41 @@ impl ZmtpGreeting {
     ///
     /// This provides backward and forward compatibility across all modern ZMQ versions.
     pub fn parse(src: &Bytes) -> crate::codec::Result<Self> {
+        let invalid = |reason| ZmtpError::InvalidGreeting { reason };
         if src.len() < GREETING_SIZE {
             return Err(ZmtpError::Incomplete);
         }
-        if src.len() != GREETING_SIZE {
-            return Err(ZmtpError::Protocol);
-        }
-        if src[0] != SIGNATURE_HEAD || src[9] != SIGNATURE_TAIL {
-            return Err(ZmtpError::Protocol);
-        }
-        let major = src[10];
-        if major < 3 {
-            return Err(ZmtpError::Protocol);
+        let Ok(greeting) = <&[u8; GREETING_SIZE]>::try_from(src.as_ref()) else {
+            return Err(invalid("longer than 64 bytes"));
+        };
+        if greeting[0] != SIGNATURE_HEAD || greeting[9] != SIGNATURE_TAIL {
+            return Err(invalid("bad signature"));
         }
+        let major = greeting[10];
+        check_major_version(major)?;
+        let version = ZmtpVersion {
+            major,
+            minor: greeting[11],
+        };
         let mut mechanism = [0u8; 20];
-        mechanism.copy_from_slice(&src[12..32]);
+        mechanism.copy_from_slice(&greeting[12..32]);
         let mech_end = mechanism.iter().position(|&b| b == 0).unwrap_or(20);
-        if mech_end == 0
-            || !mechanism[..mech_end]
+        let (name, padding) = mechanism.split_at(mech_end);
+        if name.is_empty()
+            || !name
                 .iter()
                 .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
-            || mechanism[mech_end..].iter().any(|&b| b != 0)
+            || padding.iter().any(|&b| b != 0)
         {
-            return Err(ZmtpError::Protocol);
-        }
-        if src[32] > 1 {
-            return Err(ZmtpError::Protocol);
+            return Err(invalid("mechanism is not NUL-padded uppercase ASCII"));
         }
-        let as_server = src[32] != 0;
+        let as_server = match greeting[32] {
+            0 => false,
+            1 => true,
+            _ => return Err(invalid("as-server flag is neither 0 nor 1")),
+        };
         Ok(Self {
+            version,
             mechanism,
             as_server,
         })
@@ -96,6 +252,32 @@ mod tests {
         Bytes::copy_from_slice(&greeting)
     }
 
+    #[test]
+    fn parse_reads_peer_version() {
+        let mut greeting = valid_greeting().to_vec();
+        greeting[11] = 0;
+
+        let parsed = ZmtpGreeting::parse(&Bytes::from(greeting)).unwrap();
+        assert_eq!(parsed.version, ZmtpVersion::V3_0);
+    }
+
+    #[test]
+    fn negotiate_picks_lower_minor_revision() {
+        assert_eq!(
+            ZmtpVersion::V3_1.negotiate(ZmtpVersion::V3_0),
+            ZmtpVersion::V3_0
+        );
+        assert_eq!(
+            ZmtpVersion::V3_0.negotiate(ZmtpVersion::V3_1),
+            ZmtpVersion::V3_0
+        );
+        // A future 3.2 peer still talks 3.1 with us.
+        let future = ZmtpVersion { major: 3, minor: 2 };
+        assert_eq!(ZmtpVersion::LOCAL.negotiate(future), ZmtpVersion::V3_1);
+        assert!(!ZmtpVersion::V3_0.has_v31_commands());
+        assert!(ZmtpVersion::V3_1.has_v31_commands());
+    }
+
     #[test]
     fn parse_rejects_trailing_bytes_after_fixed_greeting() {
         let mut greeting = valid_greeting().to_vec();
@@ -103,7 +285,7 @@ mod tests {
 
         assert!(matches!(
             ZmtpGreeting::parse(&Bytes::from(greeting)),
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::InvalidGreeting { .. })
         ));
     }
 
@@ -114,7 +296,7 @@ mod tests {
 
         assert!(matches!(
             ZmtpGreeting::parse(&Bytes::from(greeting)),
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::InvalidGreeting { .. })
         ));
     }
 
@@ -125,7 +307,7 @@ mod tests {
 
         assert!(matches!(
             ZmtpGreeting::parse(&Bytes::from(greeting)),
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::InvalidGreeting { .. })
         ));
     }
 
@@ -136,7 +318,28 @@ mod tests {
 
         assert!(matches!(
             ZmtpGreeting::parse(&Bytes::from(greeting)),
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::InvalidGreeting { .. })
         ));
     }
+
+    #[test]
+    fn classify_recognizes_http_and_tls_probes() {
+        assert_eq!(ProbeKind::classify(b"GET / HTTP/1.1\r\n"), ProbeKind::Http);
+        assert_eq!(ProbeKind::classify(b"OPTIONS * HT"), ProbeKind::Http);
+        assert_eq!(
+            ProbeKind::classify(b"\x16\x03\x01\x02\x00\x01\x00\x01\xfc\x03"),
+            ProbeKind::Tls
+        );
+        assert_eq!(ProbeKind::classify(b"GETX"), ProbeKind::Unknown);
+        assert_eq!(ProbeKind::classify(b"\x16\x03"), ProbeKind::Unknown);
+        assert_eq!(ProbeKind::classify(b""), ProbeKind::Unknown);
+
+        assert_eq!(ProbeKind::detect(&valid_greeting()), None);
+        assert_eq!(
+            ProbeKind::detect(b"GET / HTT"),
+            None,
+            "signature incomplete"
+        );
+        assert_eq!(ProbeKind::detect(b"GET / HTTP"), Some(ProbeKind::Http));
+    }
 }
@@ -18,26 +18,111 @@
 //!
 //! After handshake completes, the main data path uses the `core::io` read slab for zero-copy IO.
 
-use crate::codec::ZmtpError;
+use crate::codec::{ZmtpError, record_probe, record_violation};
+use crate::greeting::{GREETING_SIZE, ProbeKind, SIGNATURE_SIZE, ZmtpGreeting, ZmtpVersion};
 use crate::security::curve::CurveHandshakeResult;
+use crate::security::principal::Principal;
+use crate::security::protocol::{peer_error, send_zmtp_error};
 use crate::session::SocketType;
-use crate::utils::{FLAG_COMMAND, build_ready, encode_frame};
+use crate::utils::{FLAG_COMMAND, build_metadata_command, encode_frame, put_property};
 use bytes::{BufMut, Bytes, BytesMut};
 use compio_buf::BufResult;
 use compio_io::{AsyncRead, AsyncWrite};
 use monocoque_core::options::SocketOptions;
 use monocoque_core::timeout::{read_exact_with_timeout, write_all_with_timeout};
+use std::collections::HashMap;
+use std::io;
 use std::time::Duration;
-use tracing::{debug, warn};
+use std::time::Instant;
+use tracing::{Instrument, debug, debug_span, warn};
+
+/// Metadata command sent by NULL peers and by a PLAIN server.
+pub const READY: &str = "READY";
+/// Metadata command sent by a PLAIN client after WELCOME.
+const INITIATE: &str = "INITIATE";
+
+/// Every property a peer sent in its READY (or INITIATE) command, keyed by
+/// name as the peer spelled it.
+///
+/// Holds `Socket-Type` and `Identity` alongside application properties such
+/// as `X-Hostname`. Names that are not valid UTF-8 are converted lossily.
+///
+/// When a ZAP handler authenticated the peer, the user id it assigned is
+/// stored under [`USER_ID_PROPERTY`], replacing any value the peer sent.
+/// Repeated property names keep the last value; only `Socket-Type` and
+/// `Identity` must be unique.
+pub type PeerMetadata = HashMap<String, Bytes>;
+
+/// [`PeerMetadata`] key holding the user id assigned by ZAP.
+pub const USER_ID_PROPERTY: &str = "User-Id";
 
 /// Result of a successful handshake
 #[derive(Debug)]
 pub struct HandshakeResult {
     pub peer_identity: Option<Bytes>,
     pub peer_socket_type: SocketType,
+    /// All properties from the peer's metadata command. After a CURVE
+    /// handshake only `Socket-Type` and `Identity` are recorded.
+    pub peer_metadata: PeerMetadata,
+    /// Who the peer authenticated as, when this side is the PLAIN or CURVE
+    /// server.
+    pub principal: Option<Principal>,
     pub curve_cipher: Option<crate::security::curve::CurveMessageCipher>,
+    /// ZMTP revision both peers agreed to speak (the lower of the two greetings).
+    pub version: ZmtpVersion,
+    /// Both peers advertised `X-Sequence`, so messages carry sequence frames
+    /// (see the `dedupe_window` option).
+    pub sequenced: bool,
+}
+
+impl HandshakeResult {
+    /// Every property the peer sent in its READY command.
+    #[inline]
+    #[must_use]
+    pub const fn metadata(&self) -> &PeerMetadata {
+        &self.peer_metadata
+    }
+
+    /// The peer's `Socket-Type` property as sent, before it was parsed into
+    /// [`peer_socket_type`](Self::peer_socket_type).
+    #[must_use]
+    pub fn peer_socket_type_str(&self) -> Option<&str> {
+        self.peer_metadata
+            .get("Socket-Type")
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+}
+
+/// Convert a failed handshake into the `io::Error` socket constructors return.
+///
+/// A peer that rejected our credentials, or that we rejected, yields kind
+/// `PermissionDenied` wrapping [`ZmtpError::AuthenticationFailed`]. A peer
+/// that answered with an ERROR command yields kind `ConnectionRefused`
+/// wrapping [`ZmtpError::PeerError`] and its reason. A peer
+/// configured for another mechanism, or a second PLAIN or CURVE server, yields kind
+/// `Other` wrapping [`ZmtpError::MechanismMismatch`] or
+/// [`ZmtpError::AsServerConflict`]; every other failure reads
+/// "Handshake failed: ..." with kind `Other`, wrapping a [`HandshakeFailed`].
+pub fn handshake_error(err: ZmtpError) -> io::Error {
+    match err {
+        ZmtpError::AuthenticationFailed => io::Error::new(io::ErrorKind::PermissionDenied, err),
+        ZmtpError::PeerError { .. } => io::Error::new(io::ErrorKind::ConnectionRefused, err),
+        ZmtpError::MechanismMismatch { .. } | ZmtpError::AsServerConflict { .. } => {
+            io::Error::other(err)
+        }
+        err => io::Error::other(HandshakeFailed(err)),
+    }
 }
 
+/// A handshake failure with no more specific kind.
+///
+/// Wrapped by [`handshake_error`] to mark the error as coming from the
+/// handshake rather than from the data phase, where the same [`ZmtpError`]
+/// can also occur.
+#[derive(Debug, thiserror::Error)]
+#[error("Handshake failed: {0}")]
+pub struct HandshakeFailed(#[source] pub ZmtpError);
+
 /// Security mechanism to use for the ZMTP handshake.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SecurityMechanism {
@@ -52,9 +137,14 @@ pub enum SecurityMechanism {
 impl SecurityMechanism {
     /// Detect the mechanism from socket options.
     ///
-    /// Priority: CURVE > PLAIN > NULL.
+    /// Priority: CURVE > PLAIN > NULL. Any CURVE key selects CURVE, so an
+    /// incomplete key set fails the handshake instead of falling back to NULL.
     pub fn from_options(options: &SocketOptions) -> Self {
-        if options.curve_secretkey.is_some() || options.curve_server {
+        if options.curve_server
+            || options.curve_secretkey.is_some()
+            || options.curve_publickey.is_some()
+            || options.curve_serverkey.is_some()
+        {
             Self::Curve
         } else if options.plain_server || options.plain_username.is_some() {
             Self::Plain
@@ -65,18 +155,63 @@ impl SecurityMechanism {
 
     /// The ASCII mechanism name used in ZMTP greetings (20-byte field).
     pub fn as_greeting_bytes(self) -> &'static [u8] {
+        self.name().as_bytes()
+    }
+
+    /// The mechanism name, as in the greeting.
+    pub const fn name(self) -> &'static str {
         match self {
-            Self::Null => b"NULL",
-            Self::Plain => b"PLAIN",
-            Self::Curve => b"CURVE",
+            Self::Null => "NULL",
+            Self::Plain => "PLAIN",
+            Self::Curve => "CURVE",
+        }
+    }
+
+    /// The as-server flag this side advertises for the mechanism.
+    fn as_server(self, options: &SocketOptions) -> bool {
+        match self {
+            Self::Curve => options.curve_server,
+            Self::Plain => options.plain_server,
+            Self::Null => false,
         }
     }
 }
 
+/// Check a peer's greeting against the mechanism and role we advertise.
+///
+/// Both sides must name the same mechanism. PLAIN and CURVE also need one
+/// client and one server, so two peers that both set as-server are refused;
+/// NULL ignores the flag (RFC 23). Two clients cannot be told apart from a
+/// client and a libzmq server, which always sends the flag clear, so that
+/// case is left to the mechanism exchange to reject.
+pub fn check_peer_mechanism(
+    local: SecurityMechanism,
+    local_as_server: bool,
+    peer: &ZmtpGreeting,
+) -> Result<(), ZmtpError> {
+    let peer_mechanism = peer.mechanism_str();
+    if peer_mechanism != local.name() {
+        return Err(ZmtpError::MechanismMismatch {
+            local: local.name().to_owned(),
+            peer: peer_mechanism.to_owned(),
+        });
+    }
+    if local != SecurityMechanism::Null && peer.as_server && local_as_server {
+        return Err(ZmtpError::AsServerConflict {
+            mechanism: local.name().to_owned(),
+        });
+    }
+    Ok(())
+}
+
 /// Performs the complete ZMTP handshake, selecting the security mechanism from options.
 ///
 /// This is the primary handshake entry point for sockets that have security configured.
-#[allow(clippy::too_many_lines)]
+/// Every read is sized to the field it expects, so the peer may deliver the
+/// handshake in any split, and whatever it sends after its READY is left in
+/// `stream` for the socket's first read. It runs inside a `handshake` span
+/// that records the peer's socket type and how long the exchange took once it
+/// succeeds.
 pub async fn perform_handshake_with_options<S>(
     stream: &mut S,
     local_socket_type: SocketType,
@@ -84,6 +219,38 @@ pub async fn perform_handshake_with_options<S>(
     timeout: Option<Duration>,
     options: &SocketOptions,
 ) -> Result<HandshakeResult, ZmtpError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let span = debug_span!(
+        "handshake",
+        socket_type = local_socket_type.as_str(),
+        mechanism = SecurityMechanism::from_options(options).name(),
+        peer_socket_type = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+    );
+    let started = Instant::now();
+    let result = run_handshake(stream, local_socket_type, identity, timeout, options)
+        .instrument(span.clone())
+        .await;
+    if let Ok(hr) = &result {
+        span.record("peer_socket_type", hr.peer_socket_type.as_str());
+        span.record(
+            "duration_ms",
+            u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        );
+    }
+    result
+}
+
+#[allow(clippy::too_many_lines)]
+async fn run_handshake<S>(
+    stream: &mut S,
+    local_socket_type: SocketType,
+    identity: Option<&[u8]>,
+    timeout: Option<Duration>,
+    options: &SocketOptions,
+) -> Result<HandshakeResult, ZmtpError>
 where
     S: AsyncRead + AsyncWrite + Unpin,
 {
@@ -96,31 +263,21 @@ where
         mechanism
     );
 
-    // Step 1: Send our greeting
-    debug!("[HANDSHAKE] Step 1: Sending greeting...");
-    let greeting_bytes = build_greeting_with_mechanism(mechanism, options);
-    let BufResult(write_res, _) = write_all_with_timeout(stream, greeting_bytes.clone(), timeout)
-        .await
-        .map_err(|e| {
-            warn!("[HANDSHAKE] Step 1: Failed to send ZMTP greeting: {}", e);
-            ZmtpError::Protocol
-        })?;
-    write_res.map_err(|e| {
-        warn!(
-            "[HANDSHAKE] Step 1: Failed to write ZMTP greeting bytes: {}",
-            e
-        );
-        ZmtpError::Protocol
-    })?;
-    debug!(
-        "[HANDSHAKE] Step 1 DONE: Sent greeting ({} bytes)",
-        greeting_bytes.len()
-    );
-
-    // Step 2: Receive peer greeting
-    debug!("[HANDSHAKE] Step 2: Receiving peer greeting...");
-    let greeting_buf = [0u8; 64];
-    let BufResult(read_res, greeting_buf) = read_exact_with_timeout(stream, greeting_buf, timeout)
+    // Steps 1 and 2: exchange greetings. The peer's signature is checked
+    // before the rest of its greeting is read, so a connection that is not
+    // speaking ZMTP is dropped after its first few bytes. To answer HTTP in
+    // plain text we must not have sent our own binary greeting yet.
+    let signature = if options.friendly_http_reject {
+        let signature = read_signature(stream, timeout, options).await?;
+        send_greeting(stream, mechanism, timeout, options).await?;
+        signature
+    } else {
+        send_greeting(stream, mechanism, timeout, options).await?;
+        read_signature(stream, timeout, options).await?
+    };
+    let major = read_major_version(stream, timeout).await?;
+    let rest = [0u8; GREETING_SIZE - SIGNATURE_SIZE - 1];
+    let BufResult(read_res, rest) = read_exact_with_timeout(stream, rest, timeout)
         .await
         .map_err(|e| {
             warn!("[HANDSHAKE] Step 2: Failed to receive ZMTP greeting: {}", e);
@@ -133,106 +290,194 @@ where
         );
         ZmtpError::Protocol
     })?;
+    let mut greeting_buf = [0u8; GREETING_SIZE];
+    greeting_buf[..SIGNATURE_SIZE].copy_from_slice(&signature);
+    greeting_buf[SIGNATURE_SIZE] = major;
+    greeting_buf[SIGNATURE_SIZE + 1..].copy_from_slice(&rest);
     debug!("[HANDSHAKE] Step 2 DONE: Received peer greeting (64 bytes)");
 
-    // Validate greeting signature
-    if greeting_buf[0] != 0xFF || greeting_buf[9] != 0x7F {
-        warn!(
-            "[HANDSHAKE] ZMTP greeting: invalid signature bytes (expected [0]=0xff [9]=0x7f, got [0]=0x{:02x} [9]=0x{:02x})",
-            greeting_buf[0], greeting_buf[9]
-        );
-        return Err(ZmtpError::Protocol);
-    }
-
     // Parse peer greeting to check mechanism compatibility
-    use crate::greeting::ZmtpGreeting;
     let peer_greeting = ZmtpGreeting::parse(&Bytes::copy_from_slice(&greeting_buf[..]))
-        .map_err(|_| ZmtpError::Protocol)?;
-    let expected_mech = mechanism.as_greeting_bytes();
-    let peer_mech_str = peer_greeting.mechanism_str();
-    let our_mech_name = std::str::from_utf8(expected_mech).unwrap_or("NULL");
-    if !peer_mech_str.eq_ignore_ascii_case(our_mech_name) {
-        warn!(
-            "[HANDSHAKE] Security mechanism mismatch: we advertise {:?}, peer advertises {:?}",
-            our_mech_name, peer_mech_str
-        );
-        return Err(ZmtpError::Protocol);
-    }
-    if greeting_buf[9] != 0x7F {
-        warn!(
-            "[HANDSHAKE] ZMTP greeting: expected signature byte 0x7f at offset 9, got 0x{:02x}",
-            greeting_buf[9]
-        );
-        return Err(ZmtpError::Protocol);
+        .map_err(record_violation)?;
+    let version = ZmtpVersion::LOCAL.negotiate(peer_greeting.version);
+    debug!(
+        "[HANDSHAKE] Peer advertises ZMTP {}, speaking ZMTP {}",
+        peer_greeting.version, version
+    );
+    if let Err(e) = check_peer_mechanism(mechanism, mechanism.as_server(options), &peer_greeting) {
+        warn!("[HANDSHAKE] {}", e);
+        return Err(record_violation(e));
     }
-
     let peer_major = greeting_buf[10];
     if mechanism != SecurityMechanism::Null && peer_major != 3 {
         warn!(
             "[HANDSHAKE] non-NULL mechanism {:?} cannot negotiate with ZMTP major version {}",
             mechanism, peer_major
         );
-        return Err(ZmtpError::Protocol);
-    }
-
-    let peer_mechanism = parse_greeting_mechanism(&greeting_buf[12..32])?;
-    if peer_mechanism != mechanism {
-        warn!(
-            "[HANDSHAKE] security mechanism mismatch: local {:?}, peer {:?}",
-            mechanism, peer_mechanism
-        );
-        return Err(ZmtpError::Protocol);
+        return Err(record_violation(ZmtpError::Protocol));
     }
 
     // Step 3: Run security-mechanism-specific exchange (between greeting and READY)
     let curve_cipher: Option<crate::security::curve::CurveMessageCipher> = None;
+    let mut plain_login = None;
     match mechanism {
         SecurityMechanism::Null => {
             // No mechanism-level exchange for NULL; proceed directly to READY.
         }
         SecurityMechanism::Plain => {
-            run_plain_exchange(stream, options, timeout).await?;
+            plain_login = run_plain_exchange(stream, options, timeout).await?;
         }
         SecurityMechanism::Curve => {
             // CURVE handshake carries all metadata internally (no separate READY needed).
             let cr =
                 run_curve_exchange(stream, options, timeout, local_socket_type, identity).await?;
             let peer_socket_type = parse_socket_type(cr.peer_socket_type.as_ref())?;
+            let mut peer_metadata = PeerMetadata::new();
+            peer_metadata.insert("Socket-Type".to_owned(), cr.peer_socket_type);
+            if let Some(identity) = &cr.peer_identity {
+                peer_metadata.insert("Identity".to_owned(), identity.clone());
+            }
+            if let Some(user_id) = cr.user_id.as_ref().filter(|id| !id.is_empty()) {
+                peer_metadata.insert(USER_ID_PROPERTY.to_owned(), Bytes::from(user_id.clone()));
+            }
+            let principal = cr
+                .peer_public_key
+                .map(|key| Principal::curve(*key.as_bytes(), cr.user_id));
             return Ok(HandshakeResult {
                 peer_identity: cr.peer_identity,
                 peer_socket_type,
+                peer_metadata,
+                principal,
                 curve_cipher: cr.cipher,
+                version,
+                sequenced: false,
             });
         }
     }
 
-    // Step 4: Send READY command (NULL and PLAIN only)
-    debug!("[HANDSHAKE] Step 4: Sending READY command...");
-    let ready_body = build_ready(local_socket_type.as_str(), identity);
-    let ready_frame = encode_frame(FLAG_COMMAND, &ready_body);
-    let BufResult(write_res, _) = write_all_with_timeout(stream, ready_frame.clone(), timeout)
+    // Steps 4 and 5: exchange metadata (NULL and PLAIN only). NULL peers
+    // both send READY; a PLAIN client sends INITIATE and the server answers
+    // it with READY only once it has arrived.
+    let advertise_sequence = options.dedupe_window > 0
+        && matches!(local_socket_type, SocketType::Dealer | SocketType::Router);
+    let answers_initiate = mechanism == SecurityMechanism::Plain && options.plain_server;
+    let (local_command, peer_command) = match mechanism {
+        SecurityMechanism::Plain if answers_initiate => (READY, INITIATE),
+        SecurityMechanism::Plain => (INITIATE, READY),
+        _ => (READY, READY),
+    };
+    let mut metadata = BytesMut::from(build_metadata_command(
+        local_command,
+        local_socket_type.as_str(),
+        identity,
+    ));
+    if advertise_sequence {
+        put_property(&mut metadata, crate::sequence::SEQUENCE_PROPERTY, b"1");
+    }
+    put_local_metadata(&mut metadata, &options.metadata);
+    let metadata = metadata.freeze();
+    let mut peer = if answers_initiate {
+        let peer = recv_metadata_command(stream, peer_command, timeout).await?;
+        send_metadata_command(stream, local_command, &metadata, timeout).await?;
+        peer
+    } else {
+        send_metadata_command(stream, local_command, &metadata, timeout).await?;
+        recv_metadata_command(stream, peer_command, timeout).await?
+    };
+    let principal = plain_login.map(|(username, user_id)| {
+        if !user_id.is_empty() {
+            peer.metadata
+                .insert(USER_ID_PROPERTY.to_owned(), Bytes::from(user_id.clone()));
+        }
+        Principal::plain(username, Some(user_id))
+    });
+
+    debug!(
+        "[HANDSHAKE] Handshake complete! Peer is {}",
+        peer.socket_type.as_str()
+    );
+
+    Ok(HandshakeResult {
+        peer_identity: peer.identity,
+        peer_socket_type: peer.socket_type,
+        peer_metadata: peer.metadata,
+        principal,
+        curve_cipher,
+        version,
+        sequenced: advertise_sequence && peer.sequenced,
+    })
+}
+
+/// Append the application properties from [`SocketOptions::metadata`] to
+/// our metadata command, in name order so the bytes are deterministic.
+///
+/// Names rejected by [`SocketOptions::validate_metadata_property`] are
+/// dropped: `Socket-Type` and `Identity` are already written from the socket
+/// itself, and other names may not fit the 1-byte length field.
+fn put_local_metadata(body: &mut BytesMut, metadata: &HashMap<String, Bytes>) {
+    let mut properties: Vec<_> = metadata.iter().collect();
+    properties.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    for (name, value) in properties {
+        if let Err(e) = SocketOptions::validate_metadata_property(name) {
+            debug!(
+                "[HANDSHAKE] Not sending metadata property {:?}: {}",
+                name, e
+            );
+            continue;
+        }
+        put_property(body, name, value);
+    }
+}
+
+/// Step 4: send our metadata command (`READY` or `INITIATE`).
+async fn send_metadata_command<S>(
+    stream: &mut S,
+    name: &str,
+    body: &Bytes,
+    timeout: Option<Duration>,
+) -> Result<(), ZmtpError>
+where
+    S: AsyncWrite + Unpin,
+{
+    debug!("[HANDSHAKE] Step 4: Sending {} command...", name);
+    let frame = encode_frame(FLAG_COMMAND, body);
+    let frame_len = frame.len();
+    let BufResult(write_res, _) = write_all_with_timeout(stream, frame, timeout)
         .await
         .map_err(|e| {
             warn!(
-                "[HANDSHAKE] Step 4: Failed to send ZMTP READY command: {}",
-                e
+                "[HANDSHAKE] Step 4: Failed to send ZMTP {} command: {}",
+                name, e
             );
             ZmtpError::Protocol
         })?;
     write_res.map_err(|e| {
         warn!(
-            "[HANDSHAKE] Step 4: Failed to write ZMTP READY command bytes: {}",
-            e
+            "[HANDSHAKE] Step 4: Failed to write ZMTP {} command bytes: {}",
+            name, e
         );
         ZmtpError::Protocol
     })?;
     debug!(
-        "[HANDSHAKE] Step 4 DONE: Sent READY command ({} bytes)",
-        ready_frame.len()
+        "[HANDSHAKE] Step 4 DONE: Sent {} command ({} bytes)",
+        name, frame_len
     );
+    Ok(())
+}
 
-    // Step 5: Receive peer READY command
-    debug!("[HANDSHAKE] Step 5: Receiving peer READY command...");
+/// Step 5: receive the peer's metadata command, which must be `name`.
+///
+/// An ERROR command in its place becomes [`ZmtpError::PeerError`]; a
+/// malformed one is answered with ERROR before the error is returned.
+async fn recv_metadata_command<S>(
+    stream: &mut S,
+    name: &str,
+    timeout: Option<Duration>,
+) -> Result<ReadyProperties, ZmtpError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    debug!("[HANDSHAKE] Step 5: Receiving peer {} command...", name);
     let header_buf = [0u8; 2];
     let BufResult(read_res, header_buf) = read_exact_with_timeout(stream, header_buf, timeout)
         .await
@@ -265,7 +510,7 @@ where
              got flags=0x{:02x}  -  peer sent a data frame instead of READY",
             flags
         );
-        return Err(ZmtpError::Protocol);
+        return Err(record_violation(ZmtpError::Protocol));
     }
 
     // Read body length
@@ -293,7 +538,7 @@ where
                 "[HANDSHAKE] ZMTP READY long-frame length overflows usize: {}",
                 raw_len
             );
-            return Err(ZmtpError::Protocol);
+            return Err(record_violation(ZmtpError::Protocol));
         }
         raw_len as usize
     } else {
@@ -308,7 +553,7 @@ where
             "[HANDSHAKE] ZMTP READY body too large: got {} bytes, maximum allowed is {} bytes",
             body_len, MAX_READY_SIZE
         );
-        return Err(ZmtpError::Protocol);
+        return Err(record_violation(ZmtpError::Protocol));
     }
     let body_buf = vec![0u8; body_len];
     let BufResult(read_res, body_buf) = read_exact_with_timeout(stream, body_buf, timeout)
@@ -329,20 +574,36 @@ where
     })?;
     debug!("[HANDSHAKE] Step 5c DONE: Read {} bytes of body", body_len);
 
-    // Parse READY command
-    let ready_bytes = Bytes::from(body_buf);
-    let (peer_socket_type, peer_identity) = parse_ready_command(&ready_bytes)?;
+    parse_metadata_command(stream, name, body_buf).await
+}
 
-    debug!(
-        "[HANDSHAKE] Handshake complete! Peer is {}",
-        peer_socket_type.as_str()
-    );
+/// Step 5d: turn the body of the peer's metadata command into its properties.
+async fn parse_metadata_command<S>(
+    stream: &mut S,
+    name: &str,
+    body: Vec<u8>,
+) -> Result<ReadyProperties, ZmtpError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    // A peer that refuses us (say, a ZAP denial) sends ERROR and closes.
+    if let Some(err) = peer_error(&body) {
+        warn!(
+            "[HANDSHAKE] Step 5: peer sent ERROR instead of {}: {}",
+            name, err
+        );
+        return Err(err);
+    }
 
-    Ok(HandshakeResult {
-        peer_identity,
-        peer_socket_type,
-        curve_cipher,
-    })
+    // Parse the metadata command; tell the peer why before we hang up on it.
+    let ready_bytes = Bytes::from(body);
+    match parse_ready_properties(&ready_bytes, name) {
+        Ok(properties) => Ok(properties),
+        Err(e) => {
+            send_zmtp_error(stream, &format!("malformed {name} command")).await;
+            Err(record_violation(e))
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -353,11 +614,14 @@ where
 ///
 /// - Client mode: send HELLO, receive WELCOME/ERROR.
 /// - Server mode: receive HELLO, validate, send WELCOME/ERROR.
+///
+/// Returns the client's username and the user id ZAP assigned it, on the
+/// server side.
 async fn run_plain_exchange<S>(
     stream: &mut S,
     options: &SocketOptions,
     timeout: Option<Duration>,
-) -> Result<(), ZmtpError>
+) -> Result<Option<(String, String)>, ZmtpError>
 where
     S: AsyncRead + AsyncWrite + Unpin,
 {
@@ -366,17 +630,19 @@ where
     if options.plain_server {
         debug!("[HANDSHAKE] Running PLAIN server exchange");
         let domain = options.zap_domain.as_str();
-        crate::security::plain::plain_server_handshake_zap(stream, domain, "unknown", timeout)
+        crate::security::plain::plain_server_exchange_zap(stream, domain, "unknown", timeout)
             .await
-            .map(|_| ())
+            .map(Some)
     } else if let Some(ref username) = options.plain_username {
         debug!("[HANDSHAKE] Running PLAIN client exchange");
         let password = options.plain_password.as_deref().unwrap_or("");
         let credentials = PlainCredentials::new(username.clone(), password);
-        plain_client_handshake(stream, &credentials, timeout).await
+        plain_client_handshake(stream, &credentials, timeout)
+            .await
+            .map(|()| None)
     } else {
         // Should not happen (mechanism detection guards this), but be safe.
-        Ok(())
+        Ok(None)
     }
 }
 
@@ -451,6 +717,7 @@ where
         );
         Err(ZmtpError::Protocol)
     } else {
+        warn!("[HANDSHAKE] CURVE client mode requires curve_secretkey, but it is missing");
         Err(ZmtpError::Protocol)
     }
 }
@@ -459,7 +726,113 @@ where
 // Greeting helpers
 // ---------------------------------------------------------------------------
 
-/// Build a ZMTP 3.0 greeting (64 bytes) advertising the given security mechanism.
+/// Response to an HTTP request on a ZMTP port, when
+/// [`SocketOptions::friendly_http_reject`] is set.
+const HTTP_REJECT: &[u8] = b"HTTP/1.1 400 Bad Request\r\n\
+    Content-Type: text/plain\r\n\
+    Content-Length: 51\r\n\
+    Connection: close\r\n\r\n\
+    This port speaks ZMTP (ZeroMQ), not HTTP. Goodbye.\n";
+
+/// Step 1: send our 64-byte greeting.
+async fn send_greeting<S>(
+    stream: &mut S,
+    mechanism: SecurityMechanism,
+    timeout: Option<Duration>,
+    options: &SocketOptions,
+) -> Result<(), ZmtpError>
+where
+    S: AsyncWrite + Unpin,
+{
+    debug!("[HANDSHAKE] Step 1: Sending greeting...");
+    let greeting_bytes = build_greeting_with_mechanism(mechanism, options);
+    let len = greeting_bytes.len();
+    let BufResult(write_res, _) = write_all_with_timeout(stream, greeting_bytes, timeout)
+        .await
+        .map_err(|e| {
+            warn!("[HANDSHAKE] Step 1: Failed to send ZMTP greeting: {}", e);
+            ZmtpError::Protocol
+        })?;
+    write_res.map_err(|e| {
+        warn!(
+            "[HANDSHAKE] Step 1: Failed to write ZMTP greeting bytes: {}",
+            e
+        );
+        ZmtpError::Protocol
+    })?;
+    debug!("[HANDSHAKE] Step 1 DONE: Sent greeting ({} bytes)", len);
+    Ok(())
+}
+
+/// Step 2a: read the peer's signature, rejecting a connection that does not
+/// open with it without reading any further.
+async fn read_signature<S>(
+    stream: &mut S,
+    timeout: Option<Duration>,
+    options: &SocketOptions,
+) -> Result<[u8; SIGNATURE_SIZE], ZmtpError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    debug!("[HANDSHAKE] Step 2: Receiving peer greeting...");
+    let signature = [0u8; SIGNATURE_SIZE];
+    let BufResult(read_res, signature) = read_exact_with_timeout(stream, signature, timeout)
+        .await
+        .map_err(|e| {
+            warn!("[HANDSHAKE] Step 2: Failed to receive ZMTP greeting: {}", e);
+            ZmtpError::Protocol
+        })?;
+    read_res.map_err(|e| {
+        warn!(
+            "[HANDSHAKE] Step 2: Failed to read ZMTP greeting bytes: {}",
+            e
+        );
+        ZmtpError::Protocol
+    })?;
+    let Some(kind) = ProbeKind::detect(&signature) else {
+        return Ok(signature);
+    };
+
+    // Scanners and health checks are routine on an exposed port: log them
+    // quietly rather than as handshake failures.
+    debug!(
+        "[HANDSHAKE] Closing connection that is not speaking ZMTP ({} probe, starts with {:02x?})",
+        kind, signature
+    );
+    if kind == ProbeKind::Http && options.friendly_http_reject {
+        // Best effort: the connection is closed whether or not this arrives.
+        let _ = write_all_with_timeout(stream, Bytes::from_static(HTTP_REJECT), timeout).await;
+    }
+    Err(record_probe(kind))
+}
+
+/// Step 2b: read the major version that follows the signature, rejecting a
+/// pre-3.0 peer before waiting for a 64-byte greeting it will never send.
+async fn read_major_version<S>(stream: &mut S, timeout: Option<Duration>) -> Result<u8, ZmtpError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let BufResult(read_res, major) = read_exact_with_timeout(stream, [0u8; 1], timeout)
+        .await
+        .map_err(|e| {
+            warn!("[HANDSHAKE] Step 2: Failed to receive ZMTP greeting: {}", e);
+            ZmtpError::Protocol
+        })?;
+    read_res.map_err(|e| {
+        warn!(
+            "[HANDSHAKE] Step 2: Failed to read ZMTP greeting bytes: {}",
+            e
+        );
+        ZmtpError::Protocol
+    })?;
+    if let Err(e) = crate::greeting::check_major_version(major[0]) {
+        warn!("[HANDSHAKE] {}", e);
+        return Err(record_violation(e));
+    }
+    Ok(major[0])
+}
+
+/// Build a ZMTP 3.x greeting (64 bytes) advertising the given security mechanism.
 fn build_greeting_with_mechanism(mechanism: SecurityMechanism, options: &SocketOptions) -> Bytes {
     let mut b = BytesMut::with_capacity(64);
 
@@ -468,8 +841,8 @@ fn build_greeting_with_mechanism(mechanism: SecurityMechanism, options: &SocketO
     b.extend_from_slice(&[0u8; 8]);
     b.extend_from_slice(&[0x7F]);
 
-    // Version 3.0
-    b.extend_from_slice(&[0x03, 0x00]);
+    // Highest version we speak; the peer may answer with 3.0
+    b.extend_from_slice(&[ZmtpVersion::LOCAL.major, ZmtpVersion::LOCAL.minor]);
 
     // Mechanism field: 20 bytes, ASCII name padded with NUL
     let mech_name = mechanism.as_greeting_bytes();
@@ -478,12 +851,7 @@ fn build_greeting_with_mechanism(mechanism: SecurityMechanism, options: &SocketO
     b.put_bytes(0, padding);
 
     // As-server flag (byte 32): 1 if this side acts as CURVE/PLAIN server
-    let as_server = match mechanism {
-        SecurityMechanism::Curve => options.curve_server,
-        SecurityMechanism::Plain => options.plain_server,
-        SecurityMechanism::Null => false,
-    };
-    b.extend_from_slice(&[u8::from(as_server)]);
+    b.extend_from_slice(&[u8::from(mechanism.as_server(options))]);
 
     // Padding to reach 64 bytes total
     b.extend_from_slice(&[0u8; 31]);
@@ -491,108 +859,90 @@ fn build_greeting_with_mechanism(mechanism: SecurityMechanism, options: &SocketO
     b.freeze()
 }
 
-fn parse_greeting_mechanism(field: &[u8]) -> Result<SecurityMechanism, ZmtpError> {
-    let len = field
-        .iter()
-        .position(|&byte| byte == 0)
-        .unwrap_or(field.len());
-    match &field[..len] {
-        b"NULL" => Ok(SecurityMechanism::Null),
-        b"PLAIN" => Ok(SecurityMechanism::Plain),
-        b"CURVE" => Ok(SecurityMechanism::Curve),
-        _ => Err(ZmtpError::Protocol),
-    }
-}
-
 /// Parse READY command to extract socket type and identity
 pub fn parse_ready_command(body: &Bytes) -> Result<(SocketType, Option<Bytes>), ZmtpError> {
-    // READY format:
-    // - 1 byte: command name length
-    // - N bytes: "READY"
-    // - Properties as key-value pairs
+    parse_ready_properties(body, READY).map(|peer| (peer.socket_type, peer.identity))
+}
 
-    if body.len() < 6 {
-        warn!(
-            "[HANDSHAKE] ZMTP READY parse: body too short  -  got {} bytes, need at least 6",
-            body.len()
-        );
-        return Err(ZmtpError::Protocol);
-    }
+/// The READY properties the handshake acts on.
+pub struct ReadyProperties {
+    pub socket_type: SocketType,
+    pub identity: Option<Bytes>,
+    /// The peer advertised `X-Sequence`.
+    pub sequenced: bool,
+    /// Every property, including the ones above.
+    pub metadata: PeerMetadata,
+}
 
-    let name_len = body[0] as usize;
-    if name_len != 5 || &body[1..6] != b"READY" {
-        warn!(
-            "[HANDSHAKE] ZMTP READY parse: expected command name \"READY\" (length=5), \
-             got length={} name={:?}",
-            name_len,
-            body.get(1..1 + name_len.min(body.len().saturating_sub(1)))
-                .map(|b| String::from_utf8_lossy(b).into_owned())
-                .unwrap_or_default()
-        );
-        return Err(ZmtpError::Protocol);
-    }
+/// Parse a READY command into all of its properties.
+///
+/// Validates the command like [`parse_ready_command`]: `Socket-Type` must be
+/// present and known, and `Identity` at most 255 bytes.
+pub fn parse_ready_metadata(body: &Bytes) -> Result<PeerMetadata, ZmtpError> {
+    parse_ready_properties(body, READY).map(|peer| peer.metadata)
+}
+
+pub fn parse_ready_properties(body: &Bytes, name: &str) -> Result<ReadyProperties, ZmtpError> {
+    let header_len = check_command_name(body, name)?;
 
     // Parse properties
-    let mut offset = 6;
+    let mut offset = header_len;
     let mut socket_type = None;
     let mut identity = None;
+    let mut sequenced = false;
+    let mut metadata = PeerMetadata::new();
 
-    while offset < body.len() {
-        if offset + 1 > body.len() {
-            warn!("[HANDSHAKE] READY property truncated at key-length byte");
-            return Err(ZmtpError::Protocol);
-        }
-
-        let key_len = body[offset] as usize;
+    let malformed = |reason| ZmtpError::MalformedCommand {
+        command: name.to_owned(),
+        reason,
+    };
+    while let Some(&key_len) = body.get(offset) {
         offset += 1;
+        if key_len == 0 {
+            return Err(malformed("empty property name"));
+        }
 
-        if offset + key_len > body.len() {
+        let Some(key) = body.get(offset..offset + usize::from(key_len)) else {
             warn!(
-                "[HANDSHAKE] READY property key truncated (key_len={})",
-                key_len
+                "[HANDSHAKE] {} property key truncated (key_len={})",
+                name, key_len
             );
-            return Err(ZmtpError::Protocol);
-        }
-
-        let key = &body[offset..offset + key_len];
-        offset += key_len;
-
-        if offset + 4 > body.len() {
-            warn!("[HANDSHAKE] READY property value-length truncated");
-            return Err(ZmtpError::Protocol);
-        }
+            return Err(malformed("property name truncated"));
+        };
+        offset += key.len();
 
-        let value_len = u32::from_be_bytes([
-            body[offset],
-            body[offset + 1],
-            body[offset + 2],
-            body[offset + 3],
-        ]) as usize;
+        let Some(&len_bytes) = body
+            .get(offset..offset + 4)
+            .and_then(|b| <&[u8; 4]>::try_from(b).ok())
+        else {
+            warn!("[HANDSHAKE] {} property value-length truncated", name);
+            return Err(malformed("property value length truncated"));
+        };
         offset += 4;
 
-        if offset + value_len > body.len() {
+        let value_len = u32::from_be_bytes(len_bytes) as usize;
+        if value_len > body.len() - offset {
             warn!(
-                "[HANDSHAKE] READY property value truncated (value_len={})",
-                value_len
+                "[HANDSHAKE] {} property value truncated (value_len={})",
+                name, value_len
             );
-            return Err(ZmtpError::Protocol);
+            return Err(malformed("property value truncated"));
         }
 
-        // Store the range for zero-copy slice
-        let value_start = offset;
-        let value_end = offset + value_len;
+        // Zero-copy: the value is a slice of the command body
+        let value = body.slice(offset..offset + value_len);
         offset += value_len;
 
         match key {
             b"Socket-Type" => {
                 if socket_type.is_some() {
-                    return Err(ZmtpError::Protocol);
+                    return Err(malformed("duplicate Socket-Type property"));
                 }
-                socket_type = Some(parse_socket_type(&body[value_start..value_end])?);
+                socket_type = Some(parse_socket_type(&value)?);
             }
             b"Identity" => {
                 if identity.is_some() {
-                    return Err(ZmtpError::Protocol);
+                    return Err(malformed("duplicate Identity property"));
                 }
                 // ZMQ spec limits identities to 255 bytes.
                 if value_len > 255 {
@@ -600,22 +950,58 @@ pub fn parse_ready_command(body: &Bytes) -> Result<(SocketType, Option<Bytes>),
                         "[HANDSHAKE] READY Identity property too long: {} bytes (max 255)",
                         value_len
                     );
-                    return Err(ZmtpError::Protocol);
+                    return Err(malformed("Identity longer than 255 bytes"));
                 }
-                // Zero-copy: slice the existing Bytes instead of copying
-                identity = Some(body.slice(value_start..value_end));
+                identity = Some(value.clone());
             }
+            key if key == crate::sequence::SEQUENCE_PROPERTY.as_bytes() => sequenced = true,
             _ => {
-                // Ignore unknown properties
+                // Not acted on here; still recorded in `metadata` below.
             }
         }
+        metadata.insert(String::from_utf8_lossy(key).into_owned(), value);
     }
 
     let socket_type = socket_type.ok_or_else(|| {
         warn!("[HANDSHAKE] ZMTP READY parse: peer READY command is missing the required \"Socket-Type\" property");
-        ZmtpError::Protocol
+        malformed("missing Socket-Type property")
     })?;
-    Ok((socket_type, identity))
+    Ok(ReadyProperties {
+        socket_type,
+        identity,
+        sequenced,
+        metadata,
+    })
+}
+
+/// Check that a metadata command body starts with `name`, returning the
+/// offset of its first property.
+fn check_command_name(body: &Bytes, name: &str) -> Result<usize, ZmtpError> {
+    // READY (or INITIATE) format:
+    // - 1 byte: command name length
+    // - N bytes: command name
+    // - Properties as key-value pairs
+    //
+    // A short body cannot hold `name`, so it is some other (or a truncated)
+    // command; both are reported as an unexpected command.
+
+    let header_len = 1 + name.len();
+    let name_len = body.first().map_or(0, |&len| usize::from(len));
+    if name_len != name.len() || body.get(1..header_len) != Some(name.as_bytes()) {
+        warn!(
+            "[HANDSHAKE] ZMTP {} parse: expected command name {:?} (length={}), \
+             got length={} name={:?}",
+            name,
+            name,
+            name.len(),
+            name_len,
+            body.get(1..1 + name_len.min(body.len().saturating_sub(1)))
+                .map(|b| String::from_utf8_lossy(b).into_owned())
+                .unwrap_or_default()
+        );
+        return Err(ZmtpError::Protocol);
+    }
+    Ok(header_len)
 }
 
 /// Parse socket type from bytes
@@ -637,7 +1023,10 @@ fn parse_socket_type(value: &[u8]) -> Result<SocketType, ZmtpError> {
                 "[HANDSHAKE] ZMTP READY parse: unknown Socket-Type value {:?}",
                 String::from_utf8_lossy(value)
             );
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::MalformedCommand {
+                command: READY.to_owned(),
+                reason: "unknown Socket-Type",
+            })
         }
     }
 }
@@ -673,7 +1062,7 @@ mod tests {
 
         assert!(matches!(
             parse_ready_command(&body),
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::MalformedCommand { .. })
         ));
     }
 
@@ -687,7 +1076,35 @@ mod tests {
 
         assert!(matches!(
             parse_ready_command(&body),
-            Err(ZmtpError::Protocol)
+            Err(ZmtpError::MalformedCommand { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_ready_metadata_keeps_custom_properties() {
+        let body = Bytes::from_static(
+            b"\x05READY\
+              \x0bSocket-Type\x00\x00\x00\x06DEALER\
+              \x07User-Id\x00\x00\x00\x05alice\
+              \x08X-Region\x00\x00\x00\x07eu-west\
+              \x08X-Region\x00\x00\x00\x07us-east",
+        );
+
+        let metadata = parse_ready_metadata(&body).unwrap();
+        assert_eq!(metadata.len(), 3);
+        assert_eq!(metadata["Socket-Type"], Bytes::from_static(b"DEALER"));
+        assert_eq!(metadata[USER_ID_PROPERTY], Bytes::from_static(b"alice"));
+        // A repeated application property keeps its last value.
+        assert_eq!(metadata["X-Region"], Bytes::from_static(b"us-east"));
+    }
+
+    #[test]
+    fn parse_ready_rejects_empty_property_name() {
+        let body = ready_body(&[(b"Socket-Type", b"DEALER"), (b"", b"value")]);
+
+        assert!(matches!(
+            parse_ready_command(&body),
+            Err(ZmtpError::MalformedCommand { .. })
         ));
     }
 
@@ -706,15 +1123,16 @@ mod tests {
         write_res.unwrap();
     }
 
+    /// The command name of the first command frame the client sends, if any.
     async fn maybe_read_plain_hello(stream: &mut TcpStream) -> Option<[u8; 6]> {
-        let header = [0u8; 6];
+        let header = [0u8; 8];
         let Ok(BufResult(read_res, header)) =
             read_exact_with_timeout(stream, header, Some(TEST_TIMEOUT)).await
         else {
             return None;
         };
         read_res.ok()?;
-        Some(header)
+        header[2..].try_into().ok()
     }
 
     async fn maybe_complete_ready_exchange(stream: &mut TcpStream) {
@@ -799,6 +1217,166 @@ mod tests {
         });
     }
 
+    /// Run a server-side handshake against a client that sends `probe` and
+    /// keeps the connection open, expecting it to read `consumed` bytes.
+    /// Returns the handshake result, the probe bytes the handshake left
+    /// unread, and what the client got back.
+    fn handshake_against_probe(
+        probe: &'static [u8],
+        consumed: usize,
+        options: SocketOptions,
+    ) -> (Result<HandshakeResult, ZmtpError>, Vec<u8>, Vec<u8>) {
+        handshake_against_chunked_probe(probe.to_vec(), probe.len(), consumed, options)
+    }
+
+    /// [`handshake_against_probe`], with the client writing `probe` in
+    /// separate `chunk`-byte writes.
+    fn handshake_against_chunked_probe(
+        probe: Vec<u8>,
+        chunk: usize,
+        consumed: usize,
+        options: SocketOptions,
+    ) -> (Result<HandshakeResult, ZmtpError>, Vec<u8>, Vec<u8>) {
+        let probe_len = probe.len();
+        LocalRuntime::new().unwrap().block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+
+            let client_task = monocoque_core::rt::spawn(async move {
+                let mut stream = TcpStream::connect(addr).await.unwrap();
+                for piece in probe.chunks(chunk) {
+                    let BufResult(res, _) =
+                        write_all_with_timeout(&mut stream, piece.to_vec(), Some(TEST_TIMEOUT))
+                            .await
+                            .unwrap();
+                    res.unwrap();
+                    if chunk < probe.len() {
+                        monocoque_core::rt::sleep(Duration::from_millis(1)).await;
+                    }
+                }
+                let mut received = Vec::new();
+                loop {
+                    let BufResult(res, chunk) = stream.read(Vec::with_capacity(256)).await;
+                    if res.unwrap() == 0 {
+                        break received;
+                    }
+                    received.extend_from_slice(&chunk);
+                }
+            });
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let result = perform_handshake_with_options(
+                &mut stream,
+                SocketType::Router,
+                None,
+                Some(TEST_TIMEOUT),
+                &options,
+            )
+            .await;
+            let unread = vec![0u8; probe_len - consumed];
+            let BufResult(res, unread) =
+                read_exact_with_timeout(&mut stream, unread, Some(TEST_TIMEOUT))
+                    .await
+                    .unwrap();
+            res.unwrap();
+            drop(stream);
+            (result, unread, monocoque_core::rt::join(client_task).await)
+        })
+    }
+
+    #[test]
+    fn handshake_drops_tls_client_hello_after_the_signature() {
+        // A truncated ClientHello: shorter than a greeting, so a handshake
+        // waiting for 64 bytes would time out instead.
+        const HELLO: &[u8] = b"\x16\x03\x01\x00\xf4\x01\x00\x00\xf0\x03\x03\x5e\x21\x9a";
+        let before = crate::codec::non_zmtp_probes(ProbeKind::Tls);
+
+        let (result, unread, received) =
+            handshake_against_probe(HELLO, SIGNATURE_SIZE, SocketOptions::new());
+
+        assert!(matches!(
+            result,
+            Err(ZmtpError::NotZmtp {
+                kind: ProbeKind::Tls
+            })
+        ));
+        assert_eq!(unread, &HELLO[SIGNATURE_SIZE..], "read past the signature");
+        assert!(crate::codec::non_zmtp_probes(ProbeKind::Tls) > before);
+        // Without friendly_http_reject our greeting goes out first, as usual.
+        assert_eq!(received.len(), GREETING_SIZE);
+    }
+
+    #[test]
+    fn handshake_answers_http_probe_when_friendly_reject_is_set() {
+        const GET: &[u8] = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+        let before = crate::codec::non_zmtp_probes(ProbeKind::Http);
+
+        let (result, unread, received) = handshake_against_probe(
+            GET,
+            SIGNATURE_SIZE,
+            SocketOptions::new().with_friendly_http_reject(true),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ZmtpError::NotZmtp {
+                kind: ProbeKind::Http
+            })
+        ));
+        assert_eq!(unread, &GET[SIGNATURE_SIZE..], "read past the signature");
+        assert!(crate::codec::non_zmtp_probes(ProbeKind::Http) > before);
+        assert_eq!(received, HTTP_REJECT, "greeting sent before the HTTP reply");
+    }
+
+    #[test]
+    fn handshake_reads_the_same_from_split_and_coalesced_writes() {
+        // A DEALER's greeting, READY and first message, which a peer may
+        // deliver in any split or all at once.
+        let mut probe =
+            build_greeting_with_mechanism(SecurityMechanism::Null, &SocketOptions::new()).to_vec();
+        let ready = crate::utils::encode_frame(
+            crate::utils::FLAG_COMMAND,
+            &crate::utils::build_ready("DEALER", Some(b"d1")),
+        );
+        probe.extend_from_slice(&ready);
+        let consumed = GREETING_SIZE + ready.len();
+        probe.extend_from_slice(b"\x00\x05hello");
+
+        let summary = |chunk| {
+            let (result, unread, _) = handshake_against_chunked_probe(
+                probe.clone(),
+                chunk,
+                consumed,
+                SocketOptions::new(),
+            );
+            let hr = result.unwrap();
+            (hr.peer_socket_type, hr.peer_identity, hr.version, unread)
+        };
+
+        let whole = summary(probe.len());
+        assert_eq!(whole.0, SocketType::Dealer);
+        assert_eq!(whole.1.as_deref(), Some(&b"d1"[..]));
+        assert_eq!(whole.3, b"\x00\x05hello", "read past the handshake");
+        assert_eq!(summary(1), whole, "1-byte writes");
+        assert_eq!(summary(7), whole, "7-byte writes");
+    }
+
+    #[test]
+    fn handshake_rejects_zmtp_2_peer_after_its_major_version() {
+        // A ZMTP 2.0 DEALER greeting: signature, revision 1, socket type and
+        // an empty identity frame. Far shorter than a 3.x greeting.
+        const ZMTP_2: &[u8] = b"\xff\x00\x00\x00\x00\x00\x00\x00\x01\x7f\x01\x05\x00\x00";
+
+        let (result, unread, _) =
+            handshake_against_probe(ZMTP_2, SIGNATURE_SIZE + 1, SocketOptions::new());
+
+        assert!(
+            matches!(result, Err(ZmtpError::UnsupportedVersion { major: 1 })),
+            "{result:?}"
+        );
+        assert_eq!(unread, &ZMTP_2[SIGNATURE_SIZE + 1..]);
+    }
+
     #[test]
     fn non_null_handshake_rejects_peer_greeting_with_unsupported_major_version() {
         LocalRuntime::new().unwrap().block_on(async {
@@ -879,4 +1457,198 @@ mod tests {
             monocoque_core::rt::join(peer_task).await;
         });
     }
+
+    #[test]
+    fn ready_carries_configured_metadata_properties() {
+        LocalRuntime::new().unwrap().block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+
+            let peer_task = monocoque_core::rt::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                read_client_greeting(&mut stream).await;
+                let greeting =
+                    build_greeting_with_mechanism(SecurityMechanism::Null, &SocketOptions::new());
+                write_greeting(&mut stream, greeting.to_vec()).await;
+
+                let header = [0u8; 2];
+                let BufResult(read_res, header) =
+                    read_exact_with_timeout(&mut stream, header, Some(TEST_TIMEOUT))
+                        .await
+                        .unwrap();
+                read_res.unwrap();
+                let body = vec![0u8; header[1] as usize];
+                let BufResult(read_res, body) =
+                    read_exact_with_timeout(&mut stream, body, Some(TEST_TIMEOUT))
+                        .await
+                        .unwrap();
+                read_res.unwrap();
+
+                let ready_frame = crate::utils::encode_frame(
+                    crate::utils::FLAG_COMMAND,
+                    &crate::utils::build_ready("DEALER", None),
+                );
+                let BufResult(write_res, _) =
+                    write_all_with_timeout(&mut stream, ready_frame.to_vec(), Some(TEST_TIMEOUT))
+                        .await
+                        .unwrap();
+                write_res.unwrap();
+                Bytes::from(body)
+            });
+
+            let options = SocketOptions::new()
+                .with_metadata_property("X-Region", Bytes::from_static(b"eu-west"))
+                .with_metadata_property("socket-type", Bytes::from_static(b"PUB"))
+                .with_metadata_property("Identity", Bytes::from_static(b"spoofed"));
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            perform_handshake_with_options(
+                &mut stream,
+                SocketType::Dealer,
+                None,
+                Some(TEST_TIMEOUT),
+                &options,
+            )
+            .await
+            .unwrap();
+            let ready = monocoque_core::rt::join(peer_task).await;
+
+            let mut property = BytesMut::new();
+            put_property(&mut property, "X-Region", b"eu-west");
+            assert!(
+                ready.windows(property.len()).any(|w| w == &property[..]),
+                "READY did not carry X-Region: {ready:?}"
+            );
+
+            let metadata = parse_ready_metadata(&ready).unwrap();
+            assert_eq!(metadata["Socket-Type"], Bytes::from_static(b"DEALER"));
+            assert!(!metadata.contains_key("socket-type"));
+            assert!(!metadata.contains_key("Identity"));
+        });
+    }
+
+    #[test]
+    fn handshake_result_reports_the_peer_socket_type() {
+        LocalRuntime::new().unwrap().block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let accepted = monocoque_core::rt::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                perform_handshake_with_options(
+                    &mut stream,
+                    SocketType::Router,
+                    None,
+                    Some(TEST_TIMEOUT),
+                    &SocketOptions::new(),
+                )
+                .await
+                .unwrap()
+            });
+
+            let options = SocketOptions::new()
+                .with_metadata_property("X-Region", Bytes::from_static(b"eu-west"));
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let connected = perform_handshake_with_options(
+                &mut stream,
+                SocketType::Dealer,
+                Some(&b"client-1"[..]),
+                Some(TEST_TIMEOUT),
+                &options,
+            )
+            .await
+            .unwrap();
+            let accepted = monocoque_core::rt::join(accepted).await;
+
+            assert_eq!(connected.peer_socket_type, SocketType::Router);
+            assert_eq!(connected.peer_socket_type_str(), Some("ROUTER"));
+            assert_eq!(accepted.peer_socket_type, SocketType::Dealer);
+            assert_eq!(accepted.peer_socket_type_str(), Some("DEALER"));
+            assert_eq!(accepted.metadata()["Identity"], &b"client-1"[..]);
+            assert_eq!(accepted.metadata()["X-Region"], &b"eu-west"[..]);
+        });
+    }
+
+    fn peer_greeting(mechanism: SecurityMechanism, options: &SocketOptions) -> ZmtpGreeting {
+        ZmtpGreeting::parse(&build_greeting_with_mechanism(mechanism, options)).unwrap()
+    }
+
+    #[test]
+    fn peer_mechanism_must_match() {
+        let peer = peer_greeting(SecurityMechanism::Curve, &SocketOptions::new());
+        let err = check_peer_mechanism(SecurityMechanism::Plain, false, &peer).unwrap_err();
+        assert!(
+            matches!(&err, ZmtpError::MechanismMismatch { local, peer } if local == "PLAIN" && peer == "CURVE"),
+            "{err}"
+        );
+        assert_eq!(
+            err.to_string(),
+            "Security mechanism mismatch: local PLAIN, peer CURVE"
+        );
+    }
+
+    #[test]
+    fn plain_and_curve_refuse_two_servers() {
+        let server = SocketOptions::new()
+            .with_plain_server(true)
+            .with_curve_server(true);
+        let client = SocketOptions::new();
+        for mechanism in [SecurityMechanism::Plain, SecurityMechanism::Curve] {
+            let peer = peer_greeting(mechanism, &server);
+            let err = check_peer_mechanism(mechanism, true, &peer).unwrap_err();
+            assert!(
+                matches!(&err, ZmtpError::AsServerConflict { mechanism: name } if name == mechanism.name()),
+                "{err}"
+            );
+            assert!(check_peer_mechanism(mechanism, false, &peer).is_ok());
+
+            // A clear flag may be a libzmq server, so it is not refused here.
+            let peer = peer_greeting(mechanism, &client);
+            assert!(check_peer_mechanism(mechanism, true, &peer).is_ok());
+            assert!(check_peer_mechanism(mechanism, false, &peer).is_ok());
+        }
+
+        // NULL has no roles.
+        let peer = peer_greeting(SecurityMechanism::Null, &server);
+        assert!(check_peer_mechanism(SecurityMechanism::Null, false, &peer).is_ok());
+    }
+
+    #[test]
+    fn two_plain_servers_fail_fast_with_as_server_conflict() {
+        LocalRuntime::new().unwrap().block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let options = SocketOptions::new().with_plain_server(true);
+
+            let server_options = options.clone();
+            let accepted = monocoque_core::rt::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                perform_handshake_with_options(
+                    &mut stream,
+                    SocketType::Router,
+                    None,
+                    Some(TEST_TIMEOUT),
+                    &server_options,
+                )
+                .await
+            });
+
+            let started = std::time::Instant::now();
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let connected = perform_handshake_with_options(
+                &mut stream,
+                SocketType::Dealer,
+                None,
+                Some(TEST_TIMEOUT),
+                &options,
+            )
+            .await;
+            let accepted = monocoque_core::rt::join(accepted).await;
+
+            for result in [connected, accepted] {
+                let err = result.unwrap_err();
+                assert!(matches!(err, ZmtpError::AsServerConflict { .. }), "{err}");
+                assert!(handshake_error(err).get_ref().is_some());
+            }
+            assert!(started.elapsed() < TEST_TIMEOUT);
+        });
+    }
 }
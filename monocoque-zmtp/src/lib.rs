@@ -90,6 +90,7 @@ mod utils;
 
 // Public protocol types
 pub mod adapters;
+pub mod diagnostics;
 /// Sans-IO ZMTP session and related types.
 pub mod session;
 pub mod socket_trait;
@@ -100,6 +101,8 @@ pub mod security;
 
 // Socket implementations
 pub mod dealer;
+pub mod frame_reader;
+pub mod multi_dealer;
 pub mod pair;
 pub mod proxy;
 /// PUB socket implementation.
@@ -109,34 +112,46 @@ pub mod push;
 pub mod rep;
 pub mod req;
 pub mod router;
+pub mod router_hub;
+mod sequence;
 pub mod stream;
 pub mod subscriber;
+#[cfg(feature = "tls")]
+pub mod tls;
 pub mod xpub;
 pub mod xsub;
 
 // Re-export socket types for clean API
 pub use dealer::DealerSocket;
+pub use frame_reader::FrameReader;
+pub use multi_dealer::MultiDealerSocket;
 pub use pair::PairSocket;
-pub use publisher::PubSocket;
+pub use publisher::{PubSocket, PubStats};
 pub use pull::PullSocket;
 pub use push::PushSocket;
 pub use rep::RepSocket;
 pub use req::ReqSocket;
 pub use router::RouterSocket;
+pub use router_hub::{FlushOutcome, MessageMeta, PeerSnapshot, RouterHubSocket};
 pub use stream::StreamSocket;
 pub use subscriber::SubSocket;
 pub use xpub::XPubSocket;
 pub use xsub::XSubSocket;
 
 // Re-export commonly used types
+pub use base::CloseReason;
+pub use diagnostics::DiagnosticsSnapshot;
+pub use greeting::ProbeKind;
+pub use handshake::{HandshakeFailed, HandshakeResult, PeerMetadata, USER_ID_PROPERTY};
+pub use inproc_stream::InprocStream;
 pub use session::{SocketType, ZmtpSession};
 pub use socket_trait::Socket;
 
 // Wire-parser entry points reachable from the fuzz crate (monocoque-fuzz).
 // This is an internal implementation crate, so exposing the greeting and READY
 // command parsers here widens no public-facing (monocoque) API surface.
-pub use greeting::ZmtpGreeting;
-pub use handshake::parse_ready_command;
+pub use greeting::{ZmtpGreeting, ZmtpVersion};
+pub use handshake::{parse_ready_command, parse_ready_metadata};
 
 /// Prelude module for convenient imports
 ///
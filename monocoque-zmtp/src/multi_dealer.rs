@@ -0,0 +1,715 @@
+//! DEALER socket connected to several servers at once.
+//!
+//! A plain [`DealerSocket`](crate::dealer::DealerSocket) owns one connection.
+//! [`MultiDealerSocket`] connects to any number of ROUTER/REP servers and
+//! applies the DEALER routing rules across them: `send()` hands each message
+//! to the next live connection in round-robin order, and `recv()` fair-queues
+//! whatever the servers send back.
+//!
+//! ```text
+//!                  +--> per-connection writer --> [ROUTER a]
+//! send -round-robin+--> per-connection writer --> [ROUTER b]
+//!
+//! [ROUTER a] --> per-connection reader --\   +---------+
+//! [ROUTER b] --> per-connection reader ----->| inbound |--recv--> messages
+//!                                            +---------+
+//! ```
+//!
+//! Each connection is split after its handshake and gets a reader and a writer
+//! task, as in [`RouterHubSocket`](crate::router_hub::RouterHubSocket). Waiting
+//! on several connections by selecting over their `recv` futures would cancel
+//! in-flight reads on the losers; a dedicated reader per connection keeps every
+//! read running to completion, and the shared inbound channel yields messages
+//! in the order they arrived.
+//!
+//! ## Failover
+//!
+//! A server that is down at connect time, or whose connection drops later, is
+//! handed to a redial task of its own. The task waits out the
+//! `reconnect_ivl`/`reconnect_ivl_max` backoff between attempts, and the
+//! socket puts the connection back into the rotation on its next `send` or
+//! `recv` once the task gets through. Meanwhile the remaining servers carry
+//! the traffic: `send` only fails when none of them is up.
+//!
+//! A waiting `recv` notices a connection going down and starts its redial
+//! itself, so a socket that only receives reconnects too. A redial task gives
+//! up after `max_reconnect_attempts` failed attempts; once every connection
+//! is down and every redial has given up, `recv` returns `Ok(None)`. With the
+//! default of unlimited attempts, `recv` keeps waiting for a server to come
+//! back.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use bytes::Bytes;
+//! use monocoque_core::options::SocketOptions;
+//! use monocoque_zmtp::multi_dealer::MultiDealerSocket;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let mut dealer = MultiDealerSocket::connect_with_options(
+//!     ["127.0.0.1:5555".parse().unwrap(), "127.0.0.1:5556".parse().unwrap()],
+//!     SocketOptions::default(),
+//! )
+//! .await?;
+//! dealer.add_connection("127.0.0.1:5557").await?;
+//!
+//! dealer.send(vec![Bytes::from("job")]).await?;
+//! if let Some(reply) = dealer.recv().await? {
+//!     println!("{reply:?}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::{Bytes, BytesMut};
+use flume::{Receiver, Sender, WeakSender};
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::reconnect::ReconnectState;
+use monocoque_core::rt::{JoinHandle, OwnedReadHalf, OwnedWriteHalf, TcpStream};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::handshake::{handshake_error, perform_handshake_with_options};
+use crate::router_hub::{PeerCipher, encode_curve_wire, hwm_channel};
+use crate::security::curve::CurveMessageCipher;
+use crate::session::SocketType;
+
+/// One server connection, as seen by the socket.
+struct Connection {
+    /// Address the connection was established to.
+    endpoint: Endpoint,
+    /// Outbound queue drained by the connection's writer.
+    tx: Sender<Vec<Bytes>>,
+    /// The writer task; joined on close so queued messages are flushed.
+    writer: JoinHandle<()>,
+}
+
+impl Connection {
+    /// The writer drops its receiver when either half of the connection
+    /// stops, so a disconnected queue means the connection is gone.
+    fn is_connected(&self) -> bool {
+        !self.tx.is_disconnected()
+    }
+}
+
+/// A server connection past its handshake, before its reader and writer
+/// are started.
+struct Dialed {
+    stream: TcpStream,
+    peer_addr: SocketAddr,
+    cipher: Option<PeerCipher>,
+}
+
+/// DEALER socket load-balancing across several server connections.
+///
+/// Create one with [`connect`](Self::connect) or
+/// [`connect_with_options`](Self::connect_with_options) and grow it with
+/// [`add_connection`](Self::add_connection). Dropping the socket closes every
+/// connection and stops every redial; [`close`](Self::close) also flushes
+/// queued messages per the `linger` option.
+pub struct MultiDealerSocket {
+    connections: Vec<Connection>,
+    /// Index of the connection that receives the next message.
+    next: usize,
+    /// Merged inbound messages from every connection reader.
+    inbound_rx: Receiver<Vec<Bytes>>,
+    /// Weak so the channel disconnects once every reader has exited.
+    inbound_tx: WeakSender<Vec<Bytes>>,
+    /// Messages carried over from a drained inbound channel.
+    buf: VecDeque<Vec<Bytes>>,
+    /// Connections re-established by redial tasks, waiting to be started.
+    revived_rx: Receiver<Dialed>,
+    /// Weak so the channel disconnects once no redial task is running.
+    revived_tx: WeakSender<Dialed>,
+    /// Signalled by each connection's writer as it exits.
+    closed_rx: Receiver<()>,
+    closed_tx: Sender<()>,
+    options: SocketOptions,
+}
+
+impl MultiDealerSocket {
+    /// Connect to every address in `addrs` with default options.
+    pub async fn connect(addrs: impl IntoIterator<Item = SocketAddr>) -> io::Result<Self> {
+        Self::connect_with_options(addrs, SocketOptions::default()).await
+    }
+
+    /// Connect to every address in `addrs`, applying `options` to each
+    /// connection.
+    ///
+    /// `send_hwm` bounds each connection's outbound queue and `recv_hwm` the
+    /// merged inbound queue; zero means unbounded. Servers that cannot be
+    /// reached are redialed in the background.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` when `addrs` is empty or the routing id is
+    /// invalid, and the first connect or handshake error when no server
+    /// could be reached.
+    pub async fn connect_with_options(
+        addrs: impl IntoIterator<Item = SocketAddr>,
+        options: SocketOptions,
+    ) -> io::Result<Self> {
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+        let (inbound_tx, inbound_rx) = hwm_channel(options.recv_hwm);
+        let (revived_tx, revived_rx) = flume::unbounded();
+        let (closed_tx, closed_rx) = flume::unbounded();
+        let mut socket = Self {
+            connections: Vec::new(),
+            next: 0,
+            inbound_rx,
+            inbound_tx: inbound_tx.downgrade(),
+            buf: VecDeque::new(),
+            revived_rx,
+            revived_tx: revived_tx.downgrade(),
+            closed_rx,
+            closed_tx,
+            options,
+        };
+        let mut first_err = None;
+        for addr in addrs {
+            match dial(addr, &socket.options).await {
+                Ok(dialed) => socket.start(dialed),
+                Err(e) => {
+                    warn!("[DEALER] Cannot reach {addr}, redialing in the background: {e}");
+                    first_err.get_or_insert(e);
+                    socket.redial(addr);
+                }
+            }
+        }
+        drop((inbound_tx, revived_tx));
+        if socket.connections.is_empty() {
+            return Err(first_err.unwrap_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "DEALER connect: no endpoints given",
+                )
+            }));
+        }
+        Ok(socket)
+    }
+
+    /// Connect to one more server and add it to the round-robin rotation.
+    ///
+    /// Returns the endpoint of the new connection.
+    pub async fn add_connection(
+        &mut self,
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+    ) -> io::Result<Endpoint> {
+        let dialed = dial(addr, &self.options).await?;
+        self.start(dialed);
+        Ok(self.connections[self.connections.len() - 1]
+            .endpoint
+            .clone())
+    }
+
+    /// Queue `msg` on the next live connection in round-robin order.
+    ///
+    /// Connections known to be down are dropped from the rotation on the way
+    /// and redialed, and a message refused by a connection that went down is
+    /// handed to the next one, so it is only lost if it was already written.
+    /// Waits while the chosen connection's queue is at `send_hwm`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotConnected` when no live connection remains.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        self.start_revived();
+        let mut msg = msg;
+        while !self.connections.is_empty() {
+            let idx = self.next % self.connections.len();
+            if !self.connections[idx].is_connected() {
+                self.drop_connection(idx);
+                continue;
+            }
+            match self.connections[idx].tx.send_async(msg).await {
+                Ok(()) => {
+                    self.next = idx + 1;
+                    return Ok(());
+                }
+                Err(flume::SendError(refused)) => {
+                    msg = refused;
+                    self.drop_connection(idx);
+                }
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotConnected,
+            "DEALER has no live connections",
+        ))
+    }
+
+    /// Receive the next message from any connection.
+    ///
+    /// Connections that go down while this waits are dropped from the
+    /// rotation and redialed, and revived ones are started, so a socket that
+    /// never sends still fails over.
+    ///
+    /// Returns `Ok(None)` once every connection has closed, every redial has
+    /// given up after `max_reconnect_attempts`, and everything the
+    /// connections delivered has been received.
+    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        use futures::future::pending;
+        use futures::{FutureExt, select_biased};
+
+        loop {
+            self.start_revived();
+            self.drop_closed();
+            if let Some(msg) = self.buf.pop_front() {
+                return Ok(Some(msg));
+            }
+            let readers_gone = self.inbound_rx.is_disconnected() && self.inbound_rx.is_empty();
+            let redialing = !(self.revived_rx.is_disconnected() && self.revived_rx.is_empty());
+            if readers_gone && !redialing && self.connections.is_empty() {
+                return Ok(None);
+            }
+            // A channel that is already disconnected would wake the loop at
+            // once, so it is left out of the wait; one that disconnects during
+            // the wait wakes it to re-check the state above.
+            let (inbound, revived) = (&self.inbound_rx, &self.revived_rx);
+            let dialed = select_biased! {
+                msg = async {
+                    if readers_gone { pending().await } else { inbound.recv_async().await }
+                }.fuse() => match msg {
+                    Ok(msg) => return Ok(Some(msg)),
+                    Err(_) => continue,
+                },
+                _ = self.closed_rx.recv_async().fuse() => continue,
+                dialed = async {
+                    if redialing { revived.recv_async().await } else { pending().await }
+                }.fuse() => dialed,
+            };
+            if let Ok(dialed) = dialed {
+                debug!("[DEALER] Reconnected to {}", dialed.peer_addr);
+                self.start(dialed);
+            }
+        }
+    }
+
+    /// Number of connections still in the rotation.
+    ///
+    /// A connection that went down is counted until a `send` or `recv`
+    /// notices; one being redialed is not counted until it is back.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    /// True when no connection remains.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.connections.is_empty()
+    }
+
+    /// Endpoints of the connections that are currently up.
+    pub fn connected_endpoints(&self) -> Vec<Endpoint> {
+        self.connections
+            .iter()
+            .filter(|c| c.is_connected())
+            .map(|c| c.endpoint.clone())
+            .collect()
+    }
+
+    /// Get a reference to the socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        &self.options
+    }
+
+    /// Check if at least one connection is up.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connections.iter().any(Connection::is_connected)
+    }
+
+    /// Always `false`: `send` only queues the message for a connection's
+    /// writer, so a cancelled call cannot leave a frame half-written.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        false
+    }
+
+    /// The endpoint of the connection started most recently, if any.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub fn last_endpoint(&self) -> Option<&Endpoint> {
+        self.connections.last().map(|c| &c.endpoint)
+    }
+
+    /// Always `false`: `recv` hands out whole messages.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub const fn has_more(&self) -> bool {
+        false
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// `1` (POLLIN) when a received message is waiting, `2` (POLLOUT) when a
+    /// live connection has room in its send queue.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    pub fn events(&self) -> u32 {
+        let mut events = 0;
+        if !self.buf.is_empty() || !self.inbound_rx.is_empty() {
+            events |= 1;
+        }
+        if self
+            .connections
+            .iter()
+            .any(|c| c.is_connected() && !c.tx.is_full())
+        {
+            events |= 2;
+        }
+        events
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// Connections that are already up keep the options they started with;
+    /// the new values apply to connections added or revived afterwards, and
+    /// `linger` to [`close`](Self::close).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.options.update(f)
+    }
+
+    /// Close every connection, flushing queued messages within `linger`.
+    pub async fn close(self) -> io::Result<()> {
+        // Dropping the queues lets each writer finish what is queued and exit.
+        let writers: Vec<JoinHandle<()>> = self
+            .connections
+            .into_iter()
+            .map(|connection| connection.writer)
+            .collect();
+        let flush = async {
+            for writer in writers {
+                monocoque_core::rt::join(writer).await;
+            }
+        };
+        match self.options.linger {
+            Some(dur) if dur.is_zero() => {}
+            Some(dur) => {
+                if monocoque_core::rt::timeout(dur, flush).await.is_err() {
+                    warn!("[DEALER] Linger expired with messages unsent");
+                }
+            }
+            None => flush.await,
+        }
+        Ok(())
+    }
+
+    /// Drop every connection whose writer has exited, redialing each.
+    fn drop_closed(&mut self) {
+        // The scan below covers every signal received so far.
+        self.closed_rx.drain().for_each(drop);
+        let mut idx = 0;
+        while idx < self.connections.len() {
+            if self.connections[idx].is_connected() {
+                idx += 1;
+            } else {
+                self.drop_connection(idx);
+            }
+        }
+    }
+
+    fn drop_connection(&mut self, idx: usize) {
+        let connection = self.connections.remove(idx);
+        debug!("[DEALER] Dropping connection to {}", connection.endpoint);
+        if let Endpoint::Tcp(addr) = connection.endpoint {
+            self.redial(addr);
+        }
+        // `idx` now names whatever shifted into the slot, so start there.
+        self.next = idx;
+    }
+
+    /// Start the reader and writer of a dialed connection and add it to the
+    /// rotation.
+    fn start(&mut self, dialed: Dialed) {
+        let inbound = if let Some(tx) = self.inbound_tx.upgrade() {
+            tx
+        } else {
+            // Every earlier reader exited and closed the channel. Keep what
+            // they queued and start a fresh channel for the new connection.
+            let (tx, rx) = hwm_channel(self.options.recv_hwm);
+            self.buf.extend(self.inbound_rx.drain());
+            self.inbound_rx = rx;
+            self.inbound_tx = tx.downgrade();
+            tx
+        };
+        self.connections.push(start_connection(
+            dialed,
+            &self.options,
+            inbound,
+            self.closed_tx.clone(),
+        ));
+    }
+
+    /// Start every connection the redial tasks have re-established so far.
+    fn start_revived(&mut self) {
+        while let Ok(dialed) = self.revived_rx.try_recv() {
+            debug!("[DEALER] Reconnected to {}", dialed.peer_addr);
+            self.start(dialed);
+        }
+    }
+
+    /// Redial `addr` in the background until it answers, the attempts run
+    /// out, or the socket is dropped.
+    fn redial(&mut self, addr: SocketAddr) {
+        let revived = if let Some(tx) = self.revived_tx.upgrade() {
+            tx
+        } else {
+            // Every earlier redial finished. Start what they delivered and
+            // open a fresh channel for this one.
+            self.start_revived();
+            let (tx, rx) = flume::unbounded();
+            self.revived_rx = rx;
+            self.revived_tx = tx.downgrade();
+            tx
+        };
+        monocoque_core::rt::spawn_detached(redial(addr, self.options.clone(), revived));
+    }
+}
+
+/// Connect and handshake one server.
+async fn dial(
+    addr: impl monocoque_core::rt::ToSocketAddrs,
+    options: &SocketOptions,
+) -> io::Result<Dialed> {
+    let mut stream = TcpStream::connect(addr).await?;
+    let peer_addr = stream.peer_addr()?;
+    crate::utils::configure_tcp_stream(&stream, options, "DEALER")?;
+    let handshake = perform_handshake_with_options(
+        &mut stream,
+        SocketType::Dealer,
+        options.routing_id.as_deref(),
+        options.handshake_deadline(),
+        options,
+    )
+    .await
+    .map_err(handshake_error)?;
+    debug!(
+        peer_socket_type = ?handshake.peer_socket_type,
+        "[DEALER] Connected to {}",
+        peer_addr
+    );
+    Ok(Dialed {
+        stream,
+        peer_addr,
+        cipher: handshake.curve_cipher.map(|c| Arc::new(Mutex::new(c))),
+    })
+}
+
+/// Dial `addr` with backoff until it answers, then hand the connection to
+/// the socket. Gives up once the socket is dropped or after
+/// `max_reconnect_attempts` failed attempts.
+async fn redial(addr: SocketAddr, options: SocketOptions, revived: Sender<Dialed>) {
+    let mut backoff = ReconnectState::new(&options);
+    loop {
+        if let Some(limit) = options.max_reconnect_attempts
+            && backoff.attempt() >= limit
+        {
+            warn!("[DEALER] Giving up on {addr} after {limit} reconnection attempts");
+            return;
+        }
+        monocoque_core::rt::sleep(backoff.next_delay()).await;
+        if revived.is_disconnected() {
+            return;
+        }
+        match dial(addr, &options).await {
+            Ok(dialed) => {
+                let _ = revived.send(dialed);
+                return;
+            }
+            Err(e) => debug!(
+                attempt = backoff.attempt(),
+                "[DEALER] Redial of {addr} failed: {e}"
+            ),
+        }
+    }
+}
+
+/// Start the reader and writer of one server connection.
+fn start_connection(
+    dialed: Dialed,
+    options: &SocketOptions,
+    inbound: Sender<Vec<Bytes>>,
+    closed: Sender<()>,
+) -> Connection {
+    let Dialed {
+        stream,
+        peer_addr,
+        cipher,
+    } = dialed;
+    let (read_half, write_half) = stream.into_split();
+    let (tx, rx) = hwm_channel(options.send_hwm);
+    let (reader_alive, reader_gone) = flume::bounded::<()>(1);
+    let (writer_alive, writer_gone) = flume::bounded::<()>(1);
+
+    monocoque_core::rt::spawn_detached(connection_reader(
+        read_half,
+        cipher.clone(),
+        options.clone(),
+        inbound,
+        writer_gone,
+        reader_alive,
+    ));
+    let writer = monocoque_core::rt::spawn(async move {
+        connection_writer(write_half, rx, cipher, reader_gone, writer_alive).await;
+        // The queue is dropped by now, so the socket sees the connection down.
+        let _ = closed.send(());
+    });
+    Connection {
+        endpoint: Endpoint::Tcp(peer_addr),
+        tx,
+        writer,
+    }
+}
+
+/// Decode messages from one server and forward them to the socket.
+///
+/// Stops on EOF, a read or decode error, when the socket is dropped, or when
+/// the connection's writer exits. Exiting drops `_alive`, which stops the
+/// writer.
+async fn connection_reader(
+    mut reader: OwnedReadHalf,
+    cipher: Option<PeerCipher>,
+    options: SocketOptions,
+    inbound: Sender<Vec<Bytes>>,
+    writer_gone: Receiver<()>,
+    _alive: Sender<()>,
+) {
+    use compio_buf::BufResult;
+    use compio_io::AsyncRead;
+    use futures::{FutureExt, select_biased};
+    use monocoque_core::buffer::SegmentedBuffer;
+    use monocoque_core::io::take_read_buffer;
+
+    let mut recv_buf = SegmentedBuffer::new();
+    let mut decoder = crate::codec::ZmtpDecoder::with_size_limits(&options);
+    let mut frames = Vec::new();
+    let mut read_buf = BytesMut::new();
+
+    'read: loop {
+        // SAFETY: `buf` is passed straight to `read`; the data path truncates
+        // it to `n` before freezing, and EOF/error drop it without reading it.
+        let buf = unsafe { take_read_buffer(&mut read_buf, options.read_buffer_size) };
+        let BufResult(result, mut buf) = select_biased! {
+            _ = writer_gone.recv_async().fuse() => break,
+            res = reader.read(buf).fuse() => res,
+        };
+        match result {
+            Ok(0) => {
+                debug!("[DEALER] Server disconnected");
+                break;
+            }
+            Ok(n) => {
+                buf.truncate(n);
+                recv_buf.push(buf.freeze());
+            }
+            Err(e) => {
+                debug!("[DEALER] Read error: {}", e);
+                break;
+            }
+        }
+
+        loop {
+            let frame = match decoder.decode(&mut recv_buf) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(e) => {
+                    debug!("[DEALER] Decode error: {}", e);
+                    break 'read;
+                }
+            };
+            let (more, payload) = if frame.is_command() {
+                match &cipher {
+                    Some(cipher) if CurveMessageCipher::is_curve_message(&frame.payload) => {
+                        let decrypted = cipher.lock().decrypt_frame(&frame.payload);
+                        let Ok(decrypted) = decrypted else {
+                            break 'read;
+                        };
+                        decrypted
+                    }
+                    _ => continue,
+                }
+            } else if cipher.is_some() {
+                // Reject plaintext data frames when CURVE is active.
+                break 'read;
+            } else {
+                (frame.more(), frame.payload)
+            };
+
+            frames.push(payload);
+            if !more
+                && inbound
+                    .send_async(std::mem::take(&mut frames))
+                    .await
+                    .is_err()
+            {
+                break 'read;
+            }
+        }
+    }
+}
+
+/// Write queued messages to one server until the socket drops the queue, the
+/// reader exits, or a write fails. Exiting drops `_alive`, which stops the
+/// reader.
+async fn connection_writer(
+    mut writer: OwnedWriteHalf,
+    outbound: Receiver<Vec<Bytes>>,
+    cipher: Option<PeerCipher>,
+    reader_gone: Receiver<()>,
+    _alive: Sender<()>,
+) {
+    use compio_buf::BufResult;
+    use compio_io::{AsyncWrite, AsyncWriteExt};
+    use futures::{FutureExt, select_biased};
+
+    loop {
+        let msg = select_biased! {
+            msg = outbound.recv_async().fuse() => msg,
+            _ = reader_gone.recv_async().fuse() => return,
+        };
+        // The socket dropped the queue after everything in it was written.
+        let Ok(msg) = msg else { break };
+
+        let wire = if let Some(cipher) = &cipher {
+            let Some(wire) = encode_curve_wire(&msg, cipher) else {
+                return;
+            };
+            wire
+        } else {
+            let mut wire = BytesMut::new();
+            crate::codec::encode_multipart(&msg, &mut wire);
+            wire.freeze()
+        };
+        let BufResult(res, _) = writer.write_all(wire).await;
+        if res.is_err() {
+            return;
+        }
+    }
+    let _ = writer.shutdown().await;
+}
@@ -18,11 +18,14 @@
 
 use crate::base::SocketBase;
 use crate::inproc_stream::InprocStream;
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
 use monocoque_core::endpoint::Endpoint;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use smallvec::SmallVec;
 use std::io;
@@ -61,11 +64,11 @@ where
             &mut stream,
             SocketType::Pair,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -77,6 +80,8 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Pair, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
@@ -90,12 +95,11 @@ where
     /// Returns an error if the socket is poisoned, disconnected, or if the write fails.
     pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
         trace!("[PAIR] Sending {} frames", msg.len());
+        self.base.recover_if_poisoned(SocketType::Pair).await;
 
-        // Encode message into write_buf (with CURVE encryption if active)
-        self.base.encode_message_to_write_buf(&msg)?;
-
-        // Delegate to base for writing
-        self.base.write_from_buf().await?;
+        // Encode and write (or hold for the coalescing window), with CURVE
+        // encryption if active
+        self.base.send_message(&msg).await?;
 
         trace!("[PAIR] Message sent successfully");
         Ok(())
@@ -107,6 +111,7 @@ where
     /// connection was closed, or an error.
     pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
         trace!("[PAIR] Waiting for message");
+        self.base.recover_if_poisoned(SocketType::Pair).await;
 
         // Read from stream until we have a complete message
         loop {
@@ -156,16 +161,45 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
     }
 
-    /// Set socket options (builder-style).
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 
     /// Get the socket type.
@@ -282,11 +316,11 @@ impl PairSocket<TcpStream> {
             &mut stream,
             SocketType::Pair,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -297,8 +331,10 @@ impl PairSocket<TcpStream> {
 
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
         let mut base =
-            crate::base::SocketBase::with_endpoint(stream, SocketType::Pair, endpoint, options);
+            crate::base::SocketBase::with_tcp_endpoint(stream, SocketType::Pair, endpoint, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
@@ -317,18 +353,43 @@ impl PairSocket<TcpStream> {
     ) -> io::Result<Self> {
         // Configure TCP optimizations including keepalive
         crate::utils::configure_tcp_stream(&stream, &options, "PAIR")?;
-        Self::with_options(stream, options).await
+        let mut socket = Self::with_options(stream, options).await?;
+        socket.base.remember_peer_for_recovery();
+        Ok(socket)
     }
 
-    /// Check if the socket is currently connected.
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
     #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.base.is_connected()
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
     }
 
     /// Try to reconnect to the stored endpoint.
+    ///
+    /// Frames of a message cut off by the lost connection are discarded, so
+    /// they cannot prefix the first message on the new one.
     pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.base.try_reconnect(SocketType::Pair).await
+        self.base.try_reconnect(SocketType::Pair).await?;
+        self.frames.clear();
+        Ok(())
     }
 
     /// Receive a message with automatic reconnection on EOF or network error.
@@ -398,6 +459,7 @@ impl PairSocket<TcpStream> {
 
         loop {
             if self.base.stream.is_none() {
+                self.base.check_redial_backoff()?;
                 if let Some(limit) = max
                     && attempts >= limit
                 {
@@ -411,7 +473,9 @@ impl PairSocket<TcpStream> {
                     "[PAIR] Stream disconnected, reconnecting (attempt {})",
                     attempts
                 );
-                self.try_reconnect().await?;
+                self.try_reconnect()
+                    .await
+                    .map_err(|e| self.base.redial_error(e))?;
             }
 
             match self.send(msg.clone()).await {
@@ -455,8 +519,9 @@ impl PairSocket<InprocStream> {
     pub fn bind_inproc_with_options(endpoint: &str, options: SocketOptions) -> io::Result<Self> {
         debug!("[PAIR] Binding to inproc endpoint: {}", endpoint);
 
-        // Bind to inproc endpoint
-        let (tx, rx) = monocoque_core::inproc::bind_inproc(endpoint)?;
+        // bind_inproc_bidi also registers the reply channel that
+        // connect_inproc_with_options picks up, so the peer can answer.
+        let (tx, rx) = monocoque_core::inproc::bind_inproc_bidi(endpoint)?;
         let stream = InprocStream::new(tx, rx);
 
         // Parse endpoint for storage
@@ -17,10 +17,17 @@
 //! Clients    → ROUTER (frontend) → DEALER (backend) → Workers
 //! ```
 //!
+//! # Rate Limiting
+//!
+//! [`ProxyOptions`] can cap each direction with a token-bucket [`RateLimit`].
+//! A throttled proxy delays forwarding rather than dropping messages, and
+//! [`ProxyOptions::stats`] reports how much it forwarded and how long it
+//! waited.
+//!
 //! # Example: PUB-SUB Broker
 //!
 //! ```rust,ignore
-//! use monocoque_zmtp::proxy::{proxy, ProxySocket};
+//! use monocoque_zmtp::proxy::{proxy, ProxyOptions, ProxySocket};
 //! use monocoque_zmtp::xsub::XSubSocket;
 //! use monocoque_zmtp::xpub::XPubSocket;
 //!
@@ -33,7 +40,7 @@
 //!     let mut backend = XPubSocket::bind("127.0.0.1:5556").await?;
 //!
 //!     // Forward messages and subscriptions bidirectionally
-//!     proxy(&mut frontend, &mut backend, None).await?;
+//!     proxy(&mut frontend, &mut backend, None, ProxyOptions::default()).await?;
 //!     Ok(())
 //! }
 //! ```
@@ -41,7 +48,7 @@
 //! # Example: REQ-REP Load Balancer
 //!
 //! ```rust,ignore
-//! use monocoque_zmtp::proxy::{proxy, ProxySocket};
+//! use monocoque_zmtp::proxy::{proxy, ProxyOptions, ProxySocket};
 //! use monocoque_zmtp::router::RouterSocket;
 //! use monocoque_zmtp::dealer::DealerSocket;
 //!
@@ -54,13 +61,16 @@
 //!     let mut backend = DealerSocket::bind("127.0.0.1:5556").await?;
 //!
 //!     // Load balance requests across workers
-//!     proxy(&mut frontend, &mut backend, None).await?;
+//!     proxy(&mut frontend, &mut backend, None, ProxyOptions::default()).await?;
 //!     Ok(())
 //! }
 //! ```
 
 use bytes::Bytes;
 use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use tracing::debug;
 
 // Import socket types
@@ -90,6 +100,211 @@ fn is_transient_send_error(err: &io::Error) -> bool {
     )
 }
 
+/// Token-bucket limit on how many messages a proxy forwards in one direction.
+///
+/// The bucket holds up to `burst` tokens and refills at `messages_per_second`.
+/// Each forwarded message takes a token; when none is left the proxy waits
+/// for the next one instead of dropping the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Sustained forwarding rate. Must be non-zero.
+    pub messages_per_second: u64,
+    /// Messages that may pass back to back after an idle period. Values below
+    /// 1 are treated as 1.
+    pub burst: u64,
+}
+
+impl RateLimit {
+    /// Create a rate limit of `messages_per_second` with room for `burst`.
+    pub const fn new(messages_per_second: u64, burst: u64) -> Self {
+        Self {
+            messages_per_second,
+            burst,
+        }
+    }
+}
+
+/// Options for [`proxy`] and [`proxy_steerable`].
+///
+/// Clones share the same counters, so keep a clone before handing the options
+/// to the proxy to read [`stats`](Self::stats) while it runs.
+///
+/// # Example
+///
+/// ```rust
+/// use monocoque_zmtp::proxy::{ProxyOptions, RateLimit};
+///
+/// let options = ProxyOptions::new().with_frontend_rate_limit(RateLimit::new(1000, 100));
+/// let handle = options.clone();
+/// // proxy(&mut frontend, &mut backend, None, options).await?;
+/// assert_eq!(handle.stats().frontend_forwarded, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProxyOptions {
+    /// Limit on messages forwarded from the frontend to the backend.
+    pub frontend_rate_limit: Option<RateLimit>,
+    /// Limit on messages forwarded from the backend to the frontend.
+    pub backend_rate_limit: Option<RateLimit>,
+    /// Most messages forwarded in one send. After each receive the proxy
+    /// drains up to this many messages the socket already holds (see
+    /// [`ProxySocket::try_recv_multipart`]) and hands them to the other side
+    /// with one [`ProxySocket::send_multipart_batch`]. Defaults to 1, one
+    /// send per message; zero is treated as 1.
+    pub batch_size: usize,
+    counters: Arc<ProxyCounters>,
+}
+
+impl Default for ProxyOptions {
+    fn default() -> Self {
+        Self {
+            frontend_rate_limit: None,
+            backend_rate_limit: None,
+            batch_size: 1,
+            counters: Arc::default(),
+        }
+    }
+}
+
+impl ProxyOptions {
+    /// Options with no rate limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forward up to `batch_size` already-received messages per send.
+    #[must_use]
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Limit messages forwarded from the frontend to the backend.
+    #[must_use]
+    pub const fn with_frontend_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.frontend_rate_limit = Some(limit);
+        self
+    }
+
+    /// Limit messages forwarded from the backend to the frontend.
+    #[must_use]
+    pub const fn with_backend_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.backend_rate_limit = Some(limit);
+        self
+    }
+
+    /// Forwarding counters of the proxy running with these options.
+    pub fn stats(&self) -> ProxyStats {
+        ProxyStats {
+            frontend_forwarded: self.counters.frontend_forwarded.load(Ordering::Relaxed),
+            backend_forwarded: self.counters.backend_forwarded.load(Ordering::Relaxed),
+            rate_limited_ms: self.counters.rate_limited_ns.load(Ordering::Relaxed) / 1_000_000,
+        }
+    }
+
+    /// Validate the limits and build the frontend and backend token buckets.
+    fn buckets(&self) -> io::Result<(Option<TokenBucket>, Option<TokenBucket>)> {
+        let now = Instant::now();
+        let bucket = |limit: Option<RateLimit>| match limit {
+            Some(limit) if limit.messages_per_second == 0 => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "RateLimit::messages_per_second must be non-zero",
+            )),
+            limit => Ok(limit.map(|limit| TokenBucket::new(limit, now))),
+        };
+        Ok((
+            bucket(self.frontend_rate_limit)?,
+            bucket(self.backend_rate_limit)?,
+        ))
+    }
+}
+
+/// Forwarding counters of a proxy.
+///
+/// Returned by [`ProxyOptions::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProxyStats {
+    /// Messages forwarded from the frontend to the backend.
+    pub frontend_forwarded: u64,
+    /// Messages forwarded from the backend to the frontend.
+    pub backend_forwarded: u64,
+    /// Time spent waiting on rate limits, in both directions.
+    pub rate_limited_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct ProxyCounters {
+    frontend_forwarded: AtomicU64,
+    backend_forwarded: AtomicU64,
+    rate_limited_ns: AtomicU64,
+}
+
+fn count_forwarded(counter: &AtomicU64, messages: usize) {
+    counter.fetch_add(messages as u64, Ordering::Relaxed);
+}
+
+/// Token bucket enforcing a [`RateLimit`].
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        let burst = limit.burst.max(1) as f64;
+        Self {
+            rate: limit.messages_per_second as f64,
+            burst,
+            tokens: burst,
+            refilled_at: now,
+        }
+    }
+
+    /// Take a token, returning how long to wait until it is actually
+    /// available. The token is reserved either way, so waits never overlap.
+    fn acquire(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = elapsed
+            .as_secs_f64()
+            .mul_add(self.rate, self.tokens)
+            .min(self.burst);
+        self.refilled_at = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+/// Wait for a token from `bucket`, if the direction is rate limited.
+async fn throttle(bucket: &mut Option<TokenBucket>, counters: &ProxyCounters) {
+    let Some(bucket) = bucket else {
+        return;
+    };
+    let wait = bucket.acquire(Instant::now());
+    if !wait.is_zero() {
+        debug!("Proxy: rate limited, waiting {:?}", wait);
+        counters
+            .rate_limited_ns
+            .fetch_add(wait.as_nanos() as u64, Ordering::Relaxed);
+        monocoque_core::rt::sleep(wait).await;
+    }
+}
+
+/// What [`ProxySocket::recv_multipart_timeout`] received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecvOutcome {
+    /// A whole multipart message.
+    Message(Vec<Bytes>),
+    /// The connection closed.
+    Closed,
+    /// Nothing completed within the duration.
+    TimedOut,
+}
+
 /// Socket types that can participate in a proxy.
 ///
 /// Sockets must implement multipart message send/receive operations
@@ -104,6 +319,26 @@ pub trait ProxySocket {
     /// Returns `None` if no message is available or connection closed.
     async fn recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>>;
 
+    /// Receive a multipart message, giving up after `dur`.
+    ///
+    /// Tells a quiet socket ([`RecvOutcome::TimedOut`]) apart from a closed
+    /// one ([`RecvOutcome::Closed`]), so a proxy loop can wake up on a timer,
+    /// for instance to report liveness. The sockets keep the frames of a
+    /// message that was only partly received when the timer fired, and the
+    /// next receive resumes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the underlying receive.
+    async fn recv_multipart_timeout(&mut self, dur: Duration) -> io::Result<RecvOutcome> {
+        match monocoque_core::rt::timeout(dur, self.recv_multipart()).await {
+            Ok(Ok(Some(msg))) => Ok(RecvOutcome::Message(msg)),
+            Ok(Ok(None)) => Ok(RecvOutcome::Closed),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Ok(RecvOutcome::TimedOut),
+        }
+    }
+
     /// Send a multipart message to the socket.
     ///
     /// # Errors
@@ -113,6 +348,105 @@ pub trait ProxySocket {
 
     /// Get a description of the socket for logging.
     fn socket_desc(&self) -> &'static str;
+
+    /// Take a message the socket has already received, without waiting.
+    ///
+    /// The proxy calls this after each [`recv_multipart`](Self::recv_multipart)
+    /// to fill a batch. The default returns `None`, so sockets that cannot
+    /// tell whether a message is ready are forwarded one at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decoding the buffered data fails.
+    fn try_recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    /// Send several multipart messages, in order.
+    ///
+    /// The default sends them one by one; sockets with a send buffer override
+    /// it to encode the whole batch and flush once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the send operation fails. Messages before the
+    /// failing one may already have been sent.
+    async fn send_multipart_batch(&mut self, msgs: Vec<Vec<Bytes>>) -> io::Result<()> {
+        for msg in msgs {
+            self.send_multipart(msg).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Top `batch` up to `batch_size` with messages `from` already holds.
+fn fill_batch<S: ProxySocket + ?Sized>(
+    from: &mut S,
+    batch: &mut Vec<Vec<Bytes>>,
+    batch_size: usize,
+) -> io::Result<()> {
+    while batch.len() < batch_size {
+        let Some(msg) = from.try_recv_multipart()? else {
+            break;
+        };
+        batch.push(msg);
+    }
+    Ok(())
+}
+
+/// Forward one batch received from `from_desc` to `to`.
+///
+/// Each message takes its rate-limit token and is copied to `capture` in
+/// order before the batch goes out in one send. A transient send error drops
+/// the batch but keeps the proxy up; a fatal one is returned.
+async fn forward<T: ProxySocket + ?Sized>(
+    batch: Vec<Vec<Bytes>>,
+    from_desc: &'static str,
+    to: &mut T,
+    capture: &mut Option<&mut dyn ProxySocket>,
+    bucket: &mut Option<TokenBucket>,
+    counters: &ProxyCounters,
+    forwarded: &AtomicU64,
+) -> io::Result<()> {
+    for msg in &batch {
+        debug!(
+            "Proxy: {} → {}: {} frames",
+            from_desc,
+            to.socket_desc(),
+            msg.len()
+        );
+        throttle(bucket, counters).await;
+
+        // Send copy to capture if present
+        if let Some(cap) = capture
+            && let Err(e) = cap.send_multipart(msg.clone()).await
+        {
+            debug!("Capture socket send failed: {}", e);
+        }
+    }
+
+    let messages = batch.len();
+    let result = if messages == 1 {
+        let Some(msg) = batch.into_iter().next() else {
+            return Ok(());
+        };
+        to.send_multipart(msg).await
+    } else {
+        to.send_multipart_batch(batch).await
+    };
+    match result {
+        Ok(()) => count_forwarded(forwarded, messages),
+        Err(e) if is_transient_send_error(&e) => {
+            debug!(
+                "Proxy: transient send to {}, dropping {} message(s): {}",
+                to.socket_desc(),
+                messages,
+                e
+            );
+        }
+        Err(e) => return Err(e),
+    }
+    Ok(())
 }
 
 /// Run a bidirectional message proxy between frontend and backend sockets.
@@ -127,7 +461,10 @@ pub trait ProxySocket {
 ///
 /// - `frontend`: Socket facing clients/publishers
 /// - `backend`: Socket facing workers/subscribers
-/// - `capture`: Optional socket to receive message copies
+/// - `capture`: Optional socket to receive message copies. Taken as a trait
+///   object so `None` needs no type annotation.
+/// - `options`: Per-direction rate limits and the forwarding counters; see
+///   [`ProxyOptions`].
 ///
 /// # Patterns
 ///
@@ -146,7 +483,7 @@ pub trait ProxySocket {
 /// # Example
 ///
 /// ```rust,ignore
-/// use monocoque_zmtp::proxy::{proxy, ProxySocket};
+/// use monocoque_zmtp::proxy::{proxy, ProxyOptions, ProxySocket};
 /// use monocoque_zmtp::xsub::XSubSocket;
 /// use monocoque_zmtp::xpub::XPubSocket;
 ///
@@ -155,83 +492,68 @@ pub trait ProxySocket {
 ///     let mut frontend = XSubSocket::bind("127.0.0.1:5555").await?;
 ///     let mut backend = XPubSocket::bind("127.0.0.1:5556").await?;
 ///
-///     proxy(&mut frontend, &mut backend, None).await
+///     proxy(&mut frontend, &mut backend, None, ProxyOptions::default()).await
 /// }
 /// ```
-pub async fn proxy<F, B, C>(
+pub async fn proxy<F, B>(
     frontend: &mut F,
     backend: &mut B,
-    mut capture: Option<&mut C>,
+    mut capture: Option<&mut dyn ProxySocket>,
+    options: ProxyOptions,
 ) -> io::Result<()>
 where
     F: ProxySocket,
     B: ProxySocket,
-    C: ProxySocket,
 {
     use futures::{FutureExt, select};
 
+    let (mut frontend_bucket, mut backend_bucket) = options.buckets()?;
+    let counters = &*options.counters;
+
     debug!(
         "Starting proxy: {} ←→ {}",
         frontend.socket_desc(),
         backend.socket_desc()
     );
 
+    let batch_size = options.batch_size.max(1);
+
     loop {
         // Use select! to multiplex between frontend and backend in single-threaded runtime
         select! {
             // Forward frontend → backend
             msg_result = frontend.recv_multipart().fuse() => {
                 if let Some(msg) = msg_result? {
-                    debug!("Proxy: {} → {}: {} frames",
-                           frontend.socket_desc(),
-                           backend.socket_desc(),
-                           msg.len());
-
-                    // Send copy to capture if present
-                    if let Some(ref mut cap) = capture
-                        && let Err(e) = cap.send_multipart(msg.clone()).await
-                    {
-                        debug!("Capture socket send failed: {}", e);
-                    }
-
-                    // Forward to backend. A transient error (HWM/EAGAIN) drops
-                    // this frame but keeps the proxy alive; a fatal error tears
-                    // the loop down.
-                    if let Err(e) = backend.send_multipart(msg).await {
-                        if is_transient_send_error(&e) {
-                            debug!("Proxy: transient send to {}, dropping frame: {}",
-                                   backend.socket_desc(), e);
-                        } else {
-                            return Err(e);
-                        }
-                    }
+                    let mut batch = vec![msg];
+                    fill_batch(frontend, &mut batch, batch_size)?;
+                    forward(
+                        batch,
+                        frontend.socket_desc(),
+                        backend,
+                        &mut capture,
+                        &mut frontend_bucket,
+                        counters,
+                        &counters.frontend_forwarded,
+                    )
+                    .await?;
                 }
             }
 
             // Forward backend → frontend
             msg_result = backend.recv_multipart().fuse() => {
                 if let Some(msg) = msg_result? {
-                    debug!("Proxy: {} → {}: {} frames",
-                           backend.socket_desc(),
-                           frontend.socket_desc(),
-                           msg.len());
-
-                    // Send copy to capture if present
-                    if let Some(ref mut cap) = capture
-                        && let Err(e) = cap.send_multipart(msg.clone()).await
-                    {
-                        debug!("Capture socket send failed: {}", e);
-                    }
-
-                    // Forward to frontend (transient errors keep the proxy up).
-                    if let Err(e) = frontend.send_multipart(msg).await {
-                        if is_transient_send_error(&e) {
-                            debug!("Proxy: transient send to {}, dropping frame: {}",
-                                   frontend.socket_desc(), e);
-                        } else {
-                            return Err(e);
-                        }
-                    }
+                    let mut batch = vec![msg];
+                    fill_batch(backend, &mut batch, batch_size)?;
+                    forward(
+                        batch,
+                        backend.socket_desc(),
+                        frontend,
+                        &mut capture,
+                        &mut backend_bucket,
+                        counters,
+                        &counters.backend_forwarded,
+                    )
+                    .await?;
                 }
             }
         }
@@ -290,6 +612,8 @@ impl ProxyCommand {
 /// - `backend`: Socket facing workers/subscribers
 /// - `capture`: Optional socket to receive message copies
 /// - `control`: Socket that receives control commands
+/// - `options`: Per-direction rate limits and the forwarding counters; see
+///   [`ProxyOptions`].
 ///
 /// # Control Socket Protocol
 ///
@@ -304,7 +628,7 @@ impl ProxyCommand {
 /// # Example
 ///
 /// ```rust,ignore
-/// use monocoque_zmtp::proxy::{proxy_steerable, ProxySocket, ProxyCommand};
+/// use monocoque_zmtp::proxy::{proxy_steerable, ProxyCommand, ProxyOptions, ProxySocket};
 /// use monocoque_zmtp::router::RouterSocket;
 /// use monocoque_zmtp::dealer::DealerSocket;
 /// use monocoque_zmtp::pair::PairSocket;
@@ -319,7 +643,8 @@ impl ProxyCommand {
 ///     let (_, mut control) = PairSocket::bind("127.0.0.1:5557").await?;
 ///
 ///     // Run steerable proxy
-///     proxy_steerable(&mut frontend, &mut backend, None, &mut control).await?;
+///     proxy_steerable(&mut frontend, &mut backend, None, &mut control, ProxyOptions::default())
+///         .await?;
 ///     Ok(())
 /// }
 /// ```
@@ -343,28 +668,31 @@ impl ProxyCommand {
 /// # Ok(())
 /// # }
 /// ```
-pub async fn proxy_steerable<F, B, C, Ctrl>(
+pub async fn proxy_steerable<F, B, Ctrl>(
     frontend: &mut F,
     backend: &mut B,
-    mut capture: Option<&mut C>,
+    mut capture: Option<&mut dyn ProxySocket>,
     control: &mut Ctrl,
+    options: ProxyOptions,
 ) -> io::Result<()>
 where
     F: ProxySocket,
     B: ProxySocket,
-    C: ProxySocket,
     Ctrl: ProxySocket,
 {
     use futures::{FutureExt, select};
 
+    let (mut frontend_bucket, mut backend_bucket) = options.buckets()?;
+    let counters = &*options.counters;
+
     debug!(
         "Starting steerable proxy: {} ←→ {} (control enabled)",
         frontend.socket_desc(),
         backend.socket_desc()
     );
 
+    let batch_size = options.batch_size.max(1);
     let mut paused = false;
-    let mut message_count = 0u64;
 
     loop {
         select! {
@@ -386,10 +714,12 @@ where
                             paused = false;
                         }
                         ProxyCommand::Terminate => {
-                            debug!("Proxy TERMINATING (forwarded {} messages)", message_count);
+                            debug!("Proxy TERMINATING ({:?})", options.stats());
                             return Ok(());
                         }
                         ProxyCommand::Statistics => {
+                            let stats = options.stats();
+                            let message_count = stats.frontend_forwarded + stats.backend_forwarded;
                             debug!("Proxy statistics: {} messages forwarded", message_count);
                             let stats = format!("messages_forwarded={}", message_count);
                             let _ = control.send_multipart(vec![bytes::Bytes::from(stats)]).await;
@@ -404,27 +734,18 @@ where
                     if paused {
                         debug!("Proxy: dropped message (paused)");
                     } else {
-                        debug!("Proxy: {} → {}: {} frames",
-                               frontend.socket_desc(),
-                               backend.socket_desc(),
-                               msg.len());
-
-                        // Send copy to capture if present
-                        if let Some(ref mut cap) = capture
-                            && let Err(e) = cap.send_multipart(msg.clone()).await
-                        {
-                            debug!("Capture socket send failed: {}", e);
-                        }
-
-                        // Forward to backend (transient errors keep the proxy up).
-                        match backend.send_multipart(msg).await {
-                            Ok(()) => message_count += 1,
-                            Err(e) if is_transient_send_error(&e) => {
-                                debug!("Proxy: transient send to {}, dropping frame: {}",
-                                       backend.socket_desc(), e);
-                            }
-                            Err(e) => return Err(e),
-                        }
+                        let mut batch = vec![msg];
+                        fill_batch(frontend, &mut batch, batch_size)?;
+                        forward(
+                            batch,
+                            frontend.socket_desc(),
+                            backend,
+                            &mut capture,
+                            &mut frontend_bucket,
+                            counters,
+                            &counters.frontend_forwarded,
+                        )
+                        .await?;
                     }
                 }
             }
@@ -435,27 +756,18 @@ where
                     if paused {
                         debug!("Proxy: dropped message (paused)");
                     } else {
-                        debug!("Proxy: {} → {}: {} frames",
-                               backend.socket_desc(),
-                               frontend.socket_desc(),
-                               msg.len());
-
-                        // Send copy to capture if present
-                        if let Some(ref mut cap) = capture
-                            && let Err(e) = cap.send_multipart(msg.clone()).await
-                        {
-                            debug!("Capture socket send failed: {}", e);
-                        }
-
-                        // Forward to frontend (transient errors keep the proxy up).
-                        match frontend.send_multipart(msg).await {
-                            Ok(()) => message_count += 1,
-                            Err(e) if is_transient_send_error(&e) => {
-                                debug!("Proxy: transient send to {}, dropping frame: {}",
-                                       frontend.socket_desc(), e);
-                            }
-                            Err(e) => return Err(e),
-                        }
+                        let mut batch = vec![msg];
+                        fill_batch(backend, &mut batch, batch_size)?;
+                        forward(
+                            batch,
+                            backend.socket_desc(),
+                            frontend,
+                            &mut capture,
+                            &mut backend_bucket,
+                            counters,
+                            &counters.backend_forwarded,
+                        )
+                        .await?;
                     }
                 }
             }
@@ -473,45 +785,8 @@ impl ProxySocket for XSubSocket {
     }
 
     async fn send_multipart(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        // In a PUB-SUB broker the proxy receives subscription events from XPUB
-        // (backend) and must forward them upstream via XSUB so the publisher stops
-        // or starts sending the relevant topics.
-        //
-        // The message format produced by XPubSocket::recv_multipart is:
-        //   [b"\x01", topic]  - subscribe
-        //   [b"\x00", topic]  - unsubscribe
-        //
-        // We reconstruct the raw ZMTP subscription frame and dispatch it.
-        if msg.is_empty() {
-            return Ok(());
-        }
-
-        let cmd_frame = &msg[0];
-        if cmd_frame.is_empty() {
-            return Ok(());
-        }
-
-        let cmd_byte = cmd_frame[0];
-        // Topic is either in a second frame or appended after the command byte
-        // in the same frame, depending on how the message was encoded.
-        let topic: Bytes = if msg.len() >= 2 {
-            msg[1].clone()
-        } else if cmd_frame.len() > 1 {
-            cmd_frame.slice(1..)
-        } else {
-            Bytes::new()
-        };
-
-        let event = if cmd_byte == 0x01 {
-            monocoque_core::subscription::SubscriptionEvent::Subscribe(topic)
-        } else if cmd_byte == 0x00 {
-            monocoque_core::subscription::SubscriptionEvent::Unsubscribe(topic)
-        } else {
-            // Unknown command - ignore
-            return Ok(());
-        };
-
-        self.send_subscription_event(event).await
+        // Subscription events from the XPUB backend, forwarded upstream.
+        self.send(msg).await
     }
 
     fn socket_desc(&self) -> &'static str {
@@ -563,6 +838,10 @@ impl ProxySocket for DealerSocket {
     fn socket_desc(&self) -> &'static str {
         "DEALER"
     }
+
+    async fn send_multipart_batch(&mut self, msgs: Vec<Vec<Bytes>>) -> io::Result<()> {
+        self.send_batch(&msgs).await
+    }
 }
 
 // ROUTER socket (frontend in REQ-REP load balancer)
@@ -579,6 +858,10 @@ impl ProxySocket for RouterSocket {
     fn socket_desc(&self) -> &'static str {
         "ROUTER"
     }
+
+    async fn send_multipart_batch(&mut self, msgs: Vec<Vec<Bytes>>) -> io::Result<()> {
+        self.send_batch(&msgs).await
+    }
 }
 
 // PULL socket (frontend in PUSH-PULL forwarder)
@@ -596,6 +879,10 @@ impl ProxySocket for PullSocket {
     fn socket_desc(&self) -> &'static str {
         "PULL"
     }
+
+    fn try_recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        self.try_recv()
+    }
 }
 
 // PUSH socket (backend in PUSH-PULL forwarder)
@@ -613,6 +900,10 @@ impl ProxySocket for PushSocket {
     fn socket_desc(&self) -> &'static str {
         "PUSH"
     }
+
+    async fn send_multipart_batch(&mut self, msgs: Vec<Vec<Bytes>>) -> io::Result<()> {
+        self.send_batch(msgs).await.map(drop)
+    }
 }
 
 // REQ socket
@@ -765,6 +1056,299 @@ mod tests {
         assert_eq!(sock.recv_queue.len(), 1);
     }
 
+    #[test]
+    fn token_bucket_allows_burst_then_paces() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimit::new(100, 3), start);
+        for _ in 0..3 {
+            assert_eq!(bucket.acquire(start), Duration::ZERO);
+        }
+        // Each further token is 10ms behind the previous one.
+        let wait = bucket.acquire(start);
+        assert!((wait.as_secs_f64() - 0.010).abs() < 1e-6, "{wait:?}");
+        let wait = bucket.acquire(start);
+        assert!((wait.as_secs_f64() - 0.020).abs() < 1e-6, "{wait:?}");
+
+        // An idle second refills up to the burst, no further.
+        let later = start + Duration::from_secs(1);
+        for _ in 0..3 {
+            assert_eq!(bucket.acquire(later), Duration::ZERO);
+        }
+        assert!(!bucket.acquire(later).is_zero());
+    }
+
+    #[test]
+    fn zero_rate_is_rejected() {
+        let options = ProxyOptions::new().with_backend_rate_limit(RateLimit::new(0, 1));
+        let err = options.buckets().err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    /// Yields `remaining` messages, then fails to end the proxy loop.
+    struct FiniteSource {
+        remaining: usize,
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl ProxySocket for FiniteSource {
+        async fn recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+            if self.remaining == 0 {
+                return Err(io::Error::new(io::ErrorKind::ConnectionAborted, "done"));
+            }
+            self.remaining -= 1;
+            Ok(Some(vec![Bytes::from_static(b"msg")]))
+        }
+
+        async fn send_multipart(&mut self, _msg: Vec<Bytes>) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn socket_desc(&self) -> &'static str {
+            "source"
+        }
+    }
+
+    /// Never receives; counts what is forwarded to it.
+    #[derive(Default)]
+    struct IdleSink {
+        received: usize,
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl ProxySocket for IdleSink {
+        async fn recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+            std::future::pending().await
+        }
+
+        async fn send_multipart(&mut self, _msg: Vec<Bytes>) -> io::Result<()> {
+            self.received += 1;
+            Ok(())
+        }
+
+        fn socket_desc(&self) -> &'static str {
+            "sink"
+        }
+    }
+
+    #[test]
+    fn recv_multipart_timeout_tells_a_quiet_socket_from_a_closed_one() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let dur = Duration::from_millis(50);
+                let started = Instant::now();
+                let outcome = IdleSink::default().recv_multipart_timeout(dur).await;
+                assert_eq!(outcome.unwrap(), RecvOutcome::TimedOut);
+                assert!(started.elapsed() >= dur);
+
+                let mut source = MockSocket::new("source");
+                source.enqueue(vec![Bytes::from_static(b"msg")]);
+                assert_eq!(
+                    source.recv_multipart_timeout(dur).await.unwrap(),
+                    RecvOutcome::Message(vec![Bytes::from_static(b"msg")])
+                );
+                assert_eq!(
+                    source.recv_multipart_timeout(dur).await.unwrap(),
+                    RecvOutcome::Closed
+                );
+            });
+    }
+
+    #[test]
+    fn recv_multipart_timeout_resumes_a_partly_received_message() {
+        use crate::session::SocketType;
+        use compio_buf::BufResult;
+        use compio_io::AsyncWriteExt;
+        use monocoque_core::rt::{TcpListener, TcpStream};
+
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let addr = listener.local_addr().unwrap();
+                let peer = monocoque_core::rt::spawn(async move {
+                    let (mut stream, _) = listener.accept().await.unwrap();
+                    crate::handshake::perform_handshake_with_options(
+                        &mut stream,
+                        SocketType::Router,
+                        None,
+                        None,
+                        &monocoque_core::options::SocketOptions::default(),
+                    )
+                    .await
+                    .unwrap();
+                    stream
+                });
+                let mut dealer = DealerSocket::from_tcp(TcpStream::connect(addr).await.unwrap())
+                    .await
+                    .unwrap();
+                let mut peer = monocoque_core::rt::join(peer).await;
+
+                // The first frame and half of the second, then silence.
+                let BufResult(res, _) = peer.write_all(&b"\x01\x03one\x00\x03tw"[..]).await;
+                res.unwrap();
+                let outcome = dealer
+                    .recv_multipart_timeout(Duration::from_millis(50))
+                    .await;
+                assert_eq!(outcome.unwrap(), RecvOutcome::TimedOut);
+
+                let BufResult(res, _) = peer.write_all(&b"o"[..]).await;
+                res.unwrap();
+                let outcome = dealer.recv_multipart_timeout(Duration::from_secs(5)).await;
+                assert_eq!(
+                    outcome.unwrap(),
+                    RecvOutcome::Message(vec![
+                        Bytes::from_static(b"one"),
+                        Bytes::from_static(b"two")
+                    ])
+                );
+            });
+    }
+
+    #[test]
+    fn rate_limited_proxy_forwards_at_configured_rate() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                // 51 messages at 100 msg/s with a burst of 1: the first goes
+                // at once, the other 50 take 500ms.
+                let mut source = FiniteSource { remaining: 51 };
+                let mut sink = IdleSink::default();
+                let options = ProxyOptions::new().with_frontend_rate_limit(RateLimit::new(100, 1));
+                let handle = options.clone();
+
+                let start = Instant::now();
+                let err = proxy(&mut source, &mut sink, None, options)
+                    .await
+                    .unwrap_err();
+                let elapsed = start.elapsed();
+
+                assert_eq!(err.kind(), io::ErrorKind::ConnectionAborted);
+                assert_eq!(sink.received, 51);
+                let expected = Duration::from_millis(500);
+                assert!(
+                    elapsed >= expected.mul_f64(0.9) && elapsed <= expected.mul_f64(1.1),
+                    "forwarding took {elapsed:?}, expected {expected:?} ± 10%"
+                );
+
+                let stats = handle.stats();
+                assert_eq!(stats.frontend_forwarded, 51);
+                assert_eq!(stats.backend_forwarded, 0);
+                assert!(
+                    (450..=550).contains(&stats.rate_limited_ms),
+                    "rate_limited_ms = {}",
+                    stats.rate_limited_ms
+                );
+            });
+    }
+
+    /// Holds queued messages, all of them ready at once; fails when empty to
+    /// end the proxy loop.
+    struct QueuedSource {
+        queue: std::collections::VecDeque<Vec<Bytes>>,
+    }
+
+    impl QueuedSource {
+        fn with_messages(n: usize) -> Self {
+            Self {
+                queue: (0..n).map(|i| vec![Bytes::from(format!("m{i}"))]).collect(),
+            }
+        }
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl ProxySocket for QueuedSource {
+        async fn recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+            match self.queue.pop_front() {
+                Some(msg) => Ok(Some(msg)),
+                None => Err(io::Error::new(io::ErrorKind::ConnectionAborted, "done")),
+            }
+        }
+
+        async fn send_multipart(&mut self, _msg: Vec<Bytes>) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn socket_desc(&self) -> &'static str {
+            "queued"
+        }
+
+        fn try_recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+            Ok(self.queue.pop_front())
+        }
+    }
+
+    /// Never receives; records the messages of each send call, one entry per
+    /// flush.
+    #[derive(Default)]
+    struct FlushRecorder {
+        flushes: Vec<Vec<Vec<Bytes>>>,
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl ProxySocket for FlushRecorder {
+        async fn recv_multipart(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+            std::future::pending().await
+        }
+
+        async fn send_multipart(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+            self.flushes.push(vec![msg]);
+            Ok(())
+        }
+
+        fn socket_desc(&self) -> &'static str {
+            "recorder"
+        }
+
+        async fn send_multipart_batch(&mut self, msgs: Vec<Vec<Bytes>>) -> io::Result<()> {
+            self.flushes.push(msgs);
+            Ok(())
+        }
+    }
+
+    /// Run `proxy` from a source of `n` ready messages, returning the
+    /// backend's flushes and what the capture socket saw.
+    fn run_batched(n: usize, batch_size: usize) -> (Vec<Vec<Vec<Bytes>>>, Vec<Vec<Bytes>>) {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let mut source = QueuedSource::with_messages(n);
+                let mut backend = FlushRecorder::default();
+                let mut capture = MockSocket::new("capture");
+                let options = ProxyOptions::new().with_batch_size(batch_size);
+                let handle = options.clone();
+
+                let err = proxy(&mut source, &mut backend, Some(&mut capture), options)
+                    .await
+                    .unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::ConnectionAborted);
+                assert_eq!(handle.stats().frontend_forwarded, n as u64);
+                (backend.flushes, capture.send_queue)
+            })
+    }
+
+    #[test]
+    fn ready_messages_reach_the_backend_in_one_flush() {
+        let expected: Vec<_> = QueuedSource::with_messages(5).queue.into();
+        for batch_size in [5, 8] {
+            let (flushes, captured) = run_batched(5, batch_size);
+            assert_eq!(flushes, vec![expected.clone()], "batch_size {batch_size}");
+            assert_eq!(captured, expected, "capture sees every message, in order");
+        }
+    }
+
+    #[test]
+    fn batches_are_capped_at_batch_size() {
+        let (flushes, captured) = run_batched(5, 2);
+        let sizes: Vec<_> = flushes.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(captured, flushes.concat());
+
+        // The default forwards one message per send.
+        let (flushes, _) = run_batched(5, ProxyOptions::default().batch_size);
+        assert_eq!(flushes.len(), 5);
+    }
+
     // TODO: Add integration tests with real sockets
     // - Test XSUB-XPUB broker pattern
     // - Test ROUTER-DEALER load balancer
@@ -38,12 +38,14 @@
 /// - Zero-copy via `Arc<Bytes>` for message data
 use bytes::Bytes;
 use flume::{Receiver, Sender};
+use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
 use monocoque_core::rt::{OwnedReadHalf, OwnedWriteHalf, TcpListener, TcpStream};
 use monocoque_core::subscription::SubscriptionEvent;
 
-use crate::handshake::perform_handshake_with_options;
+use crate::codec::{Command, parse_command};
+use crate::handshake::{handshake_error, perform_handshake_with_options};
 use crate::session::SocketType;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::io;
@@ -185,13 +187,44 @@ enum WorkerCommand {
         max_frame_size: Option<usize>,
         /// Shared subscription union for the `send()` prefilter.
         union: Arc<SharedSubscriptions>,
+        /// Application-visible index mirrored by the subscription reader.
+        index: Option<SharedSubscriptionIndex>,
+    },
+    /// Broadcast a message to the matching subscribers in this worker, or to
+    /// every subscriber when `to_all` is set
+    Broadcast {
+        message: Arc<Vec<Bytes>>,
+        to_all: bool,
     },
-    /// Broadcast a message to all subscribers in this worker
-    Broadcast { message: Arc<Vec<Bytes>> },
     /// Shutdown the worker
     Shutdown,
 }
 
+/// Live counters a worker shares with the owning socket.
+#[derive(Default)]
+struct WorkerStats {
+    /// Subscribers currently assigned to the worker.
+    subscribers: AtomicUsize,
+    /// Messages written to subscribers, one per message per subscriber.
+    delivered: AtomicU64,
+}
+
+/// Counters aggregated across every worker of a [`PubSocket`].
+///
+/// Returned by [`PubSocket::stats`]. Subscriber counts are live: a worker
+/// decrements its count as soon as it evicts a dead subscriber.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PubStats {
+    /// Live subscribers on each worker, indexed by worker.
+    pub per_worker: Vec<usize>,
+    /// Live subscribers across all workers.
+    pub subscribers: usize,
+    /// Messages written to subscribers, counting each subscriber separately.
+    pub delivered: u64,
+    /// Broadcasts dropped at a full worker channel (HWM).
+    pub dropped: u64,
+}
+
 /// Per-subscriber state managed by worker
 struct WorkerSubscriber {
     id: SubscriberId,
@@ -233,7 +266,7 @@ impl WorkerSubscriber {
 // The subscriptions write lock is intentionally held across `union.update` so a
 // subscription and its union entry change together atomically; releasing it
 // earlier (as the lint suggests) would let a broadcast observe a torn state.
-#[allow(clippy::significant_drop_tightening)]
+#[allow(clippy::significant_drop_tightening, clippy::too_many_lines)]
 async fn subscription_reader(
     id: SubscriberId,
     mut reader: OwnedReadHalf,
@@ -241,6 +274,7 @@ async fn subscription_reader(
     cipher: Option<SubCipher>,
     max_frame_size: Option<usize>,
     union: Arc<SharedSubscriptions>,
+    index: Option<SharedSubscriptionIndex>,
 ) {
     use compio_buf::BufResult;
     use compio_io::AsyncRead;
@@ -273,22 +307,29 @@ async fn subscription_reader(
                 loop {
                     match decoder.decode(&mut recv_buf) {
                         Ok(Some(frame)) => {
-                            // Resolve payload: decrypt CURVE MESSAGE frames, skip other commands.
+                            // Resolve payload: decrypt CURVE MESSAGE frames, translate
+                            // ZMTP 3.1 SUBSCRIBE/CANCEL commands, skip other commands.
                             let payload = if frame.is_command() {
-                                if let Some(ref arc_cipher) = cipher {
-                                    if crate::security::curve::CurveMessageCipher::is_curve_message(
+                                if let Some(ref arc_cipher) = cipher
+                                    && crate::security::curve::CurveMessageCipher::is_curve_message(
                                         &frame.payload,
-                                    ) {
-                                        let mut cipher_guard = arc_cipher.lock();
-                                        match cipher_guard.decrypt_frame(&frame.payload) {
-                                            Ok((_more, data)) => data,
-                                            Err(_) => continue,
-                                        }
-                                    } else {
-                                        continue;
+                                    )
+                                {
+                                    let mut cipher_guard = arc_cipher.lock();
+                                    match cipher_guard.decrypt_frame(&frame.payload) {
+                                        Ok((_more, data)) => data,
+                                        Err(_) => continue,
                                     }
                                 } else {
-                                    continue;
+                                    match parse_command(&frame) {
+                                        Ok(Command::Subscribe(prefix)) => {
+                                            SubscriptionEvent::Subscribe(prefix).to_message()
+                                        }
+                                        Ok(Command::Cancel(prefix)) => {
+                                            SubscriptionEvent::Unsubscribe(prefix).to_message()
+                                        }
+                                        _ => continue,
+                                    }
                                 }
                             } else if cipher.is_some() {
                                 // Reject plaintext data frames when CURVE is active.
@@ -308,6 +349,9 @@ async fn subscription_reader(
                                             );
                                             let was_empty = subs.is_empty();
                                             union.update(|u| u.subscribe(&prefix, was_empty));
+                                            if let Some(index) = &index {
+                                                index.subscribe(id, prefix.clone());
+                                            }
                                             subs.push(prefix);
                                         }
                                     }
@@ -321,6 +365,9 @@ async fn subscription_reader(
                                             );
                                             let now_empty = subs.is_empty();
                                             union.update(|u| u.unsubscribe(&prefix, now_empty));
+                                            if let Some(index) = &index {
+                                                index.unsubscribe(id, &prefix);
+                                            }
                                         }
                                     }
                                 }
@@ -347,12 +394,16 @@ async fn subscription_reader(
         }
     }
 
+    // A gone subscriber no longer wants anything; keep the shared index exact.
+    if let Some(index) = &index {
+        index.remove_peer(id);
+    }
     trace!("[PUB] Subscription reader exiting for subscriber {}", id);
 }
 
 /// Worker thread that handles multiple subscribers
 #[allow(clippy::too_many_lines)]
-fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<AtomicUsize>) {
+fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, stats: Arc<WorkerStats>) {
     debug!("[Worker {}] Starting", worker_id);
 
     let rt = match monocoque_core::rt::LocalRuntime::new() {
@@ -375,6 +426,7 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
                     cipher,
                     max_frame_size,
                     union,
+                    index,
                 }) => {
                     add_subscriber(
                         &mut subscribers,
@@ -385,22 +437,23 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
                         cipher,
                         max_frame_size,
                         union,
+                        index,
                     );
                 }
-                Ok(WorkerCommand::Broadcast { message }) => {
+                Ok(WorkerCommand::Broadcast { message, to_all }) => {
                     // Coalescing: drain any broadcasts already queued behind this
                     // one into a single batch, so each subscriber receives one
                     // vectored write per burst instead of one write per message.
-                    let mut batch: Vec<Arc<Vec<Bytes>>> = Vec::with_capacity(4);
+                    let mut batch: Vec<(Arc<Vec<Bytes>>, bool)> = Vec::with_capacity(4);
                     let mut batch_bytes = approx_wire_len(&message);
-                    batch.push(message);
+                    batch.push((message, to_all));
 
                     let mut deferred: Option<WorkerCommand> = None;
                     while batch.len() < MAX_COALESCE_MSGS && batch_bytes < COALESCE_BYTE_LIMIT {
                         match rx.try_recv() {
-                            Ok(WorkerCommand::Broadcast { message }) => {
+                            Ok(WorkerCommand::Broadcast { message, to_all }) => {
                                 batch_bytes += approx_wire_len(&message);
-                                batch.push(message);
+                                batch.push((message, to_all));
                             }
                             // A non-broadcast command interrupts the burst: stash
                             // it and process after flushing this batch so command
@@ -436,11 +489,12 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
 
                         // Encode this subscriber's slice of the batch into one
                         // contiguous buffer.
+                        let mut matched = batch.len();
                         let out: Bytes = if plaintext_all {
                             full_batch
                                 .get_or_insert_with(|| {
                                     let mut buf = bytes::BytesMut::new();
-                                    for msg in &batch {
+                                    for (msg, _) in &batch {
                                         crate::codec::encode_multipart(msg, &mut buf);
                                     }
                                     buf.freeze()
@@ -449,10 +503,12 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
                         } else {
                             let mut buf = bytes::BytesMut::new();
                             let mut failed = false;
-                            for (idx, msg) in batch.iter().enumerate() {
-                                if !sub.matches(msg) {
+                            matched = 0;
+                            for (idx, (msg, to_all)) in batch.iter().enumerate() {
+                                if !to_all && !sub.matches(msg) {
                                     continue;
                                 }
+                                matched += 1;
                                 if let Some(ref arc_cipher) = sub.cipher {
                                     if let Some(wire) = encode_curve_wire(msg, arc_cipher) {
                                         buf.extend_from_slice(&wire);
@@ -486,6 +542,7 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
 
                         match send_result {
                             Ok(Ok(())) => {
+                                stats.delivered.fetch_add(matched as u64, Ordering::Relaxed);
                                 trace!("[Worker {}] Sent to subscriber {}", worker_id, sub.id);
                             }
                             Ok(Err(e)) => {
@@ -504,7 +561,9 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
 
                     // Clean up failed subscribers
                     if !dead_subs.is_empty() {
-                        sub_count.fetch_sub(dead_subs.len(), Ordering::Relaxed);
+                        stats
+                            .subscribers
+                            .fetch_sub(dead_subs.len(), Ordering::Relaxed);
                     }
                     for id in dead_subs {
                         subscribers.remove(&id);
@@ -520,6 +579,7 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
                             cipher,
                             max_frame_size,
                             union,
+                            index,
                         }) => {
                             add_subscriber(
                                 &mut subscribers,
@@ -530,6 +590,7 @@ fn worker_thread(worker_id: usize, rx: Receiver<WorkerCommand>, sub_count: Arc<A
                                 cipher,
                                 max_frame_size,
                                 union,
+                                index,
                             );
                         }
                         Some(WorkerCommand::Shutdown) => {
@@ -608,6 +669,7 @@ fn add_subscriber(
     cipher: Option<SubCipher>,
     max_frame_size: Option<usize>,
     union: Arc<SharedSubscriptions>,
+    index: Option<SharedSubscriptionIndex>,
 ) {
     debug!("[Worker {}] Adding subscriber {}", worker_id, id);
 
@@ -639,6 +701,7 @@ fn add_subscriber(
         reader_cipher,
         max_frame_size,
         union,
+        index,
     ));
 
     subscribers.insert(
@@ -657,14 +720,15 @@ fn add_subscriber(
 /// Uses multiple worker threads to handle subscribers in parallel.
 /// Each worker runs its own compio runtime with io_uring.
 pub struct PubSocket {
-    /// Worker thread channels (bounded by send_hwm for backpressure)
+    /// Worker thread channels (held to the current send_hwm by `dispatch`)
     workers: Vec<Sender<WorkerCommand>>,
     /// Join handles for the worker threads, retained so `close()` can await
     /// their completion instead of detaching them (see [`PubSocket::close`]).
     worker_handles: Vec<thread::JoinHandle<()>>,
-    /// Live subscriber count per worker. `send()` skips workers at zero so a
-    /// low-subscriber broadcast does not pay a channel hand-off to every worker.
-    worker_sub_counts: Vec<Arc<AtomicUsize>>,
+    /// Live counters per worker. `send()` skips workers with no subscribers so
+    /// a low-subscriber broadcast does not pay a channel hand-off to every
+    /// worker, and `accept_subscriber` picks the least-loaded worker.
+    worker_stats: Vec<Arc<WorkerStats>>,
     /// Union of all subscriptions, kept current by the subscription readers.
     /// `send()` consults it to drop a broadcast before the hand-off when no
     /// subscriber could match (the big win for topic-filtered workloads).
@@ -674,9 +738,12 @@ pub struct PubSocket {
     /// lock) only when the shared generation advances.
     local_union: SubscriptionUnion,
     local_gen: u64,
+    /// Application-visible subscription index, if one was attached with
+    /// [`PubSocket::with_shared_index`].
+    shared_index: Option<SharedSubscriptionIndex>,
     /// Next subscriber ID
     next_id: SubscriberId,
-    /// Next worker to assign (round-robin)
+    /// Where the next least-loaded scan starts, so ties rotate across workers
     next_worker: usize,
     /// Socket options
     options: SocketOptions,
@@ -714,40 +781,41 @@ impl PubSocket {
 
     /// Create with a specific number of worker threads and custom socket options.
     ///
-    /// Worker channels are bounded by `options.send_hwm`. When a worker's channel
-    /// is full (the worker is slow/blocked), broadcast messages for that worker are
+    /// Worker channels are held to `options.send_hwm` (read on every send, so
+    /// a changed HWM applies at once). When a worker's channel is full (the
+    /// worker is slow/blocked), broadcast messages for that worker are
     /// silently dropped and counted in `drop_count()`.
     pub fn with_workers_opts(worker_count: usize, options: SocketOptions) -> Self {
-        let hwm = options.send_hwm;
         debug!(
             "[PUB] Starting {} worker threads (channel HWM={})",
-            worker_count, hwm
+            worker_count, options.send_hwm
         );
 
         let mut workers = Vec::with_capacity(worker_count);
         let mut worker_handles = Vec::with_capacity(worker_count);
-        let mut worker_sub_counts = Vec::with_capacity(worker_count);
+        let mut worker_stats = Vec::with_capacity(worker_count);
 
         for i in 0..worker_count {
-            let (tx, rx) = flume::bounded(hwm);
-            let sub_count = Arc::new(AtomicUsize::new(0));
-            let worker_count = Arc::clone(&sub_count);
+            let (tx, rx) = flume::unbounded();
+            let stats = Arc::new(WorkerStats::default());
+            let thread_stats = Arc::clone(&stats);
             let handle = thread::Builder::new()
                 .name(format!("pub-worker-{}", i))
-                .spawn(move || worker_thread(i, rx, worker_count))
+                .spawn(move || worker_thread(i, rx, thread_stats))
                 .expect("Failed to spawn worker thread");
             workers.push(tx);
             worker_handles.push(handle);
-            worker_sub_counts.push(sub_count);
+            worker_stats.push(stats);
         }
 
         Self {
             workers,
             worker_handles,
-            worker_sub_counts,
+            worker_stats,
             subscription_union: SharedSubscriptions::new(),
             local_union: SubscriptionUnion::default(),
             local_gen: 0,
+            shared_index: None,
             next_id: 1,
             next_worker: 0,
             options,
@@ -757,9 +825,47 @@ impl PubSocket {
         }
     }
 
+    /// Mirror subscriptions into an application-visible index.
+    ///
+    /// Every SUBSCRIBE/CANCEL this socket processes is applied to `index`
+    /// under the same lock that updates the subscriber's own filter, keyed by
+    /// the id returned from [`Self::accept_subscriber`], and a
+    /// subscriber's entries are dropped when it disconnects. A query made after
+    /// the socket processed a subscription therefore sees it. Subscribers that
+    /// have not subscribed yet receive everything but have no index entries.
+    ///
+    /// The broadcast path never reads the index, so application queries do
+    /// not slow down `send()`. Attach before accepting subscribers.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
+    /// use monocoque_zmtp::publisher::PubSocket;
+    ///
+    /// let index = SharedSubscriptionIndex::new();
+    /// let publisher = PubSocket::new().with_shared_index(index.clone());
+    /// if index.matches(b"weather.paris") {
+    ///     // someone is listening
+    /// }
+    /// # drop(publisher);
+    /// ```
+    #[must_use]
+    pub fn with_shared_index(mut self, index: SharedSubscriptionIndex) -> Self {
+        self.shared_index = Some(index);
+        self
+    }
+
+    /// The attached application-visible subscription index, if any.
+    #[must_use]
+    pub const fn shared_index(&self) -> Option<&SharedSubscriptionIndex> {
+        self.shared_index.as_ref()
+    }
+
     /// Accept a new subscriber connection
     ///
-    /// Performs ZMTP handshake and assigns subscriber to a worker thread (round-robin).
+    /// Performs ZMTP handshake and assigns subscriber to the worker with the
+    /// fewest live subscribers (ties rotate across workers).
     /// A background subscription reader task is spawned in the worker to handle
     /// subscribe/unsubscribe messages from this subscriber.
     pub async fn accept_subscriber(&mut self, listener: &TcpListener) -> io::Result<SubscriberId> {
@@ -773,11 +879,11 @@ impl PubSocket {
             &mut stream,
             SocketType::Pub,
             self.options.routing_id.as_deref(),
-            Some(self.options.handshake_timeout),
+            self.options.handshake_deadline(),
             &self.options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(peer_socket_type = ?handshake_result.peer_socket_type, "[PUB] Handshake complete");
 
@@ -787,12 +893,13 @@ impl PubSocket {
         // Create subscription state for this subscriber (starts empty = match all)
         let subscriptions = Arc::new(RwLock::new(Vec::new()));
 
-        // Assign to next worker (round-robin). Bump the worker's live-subscriber
+        // Assign to the least-loaded worker. Bump the worker's live-subscriber
         // count now (before the AddSubscriber is processed) so a concurrent
         // broadcast is never skipped for this worker.
-        let worker_idx = self.next_worker;
-        self.next_worker = (self.next_worker + 1) % self.workers.len();
-        self.worker_sub_counts[worker_idx].fetch_add(1, Ordering::Relaxed);
+        let worker_idx = self.least_loaded_worker();
+        self.worker_stats[worker_idx]
+            .subscribers
+            .fetch_add(1, Ordering::Relaxed);
         // A new subscriber with no subscription yet matches everything, so the
         // prefilter must not drop anything until it narrows its interest.
         self.subscription_union
@@ -824,6 +931,7 @@ impl PubSocket {
                 cipher,
                 max_frame_size: self.options.max_msg_size,
                 union: Arc::clone(&self.subscription_union),
+                index: self.shared_index.clone(),
             })
             .await
             .map_err(|e| io::Error::other(format!("Failed to send to worker: {}", e)))?;
@@ -837,6 +945,20 @@ impl PubSocket {
         Ok(id)
     }
 
+    /// Index of the worker with the fewest live subscribers.
+    ///
+    /// The scan starts after the previous pick, so workers with equal load
+    /// are filled round-robin.
+    fn least_loaded_worker(&mut self) -> usize {
+        let n = self.workers.len();
+        let idx = (0..n)
+            .map(|offset| (self.next_worker + offset) % n)
+            .min_by_key(|&idx| self.worker_stats[idx].subscribers.load(Ordering::Relaxed))
+            .unwrap_or(0);
+        self.next_worker = (idx + 1) % n;
+        idx
+    }
+
     /// Remove a subscriber and decrement the subscriber count.
     ///
     /// Workers also evict dead subscribers automatically when a send error is
@@ -877,20 +999,27 @@ impl PubSocket {
     /// to no stream. **If this hand-off ever gains a suspension point** (e.g. an
     /// awaiting `send_async` for backpressure instead of HWM-dropping), wrap the
     /// loop in a `PoisonGuard` like the PUSH/DEALER/REP write paths do.
-    fn dispatch(&self, message: &Arc<Vec<Bytes>>) -> io::Result<()> {
+    fn dispatch(&self, message: &Arc<Vec<Bytes>>, to_all: bool) -> io::Result<()> {
+        // send_hwm == 0 means no limit, as for the other sockets.
+        let hwm = self.options.send_hwm;
         for (idx, worker) in self.workers.iter().enumerate() {
             // Skip workers with no subscribers: no point paying the channel
             // hand-off + Arc clone for a worker that will match nothing.
-            if self.worker_sub_counts[idx].load(Ordering::Relaxed) == 0 {
+            if self.worker_stats[idx].subscribers.load(Ordering::Relaxed) == 0 {
+                continue;
+            }
+            if hwm != 0 && worker.len() >= hwm {
+                self.drop_count.fetch_add(1, Ordering::Relaxed);
+                debug!("[PUB] Worker {} channel full (HWM), message dropped", idx);
                 continue;
             }
             match worker.try_send(WorkerCommand::Broadcast {
                 message: Arc::clone(message),
+                to_all,
             }) {
                 Ok(()) => {}
                 Err(flume::TrySendError::Full(_)) => {
-                    self.drop_count.fetch_add(1, Ordering::Relaxed);
-                    debug!("[PUB] Worker {} channel full (HWM), message dropped", idx);
+                    unreachable!("worker channels are unbounded")
                 }
                 Err(flume::TrySendError::Disconnected(_)) => {
                     return Err(io::Error::other(format!(
@@ -922,7 +1051,7 @@ impl PubSocket {
         if !self.prefilter_allows(msg.first().map_or(&[][..], |f| f.as_ref())) {
             return Ok(());
         }
-        self.dispatch(&Arc::new(msg))
+        self.dispatch(&Arc::new(msg), false)
     }
 
     /// Broadcast a message given as borrowed frames.
@@ -945,7 +1074,45 @@ impl PubSocket {
         if !self.prefilter_allows(frames.first().map_or(&[][..], |f| f.as_ref())) {
             return Ok(());
         }
-        self.dispatch(&Arc::new(frames.to_vec()))
+        self.dispatch(&Arc::new(frames.to_vec()), false)
+    }
+
+    /// Publish `data` under `topic`: sends `[topic, data]`.
+    pub async fn publish(
+        &mut self,
+        topic: impl Into<Bytes>,
+        data: impl Into<Bytes>,
+    ) -> io::Result<()> {
+        self.send(vec![topic.into(), data.into()]).await
+    }
+
+    /// Publish `frames` under `topic`: sends `[topic, frames...]`.
+    pub async fn publish_multipart(
+        &mut self,
+        topic: impl Into<Bytes>,
+        mut frames: Vec<Bytes>,
+    ) -> io::Result<()> {
+        frames.insert(0, topic.into());
+        self.send(frames).await
+    }
+
+    /// Broadcast `frames` to every subscriber, whatever it subscribed to.
+    ///
+    /// For administrative messages such as shutdown notices: no subscription
+    /// filter is applied to the first frame. SUB peers still filter what they
+    /// receive, so the message reaches them only through a matching
+    /// subscription; XSUB peers, such as a proxy's, get it regardless.
+    pub async fn publish_to_all(&mut self, frames: Vec<Bytes>) -> io::Result<()> {
+        if self.is_poisoned {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "Socket is poisoned from previous incomplete operation",
+            ));
+        }
+        if frames.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Empty message"));
+        }
+        self.dispatch(&Arc::new(frames), true)
     }
 
     /// Get subscriber count.
@@ -960,6 +1127,56 @@ impl PubSocket {
         self.workers.len()
     }
 
+    /// Subscriber and delivery counters aggregated across all workers.
+    pub fn stats(&self) -> PubStats {
+        let per_worker: Vec<usize> = self
+            .worker_stats
+            .iter()
+            .map(|w| w.subscribers.load(Ordering::Relaxed))
+            .collect();
+        PubStats {
+            subscribers: per_worker.iter().sum(),
+            per_worker,
+            delivered: self
+                .worker_stats
+                .iter()
+                .map(|w| w.delivered.load(Ordering::Relaxed))
+                .sum(),
+            dropped: self.drop_count(),
+        }
+    }
+
+    /// Structured diagnostics of the socket, serializable with the `serde`
+    /// feature: one queue per worker with the broadcasts waiting in its
+    /// channel and the subscribers it serves, and the broadcasts dropped at
+    /// `send_hwm`. Subscribers have no routing identity, so `peers` stays
+    /// empty.
+    pub fn snapshot(&self) -> crate::DiagnosticsSnapshot {
+        let stats = self.stats();
+        let state = match (self.is_poisoned, stats.subscribers) {
+            (true, _) => "poisoned",
+            (false, 0) => "disconnected",
+            (false, _) => "connected",
+        };
+        let mut snapshot =
+            crate::DiagnosticsSnapshot::multi_peer(SocketType::Pub, None, state, &self.options);
+        snapshot.queues = self
+            .workers
+            .iter()
+            .zip(&stats.per_worker)
+            .enumerate()
+            .map(
+                |(i, (worker, &subscribers))| crate::diagnostics::QueueRecord {
+                    name: format!("worker {i}"),
+                    depth: worker.len(),
+                    peers: subscribers,
+                },
+            )
+            .collect();
+        snapshot.hwm_dropped = stats.dropped;
+        snapshot
+    }
+
     /// Gracefully shut down the socket, draining queued broadcasts and awaiting
     /// worker completion.
     ///
@@ -1000,10 +1217,25 @@ impl PubSocket {
         &self.options
     }
 
-    /// Get mutable socket options
+    /// Check if a send was cancelled mid-operation, leaving the socket unusable.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.is_poisoned
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.options
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.options.update(f)
     }
 
     /// Get the socket type.
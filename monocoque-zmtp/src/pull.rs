@@ -17,10 +17,14 @@
 //! - Work queue consumption
 
 use crate::base::SocketBase;
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use smallvec::SmallVec;
 use std::io;
@@ -59,11 +63,11 @@ where
             &mut stream,
             SocketType::Pull,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -75,6 +79,8 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Pull, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
@@ -169,6 +175,7 @@ where
     /// connection was closed, or an error.
     pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
         trace!("[PULL] Waiting for message");
+        self.base.recover_if_poisoned(SocketType::Pull).await;
 
         // Read from stream until we have a complete message
         loop {
@@ -210,6 +217,26 @@ where
         }
     }
 
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        }))
+    }
+
     /// Receive a message into a caller-provided buffer, reusing its allocation.
     ///
     /// Identical to [`recv`](Self::recv) except the message frames are pushed
@@ -221,6 +248,7 @@ where
     /// Returns `Ok(true)` when a complete message was read into `out`, `Ok(false)`
     /// when the connection was closed.
     pub async fn recv_into(&mut self, out: &mut Vec<Bytes>) -> io::Result<bool> {
+        self.base.recover_if_poisoned(SocketType::Pull).await;
         out.clear();
         loop {
             loop {
@@ -304,16 +332,101 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
     }
 
-    /// Set socket options (builder-style).
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Get the endpoint this socket is connected/bound to, if available.
+    ///
+    /// Returns `None` if the socket was created from a raw stream.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> Option<&Endpoint> {
+        self.base.last_endpoint()
+    }
+
+    /// Check if the last received message has more frames coming.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.base.has_more()
+    }
+
+    /// Get the event state of the socket (`1` = POLLIN, `2` = POLLOUT).
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.base.events()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 }
 
@@ -331,7 +444,9 @@ impl PullSocket<TcpStream> {
     ) -> io::Result<Self> {
         // Configure TCP optimizations including keepalive
         crate::utils::configure_tcp_stream(&stream, &options, "PULL")?;
-        Self::with_options(stream, options).await
+        let mut socket = Self::with_options(stream, options).await?;
+        socket.base.remember_peer_for_recovery();
+        Ok(socket)
     }
 
     /// Connect to a remote PULL socket, storing the endpoint for automatic reconnection.
@@ -353,11 +468,11 @@ impl PullSocket<TcpStream> {
             &mut stream,
             SocketType::Pull,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -368,23 +483,24 @@ impl PullSocket<TcpStream> {
 
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
         let mut base =
-            crate::base::SocketBase::with_endpoint(stream, SocketType::Pull, endpoint, options);
+            crate::base::SocketBase::with_tcp_endpoint(stream, SocketType::Pull, endpoint, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
         })
     }
 
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.base.is_connected()
-    }
-
     /// Try to reconnect to the stored endpoint.
+    ///
+    /// Frames of a message cut off by the lost connection are discarded, so
+    /// they cannot prefix the first message on the new one.
     pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.base.try_reconnect(SocketType::Pull).await
+        self.base.try_reconnect(SocketType::Pull).await?;
+        self.frames.clear();
+        Ok(())
     }
 
     /// Receive a message with automatic reconnection on EOF or network error.
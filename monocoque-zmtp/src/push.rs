@@ -17,10 +17,14 @@
 //! - Work queue distribution
 
 use crate::base::SocketBase;
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use std::io;
 use tracing::{debug, trace};
@@ -56,11 +60,11 @@ where
             &mut stream,
             SocketType::Push,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -72,6 +76,8 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Push, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self { base })
     }
 
@@ -92,16 +98,14 @@ where
     /// Returns an error if the socket is poisoned, disconnected, or if the write fails.
     pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
         trace!("[PUSH] Sending {} frames", msg.len());
+        self.base.recover_if_poisoned(SocketType::Push).await;
 
-        if self.base.options.write_coalescing {
+        if self.base.options.write_coalescing && self.base.options.coalesce_window.is_none() {
             self.base.send_coalesced(&msg).await?;
-        } else if self.base.should_vectored_write(&msg) {
-            // Large frame: write header + body as an iovec, skipping the copy
-            // into the userspace send buffer.
-            self.base.send_vectored(&msg).await?;
         } else {
-            self.base.encode_message_to_write_buf(&msg)?;
-            self.base.write_from_buf().await?;
+            // Eager path; large frames go out as an iovec, skipping the copy
+            // into the userspace send buffer.
+            self.base.send_message(&msg).await?;
         }
 
         // Check heartbeat: send PING if the connection has been idle too long
@@ -121,18 +125,12 @@ where
     pub async fn send_one(&mut self, frame: Bytes) -> io::Result<()> {
         trace!("[PUSH] Sending 1 frame");
 
-        if self.base.options.write_coalescing {
+        if self.base.options.write_coalescing && self.base.options.coalesce_window.is_none() {
             if self.base.encode_one_coalesced(&frame)? {
                 self.base.flush_send_buffer().await?;
             }
         } else {
-            let msg = std::slice::from_ref(&frame);
-            if self.base.should_vectored_write(msg) {
-                self.base.send_vectored(msg).await?;
-            } else {
-                self.base.encode_message_to_write_buf(msg)?;
-                self.base.write_from_buf().await?;
-            }
+            self.base.send_message(std::slice::from_ref(&frame)).await?;
         }
 
         if self.base.check_heartbeat()? {
@@ -193,16 +191,101 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
     }
 
-    /// Set socket options (builder-style).
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Get the endpoint this socket is connected/bound to, if available.
+    ///
+    /// Returns `None` if the socket was created from a raw stream.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> Option<&Endpoint> {
+        self.base.last_endpoint()
+    }
+
+    /// Check if the last received message has more frames coming.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.base.has_more()
+    }
+
+    /// Get the event state of the socket (`1` = POLLIN, `2` = POLLOUT).
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.base.events()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 }
 
@@ -220,7 +303,9 @@ impl PushSocket<TcpStream> {
     ) -> io::Result<Self> {
         // Configure TCP optimizations including keepalive
         crate::utils::configure_tcp_stream(&stream, &options, "PUSH")?;
-        Self::with_options(stream, options).await
+        let mut socket = Self::with_options(stream, options).await?;
+        socket.base.remember_peer_for_recovery();
+        Ok(socket)
     }
 
     /// Connect to a remote PUSH socket, storing the endpoint for automatic reconnection.
@@ -242,11 +327,11 @@ impl PushSocket<TcpStream> {
             &mut stream,
             SocketType::Push,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -257,20 +342,16 @@ impl PushSocket<TcpStream> {
 
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
         let mut base =
-            crate::base::SocketBase::with_endpoint(stream, SocketType::Push, endpoint, options);
+            crate::base::SocketBase::with_tcp_endpoint(stream, SocketType::Push, endpoint, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self { base })
     }
 
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.base.is_connected()
-    }
-
     /// Try to reconnect to the stored endpoint.
     pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.base.try_reconnect(SocketType::Push).await
+        self.base.try_reconnect(SocketType::Push).await.map(drop)
     }
 
     /// Send a message with automatic reconnection on network error.
@@ -285,6 +366,7 @@ impl PushSocket<TcpStream> {
 
         loop {
             if self.base.stream.is_none() {
+                self.base.check_redial_backoff()?;
                 if let Some(limit) = max
                     && attempts >= limit
                 {
@@ -298,7 +380,9 @@ impl PushSocket<TcpStream> {
                     "[PUSH] Stream disconnected, reconnecting (attempt {})",
                     attempts
                 );
-                self.try_reconnect().await?;
+                self.try_reconnect()
+                    .await
+                    .map_err(|e| self.base.redial_error(e))?;
             }
 
             match self.send(msg.clone()).await {
@@ -11,6 +11,10 @@
 //! - Transition back to `AwaitingRequest` after sending a reply
 //!
 //! Attempting to send before receiving, or receive before sending will return an error.
+//!
+//! The routing envelope of each request (every frame up to and including the
+//! empty delimiter) is saved by `recv()` and replayed in front of the reply, so
+//! the application only ever sees the request body.
 
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
@@ -20,9 +24,12 @@ use std::io;
 use tracing::{debug, trace};
 
 use crate::base::SocketBase;
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use monocoque_core::endpoint::Endpoint;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 
 /// REP socket state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -83,6 +90,9 @@ where
     frames: SmallVec<[Bytes; 4]>,
     /// Current state of the REP state machine
     state: RepState,
+    /// Routing envelope of the pending request, up to and including the
+    /// empty delimiter, replayed in front of the reply
+    envelope: SmallVec<[Bytes; 3]>,
 }
 
 impl<S> RepSocket<S>
@@ -118,11 +128,11 @@ where
             &mut stream,
             SocketType::Rep,
             None,
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -134,10 +144,13 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Rep, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
             state: RepState::AwaitingRequest,
+            envelope: SmallVec::new(),
         })
     }
 
@@ -193,8 +206,16 @@ where
                     crate::base::FrameResult::Data(more, payload) => {
                         self.frames.push(payload);
                         if !more {
+                            trace!("[REP] Received {} frames", self.frames.len());
+                            // Everything up to the empty delimiter is the
+                            // envelope (REQ correlation ID, ROUTER hops);
+                            // hide it from the application and restore it on
+                            // reply. A request without a delimiter has none.
+                            self.envelope.clear();
+                            if let Some(delim) = self.frames.iter().position(Bytes::is_empty) {
+                                self.envelope.extend(self.frames.drain(..=delim));
+                            }
                             let msg: Vec<Bytes> = self.frames.drain(..).collect();
-                            trace!("[REP] Received {} frames", msg.len());
                             self.state = RepState::ReadyToReply;
                             return Ok(Some(msg));
                         }
@@ -248,11 +269,18 @@ where
 
         trace!("[REP] Sending {} frames", msg.len());
 
-        // Encode message into write_buf (with CURVE encryption if active)
-        self.base.encode_message_to_write_buf(&msg)?;
+        let msg = if self.envelope.is_empty() {
+            msg
+        } else {
+            let mut framed = Vec::with_capacity(self.envelope.len() + msg.len());
+            framed.extend(self.envelope.drain(..));
+            framed.extend(msg);
+            framed
+        };
 
-        // Delegate to base for writing
-        self.base.write_from_buf().await?;
+        // Encode and write (or hold for the coalescing window), with CURVE
+        // encryption if active
+        self.base.send_message(&msg).await?;
 
         // Transition back to awaiting request
         self.state = RepState::AwaitingRequest;
@@ -261,13 +289,53 @@ where
         Ok(())
     }
 
+    /// Receive a request, answer it with `f` and send the reply.
+    ///
+    /// If the reply cannot be sent, the socket goes back to awaiting a
+    /// request, so the next `recv_send` can go ahead instead of failing on
+    /// the state machine; the requester never gets that reply.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receive or the send fails, and
+    /// `UnexpectedEof` if the connection closes before a request arrives.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque_zmtp::rep::RepSocket;
+    /// # async fn example(socket: &mut RepSocket) -> std::io::Result<()> {
+    /// // Echo server
+    /// loop {
+    ///     socket.recv_send(|request| request).await?;
+    /// }
+    /// # }
+    /// ```
+    pub async fn recv_send<F>(&mut self, f: F) -> io::Result<()>
+    where
+        F: FnOnce(Vec<Bytes>) -> Vec<Bytes>,
+    {
+        let Some(request) = self.recv().await? else {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed before a request arrived",
+            ));
+        };
+        let result = self.send(f(request)).await;
+        if result.is_err() {
+            self.envelope.clear();
+            self.state = RepState::AwaitingRequest;
+        }
+        result
+    }
+
     /// Close the socket gracefully.
     ///
-    /// REP sockets send immediately (no buffering), so this simply drops the socket.
-    /// The linger option is not applicable to REP sockets.
-    pub async fn close(self) -> io::Result<()> {
+    /// Shuts down the underlying stream. Nothing is buffered on this socket
+    /// type beyond a pending heartbeat reply, so linger rarely applies.
+    pub async fn close(mut self) -> io::Result<()> {
         trace!("[REP] Closing socket");
-        Ok(())
+        self.base.close().await
     }
 
     /// Get the current socket options.
@@ -275,14 +343,69 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
     }
 
-    /// Set socket options.
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 
     /// Get the current state of the REP socket.
@@ -42,13 +42,17 @@
 //! ```
 
 use crate::base::SocketBase;
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
 use monocoque_core::endpoint::Endpoint;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use smallvec::SmallVec;
+use std::collections::VecDeque;
 use std::io;
 use tracing::{debug, trace};
 
@@ -102,8 +106,9 @@ where
     state: ReqState,
     /// Request ID counter for correlation tracking
     request_id: u32,
-    /// Expected request ID for correlation mode (when req_correlate is enabled)
-    expected_request_id: Option<u32>,
+    /// Request IDs sent and not yet answered, oldest first (when req_correlate is enabled).
+    /// Holds at most `send_hwm` IDs; the oldest is forgotten to make room.
+    outstanding: VecDeque<u32>,
 }
 
 impl<S> ReqSocket<S>
@@ -153,17 +158,22 @@ where
     pub async fn with_options(mut stream: S, options: SocketOptions) -> io::Result<Self> {
         debug!("[REQ] Creating new direct REQ socket");
 
+        // Reject an invalid routing id before anything is sent
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+
         // Perform ZMTP handshake
         debug!("[REQ] Performing ZMTP handshake...");
         let handshake_result = perform_handshake_with_options(
             &mut stream,
             SocketType::Req,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -175,12 +185,14 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Req, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
             state: ReqState::Idle,
             request_id: 0,
-            expected_request_id: None,
+            outstanding: VecDeque::new(),
         })
     }
 
@@ -206,6 +218,13 @@ where
     /// # }
     /// ```
     pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        if self.base.auto_reconnect {
+            return self.send_with_reconnect(msg).await;
+        }
+        self.send_request(msg).await
+    }
+
+    async fn send_request(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
         // Check state machine (unless in relaxed mode)
         if !self.base.options.req_relaxed && self.state != ReqState::Idle {
             return Err(io::Error::new(
@@ -216,11 +235,19 @@ where
 
         trace!("[REQ] Sending {} frames", msg.len());
 
-        // If correlation is enabled, prepend request ID as an envelope
-        let frames_to_send = if self.base.options.req_correlate {
+        // REQ puts an empty delimiter in front of the body; libzmq REP drops
+        // requests without one. The correlation ID, if any, goes before it.
+        let mut frames_to_send = Vec::with_capacity(msg.len() + 2);
+        if self.base.options.req_correlate {
             // Increment request ID
             self.request_id = self.request_id.wrapping_add(1);
-            self.expected_request_id = Some(self.request_id);
+            // Bound the wait list: past `send_hwm` unanswered requests the
+            // oldest is given up on, and a late reply to it is rejected.
+            let hwm = self.base.options.send_hwm;
+            if hwm != 0 && self.outstanding.len() >= hwm {
+                self.outstanding.pop_front();
+            }
+            self.outstanding.push_back(self.request_id);
 
             trace!(
                 "[REQ] Correlation enabled, prepending request ID: {}",
@@ -228,19 +255,14 @@ where
             );
 
             // Prepend request ID as first frame (4 bytes, big-endian)
-            let mut correlated_msg = Vec::with_capacity(msg.len() + 1);
-            correlated_msg.push(Bytes::copy_from_slice(&self.request_id.to_be_bytes()));
-            correlated_msg.extend(msg);
-            correlated_msg
-        } else {
-            msg
-        };
-
-        // Encode message into write_buf (with CURVE encryption if active)
-        self.base.encode_message_to_write_buf(&frames_to_send)?;
+            frames_to_send.push(Bytes::copy_from_slice(&self.request_id.to_be_bytes()));
+        }
+        frames_to_send.push(Bytes::new());
+        frames_to_send.extend(msg);
 
-        // Delegate to base for writing
-        self.base.write_from_buf().await?;
+        // Encode and write (or hold for the coalescing window), with CURVE
+        // encryption if active
+        self.base.send_message(&frames_to_send).await?;
 
         // Transition to awaiting reply (unless already there in relaxed mode)
         self.state = ReqState::AwaitingReply;
@@ -254,6 +276,13 @@ where
     /// This blocks until a reply is received. You must call this after `send()`
     /// before calling `send()` again.
     ///
+    /// With `req_relaxed` and `req_correlate` both set, several requests may be
+    /// outstanding at once. Replies are returned in the order they arrive, each
+    /// accepted if its request ID matches any outstanding request, and the
+    /// socket stays awaiting replies until every request has been answered.
+    /// At most `send_hwm` requests are tracked: sending more forgets the
+    /// oldest, and a reply to a forgotten request is an `InvalidData` error.
+    ///
     /// # Returns
     ///
     /// - `Ok(Some(msg))` - Received a multipart message
@@ -278,6 +307,13 @@ where
     /// # }
     /// ```
     pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        if self.base.auto_reconnect {
+            return self.recv_or_reconnect().await;
+        }
+        self.recv_reply().await
+    }
+
+    async fn recv_reply(&mut self) -> io::Result<Option<Vec<Bytes>>> {
         // Check state machine
         if self.state != ReqState::AwaitingReply {
             return Err(io::Error::new(
@@ -308,7 +344,7 @@ where
                             trace!("[REQ] Received {} frames", msg.len());
 
                             // If correlation is enabled, validate request ID
-                            let validated_msg = if self.base.options.req_correlate {
+                            let mut validated_msg = if self.base.options.req_correlate {
                                 if msg.is_empty() {
                                     return Err(io::Error::new(
                                         io::ErrorKind::InvalidData,
@@ -337,19 +373,21 @@ where
 
                                 trace!("[REQ] Received correlation ID: {}", received_id);
 
-                                // Validate against expected ID
-                                if let Some(expected) = self.expected_request_id {
-                                    if received_id != expected {
-                                        return Err(io::Error::new(
-                                            io::ErrorKind::InvalidData,
-                                            format!(
-                                                "Request ID mismatch: expected {}, got {}",
-                                                expected, received_id
-                                            ),
-                                        ));
-                                    }
-                                    trace!("[REQ] Correlation ID validated successfully");
-                                }
+                                // Match against any outstanding request
+                                let Some(pos) =
+                                    self.outstanding.iter().position(|&id| id == received_id)
+                                else {
+                                    let detail = match self.outstanding.as_slices() {
+                                        ([expected], []) => format!("expected {expected}"),
+                                        _ => format!("expected one of {:?}", self.outstanding),
+                                    };
+                                    return Err(io::Error::new(
+                                        io::ErrorKind::InvalidData,
+                                        format!("Request ID mismatch: {detail}, got {received_id}"),
+                                    ));
+                                };
+                                self.outstanding.remove(pos);
+                                trace!("[REQ] Correlation ID validated successfully");
 
                                 // Strip correlation frame and return the remaining owned frames.
                                 let mut msg = msg;
@@ -358,9 +396,15 @@ where
                             } else {
                                 msg
                             };
-
-                            self.state = ReqState::Idle;
-                            self.expected_request_id = None;
+                            // Drop the delimiter the REP echoed back.
+                            if validated_msg.first().is_some_and(Bytes::is_empty) {
+                                validated_msg.remove(0);
+                            }
+
+                            // Replies to other outstanding requests are still due
+                            if self.outstanding.is_empty() {
+                                self.state = ReqState::Idle;
+                            }
                             return Ok(Some(validated_msg));
                         }
                     }
@@ -373,6 +417,7 @@ where
                 // EOF - connection closed
                 trace!("[REQ] Connection closed");
                 self.state = ReqState::Idle;
+                self.outstanding.clear();
                 return Ok(None);
             }
             if self.base.check_heartbeat()? {
@@ -382,6 +427,164 @@ where
         }
     }
 
+    /// Send `request` and wait for its reply.
+    ///
+    /// The same as `send()` followed by `recv()`, except that a failed or
+    /// timed-out receive, or a connection closed before the reply, puts the
+    /// socket back in `Idle`, so the next `send_recv` can go ahead instead of
+    /// failing on the state machine. A reply to the abandoned request that
+    /// arrives later would then be taken for the next one's; set
+    /// `req_correlate` to have it rejected instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the send or the receive fails, and
+    /// `UnexpectedEof` if the connection closes before the reply arrives.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque_zmtp::req::ReqSocket;
+    /// # use bytes::Bytes;
+    /// # async fn example(socket: &mut ReqSocket) -> std::io::Result<()> {
+    /// let reply = socket.send_recv(vec![Bytes::from("REQUEST")]).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_recv(&mut self, request: Vec<Bytes>) -> io::Result<Vec<Bytes>> {
+        self.send(request).await?;
+        let err = match self.recv().await {
+            Ok(Some(reply)) => return Ok(reply),
+            Ok(None) => io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed before the reply arrived",
+            ),
+            Err(e) => e,
+        };
+        self.state = ReqState::Idle;
+        self.outstanding.clear();
+        Err(err)
+    }
+
+    /// Try to reconnect to the stored endpoint.
+    ///
+    /// On success the socket starts over in `Idle`: a reply still owed on
+    /// the old connection can no longer arrive.
+    pub async fn try_reconnect(&mut self) -> io::Result<()> {
+        self.base.try_reconnect(SocketType::Req).await?;
+        self.frames.clear();
+        self.state = ReqState::Idle;
+        self.outstanding.clear();
+        Ok(())
+    }
+
+    /// Reconnect within `max_reconnect_attempts` and start over in `Idle`.
+    async fn reconnect(&mut self, attempts: &mut u32) -> io::Result<()> {
+        self.base
+            .reconnect_within_budget(SocketType::Req, attempts)
+            .await?;
+        self.frames.clear();
+        self.state = ReqState::Idle;
+        self.outstanding.clear();
+        Ok(())
+    }
+
+    /// `recv` for sockets from `connect_with_reconnect`.
+    ///
+    /// A reply lost with the connection cannot be recovered, and resending
+    /// the request is not always safe, so after reconnecting this reports
+    /// `ConnectionReset` with the socket back in `Idle`.
+    async fn recv_or_reconnect(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        let err = match self.recv_reply().await {
+            Ok(Some(msg)) => return Ok(Some(msg)),
+            Ok(None) => None,
+            Err(e) if self.base.lost_connection(&e) => Some(e),
+            Err(e) => return Err(e),
+        };
+        debug!(
+            "[REQ] Connection lost awaiting reply ({:?}), reconnecting",
+            err
+        );
+        self.base.stream = None;
+        self.reconnect(&mut 0).await?;
+        Err(io::Error::new(
+            io::ErrorKind::ConnectionReset,
+            "connection lost before the reply arrived; reconnected, send the request again",
+        ))
+    }
+
+    /// Receive a reply with automatic reconnection on EOF or network error.
+    ///
+    /// If the socket was created with `connect()` and stores an endpoint, this
+    /// method loops: on EOF or broken-pipe it clears the stream and calls
+    /// `try_reconnect()` (which applies exponential backoff), then retries `recv()`.
+    ///
+    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
+    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        let mut attempts = 0u32;
+
+        loop {
+            if self.base.stream.is_none() {
+                trace!("[REQ] Stream disconnected, reconnecting");
+                self.reconnect(&mut attempts).await?;
+            }
+
+            match self.recv_reply().await {
+                Ok(Some(msg)) => return Ok(Some(msg)),
+                // EOF: read_raw() already set stream = None
+                Ok(None) => {
+                    debug!("[REQ] EOF on recv, will reconnect");
+                    self.state = ReqState::Idle;
+                    self.outstanding.clear();
+                }
+                Err(e) if self.base.lost_connection(&e) => {
+                    debug!("[REQ] Connection error on recv ({}), will reconnect", e);
+                    self.base.stream = None;
+                    self.state = ReqState::Idle;
+                    self.outstanding.clear();
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Send a request with automatic reconnection on network error.
+    ///
+    /// On BrokenPipe / ConnectionReset, `write_from_buf()` already sets
+    /// `stream = None`, so the next loop iteration reconnects automatically.
+    /// On reconnect the REQ state machine is reset to `Idle`.
+    ///
+    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
+    pub async fn send_with_reconnect(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        let mut attempts = 0u32;
+
+        loop {
+            if self.base.stream.is_none() {
+                trace!("[REQ] Stream disconnected, reconnecting");
+                if self.base.options.immediate {
+                    // One redial, without waiting: fail rather than hold the request.
+                    self.base.check_redial_backoff()?;
+                    self.try_reconnect()
+                        .await
+                        .map_err(|e| self.base.redial_error(e))?;
+                } else {
+                    self.reconnect(&mut attempts).await?;
+                }
+            }
+
+            match self.send_request(msg.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(_) if self.base.stream.is_none() => {
+                    // write_from_buf set stream = None → network error, retry
+                    debug!("[REQ] Send failed (stream lost), will reconnect");
+                    self.state = ReqState::Idle;
+                    self.outstanding.clear();
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
     /// Get the current state of the REQ socket.
     ///
     /// This is primarily for debugging and testing.
@@ -401,11 +604,11 @@ where
 
     /// Close the socket gracefully.
     ///
-    /// REQ sockets send immediately (no buffering), so this simply drops the socket.
-    /// The linger option is not applicable to REQ sockets.
-    pub async fn close(self) -> io::Result<()> {
+    /// Shuts down the underlying stream. Nothing is buffered on this socket
+    /// type beyond a pending heartbeat reply, so linger rarely applies.
+    pub async fn close(mut self) -> io::Result<()> {
         trace!("[REQ] Closing socket");
-        Ok(())
+        self.base.close().await
     }
 
     /// Get a reference to the socket options.
@@ -414,16 +617,69 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
     }
 
-    /// Set socket options (builder-style).
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 
     /// Get the socket type.
@@ -539,11 +795,39 @@ impl ReqSocket<TcpStream> {
         Self::connect_with_options(addr, SocketOptions::default()).await
     }
 
+    /// Connect and keep the connection up on its own.
+    ///
+    /// `send` on the returned socket reconnects with backoff when the
+    /// connection is down. If the connection drops while `recv` awaits a
+    /// reply, `recv` reconnects and returns `ConnectionReset`; the socket is
+    /// back in `Idle` and the request can be sent again.
+    pub async fn connect_with_reconnect(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+    ) -> io::Result<Self> {
+        Self::connect_with_reconnect_options(addr, SocketOptions::default()).await
+    }
+
+    /// [`connect_with_reconnect`](Self::connect_with_reconnect) with custom
+    /// options.
+    pub async fn connect_with_reconnect_options(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+        options: SocketOptions,
+    ) -> io::Result<Self> {
+        let mut socket = Self::connect_with_options(addr, options).await?;
+        socket.base.auto_reconnect = true;
+        Ok(socket)
+    }
+
     /// Connect with custom options, storing the endpoint for reconnection.
     pub async fn connect_with_options(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         options: SocketOptions,
     ) -> io::Result<Self> {
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+        let started_at = std::time::SystemTime::now();
+        let started = std::time::Instant::now();
         let stream = TcpStream::connect(addr).await?;
         let peer_addr = stream.peer_addr()?;
         crate::utils::configure_tcp_stream(&stream, &options, "REQ")?;
@@ -553,11 +837,11 @@ impl ReqSocket<TcpStream> {
             &mut stream,
             SocketType::Req,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -568,131 +852,44 @@ impl ReqSocket<TcpStream> {
 
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
         let mut base =
-            crate::base::SocketBase::with_endpoint(stream, SocketType::Req, endpoint, options);
+            crate::base::SocketBase::with_tcp_endpoint(stream, SocketType::Req, endpoint, options);
+        base.record_initial_connect(started_at, started);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             frames: SmallVec::new(),
             state: ReqState::Idle,
             request_id: 0,
-            expected_request_id: None,
+            outstanding: VecDeque::new(),
         })
     }
 
-    /// Check if the socket is currently connected.
+    /// The latest connection attempts (initial connect and reconnects),
+    /// oldest first, with their timestamps and errors.
     #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.base.is_connected()
+    pub const fn connect_history(&self) -> &monocoque_core::reconnect::ConnectHistory {
+        self.base.connect_history()
     }
 
-    /// Try to reconnect to the stored endpoint.
-    pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.base.try_reconnect(SocketType::Req).await
+    /// Multi-line dump of the socket's endpoint, state, non-default options,
+    /// buffer usage and connection history, for logs and bug reports.
+    pub fn diagnostics(&self) -> String {
+        self.base.diagnostics()
     }
 
-    /// Receive a reply with automatic reconnection on EOF or network error.
-    ///
-    /// If the socket was created with `connect()` and stores an endpoint, this
-    /// method loops: on EOF or broken-pipe it clears the stream and calls
-    /// `try_reconnect()` (which applies exponential backoff), then retries `recv()`.
-    ///
-    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
-    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        let max = self.base.options.max_reconnect_attempts;
-        let mut attempts = 0u32;
-
-        loop {
-            if self.base.stream.is_none() {
-                if let Some(limit) = max
-                    && attempts >= limit
-                {
-                    return Err(io::Error::new(
-                        io::ErrorKind::NotConnected,
-                        format!("Max {} reconnection attempts exceeded", limit),
-                    ));
-                }
-                attempts += 1;
-                trace!(
-                    "[REQ] Stream disconnected, reconnecting (attempt {})",
-                    attempts
-                );
-                self.state = ReqState::Idle;
-                self.expected_request_id = None;
-                self.try_reconnect().await?;
-            }
-
-            match self.recv().await {
-                Ok(Some(msg)) => return Ok(Some(msg)),
-                // EOF: read_raw() already set stream = None
-                Ok(None) => {
-                    debug!("[REQ] EOF on recv, will reconnect");
-                    self.state = ReqState::Idle;
-                    self.expected_request_id = None;
-                }
-                Err(e) => {
-                    if self.base.stream.is_none()
-                        || matches!(
-                            e.kind(),
-                            io::ErrorKind::ConnectionReset
-                                | io::ErrorKind::ConnectionAborted
-                                | io::ErrorKind::BrokenPipe
-                                | io::ErrorKind::UnexpectedEof
-                        )
-                    {
-                        debug!("[REQ] Connection error on recv ({}), will reconnect", e);
-                        self.base.stream = None;
-                        self.state = ReqState::Idle;
-                        self.expected_request_id = None;
-                    } else {
-                        return Err(e);
-                    }
-                }
-            }
-        }
+    /// The same state as [`diagnostics`](Self::diagnostics) as a structured
+    /// [`DiagnosticsSnapshot`](crate::DiagnosticsSnapshot), serializable with
+    /// the `serde` feature.
+    pub fn snapshot(&self) -> crate::DiagnosticsSnapshot {
+        self.base.snapshot(SocketType::Req)
     }
 
-    /// Send a request with automatic reconnection on network error.
-    ///
-    /// On BrokenPipe / ConnectionReset, `write_from_buf()` already sets
-    /// `stream = None`, so the next loop iteration reconnects automatically.
-    /// On reconnect the REQ state machine is reset to `Idle`.
-    ///
-    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
-    pub async fn send_with_reconnect(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        let max = self.base.options.max_reconnect_attempts;
-        let mut attempts = 0u32;
-
-        loop {
-            if self.base.stream.is_none() {
-                if let Some(limit) = max
-                    && attempts >= limit
-                {
-                    return Err(io::Error::new(
-                        io::ErrorKind::NotConnected,
-                        format!("Max {} reconnection attempts exceeded", limit),
-                    ));
-                }
-                attempts += 1;
-                trace!(
-                    "[REQ] Stream disconnected, reconnecting (attempt {})",
-                    attempts
-                );
-                self.state = ReqState::Idle;
-                self.expected_request_id = None;
-                self.try_reconnect().await?;
-            }
-
-            match self.send(msg.clone()).await {
-                Ok(()) => return Ok(()),
-                Err(_) if self.base.stream.is_none() => {
-                    // write_from_buf set stream = None → network error, retry
-                    debug!("[REQ] Send failed (stream lost), will reconnect");
-                    self.state = ReqState::Idle;
-                    self.expected_request_id = None;
-                }
-                Err(e) => return Err(e),
-            }
-        }
+    /// Number of reconnection attempts made since the connection was lost.
+    #[inline]
+    pub fn reconnect_attempt(&self) -> u32 {
+        self.base.reconnect_attempt()
     }
 }
 
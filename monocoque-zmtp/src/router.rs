@@ -10,19 +10,86 @@
 
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
-use monocoque_core::options::SocketOptions;
-use monocoque_core::rt::TcpStream;
+use monocoque_core::error::TrySendError;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::rt::{TcpListener, TcpStream};
 use smallvec::SmallVec;
+use std::future::Future;
 use std::io;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{debug, trace};
 
 use crate::base::SocketBase;
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::frame_reader::FrameReader;
+use crate::router_hub::RouterHubSocket;
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use monocoque_core::endpoint::Endpoint;
 
 static PEER_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
 
+/// Generate an identity for a peer that did not announce one.
+///
+/// The leading zero byte keeps generated ids out of the space applications
+/// use for explicit routing ids, as libzmq does.
+pub(crate) fn auto_identity() -> Bytes {
+    let peer_id = PEER_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    Bytes::from(format!("\0peer-{}", peer_id))
+}
+
+/// Build the `[identity, "", frames...]` envelope sent by `send_to`.
+pub(crate) fn envelope(identity: &[u8], frames: Vec<Bytes>) -> Vec<Bytes> {
+    let mut msg = Vec::with_capacity(frames.len() + 2);
+    msg.push(Bytes::copy_from_slice(identity));
+    msg.push(Bytes::new());
+    msg.extend(frames);
+    msg
+}
+
+/// Split a received `[identity, (""), body...]` into the identity and the
+/// body, dropping the delimiter when the peer sent one.
+pub(crate) fn open_envelope(msg: Vec<Bytes>) -> (Bytes, Vec<Bytes>) {
+    let mut frames = msg.into_iter();
+    let identity = frames.next().unwrap_or_default();
+    let mut body: Vec<Bytes> = frames.collect();
+    if body.first().is_some_and(Bytes::is_empty) {
+        body.remove(0);
+    }
+    (identity, body)
+}
+
+/// Error for a message addressed to an identity with no connected peer
+/// under `router_mandatory`, matching libzmq's `EHOSTUNREACH`.
+pub(crate) fn no_route(identity: &Bytes) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::HostUnreachable,
+        format!("ROUTER mandatory: no route for identity {:?}", identity),
+    )
+}
+
+/// Determine peer identity (priority order):
+/// 1. connect_routing_id (explicitly assigned by ROUTER)
+/// 2. peer_identity from handshake (peer's self-reported identity)
+/// 3. Auto-generate
+fn choose_identity(options: &mut SocketOptions, reported: Option<Bytes>) -> Bytes {
+    if let Some(id) = options.connect_routing_id.take() {
+        // Use the explicitly assigned identity
+        debug!("[ROUTER] Using assigned identity: {:?}", id);
+        id
+    } else if let Some(id) = reported {
+        // Use peer's self-reported identity
+        debug!("[ROUTER] Using peer-reported identity: {:?}", id);
+        id
+    } else {
+        // Auto-generate identity using counter
+        let id = auto_identity();
+        debug!("[ROUTER] Auto-generated identity: {:?}", id);
+        id
+    }
+}
+
 /// Direct-stream ROUTER socket.
 pub struct RouterSocket<S = TcpStream>
 where
@@ -34,9 +101,6 @@ where
     frames: SmallVec<[Bytes; 4]>,
     /// Peer identity (auto-generated or from handshake)
     peer_identity: Bytes,
-    /// When true, sending to an unknown identity returns an error instead of
-    /// silently dropping the message (ZMQ_ROUTER_MANDATORY).
-    router_mandatory: bool,
 }
 
 impl<S> RouterSocket<S>
@@ -90,31 +154,13 @@ where
             &mut stream,
             SocketType::Router,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
-
-        // Determine peer identity (priority order):
-        // 1. connect_routing_id (explicitly assigned by ROUTER)
-        // 2. peer_identity from handshake (peer's self-reported identity)
-        // 3. Auto-generate
-        let peer_identity = if let Some(id) = options.connect_routing_id.take() {
-            // Use the explicitly assigned identity
-            debug!("[ROUTER] Using assigned identity: {:?}", id);
-            id
-        } else if let Some(id) = handshake_result.peer_identity {
-            // Use peer's self-reported identity
-            debug!("[ROUTER] Using peer-reported identity: {:?}", id);
-            id
-        } else {
-            // Auto-generate identity using counter
-            let peer_id = PEER_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
-            let id = Bytes::from(format!("\0peer-{}", peer_id));
-            debug!("[ROUTER] Auto-generated identity: {:?}", id);
-            id
-        };
+        .map_err(handshake_error)?;
+
+        let peer_identity = choose_identity(&mut options, handshake_result.peer_identity);
 
         debug!(
             peer_identity = ?peer_identity,
@@ -124,21 +170,138 @@ where
 
         debug!("[ROUTER] Socket initialized");
 
-        let router_mandatory = options.router_mandatory;
         let mut base = SocketBase::new(stream, SocketType::Router, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
+        base.sequenced = handshake_result.sequenced;
         Ok(Self {
             base,
             frames: SmallVec::new(),
             peer_identity,
-            router_mandatory,
         })
     }
 
     /// Receive a message with sender identity prepended.
     ///
     /// Returns a multipart message where the first frame is the sender identity.
+    /// On a socket from `connect_with_reconnect`, a lost connection is
+    /// re-established and receiving continues.
     pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        self.recover_if_poisoned().await;
+        if !self.base.auto_reconnect {
+            return self.recv_routed().await;
+        }
+        let mut attempts = 0u32;
+        loop {
+            if self.base.stream.is_none() {
+                self.reconnect(&mut attempts).await?;
+            }
+            match self.recv_routed().await {
+                Ok(Some(msg)) => return Ok(Some(msg)),
+                // EOF: read_raw() already set stream = None
+                Ok(None) => debug!("[ROUTER] EOF on recv, will reconnect"),
+                Err(e) if self.base.lost_connection(&e) => {
+                    debug!("[ROUTER] Connection error on recv ({}), will reconnect", e);
+                    self.base.stream = None;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Receive a message as `(identity, body)`, with the delimiter frame
+    /// stripped when the peer sent one.
+    ///
+    /// Returns `Ok(None)` as [`recv`](Self::recv) does.
+    pub async fn recv_from(&mut self) -> io::Result<Option<(Bytes, Vec<Bytes>)>> {
+        Ok(self.recv().await?.map(open_envelope))
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        }))
+    }
+
+    /// Try to reconnect to the stored endpoint.
+    ///
+    /// A fresh handshake that reports an identity replaces the peer's
+    /// identity; otherwise the previous one is kept.
+    pub async fn try_reconnect(&mut self) -> io::Result<()> {
+        if let Some(id) = self.base.try_reconnect(SocketType::Router).await? {
+            self.peer_identity = id;
+        }
+        self.frames.clear();
+        Ok(())
+    }
+
+    /// Reconnect a poisoned socket before the next operation when
+    /// [`SocketOptions::auto_recover`] allows it. A failed attempt leaves the
+    /// socket poisoned, so the operation still fails with `BrokenPipe`.
+    async fn recover_if_poisoned(&mut self) {
+        if !self.base.should_auto_recover() {
+            return;
+        }
+        // Boxed: the handshake may call back into a socket send (ZAP).
+        match Box::pin(self.try_reconnect()).await {
+            Ok(()) => debug!("[ROUTER] Recovered poisoned socket by reconnecting"),
+            Err(e) => debug!("[ROUTER] Automatic recovery failed: {}", e),
+        }
+    }
+
+    /// Reconnect within `max_reconnect_attempts`.
+    async fn reconnect(&mut self, attempts: &mut u32) -> io::Result<()> {
+        trace!("[ROUTER] Stream disconnected, reconnecting");
+        if let Some(id) = self
+            .base
+            .reconnect_within_budget(SocketType::Router, attempts)
+            .await?
+        {
+            self.peer_identity = id;
+        }
+        self.frames.clear();
+        Ok(())
+    }
+
+    /// Receive the next frame as a [`FrameReader`], streaming frames larger
+    /// than the `stream_threshold` option instead of buffering them.
+    ///
+    /// The ROUTER counterpart to
+    /// [`DealerSocket::recv_frame_streaming`](crate::DealerSocket::recv_frame_streaming).
+    /// Only body frames are returned; the sender is
+    /// [`peer_identity()`](Self::peer_identity), which `recv()` would have
+    /// put in front of the message. Frames of the interrupted message that
+    /// `recv()` had already decoded come first. Keep calling until a frame
+    /// without [`more`](FrameReader::more) ends the message.
+    ///
+    /// Returns `Ok(None)` when the connection closed between frames.
+    pub async fn recv_frame_streaming(&mut self) -> io::Result<Option<FrameReader<'_, S>>> {
+        if !self.frames.is_empty() {
+            return Ok(Some(FrameReader::buffered(
+                &mut self.base,
+                self.frames.remove(0),
+                true,
+            )));
+        }
+        self.base.recv_frame_streaming().await
+    }
+
+    async fn recv_routed(&mut self) -> io::Result<Option<Vec<Bytes>>> {
         trace!("[ROUTER] Waiting for message");
 
         // Read from stream until we have a complete message
@@ -155,7 +318,11 @@ where
                     crate::base::FrameResult::Data(more, payload) => {
                         self.frames.push(payload);
                         if !more {
-                            let msg: Vec<Bytes> = self.frames.drain(..).collect();
+                            let mut msg: Vec<Bytes> = self.frames.drain(..).collect();
+                            if !self.base.admit(&mut msg)? {
+                                trace!("[ROUTER] Dropped a duplicate message");
+                                continue;
+                            }
                             trace!("[ROUTER] Received {} frames", msg.len());
 
                             // Prepend peer identity to the message
@@ -186,13 +353,54 @@ where
     ///
     /// The first frame of `msg` is the routing identity of the destination peer.
     /// For this single-peer implementation, the identity must match the connected
-    /// peer's identity or the message is silently dropped (or an error is returned
-    /// if `router_mandatory` mode is enabled).
+    /// peer's identity or the message is silently dropped (or `HostUnreachable`
+    /// is returned if `router_mandatory` mode is enabled).
     ///
     /// Encodes and sends the message in a single I/O operation.
     /// For high-throughput scenarios, consider using `send_buffered()` + `flush()`
-    /// to batch multiple messages.
+    /// to batch multiple messages. On a socket from `connect_with_reconnect`,
+    /// a lost connection is re-established and the send retried.
     pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        // Retries keep the sequence number so a `dedupe_window` receiver
+        // can drop the copy.
+        let seq = self.base.next_sequence();
+        self.recover_if_poisoned().await;
+        if !self.base.auto_reconnect {
+            return self.send_routed(seq, &msg).await;
+        }
+        let mut attempts = 0u32;
+        loop {
+            if self.base.stream.is_none() {
+                if self.base.options.immediate {
+                    // One redial, without waiting: fail rather than hold the message.
+                    self.base.check_redial_backoff()?;
+                    self.try_reconnect()
+                        .await
+                        .map_err(|e| self.base.redial_error(e))?;
+                } else {
+                    self.reconnect(&mut attempts).await?;
+                }
+            }
+            match self.send_routed(seq, &msg).await {
+                Err(e) if self.base.lost_connection(&e) => {
+                    debug!("[ROUTER] Send failed ({}), will reconnect", e);
+                    self.base.stream = None;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Send `frames` to the peer with routing id `identity`.
+    ///
+    /// Sends `[identity, "", frames...]` through [`send`](Self::send), so
+    /// the peer gets the empty delimiter a REQ socket expects in front of
+    /// the body.
+    pub async fn send_to(&mut self, identity: &[u8], frames: Vec<Bytes>) -> io::Result<()> {
+        self.send(envelope(identity, frames)).await
+    }
+
+    async fn send_routed(&mut self, seq: u64, msg: &[Bytes]) -> io::Result<()> {
         trace!("[ROUTER] Sending {} frames", msg.len());
 
         if msg.is_empty() {
@@ -205,11 +413,8 @@ where
         // First frame is the routing identity. Validate it against our connected peer.
         let identity = &msg[0];
         if *identity != self.peer_identity {
-            if self.router_mandatory {
-                return Err(io::Error::new(
-                    io::ErrorKind::NotFound,
-                    format!("ROUTER mandatory: no route for identity {:?}", identity),
-                ));
+            if self.base.options.router_mandatory {
+                return Err(no_route(identity));
             }
             // Non-mandatory: silently drop messages to unknown peers
             trace!(
@@ -222,11 +427,9 @@ where
         // Skip the identity frame and send the rest
         let frames_to_send = &msg[1..];
 
-        // Encode message into write_buf (with CURVE encryption if active)
-        self.base.encode_message_to_write_buf(frames_to_send)?;
-
-        // Delegate to base for writing
-        self.base.write_from_buf().await?;
+        // Encode and write (or hold for the coalescing window), with CURVE
+        // encryption if active
+        self.base.send_sequenced(seq, frames_to_send).await?;
 
         trace!("[ROUTER] Message sent successfully");
         Ok(())
@@ -234,14 +437,14 @@ where
 
     /// Set ROUTER mandatory mode.
     ///
-    /// When enabled, sending to an unknown peer identity returns a `NotFound` error
-    /// instead of silently dropping the message.
+    /// When enabled, sending to an unknown peer identity returns a
+    /// `HostUnreachable` error (libzmq's `EHOSTUNREACH`) instead of silently
+    /// dropping the message.
     ///
     /// # ZeroMQ Compatibility
     ///
     /// Corresponds to `ZMQ_ROUTER_MANDATORY` (33) option.
     pub fn set_router_mandatory(&mut self, mandatory: bool) {
-        self.router_mandatory = mandatory;
         self.base.options.router_mandatory = mandatory;
     }
 
@@ -265,11 +468,8 @@ where
 
         let identity = &msg[0];
         if *identity != self.peer_identity {
-            if self.router_mandatory {
-                return Err(io::Error::new(
-                    io::ErrorKind::NotFound,
-                    format!("ROUTER mandatory: no route for identity {:?}", identity),
-                ));
+            if self.base.options.router_mandatory {
+                return Err(no_route(identity));
             }
             trace!(
                 "[ROUTER] Dropping buffered message to unknown identity {:?}",
@@ -289,10 +489,30 @@ where
         }
 
         let frames_to_send = &msg[1..];
-        self.base.encode_message_to_send_buf(frames_to_send)?;
+        let seq = self.base.next_sequence();
+        self.base.buffer_sequenced(seq, frames_to_send)?;
         Ok(())
     }
 
+    /// Queue a message without ever suspending.
+    ///
+    /// The non-blocking counterpart to [`send_buffered`](Self::send_buffered):
+    /// the message goes out with the next [`flush()`](Self::flush), and
+    /// routing works the same way. See
+    /// [`DealerSocket::try_send`](crate::DealerSocket::try_send) for why no
+    /// write is attempted here.
+    ///
+    /// # Errors
+    ///
+    /// [`TrySendError::Full`] at `send_hwm` and [`TrySendError::WouldBlock`]
+    /// without a connection, both handing the message back.
+    /// [`TrySendError::Io`] for an empty message, an unknown identity under
+    /// `router_mandatory`, or a socket that needs reconnecting.
+    pub fn try_send(&mut self, msg: Vec<Bytes>) -> Result<(), TrySendError> {
+        let msg = self.base.try_send_ready(msg)?;
+        self.send_buffered(msg).map_err(TrySendError::Io)
+    }
+
     /// Flush all buffered messages to the network.
     pub async fn flush(&mut self) -> io::Result<()> {
         trace!("[ROUTER] Flushing {} bytes", self.base.send_buffer.len());
@@ -314,11 +534,8 @@ where
             }
             let identity = &msg[0];
             if *identity != self.peer_identity {
-                if self.router_mandatory {
-                    return Err(io::Error::new(
-                        io::ErrorKind::NotFound,
-                        format!("ROUTER mandatory: no route for identity {:?}", identity),
-                    ));
+                if self.base.options.router_mandatory {
+                    return Err(no_route(identity));
                 }
                 trace!(
                     "[ROUTER] Skipping batch message to unknown identity {:?}",
@@ -327,7 +544,8 @@ where
                 continue;
             }
             let frames_to_send = &msg[1..];
-            self.base.encode_message_to_send_buf(frames_to_send)?;
+            let seq = self.base.next_sequence();
+            self.base.buffer_sequenced(seq, frames_to_send)?;
         }
 
         self.flush().await
@@ -339,6 +557,31 @@ where
         self.base.send_buffer.len()
     }
 
+    /// Get the current capacities of the socket's userspace buffers.
+    ///
+    /// Write buffers grow to fit the largest burst and are shrunk back to
+    /// `write_buffer_size` after flushes once traffic returns to normal; see
+    /// `SocketOptions::write_buffer_shrink_factor`.
+    #[inline]
+    pub fn buffer_stats(&self) -> monocoque_core::config::BufferStats {
+        self.base.buffer_stats()
+    }
+
+    /// Structured diagnostics of the socket, serializable with the `serde`
+    /// feature. While connected, `peers` lists the one peer with the
+    /// messages buffered for it.
+    pub fn snapshot(&self) -> crate::DiagnosticsSnapshot {
+        let mut snapshot = self.base.snapshot(SocketType::Router);
+        if self.base.is_connected() {
+            snapshot.peers.push(crate::diagnostics::PeerRecord {
+                identity: format!("{:?}", self.peer_identity),
+                principal: None,
+                pending: self.base.buffered_messages(),
+            });
+        }
+        snapshot
+    }
+
     /// Close the socket gracefully, respecting the linger timeout.
     ///
     /// This method attempts to flush any buffered send data before closing.
@@ -348,49 +591,12 @@ where
     /// - `Some(duration)`: Try to flush buffered data within the timeout
     /// - `None`: Block indefinitely until all data is sent (default libzmq behavior)
     pub async fn close(mut self) -> io::Result<()> {
-        let linger = self.base.options.linger;
-
-        if self.base.send_buffer.is_empty() {
-            trace!("[ROUTER] No buffered data, closing immediately");
-            return Ok(());
-        }
-
         trace!(
             "[ROUTER] Closing with {} bytes buffered, linger={:?}",
             self.base.send_buffer.len(),
-            linger
+            self.base.options.linger
         );
-
-        match linger {
-            Some(dur) if dur.is_zero() => {
-                debug!(
-                    "[ROUTER] Linger=0, discarding {} bytes",
-                    self.base.send_buffer.len()
-                );
-                Ok(())
-            }
-            Some(dur) => {
-                use monocoque_core::rt::timeout;
-                match timeout(dur, self.flush()).await {
-                    Ok(Ok(())) => {
-                        debug!("[ROUTER] Successfully flushed before close");
-                        Ok(())
-                    }
-                    Ok(Err(e)) => {
-                        debug!("[ROUTER] Flush failed: {}", e);
-                        Err(e)
-                    }
-                    Err(_) => {
-                        debug!("[ROUTER] Linger timeout expired, closing anyway");
-                        Ok(())
-                    }
-                }
-            }
-            None => {
-                debug!("[ROUTER] Linger=indefinite, flushing all buffered data");
-                self.flush().await
-            }
-        }
+        self.base.close().await
     }
 
     /// Get a reference to the socket options.
@@ -399,16 +605,96 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Get the number of currently buffered messages.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub const fn buffered_messages(&self) -> usize {
+        self.base.buffered_messages()
     }
 
-    /// Set socket options (builder-style).
+    /// Check if the socket is currently connected.
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Routing identities of the connected peers: the single peer's identity
+    /// while connected, otherwise empty.
+    pub fn connected_peers(&self) -> Vec<Bytes> {
+        if self.is_connected() {
+            vec![self.peer_identity.clone()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Check whether the peer with routing identity `id` is connected.
+    pub fn is_peer_connected(&self, id: &[u8]) -> bool {
+        self.is_connected() && self.peer_identity == id
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` option.
+    #[inline]
+    pub const fn duplicates_dropped(&self) -> u64 {
+        self.base.duplicates_dropped()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 
     /// Get the socket type.
@@ -479,6 +765,32 @@ where
 
 // Specialized implementation for TCP streams to enable TCP_NODELAY
 impl RouterSocket<TcpStream> {
+    /// Serve every peer that connects to `listener` from one ROUTER.
+    ///
+    /// A `RouterSocket` owns a single connection; this returns a multi-peer
+    /// [`RouterHubSocket`] plus the future that accepts connections and routes
+    /// messages. See [`RouterHubSocket::accept_loop`].
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use monocoque_zmtp::router::RouterSocket;
+    /// # use monocoque_core::options::SocketOptions;
+    /// # use monocoque_core::rt::{self, TcpListener};
+    /// # async fn example() -> std::io::Result<()> {
+    /// let listener = TcpListener::bind("127.0.0.1:5555").await?;
+    /// let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+    /// rt::spawn_detached(driver);
+    /// let request = router.recv().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn accept_loop(
+        listener: TcpListener,
+        options: SocketOptions,
+    ) -> (RouterHubSocket, impl Future<Output = ()>) {
+        RouterHubSocket::accept_loop(listener, options)
+    }
+
     /// Create a ROUTER socket from a TCP stream with default options.
     ///
     /// Automatically enables TCP_NODELAY and applies TCP keepalive settings.
@@ -521,6 +833,97 @@ impl RouterSocket<TcpStream> {
         // Apply TCP-specific configuration
         crate::utils::configure_tcp_stream(&stream, &options, "ROUTER")?;
 
+        let mut socket = Self::with_options(stream, options).await?;
+        socket.base.remember_peer_for_recovery();
+        Ok(socket)
+    }
+
+    /// Connect to a remote peer, storing the endpoint for reconnection.
+    pub async fn connect(addr: impl monocoque_core::rt::ToSocketAddrs) -> io::Result<Self> {
+        Self::connect_with_options(addr, SocketOptions::default()).await
+    }
+
+    /// Connect and keep the connection up on its own.
+    ///
+    /// `recv` and `send` on the returned socket re-establish a lost
+    /// connection with backoff and carry on. Gives up with `NotConnected`
+    /// once `max_reconnect_attempts` consecutive attempts have failed.
+    pub async fn connect_with_reconnect(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+    ) -> io::Result<Self> {
+        Self::connect_with_reconnect_options(addr, SocketOptions::default()).await
+    }
+
+    /// [`connect_with_reconnect`](Self::connect_with_reconnect) with custom
+    /// options.
+    pub async fn connect_with_reconnect_options(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+        options: SocketOptions,
+    ) -> io::Result<Self> {
+        let mut socket = Self::connect_with_options(addr, options).await?;
+        socket.base.auto_reconnect = true;
+        Ok(socket)
+    }
+
+    /// Connect with custom options, storing the endpoint for reconnection.
+    pub async fn connect_with_options(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+        mut options: SocketOptions,
+    ) -> io::Result<Self> {
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+        let mut stream = TcpStream::connect(addr).await?;
+        let peer_addr = stream.peer_addr()?;
+        crate::utils::configure_tcp_stream(&stream, &options, "ROUTER")?;
+
+        let handshake_result = perform_handshake_with_options(
+            &mut stream,
+            SocketType::Router,
+            options.routing_id.as_deref(),
+            options.handshake_deadline(),
+            &options,
+        )
+        .await
+        .map_err(handshake_error)?;
+        let peer_identity = choose_identity(&mut options, handshake_result.peer_identity);
+
+        debug!(
+            peer_identity = ?peer_identity,
+            peer_socket_type = ?handshake_result.peer_socket_type,
+            "[ROUTER] Connected to {} (endpoint stored for reconnection)",
+            peer_addr
+        );
+
+        let endpoint = Endpoint::Tcp(peer_addr);
+        let mut base = SocketBase::with_tcp_endpoint(stream, SocketType::Router, endpoint, options);
+        base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
+        base.sequenced = handshake_result.sequenced;
+        Ok(Self {
+            base,
+            frames: SmallVec::new(),
+            peer_identity,
+        })
+    }
+}
+
+#[cfg(feature = "tls")]
+impl RouterSocket<crate::tls::TlsStream<TcpStream>> {
+    /// Accept the next connection on `listener`, complete the TLS handshake,
+    /// then run the ZMTP handshake inside the TLS session.
+    ///
+    /// A client that rejects the certificate aborts the TLS handshake, which
+    /// fails here with a [`TlsError`](crate::tls::TlsError) before any ZMTP
+    /// bytes flow. The ZMTP mechanism comes from `options` as usual, and
+    /// `handshake_timeout` bounds each of the two handshakes.
+    pub async fn accept_tls(
+        listener: &TcpListener,
+        tls: &crate::tls::TlsAcceptorConfig,
+        options: SocketOptions,
+    ) -> io::Result<Self> {
+        let (stream, _) = crate::tls::accept_with_options(listener, tls, &options).await?;
         Self::with_options(stream, options).await
     }
 }
@@ -558,13 +961,23 @@ mod tests {
 
     fn test_router(peer_identity: Bytes, options: SocketOptions) -> RouterSocket<TestStream> {
         RouterSocket {
-            base: SocketBase::new(TestStream, SocketType::Router, options.clone()),
+            base: SocketBase::new(TestStream, SocketType::Router, options),
             frames: SmallVec::new(),
             peer_identity,
-            router_mandatory: options.router_mandatory,
         }
     }
 
+    #[test]
+    fn snapshot_lists_the_peer() {
+        let router = test_router(Bytes::from_static(b"peer-1"), SocketOptions::default());
+        let snapshot = router.snapshot();
+
+        assert_eq!(snapshot.socket_type, "ROUTER");
+        assert_eq!(snapshot.peers.len(), 1);
+        assert_eq!(snapshot.peers[0].identity, "b\"peer-1\"");
+        assert_eq!(snapshot.peers[0].pending, 0);
+    }
+
     #[test]
     fn buffered_send_drops_unknown_identity_even_when_hwm_reached() {
         let mut router = test_router(
@@ -589,6 +1002,51 @@ mod tests {
         assert_eq!(router.base.buffered_messages(), 1);
     }
 
+    #[test]
+    fn unknown_identity_is_dropped_unless_mandatory() {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async {
+                let mut router =
+                    test_router(Bytes::from_static(b"known"), SocketOptions::default());
+
+                router
+                    .send(vec![
+                        Bytes::from_static(b"unknown"),
+                        Bytes::from_static(b"x"),
+                    ])
+                    .await
+                    .unwrap();
+                router
+                    .send(vec![Bytes::from_static(b"known"), Bytes::from_static(b"x")])
+                    .await
+                    .unwrap();
+
+                router.set_router_mandatory(true);
+                let err = router
+                    .send(vec![
+                        Bytes::from_static(b"unknown"),
+                        Bytes::from_static(b"x"),
+                    ])
+                    .await
+                    .unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::HostUnreachable);
+                let err = router
+                    .send_buffered(vec![
+                        Bytes::from_static(b"unknown"),
+                        Bytes::from_static(b"x"),
+                    ])
+                    .unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::HostUnreachable);
+                assert_eq!(router.base.buffered_messages(), 0);
+
+                router
+                    .send(vec![Bytes::from_static(b"known"), Bytes::from_static(b"x")])
+                    .await
+                    .unwrap();
+            });
+    }
+
     #[test]
     fn buffered_send_strips_empty_routing_identity() {
         let mut router = test_router(Bytes::new(), SocketOptions::default());
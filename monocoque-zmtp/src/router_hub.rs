@@ -0,0 +1,996 @@
+//! Multi-peer ROUTER socket driven by an accept loop.
+//!
+//! A plain [`RouterSocket`](crate::router::RouterSocket) owns one connection.
+//! [`RouterHubSocket`] owns a listener: the future returned by
+//! [`RouterHubSocket::accept_loop`] accepts ZMTP peers and registers each with a
+//! [`RouterHub`], which holds the routing table and delivers outbound messages
+//! to the peer named in frame 0.
+//!
+//! ```text
+//! [DEALER a] --\                     +------------+
+//! [DEALER b] ---> per-peer readers ->| inbound    |--recv--> [id, "", body...]
+//! [REQ    c] --/                     +------------+
+//!      ^                             +------------+
+//!      +----- per-peer writers <-----| RouterHub  |<--send--- [id, "", body...]
+//!                                    +------------+
+//! ```
+//!
+//! ## Envelope
+//!
+//! Received messages are normalized to `[identity, "", body...]` whether or not
+//! the peer framed its request with an empty delimiter. `send()` accepts
+//! `[identity, (""), body...]`; the hub strips the envelope and the peer's
+//! writer restores the delimiter for peers that sent one (REQ, or DEALERs
+//! emulating REQ), so both request styles round-trip unchanged.
+//!
+//! ## Authenticated peers
+//!
+//! When the socket is the PLAIN or CURVE server, each connection remembers
+//! the [`Principal`] its peer authenticated as.
+//! [`recv_with_meta`](RouterHubSocket::recv_with_meta) reports it with every
+//! message, and [`peer_principal`](RouterHubSocket::peer_principal) looks it
+//! up by routing identity. A connection taking an identity over under
+//! `router_handover` brings its own principal.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use monocoque_core::options::SocketOptions;
+//! use monocoque_core::rt::{self, TcpListener};
+//! use monocoque_zmtp::router_hub::RouterHubSocket;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let listener = TcpListener::bind("127.0.0.1:5555").await?;
+//! let (mut router, driver) = RouterHubSocket::accept_loop(listener, SocketOptions::default());
+//! rt::spawn_detached(driver);
+//!
+//! // Echo server for any number of peers.
+//! while let Some(msg) = router.recv().await? {
+//!     router.send(msg).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::{Bytes, BytesMut};
+use flume::{Receiver, Sender};
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::lanes::{PriorityLanes, control_lane};
+use monocoque_core::options::SocketOptions;
+use monocoque_core::router::{HubEvent, PeerCmd, RouterBehavior, RouterCmd, RouterHub};
+use monocoque_core::rt::{OwnedReadHalf, OwnedWriteHalf, TcpListener, TcpStream};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+use tracing::{debug, trace};
+
+use crate::codec::{Command, parse_command};
+use crate::diagnostics::{PeerRecord, QueueRecord};
+use crate::handshake::perform_handshake_with_options;
+use crate::security::curve::CurveMessageCipher;
+use crate::security::principal::Principal;
+use crate::sequence::{Deduper, new_epoch, stamp};
+use crate::session::SocketType;
+
+pub(crate) type PeerCipher = Arc<Mutex<CurveMessageCipher>>;
+
+/// Sequence stamps and the duplicate filter for the `dedupe_window` option,
+/// shared by every peer of the socket.
+struct HubSequencing {
+    epoch: u64,
+    next: AtomicU64,
+    dedupe: Mutex<Deduper>,
+}
+
+impl HubSequencing {
+    fn new(window: usize) -> Self {
+        Self {
+            epoch: new_epoch(),
+            next: AtomicU64::new(0),
+            dedupe: Mutex::new(Deduper::new(window)),
+        }
+    }
+}
+
+/// Identities with a live connection, each mapped to the connection that
+/// currently owns it.
+///
+/// A second connection announcing a live identity is dropped (libzmq's
+/// default) unless `router_handover` is set, in which case it takes the
+/// identity over. Only the owning connection may unregister an identity, so
+/// the displaced connection's exit cannot remove the newcomer.
+type LiveIdentities = Arc<Mutex<HashMap<Bytes, LivePeer>>>;
+
+/// A registered connection.
+#[derive(Clone)]
+struct LivePeer {
+    /// Serial of the connection, from [`NEXT_CONNECTION`].
+    serial: u64,
+    /// The connection's outbound queue.
+    queue: Arc<PeerQueue>,
+    /// Who the peer authenticated as.
+    principal: Option<Arc<Principal>>,
+}
+
+/// Message received from a peer, with what is known about its sender.
+type Inbound = (Vec<Bytes>, MessageMeta);
+
+/// What [`RouterHubSocket::recv_with_meta`] knows about a message's sender.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageMeta {
+    /// Who the sending connection authenticated as; `None` unless this
+    /// socket is the PLAIN or CURVE server.
+    pub principal: Option<Arc<Principal>>,
+}
+
+/// A connected peer, as listed by [`RouterHubSocket::peers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerSnapshot {
+    /// Routing identity of the peer.
+    pub identity: Bytes,
+    /// Who the peer authenticated as.
+    pub principal: Option<Arc<Principal>>,
+    /// Messages queued for the peer and not yet written.
+    pub pending: usize,
+}
+
+/// Outbound queue of one connection, shared by its writer and the socket.
+struct PeerQueue {
+    /// Clone of the writer's command channel, read only for its length.
+    queued: Receiver<PeerCmd>,
+    /// Whether the writer holds a message it has not finished writing.
+    writing: AtomicBool,
+    /// Messages written to the connection.
+    delivered: AtomicUsize,
+    /// Ready after each message the writer finishes; disconnects when the
+    /// writer exits.
+    progress: Receiver<()>,
+    /// Stops the writer, and with it the connection.
+    kick: Sender<()>,
+}
+
+impl PeerQueue {
+    /// Queue state for a writer draining `queued`, with the signals the
+    /// writer keeps. `alive` is dropped when the writer exits.
+    fn new(queued: Receiver<PeerCmd>, alive: Sender<()>) -> (Arc<Self>, WriterSignals) {
+        let (progress_tx, progress_rx) = flume::bounded(1);
+        let (kick_tx, kick_rx) = flume::bounded(1);
+        let queue = Arc::new(Self {
+            queued,
+            writing: AtomicBool::new(false),
+            delivered: AtomicUsize::new(0),
+            progress: progress_rx,
+            kick: kick_tx,
+        });
+        let signals = WriterSignals {
+            progress: progress_tx,
+            kick: kick_rx,
+            _alive: alive,
+        };
+        (queue, signals)
+    }
+
+    /// Messages routed to the peer and not yet written.
+    fn pending(&self) -> usize {
+        self.queued.len() + usize::from(self.writing.load(Ordering::Acquire))
+    }
+
+    fn delivered(&self) -> usize {
+        self.delivered.load(Ordering::Acquire)
+    }
+}
+
+/// Result of [`RouterHubSocket::flush_peer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushOutcome {
+    /// Messages written to the peer while flushing.
+    pub delivered: usize,
+    /// Messages still queued for the peer when the flush returned.
+    pub remaining: usize,
+}
+
+/// Source of per-connection serials for [`LiveIdentities`].
+static NEXT_CONNECTION: AtomicU64 = AtomicU64::new(0);
+
+/// ROUTER socket serving every peer accepted by its accept loop.
+///
+/// Created by [`RouterHubSocket::accept_loop`] (or
+/// [`RouterSocket::accept_loop`](crate::router::RouterSocket::accept_loop)).
+/// Dropping the socket stops the accept loop and closes every peer.
+pub struct RouterHubSocket {
+    /// Outbound commands for the hub.
+    user_tx: Sender<RouterCmd>,
+    /// Hub commands that overtake queued messages.
+    user_control: Sender<RouterCmd>,
+    /// Normalized inbound messages from every peer reader.
+    inbound_rx: Receiver<Inbound>,
+    /// Identities currently registered with the hub.
+    live: LiveIdentities,
+    /// Refuse messages for identities not in `live` (`ZMQ_ROUTER_MANDATORY`).
+    router_mandatory: bool,
+    /// Flush budget of a graceful [`kick`](Self::kick).
+    linger: Option<Duration>,
+    /// Sequencing state when `dedupe_window` is set.
+    sequencing: Option<Arc<HubSequencing>>,
+    /// Address the accept loop listens on.
+    endpoint: Option<Endpoint>,
+    /// Options every accepted peer was started with.
+    options: SocketOptions,
+    /// Dropping this stops the accept loop.
+    _shutdown: Sender<()>,
+}
+
+impl RouterHubSocket {
+    /// Serve ROUTER peers on `listener`.
+    ///
+    /// Returns the socket together with the future that drives it. The future
+    /// runs the [`RouterHub`] and the accept loop; poll it on the current
+    /// runtime (e.g. `rt::spawn_detached(driver)`). Every accepted connection
+    /// performs its ZMTP handshake in its own task, so a slow peer never holds
+    /// up the next accept. The future completes after the socket is dropped.
+    ///
+    /// `options` applies to every accepted peer (handshake timeout, security,
+    /// buffer sizes). `send_hwm` bounds the outbound queue in front of the hub
+    /// and `recv_hwm` bounds the merged inbound queue; zero means unbounded.
+    pub fn accept_loop(
+        listener: TcpListener,
+        options: SocketOptions,
+    ) -> (Self, impl Future<Output = ()>) {
+        let (hub_tx, hub_rx) = flume::unbounded();
+        let (user_tx, user_rx) = hwm_channel(options.send_hwm);
+        let (user_control, control_rx) = control_lane();
+        let (inbound_tx, inbound_rx) = hwm_channel(options.recv_hwm);
+        let (shutdown_tx, shutdown_rx) = flume::bounded(1);
+        let live = LiveIdentities::default();
+        let peers = Arc::clone(&live);
+        let router_mandatory = options.router_mandatory;
+        let linger = options.linger;
+        let sequencing = (options.dedupe_window > 0)
+            .then(|| Arc::new(HubSequencing::new(options.dedupe_window)));
+        let peer_sequencing = sequencing.clone();
+        let endpoint = listener.local_addr().ok().map(Endpoint::Tcp);
+        let peer_options = options.clone();
+
+        let hub =
+            RouterHub::new(hub_rx, user_rx, RouterBehavior::Standard).with_control(control_rx);
+        let driver = async move {
+            futures::join!(
+                hub.run(),
+                accept_peers(
+                    listener,
+                    peer_options,
+                    hub_tx,
+                    inbound_tx,
+                    shutdown_rx,
+                    peers,
+                    peer_sequencing
+                )
+            );
+        };
+
+        let socket = Self {
+            user_tx,
+            user_control,
+            inbound_rx,
+            live,
+            router_mandatory,
+            linger,
+            sequencing,
+            endpoint,
+            options,
+            _shutdown: shutdown_tx,
+        };
+        (socket, driver)
+    }
+
+    /// Receive the next message from any peer as `[identity, "", body...]`.
+    ///
+    /// Returns `Ok(None)` once the driver future has finished and every peer
+    /// reader has exited.
+    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        Ok(self.inbound_rx.recv_async().await.ok().map(|(msg, _)| msg))
+    }
+
+    /// [`recv`](Self::recv), also reporting who sent the message.
+    ///
+    /// The principal is the one of the connection the message arrived on,
+    /// even if that connection has since been replaced or closed.
+    pub async fn recv_with_meta(&mut self) -> io::Result<Option<(Vec<Bytes>, MessageMeta)>> {
+        Ok(self.inbound_rx.recv_async().await.ok())
+    }
+
+    /// Receive the next message from any peer as `(identity, body)`.
+    ///
+    /// Returns `Ok(None)` as [`recv`](Self::recv) does.
+    pub async fn recv_from(&mut self) -> io::Result<Option<(Bytes, Vec<Bytes>)>> {
+        Ok(self.recv().await?.map(crate::router::open_envelope))
+    }
+
+    /// Route `msg` to the peer named by its first frame.
+    ///
+    /// The layout is `[identity, (""), body...]`. Messages for unknown or
+    /// disconnected identities are dropped silently, as on a libzmq ROUTER,
+    /// unless `router_mandatory` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for an empty message, `HostUnreachable` under
+    /// `router_mandatory` when no connected peer has the identity, and
+    /// `BrokenPipe` once the driver future has stopped.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        if msg.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ROUTER send: empty message",
+            ));
+        }
+        if self.router_mandatory && !self.is_peer_connected(&msg[0]) {
+            return Err(crate::router::no_route(&msg[0]));
+        }
+        trace!("[ROUTER] Routing {} frames to {:?}", msg.len(), msg[0]);
+        self.user_tx
+            .send_async(RouterCmd::SendMessage(msg))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "ROUTER hub stopped"))
+    }
+
+    /// Route `frames` to the peer with routing id `identity`.
+    ///
+    /// The delimiter is written only for peers that send one, as with
+    /// [`send`](Self::send).
+    ///
+    /// # Errors
+    ///
+    /// As [`send`](Self::send).
+    pub async fn send_to(&mut self, identity: &[u8], frames: Vec<Bytes>) -> io::Result<()> {
+        self.send(crate::router::envelope(identity, frames)).await
+    }
+
+    /// Routing identities of the peers currently connected, in no particular
+    /// order.
+    ///
+    /// A peer appears from the moment its handshake completes until its
+    /// connection ends. An identity taken over under `router_handover` is
+    /// listed once, for the newest connection.
+    pub fn connected_peers(&self) -> Vec<Bytes> {
+        self.live.lock().keys().cloned().collect()
+    }
+
+    /// Check whether a peer with routing identity `id` is connected.
+    pub fn is_peer_connected(&self, id: &[u8]) -> bool {
+        self.live.lock().contains_key(id)
+    }
+
+    /// Who the peer with routing identity `id` authenticated as; `None` if
+    /// no such peer is connected or it did not authenticate.
+    pub fn peer_principal(&self, id: &[u8]) -> Option<Arc<Principal>> {
+        self.live
+            .lock()
+            .get(id)
+            .and_then(|peer| peer.principal.clone())
+    }
+
+    /// The peers currently connected, in no particular order.
+    pub fn peers(&self) -> Vec<PeerSnapshot> {
+        self.live
+            .lock()
+            .iter()
+            .map(|(identity, peer)| PeerSnapshot {
+                identity: identity.clone(),
+                principal: peer.principal.clone(),
+                pending: peer.queue.pending(),
+            })
+            .collect()
+    }
+
+    /// Messages queued for the peer `id` and not yet written to its
+    /// connection; zero if no such peer is connected.
+    ///
+    /// Messages passed to [`send`](Self::send) reach the peer's queue shortly
+    /// after `send` returns; until then they are not counted here.
+    /// [`flush_peer`](Self::flush_peer) accounts for them.
+    pub fn pending_for(&self, id: &[u8]) -> usize {
+        self.peer_queue(id).map_or(0, |queue| queue.pending())
+    }
+
+    /// Wait until every message sent to the peer `id` so far has been
+    /// written to its connection, or `timeout` expires.
+    ///
+    /// Returns how many messages were written meanwhile and how many are
+    /// still queued. A peer that disconnects while flushing ends the wait
+    /// early with its undelivered messages reported as remaining.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HostUnreachable` if no peer with the identity is connected,
+    /// and `BrokenPipe` once the driver future has stopped.
+    pub async fn flush_peer(&mut self, id: &[u8], timeout: Duration) -> io::Result<FlushOutcome> {
+        self.flush_peer_within(id, Some(timeout)).await
+    }
+
+    /// Structured diagnostics of the socket, serializable with the `serde`
+    /// feature: every connected peer with its pending messages, and the depth
+    /// of the outbound queue in front of the hub and of the merged inbound
+    /// queue.
+    pub fn snapshot(&self) -> crate::DiagnosticsSnapshot {
+        let peers = self.peers();
+        let state = if peers.is_empty() {
+            "disconnected"
+        } else {
+            "connected"
+        };
+        let mut snapshot = crate::DiagnosticsSnapshot::multi_peer(
+            SocketType::Router,
+            self.endpoint.as_ref().map(ToString::to_string),
+            state,
+            &self.options,
+        );
+        snapshot.duplicates_dropped = self.duplicates_dropped();
+        snapshot.queues = vec![
+            QueueRecord {
+                name: "outbound".to_owned(),
+                depth: self.user_tx.len(),
+                peers: peers.len(),
+            },
+            QueueRecord {
+                name: "inbound".to_owned(),
+                depth: self.inbound_rx.len(),
+                peers: peers.len(),
+            },
+        ];
+        snapshot.peers = peers
+            .into_iter()
+            .map(|peer| PeerRecord {
+                identity: format!("{:?}", peer.identity),
+                principal: peer.principal.map(|p| format!("{p:?}")),
+                pending: peer.pending,
+            })
+            .collect();
+        snapshot
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` option, across
+    /// all peers.
+    pub fn duplicates_dropped(&self) -> u64 {
+        self.sequencing
+            .as_ref()
+            .map_or(0, |s| s.dedupe.lock().duplicates())
+    }
+
+    /// Disconnect the peer `id`.
+    ///
+    /// With `graceful`, its queue is first flushed for up to the `linger`
+    /// option (indefinitely if unset, not at all if zero), as by
+    /// [`flush_peer`](Self::flush_peer); otherwise queued messages are
+    /// discarded. The returned outcome counts what was delivered during the
+    /// flush and what was left undelivered. The connection closes
+    /// asynchronously; [`is_peer_connected`](Self::is_peer_connected) may
+    /// report it for a moment after this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HostUnreachable` if no peer with the identity is connected.
+    pub async fn kick(&mut self, id: &[u8], graceful: bool) -> io::Result<FlushOutcome> {
+        let outcome = if graceful {
+            self.flush_peer_within(id, self.linger).await?
+        } else {
+            let queue = self
+                .peer_queue(id)
+                .ok_or_else(|| crate::router::no_route(&Bytes::copy_from_slice(id)))?;
+            FlushOutcome {
+                delivered: 0,
+                remaining: queue.pending(),
+            }
+        };
+        if let Some(queue) = self.peer_queue(id) {
+            debug!(
+                "[ROUTER] Kicking {:?} ({} messages undelivered)",
+                id, outcome.remaining
+            );
+            let _ = queue.kick.try_send(());
+        }
+        Ok(outcome)
+    }
+
+    /// Disconnect every peer now, discarding the messages queued for them.
+    ///
+    /// The request overtakes messages already passed to [`send`](Self::send),
+    /// so it takes effect as soon as each peer's writer finishes the message
+    /// it is writing rather than after the backlog drains. Peers that connect
+    /// afterwards are served as usual.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BrokenPipe` once the driver future has stopped.
+    pub async fn kick_all(&mut self) -> io::Result<()> {
+        self.user_control
+            .send_async(RouterCmd::Close)
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "ROUTER hub stopped"))
+    }
+
+    /// [`flush_peer`](Self::flush_peer) with an optional deadline.
+    async fn flush_peer_within(
+        &mut self,
+        id: &[u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<FlushOutcome> {
+        let queue = self
+            .peer_queue(id)
+            .ok_or_else(|| crate::router::no_route(&Bytes::copy_from_slice(id)))?;
+        let delivered_before = queue.delivered();
+
+        let flush = async {
+            // Everything sent before now has reached the peer's queue once
+            // the hub answers the barrier.
+            let (done_tx, done_rx) = flume::bounded(1);
+            self.user_tx
+                .send_async(RouterCmd::Barrier(done_tx))
+                .await
+                .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "ROUTER hub stopped"))?;
+            done_rx
+                .recv_async()
+                .await
+                .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "ROUTER hub stopped"))?;
+            while queue.pending() > 0 {
+                if queue.progress.recv_async().await.is_err() {
+                    break; // writer gone
+                }
+            }
+            Ok::<_, io::Error>(())
+        };
+        match timeout {
+            None => flush.await?,
+            Some(timeout) if timeout.is_zero() => {}
+            Some(timeout) => {
+                if let Ok(result) = monocoque_core::rt::timeout(timeout, flush).await {
+                    result?;
+                }
+            }
+        }
+
+        Ok(FlushOutcome {
+            delivered: queue.delivered() - delivered_before,
+            remaining: queue.pending(),
+        })
+    }
+
+    fn peer_queue(&self, id: &[u8]) -> Option<Arc<PeerQueue>> {
+        self.live.lock().get(id).map(|peer| Arc::clone(&peer.queue))
+    }
+
+    /// Set ROUTER mandatory mode, overriding the `router_mandatory` option.
+    ///
+    /// When enabled, [`send`](Self::send) to an identity with no connected
+    /// peer returns `HostUnreachable` instead of dropping the message.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_ROUTER_MANDATORY` (33) option.
+    pub const fn set_router_mandatory(&mut self, mandatory: bool) {
+        self.router_mandatory = mandatory;
+    }
+}
+
+/// Channel bounded by a high-water mark, where zero means unbounded.
+pub(crate) fn hwm_channel<T>(hwm: usize) -> (Sender<T>, Receiver<T>) {
+    if hwm == 0 {
+        flume::unbounded()
+    } else {
+        flume::bounded(hwm)
+    }
+}
+
+/// Accept connections until the socket is dropped, handing each to its own
+/// peer task.
+async fn accept_peers(
+    listener: TcpListener,
+    options: SocketOptions,
+    hub_tx: Sender<HubEvent>,
+    inbound: Sender<Inbound>,
+    shutdown: Receiver<()>,
+    live: LiveIdentities,
+    sequencing: Option<Arc<HubSequencing>>,
+) {
+    use futures::{FutureExt, select_biased};
+
+    loop {
+        let accepted = select_biased! {
+            _ = shutdown.recv_async().fuse() => break,
+            res = listener.accept().fuse() => res,
+        };
+        match accepted {
+            Ok((stream, addr)) => {
+                if let Err(e) = crate::utils::configure_tcp_stream(&stream, &options, "ROUTER") {
+                    debug!("[ROUTER] Dropping connection from {}: {}", addr, e);
+                    continue;
+                }
+                debug!("[ROUTER] Accepted connection from {}", addr);
+                monocoque_core::rt::spawn_detached(serve_peer(
+                    stream,
+                    options.clone(),
+                    hub_tx.clone(),
+                    inbound.clone(),
+                    Arc::clone(&live),
+                    sequencing.clone(),
+                ));
+            }
+            Err(e) => {
+                debug!("[ROUTER] Accept failed: {}", e);
+                crate::utils::backoff_on_fd_exhaustion(&e).await;
+            }
+        }
+    }
+    debug!("[ROUTER] Accept loop stopped");
+}
+
+/// Handshake one connection, register it with the hub, and run its reader
+/// until the connection or the socket goes away.
+async fn serve_peer(
+    mut stream: TcpStream,
+    options: SocketOptions,
+    hub_tx: Sender<HubEvent>,
+    inbound: Sender<Inbound>,
+    live: LiveIdentities,
+    sequencing: Option<Arc<HubSequencing>>,
+) {
+    let handshake = match perform_handshake_with_options(
+        &mut stream,
+        SocketType::Router,
+        options.routing_id.as_deref(),
+        options.handshake_deadline(),
+        &options,
+    )
+    .await
+    {
+        Ok(handshake) => handshake,
+        Err(e) => {
+            debug!("[ROUTER] Handshake failed: {}", e);
+            return;
+        }
+    };
+
+    let identity = handshake
+        .peer_identity
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(crate::router::auto_identity);
+    let serial = NEXT_CONNECTION.fetch_add(1, Ordering::Relaxed);
+    let (peer_tx, peer_rx) = flume::unbounded();
+    let (control_tx, control_rx) = control_lane();
+    let (writer_alive, writer_gone) = flume::bounded::<()>(1);
+    let (queue, signals) = PeerQueue::new(peer_rx.clone(), writer_alive);
+    let meta = MessageMeta {
+        principal: handshake.principal.map(Arc::new),
+    };
+    let peer = LivePeer {
+        serial,
+        queue: Arc::clone(&queue),
+        principal: meta.principal.clone(),
+    };
+    if !register(&live, &identity, peer, options.router_handover) {
+        return;
+    }
+    debug!(
+        peer_identity = ?identity,
+        peer_socket_type = ?handshake.peer_socket_type,
+        "[ROUTER] Peer registered"
+    );
+
+    let cipher = handshake.curve_cipher.map(|c| Arc::new(Mutex::new(c)));
+    let sequencing = sequencing.filter(|_| handshake.sequenced);
+    let delimited = Arc::new(AtomicBool::new(
+        handshake.peer_socket_type == SocketType::Req,
+    ));
+    let (read_half, write_half) = stream.into_split();
+
+    if hub_tx
+        .send(HubEvent::PeerUp {
+            routing_id: identity.clone(),
+            tx: peer_tx,
+            control: Some(control_tx.clone()),
+        })
+        .is_ok()
+    {
+        monocoque_core::rt::spawn_detached(peer_writer(
+            write_half,
+            PriorityLanes::new(control_rx, peer_rx),
+            queue,
+            signals,
+            cipher.clone(),
+            Arc::clone(&delimited),
+            sequencing.clone(),
+        ));
+        let epoch = peer_reader(
+            &identity,
+            &meta,
+            read_half,
+            cipher,
+            sequencing.as_deref(),
+            &delimited,
+            &options,
+            &inbound,
+            &control_tx,
+            &writer_gone,
+        )
+        .await;
+        if let (Some(sequencing), Some(epoch)) = (&sequencing, epoch) {
+            sequencing.dedupe.lock().release(epoch);
+        }
+    }
+
+    let mut live = live.lock();
+    if live
+        .get(&identity)
+        .is_some_and(|peer| peer.serial == serial)
+    {
+        live.remove(&identity);
+        drop(live);
+        let _ = hub_tx.send(HubEvent::PeerDown {
+            routing_id: identity,
+        });
+    } else {
+        debug!("[ROUTER] Identity {:?} handed over", identity);
+    }
+}
+
+/// Claim `identity` for `peer`; `false` if another connection holds it and
+/// `handover` does not allow taking it over.
+fn register(live: &LiveIdentities, identity: &Bytes, peer: LivePeer, handover: bool) -> bool {
+    let mut live = live.lock();
+    if live.contains_key(identity) && !handover {
+        debug!(
+            "[ROUTER] Identity {:?} already connected; dropping",
+            identity
+        );
+        return false;
+    }
+    // Under handover, the hub replacing the old connection's sender on
+    // `PeerUp` is what shuts the old connection down.
+    live.insert(identity.clone(), peer);
+    true
+}
+
+/// Decode messages from one peer and forward them, normalized, to the socket.
+/// A PING is answered by queueing a PONG on the writer's control lane, so it
+/// is not held up behind the bodies already queued for the peer.
+///
+/// Stops on EOF, a read or decode error, when the socket is dropped, or when
+/// the peer's writer exits (`writer_gone` disconnects). Returns the sequence
+/// epoch the peer stamped its messages with, if any; a peer that switches
+/// epochs mid-connection is dropped.
+#[allow(clippy::too_many_arguments)]
+async fn peer_reader(
+    identity: &Bytes,
+    meta: &MessageMeta,
+    mut reader: OwnedReadHalf,
+    cipher: Option<PeerCipher>,
+    sequencing: Option<&HubSequencing>,
+    delimited: &AtomicBool,
+    options: &SocketOptions,
+    inbound: &Sender<Inbound>,
+    control: &Sender<PeerCmd>,
+    writer_gone: &Receiver<()>,
+) -> Option<u64> {
+    use compio_buf::BufResult;
+    use compio_io::AsyncRead;
+    use futures::{FutureExt, select_biased};
+    use monocoque_core::buffer::SegmentedBuffer;
+    use monocoque_core::io::take_read_buffer;
+
+    let mut recv_buf = SegmentedBuffer::new();
+    let mut decoder = crate::codec::ZmtpDecoder::with_size_limits(options);
+    let mut frames = Vec::new();
+    let mut read_buf = BytesMut::new();
+    let mut epoch = None;
+
+    'read: loop {
+        // SAFETY: `buf` is passed straight to `read`; the data path truncates
+        // it to `n` before freezing, and EOF/error drop it without reading it.
+        let buf = unsafe { take_read_buffer(&mut read_buf, options.read_buffer_size) };
+        let BufResult(result, mut buf) = select_biased! {
+            _ = writer_gone.recv_async().fuse() => break,
+            res = reader.read(buf).fuse() => res,
+        };
+        match result {
+            Ok(0) => {
+                debug!("[ROUTER] Peer {:?} disconnected", identity);
+                break;
+            }
+            Ok(n) => {
+                buf.truncate(n);
+                recv_buf.push(buf.freeze());
+            }
+            Err(e) => {
+                debug!("[ROUTER] Peer {:?} read error: {}", identity, e);
+                break;
+            }
+        }
+
+        loop {
+            let frame = match decoder.decode(&mut recv_buf) {
+                Ok(Some(frame)) => frame,
+                Ok(None) => break,
+                Err(e) => {
+                    debug!("[ROUTER] Peer {:?} decode error: {}", identity, e);
+                    break 'read;
+                }
+            };
+            let (more, payload) = if frame.is_command() {
+                // CURVE MESSAGE carries data; PING is answered; other
+                // commands are ignored.
+                match &cipher {
+                    Some(cipher) if CurveMessageCipher::is_curve_message(&frame.payload) => {
+                        let decrypted = cipher.lock().decrypt_frame(&frame.payload);
+                        let Ok(decrypted) = decrypted else {
+                            break 'read;
+                        };
+                        decrypted
+                    }
+                    _ => {
+                        if let Ok(Command::Ping { context, .. }) = parse_command(&frame) {
+                            // A full lane means PONGs are already pending.
+                            let pong = crate::base::build_pong_frame(&context);
+                            let _ = control.try_send(PeerCmd::SendCommand(pong));
+                        }
+                        continue;
+                    }
+                }
+            } else if cipher.is_some() {
+                // Reject plaintext data frames when CURVE is active.
+                break 'read;
+            } else {
+                (frame.more(), frame.payload)
+            };
+
+            frames.push(payload);
+            if !more {
+                let mut frames = std::mem::take(&mut frames);
+                if let Some(sequencing) = sequencing {
+                    let admitted = sequencing
+                        .dedupe
+                        .lock()
+                        .admit_bound(&mut frames, &mut epoch);
+                    match admitted {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            trace!("[ROUTER] Dropped a duplicate from {:?}", identity);
+                            continue;
+                        }
+                        Err(e) => {
+                            debug!("[ROUTER] Peer {:?} sequence error: {}", identity, e);
+                            break 'read;
+                        }
+                    }
+                }
+                let msg = envelope(identity, frames, delimited);
+                if inbound.send_async((msg, meta.clone())).await.is_err() {
+                    break 'read;
+                }
+            }
+        }
+    }
+    epoch
+}
+
+/// Prefix a peer's message with `[identity, ""]`, remembering whether the
+/// peer supplied the delimiter itself so replies can mirror it.
+fn envelope(identity: &Bytes, frames: Vec<Bytes>, delimited: &AtomicBool) -> Vec<Bytes> {
+    let has_delimiter = frames.first().is_some_and(Bytes::is_empty);
+    delimited.store(has_delimiter, Ordering::Relaxed);
+
+    let mut msg = Vec::with_capacity(frames.len() + 2);
+    msg.push(identity.clone());
+    if !has_delimiter {
+        msg.push(Bytes::new());
+    }
+    msg.extend(frames);
+    msg
+}
+
+/// Channels tying a peer's writer to the rest of the socket.
+struct WriterSignals {
+    /// Nudged after each message written.
+    progress: Sender<()>,
+    /// Stops the writer when signalled.
+    kick: Receiver<()>,
+    /// Dropped when the writer exits, which stops the peer's reader.
+    _alive: Sender<()>,
+}
+
+/// Write bodies routed to this peer by the hub until it is closed, kicked, or
+/// the connection fails. Control commands (PONG replies, `Close`) are taken
+/// ahead of queued bodies. Exiting drops `signals._alive`, which stops the
+/// peer's reader.
+async fn peer_writer(
+    mut writer: OwnedWriteHalf,
+    mut commands: PriorityLanes<PeerCmd>,
+    queue: Arc<PeerQueue>,
+    signals: WriterSignals,
+    cipher: Option<PeerCipher>,
+    delimited: Arc<AtomicBool>,
+    sequencing: Option<Arc<HubSequencing>>,
+) {
+    use compio_buf::BufResult;
+    use compio_io::AsyncWriteExt;
+    use futures::{FutureExt, select_biased};
+
+    loop {
+        let cmd = select_biased! {
+            _ = signals.kick.recv_async().fuse() => break,
+            cmd = commands.recv().fuse() => cmd,
+        };
+        let body = match cmd {
+            Some(PeerCmd::SendBody(body)) => body,
+            Some(PeerCmd::SendCommand(frame)) => {
+                let BufResult(res, _) = select_biased! {
+                    _ = signals.kick.recv_async().fuse() => break,
+                    res = writer.write_all(frame).fuse() => res,
+                };
+                if res.is_err() {
+                    break;
+                }
+                continue;
+            }
+            Some(PeerCmd::Close) | None => break,
+        };
+        queue.writing.store(true, Ordering::Release);
+        let sequence_frame = sequencing
+            .as_ref()
+            .map(|s| stamp(s.epoch, s.next.fetch_add(1, Ordering::Relaxed)));
+        let framed;
+        let frames: &[Bytes] = if delimited.load(Ordering::Relaxed) || sequence_frame.is_some() {
+            let delimiter = delimited.load(Ordering::Relaxed).then(Bytes::new);
+            framed = sequence_frame
+                .into_iter()
+                .chain(delimiter)
+                .chain(body.iter().cloned())
+                .collect::<Vec<_>>();
+            &framed
+        } else {
+            &body
+        };
+
+        let wire = if let Some(cipher) = &cipher {
+            let Some(wire) = encode_curve_wire(frames, cipher) else {
+                break;
+            };
+            wire
+        } else {
+            let mut wire = BytesMut::new();
+            crate::codec::encode_multipart(frames, &mut wire);
+            wire.freeze()
+        };
+
+        let BufResult(res, _) = select_biased! {
+            _ = signals.kick.recv_async().fuse() => break,
+            res = writer.write_all(wire).fuse() => res,
+        };
+        if res.is_err() {
+            break;
+        }
+        queue.delivered.fetch_add(1, Ordering::Release);
+        queue.writing.store(false, Ordering::Release);
+        let _ = signals.progress.try_send(());
+    }
+    // A message interrupted by a kick or a failed write was not delivered
+    // but is no longer queued either.
+    queue.writing.store(false, Ordering::Release);
+}
+
+/// Encrypt one message into its CURVE wire bytes; `None` if a frame fails to
+/// encrypt.
+pub(crate) fn encode_curve_wire(frames: &[Bytes], cipher: &PeerCipher) -> Option<Bytes> {
+    let last = frames.len().saturating_sub(1);
+    let mut wire = BytesMut::new();
+    let mut cipher = cipher.lock();
+    for (i, frame) in frames.iter().enumerate() {
+        let sealed = cipher.encrypt_frame(frame, i < last).ok()?;
+        crate::base::append_zmtp_cmd_frame(&mut wire, &sealed);
+    }
+    drop(cipher);
+    Some(wire.freeze())
+}
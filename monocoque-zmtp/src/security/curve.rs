@@ -35,11 +35,12 @@ use chacha20poly1305::{
     XChaCha20Poly1305, XNonce,
     aead::{Aead, KeyInit, OsRng},
 };
-use compio_io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use compio_io::{AsyncRead, AsyncWrite};
 use crypto_box::{
     PublicKey as SalsaPublicKey, SalsaBox, SecretKey as SalsaSecretKey,
     aead::generic_array::GenericArray,
 };
+use crypto_secretbox::XSalsa20Poly1305;
 use rand::RngCore;
 use sha2::{Digest, Sha256};
 use std::time::Duration;
@@ -49,6 +50,7 @@ use x25519_dalek::{PublicKey, StaticSecret};
 use zeroize::Zeroize;
 
 use crate::codec::ZmtpError;
+use crate::security::protocol::{peer_error, read_zmtp_cmd, send_zmtp_error, write_zmtp_cmd};
 use crate::security::zap::{ZapMechanism, ZapRequest, ZapStatus};
 
 /// CURVE command identifiers
@@ -127,6 +129,11 @@ impl CurveSecretKey {
         Self(StaticSecret::from(bytes))
     }
 
+    /// Raw bytes, as taken by `SocketOptions::with_curve_keypair`.
+    pub fn as_bytes(&self) -> &[u8; CURVE_KEY_SIZE] {
+        self.0.as_bytes()
+    }
+
     /// Get public key
     pub fn public_key(&self) -> CurvePublicKey {
         CurvePublicKey::from(PublicKey::from(&self.0))
@@ -283,6 +290,41 @@ fn parse_curve_message(message: &[u8]) -> Result<CurveMessageParts<'_>, CurveErr
     })
 }
 
+/// Last counter a sender may use; past it the session has to be re-keyed.
+const CURVE_MAX_SEND_NONCE: u64 = u64::MAX - 1;
+
+/// Take the next MESSAGE counter from `send_nonce`, refusing to wrap.
+fn next_send_nonce(send_nonce: &mut u64) -> Result<u64, CurveError> {
+    let counter = *send_nonce;
+    if counter >= CURVE_MAX_SEND_NONCE {
+        return Err(CurveError::NonceExhausted);
+    }
+    *send_nonce = counter + 1;
+    Ok(counter)
+}
+
+/// Check an incoming MESSAGE counter against the next one expected.
+///
+/// Counters must arrive in sequence: an old one is a replay, a skipped one
+/// means frames were dropped or reordered, and either way the stream can no
+/// longer be trusted. The caller advances `recv_nonce` only once the frame
+/// has decrypted.
+fn check_recv_nonce(short_nonce: &[u8], recv_nonce: u64) -> Result<u64, CurveError> {
+    let counter = u64::from_be_bytes(
+        short_nonce
+            .try_into()
+            .map_err(|_| CurveError::InvalidNonce)?,
+    );
+    if counter != recv_nonce {
+        return Err(CurveError::NonceReplay);
+    }
+    // No honest sender gets this far (see `next_send_nonce`).
+    if counter >= CURVE_MAX_SEND_NONCE {
+        return Err(CurveError::ProtocolViolation);
+    }
+    Ok(counter)
+}
+
 // ── Key derivation ────────────────────────────────────────────────────────────
 
 /// Derive the post-handshake message key via SHA-256 of the three DH legs.
@@ -296,94 +338,6 @@ fn derive_message_key(dh1: &[u8; 32], dh2: &[u8; 32], dh3: &[u8; 32]) -> [u8; 32
     h.finalize().into()
 }
 
-// ── ZMTP frame helpers ────────────────────────────────────────────────────────
-
-/// Read one ZMTP command frame and return its body.
-/// Rejects data frames (command flag 0x04 must be set).
-async fn read_zmtp_cmd<S>(
-    stream: &mut S,
-    timeout: Option<Duration>,
-    max_body: usize,
-) -> Result<Vec<u8>, ZmtpError>
-where
-    S: AsyncRead + Unpin,
-{
-    use compio_buf::BufResult;
-    use monocoque_core::timeout::read_exact_with_timeout;
-
-    let BufResult(r, flags_buf) = read_exact_with_timeout(stream, [0u8; 1], timeout)
-        .await
-        .map_err(ZmtpError::from)?;
-    r?;
-    let flags = flags_buf[0];
-
-    if flags & 0x04 == 0 {
-        warn!(
-            "Expected ZMTP command frame, got data frame (flags=0x{:02x})",
-            flags
-        );
-        return Err(ZmtpError::Protocol);
-    }
-
-    let body_len: usize = if flags & 0x02 != 0 {
-        let BufResult(r, len_buf) = read_exact_with_timeout(stream, [0u8; 8], timeout)
-            .await
-            .map_err(ZmtpError::from)?;
-        r?;
-        let raw_len = u64::from_be_bytes(len_buf);
-        if raw_len > usize::MAX as u64 {
-            warn!("ZMTP long-frame length overflows usize: {}", raw_len);
-            return Err(ZmtpError::Protocol);
-        }
-        raw_len as usize
-    } else {
-        let BufResult(r, len_buf) = read_exact_with_timeout(stream, [0u8; 1], timeout)
-            .await
-            .map_err(ZmtpError::from)?;
-        r?;
-        len_buf[0] as usize
-    };
-
-    if body_len > max_body {
-        warn!("ZMTP command body too large: {} > {}", body_len, max_body);
-        return Err(ZmtpError::Protocol);
-    }
-
-    let BufResult(r, body) = read_exact_with_timeout(stream, vec![0u8; body_len], timeout)
-        .await
-        .map_err(ZmtpError::from)?;
-    r?;
-    Ok(body)
-}
-
-/// Write a slice as a ZMTP command frame (flag + length + body).
-async fn write_zmtp_cmd<S>(
-    stream: &mut S,
-    body: &[u8],
-    timeout: Option<Duration>,
-) -> Result<(), ZmtpError>
-where
-    S: AsyncWrite + Unpin,
-{
-    use compio_buf::BufResult;
-    use monocoque_core::timeout::write_all_with_timeout;
-
-    let len = body.len();
-    let mut frame = BytesMut::with_capacity(if len <= 255 { 2 + len } else { 9 + len });
-    if len <= 255 {
-        frame.extend_from_slice(&[0x04, len as u8]);
-    } else {
-        frame.extend_from_slice(&[0x06]);
-        frame.extend_from_slice(&(len as u64).to_be_bytes());
-    }
-    frame.extend_from_slice(body);
-
-    let BufResult(r, _) = write_all_with_timeout(stream, frame.freeze().to_vec(), timeout)
-        .await
-        .map_err(ZmtpError::from)?;
-    r.map_err(Into::into)
-}
-
 // ── ZMTP property helpers ─────────────────────────────────────────────────────
 
 /// Encode Socket-Type and optional Identity as ZMTP property bytes (RFC 23 §2.5).
@@ -502,11 +456,8 @@ impl CurveMessageCipher {
         };
         let mut nonce = [0u8; CURVE_NONCE_SIZE];
         nonce[..16].copy_from_slice(prefix);
-        nonce[16..].copy_from_slice(&self.send_nonce.to_be_bytes());
-        self.send_nonce = self
-            .send_nonce
-            .checked_add(1)
-            .ok_or(CurveError::ProtocolViolation)?;
+        let counter = next_send_nonce(&mut self.send_nonce)?;
+        nonce[16..].copy_from_slice(&counter.to_be_bytes());
 
         let mut pt = Vec::with_capacity(1 + payload.len());
         pt.push(u8::from(more));
@@ -526,18 +477,7 @@ impl CurveMessageCipher {
     /// Returns (more_flag, payload).
     pub fn decrypt_frame(&mut self, cmd_body: &[u8]) -> Result<(bool, Bytes), CurveError> {
         let parts = parse_curve_message(cmd_body)?;
-        let counter = u64::from_be_bytes(
-            parts
-                .short_nonce
-                .try_into()
-                .map_err(|_| CurveError::InvalidNonce)?,
-        );
-        if counter < self.recv_nonce {
-            return Err(CurveError::ProtocolViolation);
-        }
-        self.recv_nonce = counter
-            .checked_add(1)
-            .ok_or(CurveError::ProtocolViolation)?;
+        let counter = check_recv_nonce(parts.short_nonce, self.recv_nonce)?;
 
         let prefix: &[u8; 16] = if self.is_client {
             b"CurveZMQMESSAGES"
@@ -552,6 +492,7 @@ impl CurveMessageCipher {
         if plaintext.is_empty() {
             return Err(CurveError::ProtocolViolation);
         }
+        self.recv_nonce = counter + 1;
         let more = (plaintext[0] & 0x01) != 0;
         // Convert the owned plaintext into `Bytes` (O(1), no copy) and slice off
         // the 1-byte flags prefix by advancing the view (also O(1)). The prior
@@ -595,6 +536,13 @@ pub enum CurveError {
     /// The peer's identity could not be verified.
     #[error("Authentication failed")]
     AuthenticationFailed,
+    /// A MESSAGE counter was not the next one expected (replayed, reordered,
+    /// or skipped).
+    #[error("Nonce replayed or out of order")]
+    NonceReplay,
+    /// The send counter is used up; the session has to be re-keyed.
+    #[error("Nonce counter exhausted; re-key required")]
+    NonceExhausted,
     /// An underlying I/O error occurred.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -610,6 +558,8 @@ pub struct CurveHandshakeResult {
     pub peer_public_key: Option<CurvePublicKey>,
     /// Post-handshake cipher for encrypting/decrypting application messages.
     pub cipher: Option<CurveMessageCipher>,
+    /// User id the ZAP handler assigned to the client (server-side with ZAP only).
+    pub user_id: Option<String>,
 }
 
 // ── CurveClient ───────────────────────────────────────────────────────────────
@@ -686,6 +636,7 @@ impl CurveClient {
             peer_identity: self.peer_identity_recv.clone(),
             peer_public_key: None,
             cipher: Some(cipher),
+            user_id: None,
         })
     }
 
@@ -746,6 +697,10 @@ impl CurveClient {
         debug!("[CURVE CLIENT] Waiting for WELCOME");
 
         let body = read_zmtp_cmd(stream, timeout, MAX_CURVE_BODY).await?;
+        if let Some(err) = peer_error(&body) {
+            warn!("[CURVE CLIENT] Server refused HELLO: {}", err);
+            return Err(err);
+        }
         if body.len() != 168 || &body[..8] != CURVE_WELCOME {
             warn!("[CURVE CLIENT] Invalid WELCOME frame (len={})", body.len());
             return Err(ZmtpError::Protocol);
@@ -790,10 +745,13 @@ impl CurveClient {
 
     /// Send INITIATE command wrapped in a ZMTP command frame.
     ///
-    /// Body: cookie(96) + C(32) + nonce_suffix(8) + initiate_box(variable)
+    /// Body: \x08INITIATE(9) + cookie(96) + nonce_suffix(8) + initiate_box(variable)
+    ///
+    /// initiate_box = SalsaBox(c'→s').encrypt(C ‖ vouch ‖ metadata, "CurveZMQINITIATE" ‖ nonce_suffix)
+    /// vouch = vouch_nonce_16(16) + SalsaBox(C→s').encrypt(c'.pk ‖ S, "VOUCH---" ‖ vouch_nonce_16)
     ///
-    /// initiate_box = SalsaBox(c'→s').encrypt(vouch ‖ metadata, "CurveZMQINITIATE" ‖ nonce_suffix)
-    /// vouch = vouch_nonce_16(16) + SalsaBox(C→S).encrypt(c'.pk ‖ s'.pk, "VOUCH---" ‖ vouch_nonce_16)
+    /// The vouch proves the holder of C chose this short-term key for this
+    /// server; only the server that minted s' can open it.
     #[allow(clippy::similar_names)]
     async fn send_initiate<S>(
         &mut self,
@@ -808,7 +766,7 @@ impl CurveClient {
         let server_short_public = self.server_short_public.ok_or(ZmtpError::Protocol)?;
         let cookie = self.cookie.as_ref().ok_or(ZmtpError::Protocol)?;
 
-        // Build vouch: Box[c'.pk ‖ s'.pk](C→S)
+        // Build vouch: Box[c'.pk ‖ S](C→s')
         let mut vouch_nonce_16 = [0u8; 16];
         rand::thread_rng().fill_bytes(&mut vouch_nonce_16);
         let mut vouch_nonce_24 = [0u8; 24];
@@ -817,10 +775,10 @@ impl CurveClient {
 
         let mut vouch_pt = [0u8; 64];
         vouch_pt[..32].copy_from_slice(self.client_short_keypair.public.as_bytes()); // c'.pk
-        vouch_pt[32..].copy_from_slice(server_short_public.as_bytes()); // s'.pk
+        vouch_pt[32..].copy_from_slice(self.server_public.as_bytes()); // S
 
         let vouch_ct = salsa_encrypt(
-            self.server_public.as_bytes(),
+            server_short_public.as_bytes(),
             &self.client_keypair.secret.to_raw_bytes(),
             &vouch_nonce_24,
             &vouch_pt,
@@ -836,11 +794,12 @@ impl CurveClient {
         // Append ZMTP properties (Socket-Type + optional Identity)
         let local_props =
             encode_zmtp_props(&self.local_socket_type, self.local_identity.as_deref());
-        let mut initiate_pt = Vec::with_capacity(96 + local_props.len());
+        let mut initiate_pt = Vec::with_capacity(32 + 96 + local_props.len());
+        initiate_pt.extend_from_slice(self.client_keypair.public.as_bytes()); // C
         initiate_pt.extend_from_slice(&vouch);
         initiate_pt.extend_from_slice(&local_props);
 
-        // Build initiate_box: Box[vouch ‖ metadata](c'→s')
+        // Build initiate_box: Box[C ‖ vouch ‖ metadata](c'→s')
         let initiate_counter: u64 = 1;
         let mut initiate_nonce_24 = [0u8; 24];
         initiate_nonce_24[..16].copy_from_slice(b"CurveZMQINITIATE");
@@ -854,10 +813,9 @@ impl CurveClient {
         )
         .map_err(|_| ZmtpError::Protocol)?;
 
-        let mut frame = BytesMut::with_capacity(9 + 96 + 32 + 8 + initiate_box.len());
+        let mut frame = BytesMut::with_capacity(9 + 96 + 8 + initiate_box.len());
         frame.extend_from_slice(CURVE_INITIATE); //   9
         frame.extend_from_slice(cookie); //  96
-        frame.extend_from_slice(self.client_keypair.public.as_bytes()); //  32  C
         frame.extend_from_slice(&initiate_counter.to_be_bytes()); //   8
         frame.extend_from_slice(&initiate_box); // variable
 
@@ -876,6 +834,10 @@ impl CurveClient {
         debug!("[CURVE CLIENT] Waiting for READY");
 
         let body = read_zmtp_cmd(stream, timeout, MAX_CURVE_BODY).await?;
+        if let Some(err) = peer_error(&body) {
+            warn!("[CURVE CLIENT] Server refused INITIATE: {}", err);
+            return Err(err);
+        }
         // body = \x05READY (6) + nonce_8 (8) + ready_box (variable)
         if body.len() < 30 || &body[..6] != CURVE_READY {
             warn!(
@@ -946,11 +908,8 @@ impl CurveClient {
         // Nonce = "CurveZMQMESSAGEC" + 8-byte counter (client→server)
         let mut nonce = [0u8; CURVE_NONCE_SIZE];
         nonce[..16].copy_from_slice(b"CurveZMQMESSAGEC");
-        nonce[16..].copy_from_slice(&self.send_nonce.to_be_bytes());
-        self.send_nonce = self
-            .send_nonce
-            .checked_add(1)
-            .ok_or(CurveError::ProtocolViolation)?;
+        let counter = next_send_nonce(&mut self.send_nonce)?;
+        nonce[16..].copy_from_slice(&counter.to_be_bytes());
 
         let ciphertext = message_box.encrypt(plaintext, &nonce)?;
 
@@ -970,18 +929,7 @@ impl CurveClient {
             .as_ref()
             .ok_or(CurveError::ProtocolViolation)?;
 
-        let counter = u64::from_be_bytes(
-            parts
-                .short_nonce
-                .try_into()
-                .map_err(|_| CurveError::InvalidNonce)?,
-        );
-        if counter < self.recv_nonce {
-            return Err(CurveError::ProtocolViolation); // replay
-        }
-        self.recv_nonce = counter
-            .checked_add(1)
-            .ok_or(CurveError::ProtocolViolation)?;
+        let counter = check_recv_nonce(parts.short_nonce, self.recv_nonce)?;
 
         // Reconstruct full 24-byte nonce (server→client direction)
         let mut nonce = [0u8; CURVE_NONCE_SIZE];
@@ -989,6 +937,7 @@ impl CurveClient {
         nonce[16..].copy_from_slice(parts.short_nonce);
 
         let plaintext = message_box.decrypt(parts.ciphertext, &nonce)?;
+        self.recv_nonce = counter + 1;
         Ok(Bytes::from(plaintext))
     }
 }
@@ -1005,8 +954,9 @@ pub struct CurveServer {
     client_short_public: Option<CurvePublicKey>,
     /// Client's long-term public key received in INITIATE (C)
     client_public: Option<CurvePublicKey>,
-    /// Symmetric key for cookie encryption/decryption (32 bytes, random per-server)
-    cookie_key: [u8; 32],
+    /// Transient cookie key (K), minted for WELCOME and wiped once INITIATE
+    /// has been checked
+    cookie_key: Option<[u8; 32]>,
     /// Local socket type to announce in READY metadata
     local_socket_type: String,
     /// Peer socket type received in INITIATE metadata
@@ -1024,14 +974,12 @@ pub struct CurveServer {
 impl CurveServer {
     /// Create new CURVE server
     pub fn new(server_keypair: CurveKeyPair, local_socket_type: impl Into<String>) -> Self {
-        let mut cookie_key = [0u8; 32];
-        rand::thread_rng().fill_bytes(&mut cookie_key);
         Self {
             server_keypair,
             server_short_keypair: CurveKeyPair::generate(),
             client_short_public: None,
             client_public: None,
-            cookie_key,
+            cookie_key: None,
             local_socket_type: local_socket_type.into(),
             peer_socket_type: None,
             peer_identity_recv: None,
@@ -1061,6 +1009,7 @@ impl CurveServer {
             peer_identity: self.peer_identity_recv.clone(),
             peer_public_key: self.client_public,
             cipher: Some(cipher),
+            user_id: None,
         })
     }
 
@@ -1127,7 +1076,10 @@ impl CurveServer {
     ///
     /// Body: \x07WELCOME(8) + server_nonce_16(16) + welcome_box(144) = 168 bytes
     ///
-    /// Cookie = cookie_nonce_16(16) + XChaCha20.encrypt(c'.pk ‖ s'.sk, "COOKIE--" ‖ cookie_nonce_16)
+    /// Cookie = cookie_nonce_16(16) + SecretBox(K).encrypt(c'.pk ‖ s'.sk, "COOKIE--" ‖ cookie_nonce_16)
+    ///
+    /// K is a fresh cookie key that never leaves the server, so the server
+    /// keeps no per-client state between WELCOME and INITIATE beyond it.
     #[allow(clippy::similar_names)]
     async fn send_welcome<S>(
         &mut self,
@@ -1141,7 +1093,7 @@ impl CurveServer {
 
         let c_prime_pk = self.client_short_public.ok_or(ZmtpError::Protocol)?;
 
-        // Build cookie (96 bytes): cookie_nonce_16 + XChaCha20ct(c'.pk ‖ s'.sk_bytes)
+        // Build cookie (96 bytes): cookie_nonce_16 + Box[c'.pk ‖ s'.sk](K)
         let mut cookie_nonce_16 = [0u8; 16];
         rand::thread_rng().fill_bytes(&mut cookie_nonce_16);
         let mut cookie_nonce_24 = [0u8; 24];
@@ -1152,10 +1104,15 @@ impl CurveServer {
         cookie_pt[..32].copy_from_slice(c_prime_pk.as_bytes());
         cookie_pt[32..].copy_from_slice(&self.server_short_keypair.secret.to_raw_bytes());
 
-        let cookie_cipher = XChaCha20Poly1305::new(self.cookie_key.as_ref().into());
+        let mut cookie_key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut cookie_key);
+        let cookie_cipher = XSalsa20Poly1305::new(cookie_key.as_ref().into());
         let cookie_ct = cookie_cipher
-            .encrypt(XNonce::from_slice(&cookie_nonce_24), cookie_pt.as_ref())
-            .map_err(|_| ZmtpError::Protocol)?;
+            .encrypt(cookie_nonce_24.as_ref().into(), cookie_pt.as_ref())
+            .map_err(|_| ZmtpError::Protocol);
+        cookie_pt.zeroize();
+        self.cookie_key = Some(cookie_key);
+        let cookie_ct = cookie_ct?;
         // cookie_ct = 64 + 16 = 80 bytes
 
         let mut cookie = Vec::with_capacity(96);
@@ -1209,17 +1166,16 @@ impl CurveServer {
         debug!("[CURVE SERVER] Waiting for INITIATE");
 
         let body = read_zmtp_cmd(stream, timeout, MAX_INITIATE_BODY).await?;
-        // body = \x08INITIATE(9) + cookie(96) + C(32) + nonce_8(8) + initiate_box(rest)
-        // 9 + 96 + 32 + 8 = 145 minimum before initiate_box
-        if body.len() < 145 || &body[..9] != CURVE_INITIATE {
+        // body = \x08INITIATE(9) + cookie(96) + nonce_8(8) + initiate_box(rest)
+        // 9 + 96 + 8 = 113 bytes before initiate_box
+        if body.len() < 113 || &body[..9] != CURVE_INITIATE {
             warn!("[CURVE SERVER] Invalid INITIATE frame (len={})", body.len());
             return Err(ZmtpError::Protocol);
         }
 
         let cookie_bytes = &body[9..105]; // 96 bytes
-        let c_pk_buf = &body[105..137]; // 32 bytes C
-        let nonce_suffix = &body[137..145]; // 8 bytes
-        let initiate_box = &body[145..]; // variable
+        let nonce_suffix = &body[105..113]; // 8 bytes
+        let initiate_box = &body[113..]; // variable
 
         // Decrypt cookie to recover (c'.pk ‖ s'.sk_bytes)
         let cookie_nonce_16 = &cookie_bytes[..16];
@@ -1228,9 +1184,12 @@ impl CurveServer {
         cookie_nonce_24[..8].copy_from_slice(b"COOKIE--");
         cookie_nonce_24[8..].copy_from_slice(cookie_nonce_16);
 
-        let cookie_cipher = XChaCha20Poly1305::new(self.cookie_key.as_ref().into());
-        let cookie_pt = cookie_cipher
-            .decrypt(XNonce::from_slice(&cookie_nonce_24), cookie_ct)
+        // The cookie key is single-use: a replayed INITIATE finds it gone.
+        let mut cookie_key = self.cookie_key.take().ok_or(ZmtpError::Protocol)?;
+        let cookie_cipher = XSalsa20Poly1305::new(cookie_key.as_ref().into());
+        cookie_key.zeroize();
+        let mut cookie_pt = cookie_cipher
+            .decrypt(cookie_nonce_24.as_ref().into(), cookie_ct)
             .map_err(|_| {
                 warn!("[CURVE SERVER] Cookie decryption failed (tampered or wrong key)");
                 ZmtpError::AuthenticationFailed
@@ -1243,6 +1202,7 @@ impl CurveServer {
         recovered_c_prime_pk.copy_from_slice(&cookie_pt[..32]);
         let mut recovered_s_prime_sk = [0u8; 32];
         recovered_s_prime_sk.copy_from_slice(&cookie_pt[32..]);
+        cookie_pt.zeroize();
 
         // Verify cookie's c'.pk matches the HELLO c'.pk
         let c_prime_pk = self.client_short_public.ok_or(ZmtpError::Protocol)?;
@@ -1251,9 +1211,6 @@ impl CurveServer {
             return Err(ZmtpError::AuthenticationFailed);
         }
 
-        let mut c_pk = [0u8; 32];
-        c_pk.copy_from_slice(c_pk_buf);
-
         // Decrypt initiate_box: SalsaBox(c'→s') using s'_sk (from cookie) + c'_pk
         let mut initiate_nonce_24 = [0u8; 24];
         initiate_nonce_24[..16].copy_from_slice(b"CurveZMQINITIATE");
@@ -1270,8 +1227,8 @@ impl CurveServer {
             ZmtpError::AuthenticationFailed
         })?;
 
-        // initiate_pt = vouch(96) + metadata(0+)
-        if initiate_pt.len() < 96 {
+        // initiate_pt = C(32) + vouch(96) + metadata(0+)
+        if initiate_pt.len() < 128 {
             warn!(
                 "[CURVE SERVER] INITIATE plaintext too short: {}",
                 initiate_pt.len()
@@ -1279,26 +1236,26 @@ impl CurveServer {
             return Err(ZmtpError::Protocol);
         }
 
+        let mut c_pk = [0u8; 32];
+        c_pk.copy_from_slice(&initiate_pt[..32]);
+
         // Extract and verify vouch: vouch_nonce_16(16) + vouch_ct(80) = 96 bytes
-        let vouch_nonce_16 = &initiate_pt[..16];
-        let vouch_ct = &initiate_pt[16..96];
+        let vouch_nonce_16 = &initiate_pt[32..48];
+        let vouch_ct = &initiate_pt[48..128];
         let mut vouch_nonce_24 = [0u8; 24];
         vouch_nonce_24[..8].copy_from_slice(b"VOUCH---");
         vouch_nonce_24[8..].copy_from_slice(vouch_nonce_16);
 
-        // Decrypt vouch: SalsaBox(C→S) using S_sk + C_pk
-        let vouch_pt = salsa_decrypt(
-            &c_pk,
-            &self.server_keypair.secret.to_raw_bytes(),
-            &vouch_nonce_24,
-            vouch_ct,
-        )
-        .map_err(|_| {
-            warn!("[CURVE SERVER] Vouch verification failed");
-            ZmtpError::AuthenticationFailed
-        })?;
+        // Decrypt vouch: SalsaBox(C→s') using s'_sk + C_pk
+        let vouch_pt = salsa_decrypt(&c_pk, &recovered_s_prime_sk, &vouch_nonce_24, vouch_ct)
+            .map_err(|_| {
+                warn!("[CURVE SERVER] Vouch verification failed");
+                ZmtpError::AuthenticationFailed
+            });
+        recovered_s_prime_sk.zeroize();
+        let vouch_pt = vouch_pt?;
 
-        // vouch plaintext must be c'.pk(32) + s'.pk(32)
+        // vouch plaintext must be c'.pk(32) + S(32)
         if vouch_pt.len() != 64 {
             warn!(
                 "[CURVE SERVER] Vouch plaintext wrong size: {}",
@@ -1310,13 +1267,13 @@ impl CurveServer {
             warn!("[CURVE SERVER] Vouch c'.pk mismatch");
             return Err(ZmtpError::AuthenticationFailed);
         }
-        if &vouch_pt[32..] != self.server_short_keypair.public.as_bytes() {
-            warn!("[CURVE SERVER] Vouch s'.pk mismatch");
+        if &vouch_pt[32..] != self.server_keypair.public.as_bytes() {
+            warn!("[CURVE SERVER] Vouch was made out to a different server key");
             return Err(ZmtpError::AuthenticationFailed);
         }
 
-        // Parse metadata from initiate_pt[96..]
-        let metadata = &initiate_pt[96..];
+        // Parse metadata from initiate_pt[128..]
+        let metadata = &initiate_pt[128..];
         let (peer_st, peer_id) = decode_zmtp_props(metadata)?;
         if peer_st.is_none() {
             warn!("[CURVE SERVER] INITIATE missing Socket-Type in metadata");
@@ -1399,11 +1356,8 @@ impl CurveServer {
         // Nonce = "CurveZMQMESSAGES" + 8-byte counter (server→client)
         let mut nonce = [0u8; CURVE_NONCE_SIZE];
         nonce[..16].copy_from_slice(b"CurveZMQMESSAGES");
-        nonce[16..].copy_from_slice(&self.send_nonce.to_be_bytes());
-        self.send_nonce = self
-            .send_nonce
-            .checked_add(1)
-            .ok_or(CurveError::ProtocolViolation)?;
+        let counter = next_send_nonce(&mut self.send_nonce)?;
+        nonce[16..].copy_from_slice(&counter.to_be_bytes());
 
         let ciphertext = message_box.encrypt(plaintext, &nonce)?;
 
@@ -1423,18 +1377,7 @@ impl CurveServer {
             .as_ref()
             .ok_or(CurveError::ProtocolViolation)?;
 
-        let counter = u64::from_be_bytes(
-            parts
-                .short_nonce
-                .try_into()
-                .map_err(|_| CurveError::InvalidNonce)?,
-        );
-        if counter < self.recv_nonce {
-            return Err(CurveError::ProtocolViolation); // replay
-        }
-        self.recv_nonce = counter
-            .checked_add(1)
-            .ok_or(CurveError::ProtocolViolation)?;
+        let counter = check_recv_nonce(parts.short_nonce, self.recv_nonce)?;
 
         // Reconstruct full 24-byte nonce (client→server direction)
         let mut nonce = [0u8; CURVE_NONCE_SIZE];
@@ -1442,6 +1385,7 @@ impl CurveServer {
         nonce[16..].copy_from_slice(parts.short_nonce);
 
         let plaintext = message_box.decrypt(parts.ciphertext, &nonce)?;
+        self.recv_nonce = counter + 1;
         Ok(Bytes::from(plaintext))
     }
 }
@@ -1487,7 +1431,7 @@ where
     debug!("[CURVE SERVER ZAP] Starting ZAP-authenticated handshake");
 
     let mut curve_server = CurveServer::new(server_keypair, local_socket_type);
-    let result = curve_server.handshake(stream, timeout).await?;
+    let mut result = curve_server.handshake(stream, timeout).await?;
 
     let client_public_key = result.peer_public_key.ok_or(ZmtpError::Protocol)?;
 
@@ -1512,6 +1456,7 @@ where
             "[CURVE SERVER ZAP] Authentication successful for client key: {:?}",
             client_public_key
         );
+        result.user_id = Some(zap_response.user_id);
         Ok(result)
     } else {
         warn!(
@@ -1523,31 +1468,6 @@ where
     }
 }
 
-/// Send a ZMTP ERROR command frame to the peer (best-effort).
-async fn send_zmtp_error<S>(stream: &mut S, reason: &str)
-where
-    S: AsyncRead + AsyncWrite + Unpin,
-{
-    use compio_buf::BufResult;
-
-    // Cap reason at 248 bytes: body = 6 ("\x05ERROR") + 1 (len byte) + reason ≤ 255 (short-frame limit)
-    let reason_bytes = reason.as_bytes();
-    let reason_len = reason_bytes.len().min(248) as u8;
-
-    let mut body = BytesMut::with_capacity(7 + reason_len as usize);
-    body.extend_from_slice(b"\x05ERROR");
-    body.extend_from_slice(&[reason_len]);
-    body.extend_from_slice(&reason_bytes[..reason_len as usize]);
-    // body.len() ≤ 255, so the short ZMTP frame format (0x04 + 1-byte length) is always valid.
-
-    let body_len = body.len() as u8;
-    let mut frame = BytesMut::with_capacity(2 + body_len as usize);
-    frame.extend_from_slice(&[0x04, body_len]);
-    frame.extend_from_slice(&body);
-
-    let BufResult(_, _) = stream.write_all(frame.freeze()).await;
-}
-
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -1816,7 +1736,7 @@ mod tests {
 
         let mut nonce = [0u8; CURVE_NONCE_SIZE];
         nonce[..16].copy_from_slice(b"CurveZMQMESSAGEC");
-        nonce[16..].copy_from_slice(&2u64.to_be_bytes());
+        nonce[16..].copy_from_slice(&1u64.to_be_bytes());
 
         let ciphertext = box_.encrypt(b"client message", &nonce).unwrap();
         let mut frame = BytesMut::new();
@@ -1863,6 +1783,117 @@ mod tests {
         ));
     }
 
+    /// A server→client MESSAGE carrying `counter`, sealed with `key`.
+    fn server_message(key: &[u8; CURVE_KEY_SIZE], counter: u64, payload: &[u8]) -> BytesMut {
+        let mut nonce = [0u8; CURVE_NONCE_SIZE];
+        nonce[..16].copy_from_slice(b"CurveZMQMESSAGES");
+        nonce[16..].copy_from_slice(&counter.to_be_bytes());
+        let ciphertext = CurveBox::new(key).encrypt(payload, &nonce).unwrap();
+
+        let mut frame = BytesMut::new();
+        frame.extend_from_slice(CURVE_MESSAGE);
+        frame.extend_from_slice(&nonce[16..]);
+        frame.extend_from_slice(&ciphertext);
+        frame
+    }
+
+    #[test]
+    fn decrypt_message_rejects_out_of_order_nonces() {
+        let key = [44u8; CURVE_KEY_SIZE];
+        let client_keypair = CurveKeyPair::generate();
+        let server_public = CurveKeyPair::generate().public;
+        let mut client = CurveClient::new(client_keypair, server_public, "DEALER", None);
+        client.message_box = Some(CurveBox::new(&key));
+
+        let first = server_message(&key, 1, b"one");
+        let skipped = server_message(&key, 3, b"three");
+        assert_eq!(client.decrypt_message(&first).unwrap().as_ref(), b"one");
+
+        // A replay of the first frame and a frame from further ahead both fail.
+        assert!(matches!(
+            client.decrypt_message(&first),
+            Err(CurveError::NonceReplay)
+        ));
+        assert!(matches!(
+            client.decrypt_message(&skipped),
+            Err(CurveError::NonceReplay)
+        ));
+
+        // Neither moved the expected counter on.
+        let second = server_message(&key, 2, b"two");
+        assert_eq!(client.decrypt_message(&second).unwrap().as_ref(), b"two");
+    }
+
+    #[test]
+    fn failed_decrypt_does_not_consume_the_nonce() {
+        let key = [45u8; CURVE_KEY_SIZE];
+        let client_keypair = CurveKeyPair::generate();
+        let server_public = CurveKeyPair::generate().public;
+        let mut client = CurveClient::new(client_keypair, server_public, "DEALER", None);
+        client.message_box = Some(CurveBox::new(&key));
+
+        let forged = server_message(&[46u8; CURVE_KEY_SIZE], 1, b"forged");
+        assert!(matches!(
+            client.decrypt_message(&forged),
+            Err(CurveError::DecryptionFailed)
+        ));
+
+        let genuine = server_message(&key, 1, b"genuine");
+        assert_eq!(
+            client.decrypt_message(&genuine).unwrap().as_ref(),
+            b"genuine"
+        );
+    }
+
+    #[test]
+    fn message_cipher_rejects_replayed_frames() {
+        let key = [47u8; CURVE_KEY_SIZE];
+        let mut sender = CurveMessageCipher::new_server(CurveBox::new(&key), 1, 1);
+        let mut receiver = CurveMessageCipher::new_client(CurveBox::new(&key), 1, 1);
+
+        let first = sender.encrypt_frame(b"one", false).unwrap();
+        let second = sender.encrypt_frame(b"two", true).unwrap();
+        assert!(matches!(
+            receiver.decrypt_frame(&second),
+            Err(CurveError::NonceReplay)
+        ));
+        assert_eq!(
+            receiver.decrypt_frame(&first).unwrap(),
+            (false, Bytes::from_static(b"one"))
+        );
+        assert_eq!(
+            receiver.decrypt_frame(&second).unwrap(),
+            (true, Bytes::from_static(b"two"))
+        );
+        assert!(matches!(
+            receiver.decrypt_frame(&first),
+            Err(CurveError::NonceReplay)
+        ));
+    }
+
+    #[test]
+    fn encrypt_refuses_to_wrap_the_send_nonce() {
+        let key = [48u8; CURVE_KEY_SIZE];
+        let client_keypair = CurveKeyPair::generate();
+        let server_public = CurveKeyPair::generate().public;
+        let mut client = CurveClient::new(client_keypair, server_public, "DEALER", None);
+        client.message_box = Some(CurveBox::new(&key));
+        client.send_nonce = CURVE_MAX_SEND_NONCE - 1;
+
+        assert!(client.encrypt_message(b"last").is_ok());
+        assert!(matches!(
+            client.encrypt_message(b"one too many"),
+            Err(CurveError::NonceExhausted)
+        ));
+
+        let mut cipher =
+            CurveMessageCipher::new_client(CurveBox::new(&key), CURVE_MAX_SEND_NONCE, 1);
+        assert!(matches!(
+            cipher.encrypt_frame(b"frame", false),
+            Err(CurveError::NonceExhausted)
+        ));
+    }
+
     #[test]
     fn test_curve_zap_request() {
         let keypair = CurveKeyPair::generate();
@@ -4,6 +4,7 @@
 
 pub mod curve;
 pub mod plain;
+pub mod principal;
 pub mod protocol;
 pub mod zap;
 /// ZAP client for sending authentication requests.
@@ -13,8 +14,9 @@ pub mod zap_handler;
 
 pub use curve::{CurveKeyPair, CurvePublicKey, CurveSecretKey};
 pub use plain::{PlainAuthHandler, PlainCredentials, StaticPlainHandler};
+pub use principal::Principal;
 pub use zap::{ZAP_ENDPOINT, ZAP_VERSION, ZapMechanism, ZapRequest, ZapResponse, ZapStatus};
-pub use zap_client::ZapClient;
+pub use zap_client::{CachingZapClient, ZapCache, ZapClient};
 pub use zap_handler::{
     DefaultZapHandler, ZapHandler, ZapServer, spawn_zap_server, start_default_zap_server,
 };
@@ -13,6 +13,8 @@
 //!
 //! ## Protocol Flow
 //!
+//! Every command travels in a ZMTP command frame (RFC 24).
+//!
 //! **Client → Server: HELLO**
 //! ```text
 //! [0] 0x05 "HELLO"
@@ -32,11 +34,17 @@
 //!
 //! **Server → Client: WELCOME or ERROR**
 //! ```text
-//! WELCOME (if 200) or ERROR (if not 200)
+//! WELCOME (if 200) or ERROR with a reason (if not 200)
 //! ```
+//!
+//! After WELCOME the client sends INITIATE with its metadata and the server
+//! answers READY with its own; the handshake module drives that step.
 
 use crate::codec::ZmtpError;
-use crate::security::protocol::reject_immediately_available_trailing_bytes;
+use crate::security::protocol::{
+    parse_error_reason, read_zmtp_cmd, reject_immediately_available_trailing_bytes,
+    send_zmtp_error, write_zmtp_cmd,
+};
 use crate::security::zap::{ZapMechanism, ZapRequest, ZapStatus};
 use bytes::{Bytes, BytesMut};
 use compio_io::{AsyncRead, AsyncWrite};
@@ -176,9 +184,69 @@ impl PlainAuthHandler for StaticPlainHandler {
     }
 }
 
+/// Largest well-formed HELLO body: name, then two length-prefixed strings of
+/// at most 255 bytes each.
+const MAX_HELLO_BODY: usize = PLAIN_HELLO.len() + 2 * (1 + 255);
+
+/// Largest ERROR body the client accepts in place of WELCOME.
+const MAX_REPLY_BODY: usize = PLAIN_ERROR.len() + 1 + 255;
+
+/// Build the HELLO command body for `credentials`.
+fn build_hello(credentials: &PlainCredentials) -> Result<BytesMut, ZmtpError> {
+    let username = credentials.username.as_bytes();
+    let password = credentials.password.as_bytes();
+    if username.len() > 255 || password.len() > 255 {
+        return Err(ZmtpError::Protocol);
+    }
+    let mut hello =
+        BytesMut::with_capacity(PLAIN_HELLO.len() + 2 + username.len() + password.len());
+    hello.extend_from_slice(PLAIN_HELLO);
+    hello.extend_from_slice(&[username.len() as u8]);
+    hello.extend_from_slice(username);
+    hello.extend_from_slice(&[password.len() as u8]);
+    hello.extend_from_slice(password);
+    Ok(hello)
+}
+
+/// Split a HELLO command body into username and password.
+///
+/// The body must hold exactly the two length-prefixed strings; anything
+/// after the password is a protocol violation.
+fn parse_hello(body: &[u8]) -> Result<(String, String), ZmtpError> {
+    let rest = body.strip_prefix(PLAIN_HELLO).ok_or(ZmtpError::Protocol)?;
+    let (&username_len, rest) = rest.split_first().ok_or(ZmtpError::Protocol)?;
+    let (username, rest) = rest
+        .split_at_checked(username_len as usize)
+        .ok_or(ZmtpError::Protocol)?;
+    let (&password_len, password) = rest.split_first().ok_or(ZmtpError::Protocol)?;
+    if password.len() != password_len as usize {
+        return Err(ZmtpError::Protocol);
+    }
+    let username = String::from_utf8(username.to_vec()).map_err(|_| ZmtpError::Protocol)?;
+    let password = String::from_utf8(password.to_vec()).map_err(|_| ZmtpError::Protocol)?;
+    Ok((username, password))
+}
+
+/// Read the client's HELLO command and return its credentials.
+async fn recv_hello<S>(
+    stream: &mut S,
+    timeout: Option<Duration>,
+) -> Result<(String, String), ZmtpError>
+where
+    S: AsyncRead + Unpin,
+{
+    let body = read_zmtp_cmd(stream, timeout, MAX_HELLO_BODY).await?;
+    let credentials = parse_hello(&body).inspect_err(|_| {
+        warn!("[PLAIN SERVER] Malformed PLAIN HELLO command");
+    })?;
+    reject_immediately_available_trailing_bytes(stream, TRAILING_BYTE_CHECK_TIMEOUT).await?;
+    Ok(credentials)
+}
+
 /// PLAIN client handshake
 ///
-/// Sends HELLO with username/password, waits for WELCOME or ERROR.
+/// Sends HELLO with username/password, waits for WELCOME or ERROR. An ERROR
+/// reply is reported as [`ZmtpError::AuthenticationFailed`].
 pub async fn plain_client_handshake<S>(
     stream: &mut S,
     credentials: &PlainCredentials,
@@ -187,73 +255,31 @@ pub async fn plain_client_handshake<S>(
 where
     S: AsyncRead + AsyncWrite + Unpin,
 {
-    use compio_buf::BufResult;
-    use monocoque_core::timeout::{read_exact_with_timeout, write_all_with_timeout};
-
     debug!(
         "[PLAIN CLIENT] Starting PLAIN authentication for user: {}",
         credentials.username
     );
 
-    // Build HELLO command
-    let mut hello = BytesMut::new();
-    hello.extend_from_slice(PLAIN_HELLO);
+    let hello = build_hello(credentials)?;
+    write_zmtp_cmd(stream, &hello, timeout).await?;
 
-    // Username (length-prefixed)
-    let username_bytes = credentials.username.as_bytes();
-    if username_bytes.len() > 255 {
-        return Err(ZmtpError::Protocol);
+    let reply = read_zmtp_cmd(stream, timeout, MAX_REPLY_BODY).await?;
+    if reply == PLAIN_WELCOME {
+        debug!("[PLAIN CLIENT] Authentication successful");
+        return Ok(());
     }
-    hello.extend_from_slice(&[username_bytes.len() as u8]);
-    hello.extend_from_slice(username_bytes);
-
-    // Password (length-prefixed)
-    let password_bytes = credentials.password.as_bytes();
-    if password_bytes.len() > 255 {
-        return Err(ZmtpError::Protocol);
-    }
-    hello.extend_from_slice(&[password_bytes.len() as u8]);
-    hello.extend_from_slice(password_bytes);
-
-    // Send HELLO
-    let buf_result = write_all_with_timeout(stream, hello.freeze().to_vec(), timeout).await?;
-    let BufResult(result, _) = buf_result;
-    result?;
-
-    // Read the first byte to determine response type (command name length)
-    let len_buf = vec![0u8; 1];
-    let BufResult(res, len_buf) = read_exact_with_timeout(stream, len_buf, timeout).await?;
-    res?;
-    let cmd_len = len_buf[0] as usize;
-    if cmd_len == 0 || cmd_len > 32 {
+    if let Some(reason) = parse_error_reason(&reply) {
         warn!(
-            "[PLAIN CLIENT] Invalid PLAIN response command length: {}",
-            cmd_len
+            "[PLAIN CLIENT] Authentication failed: {}",
+            String::from_utf8_lossy(reason)
         );
-        return Err(ZmtpError::Protocol);
-    }
-    // Read command name
-    let cmd_buf = vec![0u8; cmd_len];
-    let BufResult(res, cmd_buf) = read_exact_with_timeout(stream, cmd_buf, timeout).await?;
-    res?;
-
-    match cmd_buf.as_slice() {
-        b"WELCOME" => {
-            debug!("[PLAIN CLIENT] Authentication successful");
-            Ok(())
-        }
-        b"ERROR" => {
-            warn!("[PLAIN CLIENT] Authentication failed");
-            Err(ZmtpError::AuthenticationFailed)
-        }
-        other => {
-            warn!(
-                "[PLAIN CLIENT] Invalid PLAIN response command: {:?}",
-                String::from_utf8_lossy(other)
-            );
-            Err(ZmtpError::Protocol)
-        }
+        return Err(ZmtpError::AuthenticationFailed);
     }
+    warn!(
+        "[PLAIN CLIENT] Invalid PLAIN response command: {:?}",
+        String::from_utf8_lossy(&reply)
+    );
+    Err(ZmtpError::Protocol)
 }
 
 /// PLAIN server handshake
@@ -270,53 +296,12 @@ where
     S: AsyncRead + AsyncWrite + Unpin,
     H: PlainAuthHandler,
 {
-    use compio_buf::BufResult;
-    use monocoque_core::timeout::{read_exact_with_timeout, write_all_with_timeout};
-
     debug!(
         "[PLAIN SERVER] Waiting for PLAIN HELLO from {}",
         peer_address
     );
 
-    // Read command header (6 bytes: \x05HELLO)
-    let header = vec![0u8; 6];
-    let buf_result = read_exact_with_timeout(stream, header, timeout).await?;
-    let BufResult(result, header) = buf_result;
-    result?;
-
-    if &header[..] != PLAIN_HELLO {
-        warn!("[PLAIN SERVER] Invalid PLAIN command header");
-        return Err(ZmtpError::Protocol);
-    }
-
-    // Read username length
-    let len_buf = vec![0u8; 1];
-    let buf_result = read_exact_with_timeout(stream, len_buf, timeout).await?;
-    let BufResult(result, len_buf) = buf_result;
-    result?;
-    let username_len = len_buf[0] as usize;
-
-    // Read username
-    let username_buf = vec![0u8; username_len];
-    let buf_result = read_exact_with_timeout(stream, username_buf, timeout).await?;
-    let BufResult(result, username_buf) = buf_result;
-    result?;
-    let username = String::from_utf8(username_buf).map_err(|_| ZmtpError::Protocol)?;
-
-    // Read password length
-    let len_buf = vec![0u8; 1];
-    let buf_result = read_exact_with_timeout(stream, len_buf, timeout).await?;
-    let BufResult(result, len_buf) = buf_result;
-    result?;
-    let password_len = len_buf[0] as usize;
-
-    // Read password
-    let password_buf = vec![0u8; password_len];
-    let buf_result = read_exact_with_timeout(stream, password_buf, timeout).await?;
-    let BufResult(result, password_buf) = buf_result;
-    result?;
-    let password = String::from_utf8(password_buf).map_err(|_| ZmtpError::Protocol)?;
-    reject_immediately_available_trailing_bytes(stream, TRAILING_BYTE_CHECK_TIMEOUT).await?;
+    let (username, password) = recv_hello(stream, timeout).await?;
 
     debug!("[PLAIN SERVER] Received credentials for user: {}", username);
 
@@ -330,23 +315,12 @@ where
                 "[PLAIN SERVER] Authentication successful for user: {}",
                 user_id
             );
-
-            // Send WELCOME
-            let buf_result =
-                write_all_with_timeout(stream, PLAIN_WELCOME.to_vec(), timeout).await?;
-            let BufResult(result, _) = buf_result;
-            result?;
-
+            write_zmtp_cmd(stream, PLAIN_WELCOME, timeout).await?;
             Ok(user_id)
         }
         Err(reason) => {
             warn!("[PLAIN SERVER] Authentication failed: {}", reason);
-
-            // Send ERROR
-            let buf_result = write_all_with_timeout(stream, PLAIN_ERROR.to_vec(), timeout).await?;
-            let BufResult(result, _) = buf_result;
-            result?;
-
+            send_zmtp_error(stream, &reason).await;
             Err(ZmtpError::AuthenticationFailed)
         }
     }
@@ -362,76 +336,59 @@ pub async fn plain_server_handshake_zap<S>(
     peer_address: &str,
     timeout: Option<Duration>,
 ) -> Result<String, ZmtpError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    plain_server_exchange_zap(stream, domain, peer_address, timeout)
+        .await
+        .map(|(_, user_id)| user_id)
+}
+
+/// [`plain_server_handshake_zap`], returning the client's username together
+/// with the user id ZAP assigned.
+pub(crate) async fn plain_server_exchange_zap<S>(
+    stream: &mut S,
+    domain: &str,
+    peer_address: &str,
+    timeout: Option<Duration>,
+) -> Result<(String, String), ZmtpError>
 where
     S: AsyncRead + AsyncWrite + Unpin,
 {
     use crate::security::zap_client::ZapClient;
-    use compio_buf::BufResult;
-    use monocoque_core::timeout::{read_exact_with_timeout, write_all_with_timeout};
 
     debug!(
         "[PLAIN SERVER ZAP] Waiting for PLAIN HELLO from {}",
         peer_address
     );
 
-    // Read command header (6 bytes: \x05HELLO)
-    let header = vec![0u8; 6];
-    let buf_result = read_exact_with_timeout(stream, header, timeout).await?;
-    let BufResult(result, header) = buf_result;
-    result?;
-
-    if &header[..] != PLAIN_HELLO {
-        warn!("[PLAIN SERVER ZAP] Invalid PLAIN command header");
-        return Err(ZmtpError::Protocol);
-    }
-
-    // Read username length
-    let len_buf = vec![0u8; 1];
-    let buf_result = read_exact_with_timeout(stream, len_buf, timeout).await?;
-    let BufResult(result, len_buf) = buf_result;
-    result?;
-    let username_len = len_buf[0] as usize;
-
-    // Read username
-    let username_buf = vec![0u8; username_len];
-    let buf_result = read_exact_with_timeout(stream, username_buf, timeout).await?;
-    let BufResult(result, username_buf) = buf_result;
-    result?;
-    let username = String::from_utf8(username_buf).map_err(|_| ZmtpError::Protocol)?;
-
-    // Read password length
-    let len_buf = vec![0u8; 1];
-    let buf_result = read_exact_with_timeout(stream, len_buf, timeout).await?;
-    let BufResult(result, len_buf) = buf_result;
-    result?;
-    let password_len = len_buf[0] as usize;
-
-    // Read password
-    let password_buf = vec![0u8; password_len];
-    let buf_result = read_exact_with_timeout(stream, password_buf, timeout).await?;
-    let BufResult(result, password_buf) = buf_result;
-    result?;
-    let password = String::from_utf8(password_buf).map_err(|_| ZmtpError::Protocol)?;
-    reject_immediately_available_trailing_bytes(stream, TRAILING_BYTE_CHECK_TIMEOUT).await?;
+    let (username, password) = recv_hello(stream, timeout).await?;
 
     debug!(
         "[PLAIN SERVER ZAP] Received credentials for user: {}, sending ZAP request",
         username
     );
 
-    // Create ZAP client and send authentication request
-    let mut zap_client = ZapClient::new(Duration::from_secs(5)).map_err(|_| {
+    // Create ZAP client and send authentication request. The round trip is
+    // bounded by the handshake timeout, as on the CURVE path.
+    let zap_timeout = timeout.unwrap_or(Duration::from_secs(5));
+    let Ok(mut zap_client) = ZapClient::new(zap_timeout) else {
         warn!("[PLAIN SERVER ZAP] Failed to connect to ZAP handler");
-        ZmtpError::AuthenticationFailed
-    })?;
+        send_zmtp_error(stream, "no ZAP handler").await;
+        return Err(ZmtpError::AuthenticationFailed);
+    };
 
-    let zap_response = zap_client
+    let zap_response = match zap_client
         .authenticate_plain(&username, &password, domain, peer_address)
         .await
-        .map_err(|e| {
+    {
+        Ok(response) => response,
+        Err(e) => {
             warn!("[PLAIN SERVER ZAP] ZAP request failed: {}", e);
-            ZmtpError::AuthenticationFailed
-        })?;
+            send_zmtp_error(stream, "ZAP request failed").await;
+            return Err(ZmtpError::AuthenticationFailed);
+        }
+    };
 
     // Check ZAP response status
     if matches!(zap_response.status_code, ZapStatus::Success) {
@@ -439,24 +396,14 @@ where
             "[PLAIN SERVER ZAP] Authentication successful for user: {}",
             zap_response.user_id
         );
-
-        // Send WELCOME
-        let buf_result = write_all_with_timeout(stream, PLAIN_WELCOME.to_vec(), timeout).await?;
-        let BufResult(result, _) = buf_result;
-        result?;
-
-        Ok(zap_response.user_id)
+        write_zmtp_cmd(stream, PLAIN_WELCOME, timeout).await?;
+        Ok((username, zap_response.user_id))
     } else {
         warn!(
             "[PLAIN SERVER ZAP] Authentication failed: {}",
             zap_response.status_text
         );
-
-        // Send ERROR
-        let buf_result = write_all_with_timeout(stream, PLAIN_ERROR.to_vec(), timeout).await?;
-        let BufResult(result, _) = buf_result;
-        result?;
-
+        send_zmtp_error(stream, &zap_response.status_text).await;
         Err(ZmtpError::AuthenticationFailed)
     }
 }
@@ -582,13 +529,15 @@ mod tests {
             let mut stream = TcpStream::connect(addr).await.unwrap();
             let mut hello = plain_hello(b"admin", b"secret");
             hello.extend_from_slice(b"\x05extra");
+            let mut frame = vec![0x04, hello.len() as u8];
+            frame.extend_from_slice(&hello);
             let BufResult(write_result, _) =
-                write_all_with_timeout(&mut stream, hello, Some(Duration::from_secs(1)))
+                write_all_with_timeout(&mut stream, frame, Some(Duration::from_secs(1)))
                     .await
                     .unwrap();
             write_result.unwrap();
 
-            let response = vec![0u8; PLAIN_WELCOME.len()];
+            let response = vec![0u8; 2 + PLAIN_WELCOME.len()];
             let BufResult(read_result, response) =
                 read_exact_with_timeout(&mut stream, response, Some(Duration::from_secs(1)))
                     .await
@@ -597,7 +546,7 @@ mod tests {
 
             let result = monocoque_core::rt::join(server_task).await;
             assert!(
-                result.is_err() && response.as_slice() != PLAIN_WELCOME,
+                result.is_err() && response[2..] != *PLAIN_WELCOME,
                 "PLAIN server authenticated a HELLO command with trailing credential bytes"
             );
         });
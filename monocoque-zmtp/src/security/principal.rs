@@ -0,0 +1,89 @@
+//! Authenticated identity of a peer.
+//!
+//! A server-side handshake that authenticates its peer records who the peer
+//! turned out to be as a [`Principal`]. It stays with the connection, so a
+//! multi-peer socket can tell which principal sent each message without the
+//! application keeping its own table from routing identity to credentials.
+
+use std::fmt;
+
+/// Who an authenticated peer is.
+///
+/// When a ZAP handler assigned a user id, that id is the principal; otherwise
+/// it is what the mechanism itself authenticated.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Principal {
+    /// Long-term public key of a CURVE client.
+    CurveKey([u8; 32]),
+    /// Username of a PLAIN client.
+    PlainUser(String),
+    /// User id assigned by the ZAP handler.
+    ZapUserId(String),
+}
+
+impl Principal {
+    /// Principal for a peer authenticated with `key`, or with the user id
+    /// ZAP assigned it, if non-empty.
+    #[must_use]
+    pub fn curve(key: [u8; 32], zap_user_id: Option<String>) -> Self {
+        match zap_user_id.filter(|id| !id.is_empty()) {
+            Some(id) => Self::ZapUserId(id),
+            None => Self::CurveKey(key),
+        }
+    }
+
+    /// Principal for a PLAIN peer that logged in as `username`, or the user
+    /// id ZAP assigned it, if non-empty.
+    #[must_use]
+    pub fn plain(username: String, zap_user_id: Option<String>) -> Self {
+        match zap_user_id.filter(|id| !id.is_empty()) {
+            Some(id) => Self::ZapUserId(id),
+            None => Self::PlainUser(username),
+        }
+    }
+}
+
+impl fmt::Debug for Principal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CurveKey(key) => {
+                f.write_str("CurveKey(")?;
+                for b in key {
+                    write!(f, "{b:02x}")?;
+                }
+                f.write_str(")")
+            }
+            Self::PlainUser(name) => f.debug_tuple("PlainUser").field(name).finish(),
+            Self::ZapUserId(id) => f.debug_tuple("ZapUserId").field(id).finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zap_user_id_takes_precedence() {
+        assert_eq!(
+            Principal::curve([7; 32], Some("alice".into())),
+            Principal::ZapUserId("alice".into())
+        );
+        assert_eq!(
+            Principal::curve([7; 32], Some(String::new())),
+            Principal::CurveKey([7; 32])
+        );
+        assert_eq!(
+            Principal::plain("bob".into(), None),
+            Principal::PlainUser("bob".into())
+        );
+    }
+
+    #[test]
+    fn debug_prints_curve_key_as_hex() {
+        let mut key = [0; 32];
+        key[0] = 0xab;
+        let debug = format!("{:?}", Principal::CurveKey(key));
+        assert!(debug.starts_with("CurveKey(ab00"), "{debug}");
+    }
+}
@@ -2,9 +2,9 @@
 
 use crate::codec::ZmtpError;
 use crate::session::SocketType;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use compio_buf::BufResult;
-use compio_io::AsyncRead;
+use compio_io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use monocoque_core::timeout::read_exact_with_timeout;
 use std::time::Duration;
 use tracing::warn;
@@ -48,6 +48,149 @@ where
         })
 }
 
+/// Read one ZMTP command frame and return its body.
+/// Rejects data frames (command flag 0x04 must be set).
+pub async fn read_zmtp_cmd<S>(
+    stream: &mut S,
+    timeout: Option<Duration>,
+    max_body: usize,
+) -> Result<Vec<u8>, ZmtpError>
+where
+    S: AsyncRead + Unpin,
+{
+    use compio_buf::BufResult;
+    use monocoque_core::timeout::read_exact_with_timeout;
+
+    let BufResult(r, flags_buf) = read_exact_with_timeout(stream, [0u8; 1], timeout)
+        .await
+        .map_err(ZmtpError::from)?;
+    r?;
+    let flags = flags_buf[0];
+
+    if flags & 0x04 == 0 {
+        warn!(
+            "Expected ZMTP command frame, got data frame (flags=0x{:02x})",
+            flags
+        );
+        return Err(ZmtpError::Protocol);
+    }
+
+    let body_len: usize = if flags & 0x02 != 0 {
+        let BufResult(r, len_buf) = read_exact_with_timeout(stream, [0u8; 8], timeout)
+            .await
+            .map_err(ZmtpError::from)?;
+        r?;
+        let raw_len = u64::from_be_bytes(len_buf);
+        if raw_len > usize::MAX as u64 {
+            warn!("ZMTP long-frame length overflows usize: {}", raw_len);
+            return Err(ZmtpError::Protocol);
+        }
+        raw_len as usize
+    } else {
+        let BufResult(r, len_buf) = read_exact_with_timeout(stream, [0u8; 1], timeout)
+            .await
+            .map_err(ZmtpError::from)?;
+        r?;
+        len_buf[0] as usize
+    };
+
+    if body_len > max_body {
+        warn!("ZMTP command body too large: {} > {}", body_len, max_body);
+        return Err(ZmtpError::Protocol);
+    }
+
+    let BufResult(r, body) = read_exact_with_timeout(stream, vec![0u8; body_len], timeout)
+        .await
+        .map_err(ZmtpError::from)?;
+    r?;
+    Ok(body)
+}
+
+/// Write a slice as a ZMTP command frame (flag + length + body).
+pub async fn write_zmtp_cmd<S>(
+    stream: &mut S,
+    body: &[u8],
+    timeout: Option<Duration>,
+) -> Result<(), ZmtpError>
+where
+    S: AsyncWrite + Unpin,
+{
+    use compio_buf::BufResult;
+    use monocoque_core::timeout::write_all_with_timeout;
+
+    let len = body.len();
+    let mut frame = BytesMut::with_capacity(if len <= 255 { 2 + len } else { 9 + len });
+    if len <= 255 {
+        frame.extend_from_slice(&[0x04, len as u8]);
+    } else {
+        frame.extend_from_slice(&[0x06]);
+        frame.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+    frame.extend_from_slice(body);
+
+    let BufResult(r, _) = write_all_with_timeout(stream, frame.freeze().to_vec(), timeout)
+        .await
+        .map_err(ZmtpError::from)?;
+    r.map_err(Into::into)
+}
+
+/// Longest reason [`build_error_command`] keeps, so the body (`\x05ERROR`,
+/// length byte, reason) always fits a short frame.
+const MAX_ERROR_REASON: usize = 255 - 7;
+
+/// Build an ERROR command body (`\x05ERROR` + 1-byte length + reason).
+///
+/// Reasons longer than 248 bytes are truncated so the command always fits a
+/// short frame.
+pub fn build_error_command(reason: &str) -> Bytes {
+    let reason = &reason.as_bytes()[..reason.len().min(MAX_ERROR_REASON)];
+    let mut body = BytesMut::with_capacity(7 + reason.len());
+    body.extend_from_slice(b"\x05ERROR");
+    body.extend_from_slice(&[reason.len() as u8]);
+    body.extend_from_slice(reason);
+    body.freeze()
+}
+
+/// Send a ZMTP ERROR command frame to the peer (best-effort).
+///
+/// Servers call this with the reason for rejecting a peer just before they
+/// close the connection, so the peer can report why instead of seeing EOF.
+pub async fn send_zmtp_error<S>(stream: &mut S, reason: &str)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    // body.len() ≤ 255, so the short ZMTP frame format (0x04 + 1-byte length) is always valid.
+    let body = build_error_command(reason);
+    let mut frame = BytesMut::with_capacity(2 + body.len());
+    frame.extend_from_slice(&[0x04, body.len() as u8]);
+    frame.extend_from_slice(&body);
+
+    let BufResult(_, _) = stream.write_all(frame.freeze()).await;
+}
+
+/// The name libzmq 4.3's PLAIN mechanism puts on its ERROR command: the C
+/// literal `"\x05ERROR"` reads as the single escape `\x05E` (`^`) followed
+/// by `RROR`.
+const LIBZMQ_PLAIN_ERROR: &[u8] = b"^RROR";
+
+/// The reason text of an ERROR command body (`\x05ERROR` + reason), or
+/// `None` if `body` is not a well-formed ERROR command.
+pub fn parse_error_reason(body: &[u8]) -> Option<&[u8]> {
+    let rest = body
+        .strip_prefix(b"\x05ERROR")
+        .or_else(|| body.strip_prefix(LIBZMQ_PLAIN_ERROR))?;
+    let (&len, reason) = rest.split_first()?;
+    (reason.len() == len as usize).then_some(reason)
+}
+
+/// [`ZmtpError::PeerError`] carrying the reason of an ERROR command body, or
+/// `None` if `body` is some other command.
+pub fn peer_error(body: &[u8]) -> Option<ZmtpError> {
+    parse_error_reason(body).map(|reason| ZmtpError::PeerError {
+        reason: String::from_utf8_lossy(reason).into_owned(),
+    })
+}
+
 /// Parse a READY command body and return the socket type and optional identity.
 pub fn parse_ready_command(body: &Bytes) -> Result<(SocketType, Option<Bytes>), ZmtpError> {
     if body.len() < 6 {
@@ -97,7 +240,7 @@ pub fn parse_ready_command(body: &Bytes) -> Result<(SocketType, Option<Bytes>),
         ]) as usize;
         offset += 4;
 
-        if offset + value_len > body.len() {
+        if value_len > body.len() - offset {
             return Err(ZmtpError::Protocol);
         }
 
@@ -149,3 +292,32 @@ fn parse_socket_type(bytes: &[u8]) -> Result<SocketType, ZmtpError> {
         _ => Err(ZmtpError::Protocol),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_reason_reads_rfc_and_libzmq_plain_names() {
+        assert_eq!(parse_error_reason(b"\x05ERROR\x03400"), Some(&b"400"[..]));
+        assert_eq!(parse_error_reason(b"^RROR\x03400"), Some(&b"400"[..]));
+        assert_eq!(parse_error_reason(b"\x05ERROR\x04400"), None);
+        assert_eq!(parse_error_reason(b"\x07WELCOME"), None);
+    }
+
+    #[test]
+    fn error_command_round_trips_and_truncates_long_reasons() {
+        let body = build_error_command("Invalid credentials");
+        assert_eq!(&body[..], b"\x05ERROR\x13Invalid credentials");
+        assert!(matches!(
+            peer_error(&body),
+            Some(ZmtpError::PeerError { reason }) if reason == "Invalid credentials"
+        ));
+
+        let long = "x".repeat(400);
+        let body = build_error_command(&long);
+        assert_eq!(body.len(), 255);
+        assert_eq!(parse_error_reason(&body).unwrap().len(), MAX_ERROR_REASON);
+        assert!(peer_error(b"\x05READY").is_none());
+    }
+}
@@ -52,7 +52,7 @@ pub const ZAP_VERSION: &str = "1.0";
 pub const ZAP_ENDPOINT: &str = "inproc://zeromq.zap.01";
 
 /// Authentication mechanism
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ZapMechanism {
     /// No authentication (NULL mechanism).
     Null,
@@ -444,7 +444,7 @@ impl ZapResponse {
             cursor += 4;
 
             // Read value
-            if cursor + value_len > data.len() {
+            if value_len > data.len() - cursor {
                 return Err("Invalid metadata: value out of bounds".to_string());
             }
             let value = String::from_utf8(data[cursor..cursor + value_len].to_vec())
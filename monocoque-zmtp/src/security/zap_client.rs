@@ -12,12 +12,22 @@
 /// (equivalent to a 403/400 response).  This implements the correct
 /// "default-deny" security posture: if there is no handler to approve the
 /// connection it must be denied, not silently accepted.
+///
+/// ## Caching
+///
+/// [`CachingZapClient`] puts a [`ZapCache`] in front of a `ZapClient` so a
+/// peer that reconnects with the same credentials inside the TTL is approved
+/// without another round trip to the handler.
 use crate::security::zap::{ZapMechanism, ZapRequest, ZapResponse, ZapStatus};
 use crate::{DealerSocket, inproc_stream::InprocStream};
 use bytes::Bytes;
 use monocoque_core::options::SocketOptions;
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::io;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// ZAP client for sending authentication requests
 ///
@@ -220,6 +230,187 @@ impl ZapClient {
     }
 }
 
+/// Cache key: the request's domain, peer address, identity and mechanism plus
+/// a SHA-256 digest of its credentials, so raw passwords and keys are never
+/// held by the cache.
+///
+/// The address and identity are part of the key because a handler may decide
+/// on them alone, as with an IP allowlist for NULL.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ZapCacheKey {
+    domain: String,
+    address: String,
+    identity: Bytes,
+    mechanism: ZapMechanism,
+    credential_hash: [u8; 32],
+}
+
+impl ZapCacheKey {
+    fn new(request: &ZapRequest) -> Self {
+        let mut hasher = Sha256::new();
+        if let [client_key] = request.credentials.as_slice()
+            && request.mechanism == ZapMechanism::Curve
+        {
+            // sha256(client_public_key)
+            hasher.update(client_key);
+        } else {
+            // Length-prefix each frame so ("ab", "c") and ("a", "bc") differ.
+            for frame in &request.credentials {
+                hasher.update((frame.len() as u64).to_be_bytes());
+                hasher.update(frame);
+            }
+        }
+        Self {
+            domain: request.domain.clone(),
+            address: request.address.clone(),
+            identity: request.identity.clone(),
+            mechanism: request.mechanism.clone(),
+            credential_hash: hasher.finalize().into(),
+        }
+    }
+}
+
+/// Successful ZAP responses, keyed by domain, peer address, identity,
+/// mechanism and credential hash.
+///
+/// Clones share the same entries, so one cache can serve every connection a
+/// server accepts. Only `ZapStatus::Success` responses are stored: a rejected
+/// peer is asked about again on its next attempt. An approval is reused only
+/// for a request from the same address and identity, so verdicts that depend
+/// on where the peer connects from are cached correctly.
+#[derive(Debug, Clone)]
+pub struct ZapCache {
+    ttl: Duration,
+    entries: Arc<Mutex<HashMap<ZapCacheKey, (ZapResponse, Instant)>>>,
+}
+
+impl ZapCache {
+    /// Create an empty cache whose entries live for `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// How long a cached approval is reused.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Number of live and not yet evicted entries.
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Whether the cache holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+
+    /// Drop every entry older than the TTL.
+    pub fn evict_expired(&self) {
+        let ttl = self.ttl;
+        self.entries
+            .lock()
+            .retain(|_, (_, inserted)| inserted.elapsed() < ttl);
+    }
+
+    /// Look up a cached approval for `request`, evicting expired entries first.
+    ///
+    /// The returned response carries `request`'s request ID.
+    pub fn get(&self, request: &ZapRequest) -> Option<ZapResponse> {
+        self.evict_expired();
+        let key = ZapCacheKey::new(request);
+        let mut response = self.entries.lock().get(&key)?.0.clone();
+        response.request_id.clone_from(&request.request_id);
+        Some(response)
+    }
+
+    /// Remember `response` for `request` if it is a success; anything else is
+    /// ignored.
+    pub fn insert(&self, request: &ZapRequest, response: &ZapResponse) {
+        if response.status_code != ZapStatus::Success {
+            return;
+        }
+        self.entries.lock().insert(
+            ZapCacheKey::new(request),
+            (response.clone(), Instant::now()),
+        );
+    }
+}
+
+/// [`ZapClient`] that answers from a [`ZapCache`] before asking the handler.
+pub struct CachingZapClient {
+    inner: ZapClient,
+    cache: ZapCache,
+}
+
+impl CachingZapClient {
+    /// Wrap `inner`, sharing `cache` with any other clients holding a clone.
+    pub const fn new(inner: ZapClient, cache: ZapCache) -> Self {
+        Self { inner, cache }
+    }
+
+    /// The cache consulted by this client.
+    pub const fn cache(&self) -> &ZapCache {
+        &self.cache
+    }
+
+    /// Authenticate `request`, issuing a ZAP request only on a cache miss.
+    ///
+    /// Successful responses from the handler are cached; failures and errors
+    /// are returned as [`ZapClient::authenticate`] returns them.
+    pub async fn authenticate(&mut self, request: &ZapRequest) -> io::Result<ZapResponse> {
+        if let Some(response) = self.cache.get(request) {
+            return Ok(response);
+        }
+        let response = self.inner.authenticate(request).await?;
+        self.cache.insert(request, &response);
+        Ok(response)
+    }
+
+    /// Cached counterpart of [`ZapClient::authenticate_plain`].
+    pub async fn authenticate_plain(
+        &mut self,
+        username: &str,
+        password: &str,
+        domain: &str,
+        address: &str,
+    ) -> io::Result<ZapResponse> {
+        let request = ZapRequest::new_with_unique_id(
+            domain,
+            address,
+            Bytes::new(),
+            ZapMechanism::Plain,
+            vec![
+                Bytes::from(username.as_bytes().to_vec()),
+                Bytes::from(password.as_bytes().to_vec()),
+            ],
+        );
+
+        self.authenticate(&request).await
+    }
+
+    /// Cached counterpart of [`ZapClient::authenticate_curve`].
+    pub async fn authenticate_curve(
+        &mut self,
+        client_key: &[u8; 32],
+        domain: &str,
+        address: &str,
+    ) -> io::Result<ZapResponse> {
+        let request = ZapRequest::new_with_unique_id(
+            domain,
+            address,
+            Bytes::new(),
+            ZapMechanism::Curve,
+            vec![Bytes::from(client_key.to_vec())],
+        );
+
+        self.authenticate(&request).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,4 +486,86 @@ mod tests {
         );
         assert_eq!(resp.request_id, "42");
     }
+
+    fn curve_request(domain: &str, key: u8) -> ZapRequest {
+        ZapRequest::new_with_unique_id(
+            domain,
+            "127.0.0.1",
+            Bytes::new(),
+            ZapMechanism::Curve,
+            vec![Bytes::from(vec![key; 32])],
+        )
+    }
+
+    #[test]
+    fn test_zap_cache_keeps_only_successes() {
+        let cache = ZapCache::new(Duration::from_secs(30));
+        let request = curve_request("global", 1);
+
+        cache.insert(&request, &ZapResponse::failure("1", "denied"));
+        assert!(cache.get(&request).is_none(), "failures must not be cached");
+
+        cache.insert(&request, &ZapResponse::success("1", "alice"));
+        let again = curve_request("global", 1);
+        let hit = cache.get(&again).expect("success should be cached");
+        assert_eq!(hit.user_id, "alice");
+        assert_eq!(hit.request_id, again.request_id);
+
+        // Another key or another domain is a miss.
+        assert!(cache.get(&curve_request("global", 2)).is_none());
+        assert!(cache.get(&curve_request("other", 1)).is_none());
+    }
+
+    #[test]
+    fn test_zap_cache_keys_on_address_and_identity() {
+        let null = |address: &str, identity: &'static [u8]| {
+            ZapRequest::new_with_unique_id(
+                "global",
+                address,
+                Bytes::from_static(identity),
+                ZapMechanism::Null,
+                vec![],
+            )
+        };
+        let cache = ZapCache::new(Duration::from_secs(30));
+        cache.insert(&null("10.0.0.1", b""), &ZapResponse::success("1", "lan"));
+
+        assert!(cache.get(&null("10.0.0.1", b"")).is_some());
+        assert!(
+            cache.get(&null("203.0.113.9", b"")).is_none(),
+            "an approval for one address must not admit another"
+        );
+        assert!(cache.get(&null("10.0.0.1", b"other")).is_none());
+    }
+
+    #[test]
+    fn test_zap_cache_evicts_expired_entries() {
+        let cache = ZapCache::new(Duration::from_millis(20));
+        let request = curve_request("global", 1);
+        cache.insert(&request, &ZapResponse::success("1", "alice"));
+        assert_eq!(cache.len(), 1);
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get(&request).is_none());
+        assert!(cache.is_empty(), "lookup should evict expired entries");
+    }
+
+    #[test]
+    fn test_zap_cache_key_hashes_credentials() {
+        let request = curve_request("global", 7);
+        let key = ZapCacheKey::new(&request);
+        let digest: [u8; 32] = Sha256::digest([7u8; 32]).into();
+        assert_eq!(key.credential_hash, digest);
+
+        let split = |user: &'static [u8], pass: &'static [u8]| {
+            ZapCacheKey::new(&ZapRequest::new_with_unique_id(
+                "global",
+                "127.0.0.1",
+                Bytes::new(),
+                ZapMechanism::Plain,
+                vec![Bytes::from_static(user), Bytes::from_static(pass)],
+            ))
+        };
+        assert_ne!(split(b"ab", b"c"), split(b"a", b"bc"));
+    }
 }
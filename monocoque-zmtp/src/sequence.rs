@@ -0,0 +1,341 @@
+//! Message sequence stamps for dropping resent duplicates.
+//!
+//! With the `dedupe_window` option set, DEALER and ROUTER sockets advertise
+//! the `X-Sequence` READY property. On a connection where both peers did, each
+//! message is sent with a leading 16-byte frame: the sending socket's epoch
+//! (random, fixed for the socket's lifetime so it survives reconnects) and a
+//! per-socket sequence number, both big-endian `u64`. The receiver strips the
+//! frame and drops any message whose number it already saw among the last
+//! `dedupe_window` from that epoch.
+//!
+//! Epochs come from the peer, so the receiver bounds how many it tracks:
+//! at most [`MAX_EPOCHS`], evicting the least recently used, and a
+//! connection's epoch is released when the connection ends (see
+//! [`Deduper::release`]).
+
+use bytes::{BufMut, Bytes, BytesMut};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::io;
+
+/// READY property advertising sequence stamps.
+pub const SEQUENCE_PROPERTY: &str = "X-Sequence";
+
+/// Wire length of a sequence frame: epoch and sequence number.
+const STAMP_LEN: usize = 16;
+
+/// Most epochs a [`Deduper`] tracks at once.
+pub const MAX_EPOCHS: usize = 1024;
+
+/// Released epochs kept so a peer that reconnects soon after still has its
+/// duplicates caught.
+const RETAINED_RELEASED: usize = 64;
+
+/// A fresh random epoch for a sending socket.
+pub fn new_epoch() -> u64 {
+    use rand::Rng;
+    rand::thread_rng().r#gen()
+}
+
+/// The sequence frame for message `seq` from the socket with `epoch`.
+pub fn stamp(epoch: u64, seq: u64) -> Bytes {
+    let mut frame = BytesMut::with_capacity(STAMP_LEN);
+    frame.put_u64(epoch);
+    frame.put_u64(seq);
+    frame.freeze()
+}
+
+/// A socket's sequencing state, kept across its reconnects.
+#[derive(Debug)]
+pub struct Sequencing {
+    epoch: u64,
+    next: u64,
+    dedupe: Deduper,
+}
+
+impl Sequencing {
+    pub fn new(window: usize) -> Self {
+        Self {
+            epoch: new_epoch(),
+            next: 0,
+            dedupe: Deduper::new(window),
+        }
+    }
+
+    /// Take the number for the next outgoing message.
+    pub const fn next_sequence(&mut self) -> u64 {
+        let seq = self.next;
+        self.next = self.next.wrapping_add(1);
+        seq
+    }
+
+    /// The sequence frame for this socket's message `seq`.
+    pub fn stamp(&self, seq: u64) -> Bytes {
+        stamp(self.epoch, seq)
+    }
+
+    pub fn admit(&mut self, msg: &mut Vec<Bytes>) -> io::Result<bool> {
+        self.dedupe.admit(msg)
+    }
+
+    pub const fn duplicates(&self) -> u64 {
+        self.dedupe.duplicates()
+    }
+}
+
+/// Receive-side duplicate filter, one sliding window per sending epoch.
+#[derive(Debug)]
+pub struct Deduper {
+    window: usize,
+    epochs: HashMap<u64, SeqWindow>,
+    /// Epochs whose connection ended, oldest first.
+    released: VecDeque<u64>,
+    /// Bumped on every admit, to find the least recently used window.
+    clock: u64,
+    duplicates: u64,
+}
+
+/// Sequence numbers seen within `window` of the highest one.
+#[derive(Debug, Default)]
+struct SeqWindow {
+    highest: u64,
+    seen: BTreeSet<u64>,
+    last_used: u64,
+}
+
+impl Deduper {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            epochs: HashMap::new(),
+            released: VecDeque::new(),
+            clock: 0,
+            duplicates: 0,
+        }
+    }
+
+    /// Messages dropped as duplicates so far.
+    pub const fn duplicates(&self) -> u64 {
+        self.duplicates
+    }
+
+    /// Epochs with a window currently tracked.
+    #[cfg(test)]
+    fn tracked_epochs(&self) -> usize {
+        self.epochs.len()
+    }
+
+    /// Note that the connection sending from `epoch` has ended.
+    ///
+    /// The window is kept for a while, so a peer that reconnects and resends
+    /// still has its duplicates dropped; once [`RETAINED_RELEASED`] later
+    /// epochs have been released, or the cap needs room, it is removed.
+    pub fn release(&mut self, epoch: u64) {
+        if !self.epochs.contains_key(&epoch) || self.released.contains(&epoch) {
+            return;
+        }
+        self.released.push_back(epoch);
+        if self.released.len() > RETAINED_RELEASED
+            && let Some(oldest) = self.released.pop_front()
+        {
+            self.epochs.remove(&oldest);
+        }
+    }
+
+    /// Like [`admit`](Self::admit), for a connection allowed a single epoch:
+    /// the first stamp binds `bound`, and a stamp from any other epoch is
+    /// rejected.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidData` if the message has no sequence frame or its epoch is not
+    /// the bound one.
+    pub fn admit_bound(
+        &mut self,
+        msg: &mut Vec<Bytes>,
+        bound: &mut Option<u64>,
+    ) -> io::Result<bool> {
+        let (epoch, _) = parse_stamp(msg)?;
+        match *bound {
+            Some(expected) if expected != epoch => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "peer switched sequence epochs mid-connection",
+            )),
+            _ => {
+                *bound = Some(epoch);
+                self.admit(msg)
+            }
+        }
+    }
+
+    /// Strip the sequence frame from `msg` and decide whether to deliver it.
+    ///
+    /// Returns `Ok(false)` for a duplicate, which includes anything older
+    /// than the window since it can no longer be told apart from one.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidData` if the message does not start with a sequence frame.
+    pub fn admit(&mut self, msg: &mut Vec<Bytes>) -> io::Result<bool> {
+        let (epoch, seq) = parse_stamp(msg)?;
+        msg.remove(0);
+
+        if !self.epochs.contains_key(&epoch) && self.epochs.len() >= MAX_EPOCHS {
+            self.evict_one();
+        }
+        if let Some(at) = self.released.iter().position(|&e| e == epoch) {
+            // The peer is back: its window is live again.
+            self.released.remove(at);
+        }
+        self.clock += 1;
+        let window = self.window as u64;
+        let seen = self.epochs.entry(epoch).or_default();
+        seen.last_used = self.clock;
+        let fresh = if seen.seen.is_empty() {
+            true
+        } else if seq.saturating_add(window) <= seen.highest {
+            false
+        } else {
+            !seen.seen.contains(&seq)
+        };
+        if !fresh {
+            self.duplicates += 1;
+            return Ok(false);
+        }
+
+        seen.seen.insert(seq);
+        if seq > seen.highest || seen.seen.len() == 1 {
+            seen.highest = seq;
+            let floor = seq.saturating_sub(window - 1);
+            seen.seen = seen.seen.split_off(&floor);
+        }
+        Ok(true)
+    }
+
+    /// Make room for one more epoch: the oldest released one if any,
+    /// otherwise the least recently used.
+    fn evict_one(&mut self) {
+        let victim = self.released.pop_front().or_else(|| {
+            self.epochs
+                .iter()
+                .min_by_key(|(_, w)| w.last_used)
+                .map(|(&epoch, _)| epoch)
+        });
+        if let Some(epoch) = victim {
+            self.epochs.remove(&epoch);
+        }
+    }
+}
+
+/// Read the epoch and sequence number from the first frame of `msg`.
+fn parse_stamp(msg: &[Bytes]) -> io::Result<(u64, u64)> {
+    match msg.first() {
+        Some(frame) if frame.len() == STAMP_LEN => {
+            let (epoch, seq) = frame.split_at(8);
+            Ok((
+                u64::from_be_bytes(epoch.try_into().expect("8-byte half")),
+                u64::from_be_bytes(seq.try_into().expect("8-byte half")),
+            ))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "message is missing its negotiated sequence frame",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stamped(epoch: u64, seq: u64) -> Vec<Bytes> {
+        vec![stamp(epoch, seq), Bytes::from_static(b"body")]
+    }
+
+    #[test]
+    fn admits_each_sequence_once_and_strips_the_stamp() {
+        let mut dedupe = Deduper::new(8);
+        let mut msg = stamped(7, 0);
+        assert!(dedupe.admit(&mut msg).unwrap());
+        assert_eq!(msg, vec![Bytes::from_static(b"body")]);
+
+        for seq in [1, 3, 2] {
+            assert!(dedupe.admit(&mut stamped(7, seq)).unwrap());
+        }
+        for seq in [0, 2, 3] {
+            assert!(!dedupe.admit(&mut stamped(7, seq)).unwrap());
+        }
+        assert_eq!(dedupe.duplicates(), 3);
+    }
+
+    #[test]
+    fn windows_are_per_epoch_and_drop_what_fell_out() {
+        let mut dedupe = Deduper::new(4);
+        for seq in 0..10 {
+            assert!(dedupe.admit(&mut stamped(1, seq)).unwrap());
+        }
+        // Another sending socket starts its own window.
+        assert!(dedupe.admit(&mut stamped(2, 0)).unwrap());
+        // 6..=9 are inside the window, 5 and older are not.
+        assert!(!dedupe.admit(&mut stamped(1, 9)).unwrap());
+        assert!(!dedupe.admit(&mut stamped(1, 5)).unwrap());
+        assert!(dedupe.admit(&mut stamped(1, 11)).unwrap());
+        assert!(dedupe.admit(&mut stamped(1, 10)).unwrap());
+    }
+
+    #[test]
+    fn tracked_epochs_stay_bounded() {
+        let mut dedupe = Deduper::new(4);
+        // Keep epoch 0 in use throughout; it must survive the churn.
+        for epoch in 1..=(2 * MAX_EPOCHS as u64) {
+            assert!(dedupe.admit(&mut stamped(0, epoch)).unwrap());
+            assert!(dedupe.admit(&mut stamped(epoch, 0)).unwrap());
+            assert!(dedupe.tracked_epochs() <= MAX_EPOCHS);
+        }
+        assert_eq!(dedupe.tracked_epochs(), MAX_EPOCHS);
+        assert!(
+            !dedupe
+                .admit(&mut stamped(0, 2 * MAX_EPOCHS as u64))
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn released_epochs_are_removed_unless_reclaimed() {
+        let mut dedupe = Deduper::new(4);
+        assert!(dedupe.admit(&mut stamped(1, 0)).unwrap());
+        dedupe.release(1);
+        // A quick reconnect still has its resends caught.
+        assert!(!dedupe.admit(&mut stamped(1, 0)).unwrap());
+
+        dedupe.release(1);
+        for epoch in 2..(2 + RETAINED_RELEASED as u64) {
+            assert!(dedupe.admit(&mut stamped(epoch, 0)).unwrap());
+            dedupe.release(epoch);
+        }
+        // Epoch 1 was released longest ago and is gone.
+        assert_eq!(dedupe.tracked_epochs(), RETAINED_RELEASED);
+        assert!(dedupe.admit(&mut stamped(1, 0)).unwrap());
+    }
+
+    #[test]
+    fn a_bound_connection_cannot_switch_epochs() {
+        let mut dedupe = Deduper::new(4);
+        let mut bound = None;
+        assert!(dedupe.admit_bound(&mut stamped(5, 0), &mut bound).unwrap());
+        assert_eq!(bound, Some(5));
+        let err = dedupe
+            .admit_bound(&mut stamped(6, 0), &mut bound)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(dedupe.tracked_epochs(), 1);
+    }
+
+    #[test]
+    fn rejects_a_message_without_a_stamp() {
+        let mut dedupe = Deduper::new(4);
+        let err = dedupe
+            .admit(&mut vec![Bytes::from_static(b"plain")])
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
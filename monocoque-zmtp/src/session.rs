@@ -1,6 +1,11 @@
 use crate::codec::{ZmtpDecoder, ZmtpError, ZmtpFrame};
-use crate::greeting::ZmtpGreeting;
-use crate::handshake::parse_ready_command;
+use crate::greeting::{
+    GREETING_SIZE, ProbeKind, SIGNATURE_SIZE, ZmtpGreeting, ZmtpVersion, check_major_version,
+};
+use crate::handshake::{
+    PeerMetadata, READY, SecurityMechanism, check_peer_mechanism, parse_ready_properties,
+};
+use crate::security::protocol::{build_error_command, peer_error};
 use bytes::{Bytes, BytesMut};
 use monocoque_core::buffer::SegmentedBuffer;
 
@@ -67,22 +72,26 @@ pub enum SessionEvent {
         peer_socket_type: SocketType,
     },
 
-    /// Fatal protocol error
+    /// Fatal protocol error, or [`ZmtpError::PeerError`] with the reason
+    /// from an ERROR command the peer sent before closing.
     Error(ZmtpError),
 }
 
+/// Where the session is in the connection lifecycle.
+///
+/// Each transition happens in one assignment, after the bytes that caused it
+/// have been consumed, so no state is ever half-updated between calls.
 enum State {
-    Greeting {
-        buffer: BytesMut,
-    },
+    /// Waiting for all 64 greeting bytes, which stay in `recv` until then.
+    Greeting,
     Handshake {
         decoder: ZmtpDecoder,
-        peer_socket_type: Option<SocketType>,
-        peer_identity: Option<Bytes>,
     },
     Active {
         decoder: ZmtpDecoder,
     },
+    /// An [`SessionEvent::Error`] was reported; input is discarded.
+    Failed,
 }
 
 /// Sans-IO ZMTP session
@@ -93,6 +102,10 @@ pub struct ZmtpSession {
     /// Resolved `max_msg_size` applied to every decoder this session creates.
     /// `None` keeps the decoder's built-in default cap.
     max_frame_size: Option<usize>,
+    /// ZMTP revision agreed with the peer; `None` until its greeting arrives.
+    version: Option<ZmtpVersion>,
+    /// Properties from the peer's READY; empty until the handshake completes.
+    peer_metadata: PeerMetadata,
 }
 
 /// Build a decoder honoring an optional `max_msg_size` limit.
@@ -120,12 +133,12 @@ impl ZmtpSession {
         max_frame_size: Option<usize>,
     ) -> Self {
         Self {
-            state: State::Greeting {
-                buffer: BytesMut::with_capacity(64),
-            },
+            state: State::Greeting,
             local_socket_type,
             recv: SegmentedBuffer::new(),
             max_frame_size,
+            version: None,
+            peer_metadata: PeerMetadata::new(),
         }
     }
 
@@ -154,16 +167,35 @@ impl ZmtpSession {
             local_socket_type,
             recv: SegmentedBuffer::new(),
             max_frame_size,
+            version: None,
+            peer_metadata: PeerMetadata::new(),
         }
     }
 
+    /// ZMTP revision negotiated with the peer.
+    ///
+    /// `None` until the peer's greeting has been parsed. Sessions created with
+    /// [`Self::new_active`] skipped the greeting and report `None`.
+    #[must_use]
+    pub const fn negotiated_version(&self) -> Option<ZmtpVersion> {
+        self.version
+    }
+
+    /// Every property the peer sent in its READY command.
+    ///
+    /// Empty until the handshake completes, and for sessions created with
+    /// [`Self::new_active`], which never see the peer's READY.
+    #[must_use]
+    pub const fn peer_metadata(&self) -> &PeerMetadata {
+        &self.peer_metadata
+    }
+
     /// Generate our greeting bytes
     ///
     /// # Compatibility
     ///
-    /// Sends ZMTP 3.0 greeting for maximum backward compatibility with `ZeroMQ` 4.1+.
-    /// The implementation accepts any ZMTP 3.x version from peers, ensuring
-    /// compatibility with all modern ZMQ versions (4.1, 4.2, 4.3, 4.4).
+    /// Advertises [`ZmtpVersion::LOCAL`] (3.1). A 3.0 peer answers with 3.0
+    /// and both sides fall back to it, so `ZeroMQ` 4.1+ remains compatible.
     pub fn local_greeting(&self) -> Bytes {
         let mut b = BytesMut::with_capacity(64);
 
@@ -172,8 +204,8 @@ impl ZmtpSession {
         b.extend_from_slice(&[0u8; 8]);
         b.extend_from_slice(&[0x7F]);
 
-        // Version 3.0 (backward compatible with all ZMQ 4.x)
-        b.extend_from_slice(&[0x03, 0x00]);
+        // Highest version we speak; negotiated down on the peer's greeting
+        b.extend_from_slice(&[ZmtpVersion::LOCAL.major, ZmtpVersion::LOCAL.minor]);
 
         // Mechanism: NULL (Phase 1-3)
         b.extend_from_slice(b"NULL");
@@ -188,9 +220,54 @@ impl ZmtpSession {
         b.freeze()
     }
 
-    /// Feed incoming bytes into the session
+    /// Encode an ERROR command frame carrying `reason`.
+    ///
+    /// A side that rejects its peer writes this frame and then closes, so the
+    /// peer sees [`ZmtpError::PeerError`] with the reason instead of bare EOF.
+    #[must_use]
+    pub fn error_frame(reason: &str) -> Bytes {
+        use crate::utils::{FLAG_COMMAND, encode_frame};
+        encode_frame(FLAG_COMMAND, &build_error_command(reason))
+    }
+
+    /// Whether the session reported a fatal [`SessionEvent::Error`].
+    ///
+    /// A failed session discards further input; close the connection.
+    #[must_use]
+    pub const fn is_failed(&self) -> bool {
+        matches!(self.state, State::Failed)
+    }
+
+    /// Reject a non-ZMTP peer once its signature is in, and a pre-3.0 one
+    /// once its major version is: their greetings are shorter than ours and
+    /// would never complete.
+    fn early_greeting_error(&self) -> Option<ZmtpError> {
+        let mut prefix = [0u8; SIGNATURE_SIZE + 1];
+        if self.recv.copy_prefix(SIGNATURE_SIZE, &mut prefix)
+            && let Some(kind) = ProbeKind::detect(&prefix[..SIGNATURE_SIZE])
+        {
+            return Some(ZmtpError::NotZmtp { kind });
+        }
+        if self.recv.copy_prefix(SIGNATURE_SIZE + 1, &mut prefix) {
+            return check_major_version(prefix[SIGNATURE_SIZE]).err();
+        }
+        None
+    }
+
+    /// Feed incoming bytes into the session.
+    ///
+    /// The call runs to completion without suspending, so there is no point
+    /// inside it where an async caller can be cancelled: `src` is either
+    /// fully taken in or, if the caller is cancelled before calling, not at
+    /// all. Bytes that do not yet form a whole greeting or frame are kept
+    /// and completed by later calls, so a stream split at any offset yields
+    /// the same events as the stream delivered at once. After an error the
+    /// session is [failed](Self::is_failed) and ignores further bytes.
     pub fn on_bytes(&mut self, src: Bytes) -> Vec<SessionEvent> {
         let mut events = Vec::new();
+        if self.is_failed() {
+            return events;
+        }
 
         self.recv.push(src);
 
@@ -199,26 +276,25 @@ impl ZmtpSession {
                 // =========================
                 // Greeting
                 // =========================
-                State::Greeting { buffer } => {
-                    let needed = 64 - buffer.len();
-                    let take = needed.min(self.recv.len());
-                    if let Some(bytes) = self.recv.take_bytes(take) {
-                        buffer.extend_from_slice(&bytes);
+                State::Greeting => {
+                    if let Some(e) = self.early_greeting_error() {
+                        events.push(SessionEvent::Error(e));
+                        break;
                     }
 
-                    if buffer.len() < 64 {
+                    let Some(greeting) = self.recv.take_bytes(GREETING_SIZE) else {
                         break;
-                    }
+                    };
 
-                    let greeting = buffer.split().freeze();
+                    match ZmtpGreeting::parse(&greeting).and_then(|g| {
+                        check_peer_mechanism(SecurityMechanism::Null, false, &g).map(|()| g)
+                    }) {
+                        Ok(g) => {
+                            self.version = Some(ZmtpVersion::LOCAL.negotiate(g.version));
 
-                    match ZmtpGreeting::parse(&greeting) {
-                        Ok(_g) => {
                             // Transition to handshake
                             self.state = State::Handshake {
                                 decoder: make_decoder(self.max_frame_size),
-                                peer_socket_type: None,
-                                peer_identity: None,
                             };
 
                             // Send our greeting (if we haven't already)
@@ -228,20 +304,7 @@ impl ZmtpSession {
 
                             // Send READY command immediately after greeting exchange
                             use crate::utils::{FLAG_COMMAND, build_ready, encode_frame};
-                            let socket_type_str = match self.local_socket_type {
-                                SocketType::Dealer => "DEALER",
-                                SocketType::Router => "ROUTER",
-                                SocketType::Pub => "PUB",
-                                SocketType::Sub => "SUB",
-                                SocketType::Xpub => "XPUB",
-                                SocketType::Xsub => "XSUB",
-                                SocketType::Req => "REQ",
-                                SocketType::Rep => "REP",
-                                SocketType::Push => "PUSH",
-                                SocketType::Pull => "PULL",
-                                SocketType::Pair => "PAIR",
-                            };
-                            let ready_body = build_ready(socket_type_str, None);
+                            let ready_body = build_ready(self.local_socket_type.as_str(), None);
                             let ready_frame = encode_frame(FLAG_COMMAND, &ready_body);
                             events.push(SessionEvent::SendBytes(ready_frame));
                         }
@@ -255,61 +318,57 @@ impl ZmtpSession {
                 // =========================
                 // Handshake
                 // =========================
-                State::Handshake {
-                    decoder,
-                    peer_socket_type,
-                    peer_identity,
-                } => {
-                    match decoder.decode(&mut self.recv) {
-                        Ok(Some(frame)) => {
-                            if !frame.is_command() {
-                                events.push(SessionEvent::Error(ZmtpError::Protocol));
-                                break;
-                            }
-
-                            let (parsed_socket_type, parsed_identity) =
-                                match parse_ready_command(&frame.payload) {
-                                    Ok(parsed) => parsed,
-                                    Err(e) => {
-                                        events.push(SessionEvent::Error(e));
-                                        break;
-                                    }
-                                };
-                            *peer_socket_type = Some(parsed_socket_type);
-                            *peer_identity = parsed_identity;
-
-                            // Extract values before transitioning state
-                            let peer_id = peer_identity.take();
-                            let peer_st = peer_socket_type.unwrap_or(self.local_socket_type);
-
-                            // Reuse the handshake decoder for the Active state; the
-                            // replacement is a throwaway needed only for mem::replace.
-                            let new_decoder = make_decoder(self.max_frame_size);
-                            let old_decoder = std::mem::replace(decoder, new_decoder);
-
-                            // Now transition state
-                            self.state = State::Active {
-                                decoder: old_decoder,
-                            };
-
-                            events.push(SessionEvent::HandshakeComplete {
-                                peer_identity: peer_id,
-                                peer_socket_type: peer_st,
-                            });
+                State::Handshake { decoder } => match decoder.decode(&mut self.recv) {
+                    Ok(Some(frame)) => {
+                        if !frame.is_command() {
+                            events.push(SessionEvent::Error(ZmtpError::Protocol));
+                            break;
                         }
-                        Ok(None) => break,
-                        Err(e) => {
-                            events.push(SessionEvent::Error(e));
+                        if let Some(err) = peer_error(&frame.payload) {
+                            events.push(SessionEvent::Error(err));
                             break;
                         }
+
+                        let ready = match parse_ready_properties(&frame.payload, READY) {
+                            Ok(ready) => ready,
+                            Err(e) => {
+                                events.push(SessionEvent::Error(e));
+                                break;
+                            }
+                        };
+                        self.peer_metadata = ready.metadata;
+
+                        // Reuse the handshake decoder for the Active state; the
+                        // replacement is a throwaway needed only for mem::replace.
+                        let new_decoder = make_decoder(self.max_frame_size);
+                        let old_decoder = std::mem::replace(decoder, new_decoder);
+                        self.state = State::Active {
+                            decoder: old_decoder,
+                        };
+
+                        events.push(SessionEvent::HandshakeComplete {
+                            peer_identity: ready.identity,
+                            peer_socket_type: ready.socket_type,
+                        });
                     }
-                }
+                    Ok(None) => break,
+                    Err(e) => {
+                        events.push(SessionEvent::Error(e));
+                        break;
+                    }
+                },
 
                 // =========================
                 // Active
                 // =========================
                 State::Active { decoder } => match decoder.decode(&mut self.recv) {
                     Ok(Some(frame)) => {
+                        if frame.is_command()
+                            && let Some(err) = peer_error(&frame.payload)
+                        {
+                            events.push(SessionEvent::Error(err));
+                            break;
+                        }
                         events.push(SessionEvent::Frame(frame));
                     }
                     Ok(None) => break,
@@ -318,9 +377,17 @@ impl ZmtpSession {
                         break;
                     }
                 },
+
+                State::Failed => break,
             }
         }
 
+        // Every error is fatal: drop what is left rather than resume parsing
+        // from wherever the bad input ended.
+        if matches!(events.last(), Some(SessionEvent::Error(_))) {
+            self.state = State::Failed;
+            self.recv = SegmentedBuffer::new();
+        }
         events
     }
 }
@@ -342,10 +409,14 @@ mod tests {
         let events = session.on_bytes(Bytes::from_static(&[0x00, 20]));
 
         assert!(
-            events
-                .iter()
-                .any(|e| matches!(e, SessionEvent::Error(ZmtpError::SizeTooLarge))),
-            "oversized frame should produce a SizeTooLarge error, got {} events",
+            events.iter().any(|e| matches!(
+                e,
+                SessionEvent::Error(ZmtpError::FrameTooLarge {
+                    declared: 20,
+                    limit: 10
+                })
+            )),
+            "oversized frame should produce a FrameTooLarge error, got {} events",
             events.len()
         );
     }
@@ -366,15 +437,103 @@ mod tests {
     }
 
     fn valid_null_greeting() -> Bytes {
+        null_greeting(ZmtpVersion::V3_1)
+    }
+
+    fn null_greeting(version: ZmtpVersion) -> Bytes {
         let mut greeting = [0u8; 64];
         greeting[0] = 0xFF;
         greeting[9] = 0x7F;
-        greeting[10] = 0x03;
-        greeting[11] = 0x01;
+        greeting[10] = version.major;
+        greeting[11] = version.minor;
         greeting[12..16].copy_from_slice(b"NULL");
         Bytes::copy_from_slice(&greeting)
     }
 
+    fn greeting_with_mechanism(mechanism: &[u8], as_server: bool) -> Bytes {
+        let mut greeting = BytesMut::from(&valid_null_greeting()[..]);
+        greeting[12..32].fill(0);
+        greeting[12..12 + mechanism.len()].copy_from_slice(mechanism);
+        greeting[32] = u8::from(as_server);
+        greeting.freeze()
+    }
+
+    fn mechanism_mismatch(events: &[SessionEvent]) -> Option<(&str, &str)> {
+        events.iter().find_map(|event| match event {
+            SessionEvent::Error(ZmtpError::MechanismMismatch { local, peer }) => {
+                Some((local.as_str(), peer.as_str()))
+            }
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn null_session_rejects_plain_and_curve_peers() {
+        for (mechanism, as_server) in [("PLAIN", false), ("PLAIN", true), ("CURVE", true)] {
+            let mut session = ZmtpSession::new(SocketType::Dealer);
+            let events = session.on_bytes(greeting_with_mechanism(mechanism.as_bytes(), as_server));
+
+            assert_eq!(
+                mechanism_mismatch(&events),
+                Some(("NULL", mechanism)),
+                "{mechanism} peer"
+            );
+            // No READY goes out to a peer we cannot talk to.
+            assert!(
+                !events
+                    .iter()
+                    .any(|event| matches!(event, SessionEvent::SendBytes(_))),
+                "{mechanism} peer"
+            );
+        }
+    }
+
+    #[test]
+    fn null_session_rejects_unknown_mechanism() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let events = session.on_bytes(greeting_with_mechanism(b"GSSAPI2", false));
+        assert_eq!(mechanism_mismatch(&events), Some(("NULL", "GSSAPI2")));
+
+        // A mechanism field that is not a name at all is malformed.
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let events = session.on_bytes(greeting_with_mechanism(b"\xffNU\x01LL", false));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            SessionEvent::Error(ZmtpError::InvalidGreeting { .. })
+        )));
+        assert!(mechanism_mismatch(&events).is_none());
+    }
+
+    #[test]
+    fn session_rejects_non_zmtp_peer_after_its_signature() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let events = session.on_bytes(Bytes::from_static(b"GET / HT"));
+        assert!(events.is_empty(), "waits for the whole signature");
+        let events = session.on_bytes(Bytes::from_static(b"TP/1.1\r\n"));
+        assert!(matches!(
+            events[..],
+            [SessionEvent::Error(ZmtpError::NotZmtp {
+                kind: ProbeKind::Http
+            })]
+        ));
+    }
+
+    #[test]
+    fn null_session_ignores_peer_as_server_flag() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let events = session.on_bytes(greeting_with_mechanism(b"NULL", true));
+        assert!(
+            events
+                .iter()
+                .any(|event| matches!(event, SessionEvent::SendBytes(_)))
+        );
+        assert!(
+            !events
+                .iter()
+                .any(|event| matches!(event, SessionEvent::Error(_)))
+        );
+    }
+
     fn input_with_handshake_command(command_body: Bytes) -> Bytes {
         let command_frame = encode_frame(FLAG_COMMAND, &command_body);
         let mut input = BytesMut::with_capacity(64 + command_frame.len());
@@ -415,7 +574,11 @@ mod tests {
         let input = input_with_handshake_command(Bytes::from_static(b"\x05READY"));
         let events = session.on_bytes(input);
 
-        assert!(has_protocol_error(&events));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            SessionEvent::Error(ZmtpError::MalformedCommand { reason, .. })
+                if reason.contains("Socket-Type")
+        )));
         assert!(handshake_complete(&events).is_none());
     }
 
@@ -430,4 +593,262 @@ mod tests {
         assert_eq!(peer_socket_type, SocketType::Dealer);
         assert_eq!(peer_identity.as_deref(), Some(&b"client-1"[..]));
     }
+
+    #[test]
+    fn session_exposes_every_ready_property() {
+        let mut ready = BytesMut::from(build_ready("DEALER", Some(b"client-1")));
+        crate::utils::put_property(&mut ready, "App-Version", b"2.4.1");
+        let mut session = ZmtpSession::new(SocketType::Router);
+        assert!(session.peer_metadata().is_empty());
+
+        let events = session.on_bytes(input_with_handshake_command(ready.freeze()));
+        assert!(handshake_complete(&events).is_some());
+
+        let metadata = session.peer_metadata();
+        assert_eq!(metadata.len(), 3);
+        assert_eq!(metadata["Identity"], &b"client-1"[..]);
+        assert_eq!(metadata["Socket-Type"], &b"DEALER"[..]);
+        assert_eq!(metadata["App-Version"], &b"2.4.1"[..]);
+    }
+
+    #[test]
+    fn session_falls_back_to_zmtp_30_peer() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        assert_eq!(&session.local_greeting()[10..12], &[3, 1]);
+        assert_eq!(session.negotiated_version(), None);
+        session.on_bytes(null_greeting(ZmtpVersion::V3_0));
+        assert_eq!(session.negotiated_version(), Some(ZmtpVersion::V3_0));
+
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        session.on_bytes(null_greeting(ZmtpVersion::V3_1));
+        assert_eq!(session.negotiated_version(), Some(ZmtpVersion::V3_1));
+    }
+
+    #[test]
+    fn session_completes_handshake_with_zmtp_30_peer() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let mut input = BytesMut::from(&null_greeting(ZmtpVersion::V3_0)[..]);
+        input.extend_from_slice(&encode_frame(FLAG_COMMAND, &build_ready("DEALER", None)));
+
+        let events = session.on_bytes(input.freeze());
+        assert!(handshake_complete(&events).is_some());
+        assert_eq!(session.negotiated_version(), Some(ZmtpVersion::V3_0));
+        assert!(!session.negotiated_version().unwrap().has_v31_commands());
+    }
+
+    #[test]
+    fn session_rejects_pre_zmtp_3_peer_without_waiting_for_a_full_greeting() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        // ZMTP 2.0: signature, revision 1, DEALER, empty identity frame.
+        let events = session.on_bytes(Bytes::from_static(
+            b"\xff\x00\x00\x00\x00\x00\x00\x00\x01\x7f\x01\x05\x00\x00",
+        ));
+
+        assert!(matches!(
+            events.as_slice(),
+            [SessionEvent::Error(ZmtpError::UnsupportedVersion {
+                major: 1
+            })]
+        ));
+        assert!(session.is_failed());
+        assert_eq!(session.negotiated_version(), None);
+    }
+
+    /// ERROR frame libzmq 4.3 sends in place of READY when its ZAP handler
+    /// denies a NULL peer: the reason is the ZAP status code.
+    const LIBZMQ_ZAP_DENIED: &[u8] = b"\x04\x0a\x05ERROR\x03400";
+
+    fn peer_error_reason(events: &[SessionEvent]) -> Option<&str> {
+        events.iter().find_map(|event| match event {
+            SessionEvent::Error(ZmtpError::PeerError { reason }) => Some(reason.as_str()),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn session_reports_error_command_in_place_of_ready() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let mut input = BytesMut::from(&valid_null_greeting()[..]);
+        input.extend_from_slice(LIBZMQ_ZAP_DENIED);
+        let events = session.on_bytes(input.freeze());
+
+        assert_eq!(peer_error_reason(&events), Some("400"));
+        assert!(handshake_complete(&events).is_none());
+        assert!(!has_protocol_error(&events));
+
+        let err = match events.into_iter().last() {
+            Some(SessionEvent::Error(err)) => std::io::Error::from(err),
+            _ => panic!("expected an error event last"),
+        };
+        assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+        assert!(err.to_string().contains("400"), "{err}");
+    }
+
+    #[test]
+    fn active_session_reports_error_command() {
+        let mut session = ZmtpSession::new_active(SocketType::Dealer);
+        let mut input = BytesMut::new();
+        input.extend_from_slice(&[0x00, 2, b'h', b'i']);
+        input.extend_from_slice(&ZmtpSession::error_frame("Access denied"));
+        let events = session.on_bytes(input.freeze());
+
+        assert!(matches!(events.first(), Some(SessionEvent::Frame(_))));
+        assert_eq!(peer_error_reason(&events), Some("Access denied"));
+    }
+
+    /// Garbage after a valid greeting, fed in uneven reads, only ever yields
+    /// events or errors. A smoke run of what the `fuzz_decoder` target covers.
+    #[test]
+    fn random_input_after_greeting_never_panics() {
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..500 {
+            let len = (next() % 512) as usize;
+            let garbage: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            let read_size = (next() % 64 + 1) as usize;
+
+            let mut session = ZmtpSession::new(SocketType::Router);
+            let mut active = ZmtpSession::new_active(SocketType::Router);
+            let _ = session.on_bytes(valid_null_greeting());
+            for chunk in garbage.chunks(read_size) {
+                let _ = session.on_bytes(Bytes::copy_from_slice(chunk));
+                let _ = active.on_bytes(Bytes::copy_from_slice(chunk));
+            }
+        }
+    }
+
+    /// Flatten events into comparable strings.
+    fn describe(events: &[SessionEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                SessionEvent::SendBytes(bytes) => format!("send {bytes:?}"),
+                SessionEvent::Frame(frame) => format!("frame {} {:?}", frame.flags, frame.payload),
+                SessionEvent::HandshakeComplete {
+                    peer_identity,
+                    peer_socket_type,
+                } => format!("ready {peer_socket_type:?} {peer_identity:?}"),
+                SessionEvent::Error(e) => format!("error {e}"),
+            })
+            .collect()
+    }
+
+    /// A whole conversation: greeting, READY with an identity, a two-part
+    /// message, a long frame and a PING.
+    fn conversation() -> Bytes {
+        let mut input = BytesMut::new();
+        input.extend_from_slice(&valid_null_greeting());
+        input.extend_from_slice(&encode_frame(
+            FLAG_COMMAND,
+            &build_ready("ROUTER", Some(b"peer-1")),
+        ));
+        input.extend_from_slice(&[0x01, 3]);
+        input.extend_from_slice(b"one");
+        input.extend_from_slice(&[0x00, 3]);
+        input.extend_from_slice(b"two");
+        input.extend_from_slice(&[0x02, 0, 0, 0, 0, 0, 0, 1, 44]);
+        input.extend_from_slice(&[b'x'; 300]);
+        input.extend_from_slice(&crate::base::build_ping_frame(10));
+        input.freeze()
+    }
+
+    #[test]
+    fn interrupted_input_resumes_without_duplication_or_loss() {
+        let input = conversation();
+        let whole = describe(&ZmtpSession::new(SocketType::Dealer).on_bytes(input.clone()));
+        assert_eq!(whole.len(), 6, "{whole:?}");
+
+        // Stop after any prefix, as a cancelled read loop would, then resume
+        // with the rest.
+        for split in 0..=input.len() {
+            let mut session = ZmtpSession::new(SocketType::Dealer);
+            let mut events = session.on_bytes(input.slice(..split));
+            events.extend(session.on_bytes(input.slice(split..)));
+            assert_eq!(describe(&events), whole, "split at {split}");
+        }
+
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let mut events = Vec::new();
+        for i in 0..input.len() {
+            events.extend(session.on_bytes(input.slice(i..=i)));
+        }
+        assert_eq!(describe(&events), whole, "one byte at a time");
+    }
+
+    /// What a libzmq 4.3 DEALER with no identity writes to a fresh
+    /// connection: its greeting in the two writes libzmq makes (signature
+    /// and major version, then the rest), its READY, and an empty delimiter
+    /// followed by `hello`.
+    const LIBZMQ_DEALER_CAPTURE: &[&[u8]] = &[
+        b"\xff\x00\x00\x00\x00\x00\x00\x00\x01\x7f\x03",
+        b"\x01NULL\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+          \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\
+          \x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00",
+        b"\x04\x29\x05READY\x0bSocket-Type\x00\x00\x00\x06DEALER\x08Identity\x00\x00\x00\x00",
+        b"\x01\x00\x00\x05hello",
+    ];
+
+    #[test]
+    fn libzmq_capture_parses_the_same_in_any_chunking() {
+        let capture = Bytes::from(LIBZMQ_DEALER_CAPTURE.concat());
+        assert_eq!(LIBZMQ_DEALER_CAPTURE[..2].concat().len(), GREETING_SIZE);
+
+        let run = |chunk: usize| {
+            let mut session = ZmtpSession::new(SocketType::Router);
+            let mut events = Vec::new();
+            for start in (0..capture.len()).step_by(chunk) {
+                let end = (start + chunk).min(capture.len());
+                events.extend(session.on_bytes(capture.slice(start..end)));
+            }
+            (
+                describe(&events),
+                session.negotiated_version(),
+                session.peer_metadata().clone(),
+            )
+        };
+
+        let (events, version, metadata) = run(capture.len());
+        assert_eq!(
+            events[1..],
+            [
+                "ready Dealer Some(b\"\")",
+                "frame 1 b\"\"",
+                "frame 0 b\"hello\""
+            ],
+            "{events:?}"
+        );
+        assert_eq!(version, Some(ZmtpVersion::V3_1));
+        assert_eq!(metadata["Socket-Type"], &b"DEALER"[..]);
+        for chunk in [1, 7] {
+            assert_eq!(
+                run(chunk),
+                (events.clone(), version, metadata.clone()),
+                "{chunk}-byte reads"
+            );
+        }
+    }
+
+    #[test]
+    fn session_stays_failed_after_an_error() {
+        let mut session = ZmtpSession::new(SocketType::Dealer);
+        let mut bad = valid_null_greeting().to_vec();
+        bad[10] = 2;
+        bad.extend_from_slice(&conversation()[64..]);
+
+        let events = session.on_bytes(Bytes::from(bad));
+        assert!(matches!(
+            events[..],
+            [SessionEvent::Error(ZmtpError::UnsupportedVersion {
+                major: 2
+            })]
+        ));
+        assert!(session.is_failed());
+        // The rest of the input is not mistaken for a fresh greeting.
+        assert!(session.on_bytes(conversation()).is_empty());
+    }
 }
@@ -52,7 +52,7 @@
 
 use bytes::{Bytes, BytesMut};
 use flume::{Receiver, Sender};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::{OwnedReadHalf, OwnedWriteHalf, TcpListener};
 use std::collections::HashMap;
 use std::io;
@@ -424,9 +424,20 @@ impl StreamSocket {
         &self.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect. The
+    /// queue bounds are the exception here: `send_hwm` sizes the queue of each
+    /// peer as it is accepted, and `recv_hwm` the shared inbound queue at bind.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.options
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.options.update(f)
     }
 }
@@ -7,17 +7,30 @@
 //!
 //! SUB sockets receive messages from PUB sockets and filter them based on
 //! subscriptions.
+//!
+//! Subscriptions are matched against a message's first frame only; see
+//! [`monocoque_core::subscription`] for the exact rules.
+//!
+//! Besides prefix subscriptions, a SUB can hold exact topics and
+//! [`WildcardPattern`]s. The publisher only understands prefixes, so these
+//! subscribe to a prefix on the wire (the topic itself, or the pattern's
+//! literal prefix) and are checked here, on every received message.
 
 use crate::base::SocketBase;
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
+use monocoque_core::subscription::{SubscriptionEvent, SubscriptionFilter, WildcardPattern};
 use smallvec::SmallVec;
 use std::io;
+use std::time::Duration;
 use tracing::{debug, trace};
 
-use crate::{handshake::perform_handshake_with_options, session::SocketType};
+use crate::{
+    handshake::{handshake_error, perform_handshake_with_options},
+    session::SocketType,
+};
 use monocoque_core::endpoint::Endpoint;
 
 /// Direct-stream SUB socket.
@@ -29,8 +42,8 @@ where
     base: SocketBase<S>,
     /// Accumulated frames for current multipart message
     frames: SmallVec<[Bytes; 4]>,
-    /// List of subscription prefixes (sorted for efficient matching)
-    subscriptions: Vec<Bytes>,
+    /// Prefix, exact and wildcard subscriptions applied to received messages.
+    filter: SubscriptionFilter,
 }
 
 impl<S> SubSocket<S>
@@ -55,17 +68,22 @@ where
     pub async fn with_options(mut stream: S, mut options: SocketOptions) -> io::Result<Self> {
         debug!("[SUB] Creating new direct SUB socket");
 
+        // Reject an invalid routing id before anything is sent
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+
         // Perform ZMTP handshake
         debug!("[SUB] Performing ZMTP handshake...");
         let handshake_result = perform_handshake_with_options(
             &mut stream,
             SocketType::Sub,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_socket_type = ?handshake_result.peer_socket_type,
@@ -78,10 +96,12 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Sub, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         let mut socket = Self {
             base,
             frames: SmallVec::new(),
-            subscriptions: Vec::new(),
+            filter: SubscriptionFilter::new(),
         };
 
         // Apply subscriptions/unsubscriptions declared in options.
@@ -101,68 +121,210 @@ where
     ///
     /// An empty prefix subscribes to all messages.
     ///
-    /// This sends a subscription message to the PUB socket as a ZMTP frame.
+    /// This sends a subscription message to the PUB socket as a ZMTP frame,
+    /// bounded by the configured `send_timeout` (a zero, non-blocking
+    /// `send_timeout` is ignored here: like libzmq's `ZMQ_SUBSCRIBE`, a
+    /// subscription always waits for the frame to be queued). Use
+    /// [`subscribe_timeout`](Self::subscribe_timeout) for an explicit bound.
     pub async fn subscribe(&mut self, prefix: impl Into<Bytes>) -> io::Result<()> {
-        let prefix = prefix.into();
+        let timeout = self.control_timeout();
+        self.subscribe_within(prefix.into(), timeout).await
+    }
+
+    /// Subscribe to messages with the given prefix, giving up after `timeout`.
+    ///
+    /// On `TimedOut` the subscription is not recorded. If the stream took
+    /// nothing before the deadline the frame is withdrawn and the call can be
+    /// retried; if the deadline dropped a write in flight, the socket is
+    /// poisoned and must reconnect first.
+    pub async fn subscribe_timeout(
+        &mut self,
+        prefix: impl Into<Bytes>,
+        timeout: Duration,
+    ) -> io::Result<()> {
+        self.subscribe_within(prefix.into(), Some(timeout)).await
+    }
+
+    async fn subscribe_within(
+        &mut self,
+        prefix: Bytes,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
         trace!("[SUB] Adding subscription: {:?}", prefix);
 
-        if !self.subscriptions.contains(&prefix) {
-            self.send_sub_event(0x01, &prefix).await?;
-            self.subscriptions.push(prefix);
-            self.subscriptions.sort();
-            return Ok(());
+        // An exact or pattern subscription already holds this prefix on the
+        // wire; sending it again would double-count it at the peer.
+        if !self.filter.held_locally(&prefix) {
+            self.send_sub_event(0x01, &prefix, timeout).await?;
         }
-
-        self.send_sub_event(0x01, &prefix).await
+        self.filter.add_prefix(prefix);
+        Ok(())
     }
 
     /// Unsubscribe from messages with the given prefix.
     ///
-    /// This sends an unsubscription message to the PUB socket as a ZMTP frame.
+    /// This sends an unsubscription message to the PUB socket as a ZMTP frame,
+    /// bounded like [`subscribe`](Self::subscribe).
     pub async fn unsubscribe(&mut self, prefix: &Bytes) -> io::Result<()> {
+        let timeout = self.control_timeout();
+        self.unsubscribe_within(prefix, timeout).await
+    }
+
+    /// Unsubscribe from messages with the given prefix, giving up after `timeout`.
+    ///
+    /// On `TimedOut` the unsubscription frame has been withdrawn and the
+    /// subscription is kept.
+    pub async fn unsubscribe_timeout(
+        &mut self,
+        prefix: &Bytes,
+        timeout: Duration,
+    ) -> io::Result<()> {
+        self.unsubscribe_within(prefix, Some(timeout)).await
+    }
+
+    async fn unsubscribe_within(
+        &mut self,
+        prefix: &Bytes,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
         trace!("[SUB] Removing subscription: {:?}", prefix);
-        self.subscriptions.retain(|s| s != prefix);
 
-        self.send_sub_event(0x00, prefix).await
+        if !self.filter.held_locally(prefix) {
+            self.send_sub_event(0x00, prefix, timeout).await?;
+        }
+        self.filter.remove_prefix(prefix);
+        Ok(())
+    }
+
+    /// Subscribe to messages whose first frame matches `pattern`.
+    ///
+    /// The publisher is asked for the pattern's literal prefix (the bytes
+    /// before the first wildcard) and the pattern is applied to each message
+    /// on receipt, so `*`-heavy patterns still cost bandwidth for the
+    /// messages they filter out. Bounded like [`subscribe`](Self::subscribe).
+    pub async fn subscribe_pattern(&mut self, pattern: &WildcardPattern) -> io::Result<()> {
+        if self.filter.patterns().contains(pattern) {
+            return Ok(());
+        }
+        trace!("[SUB] Adding pattern subscription: {}", pattern);
+
+        let prefix = pattern.literal_prefix();
+        if !self.filter.uses_prefix(prefix) {
+            let timeout = self.control_timeout();
+            self.send_sub_event(0x01, prefix, timeout).await?;
+        }
+        self.filter.add_pattern(pattern.clone());
+        Ok(())
+    }
+
+    /// Remove a pattern added with [`subscribe_pattern`](Self::subscribe_pattern).
+    ///
+    /// The literal prefix is unsubscribed on the wire only when no other
+    /// subscription still relies on it.
+    pub async fn unsubscribe_pattern(&mut self, pattern: &WildcardPattern) -> io::Result<()> {
+        if !self.filter.remove_pattern(pattern) {
+            return Ok(());
+        }
+        trace!("[SUB] Removing pattern subscription: {}", pattern);
+
+        let prefix = pattern.literal_prefix();
+        if !self.filter.uses_prefix(prefix) {
+            let timeout = self.control_timeout();
+            if let Err(e) = self.send_sub_event(0x00, prefix, timeout).await {
+                self.filter.add_pattern(pattern.clone());
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Subscribe to messages whose first frame is exactly `topic`.
+    ///
+    /// The publisher is asked for `topic` as a prefix, and messages whose
+    /// first frame merely starts with it are dropped here, so they still cost
+    /// bandwidth. A prefix subscription to the same bytes keeps accepting
+    /// longer topics. Bounded like [`subscribe`](Self::subscribe).
+    pub async fn subscribe_exact(&mut self, topic: impl Into<Bytes>) -> io::Result<()> {
+        let topic = topic.into();
+        if self.filter.exact().contains(&topic) {
+            return Ok(());
+        }
+        trace!("[SUB] Adding exact subscription: {:?}", topic);
+
+        if !self.filter.uses_prefix(&topic) {
+            let timeout = self.control_timeout();
+            self.send_sub_event(0x01, &topic, timeout).await?;
+        }
+        self.filter.add_exact(topic);
+        Ok(())
+    }
+
+    /// Remove a topic added with [`subscribe_exact`](Self::subscribe_exact).
+    ///
+    /// The prefix is unsubscribed on the wire only when no other
+    /// subscription still relies on it.
+    pub async fn unsubscribe_exact(&mut self, topic: &[u8]) -> io::Result<()> {
+        if !self.filter.remove_exact(topic) {
+            return Ok(());
+        }
+        trace!("[SUB] Removing exact subscription: {:?}", topic);
+
+        if !self.filter.uses_prefix(topic) {
+            let timeout = self.control_timeout();
+            if let Err(e) = self.send_sub_event(0x00, topic, timeout).await {
+                self.filter.add_exact(Bytes::copy_from_slice(topic));
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// The subscriptions this socket filters received messages with.
+    #[must_use]
+    pub const fn subscription_filter(&self) -> &SubscriptionFilter {
+        &self.filter
+    }
+
+    /// Timeout applied to subscription commands when none is given explicitly.
+    fn control_timeout(&self) -> Option<Duration> {
+        self.base.options.send_timeout.filter(|dur| !dur.is_zero())
     }
 
     /// Encode and send a subscription/unsubscription event as a ZMTP frame.
     ///
-    /// Wire format: [flags][len][cmd: 0x01|0x00][prefix...]
+    /// ZMTP 3.1 peers get a `SUBSCRIBE`/`CANCEL` command, 3.0 peers a
+    /// `[0x01|0x00][prefix]` data frame (see `SocketBase::encode_subscription`).
     /// Using ZMTP framing ensures the PUB's subscription_reader can split
     /// consecutive messages even when they arrive in the same TCP segment.
-    async fn send_sub_event(&mut self, cmd: u8, prefix: &[u8]) -> io::Result<()> {
-        use compio_buf::BufResult;
-        use compio_io::AsyncWriteExt;
-        // Build payload: [cmd][prefix]
-        let mut payload = BytesMut::with_capacity(1 + prefix.len());
-        payload.extend_from_slice(&[cmd]);
-        payload.extend_from_slice(prefix);
-        let payload = payload.freeze();
-
-        // Encrypt if CURVE is active; otherwise plain ZMTP frame.
-        let mut wire = BytesMut::with_capacity(payload.len() + 9);
-        if let Some(ref mut cipher) = self.base.curve_cipher {
-            let body = cipher
-                .encrypt_frame(&payload, false)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-            crate::base::append_zmtp_cmd_frame(&mut wire, &body);
-        } else {
-            crate::codec::encode_multipart(&[payload], &mut wire);
+    /// The frame goes through the send buffer behind any pending data and is
+    /// either fully queued or withdrawn (see `SocketBase::send_command`).
+    async fn send_sub_event(
+        &mut self,
+        cmd: u8,
+        prefix: &[u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        // While an auto-reconnecting socket is down, the change is only
+        // recorded; the next connection replays the whole set.
+        if self.base.auto_reconnect && self.base.stream.is_none() {
+            trace!("[SUB] Disconnected, deferring subscription event");
+            return Ok(());
         }
-        let wire = wire.freeze();
+
+        let prefix = Bytes::copy_from_slice(prefix);
+        let event = if cmd == 0x01 {
+            SubscriptionEvent::Subscribe(prefix)
+        } else {
+            SubscriptionEvent::Unsubscribe(prefix)
+        };
+        let wire = self.base.encode_subscription(&event)?;
 
         trace!(
             "[SUB] Sending subscription event ({} wire bytes)",
             wire.len()
         );
 
-        let stream =
-            self.base.stream.as_mut().ok_or_else(|| {
-                io::Error::new(io::ErrorKind::NotConnected, "Socket not connected")
-            })?;
-        let BufResult(result, _) = stream.write_all(wire).await;
-        result?;
+        self.base.send_command(&wire, timeout).await?;
 
         trace!("[SUB] Subscription event sent successfully");
         Ok(())
@@ -173,8 +335,101 @@ where
     /// Receive a message that matches subscriptions.
     ///
     /// This will keep reading and filtering messages until one matches
-    /// the active subscriptions.
+    /// the active subscriptions. On a socket from `connect_with_reconnect`
+    /// this behaves like [`recv_with_reconnect`](Self::recv_with_reconnect).
     pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        if self.base.auto_reconnect {
+            return self.recv_with_reconnect().await;
+        }
+        self.recv_matching().await
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e), None)),
+            }
+        }))
+    }
+
+    /// Try to reconnect to the stored endpoint and re-send all active subscriptions.
+    pub async fn try_reconnect(&mut self) -> io::Result<()> {
+        self.base.try_reconnect(SocketType::Sub).await?;
+        self.frames.clear();
+        self.resend_subscriptions().await
+    }
+
+    /// Receive a message with automatic reconnection on EOF or network error.
+    ///
+    /// If the socket was created with `connect()` and stores an endpoint, this
+    /// method loops: on EOF or broken-pipe it clears the stream, reconnects
+    /// with exponential backoff (retrying failed attempts), replays the
+    /// subscriptions, then resumes receiving.
+    ///
+    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
+    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        let mut attempts = 0u32;
+
+        loop {
+            if self.base.stream.is_none() {
+                trace!("[SUB] Stream disconnected, reconnecting");
+                self.base
+                    .reconnect_within_budget(SocketType::Sub, &mut attempts)
+                    .await?;
+                self.frames.clear();
+                if let Err(e) = self.resend_subscriptions().await {
+                    if !self.base.lost_connection(&e) {
+                        return Err(e);
+                    }
+                    self.base.stream = None;
+                    continue;
+                }
+            }
+
+            match self.recv_matching().await {
+                Ok(Some(msg)) => return Ok(Some(msg)),
+                // EOF: read_raw() already set stream = None
+                Ok(None) => {
+                    debug!("[SUB] EOF on recv, will reconnect");
+                }
+                Err(e) if self.base.lost_connection(&e) => {
+                    debug!("[SUB] Connection error on recv ({}), will reconnect", e);
+                    self.base.stream = None;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Send every active subscription to a fresh connection.
+    async fn resend_subscriptions(&mut self) -> io::Result<()> {
+        let timeout = self.control_timeout();
+        // Collecting the prefixes first (refcounted, so cheap) keeps the
+        // filter intact if a re-send fails part-way.
+        for prefix in self.filter.wire_prefixes() {
+            self.send_sub_event(0x01, &prefix, timeout).await?;
+        }
+        Ok(())
+    }
+
+    /// Read until a message passes the subscription filter.
+    ///
+    /// With the `conflate` option set, every matching message already
+    /// received is decoded and only the newest is returned.
+    async fn recv_matching(&mut self) -> io::Result<Option<Vec<Bytes>>> {
+        let mut latest = None;
         'outer: loop {
             trace!("[SUB] Waiting for message");
 
@@ -197,26 +452,26 @@ where
                                 trace!("[SUB] Received {} frames", msg.len());
 
                                 // Check if message matches any subscription
-                                let matches = {
-                                    let subscriptions = &self.subscriptions;
-                                    msg.first().is_some_and(|first_frame| {
-                                        subscriptions.is_empty()
-                                            || subscriptions
-                                                .iter()
-                                                .any(|sub| first_frame.starts_with(sub))
-                                    })
-                                };
-
-                                if matches {
-                                    return Ok(Some(msg));
+                                if self.filter.accepts(&msg) {
+                                    if !self.base.options.conflate {
+                                        return Ok(Some(msg));
+                                    }
+                                    // The newer message replaces the pending one.
+                                    latest = Some(msg);
+                                } else {
+                                    trace!("[SUB] Message filtered out (no matching subscription)");
                                 }
-                                trace!("[SUB] Message filtered out (no matching subscription)");
                                 continue 'outer;
                             }
                         }
                     }
                 }
 
+                // Conflating: the buffer is drained, deliver the newest message.
+                if latest.is_some() {
+                    return Ok(latest);
+                }
+
                 // Need more data - read raw bytes from stream
                 let n = self.base.read_raw().await?;
                 if n == 0 {
@@ -234,11 +489,11 @@ where
 
     /// Close the socket gracefully.
     ///
-    /// SUB sockets don't send data, so this simply drops the socket.
-    /// The linger option is not applicable to SUB sockets.
-    pub async fn close(self) -> io::Result<()> {
+    /// Shuts down the underlying stream. Nothing is buffered on this socket
+    /// type beyond a pending heartbeat reply, so linger rarely applies.
+    pub async fn close(mut self) -> io::Result<()> {
         trace!("[SUB] Closing socket");
-        Ok(())
+        self.base.close().await
     }
 
     /// Get a reference to the socket options.
@@ -247,16 +502,95 @@ where
         &self.base.options
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.base.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        &mut self.base.options
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
     }
 
-    /// Set socket options (builder-style).
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
     #[inline]
-    pub fn set_options(&mut self, options: SocketOptions) {
-        self.base.set_options(options);
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// The latest connection attempts (initial connect and reconnects),
+    /// oldest first, with their timestamps and errors.
+    #[inline]
+    pub const fn connect_history(&self) -> &monocoque_core::reconnect::ConnectHistory {
+        self.base.connect_history()
+    }
+
+    /// Multi-line dump of the socket's endpoint, state, non-default options,
+    /// buffer usage and connection history, for logs and bug reports.
+    pub fn diagnostics(&self) -> String {
+        self.base.diagnostics()
+    }
+
+    /// The same state as [`diagnostics`](Self::diagnostics) as a structured
+    /// [`DiagnosticsSnapshot`](crate::DiagnosticsSnapshot), serializable with
+    /// the `serde` feature.
+    pub fn snapshot(&self) -> crate::DiagnosticsSnapshot {
+        self.base.snapshot(SocketType::Sub)
+    }
+
+    /// Number of reconnection attempts made since the connection was lost.
+    #[inline]
+    pub const fn reconnect_attempt(&self) -> u32 {
+        self.base.reconnect_attempt()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
     }
 
     /// Get the socket type.
@@ -334,11 +668,44 @@ impl SubSocket<TcpStream> {
         Self::connect_with_options(addr, SocketOptions::default()).await
     }
 
+    /// Connect and keep the connection up on its own.
+    ///
+    /// [`recv`](SubSocket::recv) on the returned socket survives publisher
+    /// restarts: when the connection drops it reconnects with backoff,
+    /// replays every subscription and pattern, and carries on receiving.
+    /// Subscription changes made while disconnected are applied on the next
+    /// connection. Gives up with `NotConnected` once `max_reconnect_attempts`
+    /// consecutive attempts have failed.
+    pub async fn connect_with_reconnect(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+    ) -> io::Result<Self> {
+        Self::connect_with_reconnect_options(addr, SocketOptions::default()).await
+    }
+
+    /// [`connect_with_reconnect`](Self::connect_with_reconnect) with custom
+    /// options.
+    pub async fn connect_with_reconnect_options(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+        options: SocketOptions,
+    ) -> io::Result<Self> {
+        let mut socket = Self::connect_with_options(addr, options).await?;
+        socket.base.auto_reconnect = true;
+        Ok(socket)
+    }
+
     /// Connect with custom options, storing the endpoint for reconnection.
+    ///
+    /// Subscriptions set in `options` are sent as in
+    /// [`with_options`](SubSocket::with_options).
     pub async fn connect_with_options(
         addr: impl monocoque_core::rt::ToSocketAddrs,
-        options: SocketOptions,
+        mut options: SocketOptions,
     ) -> io::Result<Self> {
+        if let Some(id) = options.routing_id.as_deref() {
+            SocketOptions::validate_routing_id(id)?;
+        }
+        let started_at = std::time::SystemTime::now();
+        let started = std::time::Instant::now();
         let stream = TcpStream::connect(addr).await?;
         let peer_addr = stream.peer_addr()?;
         crate::utils::configure_tcp_stream(&stream, &options, "SUB")?;
@@ -348,11 +715,11 @@ impl SubSocket<TcpStream> {
             &mut stream,
             SocketType::Sub,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -361,87 +728,28 @@ impl SubSocket<TcpStream> {
             peer_addr
         );
 
+        let initial_subs = std::mem::take(&mut options.subscriptions);
+        let initial_unsubs = std::mem::take(&mut options.unsubscriptions);
+
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
         let mut base =
-            crate::base::SocketBase::with_endpoint(stream, SocketType::Sub, endpoint, options);
+            crate::base::SocketBase::with_tcp_endpoint(stream, SocketType::Sub, endpoint, options);
+        base.record_initial_connect(started_at, started);
         base.curve_cipher = handshake_result.curve_cipher;
-        Ok(Self {
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
+        let mut socket = Self {
             base,
             frames: SmallVec::new(),
-            subscriptions: Vec::new(),
-        })
-    }
-
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.base.is_connected()
-    }
-
-    /// Try to reconnect to the stored endpoint and re-send all active subscriptions.
-    pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.base.try_reconnect(SocketType::Sub).await?;
-        let subs = std::mem::take(&mut self.subscriptions);
-        for prefix in &subs {
-            self.send_sub_event(0x01, prefix).await?;
+            filter: SubscriptionFilter::new(),
+        };
+        for prefix in initial_subs {
+            socket.subscribe(prefix).await?;
         }
-        self.subscriptions = subs;
-        Ok(())
-    }
-
-    /// Receive a message with automatic reconnection on EOF or network error.
-    ///
-    /// If the socket was created with `connect()` and stores an endpoint, this
-    /// method loops: on EOF or broken-pipe it clears the stream and calls
-    /// `try_reconnect()` (which applies exponential backoff), then retries `recv()`.
-    ///
-    /// Respects `max_reconnect_attempts`  -  returns `NotConnected` when exhausted.
-    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        let max = self.base.options.max_reconnect_attempts;
-        let mut attempts = 0u32;
-
-        loop {
-            if self.base.stream.is_none() {
-                if let Some(limit) = max
-                    && attempts >= limit
-                {
-                    return Err(io::Error::new(
-                        io::ErrorKind::NotConnected,
-                        format!("Max {} reconnection attempts exceeded", limit),
-                    ));
-                }
-                attempts += 1;
-                trace!(
-                    "[SUB] Stream disconnected, reconnecting (attempt {})",
-                    attempts
-                );
-                self.try_reconnect().await?;
-            }
-
-            match self.recv().await {
-                Ok(Some(msg)) => return Ok(Some(msg)),
-                // EOF: read_raw() already set stream = None
-                Ok(None) => {
-                    debug!("[SUB] EOF on recv, will reconnect");
-                }
-                Err(e) => {
-                    if self.base.stream.is_none()
-                        || matches!(
-                            e.kind(),
-                            io::ErrorKind::ConnectionReset
-                                | io::ErrorKind::ConnectionAborted
-                                | io::ErrorKind::BrokenPipe
-                                | io::ErrorKind::UnexpectedEof
-                        )
-                    {
-                        debug!("[SUB] Connection error on recv ({}), will reconnect", e);
-                        self.base.stream = None;
-                    } else {
-                        return Err(e);
-                    }
-                }
-            }
+        for prefix in initial_unsubs {
+            socket.unsubscribe(&prefix).await?;
         }
+        Ok(socket)
     }
 }
 
@@ -0,0 +1,114 @@
+//! TLS transport for ZMTP sockets, built on rustls.
+//!
+//! ZMTP's own CURVE mechanism encrypts messages, but some deployments require
+//! TLS on the wire. This module wraps a stream in TLS so that any socket can
+//! run its ZMTP handshake (NULL, PLAIN or CURVE) inside the TLS session:
+//!
+//! 1. The TCP connection is opened (or accepted).
+//! 2. The TLS handshake runs to completion, verifying certificates.
+//! 3. The ZMTP greeting and handshake run over the resulting [`TlsStream`].
+//!
+//! A certificate the client does not trust fails step 2 with a
+//! [`TlsError::CertificateRejected`], so no ZMTP bytes reach an unverified
+//! peer. [`DealerSocket::connect_tls`](crate::DealerSocket::connect_tls) and
+//! [`RouterSocket::accept_tls`](crate::RouterSocket::accept_tls) run all
+//! three steps; [`client`] and [`server`] wrap any other stream for use with
+//! a socket's `with_options` constructor.
+//!
+//! The stream wrapper and its configuration live in
+//! [`monocoque_core::tls`] and are re-exported here; this module adds the
+//! TCP connect and accept helpers. [`TlsConfig::default`] verifies servers
+//! against the platform's root certificates.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use monocoque_core::options::SocketOptions;
+//! use monocoque_zmtp::DealerSocket;
+//! use monocoque_zmtp::tls::{TlsConfig, rustls::RootCertStore};
+//!
+//! # async fn example(roots: RootCertStore) -> std::io::Result<()> {
+//! let tls = TlsConfig::with_roots(roots);
+//! let mut dealer =
+//!     DealerSocket::connect_tls("tcp://broker.example.com:5555", &tls, SocketOptions::default())
+//!         .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{TcpListener, TcpStream};
+use std::io;
+use std::net::SocketAddr;
+
+pub use monocoque_core::tls::{
+    TlsAcceptorConfig, TlsConfig, TlsError, TlsStream, client, rustls, server, wrap_tcp,
+};
+
+/// Connect to `endpoint` (`tls://host:port`, `tcp://host:port` or
+/// `host:port`) and run the TLS handshake, verifying the certificate against
+/// `host`.
+pub async fn connect(endpoint: &str, config: &TlsConfig) -> io::Result<TlsStream<TcpStream>> {
+    connect_with_options(endpoint, config, &SocketOptions::default()).await
+}
+
+/// [`connect`] applying the TCP settings in `options` and bounding the TLS
+/// handshake by its `handshake_timeout`.
+pub async fn connect_with_options(
+    endpoint: &str,
+    config: &TlsConfig,
+    options: &SocketOptions,
+) -> io::Result<TlsStream<TcpStream>> {
+    if let Ok(Endpoint::Tls(addr, name)) = Endpoint::parse(endpoint) {
+        let stream = TcpStream::connect(addr.as_str()).await?;
+        crate::utils::configure_tcp_stream(&stream, options, "TLS")?;
+        return within_handshake_timeout(options, wrap_tcp(stream, &name, config)).await;
+    }
+    let addr = endpoint.strip_prefix("tcp://").unwrap_or(endpoint);
+    let host = addr
+        .rsplit_once(':')
+        .map(|(host, _)| host.trim_start_matches('[').trim_end_matches(']'))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("TLS endpoint {endpoint:?} has no port"),
+            )
+        })?;
+    let stream = TcpStream::connect(addr).await?;
+    crate::utils::configure_tcp_stream(&stream, options, "TLS")?;
+    within_handshake_timeout(options, client(stream, config, host)).await
+}
+
+/// Accept the next connection on `listener` and run the TLS handshake.
+pub async fn accept(
+    listener: &TcpListener,
+    config: &TlsAcceptorConfig,
+) -> io::Result<(TlsStream<TcpStream>, SocketAddr)> {
+    accept_with_options(listener, config, &SocketOptions::default()).await
+}
+
+/// [`accept`] applying the TCP settings in `options` and bounding the TLS
+/// handshake by its `handshake_timeout`.
+pub async fn accept_with_options(
+    listener: &TcpListener,
+    config: &TlsAcceptorConfig,
+    options: &SocketOptions,
+) -> io::Result<(TlsStream<TcpStream>, SocketAddr)> {
+    let (stream, peer) = listener.accept().await?;
+    crate::utils::configure_tcp_stream(&stream, options, "TLS")?;
+    let stream = within_handshake_timeout(options, server(stream, config)).await?;
+    Ok((stream, peer))
+}
+
+async fn within_handshake_timeout<T>(
+    options: &SocketOptions,
+    handshake: impl Future<Output = io::Result<T>>,
+) -> io::Result<T> {
+    match options.handshake_deadline() {
+        None => handshake.await,
+        Some(dur) => monocoque_core::rt::timeout(dur, handshake)
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))?,
+    }
+}
@@ -88,11 +88,17 @@ pub fn encode_frame(flags: u8, body: &Bytes) -> Bytes {
 /// Optional:
 /// - Identity
 pub fn build_ready(socket_type: &str, identity: Option<&[u8]>) -> Bytes {
+    build_metadata_command("READY", socket_type, identity)
+}
+
+/// Build a metadata command body named `name` (`READY`, or `INITIATE` from a
+/// PLAIN client) carrying the same properties as [`build_ready`].
+pub fn build_metadata_command(name: &str, socket_type: &str, identity: Option<&[u8]>) -> Bytes {
     let mut body = BytesMut::new();
 
     // Command name
-    body.put_u8(5);
-    body.extend_from_slice(b"READY");
+    body.put_u8(name.len() as u8);
+    body.extend_from_slice(name.as_bytes());
 
     // Mandatory: Socket-Type
     put_property(&mut body, "Socket-Type", socket_type.as_bytes());
@@ -107,7 +113,7 @@ pub fn build_ready(socket_type: &str, identity: Option<&[u8]>) -> Bytes {
 
 /// Helper: encode a READY property
 #[inline]
-fn put_property(dst: &mut BytesMut, name: &str, value: &[u8]) {
+pub fn put_property(dst: &mut BytesMut, name: &str, value: &[u8]) {
     let name_bytes = name.as_bytes();
 
     dst.put_u8(name_bytes.len() as u8);
@@ -22,8 +22,10 @@
 //! ```
 
 use bytes::{Bytes, BytesMut};
+use monocoque_core::endpoint::Endpoint;
 use monocoque_core::io::take_read_buffer;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
 use monocoque_core::rt::{TcpListener, TcpStream};
 use monocoque_core::subscription::{SubscriptionEvent, SubscriptionTrie};
 use smallvec::SmallVec;
@@ -32,6 +34,7 @@ use std::fmt;
 use std::io;
 use tracing::{debug, trace};
 
+use crate::codec::{Command, parse_command};
 use crate::handshake::perform_handshake_with_options;
 use crate::session::SocketType;
 use crate::xsub::XSubSocket;
@@ -95,6 +98,8 @@ impl XPubSubscriber {
 /// ```
 pub struct XPubSocket {
     listener: TcpListener,
+    /// The bound address, as reported by `last_endpoint()`.
+    endpoint: Endpoint,
     subscribers: HashMap<SubscriberId, XPubSubscriber>,
     next_id: SubscriberId,
     options: SocketOptions,
@@ -116,6 +121,9 @@ pub struct XPubSocket {
     /// When the count drops to zero, the topic is removed from `seen_topics`
     /// and an Unsubscribe event is delivered.
     topic_refcount: HashMap<Vec<u8>, usize>,
+    /// Application-visible subscription index, if one was attached with
+    /// [`XPubSocket::with_shared_index`].
+    shared_index: Option<SharedSubscriptionIndex>,
 }
 
 impl XPubSocket {
@@ -138,20 +146,15 @@ impl XPubSocket {
     ///
     /// Honors `options.reuse_port`: when set, the listener is bound with
     /// `SO_REUSEPORT` so several XPUB acceptors can share one port.
+    /// `options.so_reuseaddr` is honored the same way.
     pub async fn bind_with_options(addr: &str, options: SocketOptions) -> io::Result<Self> {
-        let listener = if options.reuse_port {
-            let sock_addr = addr
-                .parse()
-                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid bind address"))?;
-            monocoque_core::rt::bind_reuseport(sock_addr)?
-        } else {
-            TcpListener::bind(addr).await?
-        };
+        let listener = monocoque_core::rt::bind_listener(addr, &options).await?;
         let local_addr = listener.local_addr()?;
         debug!("[XPUB] Bound to {}", local_addr);
 
         Ok(Self {
             listener,
+            endpoint: Endpoint::Tcp(local_addr),
             subscribers: HashMap::new(),
             next_id: 1,
             options,
@@ -159,9 +162,30 @@ impl XPubSocket {
             upstream: None,
             seen_topics: HashSet::new(),
             topic_refcount: HashMap::new(),
+            shared_index: None,
         })
     }
 
+    /// Mirror subscriptions into an application-visible index.
+    ///
+    /// Each event read by [`Self::recv_subscription`] is applied to `index`
+    /// (keyed by an internal per-subscriber id) before the call returns, in
+    /// verbose and non-verbose mode alike, so a query made right after
+    /// receiving an event already reflects it. Subscribers evicted by
+    /// [`Self::send`] have their entries removed. `send()` itself never reads
+    /// the index.
+    #[must_use]
+    pub fn with_shared_index(mut self, index: SharedSubscriptionIndex) -> Self {
+        self.shared_index = Some(index);
+        self
+    }
+
+    /// The attached application-visible subscription index, if any.
+    #[must_use]
+    pub const fn shared_index(&self) -> Option<&SharedSubscriptionIndex> {
+        self.shared_index.as_ref()
+    }
+
     /// Accept new subscriber connections (non-blocking).
     ///
     /// Call this periodically to accept new subscribers.
@@ -180,7 +204,7 @@ impl XPubSocket {
                     &mut stream,
                     SocketType::Xpub,
                     self.options.routing_id.as_deref(),
-                    Some(self.options.handshake_timeout),
+                    self.options.handshake_deadline(),
                     &self.options,
                 )
                 .await?;
@@ -196,11 +220,15 @@ impl XPubSocket {
 
                 let mut curve_cipher = handshake_result.curve_cipher;
 
-                // Send welcome message if configured
+                // Send welcome message if configured. It is part of connection
+                // setup, so it is bounded by the handshake timeout: a subscriber
+                // that never reads cannot stall `accept`. A welcome that did not
+                // go out whole leaves the stream mid-frame, so that subscriber
+                // is dropped rather than registered.
                 if let Some(ref welcome_msg) = self.options.xpub_welcome_msg.clone() {
                     use bytes::BytesMut;
                     use compio_buf::BufResult;
-                    use compio_io::AsyncWriteExt;
+                    use monocoque_core::timeout::write_all_with_timeout;
 
                     let wire = if let Some(ref mut cipher) = curve_cipher {
                         let mut buf = BytesMut::new();
@@ -215,12 +243,19 @@ impl XPubSocket {
                         buf.freeze()
                     };
 
-                    let BufResult(result, _) = stream.write_all(wire).await;
+                    let result = write_all_with_timeout(
+                        &mut stream,
+                        wire,
+                        self.options.handshake_deadline(),
+                    )
+                    .await
+                    .and_then(|BufResult(result, _)| result);
                     if let Err(e) = result {
-                        trace!(
-                            "[XPUB] Failed to send welcome message to subscriber {}: {}",
+                        debug!(
+                            "[XPUB] Failed to send welcome message to subscriber {}, dropping it: {}",
                             id, e
                         );
+                        return Ok(());
                     }
                 }
 
@@ -232,10 +267,7 @@ impl XPubSocket {
                         subscriptions: SubscriptionTrie::new(),
                         recv_buf: monocoque_core::buffer::SegmentedBuffer::new(),
                         read_buf: BytesMut::new(),
-                        decoder: self.options.max_msg_size.map_or_else(
-                            crate::codec::ZmtpDecoder::new,
-                            crate::codec::ZmtpDecoder::with_max_frame_size,
-                        ),
+                        decoder: crate::codec::ZmtpDecoder::with_size_limits(&self.options),
                         curve_cipher,
                     },
                 );
@@ -332,23 +364,33 @@ impl XPubSocket {
                                             }
                                         } else {
                                             // Non-MESSAGE command (e.g. PING): handle and skip.
-                                            if crate::base::is_ping_payload(&frame.payload) {
+                                            if let Ok(Command::Ping { context, .. }) = parse_command(&frame) {
                                                 use compio_io::AsyncWriteExt;
-                                                let pong = crate::base::build_pong_frame();
+                                                let pong = crate::base::build_pong_frame(&context);
                                                 let BufResult(result, _) = sub.stream.write_all(pong).await;
                                                 let _ = result;
                                             }
                                             continue;
                                         }
                                     } else {
-                                        if crate::base::is_ping_payload(&frame.payload) {
-                                            use compio_io::AsyncWriteExt;
-                                            let pong = crate::base::build_pong_frame();
-                                            let BufResult(result, _) =
-                                                sub.stream.write_all(pong).await;
-                                            let _ = result;
+                                        match parse_command(&frame) {
+                                            // ZMTP 3.1 SUBSCRIBE/CANCEL command.
+                                            Ok(Command::Subscribe(prefix)) => {
+                                                SubscriptionEvent::Subscribe(prefix).to_message()
+                                            }
+                                            Ok(Command::Cancel(prefix)) => {
+                                                SubscriptionEvent::Unsubscribe(prefix).to_message()
+                                            }
+                                            Ok(Command::Ping { context, .. }) => {
+                                                use compio_io::AsyncWriteExt;
+                                                let pong = crate::base::build_pong_frame(&context);
+                                                let BufResult(result, _) =
+                                                    sub.stream.write_all(pong).await;
+                                                let _ = result;
+                                                continue;
+                                            }
+                                            _ => continue,
                                         }
-                                        continue;
                                     }
                                 } else {
                                     frame.payload
@@ -429,6 +471,17 @@ impl XPubSocket {
                                         }
                                     };
 
+                                    if let Some(index) = &self.shared_index {
+                                        match &event {
+                                            SubscriptionEvent::Subscribe(prefix) => {
+                                                index.subscribe(sub.id, prefix.clone());
+                                            }
+                                            SubscriptionEvent::Unsubscribe(prefix) => {
+                                                index.unsubscribe(sub.id, prefix);
+                                            }
+                                        }
+                                    }
+
                                     if should_deliver {
                                         self.pending_events.push(event);
                                     }
@@ -537,6 +590,9 @@ impl XPubSocket {
 
         for id in dead_subs {
             self.subscribers.remove(&id);
+            if let Some(index) = &self.shared_index {
+                index.remove_peer(id);
+            }
             debug!("[XPUB] Removed dead subscriber {}", id);
         }
 
@@ -548,6 +604,68 @@ impl XPubSocket {
         self.subscribers.len()
     }
 
+    /// Check if at least one subscriber is attached.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        !self.subscribers.is_empty()
+    }
+
+    /// Always `false`: a failed write evicts only that subscriber, so no
+    /// send leaves the socket unusable.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        false
+    }
+
+    /// Get the endpoint this socket is bound to.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> Option<&Endpoint> {
+        Some(&self.endpoint)
+    }
+
+    /// Get socket options
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        &self.options
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.options.update(f)
+    }
+
+    /// Close the socket, shutting down every subscriber connection and the
+    /// upstream connection, if any.
+    ///
+    /// Sends write each message out in full, so there is nothing to linger
+    /// over.
+    pub async fn close(mut self) -> io::Result<()> {
+        use compio_io::AsyncWrite;
+
+        trace!("[XPUB] Closing socket");
+        for sub in self.subscribers.values_mut() {
+            let _ = sub.stream.shutdown().await;
+        }
+        if let Some(upstream) = self.upstream.take() {
+            upstream.close().await?;
+        }
+        Ok(())
+    }
+
     /// Get the local address.
     pub fn local_addr(&self) -> io::Result<std::net::SocketAddr> {
         self.listener.local_addr()
@@ -23,14 +23,15 @@ use crate::base::SocketBase;
 use bytes::Bytes;
 use compio_io::{AsyncRead, AsyncWrite};
 use monocoque_core::endpoint::Endpoint;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use monocoque_core::subscription::{SubscriptionEvent, SubscriptionTrie};
 use smallvec::SmallVec;
 use std::io;
+use std::time::Duration;
 use tracing::{debug, trace};
 
-use crate::handshake::perform_handshake_with_options;
+use crate::handshake::{handshake_error, perform_handshake_with_options};
 use crate::session::SocketType;
 
 /// XSUB (Extended Subscriber) socket.
@@ -95,11 +96,11 @@ where
             &mut stream,
             SocketType::Xsub,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_socket_type = ?handshake_result.peer_socket_type,
@@ -108,6 +109,8 @@ where
 
         let mut base = SocketBase::new(stream, SocketType::Xsub, options);
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             subscriptions: SubscriptionTrie::new(),
@@ -132,11 +135,32 @@ where
     /// # }
     /// ```
     pub async fn subscribe(&mut self, prefix: impl Into<Bytes>) -> io::Result<()> {
-        let prefix = prefix.into();
+        let timeout = self.control_timeout();
+        self.subscribe_within(prefix.into(), timeout).await
+    }
+
+    /// Subscribe to messages with the given prefix, giving up after `timeout`.
+    ///
+    /// On `TimedOut` the prefix is not recorded; the socket is poisoned if the
+    /// deadline dropped a write in flight.
+    pub async fn subscribe_timeout(
+        &mut self,
+        prefix: impl Into<Bytes>,
+        timeout: Duration,
+    ) -> io::Result<()> {
+        self.subscribe_within(prefix.into(), Some(timeout)).await
+    }
+
+    async fn subscribe_within(
+        &mut self,
+        prefix: Bytes,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
         trace!("[XSUB] Subscribing to: {:?}", prefix);
 
         // Send subscription message upstream first, then record the prefix locally.
-        self.send_subscription_event_prefix(0x01, &prefix).await?;
+        self.send_subscription_event_prefix(0x01, &prefix, timeout)
+            .await?;
         self.subscriptions.subscribe(prefix);
 
         Ok(())
@@ -158,19 +182,48 @@ where
     /// # }
     /// ```
     pub async fn unsubscribe(&mut self, prefix: impl Into<Bytes>) -> io::Result<()> {
-        let prefix = prefix.into();
-        trace!("[XSUB] Unsubscribing from: {:?}", prefix);
+        let timeout = self.control_timeout();
+        self.unsubscribe_within(prefix.into(), timeout).await
+    }
 
-        self.subscriptions.unsubscribe(&prefix);
+    /// Unsubscribe from messages with the given prefix, giving up after `timeout`.
+    ///
+    /// On `TimedOut` the unsubscription frame has been withdrawn and the prefix
+    /// stays subscribed.
+    pub async fn unsubscribe_timeout(
+        &mut self,
+        prefix: impl Into<Bytes>,
+        timeout: Duration,
+    ) -> io::Result<()> {
+        self.unsubscribe_within(prefix.into(), Some(timeout)).await
+    }
+
+    async fn unsubscribe_within(
+        &mut self,
+        prefix: Bytes,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        trace!("[XSUB] Unsubscribing from: {:?}", prefix);
 
         // Send unsubscribe message if verbose mode enabled
         if self.base.options.xsub_verbose_unsubs {
-            self.send_subscription_event_prefix(0x00, &prefix).await?;
+            self.send_subscription_event_prefix(0x00, &prefix, timeout)
+                .await?;
         }
 
+        self.subscriptions.unsubscribe(&prefix);
+
         Ok(())
     }
 
+    /// Timeout applied to subscription commands when none is given explicitly.
+    ///
+    /// A zero (non-blocking) `send_timeout` is ignored: subscriptions always
+    /// wait for their frame to be queued, as with libzmq's `ZMQ_SUBSCRIBE`.
+    fn control_timeout(&self) -> Option<Duration> {
+        self.base.options.send_timeout.filter(|dur| !dur.is_zero())
+    }
+
     /// Send a raw subscription event upstream (for proxies).
     ///
     /// This allows forwarding subscription messages in broker patterns.
@@ -188,48 +241,93 @@ where
     /// # }
     /// ```
     pub async fn send_subscription_event(&mut self, event: SubscriptionEvent) -> io::Result<()> {
-        let (cmd, prefix) = match &event {
-            SubscriptionEvent::Subscribe(prefix) => (0x01, prefix.as_ref()),
-            SubscriptionEvent::Unsubscribe(prefix) => (0x00, prefix.as_ref()),
+        let timeout = self.control_timeout();
+        self.send_event(&event, timeout).await
+    }
+
+    /// Send a message in XSUB wire form upstream.
+    ///
+    /// A message whose first frame starts with `0x01` subscribes and one
+    /// starting with `0x00` cancels; the topic is the rest of that frame, or
+    /// the second frame when the command byte stands alone (the form an XPUB
+    /// hands out). It goes to the connected publisher as a ZMTP SUBSCRIBE or
+    /// CANCEL and is recorded locally, like [`subscribe`](Self::subscribe)
+    /// and [`unsubscribe`](Self::unsubscribe). Cancels are forwarded whatever
+    /// `xsub_verbose_unsubs` says, since they come from downstream.
+    ///
+    /// Empty messages and other command bytes are dropped, as libzmq does.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use monocoque_zmtp::xsub::XSubSocket;
+    /// # use bytes::Bytes;
+    /// # async fn example(mut xsub: XSubSocket) -> std::io::Result<()> {
+    /// xsub.send(vec![Bytes::from_static(b"\x01"), Bytes::from_static(b"weather")])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        let Some(first) = msg.first() else {
+            return Ok(());
+        };
+        let Some(&cmd) = first.first() else {
+            return Ok(());
+        };
+        let topic = if first.len() == 1 {
+            msg.get(1).cloned().unwrap_or_default()
+        } else {
+            first.slice(1..)
         };
 
-        self.send_subscription_event_prefix(cmd, prefix).await
+        let timeout = self.control_timeout();
+        match cmd {
+            0x01 => {
+                self.send_subscription_event_prefix(0x01, &topic, timeout)
+                    .await?;
+                self.subscriptions.subscribe(topic);
+            }
+            0x00 => {
+                self.send_subscription_event_prefix(0x00, &topic, timeout)
+                    .await?;
+                self.subscriptions.unsubscribe(&topic);
+            }
+            _ => trace!("[XSUB] Dropping message with unknown command byte {cmd:#04x}"),
+        }
+        Ok(())
     }
 
-    async fn send_subscription_event_prefix(&mut self, cmd: u8, prefix: &[u8]) -> io::Result<()> {
-        use bytes::BytesMut;
-        use compio_buf::BufResult;
-        use compio_io::AsyncWriteExt;
+    async fn send_subscription_event_prefix(
+        &mut self,
+        cmd: u8,
+        prefix: &[u8],
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
+        let prefix = Bytes::copy_from_slice(prefix);
+        let event = if cmd == 0x01 {
+            SubscriptionEvent::Subscribe(prefix)
+        } else {
+            SubscriptionEvent::Unsubscribe(prefix)
+        };
+        self.send_event(&event, timeout).await
+    }
 
+    /// Send `event` upstream in the form the publisher's ZMTP version expects.
+    async fn send_event(
+        &mut self,
+        event: &SubscriptionEvent,
+        timeout: Option<Duration>,
+    ) -> io::Result<()> {
         trace!(
             "[XSUB] Sending subscription event ({} bytes)",
-            1 + prefix.len()
+            1 + event.prefix().len()
         );
 
-        let mut raw = BytesMut::with_capacity(1 + prefix.len());
-        raw.extend_from_slice(&[cmd]);
-        raw.extend_from_slice(prefix);
-        let raw = raw.freeze();
-
-        // Encrypt if CURVE is active; otherwise plain ZMTP frame.
-        let mut wire = BytesMut::with_capacity(raw.len() + 9);
-        if let Some(ref mut cipher) = self.base.curve_cipher {
-            let body = cipher
-                .encrypt_frame(&raw, false)
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
-            crate::base::append_zmtp_cmd_frame(&mut wire, &body);
-        } else {
-            crate::codec::encode_multipart(&[raw], &mut wire);
-        }
-        let wire = wire.freeze();
+        let wire = self.base.encode_subscription(event)?;
 
-        let stream =
-            self.base.stream.as_mut().ok_or_else(|| {
-                io::Error::new(io::ErrorKind::NotConnected, "Socket not connected")
-            })?;
-
-        let BufResult(result, _) = stream.write_all(wire).await;
-        result?;
+        // Queued behind any pending data; fully sent or withdrawn.
+        self.base.send_command(&wire, timeout).await?;
 
         trace!("[XSUB] Subscription event sent successfully");
         Ok(())
@@ -285,6 +383,53 @@ where
         }
     }
 
+    /// Close the socket gracefully by shutting down the underlying stream.
+    pub async fn close(mut self) -> io::Result<()> {
+        trace!("[XSUB] Closing socket");
+        self.base.close().await
+    }
+
+    /// Get a reference to the socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        &self.base.options
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.base.is_poisoned()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.base.update_options(|o| *o = options)
+    }
+
     /// Get the number of active subscriptions.
     pub fn subscription_count(&self) -> usize {
         self.subscriptions.len()
@@ -378,11 +523,11 @@ impl XSubSocket<TcpStream> {
             &mut stream,
             crate::session::SocketType::Xsub,
             options.routing_id.as_deref(),
-            Some(options.handshake_timeout),
+            options.handshake_deadline(),
             &options,
         )
         .await
-        .map_err(|e| io::Error::other(format!("Handshake failed: {}", e)))?;
+        .map_err(handshake_error)?;
 
         debug!(
             peer_identity = ?handshake_result.peer_identity,
@@ -392,13 +537,15 @@ impl XSubSocket<TcpStream> {
         );
 
         let endpoint = monocoque_core::endpoint::Endpoint::Tcp(peer_addr);
-        let mut base = crate::base::SocketBase::with_endpoint(
+        let mut base = crate::base::SocketBase::with_tcp_endpoint(
             stream,
             crate::session::SocketType::Xsub,
             endpoint,
             options,
         );
         base.curve_cipher = handshake_result.curve_cipher;
+        base.zmtp_version = handshake_result.version;
+        base.peer_metadata = handshake_result.peer_metadata;
         Ok(Self {
             base,
             subscriptions: SubscriptionTrie::new(),
@@ -411,6 +558,30 @@ impl XSubSocket<TcpStream> {
         self.base.is_connected()
     }
 
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &crate::PeerMetadata {
+        self.base.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> crate::ZmtpVersion {
+        self.base.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<crate::CloseReason> {
+        self.base.last_close_reason()
+    }
+
     /// Try to reconnect to the stored endpoint, re-sending all active subscriptions.
     pub async fn try_reconnect(&mut self) -> io::Result<()> {
         self.base
@@ -552,6 +723,84 @@ mod tests {
         });
     }
 
+    /// Stream that records everything written and never has data to read.
+    #[derive(Default)]
+    struct RecordingStream(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl AsyncRead for RecordingStream {
+        async fn read<B: compio_buf::IoBufMut>(
+            &mut self,
+            _buf: B,
+        ) -> compio_buf::BufResult<usize, B> {
+            std::future::pending().await
+        }
+    }
+
+    impl AsyncWrite for RecordingStream {
+        async fn write<B: compio_buf::IoBuf>(&mut self, buf: B) -> compio_buf::BufResult<usize, B> {
+            self.0.borrow_mut().extend_from_slice(buf.as_init());
+            compio_buf::BufResult(Ok(buf.buf_len()), buf)
+        }
+
+        async fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        async fn shutdown(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn send_forwards_subscription_commands_upstream() {
+        use monocoque_core::rt::LocalRuntime;
+
+        LocalRuntime::new().unwrap().block_on(async {
+            let upstream = RecordingStream::default();
+            let written = upstream.0.clone();
+            let mut xsub = XSubSocket {
+                base: SocketBase::new(upstream, SocketType::Xsub, SocketOptions::default()),
+                subscriptions: SubscriptionTrie::new(),
+            };
+
+            xsub.send(vec![
+                Bytes::from_static(b"\x01"),
+                Bytes::from_static(b"weather"),
+            ])
+            .await
+            .unwrap();
+            // A ZMTP 3.1 publisher gets SUBSCRIBE/CANCEL commands...
+            assert_eq!(written.borrow().as_slice(), b"\x04\x11\x09SUBSCRIBEweather");
+            assert!(xsub.is_subscribed(b"weather.paris"));
+
+            written.borrow_mut().clear();
+            xsub.send(vec![Bytes::from_static(b"\x00weather")])
+                .await
+                .unwrap();
+            assert_eq!(written.borrow().as_slice(), b"\x04\x0e\x06CANCELweather");
+            assert!(!xsub.is_subscribed(b"weather.paris"));
+
+            // ...and a 3.0 one the legacy data frames.
+            xsub.base.zmtp_version = crate::greeting::ZmtpVersion::V3_0;
+            written.borrow_mut().clear();
+            xsub.send(vec![Bytes::from_static(b"\x01weather")])
+                .await
+                .unwrap();
+            xsub.send(vec![Bytes::from_static(b"\x00weather")])
+                .await
+                .unwrap();
+            assert_eq!(
+                written.borrow().as_slice(),
+                b"\x00\x08\x01weather\x00\x08\x00weather"
+            );
+
+            written.borrow_mut().clear();
+            xsub.send(vec![Bytes::from_static(b"\x02x")]).await.unwrap();
+            xsub.send(vec![]).await.unwrap();
+            assert!(written.borrow().is_empty());
+        });
+    }
+
     #[test]
     fn test_subscription_event_creation() {
         let event = SubscriptionEvent::Subscribe(Bytes::from_static(b"topic"));
@@ -0,0 +1,45 @@
+//! Raw ZMTP fixtures shared by the tests that script one side of the wire.
+//!
+//! Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use compio_buf::BufResult;
+use compio_io::{AsyncReadExt, AsyncWriteExt};
+use monocoque_core::rt::TcpStream;
+use std::io;
+
+/// READY command advertising `Socket-Type: DEALER`, as a short command frame.
+pub const DEALER_READY: &[u8] = b"\x04\x1c\x05READY\x0bSocket-Type\x00\x00\x00\x06DEALER";
+
+/// A 64-byte ZMTP 3.1 greeting for the NULL mechanism.
+pub fn null_greeting() -> Vec<u8> {
+    null_greeting_with_minor(1)
+}
+
+/// A 64-byte ZMTP 3.`minor` greeting for the NULL mechanism.
+pub fn null_greeting_with_minor(minor: u8) -> Vec<u8> {
+    let mut greeting = vec![0u8; 64];
+    greeting[0] = 0xFF;
+    greeting[9] = 0x7F;
+    greeting[10] = 3;
+    greeting[11] = minor;
+    greeting[12..16].copy_from_slice(b"NULL");
+    greeting
+}
+
+/// Read exactly `len` bytes, or fail with the read error.
+pub async fn try_read_exact(stream: &mut TcpStream, len: usize) -> io::Result<Vec<u8>> {
+    let BufResult(res, buf) = stream.read_exact(vec![0u8; len]).await;
+    res.map(|()| buf)
+}
+
+/// Read exactly `len` bytes, panicking on error.
+pub async fn read_exact(stream: &mut TcpStream, len: usize) -> Vec<u8> {
+    try_read_exact(stream, len).await.unwrap()
+}
+
+/// Write all of `bytes`, panicking on error.
+pub async fn write_all(stream: &mut TcpStream, bytes: &[u8]) {
+    let BufResult(res, _) = stream.write_all(bytes.to_vec()).await;
+    res.unwrap();
+}
@@ -0,0 +1,143 @@
+//! Control-plane operations honour their timeouts against a stalled peer.
+//!
+//! `FaultyStream` wraps a real TCP stream and, while stalled, never completes a
+//! write — the same thing a peer that has stopped reading looks like once the
+//! kernel buffers are full. Subscription commands issued during a stall must
+//! return `TimedOut` within their bound. The timeout drops a write in flight,
+//! which io_uring may still complete, so the socket is then poisoned: later
+//! commands fail straight away instead of writing after bytes of unknown fate.
+
+use bytes::Bytes;
+use compio_buf::{BufResult, IoBuf, IoBufMut};
+use compio_io::{AsyncRead, AsyncWrite};
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{TcpListener, TcpStream};
+use monocoque_zmtp::publisher::PubSocket;
+use monocoque_zmtp::subscriber::SubSocket;
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// TCP stream whose writes hang while `stalled` is set.
+struct FaultyStream {
+    inner: TcpStream,
+    stalled: Rc<Cell<bool>>,
+}
+
+impl AsyncRead for FaultyStream {
+    async fn read<B: IoBufMut>(&mut self, buf: B) -> BufResult<usize, B> {
+        self.inner.read(buf).await
+    }
+}
+
+impl AsyncWrite for FaultyStream {
+    async fn write<B: IoBuf>(&mut self, buf: B) -> BufResult<usize, B> {
+        if self.stalled.get() {
+            return std::future::pending().await;
+        }
+        self.inner.write(buf).await
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush().await
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        self.inner.shutdown().await
+    }
+}
+
+/// Run a PUB that accepts one subscriber and holds it until `done`.
+fn spawn_publisher(
+    addr_tx: mpsc::Sender<std::net::SocketAddr>,
+    done_rx: mpsc::Receiver<()>,
+) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let mut pub_sock = PubSocket::new();
+                pub_sock.accept_subscriber(&listener).await.unwrap();
+                done_rx.recv().unwrap();
+            });
+    })
+}
+
+fn assert_bounded_timeout(result: io::Result<()>, started: Instant) {
+    let err = result.expect_err("subscription should not complete during a stall");
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "timeout took {:?}",
+        started.elapsed()
+    );
+}
+
+fn run_stalled_subscription<F>(options: SocketOptions, stalled_subscribe: F)
+where
+    F: AsyncFnOnce(&mut SubSocket<FaultyStream>) -> io::Result<()> + Send + 'static,
+{
+    let (addr_tx, addr_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel();
+    let publisher = spawn_publisher(addr_tx, done_rx);
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let client = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let stalled = Rc::new(Cell::new(false));
+                let stream = FaultyStream {
+                    inner: TcpStream::connect(addr).await.unwrap(),
+                    stalled: Rc::clone(&stalled),
+                };
+                let mut sub = SubSocket::with_options(stream, options).await.unwrap();
+
+                stalled.set(true);
+                let started = Instant::now();
+                assert_bounded_timeout(stalled_subscribe(&mut sub).await, started);
+
+                assert!(sub.is_poisoned());
+
+                // Even with the stall cleared nothing more is written on this
+                // connection, and the refusal is immediate.
+                stalled.set(false);
+                let started = Instant::now();
+                let err = sub.subscribe("news.").await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+                assert!(started.elapsed() < Duration::from_secs(1));
+                done_tx.send(()).unwrap();
+            });
+    });
+
+    client.join().expect("client thread panicked");
+    publisher.join().expect("publisher thread panicked");
+}
+
+#[test]
+fn test_subscribe_timeout_is_bounded_and_poisons() {
+    run_stalled_subscription(SocketOptions::default(), async |sub| {
+        sub.subscribe_timeout("news.", Duration::from_millis(50))
+            .await
+    });
+}
+
+#[test]
+fn test_subscribe_honours_send_timeout() {
+    let options = SocketOptions::default().with_send_timeout(Duration::from_millis(50));
+    run_stalled_subscription(options, async |sub| sub.subscribe("news.").await);
+}
+
+#[test]
+fn test_unsubscribe_timeout_is_bounded_and_poisons() {
+    run_stalled_subscription(SocketOptions::default(), async |sub| {
+        sub.unsubscribe_timeout(&Bytes::from("weather."), Duration::from_millis(50))
+            .await
+    });
+}
@@ -180,16 +180,99 @@ fn test_curve_handshake_sequence() {
 }
 
 async fn test_curve_handshake_sequence_impl() {
-    // This would be a full integration test with actual TCP streams
-    // For now, we just verify the state machine can be created
-
+    use bytes::Bytes;
+    use monocoque_core::rt::{self, TcpListener, TcpStream};
     use monocoque_zmtp::security::curve::{CurveClient, CurveServer};
+    use std::time::Duration;
 
     let client_keypair = CurveKeyPair::generate();
     let server_keypair = CurveKeyPair::generate();
+    let client_public = client_keypair.public;
+    let server_public = server_keypair.public;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = rt::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut server = CurveServer::new(server_keypair, "ROUTER");
+        let result = server
+            .handshake(&mut stream, Some(Duration::from_secs(5)))
+            .await
+            .unwrap();
+        (stream, result)
+    });
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let mut client = CurveClient::new(
+        client_keypair,
+        server_public,
+        "DEALER",
+        Some(Bytes::from_static(b"client-1")),
+    );
+    let client_result = client
+        .handshake(&mut stream, Some(Duration::from_secs(5)))
+        .await
+        .unwrap();
+    let (_server_stream, server_result) = rt::join(server).await;
+
+    // The server learns the client's real long-term key from the vouch.
+    assert_eq!(server_result.peer_public_key, Some(client_public));
+    assert_ne!(client_public.as_bytes(), &[0u8; CURVE_KEY_SIZE]);
+    assert_eq!(&server_result.peer_socket_type[..], b"DEALER");
+    assert_eq!(
+        server_result.peer_identity.as_deref(),
+        Some(&b"client-1"[..])
+    );
+    assert_eq!(&client_result.peer_socket_type[..], b"ROUTER");
+
+    // Both sides derived the same message key.
+    let mut client_cipher = client_result.cipher.unwrap();
+    let mut server_cipher = server_result.cipher.unwrap();
+    let sealed = client_cipher.encrypt_frame(b"hello", false).unwrap();
+    let (more, opened) = server_cipher.decrypt_frame(&sealed).unwrap();
+    assert!(!more);
+    assert_eq!(&opened[..], b"hello");
+}
 
-    let _client = CurveClient::new(client_keypair, server_keypair.public, "DEALER", None);
-    let _server = CurveServer::new(server_keypair, "ROUTER");
-
-    // Full handshake test would require mock streams or real TCP
+#[test]
+fn test_curve_handshake_rejects_wrong_server_key() {
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async {
+            use monocoque_core::rt::{self, TcpListener, TcpStream};
+            use monocoque_zmtp::security::curve::{CurveClient, CurveServer};
+            use std::time::Duration;
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let server = rt::spawn(async move {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut server = CurveServer::new(CurveKeyPair::generate(), "ROUTER");
+                server
+                    .handshake(&mut stream, Some(Duration::from_secs(5)))
+                    .await
+                    .is_err()
+            });
+
+            // The client trusts a key the server does not hold.
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            let mut client = CurveClient::new(
+                CurveKeyPair::generate(),
+                CurveKeyPair::generate().public,
+                "DEALER",
+                None,
+            );
+            let client_failed = rt::timeout(
+                Duration::from_secs(5),
+                client.handshake(&mut stream, Some(Duration::from_secs(5))),
+            )
+            .await
+            .map_or(true, |r| r.is_err());
+            drop(stream);
+            assert!(
+                rt::join(server).await,
+                "server accepted a HELLO for another key"
+            );
+            assert!(client_failed);
+        });
 }
@@ -0,0 +1,91 @@
+//! `max_msg_size` and `max_multipart_size` against a hostile peer.
+//!
+//! A raw ZMTP peer declares bodies far beyond the limits. The socket must
+//! refuse them from the frame header alone and drop the connection, rather
+//! than wait for (and buffer) gigabytes that will never arrive.
+
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use std::io;
+use std::time::Duration;
+
+mod common;
+use common::{DEALER_READY, null_greeting, read_exact, write_all};
+
+/// Long data-frame header declaring `len` body bytes.
+fn long_header(more: bool, len: u64) -> Vec<u8> {
+    let mut hdr = vec![0x02 | u8::from(more)];
+    hdr.extend_from_slice(&len.to_be_bytes());
+    hdr
+}
+
+/// A DEALER with `options`, connected to a raw peer that has completed the
+/// NULL handshake with it.
+async fn dealer_and_raw_peer(options: SocketOptions) -> (DealerSocket<TcpStream>, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (peer, dealer) = futures::join!(
+        async {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            write_all(&mut stream, &null_greeting()).await;
+            read_exact(&mut stream, 64).await;
+            let header = read_exact(&mut stream, 2).await;
+            read_exact(&mut stream, header[1] as usize).await;
+            write_all(&mut stream, DEALER_READY).await;
+            stream
+        },
+        async {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            DealerSocket::with_options(stream, options).await.unwrap()
+        }
+    );
+    (dealer, peer)
+}
+
+#[test]
+fn oversized_frame_header_drops_the_peer() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = SocketOptions::default().with_max_msg_size(Some(1 << 20));
+        let (mut dealer, mut peer) = dealer_and_raw_peer(options).await;
+
+        // 8 GB declared; only a few body bytes ever follow.
+        write_all(&mut peer, &long_header(false, 8 << 30)).await;
+        write_all(&mut peer, b"not much").await;
+
+        let err = rt::timeout(Duration::from_secs(5), dealer.recv())
+            .await
+            .expect("recv waited for the declared body")
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("8589934592"), "{err}");
+        assert!(!dealer.is_connected());
+    });
+}
+
+#[test]
+fn oversized_multipart_message_drops_the_peer() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = SocketOptions::default().with_max_multipart_size(Some(64 * 1024));
+        let (mut dealer, mut peer) = dealer_and_raw_peer(options).await;
+
+        // A complete message within the limit is delivered as usual.
+        write_all(&mut peer, b"\x01\x02ok\x00\x04done").await;
+        let msg = dealer.recv().await.unwrap().unwrap();
+        assert_eq!(msg, [&b"ok"[..], b"done"]);
+
+        // 40 KB buffered, then a header that would take the message to 80 KB.
+        let mut frame = long_header(true, 40 * 1024);
+        frame.resize(frame.len() + 40 * 1024, b'x');
+        write_all(&mut peer, &frame).await;
+        write_all(&mut peer, &long_header(false, 40 * 1024)).await;
+
+        let err = rt::timeout(Duration::from_secs(5), dealer.recv())
+            .await
+            .expect("recv waited for the declared body")
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("Multipart"), "{err}");
+        assert!(!dealer.is_connected());
+    });
+}
@@ -0,0 +1,62 @@
+//! `handshake_timeout` bounds the ZMTP handshake; zero disables the bound.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::router::RouterSocket;
+use std::time::Duration;
+
+/// Accept one connection and only start the handshake after `delay`.
+async fn slow_router(listener: TcpListener, delay: Duration) {
+    let (stream, _) = listener.accept().await.unwrap();
+    rt::sleep(delay).await;
+    // The client may already have given up; only echo if it is still there.
+    if let Ok(mut router) = RouterSocket::from_tcp(stream).await
+        && let Ok(Some(msg)) = router.recv().await
+    {
+        router.send(msg).await.unwrap();
+    }
+}
+
+#[test]
+fn zero_handshake_timeout_waits_for_slow_peer() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = rt::spawn(slow_router(listener, Duration::from_millis(200)));
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let options = SocketOptions::default().with_handshake_timeout(Duration::ZERO);
+        let mut dealer = DealerSocket::from_tcp_with_options(stream, options)
+            .await
+            .expect("zero handshake timeout must not fail a slow handshake");
+
+        dealer
+            .send(vec![Bytes::from_static(b"ping")])
+            .await
+            .unwrap();
+        let reply = dealer.recv().await.unwrap().expect("router EOF");
+        assert_eq!(reply, vec![Bytes::from_static(b"ping")]);
+        rt::join(server).await;
+    });
+}
+
+#[test]
+fn nonzero_handshake_timeout_still_times_out() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = rt::spawn(slow_router(listener, Duration::from_millis(300)));
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let options = SocketOptions::default().with_handshake_timeout(Duration::from_millis(50));
+        assert!(
+            DealerSocket::from_tcp_with_options(stream, options)
+                .await
+                .is_err(),
+            "a 50ms handshake timeout must fail against a 300ms-late peer"
+        );
+        rt::join(server).await;
+    });
+}
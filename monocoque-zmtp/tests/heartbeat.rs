@@ -0,0 +1,123 @@
+//! ZMTP 3.1 heartbeats on an otherwise idle DEALER.
+//!
+//! The peer speaks raw ZMTP so the tests see every PING and decide whether
+//! to answer it. An idle `recv()` keeps sending PINGs, stays connected while
+//! PONGs come back, drops a peer that goes silent, and answers the peer's own
+//! PINGs with a PONG echoing the context.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use std::io;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{DEALER_READY, null_greeting, read_exact, write_all};
+
+const IVL: Duration = Duration::from_millis(50);
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+/// Read one short frame, returning its flags and body.
+async fn read_frame(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+    let header = read_exact(stream, 2).await;
+    (header[0], read_exact(stream, header[1] as usize).await)
+}
+
+/// Accept one client and run the greeting/READY exchange as a DEALER peer.
+async fn accept(listener: &TcpListener) -> TcpStream {
+    let (mut stream, _) = listener.accept().await.unwrap();
+    write_all(&mut stream, &null_greeting()).await;
+    read_exact(&mut stream, 64).await;
+    write_all(&mut stream, DEALER_READY).await;
+    let (flags, _) = read_frame(&mut stream).await;
+    assert_eq!(flags, 0x04, "expected READY command frame");
+    stream
+}
+
+/// Connect a DEALER with heartbeats every `IVL` and a `TIMEOUT` deadline.
+async fn heartbeating_dealer(listener: &TcpListener) -> (DealerSocket<TcpStream>, TcpStream) {
+    let options = SocketOptions::default()
+        .with_heartbeat_ivl(IVL)
+        .with_heartbeat_ttl(Duration::from_secs(1))
+        .with_heartbeat_timeout(TIMEOUT);
+    let addr = listener.local_addr().unwrap();
+    let (peer, dealer) = futures::join!(accept(listener), async {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        DealerSocket::with_options(stream, options).await.unwrap()
+    });
+    (dealer, peer)
+}
+
+#[test]
+fn idle_recv_sends_pings_and_survives_answered_ones() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (mut dealer, mut peer) = heartbeating_dealer(&listener).await;
+
+        let peer = async move {
+            for _ in 0..3 {
+                let (flags, body) = read_frame(&mut peer).await;
+                assert_eq!(flags, 0x04, "expected a command frame");
+                assert!(body.starts_with(b"\x04PING"), "expected PING, got {body:?}");
+                // TTL is advertised in tenths of a second.
+                assert_eq!(&body[5..7], &10u16.to_be_bytes());
+                write_all(&mut peer, b"\x04\x05\x04PONG").await;
+            }
+            write_all(&mut peer, b"\x00\x05hello").await;
+            peer
+        };
+        let started = Instant::now();
+        let (msg, _peer) = futures::join!(dealer.recv(), peer);
+        assert_eq!(msg.unwrap(), Some(vec![Bytes::from_static(b"hello")]));
+        assert!(
+            started.elapsed() >= IVL * 2,
+            "PINGs sent before the interval"
+        );
+        assert!(dealer.is_connected());
+    });
+}
+
+#[test]
+fn silent_peer_is_dropped_after_heartbeat_timeout() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (mut dealer, mut peer) = heartbeating_dealer(&listener).await;
+
+        let peer = async move {
+            let (_, body) = read_frame(&mut peer).await;
+            assert!(body.starts_with(b"\x04PING"), "expected PING, got {body:?}");
+            // Never answer; keep the TCP connection open.
+            peer
+        };
+        let started = Instant::now();
+        let (result, _peer) =
+            futures::join!(rt::timeout(Duration::from_secs(5), dealer.recv()), peer);
+        let err = result.expect("heartbeat timeout never fired").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() >= TIMEOUT);
+        assert!(!dealer.is_connected(), "dead peer should be disconnected");
+    });
+}
+
+#[test]
+fn peer_ping_is_answered_with_its_context() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut peer, mut dealer) = futures::join!(accept(&listener), async {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            DealerSocket::new(stream).await.unwrap()
+        });
+
+        let peer = async move {
+            write_all(&mut peer, b"\x04\x0d\x04PING\x00\x0Actx-42").await;
+            let (flags, body) = read_frame(&mut peer).await;
+            assert_eq!(flags, 0x04, "expected a command frame");
+            assert_eq!(body, b"\x04PONGctx-42");
+            write_all(&mut peer, b"\x00\x02ok").await;
+        };
+        let (msg, ()) = futures::join!(dealer.recv(), peer);
+        assert_eq!(msg.unwrap(), Some(vec![Bytes::from_static(b"ok")]));
+    });
+}
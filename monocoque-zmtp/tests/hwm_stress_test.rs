@@ -4,10 +4,11 @@
 //! blocked writing to a slow subscriber, its channel fills up and subsequent
 //! `send()` calls increment the drop counter rather than blocking the caller.
 //!
-//! DEALER HWM: `send_buffered()` enforces `send_hwm` via `WouldBlock`.
+//! DEALER HWM: `send_buffered()` enforces `send_hwm` via `WouldBlock`, and
+//! `send_batch()` follows `hwm_policy`.
 
 use bytes::Bytes;
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{HwmPolicy, SocketOptions};
 use monocoque_zmtp::dealer::DealerSocket;
 use monocoque_zmtp::publisher::PubSocket as InternalPub;
 use monocoque_zmtp::router::RouterSocket;
@@ -159,6 +160,74 @@ fn test_dealer_send_buffered_hwm_returns_would_block() {
         });
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// DEALER send_batch HWM policy
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A batch larger than `send_hwm` is refused under `HwmPolicy::Error` and
+/// flushed in HWM-sized pieces under `HwmPolicy::Block`, arriving in order.
+#[test]
+fn test_dealer_send_batch_follows_hwm_policy() {
+    const HWM: usize = 2;
+    const BATCH: usize = 7;
+
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (got_tx, got_rx) = mpsc::channel::<Vec<Bytes>>();
+
+    // Server: record every body received.
+    thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = monocoque_core::rt::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+                while let Ok(Some(msg)) = router.recv().await {
+                    got_tx.send(msg[1..].to_vec()).unwrap();
+                }
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let batch: Vec<Vec<Bytes>> = (0..BATCH)
+                .map(|i| vec![Bytes::from(format!("m{i}"))])
+                .collect();
+
+            let opts = SocketOptions::default()
+                .with_send_hwm(HWM)
+                .with_hwm_policy(HwmPolicy::Error);
+            let mut dealer = DealerSocket::connect_with_options(addr, opts)
+                .await
+                .unwrap();
+            let err = dealer.send_batch(&batch).await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock);
+            assert_eq!(dealer.buffered_messages(), 0, "nothing may be queued");
+
+            dealer
+                .update_options(|o| o.hwm_policy = HwmPolicy::Block)
+                .unwrap();
+            dealer.send_buffered(vec![Bytes::from("first")]).unwrap();
+            dealer
+                .send_batch(&batch)
+                .await
+                .expect("Block policy waits for a flush instead of failing");
+            assert_eq!(dealer.buffered_messages(), 0);
+
+            let expected = std::iter::once(vec![Bytes::from("first")]).chain(batch);
+            for want in expected {
+                let got = got_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+                assert_eq!(got, want);
+            }
+        });
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // ROUTER send_buffered HWM → WouldBlock
 // ─────────────────────────────────────────────────────────────────────────────
@@ -0,0 +1,282 @@
+//! Frames above `stream_threshold` are streamed, never reassembled.
+//!
+//! `ThrottledPeer` is an in-memory duplex stream playing a ROUTER (or a
+//! DEALER, facing a ROUTER): it answers the handshake, then sends a two-frame
+//! message whose second frame declares 256 MB. The body is generated on demand and handed out at most `THROTTLE`
+//! bytes per read. Heartbeats are on and the peer never answers a PING, and
+//! it pauses before the large frame for longer than the heartbeat timeout.
+
+use bytes::Bytes;
+use compio_buf::{BufResult, IoBuf, IoBufMut};
+use compio_io::{AsyncRead, AsyncWrite};
+use monocoque_core::options::SocketOptions;
+use monocoque_zmtp::codec::ZmtpError;
+use monocoque_zmtp::{DealerSocket, RouterSocket};
+use std::collections::VecDeque;
+use std::io;
+use std::time::Duration;
+
+const FRAME_LEN: u64 = 256 << 20;
+const THRESHOLD: usize = 1 << 20;
+const THROTTLE: usize = 16 * 1024;
+/// Pause before the large frame, longer than the heartbeat timeout.
+const PAUSE: Duration = Duration::from_millis(50);
+
+/// Body byte at `offset`; a slice of `PATTERN` holds any chunk's expectation.
+fn pattern() -> Vec<u8> {
+    (0..THROTTLE + 256).map(|i| i as u8).collect()
+}
+
+enum Step {
+    Bytes(Vec<u8>),
+    Pause(Duration),
+    /// A generated body of this many bytes.
+    Body(u64),
+}
+
+struct ThrottledPeer {
+    steps: VecDeque<Step>,
+    pattern: Vec<u8>,
+    body_offset: u64,
+}
+
+impl ThrottledPeer {
+    fn new() -> Self {
+        Self::with(b"ROUTER", FRAME_LEN)
+    }
+
+    /// A peer of `socket_type` whose large frame declares `frame_len` bytes.
+    fn with(socket_type: &[u8], frame_len: u64) -> Self {
+        let mut greeting = vec![0u8; 64];
+        greeting[0] = 0xFF;
+        greeting[9] = 0x7F;
+        greeting[10] = 3;
+        greeting[11] = 1;
+        greeting[12..16].copy_from_slice(b"NULL");
+
+        let mut ready_body = b"\x05READY\x0bSocket-Type".to_vec();
+        ready_body.extend_from_slice(&(socket_type.len() as u32).to_be_bytes());
+        ready_body.extend_from_slice(socket_type);
+        let mut ready = vec![0x04, ready_body.len() as u8];
+        ready.extend_from_slice(&ready_body);
+
+        let mut header = vec![0x02];
+        header.extend_from_slice(&frame_len.to_be_bytes());
+
+        Self {
+            steps: VecDeque::from([
+                Step::Bytes(greeting),
+                Step::Bytes(ready),
+                Step::Bytes(b"\x01\x04head".to_vec()),
+                Step::Pause(PAUSE),
+                Step::Bytes(header),
+                Step::Body(frame_len),
+                Step::Bytes(b"\x00\x04tail".to_vec()),
+            ]),
+            pattern: pattern(),
+            body_offset: 0,
+        }
+    }
+}
+
+impl ThrottledPeer {
+    /// Copy up to `cap` bytes of the current step into `dst`.
+    fn next_bytes(&mut self, dst: &mut [std::mem::MaybeUninit<u8>]) -> usize {
+        let cap = dst.len().min(THROTTLE);
+        let (src, n) = match self.steps.front_mut() {
+            None => return 0,
+            Some(Step::Bytes(bytes)) => {
+                let n = cap.min(bytes.len());
+                let src: Vec<u8> = bytes.drain(..n).collect();
+                if Vec::is_empty(bytes) {
+                    self.steps.pop_front();
+                }
+                (src, n)
+            }
+            Some(Step::Body(left)) => {
+                let n = cap.min(usize::try_from(*left).unwrap_or(usize::MAX));
+                *left -= n as u64;
+                if *left == 0 {
+                    self.steps.pop_front();
+                }
+                let start = (self.body_offset % 256) as usize;
+                self.body_offset += n as u64;
+                (self.pattern[start..start + n].to_vec(), n)
+            }
+            Some(Step::Pause(_)) => unreachable!("pauses are taken before copying"),
+        };
+        for (d, s) in dst.iter_mut().zip(&src) {
+            d.write(*s);
+        }
+        n
+    }
+}
+
+impl AsyncRead for ThrottledPeer {
+    async fn read<B: IoBufMut>(&mut self, mut buf: B) -> BufResult<usize, B> {
+        if let Some(Step::Pause(pause)) = self.steps.front() {
+            let pause = *pause;
+            self.steps.pop_front();
+            monocoque_core::rt::sleep(pause).await;
+        }
+        let n = self.next_bytes(buf.as_uninit());
+        // SAFETY: `next_bytes` initialized the first `n` bytes.
+        unsafe { buf.set_len(n) };
+        BufResult(Ok(n), buf)
+    }
+}
+
+impl AsyncWrite for ThrottledPeer {
+    async fn write<B: IoBuf>(&mut self, buf: B) -> BufResult<usize, B> {
+        // Greeting, READY and PINGs are accepted and never answered.
+        let n = buf.buf_len();
+        BufResult(Ok(n), buf)
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn options() -> SocketOptions {
+    SocketOptions::default()
+        .with_stream_threshold(Some(THRESHOLD))
+        .with_heartbeat_ivl(Duration::from_millis(20))
+        .with_heartbeat_timeout(Duration::from_millis(20))
+}
+
+#[test]
+fn streams_256mb_frame_with_bounded_memory() {
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async {
+            let mut dealer = DealerSocket::with_options(ThrottledPeer::new(), options())
+                .await
+                .unwrap();
+
+            // `recv` refuses the message but leaves the connection usable.
+            for _ in 0..2 {
+                let err = dealer.recv().await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+                let inner = err
+                    .get_ref()
+                    .and_then(|e| e.downcast_ref::<ZmtpError>())
+                    .expect("error should carry a ZmtpError");
+                assert!(
+                    matches!(inner, ZmtpError::StreamingRequired { len } if *len == FRAME_LEN),
+                    "{inner:?}"
+                );
+                assert!(dealer.is_connected());
+            }
+
+            // The frame `recv` already decoded comes first.
+            let mut head = dealer.recv_frame_streaming().await.unwrap().unwrap();
+            assert!(head.more());
+            assert_eq!(head.next_chunk().await.unwrap(), Some(Bytes::from("head")));
+            assert_eq!(head.next_chunk().await.unwrap(), None);
+
+            let pattern = pattern();
+            let mut frame = dealer.recv_frame_streaming().await.unwrap().unwrap();
+            assert_eq!(frame.total_len(), FRAME_LEN);
+            assert!(!frame.more());
+            let mut received = 0u64;
+            let mut peak_queued = 0;
+            let mut peak_staging = 0;
+            while let Some(chunk) = frame.next_chunk().await.unwrap() {
+                let start = (received % 256) as usize;
+                assert!(chunk.len() <= THROTTLE);
+                assert!(chunk[..] == pattern[start..start + chunk.len()]);
+                received += chunk.len() as u64;
+
+                let stats = frame.buffer_stats();
+                peak_queued = peak_queued.max(stats.recv_queued_bytes);
+                peak_staging = peak_staging.max(stats.staging_capacity);
+            }
+            assert_eq!(received, FRAME_LEN);
+            assert_eq!(frame.remaining(), 0);
+            assert!(peak_queued <= THROTTLE, "queued {peak_queued} bytes");
+            assert!(peak_staging <= THRESHOLD, "staging grew to {peak_staging}");
+
+            // The heartbeat kept the connection through the whole transfer,
+            // and the framing resumes cleanly after the streamed frame.
+            assert_eq!(
+                dealer.recv().await.unwrap(),
+                Some(vec![Bytes::from("tail")])
+            );
+        });
+}
+
+#[test]
+fn dropped_reader_skips_rest_of_frame() {
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async {
+            let mut dealer = DealerSocket::with_options(ThrottledPeer::new(), options())
+                .await
+                .unwrap();
+
+            let head = dealer.recv_frame_streaming().await.unwrap().unwrap();
+            assert_eq!(head.total_len(), 4);
+            drop(head);
+
+            let mut frame = dealer.recv_frame_streaming().await.unwrap().unwrap();
+            assert!(frame.next_chunk().await.unwrap().is_some());
+            drop(frame);
+
+            assert_eq!(
+                dealer.recv().await.unwrap(),
+                Some(vec![Bytes::from("tail")])
+            );
+        });
+}
+
+#[test]
+fn router_streams_64mb_frame_through_8kb_reads() {
+    const FRAME_LEN: u64 = 64 << 20;
+    const READ_BUFFER: usize = 8 * 1024;
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async {
+            let options = options().with_buffer_sizes(READ_BUFFER, READ_BUFFER);
+            let mut router =
+                RouterSocket::with_options(ThrottledPeer::with(b"DEALER", FRAME_LEN), options)
+                    .await
+                    .unwrap();
+
+            let mut head = router.recv_frame_streaming().await.unwrap().unwrap();
+            assert!(head.more());
+            assert_eq!(head.next_chunk().await.unwrap(), Some(Bytes::from("head")));
+            drop(head);
+
+            let pattern = pattern();
+            let mut frame = router.recv_frame_streaming().await.unwrap().unwrap();
+            assert_eq!(frame.total_len(), FRAME_LEN);
+            let mut received = 0u64;
+            let mut peak_buffered = 0;
+            while let Some(chunk) = frame.next_chunk().await.unwrap() {
+                let start = (received % 256) as usize;
+                assert!(chunk.len() <= READ_BUFFER);
+                assert!(chunk[..] == pattern[start..start + chunk.len()]);
+                received += chunk.len() as u64;
+
+                let stats = frame.buffer_stats();
+                peak_buffered = peak_buffered.max(stats.recv_queued_bytes + stats.staging_capacity);
+            }
+            assert_eq!(received, FRAME_LEN);
+            assert!(
+                peak_buffered <= THRESHOLD,
+                "buffered {peak_buffered} bytes of a {FRAME_LEN}-byte frame"
+            );
+
+            let identity = router.peer_identity().clone();
+            assert_eq!(
+                router.recv().await.unwrap(),
+                Some(vec![identity, Bytes::from("tail")])
+            );
+        });
+}
@@ -0,0 +1,114 @@
+//! Options changed through `update_options` on a connected socket.
+//!
+//! Live options must be picked up by the very next send or receive, while a
+//! construct-only option such as the routing identity is refused outright.
+
+use bytes::Bytes;
+use monocoque_core::options::{OptionTiming, SocketOptions};
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Two DEALERs connected to each other over loopback TCP.
+async fn dealer_pair(options: SocketOptions) -> (DealerSocket<TcpStream>, DealerSocket<TcpStream>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (server, client) = futures::join!(
+        async {
+            let (stream, _) = listener.accept().await.unwrap();
+            DealerSocket::new(stream).await.unwrap()
+        },
+        async {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            DealerSocket::with_options(stream, options).await.unwrap()
+        }
+    );
+    (server, client)
+}
+
+#[test]
+fn recv_timeout_applies_to_the_next_recv() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (mut server, mut client) = dealer_pair(SocketOptions::default()).await;
+
+        let applied = client
+            .update_options(|o| o.recv_timeout = Some(Duration::from_millis(50)))
+            .unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].field(), "recv_timeout");
+        assert_eq!(applied[0].timing(), OptionTiming::Live);
+
+        let started = Instant::now();
+        let err = rt::timeout(Duration::from_secs(5), client.recv())
+            .await
+            .expect("recv_timeout was not applied")
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        // Clearing it again waits for the peer as before.
+        client.update_options(|o| o.recv_timeout = None).unwrap();
+        let (msg, sent) = futures::join!(client.recv(), async {
+            rt::sleep(Duration::from_millis(100)).await;
+            server.send(vec![Bytes::from_static(b"late")]).await
+        });
+        sent.unwrap();
+        assert_eq!(msg.unwrap(), Some(vec![Bytes::from_static(b"late")]));
+    });
+}
+
+#[test]
+fn send_hwm_applies_to_the_next_send() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (mut server, mut client) = dealer_pair(SocketOptions::default()).await;
+
+        client.update_options(|o| o.send_hwm = 2).unwrap();
+        client
+            .send_buffered(vec![Bytes::from_static(b"1")])
+            .unwrap();
+        client
+            .send_buffered(vec![Bytes::from_static(b"2")])
+            .unwrap();
+        let err = client
+            .send_buffered(vec![Bytes::from_static(b"3")])
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+
+        // Raising the mark lets the same buffer take more.
+        client.update_options(|o| o.send_hwm = 3).unwrap();
+        client
+            .send_buffered(vec![Bytes::from_static(b"3")])
+            .unwrap();
+        client.flush().await.unwrap();
+
+        for expected in [b"1", b"2", b"3"] {
+            let msg = server.recv().await.unwrap().unwrap();
+            assert_eq!(msg, vec![Bytes::from_static(expected)]);
+        }
+    });
+}
+
+#[test]
+fn routing_id_change_is_rejected() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"worker-1"));
+        let (_server, mut client) = dealer_pair(options).await;
+
+        let err = client
+            .update_options(|o| {
+                o.recv_timeout = Some(Duration::from_secs(1));
+                o.routing_id = Some(Bytes::from_static(b"worker-2"));
+            })
+            .unwrap_err();
+        assert_eq!(err.field(), "routing_id");
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::InvalidInput);
+
+        // Nothing from the rejected closure was applied.
+        assert_eq!(
+            client.options().routing_id.as_deref(),
+            Some(&b"worker-1"[..])
+        );
+        assert_eq!(client.options().recv_timeout, None);
+    });
+}
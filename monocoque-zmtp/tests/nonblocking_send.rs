@@ -0,0 +1,50 @@
+//! Non-blocking sends against a peer that has stopped reading.
+//!
+//! With a zero `send_timeout` a send must never wait on the peer: once the
+//! kernel buffers are full it has to come back with `WouldBlock` straight
+//! away instead of stalling until the peer reads again.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::router::RouterSocket;
+use std::io;
+use std::time::Duration;
+
+#[test]
+fn send_to_a_stalled_reader_returns_would_block() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let options = SocketOptions::default().with_send_timeout(Duration::ZERO);
+        let (_router, dealer) = futures::join!(
+            async {
+                let (stream, _) = listener.accept().await.unwrap();
+                // Kept alive but never read from.
+                RouterSocket::new(stream).await.unwrap()
+            },
+            async {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                DealerSocket::with_options(stream, options).await.unwrap()
+            }
+        );
+        let mut dealer = dealer;
+
+        let payload = Bytes::from(vec![0x5A; 16 * 1024]);
+        let outcome = rt::timeout(Duration::from_secs(10), async {
+            for sent in 0..60_000 {
+                if let Err(e) = dealer.send(vec![payload.clone()]).await {
+                    return (sent, e);
+                }
+            }
+            panic!("60,000 sends to a stalled reader all succeeded");
+        })
+        .await
+        .expect("a non-blocking send waited for the stalled reader");
+
+        let (sent, err) = outcome;
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock, "after {sent} sends");
+        assert!(sent > 0, "the first send should fit in the kernel buffers");
+    });
+}
@@ -0,0 +1,94 @@
+//! ZMTP ERROR commands in both directions.
+//!
+//! A raw ZMTP peer sends the ERROR frames libzmq sends when it rejects a
+//! connection, and the socket must report the reason as `ConnectionRefused`
+//! rather than hang or see bare EOF. In the other direction a peer whose
+//! READY we cannot accept is told why before the connection closes.
+
+use bytes::Bytes;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use std::io;
+
+mod common;
+use common::{DEALER_READY, null_greeting, read_exact, write_all};
+
+/// What libzmq 4.3 sends instead of READY when its ZAP handler denies a NULL
+/// peer: the reason is the ZAP status code.
+const LIBZMQ_ZAP_DENIED: &[u8] = b"\x04\x0a\x05ERROR\x03400";
+
+/// Accept one client, swap greetings and read its READY, answering with
+/// `reply` in place of our own READY.
+async fn accept_and_reply(listener: &TcpListener, reply: &[u8]) -> TcpStream {
+    let (mut stream, _) = listener.accept().await.unwrap();
+    write_all(&mut stream, &null_greeting()).await;
+    read_exact(&mut stream, 64).await;
+    let header = read_exact(&mut stream, 2).await;
+    assert_eq!(header[0], 0x04, "expected READY command frame");
+    read_exact(&mut stream, header[1] as usize).await;
+    write_all(&mut stream, reply).await;
+    stream
+}
+
+#[test]
+fn error_in_place_of_ready_fails_the_constructor() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (_peer, result) =
+            futures::join!(accept_and_reply(&listener, LIBZMQ_ZAP_DENIED), async {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                DealerSocket::new(stream).await
+            });
+
+        let err = result.err().expect("handshake should fail");
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        assert!(err.to_string().contains("400"), "{err}");
+    });
+}
+
+#[test]
+fn error_after_handshake_fails_recv_with_reason() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut peer, mut dealer) =
+            futures::join!(accept_and_reply(&listener, DEALER_READY), async {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                DealerSocket::new(stream).await.unwrap()
+            });
+
+        write_all(&mut peer, b"\x00\x05hello").await;
+        write_all(&mut peer, b"\x04\x14\x05ERROR\x0dAccess denied").await;
+
+        let msg = dealer.recv().await.unwrap();
+        assert_eq!(msg, Some(vec![Bytes::from_static(b"hello")]));
+        let err = dealer.recv().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        assert!(err.to_string().contains("Access denied"), "{err}");
+        assert!(!dealer.is_connected());
+    });
+}
+
+#[test]
+fn malformed_ready_is_answered_with_error() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut peer, result) = futures::join!(
+            // READY with no Socket-Type property.
+            accept_and_reply(&listener, b"\x04\x06\x05READY"),
+            async {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                DealerSocket::new(stream).await
+            }
+        );
+        assert!(result.is_err());
+
+        let header = read_exact(&mut peer, 2).await;
+        assert_eq!(header[0], 0x04, "expected an ERROR command frame");
+        let body = read_exact(&mut peer, header[1] as usize).await;
+        assert_eq!(&body[..7], b"\x05ERROR\x17");
+        assert_eq!(&body[7..], b"malformed READY command");
+    });
+}
@@ -0,0 +1,79 @@
+//! READY properties reach the socket through `peer_metadata()`.
+//!
+//! A raw ZMTP peer advertises `Identity`, `Socket-Type` and an
+//! application-defined `App-Version`; a DEALER that completed the handshake
+//! with it must report all three, values untouched. Properties a DEALER
+//! adds with `with_handshake_property` reach a ROUTER the same way.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::router::RouterSocket;
+
+mod common;
+use common::{null_greeting, read_exact, write_all};
+
+/// READY advertising `Socket-Type: ROUTER`, `Identity: backend-7` and
+/// `App-Version: 2.4.1`, as a short command frame.
+const READY_WITH_APP_VERSION: &[u8] = b"\x04\x47\x05READY\
+    \x0bSocket-Type\x00\x00\x00\x06ROUTER\
+    \x08Identity\x00\x00\x00\x09backend-7\
+    \x0bApp-Version\x00\x00\x00\x052.4.1";
+
+#[test]
+fn ready_properties_are_exposed_by_the_socket() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (_peer, dealer) = futures::join!(
+            async {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                write_all(&mut stream, &null_greeting()).await;
+                read_exact(&mut stream, 64).await;
+                let header = read_exact(&mut stream, 2).await;
+                read_exact(&mut stream, header[1] as usize).await;
+                write_all(&mut stream, READY_WITH_APP_VERSION).await;
+                stream
+            },
+            async {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                DealerSocket::new(stream).await.unwrap()
+            }
+        );
+
+        let metadata = dealer.peer_metadata();
+        assert_eq!(metadata.len(), 3);
+        assert_eq!(metadata["Socket-Type"], &b"ROUTER"[..]);
+        assert_eq!(metadata["Identity"], &b"backend-7"[..]);
+        assert_eq!(metadata["App-Version"], &b"2.4.1"[..]);
+    });
+}
+
+#[test]
+fn handshake_properties_reach_the_router() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let options = SocketOptions::new()
+            .with_handshake_property("X-Service", Bytes::from_static(b"billing"))
+            .unwrap()
+            .with_handshake_property("X-Region", Bytes::from_static(b"eu-west-1"))
+            .unwrap();
+        let (router, _dealer) = futures::join!(
+            async {
+                let (stream, _) = listener.accept().await.unwrap();
+                RouterSocket::new(stream).await.unwrap()
+            },
+            async {
+                let stream = TcpStream::connect(addr).await.unwrap();
+                DealerSocket::with_options(stream, options).await.unwrap()
+            }
+        );
+
+        let metadata = router.peer_metadata();
+        assert_eq!(metadata["Socket-Type"], &b"DEALER"[..]);
+        assert_eq!(metadata["X-Service"], &b"billing"[..]);
+        assert_eq!(metadata["X-Region"], &b"eu-west-1"[..]);
+    });
+}
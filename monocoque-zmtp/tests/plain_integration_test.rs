@@ -1,14 +1,54 @@
 //! Integration tests for PLAIN authentication with REQ/REP flows
 //!
-//! Note: These tests verify that PLAIN security options are correctly applied.
-//! Full end-to-end authentication requires ZAP handler integration which is
-//! implemented but requires inproc transport setup.
+//! The options and handler tests check configuration on its own; the
+//! handshake test runs PLAIN end to end between two DEALER sockets, with the
+//! server validating credentials through a ZAP handler on inproc.
 
+use bytes::Bytes;
 use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::codec::ZmtpError;
+use monocoque_zmtp::dealer::DealerSocket;
 use monocoque_zmtp::security::PlainAuthHandler;
 use monocoque_zmtp::security::plain::StaticPlainHandler;
+use monocoque_zmtp::security::zap_handler::{DefaultZapHandler, spawn_zap_server};
+use std::io;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// Handshake a DEALER built with `server` options against one built with
+/// `client` options over loopback, returning both constructor results.
+async fn plain_pair(
+    server: SocketOptions,
+    client: SocketOptions,
+) -> (
+    io::Result<DealerSocket<TcpStream>>,
+    io::Result<DealerSocket<TcpStream>>,
+) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = rt::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        DealerSocket::with_options(stream, server).await
+    });
+    let stream = TcpStream::connect(addr).await.unwrap();
+    let client = DealerSocket::with_options(stream, client).await;
+    (rt::join(server).await, client)
+}
+
+fn assert_auth_failed(result: io::Result<DealerSocket<TcpStream>>) {
+    let err = result.err().expect("handshake should be rejected");
+    assert_eq!(err.kind(), io::ErrorKind::PermissionDenied, "{err}");
+    let inner = err
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<ZmtpError>())
+        .expect("auth failure should carry a ZmtpError");
+    assert!(
+        matches!(inner, ZmtpError::AuthenticationFailed),
+        "{inner:?}"
+    );
+}
+
 #[test]
 fn test_plain_auth_options_configuration() {
     // Verify that PLAIN authentication options can be configured
@@ -82,3 +122,52 @@ fn test_plain_multiple_users() {
             assert!(result.is_err(), "Wrong password should fail");
         });
 }
+
+/// One ZAP handler serves the whole binary (its inproc endpoint is global),
+/// so accepted, rejected and misconfigured peers share this test.
+#[test]
+fn plain_handshake_authenticates_through_zap() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let mut users = StaticPlainHandler::new();
+        users.add_user("alice", "secret");
+        spawn_zap_server(Arc::new(DefaultZapHandler::new(Arc::new(users), false))).unwrap();
+
+        let server = SocketOptions::new()
+            .with_plain_server(true)
+            .with_handshake_timeout(Duration::from_secs(5));
+
+        // Valid credentials: READY metadata flows both ways.
+        let (accepted, connected) = plain_pair(
+            server.clone(),
+            SocketOptions::new().with_plain_credentials("alice", "secret"),
+        )
+        .await;
+        let mut accepted = accepted.unwrap();
+        let mut connected = connected.unwrap();
+        connected
+            .send(vec![Bytes::from_static(b"hello")])
+            .await
+            .unwrap();
+        let msg = rt::timeout(Duration::from_secs(5), accepted.recv())
+            .await
+            .expect("recv timed out")
+            .unwrap();
+        assert_eq!(msg, Some(vec![Bytes::from_static(b"hello")]));
+
+        // Wrong password: the server sends ERROR and both sides report it.
+        let (accepted, connected) = plain_pair(
+            server.clone(),
+            SocketOptions::new().with_plain_credentials("alice", "wrong"),
+        )
+        .await;
+        assert_auth_failed(accepted);
+        assert_auth_failed(connected);
+
+        // Credentials on one side only: the greetings disagree.
+        let results: [_; 2] = plain_pair(server, SocketOptions::new()).await.into();
+        for result in results {
+            let err = result.err().expect("mechanism mismatch should fail");
+            assert_eq!(err.kind(), io::ErrorKind::Other, "{err}");
+        }
+    });
+}
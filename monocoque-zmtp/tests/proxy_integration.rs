@@ -7,7 +7,7 @@
 use bytes::Bytes;
 use monocoque_core::rt::TcpListener;
 use monocoque_zmtp::pair::PairSocket;
-use monocoque_zmtp::proxy::{ProxyCommand, proxy_steerable};
+use monocoque_zmtp::proxy::{ProxyCommand, ProxyOptions, proxy_steerable};
 
 /// Bind a TCP listener and return a connected server+client PAIR socket pair.
 #[allow(clippy::future_not_send)]
@@ -62,8 +62,7 @@ async fn test_proxy_pair_forward_impl() {
         let mut fe = frontend;
         let mut be = backend;
         let mut ctrl = control;
-        let capture: Option<&mut PairSocket> = None;
-        proxy_steerable(&mut fe, &mut be, capture, &mut ctrl).await
+        proxy_steerable(&mut fe, &mut be, None, &mut ctrl, ProxyOptions::default()).await
     });
 
     client_a.send(vec![Bytes::from("hello")]).await.unwrap();
@@ -94,8 +93,7 @@ async fn test_proxy_pair_bidirectional_impl() {
         let mut fe = frontend;
         let mut be = backend;
         let mut ctrl = control;
-        let capture: Option<&mut PairSocket> = None;
-        proxy_steerable(&mut fe, &mut be, capture, &mut ctrl).await
+        proxy_steerable(&mut fe, &mut be, None, &mut ctrl, ProxyOptions::default()).await
     });
 
     // Frontend-side → backend-side
@@ -134,7 +132,14 @@ async fn test_proxy_capture_socket_impl() {
         let mut be = backend;
         let mut ctrl = control;
         let mut cap = capture_server;
-        proxy_steerable(&mut fe, &mut be, Some(&mut cap), &mut ctrl).await
+        proxy_steerable(
+            &mut fe,
+            &mut be,
+            Some(&mut cap),
+            &mut ctrl,
+            ProxyOptions::default(),
+        )
+        .await
     });
 
     // Proxy sends capture copy before forwarding to backend, so both arrive.
@@ -170,8 +175,7 @@ async fn test_proxy_steerable_terminate_impl() {
         let mut fe = frontend;
         let mut be = backend;
         let mut ctrl = control;
-        let capture: Option<&mut PairSocket> = None;
-        proxy_steerable(&mut fe, &mut be, capture, &mut ctrl).await
+        proxy_steerable(&mut fe, &mut be, None, &mut ctrl, ProxyOptions::default()).await
     });
 
     // Send one message to confirm the proxy is running.
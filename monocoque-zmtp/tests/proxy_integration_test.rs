@@ -8,7 +8,7 @@
 use bytes::Bytes;
 use monocoque_core::rt::TcpListener;
 use monocoque_zmtp::pair::PairSocket;
-use monocoque_zmtp::proxy::{ProxyCommand, proxy_steerable};
+use monocoque_zmtp::proxy::{ProxyCommand, ProxyOptions, proxy_steerable};
 use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
@@ -63,8 +63,8 @@ fn test_proxy_steerable_terminate() {
                     let mut fe = frontend;
                     let mut be = backend;
                     let mut ctrl = control;
-                    let capture: Option<&mut PairSocket> = None;
-                    proxy_steerable(&mut fe, &mut be, capture, &mut ctrl).await
+                    proxy_steerable(&mut fe, &mut be, None, &mut ctrl, ProxyOptions::default())
+                        .await
                 });
 
                 // Send one message through the proxy to confirm it is running.
@@ -115,8 +115,8 @@ fn test_proxy_steerable_statistics() {
                     let mut fe = frontend;
                     let mut be = backend;
                     let mut ctrl = control;
-                    let capture: Option<&mut PairSocket> = None;
-                    proxy_steerable(&mut fe, &mut be, capture, &mut ctrl).await
+                    proxy_steerable(&mut fe, &mut be, None, &mut ctrl, ProxyOptions::default())
+                        .await
                 });
 
                 // Forward a couple of messages so the counter is non-zero.
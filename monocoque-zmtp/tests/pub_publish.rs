@@ -0,0 +1,97 @@
+//! `PubSocket::publish`, `publish_multipart` and `publish_to_all` against two
+//! subscribers with different topics.
+//!
+//! `publish_to_all` skips the PUB side's subscription filter only. A SUB peer
+//! still filters what it receives, as libzmq's does, so the message that
+//! bypasses the filter is observed on an XSUB.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
+use monocoque_core::rt::TcpListener;
+use monocoque_zmtp::publisher::PubSocket;
+use monocoque_zmtp::subscriber::SubSocket;
+use monocoque_zmtp::xsub::XSubSocket;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn frames(parts: &[&'static str]) -> Vec<Bytes> {
+    parts
+        .iter()
+        .map(|p| Bytes::from_static(p.as_bytes()))
+        .collect()
+}
+
+#[test]
+fn publish_filters_by_topic_and_publish_to_all_skips_the_filter() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let server = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let index = SharedSubscriptionIndex::new();
+                let mut pub_sock = PubSocket::new().with_shared_index(index.clone());
+                pub_sock.accept_subscriber(&listener).await.unwrap();
+                pub_sock.accept_subscriber(&listener).await.unwrap();
+                let deadline = Instant::now() + Duration::from_secs(5);
+                while !(index.matches(b"news") && index.matches(b"weather")) {
+                    assert!(Instant::now() < deadline, "subscriptions not seen");
+                    monocoque_core::rt::sleep(Duration::from_millis(10)).await;
+                }
+
+                pub_sock.publish("news", "headline").await.unwrap();
+                pub_sock
+                    .publish_to_all(frames(&["admin", "shutdown"]))
+                    .await
+                    .unwrap();
+                pub_sock
+                    .publish_multipart("weather", frames(&["rain", "cold"]))
+                    .await
+                    .unwrap();
+                done_rx.recv_timeout(Duration::from_secs(15)).unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let mut news = SubSocket::connect_with_options(
+                addr,
+                SocketOptions::default().with_subscribe(Bytes::from_static(b"news")),
+            )
+            .await
+            .unwrap();
+            let mut weather = XSubSocket::connect(&addr.to_string()).await.unwrap();
+            weather
+                .subscribe(Bytes::from_static(b"weather"))
+                .await
+                .unwrap();
+
+            let next = |msg: Option<std::io::Result<Option<Vec<Bytes>>>>| {
+                msg.expect("recv timed out").unwrap().expect("PUB closed")
+            };
+            let recv_news = monocoque_core::rt::timeout(Duration::from_secs(5), news.recv());
+            assert_eq!(next(recv_news.await.ok()), frames(&["news", "headline"]));
+            for parts in [&["admin", "shutdown"][..], &["weather", "rain", "cold"]] {
+                let recv_weather =
+                    monocoque_core::rt::timeout(Duration::from_secs(5), weather.recv());
+                assert_eq!(next(recv_weather.await.ok()), frames(parts));
+            }
+            assert!(
+                monocoque_core::rt::timeout(Duration::from_millis(100), news.recv())
+                    .await
+                    .is_err(),
+                "news subscriber got a message it did not subscribe to"
+            );
+            done_tx.send(()).unwrap();
+        });
+
+    server.join().expect("server thread panicked");
+}
@@ -13,6 +13,7 @@
 use bytes::Bytes;
 use monocoque_core::options::SocketOptions;
 use monocoque_core::rt::TcpListener;
+use monocoque_core::subscription::WildcardPattern;
 use monocoque_zmtp::publisher::PubSocket;
 use monocoque_zmtp::subscriber::SubSocket;
 use std::sync::mpsc;
@@ -106,6 +107,83 @@ fn test_sub_options_subscriptions_are_applied() {
     );
 }
 
+/// Subscriptions set in `SocketOptions` are sent on connect and replayed on
+/// every reconnect.
+///
+/// The SUB connects with `.with_subscribe("weather.")` and never calls
+/// `subscribe()`. Each PUB waits on its shared index until the subscription
+/// arrives, so the first publication after the connect is the one received.
+#[test]
+fn test_sub_options_subscriptions_survive_reconnect() {
+    use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
+
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (first_tx, first_rx) = mpsc::channel::<()>();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let server = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                for (round, topic) in ["weather.today", "weather.tomorrow"].iter().enumerate() {
+                    let index = SharedSubscriptionIndex::new();
+                    let mut pub_sock = PubSocket::new().with_shared_index(index.clone());
+                    pub_sock.accept_subscriber(&listener).await.unwrap();
+                    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+                    while !index.matches(topic.as_bytes()) {
+                        assert!(
+                            std::time::Instant::now() < deadline,
+                            "subscription from options not seen in round {round}"
+                        );
+                        monocoque_core::rt::sleep(Duration::from_millis(10)).await;
+                    }
+                    pub_sock
+                        .send(vec![Bytes::from(*topic), Bytes::from("sunny")])
+                        .await
+                        .unwrap();
+                    if round == 0 {
+                        first_rx.recv().unwrap();
+                        pub_sock.close().await.unwrap();
+                    }
+                }
+                done_rx.recv_timeout(Duration::from_secs(15)).unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let opts = SocketOptions::default()
+                .with_subscribe(Bytes::from("weather."))
+                .with_reconnect_ivl(Duration::from_millis(10))
+                .with_reconnect_ivl_max(Duration::from_millis(100))
+                .with_max_reconnect_attempts(Some(20));
+            let mut sub = SubSocket::connect_with_reconnect_options(addr, opts)
+                .await
+                .unwrap();
+
+            for expected in ["weather.today", "weather.tomorrow"] {
+                let msg = monocoque_core::rt::timeout(Duration::from_secs(10), sub.recv())
+                    .await
+                    .expect("recv timed out")
+                    .unwrap()
+                    .expect("recv returned EOF on an auto-reconnecting socket");
+                assert_eq!(msg[0], Bytes::from(expected));
+                if expected == "weather.today" {
+                    first_tx.send(()).unwrap();
+                }
+            }
+            done_tx.send(()).unwrap();
+        });
+
+    server.join().expect("server thread panicked");
+}
+
 /// Multiple subscriptions set in `SocketOptions` are all applied.
 ///
 /// Two topics are registered via options; only messages matching either prefix
@@ -192,6 +270,179 @@ fn test_sub_options_multiple_subscriptions() {
     }
 }
 
+/// Wildcard patterns filter on the SUB side.
+///
+/// The PUB only sees the patterns' literal prefixes (`weather.`, `sensor.`),
+/// so `sensor.12.temp` reaches the SUB and must be dropped there.
+#[test]
+fn test_sub_wildcard_patterns_filter_on_receipt() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (sub_ready_tx, sub_ready_rx) = mpsc::channel::<()>();
+    let (client_done_tx, client_done_rx) = mpsc::channel::<()>();
+    let (msgs_tx, msgs_rx) = mpsc::channel::<Vec<Bytes>>();
+
+    let pub_handle = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let mut pub_sock = PubSocket::new();
+                pub_sock.accept_subscriber(&listener).await.unwrap();
+
+                sub_ready_rx.recv().unwrap();
+                std::thread::sleep(Duration::from_millis(100));
+
+                for topic in [
+                    "weather.paris",
+                    "sensor.12.temp",
+                    "sports.scores",
+                    "sensor.1.temp",
+                    "weather",
+                    "sensor.2.humidity",
+                    "weather.done",
+                ] {
+                    pub_sock.send(vec![Bytes::from(topic)]).await.unwrap();
+                }
+
+                client_done_rx.recv().unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let client = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let stream = monocoque_core::rt::TcpStream::connect(addr).await.unwrap();
+                let mut sub = SubSocket::new(stream).await.unwrap();
+                sub.subscribe_pattern(&WildcardPattern::new("weather.*"))
+                    .await
+                    .unwrap();
+                sub.subscribe_pattern(&WildcardPattern::new("sensor.?.*"))
+                    .await
+                    .unwrap();
+                sub_ready_tx.send(()).unwrap();
+
+                let mut topics = Vec::new();
+                loop {
+                    let msg = monocoque_core::rt::timeout(Duration::from_secs(3), sub.recv())
+                        .await
+                        .expect("recv timed out")
+                        .unwrap()
+                        .expect("connection closed");
+                    topics.push(msg[0].clone());
+                    if msg[0] == "weather.done" {
+                        break;
+                    }
+                }
+                msgs_tx.send(topics).unwrap();
+                client_done_tx.send(()).unwrap();
+            });
+    });
+
+    pub_handle.join().expect("pub thread panicked");
+    client.join().expect("client thread panicked");
+
+    let topics = msgs_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(
+        topics,
+        [
+            "weather.paris",
+            "sensor.1.temp",
+            "sensor.2.humidity",
+            "weather.done"
+        ]
+    );
+}
+
+/// Exact subscriptions filter on the SUB side and match on frame 0 only.
+///
+/// The PUB sees `weather` as a prefix, so `weather.paris` reaches the SUB and
+/// must be dropped there; `stop` is an ordinary prefix subscription. The
+/// second frame of `["weather", "72F"]` plays no part in the match.
+#[test]
+fn test_sub_exact_subscription_filters_on_receipt() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (sub_ready_tx, sub_ready_rx) = mpsc::channel::<()>();
+    let (client_done_tx, client_done_rx) = mpsc::channel::<()>();
+    let (msgs_tx, msgs_rx) = mpsc::channel::<Vec<Vec<Bytes>>>();
+
+    let pub_handle = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let mut pub_sock = PubSocket::new();
+                pub_sock.accept_subscriber(&listener).await.unwrap();
+
+                sub_ready_rx.recv().unwrap();
+                std::thread::sleep(Duration::from_millis(100));
+
+                for msg in [
+                    vec!["weather.paris", "18C"],
+                    vec!["weather", "72F"],
+                    vec!["sports", "weather"],
+                    vec!["weathe"],
+                    vec!["stop"],
+                ] {
+                    let msg = msg.into_iter().map(Bytes::from).collect();
+                    pub_sock.send(msg).await.unwrap();
+                }
+
+                client_done_rx.recv().unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let client = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let stream = monocoque_core::rt::TcpStream::connect(addr).await.unwrap();
+                let mut sub = SubSocket::new(stream).await.unwrap();
+                sub.subscribe_exact(Bytes::from_static(b"weather"))
+                    .await
+                    .unwrap();
+                sub.subscribe(Bytes::from_static(b"stop")).await.unwrap();
+                sub_ready_tx.send(()).unwrap();
+
+                let mut received = Vec::new();
+                loop {
+                    let msg = monocoque_core::rt::timeout(Duration::from_secs(3), sub.recv())
+                        .await
+                        .expect("recv timed out")
+                        .unwrap()
+                        .expect("connection closed");
+                    let done = msg[0] == "stop";
+                    received.push(msg);
+                    if done {
+                        break;
+                    }
+                }
+                msgs_tx.send(received).unwrap();
+                client_done_tx.send(()).unwrap();
+            });
+    });
+
+    pub_handle.join().expect("pub thread panicked");
+    client.join().expect("client thread panicked");
+
+    let received = msgs_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(
+        received,
+        [
+            vec![Bytes::from("weather"), Bytes::from("72F")],
+            vec![Bytes::from("stop")],
+        ]
+    );
+}
+
 /// Broadcast coalescing (Fix 4) delivers a rapid burst intact and in order.
 ///
 /// The PUB pushes a tight burst of messages so they queue behind the worker,
@@ -275,3 +526,81 @@ fn test_pub_broadcast_coalescing_burst() {
         assert_eq!(msg[1], Bytes::from(i.to_string()), "out-of-order at {i}");
     }
 }
+
+/// With `conflate` set, a SUB that falls behind sees only the newest message.
+///
+/// The PUB sends three multipart messages before the SUB reads anything; the
+/// SUB's first `recv()` must yield the third, whole.
+#[test]
+fn test_sub_conflate_yields_only_the_newest_message() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (sub_ready_tx, sub_ready_rx) = mpsc::channel::<()>();
+    // PUB signals here once all three messages are written.
+    let (sent_tx, sent_rx) = mpsc::channel::<()>();
+    let (client_done_tx, client_done_rx) = mpsc::channel::<()>();
+    let (msg_tx, msg_rx) = mpsc::channel::<Option<Vec<Bytes>>>();
+
+    let pub_handle = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let mut pub_sock = PubSocket::new();
+                pub_sock.accept_subscriber(&listener).await.unwrap();
+
+                sub_ready_rx.recv().unwrap();
+                std::thread::sleep(Duration::from_millis(100));
+
+                for seq in ["1", "2", "3"] {
+                    pub_sock
+                        .send(vec![Bytes::from_static(b"tick"), Bytes::from(seq)])
+                        .await
+                        .unwrap();
+                }
+                sent_tx.send(()).unwrap();
+
+                client_done_rx.recv().unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let client = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let stream = monocoque_core::rt::TcpStream::connect(addr).await.unwrap();
+                let opts = SocketOptions::default()
+                    .with_subscribe(Bytes::from_static(b"tick"))
+                    .with_conflate(true);
+                let mut sub = SubSocket::with_options(stream, opts).await.unwrap();
+
+                sub_ready_tx.send(()).unwrap();
+                sent_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+                // Let all three messages land before the first read.
+                std::thread::sleep(Duration::from_millis(100));
+
+                let msg = monocoque_core::rt::timeout(Duration::from_secs(5), sub.recv())
+                    .await
+                    .expect("recv timed out")
+                    .unwrap();
+
+                msg_tx.send(msg).unwrap();
+                client_done_tx.send(()).unwrap();
+            });
+    });
+
+    pub_handle.join().expect("pub thread panicked");
+    client.join().expect("client thread panicked");
+
+    let msg = msg_rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("did not receive a message")
+        .expect("connection closed unexpectedly");
+    assert_eq!(
+        msg,
+        vec![Bytes::from_static(b"tick"), Bytes::from_static(b"3")]
+    );
+}
@@ -357,3 +357,374 @@ fn test_no_reconnect_without_stored_endpoint() {
         "unexpected error kind: {kind:?}"
     );
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: auto_recover reconnects a poisoned from_tcp socket on its next send
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// The server never reads from the first connection, so a large send blocks
+// once the kernel buffers fill; dropping it on a timeout poisons the DEALER.
+// The DEALER came from `from_tcp`, so only the peer address it recorded lets
+// the next plain `send()` redial; the second connection receives the message.
+
+#[test]
+fn test_auto_recover_reconnects_poisoned_from_tcp_socket() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (msg_tx, msg_rx) = mpsc::channel::<Vec<Bytes>>();
+
+    thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = monocoque_core::rt::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                // First connection: handshake, then never read.
+                let (stream1, _) = listener.accept().await.unwrap();
+                let _stalled = RouterSocket::from_tcp(stream1).await.unwrap();
+
+                let (stream2, _) = listener.accept().await.unwrap();
+                let mut router2 = RouterSocket::from_tcp(stream2).await.unwrap();
+                msg_tx.send(router2.recv().await.unwrap().unwrap()).unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let stream = monocoque_core::rt::TcpStream::connect(addr).await.unwrap();
+            let mut dealer =
+                DealerSocket::from_tcp_with_options(stream, fast_opts().with_auto_recover(true))
+                    .await
+                    .unwrap();
+
+            let big = Bytes::from(vec![0u8; 64 << 20]);
+            let cancelled = monocoque_core::rt::timeout(
+                Duration::from_millis(200),
+                dealer.send(vec![Bytes::new(), big]),
+            )
+            .await;
+            assert!(cancelled.is_err(), "send should block on a stalled peer");
+            assert!(dealer.is_poisoned());
+
+            dealer
+                .send(vec![Bytes::new(), Bytes::from("recovered")])
+                .await
+                .expect("send after auto-recovery");
+            assert!(!dealer.is_poisoned());
+        });
+
+    let msg = msg_rx.recv_timeout(Duration::from_secs(15)).unwrap();
+    assert_eq!(msg.last().unwrap(), &Bytes::from("recovered"));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: SUB from connect_with_reconnect survives a PUB restart
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// The PUB serves one subscriber, closes, and a fresh PUB takes over the same
+// listener. The SUB only ever calls plain `recv()`: the reconnect and the
+// subscription replay happen inside it; the second PUB's subscription index
+// shows the replayed prefix.
+
+#[test]
+fn test_sub_survives_pub_restart() {
+    use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
+    use monocoque_zmtp::publisher::PubSocket;
+    use monocoque_zmtp::subscriber::SubSocket;
+
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (subscribed_tx, subscribed_rx) = mpsc::channel::<()>();
+    let (first_tx, first_rx) = mpsc::channel::<()>();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let server = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = monocoque_core::rt::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let mut pub1 = PubSocket::new();
+                pub1.accept_subscriber(&listener).await.unwrap();
+                subscribed_rx.recv().unwrap();
+                std::thread::sleep(Duration::from_millis(100));
+                pub1.send(vec![Bytes::from("tick.first")]).await.unwrap();
+                first_rx.recv().unwrap();
+                pub1.close().await.unwrap();
+
+                let index = SharedSubscriptionIndex::new();
+                let mut pub2 = PubSocket::new().with_shared_index(index.clone());
+                pub2.accept_subscriber(&listener).await.unwrap();
+                // The new PUB learns "tick." only from the replayed subscription.
+                let deadline = std::time::Instant::now() + Duration::from_secs(5);
+                while !index.matches(b"tick.second") {
+                    assert!(
+                        std::time::Instant::now() < deadline,
+                        "subscription was not replayed after reconnect"
+                    );
+                    monocoque_core::rt::sleep(Duration::from_millis(10)).await;
+                }
+                assert!(!index.matches(b"other.x"));
+                for topic in ["tick.second", "other.x", "tick.third"] {
+                    pub2.send(vec![Bytes::from(topic)]).await.unwrap();
+                }
+                done_rx.recv_timeout(Duration::from_secs(15)).unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let mut sub = SubSocket::connect_with_reconnect_options(addr, fast_opts())
+                .await
+                .unwrap();
+            sub.subscribe(Bytes::from_static(b"tick.")).await.unwrap();
+            subscribed_tx.send(()).unwrap();
+
+            let mut received = Vec::new();
+            for expected in ["tick.first", "tick.second", "tick.third"] {
+                let msg = monocoque_core::rt::timeout(Duration::from_secs(10), sub.recv())
+                    .await
+                    .expect("recv timed out")
+                    .expect("io error on recv")
+                    .expect("recv returned EOF on an auto-reconnecting socket");
+                if expected == "tick.first" {
+                    first_tx.send(()).unwrap();
+                }
+                received.push(msg[0].clone());
+            }
+            assert_eq!(
+                received,
+                vec![
+                    Bytes::from("tick.first"),
+                    Bytes::from("tick.second"),
+                    Bytes::from("tick.third")
+                ]
+            );
+            done_tx.send(()).unwrap();
+        });
+
+    server.join().expect("server thread panicked");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: connect_history records failed attempts and the final success
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// The server accepts once and closes its listener, so reconnects are refused
+// until it binds the same port again once the client has failed three times.
+
+#[test]
+fn test_connect_history_records_failures_then_success() {
+    use monocoque_core::reconnect::ConnectOutcome;
+
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (failed_tx, failed_rx) = mpsc::channel::<()>();
+
+    let server = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = monocoque_core::rt::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .unwrap();
+                let addr = listener.local_addr().unwrap();
+                addr_tx.send(addr).unwrap();
+                let (stream, _) = listener.accept().await.unwrap();
+                let router = RouterSocket::from_tcp(stream).await.unwrap();
+                drop(listener);
+                drop(router);
+
+                failed_rx.recv().unwrap();
+                let listener = monocoque_core::rt::TcpListener::bind(addr).await.unwrap();
+                let (stream, _) = listener.accept().await.unwrap();
+                let _router = RouterSocket::from_tcp(stream).await.unwrap();
+                std::thread::sleep(Duration::from_millis(100));
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let mut dealer = DealerSocket::connect_with_options(addr, fast_opts())
+                .await
+                .unwrap();
+            for _ in 0..3 {
+                assert!(dealer.try_reconnect().await.is_err());
+            }
+            failed_tx.send(()).unwrap();
+            let deadline = std::time::Instant::now() + Duration::from_secs(5);
+            while dealer.try_reconnect().await.is_err() {
+                assert!(std::time::Instant::now() < deadline, "never reconnected");
+            }
+
+            let history: Vec<_> = dealer.connect_history().iter().cloned().collect();
+            assert!(history.len() >= 5, "history: {history:?}");
+            assert_eq!(history[0].attempt_number, 0);
+            assert!(history[0].outcome.is_ok());
+            for attempt in &history[1..4] {
+                assert!(
+                    matches!(
+                        attempt.outcome,
+                        ConnectOutcome::Err {
+                            kind: std::io::ErrorKind::ConnectionRefused,
+                            ..
+                        }
+                    ),
+                    "unexpected outcome: {:?}",
+                    attempt.outcome
+                );
+            }
+            let last = history.last().unwrap();
+            assert!(last.outcome.is_ok());
+            assert_eq!(last.endpoint.to_string(), format!("tcp://{addr}"));
+            // Attempt numbers count up from the initial connect, and
+            // timestamps never go backwards.
+            for pair in history.windows(2) {
+                assert_eq!(pair[1].attempt_number, pair[0].attempt_number + 1);
+                assert!(pair[1].started_at >= pair[0].started_at);
+            }
+
+            let dump = dealer.diagnostics();
+            assert!(dump.contains("state: connected"), "{dump}");
+            assert!(dump.contains("ConnectionRefused"), "{dump}");
+            assert!(dump.contains("ReconnectIvl"), "{dump}");
+        });
+
+    server.join().expect("server thread panicked");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: immediate mode fails send_with_reconnect when the redial fails
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_immediate_send_with_reconnect_fails_while_disconnected() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+
+    let server = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = monocoque_core::rt::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+                let _ = router.recv().await; // receive then drop, listener too
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            // The peer is gone for good: the redial is refused, and the send
+            // fails instead of retrying after a backoff long enough to show.
+            let opts = SocketOptions::default()
+                .with_immediate(true)
+                .with_reconnect_ivl(Duration::from_secs(5));
+            let mut dealer = DealerSocket::connect_with_options(addr, opts)
+                .await
+                .unwrap();
+            dealer
+                .send(vec![Bytes::new(), Bytes::from("ping")])
+                .await
+                .unwrap();
+            assert!(matches!(dealer.recv().await, Ok(None) | Err(_)));
+            assert!(!dealer.is_connected());
+
+            let started = std::time::Instant::now();
+            let err = dealer
+                .send_with_reconnect(vec![Bytes::new(), Bytes::from("lost")])
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::NotConnected);
+            // The failed redial started a backoff; the next send does not wait it out.
+            let err = dealer
+                .send_with_reconnect(vec![Bytes::new(), Bytes::from("lost")])
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::NotConnected);
+            assert!(err.to_string().contains("backing off"), "{err}");
+            assert!(started.elapsed() < Duration::from_secs(1));
+            assert_eq!(dealer.buffered_messages(), 0);
+        });
+    server.join().unwrap();
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Test: immediate mode still redials from the send path
+// ─────────────────────────────────────────────────────────────────────────────
+//
+// Server: accept → recv "ping" → drop; accept again → recv "again".
+// Client: connect (immediate) → send "ping" → EOF → send_with_reconnect
+// redials the restarted peer and delivers "again".
+
+#[test]
+fn test_immediate_send_with_reconnect_redials_restarted_peer() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (got_tx, got_rx) = mpsc::channel::<Vec<Bytes>>();
+
+    let server = thread::spawn(move || {
+        monocoque_core::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = monocoque_core::rt::TcpListener::bind("127.0.0.1:0")
+                    .await
+                    .unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+                let _ = router.recv().await;
+                drop(router);
+
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+                let msg = router.recv().await.unwrap().expect("message after restart");
+                got_tx.send(msg).unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+
+    monocoque_core::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let opts = fast_opts().with_immediate(true);
+            let mut dealer = DealerSocket::connect_with_options(addr, opts)
+                .await
+                .unwrap();
+            dealer
+                .send(vec![Bytes::new(), Bytes::from("ping")])
+                .await
+                .unwrap();
+            assert!(matches!(dealer.recv().await, Ok(None) | Err(_)));
+            assert!(!dealer.is_connected());
+
+            dealer
+                .send_with_reconnect(vec![Bytes::new(), Bytes::from("again")])
+                .await
+                .unwrap();
+            assert!(dealer.is_connected());
+        });
+
+    let msg = got_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(msg[1..], [Bytes::new(), Bytes::from("again")]);
+    server.join().unwrap();
+}
@@ -0,0 +1,556 @@
+//! Multi-peer ROUTER from `RouterSocket::accept_loop` serving concurrent
+//! DEALER clients.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::req::ReqSocket;
+use monocoque_zmtp::router::RouterSocket;
+use monocoque_zmtp::router_hub::{FlushOutcome, RouterHubSocket};
+use monocoque_zmtp::security::{CurveKeyPair, Principal};
+use std::collections::HashSet;
+use std::time::Duration;
+
+const CLIENTS: usize = 10;
+const REQUESTS: usize = 5;
+
+#[test]
+fn routes_replies_to_ten_concurrent_dealers() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+
+        // Even clients announce an identity and frame requests REQ-style with
+        // an empty delimiter; odd clients get a generated identity and send
+        // bare bodies. Each gets back exactly what it sent, reversed.
+        let clients: Vec<_> = (0..CLIENTS)
+            .map(|i| {
+                rt::spawn(async move {
+                    let options = if i % 2 == 0 {
+                        SocketOptions::default().with_routing_id(Bytes::from(format!("client-{i}")))
+                    } else {
+                        SocketOptions::default()
+                    };
+                    let mut dealer = DealerSocket::connect_with_options(addr, options)
+                        .await
+                        .unwrap();
+                    for j in 0..REQUESTS {
+                        let body = Bytes::from(format!("{i}:{j}"));
+                        let msg = if i % 2 == 0 {
+                            vec![Bytes::new(), body.clone()]
+                        } else {
+                            vec![body.clone()]
+                        };
+                        dealer.send(msg.clone()).await.unwrap();
+                        let reply = rt::timeout(Duration::from_secs(5), dealer.recv())
+                            .await
+                            .expect("reply timed out")
+                            .unwrap()
+                            .expect("dealer EOF");
+                        let mut expected = msg;
+                        let last = expected.len() - 1;
+                        expected[last] = reversed(&body);
+                        assert_eq!(reply, expected, "client {i} request {j}");
+                    }
+                    dealer
+                })
+            })
+            .collect();
+
+        let mut identities = HashSet::new();
+        for _ in 0..CLIENTS * REQUESTS {
+            let msg = rt::timeout(Duration::from_secs(5), router.recv())
+                .await
+                .expect("request timed out")
+                .unwrap()
+                .expect("router closed");
+            assert_eq!(msg.len(), 3, "expected [identity, \"\", body], got {msg:?}");
+            assert!(msg[1].is_empty());
+            identities.insert(msg[0].clone());
+            router
+                .send(vec![msg[0].clone(), Bytes::new(), reversed(&msg[2])])
+                .await
+                .unwrap();
+        }
+
+        // Dealers are handed back so no connection closed before its last
+        // reply was read.
+        let mut dealers = Vec::new();
+        for client in clients {
+            dealers.push(rt::join(client).await);
+        }
+        assert_eq!(dealers.len(), CLIENTS);
+        assert_eq!(identities.len(), CLIENTS);
+        for i in (0..CLIENTS).step_by(2) {
+            assert!(identities.contains(format!("client-{i}").as_bytes()));
+        }
+    });
+}
+
+#[test]
+fn req_peer_gets_its_delimiter_back() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+
+        let client = rt::spawn(async move {
+            let mut req = ReqSocket::connect(addr).await.unwrap();
+            req.send(vec![Bytes::from_static(b"ping")]).await.unwrap();
+            req.recv().await.unwrap().expect("req EOF")
+        });
+
+        let msg = router.recv().await.unwrap().expect("router closed");
+        assert_eq!(&msg[1..], &[Bytes::new(), Bytes::from_static(b"ping")]);
+        router
+            .send(vec![
+                msg[0].clone(),
+                Bytes::new(),
+                Bytes::from_static(b"pong"),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(rt::join(client).await, vec![Bytes::from_static(b"pong")]);
+    });
+}
+
+#[test]
+fn send_to_reaches_only_the_named_peer() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+
+        let mut dealers = Vec::new();
+        for id in [&b"alice"[..], b"bob"] {
+            let options = SocketOptions::default().with_routing_id(Bytes::copy_from_slice(id));
+            let mut dealer = DealerSocket::connect_with_options(addr, options)
+                .await
+                .unwrap();
+            dealer
+                .send(vec![Bytes::new(), Bytes::from_static(b"hi")])
+                .await
+                .unwrap();
+            let (identity, body) = router.recv_from().await.unwrap().expect("router closed");
+            assert_eq!(identity, Bytes::copy_from_slice(id));
+            assert_eq!(body, vec![Bytes::from_static(b"hi")]);
+            dealers.push(dealer);
+        }
+
+        router
+            .send_to(b"bob", vec![Bytes::from_static(b"for bob")])
+            .await
+            .unwrap();
+        let reply = rt::timeout(Duration::from_secs(5), dealers[1].recv())
+            .await
+            .expect("reply timed out")
+            .unwrap()
+            .expect("dealer EOF");
+        assert_eq!(reply, vec![Bytes::new(), Bytes::from_static(b"for bob")]);
+        assert!(
+            rt::timeout(Duration::from_millis(100), dealers[0].recv())
+                .await
+                .is_err(),
+            "alice must not get bob's message"
+        );
+    });
+}
+
+#[test]
+fn duplicate_identity_is_refused() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+
+        let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"same"));
+        let mut first = DealerSocket::connect_with_options(addr, options.clone())
+            .await
+            .unwrap();
+        first.send(vec![Bytes::from_static(b"one")]).await.unwrap();
+        let msg = router.recv().await.unwrap().expect("router closed");
+        assert_eq!(msg[2], Bytes::from_static(b"one"));
+
+        // The handshake completes, then the router drops the duplicate.
+        let mut second = DealerSocket::connect_with_options(addr, options)
+            .await
+            .unwrap();
+        let _ = second.send(vec![Bytes::from_static(b"two")]).await;
+        assert!(
+            rt::timeout(Duration::from_millis(100), router.recv())
+                .await
+                .is_err(),
+            "duplicate identity must not be served"
+        );
+
+        // The original connection keeps its route.
+        router
+            .send(vec![Bytes::from_static(b"same"), Bytes::from_static(b"ok")])
+            .await
+            .unwrap();
+        let reply = rt::timeout(Duration::from_secs(5), first.recv())
+            .await
+            .expect("reply timed out")
+            .unwrap()
+            .expect("dealer EOF");
+        assert_eq!(reply, vec![Bytes::from_static(b"ok")]);
+    });
+}
+
+#[test]
+fn enumerates_connected_peers() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+        assert!(router.connected_peers().is_empty());
+
+        let mut dealers = Vec::new();
+        for id in [&b"alice"[..], b"bob"] {
+            let options = SocketOptions::default().with_routing_id(Bytes::copy_from_slice(id));
+            let mut dealer = DealerSocket::connect_with_options(addr, options)
+                .await
+                .unwrap();
+            dealer.send(vec![Bytes::from_static(b"hi")]).await.unwrap();
+            // A peer is registered before its first message is delivered.
+            router.recv().await.unwrap().expect("router closed");
+            dealers.push(dealer);
+        }
+
+        let peers: HashSet<_> = router.connected_peers().into_iter().collect();
+        assert_eq!(
+            peers,
+            HashSet::from([Bytes::from_static(b"alice"), Bytes::from_static(b"bob")])
+        );
+        assert!(router.is_peer_connected(b"alice"));
+        assert!(router.is_peer_connected(b"bob"));
+        assert!(!router.is_peer_connected(b"carol"));
+        assert!(dealers.iter().all(DealerSocket::is_connected));
+    });
+}
+
+#[test]
+fn handed_over_identity_is_listed_once() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(
+            listener,
+            SocketOptions::default().with_router_handover(true),
+        );
+        rt::spawn_detached(driver);
+
+        let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"same"));
+        let mut first = DealerSocket::connect_with_options(addr, options.clone())
+            .await
+            .unwrap();
+        first.send(vec![Bytes::from_static(b"one")]).await.unwrap();
+        router.recv().await.unwrap().expect("router closed");
+
+        let mut second = DealerSocket::connect_with_options(addr, options)
+            .await
+            .unwrap();
+        second.send(vec![Bytes::from_static(b"two")]).await.unwrap();
+        let msg = rt::timeout(Duration::from_secs(5), router.recv())
+            .await
+            .expect("handover peer not served")
+            .unwrap()
+            .expect("router closed");
+        assert_eq!(msg[2], Bytes::from_static(b"two"));
+
+        // The displaced connection is closed without unregistering the
+        // identity it lost.
+        let eof = rt::timeout(Duration::from_secs(5), first.recv())
+            .await
+            .expect("displaced peer not closed")
+            .unwrap();
+        assert!(eof.is_none());
+        assert_eq!(router.connected_peers(), vec![Bytes::from_static(b"same")]);
+
+        // Replies now reach the newest connection.
+        router
+            .send(vec![Bytes::from_static(b"same"), Bytes::from_static(b"ok")])
+            .await
+            .unwrap();
+        let reply = rt::timeout(Duration::from_secs(5), second.recv())
+            .await
+            .expect("reply timed out")
+            .unwrap()
+            .expect("dealer EOF");
+        assert_eq!(reply, vec![Bytes::from_static(b"ok")]);
+    });
+}
+
+#[test]
+fn messages_carry_the_curve_key_of_their_sender() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let server_keys = CurveKeyPair::generate();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(
+            listener,
+            SocketOptions::default()
+                .with_curve_server(true)
+                .with_curve_keypair(
+                    *server_keys.public.as_bytes(),
+                    *server_keys.secret.as_bytes(),
+                )
+                .with_router_handover(true),
+        );
+        rt::spawn_detached(driver);
+
+        let client = |id: &'static [u8], keys: &CurveKeyPair| {
+            SocketOptions::default()
+                .with_routing_id(Bytes::from_static(id))
+                .with_curve_keypair(*keys.public.as_bytes(), *keys.secret.as_bytes())
+                .with_curve_serverkey(*server_keys.public.as_bytes())
+        };
+        let (alice_keys, bob_keys) = (CurveKeyPair::generate(), CurveKeyPair::generate());
+        let mut alice = DealerSocket::connect_with_options(addr, client(b"alice", &alice_keys))
+            .await
+            .unwrap();
+        let mut bob = DealerSocket::connect_with_options(addr, client(b"bob", &bob_keys))
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            alice
+                .send(vec![Bytes::from_static(b"from alice")])
+                .await
+                .unwrap();
+            bob.send(vec![Bytes::from_static(b"from bob")])
+                .await
+                .unwrap();
+        }
+        for _ in 0..6 {
+            let (msg, meta) = rt::timeout(Duration::from_secs(5), router.recv_with_meta())
+                .await
+                .expect("request timed out")
+                .unwrap()
+                .expect("router closed");
+            let expected = if msg[0] == "alice" {
+                &alice_keys
+            } else {
+                &bob_keys
+            };
+            assert_eq!(
+                meta.principal.as_deref(),
+                Some(&Principal::CurveKey(*expected.public.as_bytes())),
+                "message {msg:?}"
+            );
+        }
+        assert_eq!(
+            router.peer_principal(b"bob").as_deref(),
+            Some(&Principal::CurveKey(*bob_keys.public.as_bytes()))
+        );
+
+        // A connection taking the identity over brings its own principal.
+        let mallory_keys = CurveKeyPair::generate();
+        let mut mallory = DealerSocket::connect_with_options(addr, client(b"alice", &mallory_keys))
+            .await
+            .unwrap();
+        mallory.send(vec![Bytes::from_static(b"hi")]).await.unwrap();
+        let (_, meta) = rt::timeout(Duration::from_secs(5), router.recv_with_meta())
+            .await
+            .expect("handover peer not served")
+            .unwrap()
+            .expect("router closed");
+        let mallory_principal = Principal::CurveKey(*mallory_keys.public.as_bytes());
+        assert_eq!(meta.principal.as_deref(), Some(&mallory_principal));
+        assert_eq!(
+            router.peer_principal(b"alice").as_deref(),
+            Some(&mallory_principal)
+        );
+        let snapshot = router
+            .peers()
+            .into_iter()
+            .find(|peer| peer.identity == "alice")
+            .expect("alice listed");
+        assert_eq!(snapshot.principal.as_deref(), Some(&mallory_principal));
+    });
+}
+
+#[test]
+fn unknown_identity_is_dropped_or_unreachable_under_mandatory() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+
+        let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"alice"));
+        let mut dealer = DealerSocket::connect_with_options(addr, options)
+            .await
+            .unwrap();
+        dealer.send(vec![Bytes::from_static(b"hi")]).await.unwrap();
+        router.recv().await.unwrap().expect("router closed");
+
+        // Default: a message for an unknown identity vanishes.
+        router
+            .send(vec![
+                Bytes::from_static(b"nobody"),
+                Bytes::from_static(b"lost"),
+            ])
+            .await
+            .unwrap();
+
+        router.set_router_mandatory(true);
+        let err = router
+            .send(vec![
+                Bytes::from_static(b"nobody"),
+                Bytes::from_static(b"lost"),
+            ])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::HostUnreachable);
+
+        router
+            .send(vec![
+                Bytes::from_static(b"alice"),
+                Bytes::from_static(b"ok"),
+            ])
+            .await
+            .unwrap();
+        let reply = rt::timeout(Duration::from_secs(5), dealer.recv())
+            .await
+            .expect("reply timed out")
+            .unwrap()
+            .expect("dealer EOF");
+        // Only the message for the known identity arrived.
+        assert_eq!(reply, vec![Bytes::from_static(b"ok")]);
+    });
+}
+
+/// Bodies large enough that a few of them fill the socket buffers of a peer
+/// that is not reading.
+const SLOW_BODY: usize = 1 << 20;
+const SLOW_MESSAGES: usize = 16;
+
+/// Queue `SLOW_MESSAGES` large messages for a connected DEALER with the
+/// identity `slow` that has not started reading.
+async fn router_with_slow_peer(options: SocketOptions) -> (RouterHubSocket, DealerSocket) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (mut router, driver) = RouterSocket::accept_loop(listener, options);
+    rt::spawn_detached(driver);
+
+    let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"slow"));
+    let mut dealer = DealerSocket::connect_with_options(addr, options)
+        .await
+        .unwrap();
+    dealer.send(vec![Bytes::from_static(b"hi")]).await.unwrap();
+    router.recv().await.unwrap().expect("router closed");
+
+    let body = Bytes::from(vec![7u8; SLOW_BODY]);
+    for _ in 0..SLOW_MESSAGES {
+        router
+            .send(vec![Bytes::from_static(b"slow"), body.clone()])
+            .await
+            .unwrap();
+    }
+    (router, dealer)
+}
+
+#[test]
+fn flush_peer_reports_remaining_then_drains() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (mut router, mut dealer) = router_with_slow_peer(SocketOptions::default()).await;
+
+        // The peer is not reading, so a short flush leaves messages queued
+        // and reports exactly what `pending_for` sees.
+        let stalled = router
+            .flush_peer(b"slow", Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert!(stalled.remaining > 0, "nothing left queued: {stalled:?}");
+        assert_eq!(stalled.remaining, router.pending_for(b"slow"));
+
+        let reader = rt::spawn(async move {
+            for _ in 0..SLOW_MESSAGES {
+                let msg = dealer.recv().await.unwrap().expect("dealer EOF");
+                assert_eq!(msg[0].len(), SLOW_BODY);
+            }
+            dealer
+        });
+
+        // Once the peer reads, a generous flush delivers the rest.
+        let drained = router
+            .flush_peer(b"slow", Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(
+            drained,
+            FlushOutcome {
+                delivered: stalled.remaining,
+                remaining: 0
+            }
+        );
+        assert_eq!(router.pending_for(b"slow"), 0);
+        rt::join(reader).await;
+
+        let err = router
+            .flush_peer(b"nobody", Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::HostUnreachable);
+    });
+}
+
+#[test]
+fn snapshot_lists_peers_and_queue_depths() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (mut router, _dealer) = router_with_slow_peer(SocketOptions::default()).await;
+        let stalled = router
+            .flush_peer(b"slow", Duration::from_millis(100))
+            .await
+            .unwrap();
+
+        let snapshot = router.snapshot();
+        assert_eq!(snapshot.socket_type, "ROUTER");
+        assert_eq!(snapshot.state, "connected");
+        assert!(snapshot.endpoint.unwrap().starts_with("tcp://127.0.0.1:"));
+        assert_eq!(snapshot.peers.len(), 1);
+        assert_eq!(snapshot.peers[0].identity, "b\"slow\"");
+        assert_eq!(snapshot.peers[0].pending, stalled.remaining);
+        let queues: Vec<_> = snapshot
+            .queues
+            .iter()
+            .map(|q| (q.name.as_str(), q.peers))
+            .collect();
+        assert_eq!(queues, [("outbound", 1), ("inbound", 1)]);
+    });
+}
+
+#[test]
+fn graceful_kick_flushes_within_linger_then_disconnects() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = SocketOptions::default().with_linger(Some(Duration::from_millis(100)));
+        let (mut router, _dealer) = router_with_slow_peer(options).await;
+
+        let outcome = router.kick(b"slow", true).await.unwrap();
+        assert!(outcome.remaining > 0, "nothing left queued: {outcome:?}");
+
+        rt::timeout(Duration::from_secs(5), async {
+            while router.is_peer_connected(b"slow") {
+                rt::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("kicked peer still connected");
+        assert_eq!(router.pending_for(b"slow"), 0);
+        let err = router.kick(b"slow", false).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::HostUnreachable);
+    });
+}
+
+fn reversed(body: &Bytes) -> Bytes {
+    body.iter().rev().copied().collect::<Vec<u8>>().into()
+}
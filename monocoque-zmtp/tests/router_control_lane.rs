@@ -0,0 +1,137 @@
+//! Control traffic on a hub ROUTER connection is not queued behind data.
+//!
+//! A scripted DEALER peer lets the ROUTER queue thousands of messages for it
+//! without reading them, then sends a PING. The PONG must arrive after at most
+//! what was already in flight, well before the backlog drains. The same holds
+//! for `kick_all`: the connection closes without delivering the backlog.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::router::RouterSocket;
+use monocoque_zmtp::router_hub::RouterHubSocket;
+use std::io;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{null_greeting, read_exact, try_read_exact, write_all};
+
+const BACKLOG: usize = 4000;
+const BODY_LEN: usize = 16 * 1024;
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// READY command advertising `Socket-Type: DEALER` and `Identity: raw`.
+const DEALER_READY: &[u8] =
+    b"\x04\x2c\x05READY\x0bSocket-Type\x00\x00\x00\x06DEALER\x08Identity\x00\x00\x00\x03raw";
+
+/// PING command with a zero TTL and no context.
+const PING: &[u8] = b"\x04\x07\x04PING\x00\x00";
+
+/// Read one frame, returning whether it is a command and its body.
+async fn read_frame(stream: &mut TcpStream) -> io::Result<(bool, Vec<u8>)> {
+    let flags = try_read_exact(stream, 1).await?[0];
+    let len = if flags & 0x02 == 0 {
+        usize::from(try_read_exact(stream, 1).await?[0])
+    } else {
+        let len = try_read_exact(stream, 8).await?;
+        usize::try_from(u64::from_be_bytes(len.try_into().unwrap())).unwrap()
+    };
+    let body = try_read_exact(stream, len).await?;
+    Ok((flags & 0x04 != 0, body))
+}
+
+/// Start a hub ROUTER, connect the scripted peer, and queue `BACKLOG`
+/// messages for it without reading any of them.
+async fn saturated_peer() -> (RouterHubSocket, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+    rt::spawn_detached(driver);
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    write_all(&mut stream, &null_greeting()).await;
+    read_exact(&mut stream, 64).await;
+    write_all(&mut stream, DEALER_READY).await;
+    let (is_command, ready) = read_frame(&mut stream).await.unwrap();
+    assert!(is_command && ready.starts_with(b"\x05READY"));
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !router.is_peer_connected(b"raw") {
+        assert!(Instant::now() < deadline, "peer never registered");
+        rt::sleep(Duration::from_millis(1)).await;
+    }
+
+    let body = Bytes::from(vec![0xAB; BODY_LEN]);
+    for _ in 0..BACKLOG {
+        router
+            .send(vec![Bytes::from_static(b"raw"), body.clone()])
+            .await
+            .unwrap();
+    }
+    while router.pending_for(b"raw") < BACKLOG / 2 {
+        assert!(Instant::now() < deadline, "backlog never queued");
+        rt::sleep(Duration::from_millis(1)).await;
+    }
+    (router, stream)
+}
+
+#[test]
+fn pong_overtakes_a_saturated_data_lane() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (router, mut stream) = saturated_peer().await;
+
+        let started = Instant::now();
+        write_all(&mut stream, PING).await;
+        // Let the reader queue the PONG before the writer is unblocked.
+        rt::sleep(Duration::from_millis(50)).await;
+
+        let mut bodies = 0usize;
+        loop {
+            let (is_command, frame) = rt::timeout(HEARTBEAT_TIMEOUT, read_frame(&mut stream))
+                .await
+                .expect("PONG not received within the heartbeat timeout")
+                .unwrap();
+            if is_command {
+                assert!(
+                    frame.starts_with(b"\x04PONG"),
+                    "unexpected command {frame:?}"
+                );
+                break;
+            }
+            if frame.len() == BODY_LEN {
+                bodies += 1;
+            }
+        }
+        assert!(started.elapsed() < HEARTBEAT_TIMEOUT);
+        assert!(
+            bodies < BACKLOG / 2,
+            "PONG waited behind {bodies} of {BACKLOG} queued messages"
+        );
+        drop(router);
+    });
+}
+
+#[test]
+fn kick_all_overtakes_a_saturated_data_lane() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (mut router, mut stream) = saturated_peer().await;
+
+        router.kick_all().await.unwrap();
+        rt::sleep(Duration::from_millis(50)).await;
+
+        let mut bodies = 0usize;
+        let closed = rt::timeout(HEARTBEAT_TIMEOUT, async {
+            while let Ok((is_command, frame)) = read_frame(&mut stream).await {
+                if !is_command && frame.len() == BODY_LEN {
+                    bodies += 1;
+                }
+            }
+        })
+        .await;
+        assert!(closed.is_ok(), "connection still open after kick_all");
+        assert!(
+            bodies < BACKLOG / 2,
+            "close waited behind {bodies} of {BACKLOG} queued messages"
+        );
+    });
+}
@@ -0,0 +1,87 @@
+//! DEALER routing identities reach the ROUTER through the READY handshake.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::router::RouterSocket;
+use std::io;
+
+#[test]
+fn router_sees_dealer_identities() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let workers: Vec<_> = ["worker-1", "worker-2"]
+            .into_iter()
+            .map(|name| {
+                rt::spawn(async move {
+                    let options = SocketOptions::default()
+                        .with_routing_id(Bytes::from_static(name.as_bytes()));
+                    let mut dealer = DealerSocket::connect_with_options(addr, options)
+                        .await
+                        .unwrap();
+                    dealer
+                        .send(vec![Bytes::from_static(b"ready")])
+                        .await
+                        .unwrap();
+
+                    // Echo of our own identity, routed back by the ROUTER.
+                    let reply = dealer.recv().await.unwrap().expect("dealer EOF");
+                    assert_eq!(reply, vec![Bytes::from_static(name.as_bytes())]);
+                })
+            })
+            .collect();
+
+        let mut identities = Vec::new();
+        for _ in 0..2 {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+            let msg = router.recv().await.unwrap().expect("router EOF");
+            assert_eq!(msg.len(), 2, "expected [identity, body], got {msg:?}");
+            assert_eq!(msg[1], Bytes::from_static(b"ready"));
+
+            let identity = msg[0].clone();
+            router
+                .send(vec![identity.clone(), identity.clone()])
+                .await
+                .unwrap();
+            identities.push(identity);
+        }
+        for worker in workers {
+            rt::join(worker).await;
+        }
+
+        identities.sort();
+        assert_eq!(
+            identities,
+            vec![
+                Bytes::from_static(b"worker-1"),
+                Bytes::from_static(b"worker-2")
+            ]
+        );
+    });
+}
+
+#[test]
+fn oversized_identity_is_rejected_before_connecting() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let options = SocketOptions::default().with_routing_id(Bytes::from(vec![b'x'; 256]));
+        let err = DealerSocket::connect_with_options(addr, options)
+            .await
+            .err()
+            .expect("256-byte identity must be rejected");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        // Nothing reached the listener.
+        assert!(
+            rt::timeout(std::time::Duration::from_millis(50), listener.accept())
+                .await
+                .is_err()
+        );
+    });
+}
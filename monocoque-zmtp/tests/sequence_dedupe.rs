@@ -0,0 +1,127 @@
+//! Sequence stamps and the `dedupe_window` filter: a DEALER resending its
+//! unflushed burst after a reconnect is seen exactly once by the ROUTER.
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::router_hub::RouterHubSocket;
+use std::time::Duration;
+
+const BURST: usize = 4000;
+const BODY: usize = 8 * 1024;
+const BEFORE_KICK: usize = 100;
+
+fn indexed_body(index: usize) -> Bytes {
+    let mut body = vec![0u8; BODY];
+    body[..8].copy_from_slice(&(index as u64).to_be_bytes());
+    Bytes::from(body)
+}
+
+fn body_index(body: &Bytes) -> usize {
+    u64::from_be_bytes(body[..8].try_into().unwrap()) as usize
+}
+
+#[test]
+fn resent_burst_is_delivered_exactly_once() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) = RouterHubSocket::accept_loop(
+            listener,
+            SocketOptions::default()
+                .with_dedupe_window(2 * BURST)
+                .with_recv_hwm(16),
+        );
+        rt::spawn_detached(driver);
+
+        let options = SocketOptions::default()
+            .with_routing_id(Bytes::from_static(b"resender"))
+            .with_dedupe_window(2 * BURST)
+            .with_resend_on_reconnect(true)
+            .with_send_hwm(0)
+            .with_reconnect_ivl(Duration::from_millis(10));
+        let mut dealer = DealerSocket::connect_with_options(addr, options)
+            .await
+            .unwrap();
+        // Far more than the socket buffers hold, so the flush is still
+        // writing when the ROUTER drops the connection.
+        for i in 0..BURST {
+            dealer.send_buffered(vec![indexed_body(i)]).unwrap();
+        }
+        let sender = rt::spawn(async move {
+            dealer.flush_with_reconnect().await.unwrap();
+            dealer
+        });
+
+        let mut seen = vec![0u32; BURST];
+        let mut received = 0;
+        rt::timeout(Duration::from_secs(30), async {
+            while received < BURST {
+                let msg = router.recv().await.unwrap().expect("ROUTER stopped");
+                assert_eq!(msg[0], Bytes::from_static(b"resender"));
+                assert!(msg[1].is_empty());
+                seen[body_index(&msg[2])] += 1;
+                received += 1;
+                if received == BEFORE_KICK {
+                    router.kick(b"resender", false).await.unwrap();
+                }
+            }
+        })
+        .await
+        .expect("burst not fully delivered");
+        let _dealer = rt::join(sender).await;
+
+        // Nothing more arrives: every resent copy was dropped.
+        assert!(
+            rt::timeout(Duration::from_millis(200), router.recv())
+                .await
+                .is_err(),
+            "a message was delivered twice"
+        );
+        assert!(seen.iter().all(|&n| n == 1), "burst not seen exactly once");
+        assert!(router.duplicates_dropped() > 0);
+    });
+}
+
+#[test]
+fn peer_without_dedupe_window_is_not_stamped() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (mut router, driver) =
+            RouterHubSocket::accept_loop(listener, SocketOptions::default().with_dedupe_window(64));
+        rt::spawn_detached(driver);
+
+        let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"plain"));
+        let mut dealer = DealerSocket::connect_with_options(addr, options)
+            .await
+            .unwrap();
+        dealer
+            .send(vec![Bytes::from_static(b"hello")])
+            .await
+            .unwrap();
+        let msg = rt::timeout(Duration::from_secs(5), router.recv())
+            .await
+            .expect("message timed out")
+            .unwrap()
+            .expect("ROUTER stopped");
+        assert_eq!(
+            msg,
+            vec![
+                Bytes::from_static(b"plain"),
+                Bytes::new(),
+                Bytes::from_static(b"hello")
+            ]
+        );
+
+        router.send(msg).await.unwrap();
+        let reply = rt::timeout(Duration::from_secs(5), dealer.recv())
+            .await
+            .expect("reply timed out")
+            .unwrap()
+            .expect("dealer EOF");
+        assert_eq!(reply, vec![Bytes::from_static(b"hello")]);
+        assert_eq!(router.duplicates_dropped(), 0);
+    });
+}
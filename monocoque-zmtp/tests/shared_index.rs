@@ -0,0 +1,118 @@
+//! Application-side queries against a PUB/XPUB socket's shared subscription
+//! index.
+
+use bytes::Bytes;
+use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_core::subscription::SubscriptionEvent;
+use monocoque_zmtp::publisher::PubSocket;
+use monocoque_zmtp::subscriber::SubSocket;
+use monocoque_zmtp::xpub::XPubSocket;
+use monocoque_zmtp::xsub::XSubSocket;
+use std::time::Duration;
+
+/// Poll `cond` until it holds, failing the test after two seconds.
+async fn eventually(what: &str, cond: impl Fn() -> bool) {
+    for _ in 0..200 {
+        if cond() {
+            return;
+        }
+        rt::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("timed out waiting for {what}");
+}
+
+/// Drive `recv_subscription` until it yields an event.
+async fn next_event(xpub: &mut XPubSocket) {
+    for _ in 0..200 {
+        if xpub.recv_subscription().await.unwrap().is_some() {
+            return;
+        }
+        rt::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("no subscription event from XPUB");
+}
+
+#[test]
+fn xpub_index_reflects_subscription_once_processed() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let index = SharedSubscriptionIndex::new();
+        let mut xpub = XPubSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .with_shared_index(index.clone());
+        let addr = xpub.local_addr().unwrap();
+
+        let client =
+            rt::spawn(async move { XSubSocket::connect(&addr.to_string()).await.unwrap() });
+        xpub.accept().await.unwrap();
+        let mut xsub = rt::join(client).await;
+
+        xsub.subscribe(Bytes::from_static(b"weather."))
+            .await
+            .unwrap();
+        next_event(&mut xpub).await;
+        // No polling: the event was applied before recv_subscription returned.
+        assert!(index.matches(b"weather.paris"));
+        assert!(!index.matches(b"sports.results"));
+
+        xsub.send_subscription_event(SubscriptionEvent::Unsubscribe(Bytes::from_static(
+            b"weather.",
+        )))
+        .await
+        .unwrap();
+        next_event(&mut xpub).await;
+        assert!(!index.matches(b"weather.paris"));
+        assert!(index.is_empty());
+    });
+}
+
+#[test]
+fn pub_index_tracks_subscriber_lifetime() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let index = SharedSubscriptionIndex::new();
+        let mut publisher = PubSocket::with_workers(2).with_shared_index(index.clone());
+
+        let client = rt::spawn(async move {
+            let stream = TcpStream::connect(addr).await.unwrap();
+            SubSocket::from_tcp(stream).await.unwrap()
+        });
+        let id = publisher.accept_subscriber(&listener).await.unwrap();
+        let mut sub = rt::join(client).await;
+
+        // Not subscribed yet: the subscriber gets everything, but the index
+        // only records explicit subscriptions.
+        assert!(index.is_empty());
+
+        sub.subscribe(Bytes::from_static(b"weather."))
+            .await
+            .unwrap();
+        eventually("subscription in index", || index.matches(b"weather.paris")).await;
+        assert_eq!(index.match_topic(b"weather.paris").as_slice(), &[id]);
+        assert!(!index.matches(b"sports.results"));
+
+        // Fan-out is unaffected by the attached index.
+        publisher
+            .send(vec![
+                Bytes::from_static(b"weather.paris"),
+                Bytes::from_static(b"12C"),
+            ])
+            .await
+            .unwrap();
+        let msg = rt::timeout(Duration::from_secs(5), sub.recv())
+            .await
+            .expect("recv timed out")
+            .unwrap()
+            .expect("subscriber EOF");
+        assert_eq!(msg[1], Bytes::from_static(b"12C"));
+
+        // Disconnecting drops the subscriber's entries.
+        drop(sub);
+        eventually("disconnect cleanup", || index.is_empty()).await;
+
+        publisher.close().await.unwrap();
+    });
+}
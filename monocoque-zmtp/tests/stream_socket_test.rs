@@ -162,7 +162,7 @@ fn test_stream_send_respects_send_hwm() {
             .unwrap()
             .block_on(async move {
                 let mut srv = StreamSocket::bind("127.0.0.1:0").await.unwrap();
-                srv.options_mut().send_hwm = HWM;
+                srv.update_options(|o| o.send_hwm = HWM).unwrap();
                 addr_tx.send(srv.local_addr().unwrap()).unwrap();
 
                 let routing_id = srv.accept_raw().await.unwrap();
@@ -0,0 +1,215 @@
+//! DEALER and ROUTER exchanging messages over TLS, with certificates
+//! generated per test.
+#![cfg(feature = "tls")]
+
+use bytes::Bytes;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener};
+use monocoque_zmtp::dealer::DealerSocket;
+use monocoque_zmtp::router::RouterSocket;
+use monocoque_zmtp::tls::rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use monocoque_zmtp::tls::rustls::{CertificateError, RootCertStore};
+use monocoque_zmtp::tls::{TlsAcceptorConfig, TlsConfig, TlsError};
+use std::time::Duration;
+
+/// A server config presenting a self-signed certificate for `localhost`, and
+/// a client config trusting it.
+fn localhost_configs() -> (TlsAcceptorConfig, TlsConfig) {
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert = CertificateDer::from(certified.cert.der().to_vec());
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(
+        certified.signing_key.serialize_der(),
+    ));
+
+    let mut roots = RootCertStore::empty();
+    roots.add(cert.clone()).unwrap();
+    (
+        TlsAcceptorConfig::with_single_cert(vec![cert], key).unwrap(),
+        TlsConfig::with_roots(roots),
+    )
+}
+
+fn tls_error(err: &std::io::Error) -> &TlsError {
+    err.get_ref()
+        .and_then(|e| e.downcast_ref::<TlsError>())
+        .unwrap_or_else(|| panic!("not a TLS error: {err:?}"))
+}
+
+#[test]
+fn dealer_and_router_exchange_over_tls() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (server_tls, client_tls) = localhost_configs();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = rt::spawn(async move {
+            let mut router =
+                RouterSocket::accept_tls(&listener, &server_tls, SocketOptions::default())
+                    .await
+                    .unwrap();
+            let msg = router.recv().await.unwrap().expect("router EOF");
+            assert_eq!(msg[1], Bytes::from_static(b"ping over tls"));
+            router
+                .send(vec![msg[0].clone(), Bytes::from_static(b"pong over tls")])
+                .await
+                .unwrap();
+            router
+        });
+
+        let options = SocketOptions::default().with_routing_id(Bytes::from_static(b"tls-client"));
+        let mut dealer = DealerSocket::connect_tls(
+            &format!("tcp://127.0.0.1:{port}"),
+            &client_tls.with_server_name("localhost"),
+            options,
+        )
+        .await
+        .unwrap();
+        dealer
+            .send(vec![Bytes::from_static(b"ping over tls")])
+            .await
+            .unwrap();
+        let reply = rt::timeout(Duration::from_secs(5), dealer.recv())
+            .await
+            .expect("reply timed out")
+            .unwrap()
+            .expect("dealer EOF");
+        assert_eq!(reply, vec![Bytes::from_static(b"pong over tls")]);
+
+        let _router = rt::join(server).await;
+    });
+}
+
+#[test]
+fn tls_endpoint_verifies_its_host() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (server_tls, client_tls) = localhost_configs();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = rt::spawn(async move {
+            let mut router =
+                RouterSocket::accept_tls(&listener, &server_tls, SocketOptions::default())
+                    .await
+                    .unwrap();
+            router.recv().await.unwrap().expect("router EOF")
+        });
+
+        // No server-name override: the certificate is checked against the
+        // endpoint's host.
+        let mut dealer = DealerSocket::connect_tls(
+            &format!("tls://localhost:{port}"),
+            &client_tls,
+            SocketOptions::default(),
+        )
+        .await
+        .unwrap();
+        dealer
+            .send(vec![Bytes::from_static(b"via tls endpoint")])
+            .await
+            .unwrap();
+        let msg = rt::timeout(Duration::from_secs(5), rt::join(server))
+            .await
+            .expect("ROUTER receive timed out");
+        assert_eq!(
+            msg.last().unwrap(),
+            &Bytes::from_static(b"via tls endpoint")
+        );
+    });
+}
+
+#[test]
+fn hostname_mismatch_is_rejected_before_zmtp() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (server_tls, client_tls) = localhost_configs();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = rt::spawn(async move {
+            RouterSocket::accept_tls(&listener, &server_tls, SocketOptions::default())
+                .await
+                .err()
+                .expect("ROUTER accepted a client that rejected its certificate")
+        });
+
+        let err = DealerSocket::connect_tls(
+            &format!("127.0.0.1:{port}"),
+            &client_tls.with_server_name("broker.example.com"),
+            SocketOptions::default(),
+        )
+        .await
+        .err()
+        .expect("DEALER trusted a certificate for another name");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            matches!(
+                tls_error(&err),
+                TlsError::CertificateRejected(
+                    CertificateError::NotValidForName
+                        | CertificateError::NotValidForNameContext { .. }
+                )
+            ),
+            "unexpected error: {err}"
+        );
+
+        // The ROUTER fails in the TLS layer on the client's alert, never
+        // reaching the ZMTP greeting.
+        let server_err = rt::timeout(Duration::from_secs(5), rt::join(server))
+            .await
+            .expect("ROUTER still waiting after the client gave up");
+        assert!(
+            matches!(tls_error(&server_err), TlsError::Protocol(_)),
+            "unexpected error: {server_err}"
+        );
+    });
+}
+
+#[test]
+fn recv_after_a_timed_out_recv_over_tls() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let (server_tls, client_tls) = localhost_configs();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (ready_tx, ready_rx) = flume::bounded::<()>(1);
+
+        let server = rt::spawn(async move {
+            let mut router =
+                RouterSocket::accept_tls(&listener, &server_tls, SocketOptions::default())
+                    .await
+                    .unwrap();
+            let msg = router.recv().await.unwrap().expect("router EOF");
+            // Reply only once the client's first recv has timed out.
+            ready_rx.recv_async().await.unwrap();
+            router
+                .send(vec![msg[0].clone(), Bytes::from_static(b"after timeout")])
+                .await
+                .unwrap();
+            router
+        });
+
+        let options = SocketOptions::default().with_recv_timeout(Duration::from_millis(50));
+        let mut dealer =
+            DealerSocket::connect_tls(&format!("tls://localhost:{port}"), &client_tls, options)
+                .await
+                .unwrap();
+        dealer
+            .send(vec![Bytes::from_static(b"hello")])
+            .await
+            .unwrap();
+
+        // Each timeout drops a TLS read in flight.
+        for _ in 0..3 {
+            let err = dealer.recv().await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        }
+        assert!(dealer.is_connected());
+        ready_tx.send(()).unwrap();
+
+        dealer
+            .update_options(|o| o.recv_timeout = Some(Duration::from_secs(5)))
+            .unwrap();
+        let reply = dealer.recv().await.unwrap().expect("dealer EOF");
+        assert_eq!(reply, vec![Bytes::from_static(b"after timeout")]);
+
+        let _router = rt::join(server).await;
+    });
+}
@@ -0,0 +1,128 @@
+//! ZAP response caching against a live handler on inproc.
+//!
+//! The handler counts requests, so the test can tell a cache hit from a round
+//! trip. It lives in its own test binary because the ZAP endpoint is global.
+
+use bytes::Bytes;
+use monocoque_core::rt;
+use monocoque_zmtp::security::zap::{ZapMechanism, ZapRequest, ZapResponse, ZapStatus};
+use monocoque_zmtp::security::zap_client::{CachingZapClient, ZapCache, ZapClient};
+use monocoque_zmtp::security::zap_handler::{ZapHandler, spawn_zap_server};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+const ALLOWED_KEY: [u8; 32] = [1; 32];
+const DENIED_KEY: [u8; 32] = [2; 32];
+const TTL: Duration = Duration::from_millis(200);
+const ALLOWED_ADDRESS: &str = "127.0.0.1";
+
+/// Approves `ALLOWED_KEY`, and NULL peers from `ALLOWED_ADDRESS`; counts every
+/// request it sees.
+#[derive(Default)]
+struct CountingHandler {
+    requests: AtomicUsize,
+}
+
+#[async_trait::async_trait(?Send)]
+impl ZapHandler for CountingHandler {
+    async fn authenticate(&self, request: &ZapRequest) -> ZapResponse {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        let allowed = match request.mechanism {
+            ZapMechanism::Curve => {
+                request.credentials.first().map(AsRef::as_ref) == Some(&ALLOWED_KEY[..])
+            }
+            ZapMechanism::Null => request.address == ALLOWED_ADDRESS,
+            ZapMechanism::Plain => false,
+        };
+        if allowed {
+            ZapResponse::success(request.request_id.clone(), "alice")
+        } else {
+            ZapResponse::failure(request.request_id.clone(), "unknown key")
+        }
+    }
+}
+
+/// A fresh ZAP connection sharing `cache`, as a server would open per peer.
+fn connection(cache: &ZapCache) -> CachingZapClient {
+    let inner = ZapClient::new(Duration::from_secs(2)).unwrap();
+    CachingZapClient::new(inner, cache.clone())
+}
+
+#[test]
+fn second_connection_with_same_key_skips_zap_within_ttl() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let handler = Arc::new(CountingHandler::default());
+        spawn_zap_server(handler.clone()).unwrap();
+        let cache = ZapCache::new(TTL);
+
+        let first = connection(&cache)
+            .authenticate_curve(&ALLOWED_KEY, "global", "127.0.0.1")
+            .await
+            .unwrap();
+        assert_eq!(first.user_id, "alice");
+        assert_eq!(handler.requests.load(Ordering::SeqCst), 1);
+
+        // Same key, new connection, inside the TTL: answered from the cache.
+        let second = connection(&cache)
+            .authenticate_curve(&ALLOWED_KEY, "global", "127.0.0.1")
+            .await
+            .unwrap();
+        assert_eq!(second.user_id, "alice");
+        assert_eq!(handler.requests.load(Ordering::SeqCst), 1);
+
+        // A different address is a different cache entry.
+        connection(&cache)
+            .authenticate_curve(&ALLOWED_KEY, "global", "127.0.0.2")
+            .await
+            .unwrap();
+        assert_eq!(handler.requests.load(Ordering::SeqCst), 2);
+
+        // A different domain is a different cache entry.
+        connection(&cache)
+            .authenticate_curve(&ALLOWED_KEY, "other", "127.0.0.1")
+            .await
+            .unwrap();
+        assert_eq!(handler.requests.load(Ordering::SeqCst), 3);
+
+        // Rejections are never cached.
+        for expected in [4, 5] {
+            let denied = connection(&cache)
+                .authenticate_curve(&DENIED_KEY, "global", "127.0.0.1")
+                .await
+                .unwrap();
+            assert_eq!(denied.status_text, "unknown key");
+            assert_eq!(handler.requests.load(Ordering::SeqCst), expected);
+        }
+
+        // An allowlisted address's NULL approval does not admit another one.
+        let null = |address: &str| {
+            ZapRequest::new_with_unique_id(
+                "global",
+                address,
+                Bytes::new(),
+                ZapMechanism::Null,
+                vec![],
+            )
+        };
+        let allowed = connection(&cache)
+            .authenticate(&null(ALLOWED_ADDRESS))
+            .await
+            .unwrap();
+        assert_eq!(allowed.status_code, ZapStatus::Success);
+        let other = connection(&cache)
+            .authenticate(&null("203.0.113.9"))
+            .await
+            .unwrap();
+        assert_eq!(other.status_code, ZapStatus::Failure);
+        assert_eq!(handler.requests.load(Ordering::SeqCst), 7);
+
+        // Once the TTL has passed the handler is asked again.
+        rt::sleep(TTL + Duration::from_millis(50)).await;
+        connection(&cache)
+            .authenticate_curve(&ALLOWED_KEY, "global", "127.0.0.1")
+            .await
+            .unwrap();
+        assert_eq!(handler.requests.load(Ordering::SeqCst), 8);
+    });
+}
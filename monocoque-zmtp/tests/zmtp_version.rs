@@ -0,0 +1,113 @@
+//! ZMTP 3.0 / 3.1 version negotiation against a scripted peer.
+//!
+//! The mock server speaks raw ZMTP over TCP so the test controls the version
+//! it advertises. A 3.1 PUSH client talking to a 3.0 server must fall back to
+//! 3.0: data still goes out as ordinary short/long frames, but no PING command
+//! is sent even with heartbeats enabled. Against a 3.1 server the same client
+//! does send the PING.
+
+use bytes::Bytes;
+use compio_io::AsyncRead;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::{self, TcpListener, TcpStream};
+use monocoque_zmtp::push::PushSocket;
+use std::time::Duration;
+
+mod common;
+use common::{null_greeting_with_minor, read_exact, write_all};
+
+/// READY command advertising `Socket-Type: PULL`, as a short command frame.
+const PULL_READY: &[u8] = b"\x04\x1a\x05READY\x0bSocket-Type\x00\x00\x00\x04PULL";
+
+/// Accept one client, run the greeting/READY exchange as a ZMTP 3.`minor`
+/// PULL peer, and return the stream together with the version the client
+/// advertised.
+async fn accept_as(listener: &TcpListener, minor: u8) -> (TcpStream, [u8; 2]) {
+    let (mut stream, _) = listener.accept().await.unwrap();
+
+    write_all(&mut stream, &null_greeting_with_minor(minor)).await;
+    let greeting = read_exact(&mut stream, 64).await;
+
+    write_all(&mut stream, PULL_READY).await;
+    let header = read_exact(&mut stream, 2).await;
+    assert_eq!(header[0], 0x04, "expected READY command frame");
+    read_exact(&mut stream, header[1] as usize).await;
+
+    (stream, [greeting[10], greeting[11]])
+}
+
+/// Whether anything else arrives from the client within `window`.
+async fn idle_for(stream: &mut TcpStream, window: Duration) -> bool {
+    rt::timeout(window, stream.read(vec![0u8; 64]))
+        .await
+        .is_err()
+}
+
+fn heartbeat_options() -> SocketOptions {
+    SocketOptions {
+        heartbeat_ivl: Some(Duration::from_millis(1)),
+        ..SocketOptions::default()
+    }
+}
+
+#[test]
+fn push_client_falls_back_to_zmtp_30_server() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = rt::spawn(async move {
+            let (mut stream, client_version) = accept_as(&listener, 0).await;
+            assert_eq!(client_version, [3, 1], "client should advertise ZMTP 3.1");
+
+            // Short frame: flags 0x00, 1-byte length.
+            assert_eq!(read_exact(&mut stream, 7).await, b"\x00\x05hello");
+
+            // Long frame: flags 0x02, 8-byte length. Valid in ZMTP 3.0 too.
+            let header = read_exact(&mut stream, 9).await;
+            assert_eq!(header[0], 0x02);
+            assert_eq!(u64::from_be_bytes(header[1..].try_into().unwrap()), 300);
+            assert_eq!(read_exact(&mut stream, 300).await, vec![b'x'; 300]);
+
+            assert!(
+                idle_for(&mut stream, Duration::from_millis(100)).await,
+                "client sent a ZMTP 3.1 command to a 3.0 peer"
+            );
+        });
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut push = PushSocket::from_tcp_with_options(stream, heartbeat_options())
+            .await
+            .unwrap();
+        push.send(vec![Bytes::from_static(b"hello")]).await.unwrap();
+        push.send(vec![Bytes::from(vec![b'x'; 300])]).await.unwrap();
+
+        rt::join(server).await;
+    });
+}
+
+#[test]
+fn push_client_sends_ping_to_zmtp_31_server() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = rt::spawn(async move {
+            let (mut stream, _) = accept_as(&listener, 1).await;
+            assert_eq!(read_exact(&mut stream, 7).await, b"\x00\x05hello");
+
+            let header = read_exact(&mut stream, 2).await;
+            assert_eq!(header[0], 0x04, "expected a command frame after the data");
+            let body = read_exact(&mut stream, header[1] as usize).await;
+            assert!(body.starts_with(b"\x04PING"), "expected PING, got {body:?}");
+        });
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut push = PushSocket::from_tcp_with_options(stream, heartbeat_options())
+            .await
+            .unwrap();
+        push.send(vec![Bytes::from_static(b"hello")]).await.unwrap();
+
+        rt::join(server).await;
+    });
+}
@@ -0,0 +1,109 @@
+//! Zero-copy vs copied frame decode for 4 KiB messages.
+//!
+//! `ZmtpDecoder` returns a frame whose body lies in one read segment as a
+//! slice of that segment, and copies only bodies that straddle segments. This
+//! bench decodes the same 4 KiB frames from two read layouts: one segment per
+//! frame (every body sliced) and 4 KiB segments that cut each frame apart
+//! (every body copied). Before timing, it prints the allocations per frame
+//! for each layout, counted by a global allocator, with the decoder's own
+//! zero-copy and copied counts.
+//!
+//! Run: `cargo bench --bench decode_zero_copy --features zmq`
+
+use bytes::{Bytes, BytesMut};
+use criterion::{BatchSize, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use monocoque_core::buffer::SegmentedBuffer;
+use monocoque_zmtp::codec::{ZmtpDecoder, encode_single};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const FRAME_SIZE: usize = 4 * 1024;
+const FRAMES: usize = 256;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+// SAFETY: delegates every operation to the system allocator unchanged; the
+// counter only observes, it never touches the returned pointers.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// `FRAMES` encoded 4 KiB frames, pushed as segments of `segment_len` bytes.
+fn read_buffer(segment_len: usize) -> SegmentedBuffer {
+    let mut wire = BytesMut::new();
+    let body = Bytes::from(vec![0x5A; FRAME_SIZE]);
+    for _ in 0..FRAMES {
+        encode_single(&body, &mut wire);
+    }
+    let wire = wire.freeze();
+    let mut src = SegmentedBuffer::new();
+    for start in (0..wire.len()).step_by(segment_len) {
+        src.push(wire.slice(start..(start + segment_len).min(wire.len())));
+    }
+    src
+}
+
+/// Wire length of one frame: long-form header plus body.
+const fn frame_len() -> usize {
+    9 + FRAME_SIZE
+}
+
+/// Decode every frame in `src`, returning the decoder for its counts.
+fn decode_all(mut src: SegmentedBuffer) -> ZmtpDecoder {
+    let mut decoder = ZmtpDecoder::new();
+    while let Ok(Some(frame)) = decoder.decode(&mut src) {
+        black_box(frame.payload);
+    }
+    decoder
+}
+
+fn report_allocations() {
+    for (name, segment_len) in [("one_segment", frame_len()), ("straddling", FRAME_SIZE)] {
+        let src = read_buffer(segment_len);
+        let before = ALLOCS.load(Ordering::Relaxed);
+        let decoder = decode_all(src);
+        let allocs = ALLOCS.load(Ordering::Relaxed) - before;
+        println!(
+            "decode_4kb/{name}: {:.2} allocations per frame \
+             ({} zero-copy, {} copied)",
+            allocs as f64 / FRAMES as f64,
+            decoder.zero_copy_frames(),
+            decoder.copied_frames(),
+        );
+    }
+}
+
+fn bench_decode_4kb(c: &mut Criterion) {
+    report_allocations();
+
+    let mut group = c.benchmark_group("decode_4kb");
+    group.throughput(Throughput::Bytes((FRAME_SIZE * FRAMES) as u64));
+    for (name, segment_len) in [("one_segment", frame_len()), ("straddling", FRAME_SIZE)] {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || read_buffer(segment_len),
+                decode_all,
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_decode_4kb);
+criterion_main!(benches);
@@ -0,0 +1,134 @@
+//! `RouterHub` routing policies against workers of uneven speed.
+//!
+//! Dispatches 10K jobs with `RouterHub::send_any`, one every few microseconds,
+//! to 4 worker threads that spend 5, 10, 20 and 40µs on each job, once per
+//! `RoutingPolicy`. Round robin hands the slowest worker a quarter of the
+//! jobs, so it finishes long after the others; least connections steers jobs
+//! to whichever worker has the shortest queue. The run reports, per policy,
+//! the time until every job was processed, how many jobs each worker took and
+//! the deepest queue any worker had, and whether least connections kept the
+//! queues shallower than round robin. Timings vary between machines, so it
+//! reports rather than fails.
+//!
+//! Run: `cargo bench --bench load_balancing --features zmq`
+
+use bytes::Bytes;
+use monocoque_core::router::{
+    HubEvent, PeerCmd, RouterBehavior, RouterCmd, RouterHub, RoutingPolicy,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const JOBS: usize = 10_000;
+const WORKER_COST: [Duration; 4] = [
+    Duration::from_micros(5),
+    Duration::from_micros(10),
+    Duration::from_micros(20),
+    Duration::from_micros(40),
+];
+/// Gap between dispatches: a little slower than the workers' combined rate
+/// (one job per ~2.7µs), so a policy that matches it never falls behind.
+const DISPATCH_GAP: Duration = Duration::from_micros(4);
+
+struct Outcome {
+    elapsed: Duration,
+    per_worker: Vec<usize>,
+    deepest: usize,
+}
+
+fn spin(duration: Duration) {
+    let until = Instant::now() + duration;
+    while Instant::now() < until {
+        std::hint::spin_loop();
+    }
+}
+
+fn run(policy: RoutingPolicy) -> Outcome {
+    let (_hub_tx, hub_rx) = flume::unbounded::<HubEvent>();
+    let (_user_tx, user_rx) = flume::unbounded::<RouterCmd>();
+    let mut hub = RouterHub::new(hub_rx, user_rx, RouterBehavior::Standard).with_policy(policy);
+    let done = Arc::new(AtomicUsize::new(0));
+
+    let mut queues = Vec::new();
+    let workers: Vec<_> = WORKER_COST
+        .iter()
+        .enumerate()
+        .map(|(i, &cost)| {
+            let (tx, rx) = flume::unbounded();
+            hub.handle_peer_event(HubEvent::PeerUp {
+                routing_id: Bytes::from(format!("worker-{i}")),
+                tx,
+                control: None,
+            });
+            queues.push(rx.clone());
+            let done = Arc::clone(&done);
+            thread::spawn(move || {
+                let mut taken = 0;
+                while let Ok(PeerCmd::SendBody(_)) = rx.recv() {
+                    spin(cost);
+                    taken += 1;
+                    if done.fetch_add(1, Ordering::Release) + 1 == JOBS {
+                        break;
+                    }
+                }
+                taken
+            })
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut deepest = 0;
+    for _ in 0..JOBS {
+        hub.send_any(vec![Bytes::from_static(b"job")]);
+        deepest = deepest.max(queues.iter().map(flume::Receiver::len).max().unwrap_or(0));
+        spin(DISPATCH_GAP);
+    }
+    while done.load(Ordering::Acquire) < JOBS {
+        deepest = deepest.max(queues.iter().map(flume::Receiver::len).max().unwrap_or(0));
+        thread::yield_now();
+    }
+    let elapsed = start.elapsed();
+    // Dropping the hub closes the queues of workers still waiting.
+    drop(hub);
+    let per_worker = workers.into_iter().map(|w| w.join().unwrap()).collect();
+    Outcome {
+        elapsed,
+        per_worker,
+        deepest,
+    }
+}
+
+fn main() {
+    println!("{JOBS} jobs, one every {DISPATCH_GAP:?}, to workers costing {WORKER_COST:?} per job");
+    let mut results = Vec::new();
+    for policy in [
+        RoutingPolicy::RoundRobin,
+        RoutingPolicy::LeastConnections,
+        RoutingPolicy::Random,
+    ] {
+        let outcome = run(policy);
+        println!(
+            "  {:<16} {:>10.1?}  jobs per worker {:?}  deepest queue {}",
+            format!("{policy:?}"),
+            outcome.elapsed,
+            outcome.per_worker,
+            outcome.deepest
+        );
+        results.push((policy, outcome));
+    }
+
+    let deepest = |wanted| {
+        results
+            .iter()
+            .find(|(policy, _)| *policy == wanted)
+            .map_or(0, |(_, outcome)| outcome.deepest)
+    };
+    let verdict = if deepest(RoutingPolicy::LeastConnections) < deepest(RoutingPolicy::RoundRobin) {
+        "spreads"
+    } else {
+        "does not spread"
+    };
+    println!("  least connections {verdict} the load more evenly than round robin");
+}
@@ -0,0 +1,149 @@
+//! Fan-out scaling of a sharded PUB socket.
+//!
+//! One `PubSocket::bind_sharded` publisher broadcasts to `SUBSCRIBERS`
+//! subscribe-to-all subscribers with each worker count in `WORKER_COUNTS`.
+//! With enough cores, delivery throughput should grow close to linearly with
+//! the worker count.
+//!
+//! ## Methodology
+//!
+//! - The publisher runs on its own thread and oversends until every
+//!   subscriber has timed its window, like the single-subscriber fan-out in
+//!   `patterns.rs`.
+//! - Subscribers are spread over `CLIENT_THREADS` runtimes, one task each.
+//!   Each receives `WARMUP_MSGS` untimed, then times `iters * MESSAGE_COUNT`.
+//! - The iteration cost is the slowest subscriber's window, and throughput
+//!   counts every delivery (`MESSAGE_COUNT * SUBSCRIBERS` per iteration).
+//! - Connection setup and handshakes happen outside the timed window.
+//!
+//! Holds roughly two descriptors per subscriber; raise `ulimit -n` above
+//! 2048 before running.
+
+use bytes::Bytes;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use monocoque::zmq::{PubSocket, SubSocket};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Identifies which runtime backend this build benchmarks, so compio, tokio, and smol
+// results land under distinct criterion ids instead of overwriting each other.
+const BENCH_BACKEND: &str = if cfg!(feature = "runtime-tokio") {
+    "tokio"
+} else if cfg!(feature = "runtime-smol") {
+    "smol"
+} else {
+    "compio"
+};
+
+const SUBSCRIBERS: usize = 1000;
+const WORKER_COUNTS: &[usize] = &[1, 4];
+const CLIENT_THREADS: usize = 4;
+const MESSAGE_COUNT: usize = 100;
+const MESSAGE_SIZE: usize = 64;
+const WARMUP_MSGS: usize = 20;
+
+/// Time the delivery of `iters * MESSAGE_COUNT` messages to every subscriber
+/// of a PUB with `workers` shards. Returns the slowest subscriber's window.
+fn run_sharded_fanout(workers: usize, iters: u64) -> Duration {
+    let target = iters as usize * MESSAGE_COUNT;
+    let payload = Bytes::from(vec![0u8; MESSAGE_SIZE]);
+
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (stop_tx, stop_rx) = mpsc::channel::<()>();
+
+    let publisher = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let mut socket = PubSocket::bind_sharded("127.0.0.1:0", workers)
+                    .await
+                    .unwrap();
+                addr_tx.send(socket.local_addr().unwrap()).unwrap();
+                for _ in 0..SUBSCRIBERS {
+                    socket.accept_subscriber().await.unwrap();
+                }
+                while stop_rx.try_recv().is_err() {
+                    socket
+                        .send_frames(std::slice::from_ref(&payload))
+                        .await
+                        .ok();
+                }
+                socket.close().await.unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+    let mut clients = Vec::with_capacity(CLIENT_THREADS);
+    for _ in 0..CLIENT_THREADS {
+        clients.push(thread::spawn(move || {
+            monocoque::rt::LocalRuntime::new()
+                .unwrap()
+                .block_on(async move {
+                    let mut tasks = Vec::new();
+                    for _ in 0..SUBSCRIBERS / CLIENT_THREADS {
+                        let mut sub = SubSocket::connect(&format!("tcp://{addr}")).await.unwrap();
+                        tasks.push(monocoque::rt::spawn(async move {
+                            recv_n(&mut sub, WARMUP_MSGS).await;
+                            let start = Instant::now();
+                            recv_n(&mut sub, target).await;
+                            start.elapsed()
+                        }));
+                    }
+                    let mut slowest = Duration::ZERO;
+                    for task in tasks {
+                        slowest = slowest.max(monocoque::rt::join(task).await);
+                    }
+                    slowest
+                })
+        }));
+    }
+
+    let elapsed = clients
+        .into_iter()
+        .map(|c| c.join().unwrap())
+        .max()
+        .unwrap_or_default();
+    let _ = stop_tx.send(());
+    publisher.join().unwrap();
+    elapsed
+}
+
+/// Receive exactly `n` messages, returning early only on disconnect.
+async fn recv_n(sub: &mut SubSocket, n: usize) {
+    let mut count = 0;
+    while count < n {
+        match sub.recv().await {
+            Ok(Some(_)) => count += 1,
+            Ok(None) | Err(_) => return,
+        }
+    }
+}
+
+fn monocoque_pub_sharded(c: &mut Criterion) {
+    monocoque::dev_tracing::init_tracing();
+    let mut group = c.benchmark_group(format!("pub_sharded/monocoque-{BENCH_BACKEND}"));
+    group.throughput(Throughput::Elements((MESSAGE_COUNT * SUBSCRIBERS) as u64));
+    for &workers in WORKER_COUNTS {
+        group.bench_with_input(
+            BenchmarkId::new("workers", workers),
+            &workers,
+            |b, &workers| {
+                b.iter_custom(|iters| run_sharded_fanout(workers, iters));
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = benches;
+    // Every sample connects SUBSCRIBERS fresh subscribers, so keep the sample
+    // count low to bound total wall time.
+    config = Criterion::default()
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(10))
+        .sample_size(10);
+    targets = monocoque_pub_sharded
+);
+criterion_main!(benches);
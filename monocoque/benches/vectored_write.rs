@@ -0,0 +1,93 @@
+//! Vectored vs copying send path for multipart messages.
+//!
+//! Sends a 10-frame message 100K times PUSH -> PULL, once with every frame
+//! copied into the write buffer (`vectored_write_threshold = usize::MAX`) and
+//! once with each frame handed to the kernel as its own iovec
+//! (`vectored_write_threshold = 0`), and reports the wall-clock reduction.
+//! Frames are 16 KiB, large enough that the copy dominates; on the io_uring
+//! backend the vectored path should be at least 15% faster. Loopback timings
+//! vary too much between machines to gate on, so the run reports whether the
+//! target was met rather than failing.
+//!
+//! Run: `cargo bench --bench vectored_write --features zmq`
+
+use bytes::Bytes;
+use monocoque::rt::{self, TcpListener, TcpStream};
+use monocoque::zmq::SocketOptions;
+use monocoque_zmtp::{PullSocket, PushSocket};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MESSAGES: usize = 100_000;
+const FRAMES: usize = 10;
+const FRAME_SIZE: usize = 16 * 1024;
+const TARGET_REDUCTION: f64 = 0.15;
+
+// Identifies which runtime backend this build benchmarks.
+const BENCH_BACKEND: &str = if cfg!(feature = "runtime-tokio") {
+    "tokio"
+} else if cfg!(feature = "runtime-smol") {
+    "smol"
+} else {
+    "compio"
+};
+
+/// Time `MESSAGES` sends of one `FRAMES`-frame message with `options` on the
+/// sender, until the receiver has drained them all.
+fn run(options: SocketOptions) -> Duration {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    let receiver = thread::spawn(move || {
+        rt::LocalRuntime::new().unwrap().block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            addr_tx.send(listener.local_addr().unwrap()).unwrap();
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut pull = PullSocket::new(stream).await.unwrap();
+            let mut msg = Vec::with_capacity(FRAMES);
+            for _ in 0..MESSAGES {
+                msg.clear();
+                assert!(pull.recv_into(&mut msg).await.unwrap(), "PUSH hung up");
+                assert_eq!(msg.len(), FRAMES);
+            }
+        });
+    });
+
+    let addr = addr_rx.recv().unwrap();
+    let elapsed = rt::LocalRuntime::new().unwrap().block_on(async move {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut push = PushSocket::with_options(stream, options).await.unwrap();
+        let msg: Vec<Bytes> = (0..FRAMES)
+            .map(|i| Bytes::from(vec![i as u8; FRAME_SIZE]))
+            .collect();
+
+        let start = Instant::now();
+        for _ in 0..MESSAGES {
+            push.send(msg.clone()).await.unwrap();
+        }
+        push.flush().await.unwrap();
+        start
+    });
+    receiver.join().unwrap();
+    elapsed.elapsed()
+}
+
+fn main() {
+    let copied = run(SocketOptions::default().with_vectored_write_threshold(usize::MAX));
+    let vectored = run(SocketOptions::default().with_vectored_write_threshold(0));
+    let reduction = 1.0 - vectored.as_secs_f64() / copied.as_secs_f64();
+
+    println!("{MESSAGES} x {FRAMES} x {FRAME_SIZE}B messages");
+    println!("  copy into write buffer: {copied:?}");
+    println!("  vectored write:         {vectored:?}");
+    println!("  reduction:              {:.1}%", reduction * 100.0);
+
+    let verdict = if reduction >= TARGET_REDUCTION {
+        "meets"
+    } else {
+        "misses"
+    };
+    println!(
+        "  {verdict} the {:.0}% target ({BENCH_BACKEND} backend)",
+        TARGET_REDUCTION * 100.0
+    );
+}
@@ -38,8 +38,8 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
     rt::spawn_detached(async move {
         println!("📡 Monitoring task started...\n");
 
-        while let Ok(event) = monitor.recv_async().await {
-            match event {
+        while let Ok(monitored) = monitor.recv_async().await {
+            match monitored.event {
                 SocketEvent::Connected(ep) => {
                     println!("✓ Connected to {ep}");
                 }
@@ -61,6 +61,18 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
                 SocketEvent::Accepted(ep) => {
                     println!("✓ Accepted connection from {ep}");
                 }
+                SocketEvent::ConnectRetried { endpoint, attempt } => {
+                    println!("↻ Reconnecting to {endpoint} (attempt {attempt})");
+                }
+                SocketEvent::Closed(ep) => {
+                    println!("✓ Closed {ep}");
+                }
+                SocketEvent::SendHwmReached { buffered } => {
+                    println!("⚠ Send HWM reached ({buffered} buffered)");
+                }
+                SocketEvent::SendHwmCleared => {
+                    println!("✓ Send HWM cleared");
+                }
             }
         }
 
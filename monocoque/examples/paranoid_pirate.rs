@@ -184,7 +184,8 @@ async fn run_broker() -> std::io::Result<()> {
     monocoque::zmq::proxy::proxy(
         &mut frontend,
         &mut backend,
-        Option::<&mut DealerSocket>::None,
+        None,
+        monocoque::zmq::proxy::ProxyOptions::default(),
     )
     .await?;
 
@@ -13,7 +13,7 @@
 
 use bytes::Bytes;
 use monocoque::rt::{self, LocalRuntime};
-use monocoque::zmq::proxy::proxy;
+use monocoque::zmq::proxy::{ProxyOptions, proxy};
 use monocoque::zmq::{DealerSocket, ReqSocket, RouterSocket};
 use std::time::{Duration, Instant};
 use tracing::{error, info, warn};
@@ -131,12 +131,7 @@ async fn broker() -> std::io::Result<()> {
 
     // ZeroMQ proxy - now uses futures::select! internally!
     // Forwards messages bidirectionally: frontend ←→ backend
-    proxy(
-        &mut frontend,
-        &mut backend,
-        Option::<&mut DealerSocket>::None,
-    )
-    .await?;
+    proxy(&mut frontend, &mut backend, None, ProxyOptions::default()).await?;
 
     Ok(())
 }
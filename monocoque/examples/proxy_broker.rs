@@ -25,7 +25,7 @@
 //! - REP workers connect to 5556 and process requests
 
 use monocoque::rt::LocalRuntime;
-use monocoque::zmq::proxy::proxy;
+use monocoque::zmq::proxy::{ProxyOptions, proxy};
 use monocoque::zmq::{DealerSocket, RouterSocket};
 
 fn main() -> std::io::Result<()> {
@@ -56,12 +56,7 @@ async fn async_main() -> std::io::Result<()> {
     println!("\n📡 Proxy running... Press Ctrl+C to stop\n");
 
     // Run the proxy (forwards requests and replies bidirectionally)
-    proxy(
-        &mut frontend,
-        &mut backend,
-        Option::<&mut RouterSocket>::None,
-    )
-    .await?;
+    proxy(&mut frontend, &mut backend, None, ProxyOptions::default()).await?;
 
     Ok(())
 }
@@ -17,7 +17,7 @@
 
 use bytes::Bytes;
 use monocoque::rt::{self, LocalRuntime};
-use monocoque::zmq::proxy::proxy_steerable;
+use monocoque::zmq::proxy::{ProxyOptions, proxy_steerable};
 use monocoque::zmq::{DealerSocket, ReqSocket, RouterSocket};
 use monocoque_zmtp::pair::PairSocket;
 use std::time::Duration;
@@ -119,8 +119,9 @@ async fn broker() -> std::io::Result<()> {
     proxy_steerable(
         &mut frontend,
         &mut backend,
-        Option::<&mut DealerSocket>::None,
+        None,
         &mut control,
+        ProxyOptions::default(),
     )
     .await?;
 
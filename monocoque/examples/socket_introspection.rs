@@ -94,7 +94,7 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
     let current_hwm = dealer.options().send_hwm;
     println!("[Dealer] Current send HWM: {current_hwm}");
 
-    dealer.options_mut().send_hwm = 500;
+    dealer.update_options(|o| o.send_hwm = 500)?;
     println!("[Dealer] New send HWM: {}", dealer.options().send_hwm);
 
     // 7. Multipart message check
@@ -108,7 +108,7 @@ async fn async_main() -> Result<(), Box<dyn std::error::Error>> {
     println!("  • last_endpoint() shows where the socket connected/bound");
     println!("  • TCP keepalive options enable connection monitoring");
     println!("  • REQ modes control request-reply behavior");
-    println!("  • Options can be modified at runtime via options_mut()");
+    println!("  • Options can be modified at runtime via update_options()");
 
     Ok(())
 }
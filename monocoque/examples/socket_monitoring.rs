@@ -4,7 +4,7 @@
 //! connection events like connects, disconnects, binds, etc.
 
 use monocoque::rt::{self, LocalRuntime};
-use monocoque::zmq::{SocketEvent, SocketMonitor};
+use monocoque::zmq::{MonitoredEvent, SocketEvent, SocketMonitor};
 use monocoque_core::endpoint::Endpoint;
 use std::time::Duration;
 
@@ -70,8 +70,8 @@ fn create_example_monitor() -> SocketMonitor {
 
     // Simulate some events
     let tcp_ep = Endpoint::parse("tcp://127.0.0.1:5555").unwrap();
-    let _ = sender.send(SocketEvent::Connected(tcp_ep.clone()));
-    let _ = sender.send(SocketEvent::Disconnected(tcp_ep));
+    let _ = sender.send(MonitoredEvent::new(SocketEvent::Connected(tcp_ep.clone())));
+    let _ = sender.send(MonitoredEvent::new(SocketEvent::Disconnected(tcp_ep)));
 
     receiver
 }
@@ -13,3 +13,22 @@ pub fn init_tracing() {
             .try_init();
     }
 }
+
+/// Test helper: install a subscriber that prints to the test harness output.
+///
+/// Unlike [`init_tracing`] it is always on, filtering by `RUST_LOG` or at
+/// `debug` when that is unset, and it also prints each closing span with its
+/// fields and busy/idle time, so the `handshake`, `send_message` and
+/// `recv_message` spans show up alongside the log lines. Captured output is
+/// only shown for failing tests. Calling it again is a no-op.
+pub fn init_test_subscriber() {
+    use tracing_subscriber::EnvFilter;
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("debug"));
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_test_writer()
+        .try_init();
+}
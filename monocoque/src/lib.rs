@@ -99,8 +99,12 @@
 
 // Re-export core types
 pub use bytes::Bytes;
+pub use monocoque_core::error::Error;
 pub use monocoque_core::options::SocketOptions;
-pub use monocoque_core::reconnect::{ReconnectError, ReconnectState};
+pub use monocoque_core::reconnect::{
+    ConnectAttempt, ConnectHistory, ConnectOutcome, ConstantPolicy, ExponentialPolicy,
+    FibonacciPolicy, ReconnectError, ReconnectPolicy, ReconnectState,
+};
 pub use monocoque_core::socket_type::SocketType;
 
 /// Runtime-agnostic networking types (TCP/Unix streams, listeners).
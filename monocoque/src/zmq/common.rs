@@ -2,17 +2,6 @@
 
 use std::io;
 
-/// Convert a channel send error to an IO error.
-///
-/// Used by all socket types to convert flume channel errors
-/// into standard IO errors with BrokenPipe kind.
-pub fn channel_to_io_error<T, E>(result: Result<T, E>) -> io::Result<T>
-where
-    E: std::error::Error + Send + Sync + 'static,
-{
-    result.map_err(|e| io::Error::new(io::ErrorKind::BrokenPipe, e))
-}
-
 /// Parse a TCP endpoint string into a `SocketAddr`.
 ///
 /// Accepts both URL form (`tcp://127.0.0.1:5555`) and bare form (`127.0.0.1:5555`).
@@ -1,9 +1,15 @@
 //! DEALER socket implementation.
 
-use super::common::{channel_to_io_error, parse_tcp_endpoint};
+use super::common::parse_tcp_endpoint;
+use super::{FrameReader, MultiDealerSocket};
 use bytes::Bytes;
-use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::error::{Error, TrySendError};
+use monocoque_core::monitor::{
+    HwmWatch, SocketEvent, SocketEventSender, SocketMonitor, create_monitor,
+};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
+use monocoque_zmtp::codec::classify_error;
 use monocoque_zmtp::dealer::DealerSocket as InternalDealer;
 use std::io;
 
@@ -46,6 +52,7 @@ where
 {
     inner: InternalDealer<S>,
     monitor: Option<SocketEventSender>,
+    hwm: HwmWatch,
 }
 
 impl DealerSocket {
@@ -81,16 +88,18 @@ impl DealerSocket {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connect(endpoint: &str) -> io::Result<Self> {
+    pub async fn connect(endpoint: &str) -> Result<Self, Error> {
         let addr = parse_tcp_endpoint(endpoint)?;
         let inner = InternalDealer::connect_with_options(
             addr,
             monocoque_core::options::SocketOptions::default(),
         )
-        .await?;
+        .await
+        .map_err(classify_error)?;
         let sock = Self {
             inner,
             monitor: None,
+            hwm: HwmWatch::default(),
         };
         sock.emit_event(SocketEvent::Connected(
             monocoque_core::endpoint::Endpoint::Tcp(addr),
@@ -119,12 +128,15 @@ impl DealerSocket {
     pub async fn connect_with_options(
         endpoint: &str,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         let addr = parse_tcp_endpoint(endpoint)?;
-        let inner = InternalDealer::connect_with_options(addr, options).await?;
+        let inner = InternalDealer::connect_with_options(addr, options)
+            .await
+            .map_err(classify_error)?;
         let sock = Self {
             inner,
             monitor: None,
+            hwm: HwmWatch::default(),
         };
         sock.emit_event(SocketEvent::Connected(
             monocoque_core::endpoint::Endpoint::Tcp(addr),
@@ -150,7 +162,7 @@ impl DealerSocket {
     #[cfg(unix)]
     pub async fn connect_ipc(
         path: &str,
-    ) -> io::Result<DealerSocket<monocoque_core::rt::UnixStream>> {
+    ) -> Result<DealerSocket<monocoque_core::rt::UnixStream>, Error> {
         use std::path::PathBuf;
 
         let clean_path = path.strip_prefix("ipc://").unwrap_or(path);
@@ -164,6 +176,37 @@ impl DealerSocket {
         Ok(sock)
     }
 
+    /// Connect to several servers and load-balance across them.
+    ///
+    /// Returns a [`MultiDealerSocket`] that sends round-robin over the
+    /// connections and receives from whichever server replies first. Servers
+    /// that are down are redialed in the background; the call only fails when
+    /// none is reachable. More servers can be added later with
+    /// [`add_connection`](MultiDealerSocket::add_connection).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::DealerSocket;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let socket = DealerSocket::connect_multiple(&[
+    ///     "tcp://127.0.0.1:5555",
+    ///     "tcp://127.0.0.1:5556",
+    /// ])
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_multiple(endpoints: &[&str]) -> Result<MultiDealerSocket, Error> {
+        MultiDealerSocket::connect(endpoints).await
+    }
+
+    /// Same as [`connect_multiple`](Self::connect_multiple).
+    pub async fn connect_many(endpoints: &[&str]) -> Result<MultiDealerSocket, Error> {
+        Self::connect_multiple(endpoints).await
+    }
+
     /// Bind to an address and accept the first connection.
     ///
     /// This creates a server-side DEALER socket that accepts incoming connections.
@@ -200,7 +243,7 @@ impl DealerSocket {
     /// ```
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
-    ) -> io::Result<(monocoque_core::rt::TcpListener, Self)> {
+    ) -> Result<(monocoque_core::rt::TcpListener, Self), Error> {
         let listener = monocoque_core::rt::TcpListener::bind(addr).await?;
         let (stream, _) = listener.accept().await?;
         let socket = Self::from_tcp(stream).await?;
@@ -227,10 +270,13 @@ impl DealerSocket {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalDealer::from_tcp(stream).await?,
+            inner: InternalDealer::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
@@ -274,10 +320,13 @@ impl DealerSocket {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalDealer::from_tcp_with_options(stream, options).await?,
+            inner: InternalDealer::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
@@ -304,15 +353,65 @@ impl DealerSocket {
     pub async fn with_options<Stream>(
         stream: Stream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<DealerSocket<Stream>>
+    ) -> Result<DealerSocket<Stream>, Error>
     where
         Stream: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
     {
         Ok(DealerSocket {
-            inner: InternalDealer::with_options(stream, options).await?,
+            inner: InternalDealer::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
+
+    /// Reconnect to the stored endpoint after the connection was lost or
+    /// the socket was poisoned by cancelled I/O.
+    ///
+    /// Makes one attempt (after the reconnect backoff delay). On success
+    /// the poisoned flag and all buffered state are cleared. Sockets not
+    /// created with `connect` have no endpoint to dial and fail with
+    /// `Unsupported`.
+    ///
+    /// Emits `ConnectRetried` before the attempt and `Connected` or
+    /// `ConnectFailed` after it, if monitoring is enabled.
+    pub async fn reconnect(&mut self) -> Result<(), Error> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        if let Some(endpoint) = &endpoint {
+            self.emit_event(SocketEvent::ConnectRetried {
+                endpoint: endpoint.clone(),
+                attempt: self.inner.reconnect_attempt() + 1,
+            });
+        }
+        let result = self.inner.try_reconnect().await.map_err(classify_error);
+        if let Some(endpoint) = endpoint {
+            match &result {
+                Ok(()) => self.emit_connected(endpoint),
+                Err(e) => self.emit_event(SocketEvent::ConnectFailed {
+                    endpoint,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        result
+    }
+
+    /// Flush buffered messages, reconnecting if the connection is lost.
+    ///
+    /// With the `resend_on_reconnect` option, every message buffered since
+    /// the last successful flush is sent again on the new connection. Set
+    /// `dedupe_window` on both sides so the peer drops the copies it had
+    /// already received.
+    pub async fn flush_with_reconnect(&mut self) -> Result<(), Error> {
+        let result = self
+            .inner
+            .flush_with_reconnect()
+            .await
+            .map_err(classify_error);
+        self.watch_hwm();
+        result
+    }
 }
 
 // Generic impl - works with any stream type
@@ -323,7 +422,9 @@ where
     /// Enable monitoring for this socket.
     ///
     /// Returns a receiver for socket lifecycle events. Once enabled, the socket
-    /// will emit events like Connected, Disconnected, etc.
+    /// will emit events like Connected, Disconnected, etc. If the socket is
+    /// already connected, a `Connected` event for the current peer is emitted
+    /// immediately so the stream always starts from a known state.
     ///
     /// # Example
     ///
@@ -346,6 +447,11 @@ where
     pub fn monitor(&mut self) -> SocketMonitor {
         let (sender, receiver) = create_monitor();
         self.monitor = Some(sender);
+        if self.inner.is_connected()
+            && let Some(endpoint) = self.inner.last_endpoint().cloned()
+        {
+            self.emit_connected(endpoint);
+        }
         receiver
     }
 
@@ -356,6 +462,26 @@ where
         }
     }
 
+    /// Emit `Connected` for `endpoint` carrying the peer's handshake metadata.
+    fn emit_connected(&self, endpoint: monocoque_core::endpoint::Endpoint) {
+        if let Some(monitor) = &self.monitor {
+            monocoque_core::monitor::emit_with_metadata(
+                monitor,
+                SocketEvent::Connected(endpoint),
+                self.inner.peer_metadata().clone(),
+            );
+        }
+    }
+
+    /// Emit `SendHwmReached` / `SendHwmCleared` when the buffered message
+    /// count crosses `send_hwm`.
+    fn watch_hwm(&mut self) {
+        let hwm = self.inner.options().send_hwm;
+        if let Some(event) = self.hwm.observe(self.inner.buffered_messages(), hwm) {
+            self.emit_event(event);
+        }
+    }
+
     /// Send a multipart message.
     ///
     /// Messages are sent asynchronously - this returns immediately after
@@ -378,8 +504,8 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        channel_to_io_error(self.inner.send(msg).await)
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
     }
 
     /// Send a message to the internal buffer without flushing.
@@ -402,15 +528,31 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub fn send_buffered(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        channel_to_io_error(self.inner.send_buffered(msg))
+    pub fn send_buffered(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        let result = self.inner.send_buffered(msg).map_err(classify_error);
+        self.watch_hwm();
+        result
+    }
+
+    /// Queue a message without ever suspending; it goes out with the next
+    /// [`flush()`](Self::flush).
+    ///
+    /// See [`monocoque_zmtp::DealerSocket::try_send`] for the error cases;
+    /// [`TrySendError::Full`] and [`TrySendError::WouldBlock`] hand the
+    /// message back.
+    pub fn try_send(&mut self, msg: Vec<Bytes>) -> Result<(), TrySendError> {
+        let result = self.inner.try_send(msg);
+        self.watch_hwm();
+        result
     }
 
     /// Flush all buffered messages to the network.
     ///
     /// Sends all messages buffered by `send_buffered()` in a single I/O operation.
-    pub async fn flush(&mut self) -> io::Result<()> {
-        channel_to_io_error(self.inner.flush().await)
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        let result = self.inner.flush().await.map_err(classify_error);
+        self.watch_hwm();
+        result
     }
 
     /// Send multiple messages in a single batch (convenience method).
@@ -433,8 +575,14 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn send_batch(&mut self, messages: &[Vec<Bytes>]) -> io::Result<()> {
-        channel_to_io_error(self.inner.send_batch(messages).await)
+    pub async fn send_batch(&mut self, messages: &[Vec<Bytes>]) -> Result<(), Error> {
+        let result = self
+            .inner
+            .send_batch(messages)
+            .await
+            .map_err(classify_error);
+        self.watch_hwm();
+        result
     }
 
     /// Get the number of bytes currently buffered.
@@ -443,9 +591,12 @@ where
         self.inner.buffered_bytes()
     }
 
-    /// Get the socket type.
-    ///
-    /// # ZeroMQ Compatibility
+    /// Get the current capacities of the socket's userspace buffers.
+    #[inline]
+    pub fn buffer_stats(&self) -> monocoque_core::config::BufferStats {
+        self.inner.buffer_stats()
+    }
+
     /// Get current socket events (read/write readiness).
     ///
     /// Returns a bitmask:
@@ -474,8 +625,89 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        self.inner.recv().await
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        let msg = self.inner.recv().await.map_err(classify_error)?;
+        if msg.is_none()
+            && let Some(endpoint) = self.inner.last_endpoint().cloned()
+        {
+            self.emit_event(SocketEvent::Disconnected(endpoint));
+        }
+        Ok(msg)
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<bytes::Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        }))
+    }
+
+    /// Receive a multipart message into `msg`, reusing its allocation.
+    ///
+    /// `msg` is cleared and then filled with the message's frames. Returns
+    /// `false` if the connection is closed. Use this instead of
+    /// [`recv`](Self::recv) in hot loops to avoid allocating a `Vec` per
+    /// message; the frames are zero-copy either way.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::DealerSocket;
+    /// # async fn example(mut socket: DealerSocket) -> std::io::Result<()> {
+    /// let mut msg = Vec::new();
+    /// while socket.recv_into(&mut msg).await? {
+    ///     println!("Received {} parts", msg.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn recv_into(&mut self, msg: &mut Vec<Bytes>) -> Result<bool, Error> {
+        let received = self.inner.recv_into(msg).await.map_err(classify_error)?;
+        if !received && let Some(endpoint) = self.inner.last_endpoint().cloned() {
+            self.emit_event(SocketEvent::Disconnected(endpoint));
+        }
+        Ok(received)
+    }
+
+    /// Receive the next frame in chunks, without buffering frames larger than
+    /// the `stream_threshold` option.
+    ///
+    /// Use this once `recv()` fails with `ZmtpError::StreamingRequired`; see
+    /// [`FrameReader`] for the chunk API.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::DealerSocket;
+    /// # async fn example(mut socket: DealerSocket) -> std::io::Result<()> {
+    /// if let Some(mut frame) = socket.recv_frame_streaming().await? {
+    ///     let mut received = 0;
+    ///     while let Some(chunk) = frame.next_chunk().await? {
+    ///         received += chunk.len() as u64;
+    ///     }
+    ///     assert_eq!(received, frame.total_len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn recv_frame_streaming(&mut self) -> Result<Option<FrameReader<'_, S>>, Error> {
+        self.inner
+            .recv_frame_streaming()
+            .await
+            .map_err(classify_error)
     }
 }
 
@@ -519,12 +751,125 @@ where
         self.inner.has_more()
     }
 
-    /// Get mutable access to socket options.
+    /// Check if the socket is currently connected.
     ///
-    /// Allows runtime modification of socket behavior.
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::DealerSocket;
+    /// # fn check(mut socket: DealerSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` option.
+    #[inline]
+    pub const fn duplicates_dropped(&self) -> u64 {
+        self.inner.duplicates_dropped()
+    }
+
+    /// The latest connection attempts (initial connect and reconnects),
+    /// oldest first, with their timestamps and errors.
+    #[inline]
+    pub const fn connect_history(&self) -> &crate::ConnectHistory {
+        self.inner.connect_history()
+    }
+
+    /// Multi-line dump of the socket's endpoint, state, non-default options,
+    /// buffer usage and connection history, for logs and bug reports.
+    pub fn diagnostics(&self) -> String {
+        self.inner.diagnostics()
+    }
+
+    /// The same state as [`diagnostics`](Self::diagnostics) as a structured
+    /// snapshot, serializable with the `serde` feature.
+    pub fn snapshot(&self) -> monocoque_zmtp::DiagnosticsSnapshot {
+        self.inner.snapshot()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::DealerSocket;
+    /// # async fn example(socket: DealerSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut monocoque_core::options::SocketOptions {
-        self.inner.options_mut()
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 
     /// Get immutable access to socket options.
@@ -538,10 +883,11 @@ where
 #[cfg(unix)]
 impl DealerSocket<monocoque_core::rt::UnixStream> {
     /// Create a DEALER socket from an existing Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalDealer::new(stream).await?,
+            inner: InternalDealer::new(stream).await.map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
@@ -549,10 +895,52 @@ impl DealerSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalDealer::with_options(stream, options).await?,
+            inner: InternalDealer::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
+        })
+    }
+}
+
+#[cfg(feature = "tls")]
+impl DealerSocket<monocoque_zmtp::tls::TlsStream<TcpStream>> {
+    /// Connect to a TCP endpoint over TLS and create a DEALER socket.
+    ///
+    /// The server certificate is verified before any ZMTP bytes are sent; a
+    /// rejected certificate fails with
+    /// [`TlsError::CertificateRejected`](monocoque_zmtp::tls::TlsError::CertificateRejected)
+    /// inside the returned `io::Error`. The ZMTP mechanism (NULL, PLAIN or
+    /// CURVE) still comes from `options`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(roots: monocoque::zmq::tls::rustls::RootCertStore) -> std::io::Result<()> {
+    /// use monocoque::zmq::tls::TlsConfig;
+    /// use monocoque::zmq::{DealerSocket, SocketOptions};
+    ///
+    /// let tls = TlsConfig::with_roots(roots);
+    /// let mut socket =
+    ///     DealerSocket::connect_tls("tcp://broker.example.com:5555", &tls, SocketOptions::default())
+    ///         .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_tls(
+        endpoint: &str,
+        tls: &monocoque_zmtp::tls::TlsConfig,
+        options: monocoque_core::options::SocketOptions,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalDealer::connect_tls(endpoint, tls, options)
+                .await
+                .map_err(classify_error)?,
+            monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 }
@@ -568,7 +956,7 @@ impl monocoque_zmtp::proxy::ProxySocket for DealerSocket<TcpStream> {
         'life0: 'async_trait,
         Self: 'async_trait,
     {
-        Box::pin(async move { self.recv().await })
+        Box::pin(async move { Ok(self.recv().await?) })
     }
 
     fn send_multipart<'life0, 'async_trait>(
@@ -579,10 +967,20 @@ impl monocoque_zmtp::proxy::ProxySocket for DealerSocket<TcpStream> {
         'life0: 'async_trait,
         Self: 'async_trait,
     {
-        Box::pin(async move { self.send(msg).await })
+        Box::pin(async move { Ok(self.send(msg).await?) })
     }
 
     fn socket_desc(&self) -> &'static str {
         "DEALER"
     }
+    fn send_multipart_batch<'life0, 'async_trait>(
+        &'life0 mut self,
+        msgs: Vec<Vec<Bytes>>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send_batch(&msgs).await?) })
+    }
 }
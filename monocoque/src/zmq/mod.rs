@@ -6,6 +6,7 @@
 //!
 //! - [`DealerSocket`] - Asynchronous request-reply client (load-balanced)
 //! - [`RouterSocket`] - Identity-based routing server
+//! - [`RouterHubSocket`] - ROUTER serving every peer accepted on a listener
 //! - [`ReqSocket`] - Synchronous request-reply client (strict alternation)
 //! - [`RepSocket`] - Synchronous reply server (stateful envelope tracking)
 //! - [`PubSocket`] - Publisher (broadcast to subscribers)
@@ -24,6 +25,7 @@
 //! - **Endpoint Parsing**: Use `Endpoint::parse("tcp://...")` or `Endpoint::parse("ipc://...")`
 //! - **Socket Monitoring**: Subscribe to connection events via `socket.monitor()`
 //! - **IPC Transport**: Unix domain sockets for low-latency local communication (Unix only)
+//! - **Patterns**: [`patterns::jobqueue`] builds an acknowledged work queue on DEALER and ROUTER
 //!
 //! # Quick Start
 //!
@@ -61,6 +63,9 @@
 
 mod common;
 mod dealer;
+mod multi_dealer;
+mod pair;
+pub mod patterns;
 mod publisher;
 mod pull;
 mod pull_fanin;
@@ -69,18 +74,35 @@ mod push_fanout;
 mod rep;
 mod req;
 mod router;
+mod router_hub;
+mod stream;
 mod subscriber;
+mod xpub;
+mod xsub;
 
 // Re-export socket types
 pub use dealer::DealerSocket;
 pub use monocoque_core::config::BufferConfig;
 pub use monocoque_core::endpoint::{Endpoint, EndpointError};
-pub use monocoque_core::monitor::{SocketEvent, SocketMonitor};
-pub use monocoque_core::options::SocketOptions;
+pub use monocoque_core::error::{Error, TrySendError};
+pub use monocoque_core::monitor::{MonitoredEvent, SocketEvent, SocketMonitor};
+pub use monocoque_core::options::{
+    HwmPolicy, OptionDiff, OptionTiming, OptionUpdateError, SocketOptions,
+};
 pub use monocoque_core::socket_type::SocketType;
-pub use monocoque_core::subscription::{Subscription, SubscriptionEvent, SubscriptionTrie};
+pub use monocoque_core::subscription::{
+    Subscription, SubscriptionEvent, SubscriptionFilter, SubscriptionTrie, TopicMatch,
+    WildcardPattern,
+};
+pub use monocoque_zmtp::codec::{non_zmtp_probes, protocol_violations};
 pub use monocoque_zmtp::proxy;
-pub use monocoque_zmtp::{PairSocket, StreamSocket, XPubSocket, XSubSocket};
+pub use monocoque_zmtp::security::Principal;
+pub use monocoque_zmtp::{
+    CloseReason, DiagnosticsSnapshot, FlushOutcome, FrameReader, HandshakeResult, MessageMeta,
+    PeerMetadata, PeerSnapshot, ProbeKind, PubStats, USER_ID_PROPERTY,
+};
+pub use multi_dealer::MultiDealerSocket;
+pub use pair::PairSocket;
 pub use publisher::PubSocket;
 pub use pull::PullSocket;
 pub use pull_fanin::PullFanIn;
@@ -89,11 +111,18 @@ pub use push_fanout::PushFanOut;
 pub use rep::RepSocket;
 pub use req::ReqSocket;
 pub use router::RouterSocket;
+pub use router_hub::RouterHubSocket;
+pub use stream::StreamSocket;
 pub use subscriber::SubSocket;
+pub use xpub::XPubSocket;
+pub use xsub::XSubSocket;
 
 #[cfg(unix)]
 pub use monocoque_core::ipc;
 
+#[cfg(feature = "tls")]
+pub use monocoque_zmtp::tls;
+
 /// Convenient imports for ZeroMQ protocol.
 ///
 /// # Example
@@ -102,18 +131,22 @@ pub use monocoque_core::ipc;
 /// use monocoque::zmq::prelude::*;
 ///
 /// // Now you have:
-/// // - DealerSocket, RouterSocket, ReqSocket, RepSocket
+/// // - DealerSocket, MultiDealerSocket, RouterSocket, RouterHubSocket, ReqSocket, RepSocket
 /// // - PubSocket, SubSocket, XPubSocket, XSubSocket
 /// // - PushSocket, PullSocket, PushFanOut, PullFanIn, PairSocket
 /// // - Bytes for zero-copy messages
 /// // - BufferConfig, SocketOptions, SocketType for configuration
 /// ```
 pub mod prelude {
-    pub use super::proxy::{ProxyCommand, ProxySocket, proxy, proxy_steerable};
+    pub use super::proxy::{
+        ProxyCommand, ProxyOptions, ProxySocket, ProxyStats, RateLimit, RecvOutcome, proxy,
+        proxy_steerable,
+    };
     pub use super::{
-        BufferConfig, DealerSocket, PairSocket, PubSocket, PullFanIn, PullSocket, PushFanOut,
-        PushSocket, RepSocket, ReqSocket, RouterSocket, SocketOptions, StreamSocket, SubSocket,
-        Subscription, SubscriptionEvent, SubscriptionTrie, XPubSocket, XSubSocket,
+        BufferConfig, DealerSocket, HandshakeResult, MultiDealerSocket, OptionUpdateError,
+        PairSocket, PubSocket, PullFanIn, PullSocket, PushFanOut, PushSocket, RepSocket, ReqSocket,
+        RouterHubSocket, RouterSocket, SocketOptions, StreamSocket, SubSocket, Subscription,
+        SubscriptionEvent, SubscriptionTrie, WildcardPattern, XPubSocket, XSubSocket,
     };
     pub use bytes::Bytes;
     pub use monocoque_core::socket_type::SocketType;
@@ -0,0 +1,252 @@
+//! DEALER socket connected to several servers.
+
+use super::common::parse_tcp_endpoint;
+use bytes::Bytes;
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::error::Error;
+use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_zmtp::codec::classify_error;
+use monocoque_zmtp::multi_dealer::MultiDealerSocket as InternalMultiDealer;
+use std::io;
+
+/// A DEALER socket that load-balances across several ROUTER/REP servers.
+///
+/// `send` hands each message to the next live connection in round-robin
+/// order; `recv` returns replies from whichever server answers first. A
+/// server that is down is redialed with the `reconnect_ivl` backoff while the
+/// others carry on, so `send` keeps working as long as one is up. Create
+/// one with [`DealerSocket::connect_multiple`](crate::zmq::DealerSocket::connect_multiple)
+/// or [`connect`](Self::connect).
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use monocoque::zmq::DealerSocket;
+/// use bytes::Bytes;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let mut socket = DealerSocket::connect_multiple(&[
+///     "tcp://127.0.0.1:5555",
+///     "tcp://127.0.0.1:5556",
+/// ])
+/// .await?;
+/// socket.add_connection("tcp://127.0.0.1:5557").await?;
+///
+/// for i in 0..3 {
+///     socket.send(vec![Bytes::from(format!("job-{i}"))]).await?;
+/// }
+/// while let Some(reply) = socket.recv().await? {
+///     println!("Got reply: {:?}", reply);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct MultiDealerSocket {
+    inner: InternalMultiDealer,
+    monitor: Option<SocketEventSender>,
+}
+
+impl MultiDealerSocket {
+    /// Connect to every endpoint in `endpoints` with default options.
+    ///
+    /// Endpoints are TCP, with or without the `tcp://` prefix.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for an empty list or a malformed endpoint, and
+    /// the first connect or handshake error when no endpoint is reachable.
+    /// Unreachable endpoints are otherwise redialed in the background and
+    /// join the rotation once they answer.
+    pub async fn connect(endpoints: &[&str]) -> Result<Self, Error> {
+        Self::connect_with_options(endpoints, SocketOptions::default()).await
+    }
+
+    /// Connect to every endpoint in `endpoints`, applying `options` to each
+    /// connection.
+    pub async fn connect_with_options(
+        endpoints: &[&str],
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        let addrs = endpoints
+            .iter()
+            .map(|endpoint| parse_tcp_endpoint(endpoint))
+            .collect::<io::Result<Vec<_>>>()?;
+        let inner = InternalMultiDealer::connect_with_options(addrs, options)
+            .await
+            .map_err(classify_error)?;
+        Ok(Self {
+            inner,
+            monitor: None,
+        })
+    }
+
+    /// Connect to one more server and add it to the round-robin rotation.
+    pub async fn add_connection(&mut self, endpoint: &str) -> Result<(), Error> {
+        let addr = parse_tcp_endpoint(endpoint)?;
+        let endpoint = self
+            .inner
+            .add_connection(addr)
+            .await
+            .map_err(classify_error)?;
+        self.emit_event(SocketEvent::Connected(endpoint));
+        Ok(())
+    }
+
+    /// Send a multipart message to the next live server in round-robin order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotConnected` when every connection has gone down.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Receive the next message from any server.
+    ///
+    /// Servers that drop their connection meanwhile are redialed, as in
+    /// `send`. Returns `Ok(None)` once every connection has closed and every
+    /// redial has used up `max_reconnect_attempts`; with unlimited attempts
+    /// it waits for a server to come back.
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Number of connections still in the rotation.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// True when no connection remains.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Endpoints of the connections that are currently up.
+    pub fn connected_endpoints(&self) -> Vec<Endpoint> {
+        self.inner.connected_endpoints()
+    }
+
+    /// Get a reference to the socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if at least one connection is up.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::MultiDealerSocket;
+    /// # fn check(mut socket: MultiDealerSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let writable = socket.events() & 2 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} writable={writable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().send_hwm,
+    /// );
+    /// socket.update_options(|o| o.send_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Always `false`: `send` only queues the message for a connection's
+    /// writer, so a cancelled call cannot leave a frame half-written.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// The endpoint of the connection started most recently, if any.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub fn last_endpoint(&self) -> Option<&Endpoint> {
+        self.inner.last_endpoint()
+    }
+
+    /// Always `false`: `recv` hands out whole messages.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub const fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// `1` (POLLIN) when a received message is waiting, `2` (POLLOUT) when a
+    /// live connection has room in its send queue.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.inner.events()
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// Connections that are already up keep the options they started with;
+    /// the new values apply to connections added or revived afterwards, and
+    /// `linger` to [`close`](Self::close).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
+    }
+
+    /// Enable monitoring for this socket.
+    ///
+    /// Emits `Connected` for every connection that is already up, then for
+    /// each connection added later.
+    pub fn monitor(&mut self) -> SocketMonitor {
+        let (sender, receiver) = create_monitor();
+        self.monitor = Some(sender);
+        for endpoint in self.inner.connected_endpoints() {
+            self.emit_event(SocketEvent::Connected(endpoint));
+        }
+        receiver
+    }
+
+    /// Close every connection, honoring the `linger` option.
+    ///
+    /// Emits `Closed` for each connection that was still up.
+    pub async fn close(self) -> io::Result<()> {
+        let endpoints = self.inner.connected_endpoints();
+        self.inner.close().await?;
+        if let Some(monitor) = &self.monitor {
+            for endpoint in endpoints {
+                monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+            }
+        }
+        Ok(())
+    }
+
+    fn emit_event(&self, event: SocketEvent) {
+        if let Some(monitor) = &self.monitor {
+            monocoque_core::monitor::emit(monitor, event);
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! PAIR socket implementation.
+//!
+//! PAIR sockets connect exactly two endpoints for exclusive, bidirectional
+//! messaging.
+
+use bytes::Bytes;
+use monocoque_core::error::Error;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::rt::{TcpListener, TcpStream};
+use monocoque_zmtp::InprocStream;
+use monocoque_zmtp::PairSocket as InternalPair;
+use monocoque_zmtp::SocketType;
+use monocoque_zmtp::codec::classify_error;
+use std::io;
+
+/// PAIR socket for exclusive peer-to-peer communication.
+///
+/// Messages go straight to the one connected peer, without routing or
+/// filtering.
+pub struct PairSocket<S = TcpStream>
+where
+    S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
+{
+    inner: InternalPair<S>,
+}
+
+impl PairSocket<TcpStream> {
+    /// Bind to `addr`, accept one connection, and return a ready PAIR socket.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::PairSocket;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (_listener, mut socket) = PairSocket::bind("127.0.0.1:5555").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bind(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+    ) -> Result<(TcpListener, Self), Error> {
+        let listener = TcpListener::bind(addr).await?;
+        let (stream, _) = listener.accept().await?;
+        let socket = Self::from_tcp(stream).await?;
+        Ok((listener, socket))
+    }
+
+    /// Connect to a PAIR peer, storing the endpoint for automatic reconnection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::PairSocket;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut socket = PairSocket::connect("127.0.0.1:5555").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(addr: impl monocoque_core::rt::ToSocketAddrs) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::connect(addr).await.map_err(classify_error)?,
+        })
+    }
+
+    /// Connect with custom options, storing the endpoint for reconnection.
+    pub async fn connect_with_options(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::connect_with_options(addr, options)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Create a PAIR socket from a TCP stream.
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Create a PAIR socket from a TCP stream with custom options.
+    pub async fn from_tcp_with_options(
+        stream: TcpStream,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Try to reconnect to the stored endpoint.
+    pub async fn try_reconnect(&mut self) -> Result<(), Error> {
+        self.inner.try_reconnect().await.map_err(classify_error)
+    }
+
+    /// Receive with automatic reconnection on EOF or network error.
+    pub async fn recv_with_reconnect(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner
+            .recv_with_reconnect()
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Send with automatic reconnection on network error.
+    pub async fn send_with_reconnect(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner
+            .send_with_reconnect(msg)
+            .await
+            .map_err(classify_error)
+    }
+}
+
+impl PairSocket<InprocStream> {
+    /// Bind to an inproc endpoint that one other PAIR socket in this process
+    /// can connect to.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::PairSocket;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let socket = PairSocket::bind_inproc("inproc://my-pair")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_inproc(endpoint: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::bind_inproc(endpoint).map_err(classify_error)?,
+        })
+    }
+
+    /// Bind to an inproc endpoint with custom options.
+    pub fn bind_inproc_with_options(endpoint: &str, options: SocketOptions) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::bind_inproc_with_options(endpoint, options)
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Connect to an inproc endpoint bound with
+    /// [`bind_inproc`](Self::bind_inproc).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::PairSocket;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let socket = PairSocket::connect_inproc("inproc://my-pair")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn connect_inproc(endpoint: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::connect_inproc(endpoint).map_err(classify_error)?,
+        })
+    }
+
+    /// Connect to an inproc endpoint with custom options.
+    pub fn connect_inproc_with_options(
+        endpoint: &str,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::connect_inproc_with_options(endpoint, options)
+                .map_err(classify_error)?,
+        })
+    }
+}
+
+impl<S> PairSocket<S>
+where
+    S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
+{
+    /// Create a PAIR socket from any stream.
+    pub async fn new(stream: S) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::new(stream).await.map_err(classify_error)?,
+        })
+    }
+
+    /// Create a PAIR socket from any stream with custom options.
+    pub async fn with_options(stream: S, options: SocketOptions) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalPair::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Send a multipart message to the peer.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Receive a multipart message.
+    ///
+    /// Returns `None` if the connection is closed.
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    pub async fn close(self) -> io::Result<()> {
+        self.inner.close().await
+    }
+
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PairSocket;
+    /// # fn check(mut socket: PairSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.set_options(options)
+    }
+
+    /// Get the socket type.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_TYPE` (16) option.
+    #[inline]
+    pub const fn socket_type(&self) -> SocketType {
+        SocketType::Pair
+    }
+
+    /// Get the endpoint this socket is connected/bound to, if available.
+    ///
+    /// Returns `None` if the socket was created from a raw stream.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub fn last_endpoint(&self) -> Option<&monocoque_core::endpoint::Endpoint> {
+        self.inner.last_endpoint()
+    }
+
+    /// Check if the last received message has more frames coming.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// Returns a bitmask: `1` (POLLIN) when ready to receive, `2` (POLLOUT)
+    /// when ready to send.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.inner.events()
+    }
+}
+
+// Implement ProxySocket for the high-level PairSocket wrapper, typically the
+// control or capture socket of a proxy.
+impl monocoque_zmtp::proxy::ProxySocket for PairSocket<TcpStream> {
+    fn recv_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+    ) -> ::core::pin::Pin<
+        Box<dyn ::core::future::Future<Output = io::Result<Option<Vec<Bytes>>>> + 'async_trait>,
+    >
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.recv().await?) })
+    }
+
+    fn send_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+        msg: Vec<Bytes>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send(msg).await?) })
+    }
+
+    fn socket_desc(&self) -> &'static str {
+        "PAIR"
+    }
+}
@@ -0,0 +1,692 @@
+//! Acknowledged work queue: producers, a broker, and a pool of workers.
+//!
+//! ```text
+//! [JobProducer] --JOB-->  +-----------+  --JOB--> [JobWorker]
+//!   (DEALER)    <--ACK--  | JobBroker |  <--ACK--  (DEALER)
+//! [JobProducer] --JOB-->  |  ROUTER / |  --JOB--> [JobWorker]
+//!               <--ACK--  |  ROUTER   |  <--ACK--
+//!                         +-----------+
+//! ```
+//!
+//! A [`JobProducer`] submits jobs to the broker's frontend and keeps each one
+//! until the broker acknowledges it, resending any job left unacknowledged
+//! for the redelivery interval. The [`JobBroker`] queues jobs, hands each to
+//! one idle [`JobWorker`] at a time, and passes the worker's ACK back to the
+//! producer. A job goes back to the head of the queue when its worker
+//! disconnects, goes quiet for `worker_timeout`, or holds it past
+//! `ack_timeout`.
+//!
+//! Delivery is at-least-once. A job handed out again after a failure arrives
+//! with [`Job::attempt`] above 1, and a producer resend whose first copy has
+//! already completed runs again. Every job carries a [`JobId`]; a worker that
+//! records the ids it has finished can skip repeats, which makes processing
+//! effectively once.
+//!
+//! ## Wire format
+//!
+//! Every message starts with a command frame:
+//!
+//! | Direction | Frames |
+//! |-----------|--------|
+//! | producer → broker | `JOB`, id, payload... |
+//! | broker → worker | `JOB`, id, attempt (u32, big-endian), payload... |
+//! | worker → broker → producer | `ACK`, id |
+//! | worker → broker | `READY` once connected, `HEARTBEAT` while idle |
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use bytes::Bytes;
+//! use monocoque::rt::{self, TcpListener};
+//! use monocoque::zmq::patterns::jobqueue::{JobBroker, JobBrokerOptions, JobProducer, JobWorker};
+//! use monocoque::zmq::{RouterSocket, SocketOptions};
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let (frontend, driver) =
+//!     RouterSocket::accept_loop(TcpListener::bind("127.0.0.1:5570").await?, SocketOptions::default());
+//! rt::spawn_detached(driver);
+//! let (backend, driver) =
+//!     RouterSocket::accept_loop(TcpListener::bind("127.0.0.1:5571").await?, SocketOptions::default());
+//! rt::spawn_detached(driver);
+//! let mut broker = JobBroker::new(frontend, backend, JobBrokerOptions::default());
+//! rt::spawn_detached(async move {
+//!     let _ = broker.run().await;
+//! });
+//!
+//! let mut worker = JobWorker::connect("127.0.0.1:5571").await?;
+//! rt::spawn_detached(async move {
+//!     while let Ok(Some(job)) = worker.recv().await {
+//!         // ... process job.payload ...
+//!         let _ = worker.ack(&job.id).await;
+//!     }
+//! });
+//!
+//! let mut producer = JobProducer::connect("127.0.0.1:5570").await?;
+//! let id = producer.submit(vec![Bytes::from("resize image 42")]).await?;
+//! assert_eq!(producer.recv_ack().await?, Some(id));
+//! # Ok(())
+//! # }
+//! ```
+
+use bytes::Bytes;
+use futures::{FutureExt, select_biased};
+use monocoque_core::error::Error;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+use super::super::{DealerSocket, RouterHubSocket};
+
+const JOB: &[u8] = b"JOB";
+const ACK: &[u8] = b"ACK";
+const READY: &[u8] = b"READY";
+const HEARTBEAT: &[u8] = b"HEARTBEAT";
+
+/// How often the broker checks for dead workers and expired jobs when no
+/// message arrives.
+const REAP_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Shortest wait the producer passes to `recv_timeout`; zero would make the
+/// receive non-blocking.
+const MIN_ACK_WAIT: Duration = Duration::from_millis(1);
+
+/// Source of per-producer tags for generated [`JobId`]s.
+static NEXT_PRODUCER: AtomicU64 = AtomicU64::new(0);
+
+/// Identifier of a job, used to match ACKs and to spot repeats.
+///
+/// Ids must be unique among the jobs a broker has outstanding; a submission
+/// that reuses the id of a queued or running job is dropped as a resend.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JobId(Bytes);
+
+impl JobId {
+    /// An id with the given bytes.
+    pub fn new(id: impl Into<Bytes>) -> Self {
+        Self(id.into())
+    }
+
+    /// The id's bytes as sent on the wire.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A job delivered to a [`JobWorker`].
+#[derive(Debug, Clone)]
+pub struct Job {
+    /// The id to pass to [`JobWorker::ack`].
+    pub id: JobId,
+    /// Delivery count, starting at 1. Above 1, an earlier worker may already
+    /// have processed the job.
+    pub attempt: u32,
+    /// The frames the producer submitted.
+    pub payload: Vec<Bytes>,
+}
+
+impl Job {
+    fn decode(msg: Vec<Bytes>) -> Option<Self> {
+        let mut frames = msg.into_iter();
+        if frames.next()? != JOB {
+            return None;
+        }
+        let id = JobId(frames.next()?);
+        let attempt = u32::from_be_bytes(frames.next()?.as_ref().try_into().ok()?);
+        Some(Self {
+            id,
+            attempt,
+            payload: frames.collect(),
+        })
+    }
+}
+
+fn command(name: &'static [u8], id: &JobId) -> Vec<Bytes> {
+    vec![Bytes::from_static(name), id.0.clone()]
+}
+
+// ── Producer ─────────────────────────────────────────────────────────────────
+
+/// DEALER side that submits jobs and waits for their acknowledgements.
+///
+/// Submitted jobs stay pending until their ACK arrives. While waiting in
+/// [`recv_ack`](Self::recv_ack), any job pending for longer than the
+/// redelivery interval (5 s by default) is sent again with the same id.
+pub struct JobProducer {
+    socket: DealerSocket,
+    redelivery: Duration,
+    pending: HashMap<JobId, PendingJob>,
+    tag: u64,
+    next_seq: u64,
+}
+
+struct PendingJob {
+    payload: Vec<Bytes>,
+    sent_at: Instant,
+}
+
+impl JobProducer {
+    /// Connect to a broker's frontend.
+    pub async fn connect(endpoint: &str) -> Result<Self, Error> {
+        Ok(Self::from_socket(DealerSocket::connect(endpoint).await?))
+    }
+
+    /// Submit jobs over an already connected DEALER socket.
+    ///
+    /// The producer manages the socket's `recv_timeout` to time redeliveries.
+    pub fn from_socket(socket: DealerSocket) -> Self {
+        let since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let tag = since_epoch.as_secs().rotate_left(32)
+            ^ u64::from(since_epoch.subsec_nanos())
+            ^ (u64::from(std::process::id()) << 16)
+            ^ NEXT_PRODUCER.fetch_add(1, Ordering::Relaxed);
+        Self {
+            socket,
+            redelivery: Duration::from_secs(5),
+            pending: HashMap::new(),
+            tag,
+            next_seq: 0,
+        }
+    }
+
+    /// Resend jobs left unacknowledged for `interval`.
+    pub fn with_redelivery(mut self, interval: Duration) -> Self {
+        self.redelivery = interval;
+        self
+    }
+
+    /// Submit a job under a generated id and return the id.
+    ///
+    /// Generated ids are unique to this producer instance. Use
+    /// [`submit_with_id`](Self::submit_with_id) when the job has a natural
+    /// key that should stay the same across producer restarts.
+    pub async fn submit(&mut self, payload: Vec<Bytes>) -> Result<JobId, Error> {
+        let id = JobId(Bytes::from(format!("{:016x}-{}", self.tag, self.next_seq)));
+        self.next_seq += 1;
+        self.submit_with_id(id.clone(), payload).await?;
+        Ok(id)
+    }
+
+    /// Submit a job under `id`.
+    pub async fn submit_with_id(&mut self, id: JobId, payload: Vec<Bytes>) -> Result<(), Error> {
+        self.send_job(&id, &payload).await?;
+        self.pending.insert(
+            id,
+            PendingJob {
+                payload,
+                sent_at: Instant::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Wait for the next acknowledgement and return the id it completes.
+    ///
+    /// Overdue jobs are resent while waiting. ACKs for jobs no longer
+    /// pending (the second ACK of a job that ran twice) are skipped. Returns
+    /// `Ok(None)` once the connection to the broker closes.
+    pub async fn recv_ack(&mut self) -> Result<Option<JobId>, Error> {
+        loop {
+            let now = Instant::now();
+            let wait = self
+                .pending
+                .values()
+                .map(|job| (job.sent_at + self.redelivery).saturating_duration_since(now))
+                .min()
+                .map(|wait| wait.max(MIN_ACK_WAIT));
+            self.socket
+                .update_options(|o| o.recv_timeout = wait)
+                .map_err(io::Error::from)?;
+
+            match self.socket.recv().await {
+                Ok(Some(msg)) => {
+                    if let [kind, id] = msg.as_slice()
+                        && kind == ACK
+                    {
+                        let id = JobId(id.clone());
+                        if self.pending.remove(&id).is_some() {
+                            return Ok(Some(id));
+                        }
+                    } else {
+                        debug!("[JOBQUEUE] Producer ignoring {} frame message", msg.len());
+                    }
+                }
+                Ok(None) => return Ok(None),
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => self.resend_overdue().await?,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Jobs submitted and not yet acknowledged.
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    async fn resend_overdue(&mut self) -> io::Result<()> {
+        let now = Instant::now();
+        let overdue: Vec<JobId> = self
+            .pending
+            .iter()
+            .filter(|(_, job)| now.duration_since(job.sent_at) >= self.redelivery)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in overdue {
+            debug!("[JOBQUEUE] Resending unacknowledged job {:?}", id);
+            let payload = self.pending[&id].payload.clone();
+            self.send_job(&id, &payload).await?;
+            if let Some(job) = self.pending.get_mut(&id) {
+                job.sent_at = now;
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_job(&mut self, id: &JobId, payload: &[Bytes]) -> io::Result<()> {
+        let mut msg = command(JOB, id);
+        msg.extend_from_slice(payload);
+        Ok(self.socket.send(msg).await?)
+    }
+}
+
+// ── Worker ───────────────────────────────────────────────────────────────────
+
+/// DEALER side that receives jobs from the broker's backend and acks them.
+///
+/// The broker hands a worker one job at a time. While waiting in
+/// [`recv`](Self::recv) the worker sends a heartbeat every heartbeat
+/// interval (1 s by default). A job that takes longer than the broker's
+/// `worker_timeout` should call [`heartbeat`](Self::heartbeat) as it goes,
+/// or the broker gives the job to another worker.
+pub struct JobWorker {
+    socket: DealerSocket,
+    heartbeat_ivl: Duration,
+}
+
+impl JobWorker {
+    /// Connect to a broker's backend and announce the worker as ready.
+    pub async fn connect(endpoint: &str) -> Result<Self, Error> {
+        Self::from_socket(DealerSocket::connect(endpoint).await?).await
+    }
+
+    /// Serve jobs over an already connected DEALER socket.
+    ///
+    /// The worker manages the socket's `recv_timeout` to time heartbeats.
+    pub async fn from_socket(mut socket: DealerSocket) -> Result<Self, Error> {
+        socket.send(vec![Bytes::from_static(READY)]).await?;
+        Ok(Self {
+            socket,
+            heartbeat_ivl: Duration::from_secs(1),
+        })
+    }
+
+    /// Send a heartbeat after `interval` without a job.
+    pub fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.heartbeat_ivl = interval;
+        self
+    }
+
+    /// Wait for the next job; `Ok(None)` once the broker connection closes.
+    pub async fn recv(&mut self) -> Result<Option<Job>, Error> {
+        let wait = self.heartbeat_ivl.max(MIN_ACK_WAIT);
+        self.socket
+            .update_options(|o| o.recv_timeout = Some(wait))
+            .map_err(io::Error::from)?;
+        loop {
+            match self.socket.recv().await {
+                Ok(Some(msg)) => {
+                    if let Some(job) = Job::decode(msg) {
+                        return Ok(Some(job));
+                    }
+                    debug!("[JOBQUEUE] Worker ignoring malformed job");
+                }
+                Ok(None) => return Ok(None),
+                Err(e) if e.kind() == io::ErrorKind::TimedOut => self.heartbeat().await?,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Report job `id` as done, making the worker ready for the next one.
+    pub async fn ack(&mut self, id: &JobId) -> Result<(), Error> {
+        self.socket.send(command(ACK, id)).await
+    }
+
+    /// Tell the broker the worker is alive.
+    pub async fn heartbeat(&mut self) -> Result<(), Error> {
+        self.socket.send(vec![Bytes::from_static(HEARTBEAT)]).await
+    }
+}
+
+// ── Broker ───────────────────────────────────────────────────────────────────
+
+/// Settings and counters of a [`JobBroker`].
+///
+/// Clones share the same counters, so keep a clone before handing the options
+/// to the broker to read [`stats`](Self::stats) while it runs.
+#[derive(Debug, Clone)]
+pub struct JobBrokerOptions {
+    /// A worker not heard from for this long (no READY, HEARTBEAT or ACK) is
+    /// considered dead and its job is redelivered. Defaults to 5 s.
+    pub worker_timeout: Duration,
+    /// A job not acknowledged this long after it was handed out is
+    /// redelivered, and the worker holding it gets no more jobs until it
+    /// reports in again. `None` (the default) waits as long as the worker
+    /// stays alive.
+    pub ack_timeout: Option<Duration>,
+    counters: Arc<JobCounters>,
+}
+
+impl Default for JobBrokerOptions {
+    fn default() -> Self {
+        Self {
+            worker_timeout: Duration::from_secs(5),
+            ack_timeout: None,
+            counters: Arc::default(),
+        }
+    }
+}
+
+impl JobBrokerOptions {
+    /// Options with the default timeouts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set [`worker_timeout`](Self::worker_timeout).
+    pub fn with_worker_timeout(mut self, timeout: Duration) -> Self {
+        self.worker_timeout = timeout;
+        self
+    }
+
+    /// Set [`ack_timeout`](Self::ack_timeout).
+    pub fn with_ack_timeout(mut self, timeout: Duration) -> Self {
+        self.ack_timeout = Some(timeout);
+        self
+    }
+
+    /// Queue depth and delivery counters of the broker running with these
+    /// options.
+    pub fn stats(&self) -> JobQueueStats {
+        let counters = &self.counters;
+        JobQueueStats {
+            queued: counters.queued.load(Ordering::Relaxed),
+            in_flight: counters.in_flight.load(Ordering::Relaxed),
+            workers: counters.workers.load(Ordering::Relaxed),
+            completed: counters.completed.load(Ordering::Relaxed),
+            redelivered: counters.redelivered.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Queue depth and delivery counters of a [`JobBroker`].
+///
+/// Returned by [`JobBrokerOptions::stats`]. The depths are updated each time
+/// the broker handles a message or checks its workers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobQueueStats {
+    /// Jobs waiting for a worker.
+    pub queued: usize,
+    /// Jobs handed to a worker and not yet acknowledged.
+    pub in_flight: usize,
+    /// Workers currently known to the broker.
+    pub workers: usize,
+    /// Jobs acknowledged by a worker.
+    pub completed: u64,
+    /// Jobs put back in the queue after their worker died or timed out.
+    pub redelivered: u64,
+}
+
+#[derive(Debug, Default)]
+struct JobCounters {
+    queued: AtomicUsize,
+    in_flight: AtomicUsize,
+    workers: AtomicUsize,
+    completed: AtomicU64,
+    redelivered: AtomicU64,
+}
+
+/// An outstanding job: queued, or held by `worker`.
+struct BrokerJob {
+    producer: Bytes,
+    payload: Vec<Bytes>,
+    attempts: u32,
+    worker: Option<Bytes>,
+}
+
+struct WorkerSlot {
+    last_seen: Instant,
+    /// The job handed to the worker and when.
+    job: Option<(JobId, Instant)>,
+}
+
+enum BrokerEvent {
+    Producer(Option<Vec<Bytes>>),
+    Worker(Option<Vec<Bytes>>),
+    Tick,
+}
+
+/// ROUTER/ROUTER broker between [`JobProducer`]s and [`JobWorker`]s.
+///
+/// Producers connect to `frontend` and workers to `backend`, both
+/// multi-peer ROUTERs from [`RouterSocket::accept_loop`](super::super::RouterSocket::accept_loop).
+/// [`run`](Self::run) drives the queue; read its state through the options'
+/// [`stats`](JobBrokerOptions::stats).
+pub struct JobBroker {
+    frontend: RouterHubSocket,
+    backend: RouterHubSocket,
+    options: JobBrokerOptions,
+    /// Every job not yet acknowledged.
+    jobs: HashMap<JobId, BrokerJob>,
+    /// Outstanding jobs with no worker, in dispatch order.
+    queue: VecDeque<JobId>,
+    workers: HashMap<Bytes, WorkerSlot>,
+    /// Workers waiting for a job, longest-waiting first. May hold workers
+    /// that have since died; those are skipped.
+    idle: VecDeque<Bytes>,
+}
+
+impl JobBroker {
+    /// A broker taking jobs on `frontend` and handing them out on `backend`.
+    pub fn new(
+        frontend: RouterHubSocket,
+        backend: RouterHubSocket,
+        options: JobBrokerOptions,
+    ) -> Self {
+        Self {
+            frontend,
+            backend,
+            options,
+            jobs: HashMap::new(),
+            queue: VecDeque::new(),
+            workers: HashMap::new(),
+            idle: VecDeque::new(),
+        }
+    }
+
+    /// Serve producers and workers until either socket's driver stops.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of a failed send on either socket.
+    pub async fn run(&mut self) -> io::Result<()> {
+        loop {
+            let event = select_biased! {
+                msg = self.backend.recv().fuse() => BrokerEvent::Worker(msg?),
+                msg = self.frontend.recv().fuse() => BrokerEvent::Producer(msg?),
+                () = monocoque_core::rt::sleep(REAP_INTERVAL).fuse() => BrokerEvent::Tick,
+            };
+            match event {
+                BrokerEvent::Producer(Some(msg)) => self.on_producer(msg),
+                BrokerEvent::Worker(Some(msg)) => self.on_worker(msg).await?,
+                BrokerEvent::Producer(None) | BrokerEvent::Worker(None) => return Ok(()),
+                BrokerEvent::Tick => {}
+            }
+            self.reap();
+            self.dispatch().await?;
+            self.publish_stats();
+        }
+    }
+
+    fn on_producer(&mut self, msg: Vec<Bytes>) {
+        let [producer, _, kind, id, payload @ ..] = msg.as_slice() else {
+            debug!("[JOBQUEUE] Broker ignoring short producer message");
+            return;
+        };
+        if kind != JOB {
+            debug!("[JOBQUEUE] Broker ignoring producer command {:?}", kind);
+            return;
+        }
+        let id = JobId(id.clone());
+        if self.jobs.contains_key(&id) {
+            debug!(
+                "[JOBQUEUE] Job {:?} already outstanding; dropping resend",
+                id
+            );
+            return;
+        }
+        self.jobs.insert(
+            id.clone(),
+            BrokerJob {
+                producer: producer.clone(),
+                payload: payload.to_vec(),
+                attempts: 0,
+                worker: None,
+            },
+        );
+        self.queue.push_back(id);
+    }
+
+    async fn on_worker(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
+        let [worker_id, _, kind, rest @ ..] = msg.as_slice() else {
+            debug!("[JOBQUEUE] Broker ignoring short worker message");
+            return Ok(());
+        };
+        let now = Instant::now();
+        let worker = self.workers.entry(worker_id.clone()).or_insert_with(|| {
+            debug!("[JOBQUEUE] Worker {:?} joined", worker_id);
+            self.idle.push_back(worker_id.clone());
+            WorkerSlot {
+                last_seen: now,
+                job: None,
+            }
+        });
+        worker.last_seen = now;
+
+        match (kind.as_ref(), rest) {
+            (READY | HEARTBEAT, []) => {}
+            (ACK, [id]) => {
+                let id = JobId(id.clone());
+                if worker.job.as_ref().is_some_and(|(job, _)| *job == id) {
+                    worker.job = None;
+                    self.idle.push_back(worker_id.clone());
+                }
+                self.complete(&id).await?;
+            }
+            _ => debug!("[JOBQUEUE] Broker ignoring worker command {:?}", kind),
+        }
+        Ok(())
+    }
+
+    /// Retire job `id` and acknowledge it to its producer.
+    async fn complete(&mut self, id: &JobId) -> io::Result<()> {
+        let Some(job) = self.jobs.remove(id) else {
+            return Ok(()); // a repeat that already completed
+        };
+        if job.worker.is_none() {
+            // Redelivered after a timeout, then acked by the original worker.
+            self.queue.retain(|queued| queued != id);
+        }
+        self.options
+            .counters
+            .completed
+            .fetch_add(1, Ordering::Relaxed);
+        let mut ack = vec![job.producer];
+        ack.extend(command(ACK, id));
+        Ok(self.frontend.send(ack).await?)
+    }
+
+    /// Drop dead workers and put their jobs back at the head of the queue.
+    fn reap(&mut self) {
+        let now = Instant::now();
+        let worker_timeout = self.options.worker_timeout;
+        let ack_timeout = self.options.ack_timeout;
+        let dead: Vec<Bytes> = self
+            .workers
+            .iter()
+            .filter(|(id, worker)| {
+                !self.backend.is_peer_connected(id)
+                    || now.duration_since(worker.last_seen) > worker_timeout
+                    || worker.job.as_ref().zip(ack_timeout).is_some_and(
+                        |((_, handed_out), timeout)| now.duration_since(*handed_out) > timeout,
+                    )
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for worker_id in dead {
+            let Some(worker) = self.workers.remove(&worker_id) else {
+                continue;
+            };
+            debug!("[JOBQUEUE] Worker {:?} lost", worker_id);
+            let Some((id, _)) = worker.job else {
+                continue;
+            };
+            if let Some(job) = self.jobs.get_mut(&id)
+                && job.worker.as_ref() == Some(&worker_id)
+            {
+                job.worker = None;
+                self.queue.push_front(id);
+                self.options
+                    .counters
+                    .redelivered
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Hand queued jobs to idle workers.
+    async fn dispatch(&mut self) -> io::Result<()> {
+        while !self.queue.is_empty() {
+            let Some(worker_id) = self.idle.pop_front() else {
+                break;
+            };
+            let Some(worker) = self.workers.get_mut(&worker_id) else {
+                continue;
+            };
+            if worker.job.is_some() {
+                continue;
+            }
+            let Some(id) = self.queue.pop_front() else {
+                break;
+            };
+            let job = self.jobs.get_mut(&id).expect("queued jobs are outstanding");
+            job.attempts += 1;
+            job.worker = Some(worker_id.clone());
+            worker.job = Some((id.clone(), Instant::now()));
+
+            let mut msg = Vec::with_capacity(4 + job.payload.len());
+            msg.push(worker_id);
+            msg.extend(command(JOB, &id));
+            msg.push(Bytes::copy_from_slice(&job.attempts.to_be_bytes()));
+            msg.extend(job.payload.iter().cloned());
+            self.backend.send(msg).await?;
+        }
+        Ok(())
+    }
+
+    fn publish_stats(&self) {
+        let counters = &self.options.counters;
+        counters.queued.store(self.queue.len(), Ordering::Relaxed);
+        counters
+            .in_flight
+            .store(self.jobs.len() - self.queue.len(), Ordering::Relaxed);
+        counters
+            .workers
+            .store(self.workers.len(), Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,6 @@
+//! Higher-level messaging patterns assembled from the socket types.
+//!
+//! - [`jobqueue`] - Acknowledged work queue with redelivery (DEALER producers
+//!   and workers around a ROUTER/ROUTER broker)
+
+pub mod jobqueue;
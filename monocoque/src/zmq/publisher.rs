@@ -1,11 +1,14 @@
 //! PUB socket implementation with worker pool architecture.
 
 use bytes::Bytes;
-use monocoque_core::monitor::{SocketEventSender, SocketMonitor, create_monitor};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::error::Error;
+use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpListener;
 use monocoque_zmtp::SocketType;
-use monocoque_zmtp::publisher::PubSocket as InternalPub;
+use monocoque_zmtp::codec::classify_error;
+use monocoque_zmtp::publisher::{PubSocket as InternalPub, PubStats};
 use std::io;
 
 /// A PUB socket for broadcasting messages to multiple subscribers.
@@ -13,7 +16,7 @@ use std::io;
 /// PubSocket uses a **worker pool architecture** to handle multiple subscribers efficiently:
 /// - Multiple OS threads (default: CPU core count)
 /// - Each worker runs its own compio runtime with io_uring
-/// - Round-robin subscriber distribution across workers
+/// - Least-connections subscriber distribution across workers
 /// - Zero-copy message broadcasting via `Arc<Bytes>`
 /// - Lock-free subscription management
 ///
@@ -37,16 +40,19 @@ use std::io;
 pub struct PubSocket {
     inner: InternalPub,
     listener: TcpListener,
+    endpoint: Endpoint,
     monitor: Option<SocketEventSender>,
 }
 
 impl PubSocket {
     /// Bind to an address with default worker count (CPU cores).
-    pub async fn bind(addr: impl monocoque_core::rt::ToSocketAddrs) -> io::Result<Self> {
+    pub async fn bind(addr: impl monocoque_core::rt::ToSocketAddrs) -> Result<Self, Error> {
         let listener = TcpListener::bind(addr).await?;
+        let endpoint = Endpoint::Tcp(listener.local_addr()?);
         Ok(Self {
             inner: InternalPub::new(),
             listener,
+            endpoint,
             monitor: None,
         })
     }
@@ -55,29 +61,75 @@ impl PubSocket {
     pub async fn bind_with_workers(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         worker_count: usize,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         let listener = TcpListener::bind(addr).await?;
+        let endpoint = Endpoint::Tcp(listener.local_addr()?);
         Ok(Self {
             inner: InternalPub::with_workers(worker_count),
             listener,
+            endpoint,
             monitor: None,
         })
     }
 
+    /// Bind a PUB sharded across `workers` runtime threads.
+    ///
+    /// Each shard is an OS thread running its own single-threaded runtime
+    /// that owns a subset of the subscribers, their subscription state and
+    /// their writes. Accepted subscribers go to the shard with the fewest live
+    /// subscribers. `send()` hands each message to the shards as one shared
+    /// buffer, so fan-out throughput grows with the shard count rather than
+    /// being capped by the publishing thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` if `workers` is zero, or the bind error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::PubSocket;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut socket = PubSocket::bind_sharded("127.0.0.1:5555", 4).await?;
+    /// for _ in 0..1000 {
+    ///     socket.accept_subscriber().await?;
+    /// }
+    /// println!("subscribers per shard: {:?}", socket.stats().per_worker);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bind_sharded(
+        addr: impl monocoque_core::rt::ToSocketAddrs,
+        workers: usize,
+    ) -> Result<Self, Error> {
+        if workers == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "PUB needs at least one worker",
+            )
+            .into());
+        }
+        Self::bind_with_workers(addr, workers).await
+    }
+
     /// Accept a new subscriber connection.
     ///
     /// Performs ZMTP handshake and assigns the subscriber to a worker thread.
     /// Returns the subscriber ID.
-    pub async fn accept_subscriber(&mut self) -> io::Result<u64> {
-        self.inner.accept_subscriber(&self.listener).await
+    pub async fn accept_subscriber(&mut self) -> Result<u64, Error> {
+        self.inner
+            .accept_subscriber(&self.listener)
+            .await
+            .map_err(classify_error)
     }
 
     /// Broadcast a multipart message to all matching subscribers.
     ///
     /// Messages are distributed to all workers in parallel.
     /// The first frame is typically used as a topic for subscription filtering.
-    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        self.inner.send(msg).await
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
     }
 
     /// Broadcast a message given as borrowed frames.
@@ -86,8 +138,41 @@ impl PubSocket {
     /// is allocated only when it matches a subscription, so publishing from a
     /// stack array (`send_frames(&[topic, payload])`) pays no per-message heap
     /// allocation on the common drop path of a topic-filtered stream.
-    pub async fn send_frames(&mut self, frames: &[Bytes]) -> io::Result<()> {
-        self.inner.send_frames(frames).await
+    pub async fn send_frames(&mut self, frames: &[Bytes]) -> Result<(), Error> {
+        self.inner.send_frames(frames).await.map_err(classify_error)
+    }
+
+    /// Publish `data` under `topic`: sends `[topic, data]`.
+    pub async fn publish(
+        &mut self,
+        topic: impl Into<Bytes>,
+        data: impl Into<Bytes>,
+    ) -> Result<(), Error> {
+        self.inner
+            .publish(topic, data)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Publish `frames` under `topic`: sends `[topic, frames...]`.
+    pub async fn publish_multipart(
+        &mut self,
+        topic: impl Into<Bytes>,
+        frames: Vec<Bytes>,
+    ) -> Result<(), Error> {
+        self.inner
+            .publish_multipart(topic, frames)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Broadcast `frames` to every subscriber, bypassing subscription
+    /// filtering. Meant for administrative messages.
+    pub async fn publish_to_all(&mut self, frames: Vec<Bytes>) -> Result<(), Error> {
+        self.inner
+            .publish_to_all(frames)
+            .await
+            .map_err(classify_error)
     }
 
     /// Get the number of active subscribers.
@@ -95,6 +180,27 @@ impl PubSocket {
         self.inner.subscriber_count()
     }
 
+    /// Number of worker threads (shards) backing this socket.
+    #[inline]
+    pub fn worker_count(&self) -> usize {
+        self.inner.worker_count()
+    }
+
+    /// Subscriber and delivery counters aggregated across all workers.
+    #[inline]
+    pub fn stats(&self) -> PubStats {
+        self.inner.stats()
+    }
+
+    /// Structured diagnostics of the socket, serializable with the `serde`
+    /// feature: the bound endpoint, one queue per worker with its depth and
+    /// subscribers, and the broadcasts dropped at `send_hwm`.
+    pub fn snapshot(&self) -> monocoque_zmtp::DiagnosticsSnapshot {
+        let mut snapshot = self.inner.snapshot();
+        snapshot.endpoint = Some(self.endpoint.to_string());
+        snapshot
+    }
+
     /// Get the local address this socket is bound to.
     pub fn local_addr(&self) -> io::Result<std::net::SocketAddr> {
         self.listener.local_addr()
@@ -119,10 +225,112 @@ impl PubSocket {
         receiver
     }
 
-    /// Get a mutable reference to this socket's options.
+    /// Close the socket gracefully.
+    ///
+    /// Messages already handed to the workers are delivered before they shut
+    /// down, and monitors receive a `Closed` event for the bound endpoint.
+    /// Dropping the socket instead stops the workers without waiting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PubSocket;
+    /// # async fn example(socket: PubSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let result = self.inner.close().await;
+        if let Some(monitor) = &self.monitor {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(self.endpoint));
+        }
+        result
+    }
+
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if at least one subscriber is attached.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PubSocket;
+    /// # fn check(mut socket: PubSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let writable = socket.events() & 2 != 0;
+    /// println!(
+    ///     "{} healthy={healthy} writable={writable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().send_hwm,
+    /// );
+    /// socket.update_options(|o| o.send_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub const fn is_connected(&self) -> bool {
+        self.subscriber_count() > 0
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Get the endpoint this socket is bound to.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Always `false`: PUB sockets cannot receive.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub const fn has_more(&self) -> bool {
+        false
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// PUB never blocks on send (slow subscribers are dropped at the HWM),
+    /// so this is `2` (POLLOUT) unless the socket is poisoned.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub const fn events(&self) -> u32 {
+        if self.is_poisoned() { 0 } else { 2 }
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        self.inner.options_mut()
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 
     /// Number of messages dropped due to HWM backpressure.
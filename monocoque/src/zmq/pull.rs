@@ -2,10 +2,12 @@
 //!
 //! PULL sockets are used in pipeline patterns for receiving tasks.
 
-use monocoque_core::monitor::{SocketEventSender, SocketMonitor, create_monitor};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::error::Error;
+use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::{TcpListener, TcpStream};
 use monocoque_zmtp::PullSocket as InternalPull;
+use monocoque_zmtp::codec::classify_error;
 use std::io;
 
 /// PULL socket for receiving tasks in a pipeline.
@@ -39,7 +41,7 @@ impl PullSocket<TcpStream> {
     /// ```
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
-    ) -> io::Result<(TcpListener, Self)> {
+    ) -> Result<(TcpListener, Self), Error> {
         let listener = TcpListener::bind(addr).await?;
         let (stream, _) = listener.accept().await?;
         let socket = Self::from_tcp(stream).await?;
@@ -61,9 +63,9 @@ impl PullSocket<TcpStream> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connect(addr: impl monocoque_core::rt::ToSocketAddrs) -> io::Result<Self> {
+    pub async fn connect(addr: impl monocoque_core::rt::ToSocketAddrs) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::connect(addr).await?,
+            inner: InternalPull::connect(addr).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -72,33 +74,34 @@ impl PullSocket<TcpStream> {
     pub async fn connect_with_options(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::connect_with_options(addr, options).await?,
+            inner: InternalPull::connect_with_options(addr, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
 
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.inner.is_connected()
-    }
-
     /// Try to reconnect to the stored endpoint.
-    pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.inner.try_reconnect().await
+    pub async fn try_reconnect(&mut self) -> Result<(), Error> {
+        self.inner.try_reconnect().await.map_err(classify_error)
     }
 
     /// Receive with automatic reconnection on EOF or network error.
-    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
-        self.inner.recv_with_reconnect().await
+    pub async fn recv_with_reconnect(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
+        self.inner
+            .recv_with_reconnect()
+            .await
+            .map_err(classify_error)
     }
 
     /// Create a PULL socket from a TCP stream.
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::from_tcp(stream).await?,
+            inner: InternalPull::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -107,9 +110,11 @@ impl PullSocket<TcpStream> {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::from_tcp_with_options(stream, options).await?,
+            inner: InternalPull::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -120,17 +125,19 @@ where
     S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
 {
     /// Create a PULL socket from any stream.
-    pub async fn new(stream: S) -> io::Result<Self> {
+    pub async fn new(stream: S) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::new(stream).await?,
+            inner: InternalPull::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
 
     /// Create a PULL socket from any stream with custom options.
-    pub async fn with_options(stream: S, options: SocketOptions) -> io::Result<Self> {
+    pub async fn with_options(stream: S, options: SocketOptions) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::with_options(stream, options).await?,
+            inner: InternalPull::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -153,13 +160,33 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub fn try_recv(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
-        self.inner.try_recv()
+    pub fn try_recv(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
+        self.inner.try_recv().map_err(classify_error)
     }
 
     /// Receive a message.
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
-        self.inner.recv().await
+    pub async fn recv(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<bytes::Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        }))
     }
 
     /// Receive a message into a caller-provided buffer, reusing its allocation.
@@ -169,8 +196,8 @@ where
     /// every call removes the per-message allocation from a steady recv loop,
     /// which is the dominant per-message cost for small messages. Returns
     /// `Ok(true)` when a message was read, `Ok(false)` when the connection closed.
-    pub async fn recv_into(&mut self, out: &mut Vec<bytes::Bytes>) -> io::Result<bool> {
-        self.inner.recv_into(out).await
+    pub async fn recv_into(&mut self, out: &mut Vec<bytes::Bytes>) -> Result<bool, Error> {
+        self.inner.recv_into(out).await.map_err(classify_error)
     }
 
     /// Try to receive a message into a caller-provided buffer without a kernel read.
@@ -180,8 +207,8 @@ where
     /// complete message is already buffered, or `Ok(false)` leaving `out` untouched
     /// when none is. Use it with [`recv_into`](Self::recv_into) to drain a burst
     /// from one kernel read without allocating per message.
-    pub fn try_recv_into(&mut self, out: &mut Vec<bytes::Bytes>) -> io::Result<bool> {
-        self.inner.try_recv_into(out)
+    pub fn try_recv_into(&mut self, out: &mut Vec<bytes::Bytes>) -> Result<bool, Error> {
+        self.inner.try_recv_into(out).map_err(classify_error)
     }
 
     /// Receive a batch of messages with a single `.await`.
@@ -190,8 +217,8 @@ where
     /// message already decoded from the same kernel read. Returning a burst of
     /// small messages from one `.await` amortizes per-await overhead; it is the
     /// receive-side counterpart to [`PushSocket::send_batch`](crate::zmq::PushSocket::send_batch).
-    pub async fn recv_batch(&mut self) -> io::Result<Option<Vec<Vec<bytes::Bytes>>>> {
-        self.inner.recv_batch().await
+    pub async fn recv_batch(&mut self) -> Result<Option<Vec<Vec<bytes::Bytes>>>, Error> {
+        self.inner.recv_batch().await.map_err(classify_error)
     }
 
     /// Enable monitoring for this socket.
@@ -201,19 +228,150 @@ where
         receiver
     }
 
-    /// Get a mutable reference to this socket's options.
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PullSocket;
+    /// # fn check(mut socket: PullSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        self.inner.options_mut()
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Get the endpoint this socket is connected/bound to, if available.
+    ///
+    /// Returns `None` if the socket was created from a raw stream.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> Option<&monocoque_core::endpoint::Endpoint> {
+        self.inner.last_endpoint()
+    }
+
+    /// Check if the last received message has more frames coming.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// Returns a bitmask: `1` (POLLIN) when ready to receive, `2` (POLLOUT)
+    /// when ready to send.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.inner.events()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PullSocket;
+    /// # async fn example(socket: PullSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 }
 
 #[cfg(unix)]
 impl PullSocket<monocoque_core::rt::UnixStream> {
     /// Create a PULL socket from a Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::new(stream).await?,
+            inner: InternalPull::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -222,10 +380,52 @@ impl PullSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPull::with_options(stream, options).await?,
+            inner: InternalPull::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
 }
+
+// Implement ProxySocket for the high-level PullSocket wrapper (frontend of a
+// PUSH-PULL forwarder). PULL never sends, so anything routed to it is dropped.
+impl<S> monocoque_zmtp::proxy::ProxySocket for PullSocket<S>
+where
+    S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
+{
+    fn recv_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+    ) -> ::core::pin::Pin<
+        Box<
+            dyn ::core::future::Future<Output = io::Result<Option<Vec<bytes::Bytes>>>>
+                + 'async_trait,
+        >,
+    >
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.recv().await?) })
+    }
+
+    fn send_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+        _msg: Vec<bytes::Bytes>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(()) })
+    }
+
+    fn socket_desc(&self) -> &'static str {
+        "PULL"
+    }
+    fn try_recv_multipart(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
+        Ok(self.try_recv()?)
+    }
+}
@@ -38,8 +38,11 @@
 //! limit anyway.
 
 use flume::{Receiver, Sender};
-use monocoque_core::options::SocketOptions;
-use monocoque_core::rt::{JoinHandle, spawn};
+use futures::{FutureExt, select_biased};
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::error::Error;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::rt::{JoinHandle, join, spawn};
 use monocoque_core::rt::{TcpListener, TcpStream};
 use std::collections::VecDeque;
 use std::io;
@@ -79,8 +82,16 @@ pub struct PullFanIn {
     /// time by `recv`/`try_recv` before the next channel hop.
     buf: VecDeque<Vec<bytes::Bytes>>,
     // Reader tasks are kept alive here. Dropping the handles cancels them, which
-    // is exactly what we want when the sink goes away.
-    _readers: Vec<JoinHandle<()>>,
+    // is exactly what we want when the sink goes away. A reader stopped by
+    // `shutdown` hands its socket back so `close` can close it.
+    readers: Vec<JoinHandle<Option<PullSocket<TcpStream>>>>,
+    /// Never sent on; dropping it tells every reader to stop.
+    shutdown: Option<Sender<()>>,
+    /// One per live reader; each applies what it receives before its next read.
+    option_txs: Vec<Sender<SocketOptions>>,
+    /// Address of the listener the workers were accepted on.
+    endpoint: Endpoint,
+    options: SocketOptions,
 }
 
 impl PullFanIn {
@@ -104,7 +115,7 @@ impl PullFanIn {
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         n_workers: usize,
-    ) -> io::Result<(TcpListener, Self)> {
+    ) -> Result<(TcpListener, Self), Error> {
         Self::bind_with_options(addr, n_workers, SocketOptions::default()).await
     }
 
@@ -113,8 +124,8 @@ impl PullFanIn {
         addr: impl monocoque_core::rt::ToSocketAddrs,
         n_workers: usize,
         options: SocketOptions,
-    ) -> io::Result<(TcpListener, Self)> {
-        let listener = TcpListener::bind(addr).await?;
+    ) -> Result<(TcpListener, Self), Error> {
+        let listener = monocoque_core::rt::bind_listener(addr, &options).await?;
         let fanin = Self::accept_workers(&listener, n_workers, options).await?;
         Ok((listener, fanin))
     }
@@ -127,20 +138,34 @@ impl PullFanIn {
         listener: &TcpListener,
         n_workers: usize,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
+        let endpoint = Endpoint::Tcp(listener.local_addr()?);
         let (tx, rx) = flume::bounded(CHANNEL_CAPACITY);
+        let (shutdown, shutdown_rx) = flume::bounded(0);
         let mut readers = Vec::with_capacity(n_workers);
+        let mut option_txs = Vec::with_capacity(n_workers);
         for _ in 0..n_workers {
             let (stream, _) = listener.accept().await?;
             let pull = PullSocket::from_tcp_with_options(stream, options.clone()).await?;
-            readers.push(spawn(read_into_channel(pull, tx.clone())));
+            let (option_tx, option_rx) = flume::unbounded();
+            readers.push(spawn(read_into_channel(
+                pull,
+                tx.clone(),
+                option_rx,
+                shutdown_rx.clone(),
+            )));
+            option_txs.push(option_tx);
         }
         // Drop our own sender so the channel closes once every reader is done.
         drop(tx);
         Ok(Self {
             rx,
             buf: VecDeque::new(),
-            _readers: readers,
+            readers,
+            shutdown: Some(shutdown),
+            option_txs,
+            endpoint,
+            options,
         })
     }
 
@@ -150,7 +175,7 @@ impl PullFanIn {
     /// buffer is empty. Returns `Ok(None)` once every worker has disconnected and
     /// both the buffer and channel have drained, mirroring `PullSocket::recv` on a
     /// closed connection.
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
+    pub async fn recv(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
         if let Some(msg) = self.buf.pop_front() {
             return Ok(Some(msg));
         }
@@ -169,7 +194,7 @@ impl PullFanIn {
     ///
     /// Returns `Ok(None)` when nothing is buffered or queued, even if workers are
     /// still connected. Use it to drain the sink after a `recv`.
-    pub fn try_recv(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
+    pub fn try_recv(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
         if let Some(msg) = self.buf.pop_front() {
             return Ok(Some(msg));
         }
@@ -192,7 +217,7 @@ impl PullFanIn {
     /// to [`PullSocket::recv_batch`](crate::zmq::PullSocket::recv_batch).
     ///
     /// Returns `Ok(None)` once every worker has disconnected and nothing remains.
-    pub async fn recv_batch(&mut self) -> io::Result<Option<Vec<Vec<bytes::Bytes>>>> {
+    pub async fn recv_batch(&mut self) -> Result<Option<Vec<Vec<bytes::Bytes>>>, Error> {
         let mut out: Vec<Vec<bytes::Bytes>> = self.buf.drain(..).collect();
         if out.is_empty() {
             match self.rx.recv_async().await {
@@ -206,6 +231,121 @@ impl PullFanIn {
         }
         Ok(Some(out))
     }
+
+    /// Get a reference to the options applied to every worker.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        &self.options
+    }
+
+    /// Check if at least one worker is still connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PullFanIn;
+    /// # fn check(mut sink: PullFanIn) {
+    /// let healthy = sink.is_connected() && !sink.is_poisoned();
+    /// let readable = sink.events() & 1 != 0;
+    /// println!(
+    ///     "{} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     sink.last_endpoint(),
+    ///     sink.has_more(),
+    ///     sink.options().recv_hwm,
+    /// );
+    /// sink.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        !self.rx.is_disconnected()
+    }
+
+    /// Always `false`: each worker's reads run to completion in its own task,
+    /// so a cancelled `recv` never leaves a connection half-read.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        false
+    }
+
+    /// Get the endpoint the workers were accepted on.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Always `false`: `recv` hands out whole messages.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub const fn has_more(&self) -> bool {
+        false
+    }
+
+    /// Get the event state of the sink.
+    ///
+    /// `1` (POLLIN) when a merged message is waiting, otherwise `0`.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        u32::from(!self.buf.is_empty() || !self.rx.is_empty())
+    }
+
+    /// Change the options of every worker while they run.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect. Each
+    /// worker applies the change before its next read; a read already waiting
+    /// finishes under the old options.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        let diffs = self.options.update(f)?;
+        // A reader that has exited dropped its receiver; forget it.
+        self.option_txs
+            .retain(|tx| tx.send(self.options.clone()).is_ok());
+        Ok(diffs)
+    }
+
+    /// Close every worker connection, each honoring the `linger` option.
+    ///
+    /// Stops the readers, closes the sockets they read from and drops any
+    /// merged messages not yet received. Afterwards `recv` returns `Ok(None)`
+    /// and `is_connected` is `false`. All workers are closed even if one
+    /// fails; the first error is returned.
+    pub async fn close(&mut self) -> io::Result<()> {
+        drop(self.shutdown.take());
+        self.option_txs.clear();
+        let mut result = Ok(());
+        for reader in self.readers.drain(..) {
+            // `None`: the worker had already disconnected.
+            if let Some(pull) = join(reader).await {
+                let closed = pull.close().await;
+                if result.is_ok() {
+                    result = closed;
+                }
+            }
+        }
+        self.buf.clear();
+        // Every reader has exited and dropped its sender, so this ends.
+        while self.rx.try_recv().is_ok() {}
+        result
+    }
 }
 
 /// Drive one worker connection, forwarding each kernel-read batch into the merge
@@ -213,9 +353,29 @@ impl PullFanIn {
 ///
 /// Exits when the connection closes, the worker errors, or the sink drops the
 /// receiver (so `send_async` fails). Any of those just means this worker is done.
-async fn read_into_channel(mut pull: PullSocket<TcpStream>, tx: Sender<Batch>) {
+/// Options sent on `options` are applied before the next read.
+///
+/// When `shutdown` disconnects the reader stops at once, dropping a read or
+/// forward in progress, and returns the socket for the sink to close.
+async fn read_into_channel(
+    mut pull: PullSocket<TcpStream>,
+    tx: Sender<Batch>,
+    options: Receiver<SocketOptions>,
+    shutdown: Receiver<()>,
+) -> Option<PullSocket<TcpStream>> {
     loop {
-        match pull.recv_batch().await {
+        while let Ok(next) = options.try_recv() {
+            // PullFanIn::update_options has already validated the change.
+            let _ = pull.update_options(|o| *o = next);
+        }
+        let read = select_biased! {
+            _ = shutdown.recv_async().fuse() => None,
+            read = pull.recv_batch().fuse() => Some(read),
+        };
+        let Some(read) = read else {
+            return Some(pull);
+        };
+        match read {
             Ok(Some(batch)) => {
                 // Forward in chunks of at most MAX_ITEM_MESSAGES, preserving
                 // arrival order. A read that decoded fewer messages than the cap
@@ -228,13 +388,17 @@ async fn read_into_channel(mut pull: PullSocket<TcpStream>, tx: Sender<Batch>) {
                     if chunk.is_empty() {
                         break;
                     }
-                    if tx.send_async(chunk).await.is_err() {
-                        return;
+                    let sent = select_biased! {
+                        _ = shutdown.recv_async().fuse() => return Some(pull),
+                        sent = tx.send_async(chunk).fuse() => sent,
+                    };
+                    if sent.is_err() {
+                        return None;
                     }
                 }
             }
             // Connection closed or errored: this worker has nothing more to give.
-            _ => return,
+            _ => return None,
         }
     }
 }
@@ -2,10 +2,12 @@
 //!
 //! PUSH sockets are used in pipeline patterns for distributing tasks.
 
-use monocoque_core::monitor::{SocketEventSender, SocketMonitor, create_monitor};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::error::Error;
+use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::{TcpListener, TcpStream};
 use monocoque_zmtp::PushSocket as InternalPush;
+use monocoque_zmtp::codec::classify_error;
 use std::io;
 
 /// PUSH socket for distributing tasks in a pipeline.
@@ -38,7 +40,7 @@ impl PushSocket<TcpStream> {
     /// ```
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
-    ) -> io::Result<(TcpListener, Self)> {
+    ) -> Result<(TcpListener, Self), Error> {
         let listener = TcpListener::bind(addr).await?;
         let (stream, _) = listener.accept().await?;
         let socket = Self::from_tcp(stream).await?;
@@ -59,9 +61,9 @@ impl PushSocket<TcpStream> {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connect(addr: impl monocoque_core::rt::ToSocketAddrs) -> io::Result<Self> {
+    pub async fn connect(addr: impl monocoque_core::rt::ToSocketAddrs) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::connect(addr).await?,
+            inner: InternalPush::connect(addr).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -70,33 +72,34 @@ impl PushSocket<TcpStream> {
     pub async fn connect_with_options(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::connect_with_options(addr, options).await?,
+            inner: InternalPush::connect_with_options(addr, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
 
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.inner.is_connected()
-    }
-
     /// Try to reconnect to the stored endpoint.
-    pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.inner.try_reconnect().await
+    pub async fn try_reconnect(&mut self) -> Result<(), Error> {
+        self.inner.try_reconnect().await.map_err(classify_error)
     }
 
     /// Send with automatic reconnection on network error.
-    pub async fn send_with_reconnect(&mut self, msg: Vec<bytes::Bytes>) -> io::Result<()> {
-        self.inner.send_with_reconnect(msg).await
+    pub async fn send_with_reconnect(&mut self, msg: Vec<bytes::Bytes>) -> Result<(), Error> {
+        self.inner
+            .send_with_reconnect(msg)
+            .await
+            .map_err(classify_error)
     }
 
     /// Create a PUSH socket from a TCP stream.
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::from_tcp(stream).await?,
+            inner: InternalPush::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -105,9 +108,11 @@ impl PushSocket<TcpStream> {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::from_tcp_with_options(stream, options).await?,
+            inner: InternalPush::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -118,17 +123,19 @@ where
     S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
 {
     /// Create a PUSH socket from any stream.
-    pub async fn new(stream: S) -> io::Result<Self> {
+    pub async fn new(stream: S) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::new(stream).await?,
+            inner: InternalPush::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
 
     /// Create a PUSH socket from any stream with custom options.
-    pub async fn with_options(stream: S, options: SocketOptions) -> io::Result<Self> {
+    pub async fn with_options(stream: S, options: SocketOptions) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::with_options(stream, options).await?,
+            inner: InternalPush::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -140,16 +147,16 @@ where
     /// [`SocketOptions::with_write_coalescing`] and call [`flush`](Self::flush) after
     /// the last send in each burst. See `docs/performance.md` for measured numbers and
     /// an explanation of when each mode is appropriate.
-    pub async fn send(&mut self, msg: Vec<bytes::Bytes>) -> io::Result<()> {
-        self.inner.send(msg).await
+    pub async fn send(&mut self, msg: Vec<bytes::Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
     }
 
     /// Send a single-frame message without allocating a multipart `Vec`.
     ///
     /// Equivalent to `send(vec![frame])`, but avoids the per-message container
     /// allocation in single-frame hot paths.
-    pub async fn send_one(&mut self, frame: bytes::Bytes) -> io::Result<()> {
-        self.inner.send_one(frame).await
+    pub async fn send_one(&mut self, frame: bytes::Bytes) -> Result<(), Error> {
+        self.inner.send_one(frame).await.map_err(classify_error)
     }
 
     /// Flush any messages still buffered by write coalescing.
@@ -158,8 +165,8 @@ where
     /// In coalesced mode, bytes accumulate in a userspace buffer and are not guaranteed
     /// to reach the peer until this is called (or the 64 KB threshold fills naturally).
     /// Has no effect in eager mode (the default).
-    pub async fn flush(&mut self) -> io::Result<()> {
-        self.inner.flush().await
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush().await.map_err(classify_error)
     }
 
     /// Send a batch of messages in a single kernel write.
@@ -181,11 +188,11 @@ where
     /// ```
     ///
     /// Returns the number of messages sent.
-    pub async fn send_batch<I>(&mut self, msgs: I) -> io::Result<usize>
+    pub async fn send_batch<I>(&mut self, msgs: I) -> Result<usize, Error>
     where
         I: IntoIterator<Item = Vec<bytes::Bytes>>,
     {
-        self.inner.send_batch(msgs).await
+        self.inner.send_batch(msgs).await.map_err(classify_error)
     }
 
     /// Enable monitoring for this socket.
@@ -195,19 +202,150 @@ where
         receiver
     }
 
-    /// Get a mutable reference to this socket's options.
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PushSocket;
+    /// # fn check(mut socket: PushSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Get the endpoint this socket is connected/bound to, if available.
+    ///
+    /// Returns `None` if the socket was created from a raw stream.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> Option<&monocoque_core::endpoint::Endpoint> {
+        self.inner.last_endpoint()
+    }
+
+    /// Check if the last received message has more frames coming.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        self.inner.options_mut()
+    pub fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// Returns a bitmask: `1` (POLLIN) when ready to receive, `2` (POLLOUT)
+    /// when ready to send.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.inner.events()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PushSocket;
+    /// # async fn example(socket: PushSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 }
 
 #[cfg(unix)]
 impl PushSocket<monocoque_core::rt::UnixStream> {
     /// Create a PUSH socket from a Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::new(stream).await?,
+            inner: InternalPush::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -216,10 +354,60 @@ impl PushSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalPush::with_options(stream, options).await?,
+            inner: InternalPush::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
 }
+
+// Implement ProxySocket for the high-level PushSocket wrapper (backend of a
+// PUSH-PULL forwarder). PUSH never receives: its receive side stays pending
+// rather than returning `None`, so the proxy's select loop does not spin on it.
+impl<S> monocoque_zmtp::proxy::ProxySocket for PushSocket<S>
+where
+    S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
+{
+    fn recv_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+    ) -> ::core::pin::Pin<
+        Box<
+            dyn ::core::future::Future<Output = io::Result<Option<Vec<bytes::Bytes>>>>
+                + 'async_trait,
+        >,
+    >
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { std::future::pending().await })
+    }
+
+    fn send_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+        msg: Vec<bytes::Bytes>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send(msg).await?) })
+    }
+
+    fn socket_desc(&self) -> &'static str {
+        "PUSH"
+    }
+    fn send_multipart_batch<'life0, 'async_trait>(
+        &'life0 mut self,
+        msgs: Vec<Vec<bytes::Bytes>>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send_batch(msgs).await.map(drop)?) })
+    }
+}
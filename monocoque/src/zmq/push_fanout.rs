@@ -15,7 +15,9 @@
 //! Workers connect with an ordinary `PullSocket::connect`, so the worker side
 //! needs no special type.
 
-use monocoque_core::options::SocketOptions;
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::error::Error;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::{TcpListener, TcpStream};
 use std::io;
 
@@ -29,6 +31,10 @@ use super::PushSocket;
 pub struct PushFanOut {
     workers: Vec<PushSocket<TcpStream>>,
     next: usize,
+    /// Address of the listener the workers were accepted on.
+    endpoint: Endpoint,
+    /// Options applied to every worker, including ones accepted later.
+    options: SocketOptions,
 }
 
 impl PushFanOut {
@@ -55,7 +61,7 @@ impl PushFanOut {
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         n_workers: usize,
-    ) -> io::Result<(TcpListener, Self)> {
+    ) -> Result<(TcpListener, Self), Error> {
         Self::bind_with_options(addr, n_workers, SocketOptions::default()).await
     }
 
@@ -64,8 +70,8 @@ impl PushFanOut {
         addr: impl monocoque_core::rt::ToSocketAddrs,
         n_workers: usize,
         options: SocketOptions,
-    ) -> io::Result<(TcpListener, Self)> {
-        let listener = TcpListener::bind(addr).await?;
+    ) -> Result<(TcpListener, Self), Error> {
+        let listener = monocoque_core::rt::bind_listener(addr, &options).await?;
         let fanout = Self::accept_workers(&listener, n_workers, options).await?;
         Ok((listener, fanout))
     }
@@ -78,19 +84,28 @@ impl PushFanOut {
         listener: &TcpListener,
         n_workers: usize,
         options: SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
+        let endpoint = Endpoint::Tcp(listener.local_addr()?);
         let mut workers = Vec::with_capacity(n_workers);
         for _ in 0..n_workers {
             let (stream, _) = listener.accept().await?;
             workers.push(PushSocket::from_tcp_with_options(stream, options.clone()).await?);
         }
-        Ok(Self { workers, next: 0 })
+        Ok(Self {
+            workers,
+            next: 0,
+            endpoint,
+            options,
+        })
     }
 
     /// Accept one more worker on `listener` and add it to the pool.
-    pub async fn accept(&mut self, listener: &TcpListener) -> io::Result<()> {
+    ///
+    /// The worker gets the pool's current options.
+    pub async fn accept(&mut self, listener: &TcpListener) -> Result<(), Error> {
         let (stream, _) = listener.accept().await?;
-        self.workers.push(PushSocket::from_tcp(stream).await?);
+        self.workers
+            .push(PushSocket::from_tcp_with_options(stream, self.options.clone()).await?);
         Ok(())
     }
 
@@ -106,6 +121,97 @@ impl PushFanOut {
         self.workers.is_empty()
     }
 
+    /// Get a reference to the options applied to every worker.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        &self.options
+    }
+
+    /// Check if at least one worker is connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::PushFanOut;
+    /// # fn check(mut vent: PushFanOut) {
+    /// let healthy = vent.is_connected() && !vent.is_poisoned();
+    /// let writable = vent.events() & 2 != 0;
+    /// println!(
+    ///     "{} healthy={healthy} writable={writable} more={} hwm={}",
+    ///     vent.last_endpoint(),
+    ///     vent.has_more(),
+    ///     vent.options().send_hwm,
+    /// );
+    /// vent.update_options(|o| o.send_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.workers.iter().any(PushSocket::is_connected)
+    }
+
+    /// Check if every remaining worker is poisoned, so no send can succeed.
+    ///
+    /// A poisoned worker is dropped from the pool by the next `send` that
+    /// picks it.
+    #[inline]
+    pub fn is_poisoned(&self) -> bool {
+        !self.workers.is_empty() && self.workers.iter().all(PushSocket::is_poisoned)
+    }
+
+    /// Get the endpoint the workers were accepted on.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// Always `false`: PUSH sockets cannot receive.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub const fn has_more(&self) -> bool {
+        false
+    }
+
+    /// Get the event state of the pool.
+    ///
+    /// `2` (POLLOUT) when any worker is ready to send, otherwise `0`.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.workers.iter().fold(0, |events, w| events | w.events()) & 2
+    }
+
+    /// Change the options of every worker while they run.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    /// Workers accepted later get the new options too.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        let diffs = self.options.update(f)?;
+        for worker in &mut self.workers {
+            worker.update_options(|o| *o = self.options.clone())?;
+        }
+        Ok(diffs)
+    }
+
     /// Send one message to the next worker in round-robin order.
     ///
     /// Workers already known to be disconnected are skipped (and dropped from the
@@ -116,7 +222,7 @@ impl PushFanOut {
     /// The message bodies stay zero-copy (`Bytes` are refcounted) and the healthy
     /// path moves `msg` straight into the chosen worker, so it adds no per-message
     /// allocation over a plain `PushSocket::send`.
-    pub async fn send(&mut self, msg: Vec<bytes::Bytes>) -> io::Result<()> {
+    pub async fn send(&mut self, msg: Vec<bytes::Bytes>) -> Result<(), Error> {
         // Advance to the next worker that still looks connected, dropping any
         // known-dead ones on the way. This needs no copy of `msg`, so the common
         // all-healthy case moves the message in without an extra allocation.
@@ -148,17 +254,17 @@ impl PushFanOut {
             };
         }
 
-        Err(io::Error::new(
+        Err(Error::NotConnected(io::Error::new(
             io::ErrorKind::NotConnected,
             "PushFanOut has no live workers",
-        ))
+        )))
     }
 
     /// Send one single-frame message to the next worker in round-robin order.
     ///
     /// Equivalent to `send(vec![frame])`, but avoids the per-message multipart
     /// container allocation in single-frame hot paths.
-    pub async fn send_one(&mut self, frame: bytes::Bytes) -> io::Result<()> {
+    pub async fn send_one(&mut self, frame: bytes::Bytes) -> Result<(), Error> {
         while !self.workers.is_empty() {
             let idx = self.next % self.workers.len();
             if !self.workers[idx].is_connected() {
@@ -180,20 +286,34 @@ impl PushFanOut {
             };
         }
 
-        Err(io::Error::new(
+        Err(Error::NotConnected(io::Error::new(
             io::ErrorKind::NotConnected,
             "PushFanOut has no live workers",
-        ))
+        )))
     }
 
     /// Flush every worker's write-coalescing buffer.
     ///
     /// Call this after the last `send` in a burst when the workers were created
     /// with write coalescing enabled.
-    pub async fn flush(&mut self) -> io::Result<()> {
+    pub async fn flush(&mut self) -> Result<(), Error> {
         for worker in &mut self.workers {
             worker.flush().await?;
         }
         Ok(())
     }
+
+    /// Close every worker, each honoring the `linger` option.
+    ///
+    /// All workers are closed even if one fails; the first error is returned.
+    pub async fn close(self) -> io::Result<()> {
+        let mut result = Ok(());
+        for worker in self.workers {
+            let closed = worker.close().await;
+            if result.is_ok() {
+                result = closed;
+            }
+        }
+        result
+    }
 }
@@ -1,11 +1,12 @@
 //! REP socket implementation.
 
-use super::common::channel_to_io_error;
 use bytes::Bytes;
-use monocoque_core::monitor::{SocketEventSender, SocketMonitor, create_monitor};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::error::Error;
+use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::{TcpListener, TcpStream};
 use monocoque_zmtp::SocketType;
+use monocoque_zmtp::codec::classify_error;
 use monocoque_zmtp::rep::RepSocket as InternalRep;
 use std::io;
 
@@ -78,7 +79,7 @@ impl RepSocket {
     /// ```
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
-    ) -> io::Result<(TcpListener, Self)> {
+    ) -> Result<(TcpListener, Self), Error> {
         let listener = TcpListener::bind(addr).await?;
         let (stream, _) = listener.accept().await?;
         let socket = Self::from_tcp(stream).await?;
@@ -89,8 +90,8 @@ impl RepSocket {
     pub async fn bind_with_options(
         addr: impl monocoque_core::rt::ToSocketAddrs,
         options: SocketOptions,
-    ) -> io::Result<(TcpListener, Self)> {
-        let listener = TcpListener::bind(addr).await?;
+    ) -> Result<(TcpListener, Self), Error> {
+        let listener = monocoque_core::rt::bind_listener(addr, &options).await?;
         let (stream, _) = listener.accept().await?;
         let socket = Self::from_tcp_with_options(stream, options).await?;
         Ok((listener, socket))
@@ -99,9 +100,11 @@ impl RepSocket {
     /// Create a REP socket from an existing TCP stream.
     ///
     /// Create a REP socket from a TCP stream with TCP_NODELAY enabled.
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRep::from_tcp(stream).await?,
+            inner: InternalRep::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -110,9 +113,11 @@ impl RepSocket {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRep::with_options(stream, options).await?,
+            inner: InternalRep::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -121,12 +126,14 @@ impl RepSocket {
     pub async fn with_options<Stream>(
         stream: Stream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<RepSocket<Stream>>
+    ) -> Result<RepSocket<Stream>, Error>
     where
         Stream: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
     {
         Ok(RepSocket {
-            inner: InternalRep::with_options(stream, options).await?,
+            inner: InternalRep::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -170,8 +177,8 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        self.inner.recv().await
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
     }
 
     /// Get the socket type.
@@ -199,7 +206,8 @@ where
     /// Check if the last received message has more frames coming.
     ///
     /// Returns `true` if there are more frames in the current multipart message.
-    ///\n    /// # ZeroMQ Compatibility
+    ///
+    /// # ZeroMQ Compatibility
     ///
     /// Corresponds to `ZMQ_RCVMORE` (13) option.
     #[inline]
@@ -258,14 +266,139 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        channel_to_io_error(self.inner.send(msg).await)
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Receive a request, answer it with `f` and send the reply.
+    ///
+    /// If the reply cannot be sent, the socket goes back to awaiting a
+    /// request, so the next `recv_send` can go ahead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receive or the send fails, including when the
+    /// connection closes before a request arrives.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::RepSocket;
+    ///
+    /// # async fn example(socket: &mut RepSocket) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Echo server
+    /// loop {
+    ///     socket.recv_send(|request| request).await?;
+    /// }
+    /// # }
+    /// ```
+    pub async fn recv_send<F>(&mut self, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(Vec<Bytes>) -> Vec<Bytes>,
+    {
+        self.inner.recv_send(f).await.map_err(classify_error)
+    }
+
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::RepSocket;
+    /// # fn check(mut socket: RepSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::RepSocket;
+    /// # async fn example(socket: RepSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
     }
 
-    /// Get a mutable reference to this socket's options.
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        self.inner.options_mut()
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 }
 
@@ -273,9 +406,9 @@ where
 #[cfg(unix)]
 impl RepSocket<monocoque_core::rt::UnixStream> {
     /// Create a REP socket from an existing Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRep::new(stream).await?,
+            inner: InternalRep::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -286,9 +419,11 @@ impl RepSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRep::with_options(stream, options).await?,
+            inner: InternalRep::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -1,10 +1,13 @@
 //! REQ socket implementation.
 
-use super::common::channel_to_io_error;
+use super::common::parse_tcp_endpoint;
 use bytes::Bytes;
+use monocoque_core::error::Error;
 use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
 use monocoque_zmtp::SocketType;
+use monocoque_zmtp::codec::classify_error;
 use monocoque_zmtp::req::ReqSocket as InternalReq;
 use std::io;
 
@@ -82,7 +85,7 @@ impl ReqSocket {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connect(endpoint: &str) -> io::Result<Self> {
+    pub async fn connect(endpoint: &str) -> Result<Self, Error> {
         // Try parsing as endpoint, fall back to raw address
         let addr = if let Ok(monocoque_core::endpoint::Endpoint::Tcp(a)) =
             monocoque_core::endpoint::Endpoint::parse(endpoint)
@@ -95,7 +98,7 @@ impl ReqSocket {
         };
 
         let sock = Self {
-            inner: InternalReq::connect(addr).await?,
+            inner: InternalReq::connect(addr).await.map_err(classify_error)?,
             monitor: None,
         };
         sock.emit_event(SocketEvent::Connected(
@@ -104,25 +107,103 @@ impl ReqSocket {
         Ok(sock)
     }
 
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.inner.is_connected()
+    /// Reconnect to the stored endpoint after the connection was lost or
+    /// the socket was poisoned by cancelled I/O.
+    ///
+    /// Makes one attempt (after the reconnect backoff delay). On success the
+    /// poisoned flag and all buffered state are cleared and the socket is
+    /// ready for a new request; a reply owed on the old connection is lost.
+    /// Sockets not created with `connect` have no endpoint to dial and fail
+    /// with `Unsupported`.
+    ///
+    /// Emits `ConnectRetried` before the attempt and `Connected` or
+    /// `ConnectFailed` after it, if monitoring is enabled.
+    pub async fn reconnect(&mut self) -> Result<(), Error> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        if let Some(endpoint) = &endpoint {
+            self.emit_event(SocketEvent::ConnectRetried {
+                endpoint: endpoint.clone(),
+                attempt: self.inner.reconnect_attempt() + 1,
+            });
+        }
+        let result = self.inner.try_reconnect().await.map_err(classify_error);
+        if let Some(endpoint) = endpoint {
+            match &result {
+                Ok(()) => self.emit_connected(endpoint),
+                Err(e) => self.emit_event(SocketEvent::ConnectFailed {
+                    endpoint,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        result
     }
 
     /// Try to reconnect to the stored endpoint.
-    pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.inner.try_reconnect().await
+    ///
+    /// Same as [`reconnect`](Self::reconnect).
+    pub async fn try_reconnect(&mut self) -> Result<(), Error> {
+        self.reconnect().await
     }
 
     /// Send with automatic reconnection on network error.
-    pub async fn send_with_reconnect(&mut self, msg: Vec<bytes::Bytes>) -> io::Result<()> {
-        self.inner.send_with_reconnect(msg).await
+    pub async fn send_with_reconnect(&mut self, msg: Vec<bytes::Bytes>) -> Result<(), Error> {
+        self.inner
+            .send_with_reconnect(msg)
+            .await
+            .map_err(classify_error)
     }
 
     /// Receive with automatic reconnection on EOF or network error.
-    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
-        self.inner.recv_with_reconnect().await
+    pub async fn recv_with_reconnect(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
+        self.inner
+            .recv_with_reconnect()
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Connect and keep the connection up on its own.
+    ///
+    /// [`send`](Self::send) on the returned socket reconnects with backoff
+    /// when the connection is down. If the connection drops while
+    /// [`recv`](Self::recv) awaits a reply, `recv` reconnects and returns
+    /// `ConnectionReset`; the request can then be sent again.
+    /// Gives up with `NotConnected` once `max_reconnect_attempts`
+    /// consecutive attempts have failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::ReqSocket;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut socket = ReqSocket::connect_with_reconnect("tcp://127.0.0.1:5555").await?;
+    /// socket.send(vec![bytes::Bytes::from("ping")]).await?;
+    /// let reply = socket.recv().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_reconnect(endpoint: &str) -> Result<Self, Error> {
+        Self::connect_with_reconnect_options(endpoint, SocketOptions::default()).await
+    }
+
+    /// [`connect_with_reconnect`](Self::connect_with_reconnect) with custom
+    /// options.
+    pub async fn connect_with_reconnect_options(
+        endpoint: &str,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        let addr = parse_tcp_endpoint(endpoint)?;
+        let sock = Self {
+            inner: InternalReq::connect_with_reconnect_options(addr, options)
+                .await
+                .map_err(classify_error)?,
+            monitor: None,
+        };
+        sock.emit_event(SocketEvent::Connected(
+            monocoque_core::endpoint::Endpoint::Tcp(addr),
+        ));
+        Ok(sock)
     }
 
     /// Connect to a ZeroMQ peer with custom socket options.
@@ -151,7 +232,7 @@ impl ReqSocket {
     pub async fn connect_with_options(
         endpoint: &str,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         let addr = if let Ok(monocoque_core::endpoint::Endpoint::Tcp(a)) =
             monocoque_core::endpoint::Endpoint::parse(endpoint)
         {
@@ -186,7 +267,9 @@ impl ReqSocket {
     /// # }
     /// ```
     #[cfg(unix)]
-    pub async fn connect_ipc(path: &str) -> io::Result<ReqSocket<monocoque_core::rt::UnixStream>> {
+    pub async fn connect_ipc(
+        path: &str,
+    ) -> Result<ReqSocket<monocoque_core::rt::UnixStream>, Error> {
         use std::path::PathBuf;
 
         let clean_path = path.strip_prefix("ipc://").unwrap_or(path);
@@ -201,9 +284,11 @@ impl ReqSocket {
     }
 
     /// Create a REQ socket from a TCP stream with TCP_NODELAY enabled.
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalReq::from_tcp(stream).await?,
+            inner: InternalReq::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -212,9 +297,11 @@ impl ReqSocket {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalReq::from_tcp_with_options(stream, options).await?,
+            inner: InternalReq::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -223,15 +310,24 @@ impl ReqSocket {
     pub async fn with_options<Stream>(
         stream: Stream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<ReqSocket<Stream>>
+    ) -> Result<ReqSocket<Stream>, Error>
     where
         Stream: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
     {
         Ok(ReqSocket {
-            inner: InternalReq::with_options(stream, options).await?,
+            inner: InternalReq::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
+
+    /// Structured diagnostics of the socket (state, non-default options,
+    /// buffer usage, connection history), serializable with the `serde`
+    /// feature.
+    pub fn snapshot(&self) -> monocoque_zmtp::DiagnosticsSnapshot {
+        self.inner.snapshot()
+    }
 }
 
 // Generic impl - works with any stream type
@@ -241,10 +337,16 @@ where
 {
     /// Enable monitoring for this socket.
     ///
-    /// Returns a receiver for socket lifecycle events.
+    /// Returns a receiver for socket lifecycle events. If the socket is already
+    /// connected, a `Connected` event for the current peer is emitted first.
     pub fn monitor(&mut self) -> SocketMonitor {
         let (sender, receiver) = create_monitor();
         self.monitor = Some(sender);
+        if self.inner.is_connected()
+            && let Some(endpoint) = self.inner.last_endpoint().cloned()
+        {
+            self.emit_connected(endpoint);
+        }
         receiver
     }
 
@@ -255,6 +357,17 @@ where
         }
     }
 
+    /// Emit `Connected` for `endpoint` carrying the peer's handshake metadata.
+    fn emit_connected(&self, endpoint: monocoque_core::endpoint::Endpoint) {
+        if let Some(monitor) = &self.monitor {
+            monocoque_core::monitor::emit_with_metadata(
+                monitor,
+                SocketEvent::Connected(endpoint),
+                self.inner.peer_metadata().clone(),
+            );
+        }
+    }
+
     /// Send a multipart message.
     ///
     /// This enforces the REQ state machine - you must call `recv()` before
@@ -288,8 +401,8 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        channel_to_io_error(self.inner.send(msg).await)
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
     }
 
     /// Get the socket type.
@@ -369,8 +482,48 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        self.inner.recv().await
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        let msg = self.inner.recv().await.map_err(classify_error)?;
+        if msg.is_none()
+            && let Some(endpoint) = self.inner.last_endpoint().cloned()
+        {
+            self.emit_event(SocketEvent::Disconnected(endpoint));
+        }
+        Ok(msg)
+    }
+
+    /// Send a request and wait for its reply.
+    ///
+    /// A failed or timed-out receive puts the socket back in the idle state,
+    /// so the next `send_recv` can go ahead. A reply to the abandoned request
+    /// that arrives later would be taken for the next one's; set
+    /// `req_correlate` to have it rejected instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the send or the receive fails, including when the
+    /// connection closes before the reply arrives.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::ReqSocket;
+    /// use bytes::Bytes;
+    ///
+    /// # async fn example(socket: &mut ReqSocket) -> Result<(), Box<dyn std::error::Error>> {
+    /// let reply = socket.send_recv(vec![Bytes::from("Hello")]).await?;
+    /// println!("Got {} frames", reply.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_recv(&mut self, request: Vec<Bytes>) -> Result<Vec<Bytes>, Error> {
+        let result = self.inner.send_recv(request).await;
+        if matches!(&result, Err(e) if e.kind() == io::ErrorKind::UnexpectedEof)
+            && let Some(endpoint) = self.inner.last_endpoint().cloned()
+        {
+            self.emit_event(SocketEvent::Disconnected(endpoint));
+        }
+        result.map_err(classify_error)
     }
 
     /// Get a reference to the socket options.
@@ -389,7 +542,94 @@ where
         self.inner.options()
     }
 
-    /// Get a mutable reference to the socket options.
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::ReqSocket;
+    /// # fn check(mut socket: ReqSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::ReqSocket;
+    /// # async fn example(socket: ReqSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
+    }
+
+    /// Change the socket options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option; nothing is
+    /// applied in that case.
     ///
     /// # Example
     ///
@@ -398,14 +638,24 @@ where
     /// use std::time::Duration;
     ///
     /// # async fn example(socket: &mut ReqSocket) {
-    /// socket.options_mut().recv_timeout = Some(Duration::from_secs(30));
+    /// socket
+    ///     .update_options(|o| o.recv_timeout = Some(Duration::from_secs(30)))
+    ///     .unwrap();
     /// # }
     /// ```
-    pub fn options_mut(&mut self) -> &mut monocoque_core::options::SocketOptions {
-        self.inner.options_mut()
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 
-    /// Set the socket options.
+    /// Replace the socket options.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` differs from the current options in a
+    /// construct-only field.
     ///
     /// # Example
     ///
@@ -419,11 +669,14 @@ where
     ///     .with_send_timeout(Duration::from_secs(5))
     ///     .with_recv_timeout(Duration::from_secs(10));
     ///
-    /// socket.set_options(options);
+    /// socket.set_options(options).unwrap();
     /// # }
     /// ```
-    pub fn set_options(&mut self, options: monocoque_core::options::SocketOptions) {
-        self.inner.set_options(options);
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.set_options(options)
     }
 }
 
@@ -431,9 +684,9 @@ where
 #[cfg(unix)]
 impl ReqSocket<monocoque_core::rt::UnixStream> {
     /// Create a REQ socket from an existing Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalReq::new(stream).await?,
+            inner: InternalReq::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -444,9 +697,11 @@ impl ReqSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalReq::with_options(stream, options).await?,
+            inner: InternalReq::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
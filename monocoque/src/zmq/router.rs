@@ -1,11 +1,17 @@
 //! ROUTER socket implementation.
 
-use super::common::channel_to_io_error;
+use super::FrameReader;
+use super::RouterHubSocket;
+use super::common::parse_tcp_endpoint;
 use bytes::Bytes;
-use monocoque_core::monitor::{SocketEventSender, SocketMonitor, create_monitor};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::error::{Error, TrySendError};
+use monocoque_core::monitor::{
+    HwmWatch, SocketEvent, SocketEventSender, SocketMonitor, create_monitor,
+};
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::{TcpListener, TcpStream};
 use monocoque_zmtp::SocketType;
+use monocoque_zmtp::codec::classify_error;
 use monocoque_zmtp::router::RouterSocket as InternalRouter;
 use std::io;
 
@@ -28,6 +34,9 @@ use std::io;
 /// **Incoming**: `[identity, delimiter, ...user_frames]`\
 /// **Outgoing**: `[identity, delimiter, ...user_frames]` (routes to peer with that identity)
 ///
+/// [`recv_from`](Self::recv_from) and [`send_to`](Self::send_to) take the
+/// identity apart from the user frames and handle the delimiter.
+///
 /// ## Example
 ///
 /// ```rust,no_run
@@ -38,9 +47,8 @@ use std::io;
 /// let (listener, mut socket) = RouterSocket::bind("127.0.0.1:5555").await?;
 ///
 /// // Echo server
-/// while let Ok(Some(msg)) = socket.recv().await {
-///     // msg[0] = identity, msg[1] = delimiter, msg[2+] = payload
-///     socket.send(msg).await?; // Echo back to sender
+/// while let Ok(Some((identity, body))) = socket.recv_from().await {
+///     socket.send_to(&identity, body).await?; // Echo back to sender
 /// }
 /// # Ok(())
 /// # }
@@ -51,6 +59,7 @@ where
 {
     inner: InternalRouter<S>,
     monitor: Option<SocketEventSender>,
+    hwm: HwmWatch,
 }
 
 impl RouterSocket {
@@ -89,13 +98,88 @@ impl RouterSocket {
     /// ```
     pub async fn bind(
         addr: impl monocoque_core::rt::ToSocketAddrs,
-    ) -> io::Result<(TcpListener, Self)> {
+    ) -> Result<(TcpListener, Self), Error> {
         let listener = TcpListener::bind(addr).await?;
         let (stream, _) = listener.accept().await?;
         let socket = Self::from_tcp(stream).await?;
         Ok((listener, socket))
     }
 
+    /// Connect and keep the connection up on its own.
+    ///
+    /// Client-side counterpart of [`bind`](Self::bind): [`recv`](Self::recv)
+    /// and [`send`](Self::send) on the returned socket re-establish a lost
+    /// connection with backoff and carry on.
+    /// Gives up with `NotConnected` once `max_reconnect_attempts`
+    /// consecutive attempts have failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::RouterSocket;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut socket = RouterSocket::connect_with_reconnect("tcp://127.0.0.1:5555").await?;
+    /// while let Some(msg) = socket.recv().await? {
+    ///     socket.send(msg).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_reconnect(endpoint: &str) -> Result<Self, Error> {
+        Self::connect_with_reconnect_options(endpoint, SocketOptions::default()).await
+    }
+
+    /// [`connect_with_reconnect`](Self::connect_with_reconnect) with custom
+    /// options.
+    pub async fn connect_with_reconnect_options(
+        endpoint: &str,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        let addr = parse_tcp_endpoint(endpoint)?;
+        Ok(Self {
+            inner: InternalRouter::connect_with_reconnect_options(addr, options)
+                .await
+                .map_err(classify_error)?,
+            monitor: None,
+            hwm: HwmWatch::default(),
+        })
+    }
+
+    /// Serve every peer that connects to `listener`.
+    ///
+    /// [`bind`](Self::bind) stops at the first connection. `accept_loop`
+    /// instead returns a multi-peer [`RouterHubSocket`] and the future that
+    /// drives it: the future accepts connections, handshakes each in its own
+    /// task, and routes `send()` by the identity in frame 0. Spawn the future
+    /// on the current runtime; dropping the socket stops it.
+    ///
+    /// Received messages are `[identity, "", body...]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::{RouterSocket, SocketOptions};
+    /// use monocoque_core::rt::{self, TcpListener};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let listener = TcpListener::bind("127.0.0.1:5555").await?;
+    /// let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+    /// rt::spawn_detached(driver);
+    ///
+    /// while let Some(msg) = router.recv().await? {
+    ///     router.send(msg).await?; // Echo back to whichever peer sent it
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn accept_loop(
+        listener: TcpListener,
+        options: SocketOptions,
+    ) -> (RouterHubSocket, impl std::future::Future<Output = ()>) {
+        RouterHubSocket::accept_loop(listener, options)
+    }
+
     /// Create a ROUTER socket from an existing TCP stream.
     ///
     /// **Deprecated**: Use [`RouterSocket::from_tcp()`] instead to enable TCP_NODELAY for optimal latency.
@@ -128,18 +212,22 @@ impl RouterSocket {
         since = "0.1.0",
         note = "Use `from_tcp()` instead to enable TCP_NODELAY"
     )]
-    pub async fn from_stream(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_stream(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRouter::new(stream).await?,
+            inner: InternalRouter::new(stream).await.map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
     /// Create a ROUTER socket from an existing TCP stream with TCP_NODELAY enabled.
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRouter::from_tcp(stream).await?,
+            inner: InternalRouter::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
@@ -147,10 +235,13 @@ impl RouterSocket {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRouter::from_tcp_with_options(stream, options).await?,
+            inner: InternalRouter::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
@@ -158,13 +249,16 @@ impl RouterSocket {
     pub async fn with_options<Stream>(
         stream: Stream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<RouterSocket<Stream>>
+    ) -> Result<RouterSocket<Stream>, Error>
     where
         Stream: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
     {
         Ok(RouterSocket {
-            inner: InternalRouter::with_options(stream, options).await?,
+            inner: InternalRouter::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 }
@@ -203,10 +297,139 @@ where
         receiver
     }
 
-    /// Get a mutable reference to this socket's options.
+    /// Emit `SendHwmReached` / `SendHwmCleared` when the buffered message
+    /// count crosses `send_hwm`.
+    fn watch_hwm(&mut self) {
+        let hwm = self.inner.options().send_hwm;
+        if let Some(event) = self.hwm.observe(self.inner.buffered_messages(), hwm)
+            && let Some(monitor) = &self.monitor
+        {
+            monocoque_core::monitor::emit(monitor, event);
+        }
+    }
+
+    /// Get immutable access to socket options.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        self.inner.options_mut()
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::RouterSocket;
+    /// # fn check(mut socket: RouterSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Routing identities of the connected peers.
+    ///
+    /// A socket from [`bind`](RouterSocket::bind) or `from_tcp` serves one
+    /// connection, so this holds at most one identity; use
+    /// [`accept_loop`](RouterSocket::accept_loop) for a multi-peer ROUTER.
+    #[inline]
+    pub fn connected_peers(&self) -> Vec<Bytes> {
+        self.inner.connected_peers()
+    }
+
+    /// Check whether the peer with routing identity `id` is connected.
+    #[inline]
+    pub fn is_peer_connected(&self, id: &[u8]) -> bool {
+        self.inner.is_peer_connected(id)
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` option.
+    #[inline]
+    pub const fn duplicates_dropped(&self) -> u64 {
+        self.inner.duplicates_dropped()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::RouterSocket;
+    /// # async fn example(socket: RouterSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 
     /// Send a multipart message.
@@ -232,25 +455,71 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn send(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        channel_to_io_error(self.inner.send(msg).await)
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Send `frames` to the peer with routing id `identity`.
+    ///
+    /// Builds the `[identity, "", frames...]` envelope that
+    /// [`send`](Self::send) takes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::RouterSocket;
+    /// # use bytes::Bytes;
+    /// # async fn example(mut socket: RouterSocket) -> Result<(), Box<dyn std::error::Error>> {
+    /// if let Some((identity, body)) = socket.recv_from().await? {
+    ///     socket.send_to(&identity, body).await?; // Echo back to sender
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_to(&mut self, identity: &[u8], frames: Vec<Bytes>) -> Result<(), Error> {
+        self.inner
+            .send_to(identity, frames)
+            .await
+            .map_err(classify_error)
     }
 
     /// Send a message to the internal buffer without flushing.
     ///
     /// Use this for batching multiple messages before a single flush.
-    pub fn send_buffered(&mut self, msg: Vec<Bytes>) -> io::Result<()> {
-        channel_to_io_error(self.inner.send_buffered(msg))
+    pub fn send_buffered(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        let result = self.inner.send_buffered(msg).map_err(classify_error);
+        self.watch_hwm();
+        result
+    }
+
+    /// Queue a message without ever suspending; it goes out with the next
+    /// [`flush()`](Self::flush).
+    ///
+    /// See [`monocoque_zmtp::RouterSocket::try_send`] for the error cases;
+    /// [`TrySendError::Full`] and [`TrySendError::WouldBlock`] hand the
+    /// message back.
+    pub fn try_send(&mut self, msg: Vec<Bytes>) -> Result<(), TrySendError> {
+        let result = self.inner.try_send(msg);
+        self.watch_hwm();
+        result
     }
 
     /// Flush all buffered messages to the network.
-    pub async fn flush(&mut self) -> io::Result<()> {
-        channel_to_io_error(self.inner.flush().await)
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        let result = self.inner.flush().await.map_err(classify_error);
+        self.watch_hwm();
+        result
     }
 
     /// Send multiple messages in a single batch.
-    pub async fn send_batch(&mut self, messages: &[Vec<Bytes>]) -> io::Result<()> {
-        channel_to_io_error(self.inner.send_batch(messages).await)
+    pub async fn send_batch(&mut self, messages: &[Vec<Bytes>]) -> Result<(), Error> {
+        let result = self
+            .inner
+            .send_batch(messages)
+            .await
+            .map_err(classify_error);
+        self.watch_hwm();
+        result
     }
 
     /// Get the number of bytes currently buffered.
@@ -259,6 +528,19 @@ where
         self.inner.buffered_bytes()
     }
 
+    /// Get the current capacities of the socket's userspace buffers.
+    #[inline]
+    pub fn buffer_stats(&self) -> monocoque_core::config::BufferStats {
+        self.inner.buffer_stats()
+    }
+
+    /// Structured diagnostics of the socket, serializable with the `serde`
+    /// feature. While connected, `peers` lists the peer and the messages
+    /// buffered for it.
+    pub fn snapshot(&self) -> monocoque_zmtp::DiagnosticsSnapshot {
+        self.inner.snapshot()
+    }
+
     /// Get the socket type.
     ///
     /// # ZeroMQ Compatibility
@@ -349,17 +631,17 @@ where
     pub fn set_connect_routing_id(&mut self, id: Vec<u8>) -> io::Result<()> {
         // Validate identity for ROUTER socket
         monocoque_core::options::SocketOptions::validate_router_identity(&id)?;
-        self.inner.options_mut().connect_routing_id = Some(Bytes::from(id));
+        self.inner
+            .update_options(|o| o.connect_routing_id = Some(Bytes::from(id)))?;
         Ok(())
     }
 
     /// Enable or disable ROUTER_MANDATORY mode.
     ///
-    /// When enabled, sending to an unknown identity returns an error.
-    /// When disabled (default), messages to unknown identities are silently dropped.
-    ///
-    /// **Note**: The current single-peer ROUTER implementation doesn't have a
-    /// routing table yet, so this option affects future multi-peer support.
+    /// When enabled, sending to an identity other than the connected peer's
+    /// returns `HostUnreachable` (libzmq's `EHOSTUNREACH`). When disabled
+    /// (default), such messages are silently dropped. The multi-peer socket
+    /// from [`accept_loop`](Self::accept_loop) has the same setting.
     ///
     /// # ZeroMQ Compatibility
     ///
@@ -378,7 +660,7 @@ where
     /// # }
     /// ```
     pub fn set_router_mandatory(&mut self, enabled: bool) {
-        self.inner.options_mut().router_mandatory = enabled;
+        self.inner.set_router_mandatory(enabled);
     }
 
     /// Enable or disable ROUTER_HANDOVER mode.
@@ -407,7 +689,9 @@ where
     /// # }
     /// ```
     pub fn set_router_handover(&mut self, enabled: bool) {
-        self.inner.options_mut().router_handover = enabled;
+        self.inner
+            .update_options(|o| o.router_handover = enabled)
+            .expect("router_handover is not construct-only");
     }
 
     /// Get the peer identity for this connection.
@@ -453,8 +737,49 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        self.inner.recv().await
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Receive a message as `(identity, body)`.
+    ///
+    /// The delimiter frame between the identity and the body is stripped
+    /// when the peer sent one. Returns `None` if the connection is closed.
+    pub async fn recv_from(&mut self) -> Result<Option<(Bytes, Vec<Bytes>)>, Error> {
+        self.inner.recv_from().await.map_err(classify_error)
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<bytes::Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        }))
+    }
+
+    /// Receive the next body frame in chunks, without buffering frames larger
+    /// than the `stream_threshold` option.
+    ///
+    /// Use this once `recv()` fails with `ZmtpError::StreamingRequired`; the
+    /// sender is [`peer_identity()`](Self::peer_identity). See
+    /// [`FrameReader`] for the chunk API.
+    pub async fn recv_frame_streaming(&mut self) -> Result<Option<FrameReader<'_, S>>, Error> {
+        self.inner
+            .recv_frame_streaming()
+            .await
+            .map_err(classify_error)
     }
 }
 
@@ -462,10 +787,11 @@ where
 #[cfg(unix)]
 impl RouterSocket<monocoque_core::rt::UnixStream> {
     /// Create a ROUTER socket from an existing Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRouter::new(stream).await?,
+            inner: InternalRouter::new(stream).await.map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 
@@ -475,10 +801,37 @@ impl RouterSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalRouter::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
+            monitor: None,
+            hwm: HwmWatch::default(),
+        })
+    }
+}
+
+#[cfg(feature = "tls")]
+impl RouterSocket<monocoque_zmtp::tls::TlsStream<TcpStream>> {
+    /// Accept the next connection on `listener` over TLS and create a ROUTER
+    /// socket for it.
+    ///
+    /// The TLS handshake completes before the ZMTP greeting; a client that
+    /// rejects the certificate surfaces here as a
+    /// [`TlsError`](monocoque_zmtp::tls::TlsError) inside the returned
+    /// `io::Error`.
+    pub async fn accept_tls(
+        listener: &TcpListener,
+        tls: &monocoque_zmtp::tls::TlsAcceptorConfig,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalRouter::with_options(stream, options).await?,
+            inner: InternalRouter::accept_tls(listener, tls, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
+            hwm: HwmWatch::default(),
         })
     }
 }
@@ -494,7 +847,7 @@ impl monocoque_zmtp::proxy::ProxySocket for RouterSocket<TcpStream> {
         'life0: 'async_trait,
         Self: 'async_trait,
     {
-        Box::pin(async move { self.recv().await })
+        Box::pin(async move { Ok(self.recv().await?) })
     }
 
     fn send_multipart<'life0, 'async_trait>(
@@ -505,10 +858,20 @@ impl monocoque_zmtp::proxy::ProxySocket for RouterSocket<TcpStream> {
         'life0: 'async_trait,
         Self: 'async_trait,
     {
-        Box::pin(async move { self.send(msg).await })
+        Box::pin(async move { Ok(self.send(msg).await?) })
     }
 
     fn socket_desc(&self) -> &'static str {
         "ROUTER"
     }
+    fn send_multipart_batch<'life0, 'async_trait>(
+        &'life0 mut self,
+        msgs: Vec<Vec<Bytes>>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send_batch(&msgs).await?) })
+    }
 }
@@ -0,0 +1,179 @@
+//! ROUTER socket serving every peer accepted on a listener.
+
+use bytes::Bytes;
+use monocoque_core::error::Error;
+use monocoque_core::options::SocketOptions;
+use monocoque_core::rt::TcpListener;
+use monocoque_zmtp::codec::classify_error;
+use monocoque_zmtp::router_hub::RouterHubSocket as InternalHub;
+use monocoque_zmtp::security::Principal;
+use monocoque_zmtp::{FlushOutcome, MessageMeta, PeerSnapshot};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Multi-peer ROUTER socket.
+///
+/// Created by [`accept_loop`](Self::accept_loop) or
+/// [`RouterSocket::accept_loop`](crate::zmq::RouterSocket::accept_loop).
+/// Received messages are `[identity, "", body...]`, and `send` routes by the
+/// identity in frame 0. Dropping the socket stops the accept loop and closes
+/// every peer.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use monocoque::zmq::{RouterHubSocket, SocketOptions};
+/// use monocoque_core::rt::{self, TcpListener};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let listener = TcpListener::bind("127.0.0.1:5555").await?;
+/// let (mut router, driver) = RouterHubSocket::accept_loop(listener, SocketOptions::default());
+/// rt::spawn_detached(driver);
+///
+/// while let Some((identity, body)) = router.recv_from().await? {
+///     router.send_to(&identity, body).await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct RouterHubSocket {
+    inner: InternalHub,
+}
+
+impl RouterHubSocket {
+    /// Serve ROUTER peers on `listener`.
+    ///
+    /// Returns the socket together with the future that drives it; spawn the
+    /// future on the current runtime. `options` applies to every accepted
+    /// peer. See [`monocoque_zmtp::router_hub::RouterHubSocket::accept_loop`].
+    pub fn accept_loop(
+        listener: TcpListener,
+        options: SocketOptions,
+    ) -> (Self, impl Future<Output = ()>) {
+        let (inner, driver) = InternalHub::accept_loop(listener, options);
+        (Self { inner }, driver)
+    }
+
+    /// Receive the next message from any peer as `[identity, "", body...]`.
+    ///
+    /// Returns `Ok(None)` once the driver future has finished and every peer
+    /// reader has exited.
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// [`recv`](Self::recv), also reporting who sent the message.
+    pub async fn recv_with_meta(&mut self) -> Result<Option<(Vec<Bytes>, MessageMeta)>, Error> {
+        self.inner.recv_with_meta().await.map_err(classify_error)
+    }
+
+    /// Receive the next message from any peer as `(identity, body)`.
+    pub async fn recv_from(&mut self) -> Result<Option<(Bytes, Vec<Bytes>)>, Error> {
+        self.inner.recv_from().await.map_err(classify_error)
+    }
+
+    /// Route `msg` to the peer named by its first frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for an empty message, `HostUnreachable` under
+    /// `router_mandatory` when no connected peer has the identity, and
+    /// `BrokenPipe` once the driver future has stopped.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Route `frames` to the peer with routing id `identity`.
+    ///
+    /// # Errors
+    ///
+    /// As [`send`](Self::send).
+    pub async fn send_to(&mut self, identity: &[u8], frames: Vec<Bytes>) -> Result<(), Error> {
+        self.inner
+            .send_to(identity, frames)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Routing identities of the peers currently connected.
+    pub fn connected_peers(&self) -> Vec<Bytes> {
+        self.inner.connected_peers()
+    }
+
+    /// Check whether a peer with routing identity `id` is connected.
+    pub fn is_peer_connected(&self, id: &[u8]) -> bool {
+        self.inner.is_peer_connected(id)
+    }
+
+    /// Who the peer with routing identity `id` authenticated as.
+    pub fn peer_principal(&self, id: &[u8]) -> Option<Arc<Principal>> {
+        self.inner.peer_principal(id)
+    }
+
+    /// The peers currently connected, in no particular order.
+    pub fn peers(&self) -> Vec<PeerSnapshot> {
+        self.inner.peers()
+    }
+
+    /// Messages queued for the peer `id` and not yet written.
+    pub fn pending_for(&self, id: &[u8]) -> usize {
+        self.inner.pending_for(id)
+    }
+
+    /// Wait until every message sent to the peer `id` so far has been
+    /// written, or `timeout` expires.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HostUnreachable` if no peer with the identity is connected,
+    /// and `BrokenPipe` once the driver future has stopped.
+    pub async fn flush_peer(
+        &mut self,
+        id: &[u8],
+        timeout: Duration,
+    ) -> Result<FlushOutcome, Error> {
+        self.inner
+            .flush_peer(id, timeout)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Disconnect the peer `id`, first flushing its queue within `linger`
+    /// when `graceful`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HostUnreachable` if no peer with the identity is connected.
+    pub async fn kick(&mut self, id: &[u8], graceful: bool) -> Result<FlushOutcome, Error> {
+        self.inner.kick(id, graceful).await.map_err(classify_error)
+    }
+
+    /// Disconnect every peer now, discarding the messages queued for them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BrokenPipe` once the driver future has stopped.
+    pub async fn kick_all(&mut self) -> Result<(), Error> {
+        self.inner.kick_all().await.map_err(classify_error)
+    }
+
+    /// Messages dropped as duplicates by the `dedupe_window` option.
+    pub fn duplicates_dropped(&self) -> u64 {
+        self.inner.duplicates_dropped()
+    }
+
+    /// Structured diagnostics of the socket, serializable with the `serde`
+    /// feature: every connected peer and the depth of the hub's queues.
+    pub fn snapshot(&self) -> monocoque_zmtp::DiagnosticsSnapshot {
+        self.inner.snapshot()
+    }
+
+    /// Set ROUTER mandatory mode, overriding the `router_mandatory` option.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_ROUTER_MANDATORY` (33) option.
+    pub const fn set_router_mandatory(&mut self, mandatory: bool) {
+        self.inner.set_router_mandatory(mandatory);
+    }
+}
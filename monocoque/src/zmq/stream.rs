@@ -0,0 +1,111 @@
+//! STREAM socket: raw TCP bridging without a ZMTP handshake.
+
+use bytes::Bytes;
+use monocoque_core::error::Error;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_zmtp::codec::classify_error;
+use monocoque_zmtp::stream::StreamSocket as InternalStream;
+use std::io;
+
+/// STREAM socket bridging plain TCP peers.
+///
+/// Each accepted connection gets an 8-byte routing id. Received messages are
+/// `[routing_id, "", data]`, where an empty `data` frame announces a connect
+/// or disconnect; `send` takes the same layout and writes `data` to the
+/// peer as raw bytes.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use monocoque::zmq::StreamSocket;
+/// use bytes::Bytes;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut socket = StreamSocket::bind("127.0.0.1:5555").await?;
+/// socket.accept_raw().await?;
+///
+/// while let Some(msg) = socket.recv().await? {
+///     if !msg[2].is_empty() {
+///         socket.send(vec![msg[0].clone(), Bytes::new(), msg[2].clone()]).await?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct StreamSocket {
+    inner: InternalStream,
+}
+
+impl StreamSocket {
+    /// Bind a STREAM socket to a TCP address.
+    pub async fn bind(addr: impl monocoque_core::rt::ToSocketAddrs) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalStream::bind(addr).await.map_err(classify_error)?,
+        })
+    }
+
+    /// Accept the next raw TCP connection and return its routing id.
+    pub async fn accept_raw(&mut self) -> Result<Bytes, Error> {
+        self.inner.accept_raw().await.map_err(classify_error)
+    }
+
+    /// Receive the next message from any peer as `[routing_id, "", data]`.
+    ///
+    /// Returns `Ok(None)` once the socket's inbound channel has closed.
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Write the data frame of `[routing_id, "", data]` to the peer.
+    ///
+    /// An empty `data` frame disconnects the peer; messages for unknown
+    /// peers are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for an empty message, `WouldBlock` when the
+    /// peer's queue is at `send_hwm`, and `BrokenPipe` when the peer is gone.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Close one peer. Returns `true` if it was connected.
+    pub fn close_peer(&mut self, routing_id: &Bytes) -> bool {
+        self.inner.close_peer(routing_id)
+    }
+
+    /// Disconnect a peer. Alias for [`close_peer`](Self::close_peer).
+    pub fn disconnect(&mut self, routing_id: &Bytes) {
+        self.inner.disconnect(routing_id);
+    }
+
+    /// Number of connected peers.
+    #[inline]
+    pub fn peer_count(&self) -> usize {
+        self.inner.peer_count()
+    }
+
+    /// The local address this socket is bound to.
+    pub fn local_addr(&self) -> io::Result<std::net::SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Get a reference to the socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Change socket options on the live socket.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
+    }
+}
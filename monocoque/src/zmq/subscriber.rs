@@ -1,12 +1,17 @@
 //! SUB socket implementation.
 
+use super::common::parse_tcp_endpoint;
 use bytes::Bytes;
+use monocoque_core::error::Error;
 use monocoque_core::monitor::{SocketEvent, SocketEventSender, SocketMonitor, create_monitor};
-use monocoque_core::options::SocketOptions;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
 use monocoque_core::rt::TcpStream;
+use monocoque_core::subscription::{SubscriptionFilter, WildcardPattern};
 use monocoque_zmtp::SocketType;
+use monocoque_zmtp::codec::classify_error;
 use monocoque_zmtp::subscriber::SubSocket as InternalSub;
 use std::io;
+use std::time::Duration;
 
 /// A SUB socket for receiving filtered messages.
 ///
@@ -70,20 +75,42 @@ impl SubSocket {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn connect(endpoint: &str) -> io::Result<Self> {
-        // Try parsing as endpoint, fall back to raw address
-        let addr = if let Ok(monocoque_core::endpoint::Endpoint::Tcp(a)) =
-            monocoque_core::endpoint::Endpoint::parse(endpoint)
-        {
-            a
-        } else {
-            endpoint
-                .parse::<std::net::SocketAddr>()
-                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
-        };
+    pub async fn connect(endpoint: &str) -> Result<Self, Error> {
+        Self::connect_with_options(endpoint, SocketOptions::default()).await
+    }
 
+    /// Connect to a PUB peer with custom socket options.
+    ///
+    /// Subscriptions declared with [`SocketOptions::with_subscribe`] (and
+    /// removals declared with [`SocketOptions::with_unsubscribe`]) are sent
+    /// right after the handshake, before this returns, so no publication
+    /// that follows the connect is missed for want of a `subscribe()` call.
+    /// They are replayed on every reconnect.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use bytes::Bytes;
+    /// use monocoque::zmq::{SocketOptions, SubSocket};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut socket = SubSocket::connect_with_options(
+    ///     "tcp://127.0.0.1:5555",
+    ///     SocketOptions::default().with_subscribe(Bytes::from("weather.")),
+    /// ).await?;
+    /// let update = socket.recv().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_options(
+        endpoint: &str,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        let addr = parse_tcp_endpoint(endpoint)?;
         let sock = Self {
-            inner: InternalSub::connect(addr).await?,
+            inner: InternalSub::connect_with_options(addr, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         };
         sock.emit_event(SocketEvent::Connected(
@@ -92,20 +119,96 @@ impl SubSocket {
         Ok(sock)
     }
 
-    /// Check if the socket is currently connected.
-    #[inline]
-    pub fn is_connected(&self) -> bool {
-        self.inner.is_connected()
+    /// Reconnect to the stored endpoint after the connection was lost or
+    /// the socket was poisoned by cancelled I/O.
+    ///
+    /// Makes one attempt (after the reconnect backoff delay). On success the
+    /// poisoned flag and all buffered state are cleared and every active
+    /// subscription is re-sent. Sockets not created with `connect` have no
+    /// endpoint to dial and fail with `Unsupported`.
+    ///
+    /// Emits `ConnectRetried` before the attempt and `Connected` or
+    /// `ConnectFailed` after it, if monitoring is enabled.
+    pub async fn reconnect(&mut self) -> Result<(), Error> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        if let Some(endpoint) = &endpoint {
+            self.emit_event(SocketEvent::ConnectRetried {
+                endpoint: endpoint.clone(),
+                attempt: self.inner.reconnect_attempt() + 1,
+            });
+        }
+        let result = self.inner.try_reconnect().await.map_err(classify_error);
+        if let Some(endpoint) = endpoint {
+            match &result {
+                Ok(()) => self.emit_connected(endpoint),
+                Err(e) => self.emit_event(SocketEvent::ConnectFailed {
+                    endpoint,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        result
     }
 
     /// Try to reconnect to the stored endpoint, re-sending all active subscriptions.
-    pub async fn try_reconnect(&mut self) -> io::Result<()> {
-        self.inner.try_reconnect().await
+    ///
+    /// Same as [`reconnect`](Self::reconnect).
+    pub async fn try_reconnect(&mut self) -> Result<(), Error> {
+        self.reconnect().await
     }
 
     /// Receive with automatic reconnection on EOF or network error.
-    pub async fn recv_with_reconnect(&mut self) -> io::Result<Option<Vec<bytes::Bytes>>> {
-        self.inner.recv_with_reconnect().await
+    pub async fn recv_with_reconnect(&mut self) -> Result<Option<Vec<bytes::Bytes>>, Error> {
+        self.inner
+            .recv_with_reconnect()
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Connect and keep the connection up on its own.
+    ///
+    /// [`recv`](Self::recv) on the returned socket survives publisher
+    /// restarts: it reconnects with backoff, replays every subscription and
+    /// carries on receiving. Subscriptions changed while disconnected are
+    /// applied on the next connection.
+    /// Gives up with `NotConnected` once `max_reconnect_attempts`
+    /// consecutive attempts have failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::SubSocket;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut socket = SubSocket::connect_with_reconnect("tcp://127.0.0.1:5555").await?;
+    /// socket.subscribe(b"prices.").await?;
+    /// while let Some(msg) = socket.recv().await? {
+    ///     println!("{:?}", msg);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_reconnect(endpoint: &str) -> Result<Self, Error> {
+        Self::connect_with_reconnect_options(endpoint, SocketOptions::default()).await
+    }
+
+    /// [`connect_with_reconnect`](Self::connect_with_reconnect) with custom
+    /// options.
+    pub async fn connect_with_reconnect_options(
+        endpoint: &str,
+        options: SocketOptions,
+    ) -> Result<Self, Error> {
+        let addr = parse_tcp_endpoint(endpoint)?;
+        let sock = Self {
+            inner: InternalSub::connect_with_reconnect_options(addr, options)
+                .await
+                .map_err(classify_error)?,
+            monitor: None,
+        };
+        sock.emit_event(SocketEvent::Connected(
+            monocoque_core::endpoint::Endpoint::Tcp(addr),
+        ));
+        Ok(sock)
     }
 
     /// Connect to a PUB peer via IPC (Unix domain sockets).
@@ -127,7 +230,9 @@ impl SubSocket {
     /// # }
     /// ```
     #[cfg(unix)]
-    pub async fn connect_ipc(path: &str) -> io::Result<SubSocket<monocoque_core::rt::UnixStream>> {
+    pub async fn connect_ipc(
+        path: &str,
+    ) -> Result<SubSocket<monocoque_core::rt::UnixStream>, Error> {
         use std::path::PathBuf;
 
         // Strip "ipc://" prefix if present
@@ -143,9 +248,11 @@ impl SubSocket {
     }
 
     /// Create a SUB socket from a TCP stream with TCP_NODELAY enabled.
-    pub async fn from_tcp(stream: TcpStream) -> io::Result<Self> {
+    pub async fn from_tcp(stream: TcpStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalSub::from_tcp(stream).await?,
+            inner: InternalSub::from_tcp(stream)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -172,9 +279,11 @@ impl SubSocket {
     pub async fn from_tcp_with_options(
         stream: TcpStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalSub::from_tcp_with_options(stream, options).await?,
+            inner: InternalSub::from_tcp_with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -183,12 +292,14 @@ impl SubSocket {
     pub async fn with_options<Stream>(
         stream: Stream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<SubSocket<Stream>>
+    ) -> Result<SubSocket<Stream>, Error>
     where
         Stream: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
     {
         Ok(SubSocket {
-            inner: InternalSub::with_options(stream, options).await?,
+            inner: InternalSub::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -215,28 +326,162 @@ where
         }
     }
 
+    /// Emit `Connected` for `endpoint` carrying the peer's handshake metadata.
+    fn emit_connected(&self, endpoint: monocoque_core::endpoint::Endpoint) {
+        if let Some(monitor) = &self.monitor {
+            monocoque_core::monitor::emit_with_metadata(
+                monitor,
+                SocketEvent::Connected(endpoint),
+                self.inner.peer_metadata().clone(),
+            );
+        }
+    }
+
     /// Subscribe to messages matching the given topic prefix.
     ///
     /// Empty topic subscribes to all messages.
     ///
-    /// This sends a subscription message to the PUB socket.
-    pub async fn subscribe(&mut self, topic: &[u8]) -> io::Result<()> {
-        self.inner.subscribe(Bytes::copy_from_slice(topic)).await
+    /// This sends a subscription message to the PUB socket, bounded by the
+    /// configured `send_timeout`.
+    pub async fn subscribe(&mut self, topic: &[u8]) -> Result<(), Error> {
+        self.inner
+            .subscribe(Bytes::copy_from_slice(topic))
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Subscribe to a topic prefix, giving up after `timeout`.
+    ///
+    /// Returns `TimedOut` if the subscription could not be sent in time; the
+    /// subscription is not recorded. If the deadline dropped a write in
+    /// flight the socket is poisoned (see [`is_poisoned`](Self::is_poisoned)).
+    pub async fn subscribe_timeout(
+        &mut self,
+        topic: &[u8],
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        self.inner
+            .subscribe_timeout(Bytes::copy_from_slice(topic), timeout)
+            .await
+            .map_err(classify_error)
     }
 
     /// Unsubscribe from messages matching the given topic prefix.
     ///
-    /// This sends an unsubscription message to the PUB socket.
-    pub async fn unsubscribe(&mut self, topic: &[u8]) -> io::Result<()> {
-        self.inner.unsubscribe(&Bytes::copy_from_slice(topic)).await
+    /// This sends an unsubscription message to the PUB socket, bounded by the
+    /// configured `send_timeout`.
+    pub async fn unsubscribe(&mut self, topic: &[u8]) -> Result<(), Error> {
+        self.inner
+            .unsubscribe(&Bytes::copy_from_slice(topic))
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Unsubscribe from a topic prefix, giving up after `timeout`.
+    pub async fn unsubscribe_timeout(
+        &mut self,
+        topic: &[u8],
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        self.inner
+            .unsubscribe_timeout(&Bytes::copy_from_slice(topic), timeout)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Subscribe to messages whose topic matches a wildcard pattern.
+    ///
+    /// `*` matches any run of bytes and `?` exactly one byte, over the whole
+    /// topic frame. The publisher still filters by the pattern's literal
+    /// prefix; the pattern itself is applied on receipt.
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::{SubSocket, WildcardPattern};
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut socket = SubSocket::connect("tcp://127.0.0.1:5555").await?;
+    /// socket.subscribe_pattern(&WildcardPattern::new("sensor.?.temp")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_pattern(&mut self, pattern: &WildcardPattern) -> Result<(), Error> {
+        self.inner
+            .subscribe_pattern(pattern)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Remove a wildcard pattern added with
+    /// [`subscribe_pattern`](Self::subscribe_pattern).
+    pub async fn unsubscribe_pattern(&mut self, pattern: &WildcardPattern) -> Result<(), Error> {
+        self.inner
+            .unsubscribe_pattern(pattern)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Subscribe to messages whose topic is exactly `topic`.
+    ///
+    /// Unlike [`subscribe`](Self::subscribe), `b"weather"` here does not
+    /// admit `weather.paris`. The publisher only filters by prefix, so the
+    /// longer topics still arrive and are dropped on receipt.
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::SubSocket;
+    ///
+    /// # async fn example() -> std::io::Result<()> {
+    /// let mut socket = SubSocket::connect("tcp://127.0.0.1:5555").await?;
+    /// socket.subscribe_exact(b"weather").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_exact(&mut self, topic: &[u8]) -> Result<(), Error> {
+        self.inner
+            .subscribe_exact(Bytes::copy_from_slice(topic))
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Remove a topic added with [`subscribe_exact`](Self::subscribe_exact).
+    pub async fn unsubscribe_exact(&mut self, topic: &[u8]) -> Result<(), Error> {
+        self.inner
+            .unsubscribe_exact(topic)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// The subscriptions received messages are filtered with.
+    #[inline]
+    pub const fn subscription_filter(&self) -> &SubscriptionFilter {
+        self.inner.subscription_filter()
     }
 
     /// Receive a multipart message.
     ///
     /// Only messages matching subscribed topics will be received.
     /// Returns `None` if the connection is closed.
-    pub async fn recv(&mut self) -> io::Result<Option<Vec<Bytes>>> {
-        self.inner.recv().await
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Receive messages as a [`Stream`](futures::Stream), for use with
+    /// `futures::StreamExt` combinators such as `take` and `collect`.
+    ///
+    /// Each item is one [`recv`](Self::recv). The stream ends when the peer
+    /// closes the connection; an error is yielded once and then ends it.
+    /// The stream is boxed once so it is `Unpin` and `StreamExt::next` works
+    /// on it directly.
+    pub fn recv_stream(
+        &mut self,
+    ) -> impl futures::Stream<Item = io::Result<Vec<bytes::Bytes>>> + Unpin + '_ {
+        Box::pin(futures::stream::unfold(Some(self), |socket| async move {
+            let socket = socket?;
+            match socket.recv().await {
+                Ok(Some(msg)) => Some((Ok(msg), Some(socket))),
+                Ok(None) => None,
+                Err(e) => Some((Err(e.into()), None)),
+            }
+        }))
     }
 
     /// Get the socket type.
@@ -291,10 +536,113 @@ where
         self.inner.events()
     }
 
-    /// Get a mutable reference to this socket's options.
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is currently connected.
+    ///
+    /// Together with the other state accessors this gives a cheap health
+    /// check without touching the wire:
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::SubSocket;
+    /// # fn check(mut socket: SubSocket) {
+    /// let healthy = socket.is_connected() && !socket.is_poisoned();
+    /// let readable = socket.events() & 1 != 0;
+    /// println!(
+    ///     "{:?} healthy={healthy} readable={readable} more={} hwm={}",
+    ///     socket.last_endpoint(),
+    ///     socket.has_more(),
+    ///     socket.options().recv_hwm,
+    /// );
+    /// socket.update_options(|o| o.recv_hwm = 10_000).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    ///
+    /// A poisoned socket refuses further I/O until it is reconnected.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Structured diagnostics of the socket (state, non-default options,
+    /// buffer usage, connection history), serializable with the `serde`
+    /// feature.
+    pub fn snapshot(&self) -> monocoque_zmtp::DiagnosticsSnapshot {
+        self.inner.snapshot()
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    ///
+    /// Buffered messages are flushed within the linger window before the
+    /// stream is shut down, and monitors receive a `Closed` event. Dropping
+    /// the socket instead discards anything still buffered.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use monocoque::zmq::SubSocket;
+    /// # async fn example(socket: SubSocket) -> std::io::Result<()> {
+    /// socket.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(self) -> io::Result<()> {
+        let endpoint = self.inner.last_endpoint().cloned();
+        let result = self.inner.close().await;
+        if let (Some(monitor), Some(endpoint)) = (&self.monitor, endpoint) {
+            monocoque_core::monitor::emit(monitor, SocketEvent::Closed(endpoint));
+        }
+        result
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
     #[inline]
-    pub fn options_mut(&mut self) -> &mut SocketOptions {
-        self.inner.options_mut()
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
     }
 }
 
@@ -302,9 +650,9 @@ where
 #[cfg(unix)]
 impl SubSocket<monocoque_core::rt::UnixStream> {
     /// Create a SUB socket from an existing Unix domain socket stream (IPC).
-    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> io::Result<Self> {
+    pub async fn from_unix_stream(stream: monocoque_core::rt::UnixStream) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalSub::new(stream).await?,
+            inner: InternalSub::new(stream).await.map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -315,9 +663,11 @@ impl SubSocket<monocoque_core::rt::UnixStream> {
     pub async fn from_unix_stream_with_options(
         stream: monocoque_core::rt::UnixStream,
         options: monocoque_core::options::SocketOptions,
-    ) -> io::Result<Self> {
+    ) -> Result<Self, Error> {
         Ok(Self {
-            inner: InternalSub::with_options(stream, options).await?,
+            inner: InternalSub::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
             monitor: None,
         })
     }
@@ -0,0 +1,238 @@
+//! XPUB socket implementation.
+//!
+//! XPUB extends PUB by handing subscription messages to the application, so
+//! brokers can forward them upstream.
+
+use bytes::Bytes;
+use monocoque_core::endpoint::Endpoint;
+use monocoque_core::error::Error;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::pubsub::shared::SharedSubscriptionIndex;
+use monocoque_core::subscription::SubscriptionEvent;
+use monocoque_zmtp::SocketType;
+use monocoque_zmtp::XPubSocket as InternalXPub;
+use monocoque_zmtp::codec::classify_error;
+use std::io;
+
+/// XPUB socket for subscription-aware publishing.
+///
+/// Publishes to every matching subscriber and reports their subscriptions
+/// through [`recv_subscription`](Self::recv_subscription).
+#[derive(Debug)]
+pub struct XPubSocket {
+    inner: InternalXPub,
+}
+
+impl XPubSocket {
+    /// Bind to an address and start listening for subscribers.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::XPubSocket;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut xpub = XPubSocket::bind("127.0.0.1:5555").await?;
+    /// xpub.accept().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn bind(addr: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalXPub::bind(addr).await.map_err(classify_error)?,
+        })
+    }
+
+    /// Bind with custom socket options.
+    pub async fn bind_with_options(addr: &str, options: SocketOptions) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalXPub::bind_with_options(addr, options)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Mirror subscriptions into an application-visible index.
+    ///
+    /// See [`monocoque_zmtp::XPubSocket::with_shared_index`].
+    #[must_use]
+    pub fn with_shared_index(self, index: SharedSubscriptionIndex) -> Self {
+        Self {
+            inner: self.inner.with_shared_index(index),
+        }
+    }
+
+    /// The attached application-visible subscription index, if any.
+    #[must_use]
+    pub const fn shared_index(&self) -> Option<&SharedSubscriptionIndex> {
+        self.inner.shared_index()
+    }
+
+    /// Accept one new subscriber connection.
+    pub async fn accept(&mut self) -> Result<(), Error> {
+        self.inner.accept().await.map_err(classify_error)
+    }
+
+    /// Receive the next subscription event from any subscriber.
+    ///
+    /// Returns `None` when no subscriber is connected.
+    pub async fn recv_subscription(&mut self) -> Result<Option<SubscriptionEvent>, Error> {
+        self.inner.recv_subscription().await.map_err(classify_error)
+    }
+
+    /// Publish a multipart message to every matching subscriber.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Get the number of active subscribers.
+    #[inline]
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.subscriber_count()
+    }
+
+    /// Check if at least one subscriber is attached.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Always `false`: a failed write evicts only that subscriber.
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Get the endpoint this socket is bound to.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub const fn last_endpoint(&self) -> Option<&Endpoint> {
+        self.inner.last_endpoint()
+    }
+
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
+    }
+
+    /// Close the socket, shutting down every subscriber connection and the
+    /// upstream connection, if any.
+    pub async fn close(self) -> Result<(), Error> {
+        self.inner.close().await.map_err(classify_error)
+    }
+
+    /// Get the local address.
+    pub fn local_addr(&self) -> io::Result<std::net::SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    /// Get the socket type.
+    #[inline]
+    pub const fn socket_type(&self) -> SocketType {
+        SocketType::Xpub
+    }
+
+    /// Check if subscription events are waiting to be received.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// Returns a bitmask: `1` (POLLIN) when subscription events are pending,
+    /// `2` (POLLOUT) when subscribers are connected.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.inner.events()
+    }
+
+    /// Set verbose mode, reporting duplicate subscriptions too.
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.inner.set_verbose(verbose);
+    }
+
+    /// Set manual mode, where subscriptions are forwarded upstream only
+    /// through [`send_subscription`](Self::send_subscription).
+    pub fn set_manual(&mut self, manual: bool) {
+        self.inner.set_manual(manual);
+    }
+
+    /// Connect to an upstream publisher so that subscription events can be
+    /// forwarded.
+    pub async fn connect_upstream(&mut self, addr: &str) -> Result<(), Error> {
+        self.inner
+            .connect_upstream(addr)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Manually send a subscription event to the upstream connection.
+    ///
+    /// Requires manual mode and [`connect_upstream`](Self::connect_upstream).
+    pub async fn send_subscription(&mut self, event: SubscriptionEvent) -> Result<(), Error> {
+        self.inner
+            .send_subscription(event)
+            .await
+            .map_err(classify_error)
+    }
+}
+
+// Implement ProxySocket for the high-level XPubSocket wrapper (backend of a
+// PUB-SUB broker). Subscription events are received as `[0x01|0x00, topic]`.
+impl monocoque_zmtp::proxy::ProxySocket for XPubSocket {
+    fn recv_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+    ) -> ::core::pin::Pin<
+        Box<dyn ::core::future::Future<Output = io::Result<Option<Vec<Bytes>>>> + 'async_trait>,
+    >
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        self.inner.recv_multipart()
+    }
+
+    fn send_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+        msg: Vec<Bytes>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send(msg).await?) })
+    }
+
+    fn socket_desc(&self) -> &'static str {
+        "XPUB"
+    }
+}
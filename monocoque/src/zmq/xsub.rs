@@ -0,0 +1,319 @@
+//! XSUB socket implementation.
+//!
+//! XSUB extends SUB by exposing subscriptions as messages, so brokers can
+//! forward them upstream.
+
+use bytes::Bytes;
+use monocoque_core::error::Error;
+use monocoque_core::options::{OptionDiff, OptionUpdateError, SocketOptions};
+use monocoque_core::rt::TcpStream;
+use monocoque_core::subscription::SubscriptionEvent;
+use monocoque_zmtp::SocketType;
+use monocoque_zmtp::XSubSocket as InternalXSub;
+use monocoque_zmtp::codec::classify_error;
+use std::io;
+use std::time::Duration;
+
+/// XSUB socket for subscription forwarding in brokers.
+///
+/// Subscriptions are tracked locally and sent to the connected publisher.
+pub struct XSubSocket<S = TcpStream>
+where
+    S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
+{
+    inner: InternalXSub<S>,
+}
+
+impl XSubSocket<TcpStream> {
+    /// Connect to a publisher, storing the endpoint for automatic reconnection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use monocoque::zmq::XSubSocket;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut xsub = XSubSocket::connect("127.0.0.1:5555").await?;
+    /// xsub.subscribe("topic.").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect(addr: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalXSub::connect(addr).await.map_err(classify_error)?,
+        })
+    }
+
+    /// Connect with custom options, storing the endpoint for reconnection.
+    pub async fn connect_with_options(addr: &str, options: SocketOptions) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalXSub::connect_with_options(addr, options)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Check if the socket is currently connected.
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.inner.is_connected()
+    }
+
+    /// Properties the peer sent in its READY command, such as `Identity`,
+    /// `Socket-Type` and application-defined keys.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn peer_metadata(&self) -> &monocoque_zmtp::PeerMetadata {
+        self.inner.peer_metadata()
+    }
+
+    /// ZMTP revision agreed with the peer: 3.0 or 3.1.
+    ///
+    /// Refreshed on every reconnect.
+    #[inline]
+    pub const fn negotiated_version(&self) -> monocoque_zmtp::ZmtpVersion {
+        self.inner.negotiated_version()
+    }
+
+    /// Why the latest connection ended: end-of-file, reset, heartbeat
+    /// timeout or protocol error. `None` while the first one is open.
+    #[inline]
+    pub const fn last_close_reason(&self) -> Option<monocoque_zmtp::CloseReason> {
+        self.inner.last_close_reason()
+    }
+
+    /// Try to reconnect to the stored endpoint, re-sending all active
+    /// subscriptions.
+    pub async fn try_reconnect(&mut self) -> Result<(), Error> {
+        self.inner.try_reconnect().await.map_err(classify_error)
+    }
+
+    /// Receive with automatic reconnection on EOF or network error.
+    pub async fn recv_with_reconnect(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner
+            .recv_with_reconnect()
+            .await
+            .map_err(classify_error)
+    }
+}
+
+impl<S> XSubSocket<S>
+where
+    S: compio_io::AsyncRead + compio_io::AsyncWrite + Unpin,
+{
+    /// Create an XSUB socket from any stream.
+    pub async fn new(stream: S) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalXSub::new(stream).await.map_err(classify_error)?,
+        })
+    }
+
+    /// Create an XSUB socket from any stream with custom options.
+    pub async fn with_options(stream: S, options: SocketOptions) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InternalXSub::with_options(stream, options)
+                .await
+                .map_err(classify_error)?,
+        })
+    }
+
+    /// Subscribe to messages with the given prefix; an empty prefix matches
+    /// everything.
+    pub async fn subscribe(&mut self, prefix: impl Into<Bytes>) -> Result<(), Error> {
+        self.inner.subscribe(prefix).await.map_err(classify_error)
+    }
+
+    /// Subscribe to messages with the given prefix, giving up after `timeout`.
+    ///
+    /// On `TimedOut` the prefix is not recorded; the socket is poisoned if the
+    /// deadline dropped a write in flight.
+    pub async fn subscribe_timeout(
+        &mut self,
+        prefix: impl Into<Bytes>,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        self.inner
+            .subscribe_timeout(prefix, timeout)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Unsubscribe from messages with the given prefix.
+    pub async fn unsubscribe(&mut self, prefix: impl Into<Bytes>) -> Result<(), Error> {
+        self.inner.unsubscribe(prefix).await.map_err(classify_error)
+    }
+
+    /// Unsubscribe from messages with the given prefix, giving up after `timeout`.
+    ///
+    /// On `TimedOut` the unsubscription frame has been withdrawn and the prefix
+    /// stays subscribed.
+    pub async fn unsubscribe_timeout(
+        &mut self,
+        prefix: impl Into<Bytes>,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        self.inner
+            .unsubscribe_timeout(prefix, timeout)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Send a raw subscription event upstream (for proxies).
+    pub async fn send_subscription_event(&mut self, event: SubscriptionEvent) -> Result<(), Error> {
+        self.inner
+            .send_subscription_event(event)
+            .await
+            .map_err(classify_error)
+    }
+
+    /// Send a message in XSUB wire form upstream.
+    ///
+    /// See [`monocoque_zmtp::XSubSocket::send`] for the wire form.
+    pub async fn send(&mut self, msg: Vec<Bytes>) -> Result<(), Error> {
+        self.inner.send(msg).await.map_err(classify_error)
+    }
+
+    /// Receive a multipart message from the publisher.
+    ///
+    /// Returns `None` if the connection is closed.
+    pub async fn recv(&mut self) -> Result<Option<Vec<Bytes>>, Error> {
+        self.inner.recv().await.map_err(classify_error)
+    }
+
+    /// Close the socket gracefully, honoring the `linger` option.
+    pub async fn close(self) -> Result<(), Error> {
+        self.inner.close().await.map_err(classify_error)
+    }
+
+    /// Get immutable access to socket options.
+    #[inline]
+    pub const fn options(&self) -> &SocketOptions {
+        self.inner.options()
+    }
+
+    /// Check if the socket is poisoned (I/O was cancelled mid-operation).
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /// Change this socket's options while it runs.
+    ///
+    /// See [`SocketOptions::update`] for when each change takes effect.
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `f` changes a construct-only option such as
+    /// `routing_id`; nothing is applied in that case.
+    #[inline]
+    pub fn update_options(
+        &mut self,
+        f: impl FnOnce(&mut SocketOptions),
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.update_options(f)
+    }
+
+    /// Replace the socket options, with the same rules as
+    /// [`update_options`](Self::update_options).
+    ///
+    /// # Errors
+    ///
+    /// [`OptionUpdateError`] if `options` changes a construct-only option.
+    #[inline]
+    pub fn set_options(
+        &mut self,
+        options: SocketOptions,
+    ) -> Result<Vec<OptionDiff>, OptionUpdateError> {
+        self.inner.set_options(options)
+    }
+
+    /// Get the number of active subscriptions.
+    #[inline]
+    pub fn subscription_count(&self) -> usize {
+        self.inner.subscription_count()
+    }
+
+    /// Check if subscribed to a specific topic.
+    #[inline]
+    pub fn is_subscribed(&self, topic: &[u8]) -> bool {
+        self.inner.is_subscribed(topic)
+    }
+
+    /// Get all subscriptions.
+    #[inline]
+    pub fn subscriptions(&self) -> Vec<monocoque_core::subscription::Subscription> {
+        self.inner.subscriptions()
+    }
+
+    /// Get the socket type.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_TYPE` (16) option.
+    #[inline]
+    pub const fn socket_type(&self) -> SocketType {
+        SocketType::Xsub
+    }
+
+    /// Get the endpoint this socket is connected to, if available.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_LAST_ENDPOINT` (32) option.
+    #[inline]
+    pub fn last_endpoint(&self) -> Option<&monocoque_core::endpoint::Endpoint> {
+        self.inner.last_endpoint()
+    }
+
+    /// Check if the last received message has more frames coming.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_RCVMORE` (13) option.
+    #[inline]
+    pub fn has_more(&self) -> bool {
+        self.inner.has_more()
+    }
+
+    /// Get the event state of the socket.
+    ///
+    /// # ZeroMQ Compatibility
+    ///
+    /// Corresponds to `ZMQ_EVENTS` (15) option.
+    #[inline]
+    pub fn events(&self) -> u32 {
+        self.inner.events()
+    }
+}
+
+// Implement ProxySocket for the high-level XSubSocket wrapper (frontend of a
+// PUB-SUB broker).
+impl monocoque_zmtp::proxy::ProxySocket for XSubSocket<TcpStream> {
+    fn recv_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+    ) -> ::core::pin::Pin<
+        Box<dyn ::core::future::Future<Output = io::Result<Option<Vec<Bytes>>>> + 'async_trait>,
+    >
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.recv().await?) })
+    }
+
+    fn send_multipart<'life0, 'async_trait>(
+        &'life0 mut self,
+        msg: Vec<Bytes>,
+    ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = io::Result<()>> + 'async_trait>>
+    where
+        'life0: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(async move { Ok(self.send(msg).await?) })
+    }
+
+    fn socket_desc(&self) -> &'static str {
+        "XSUB"
+    }
+}
@@ -0,0 +1,40 @@
+//! Raw ZMTP fixtures shared by the tests that script one side of the wire.
+//!
+//! Each test crate uses only some of these.
+#![allow(dead_code)]
+
+/// A 64-byte ZMTP 3.1 greeting advertising `mechanism`.
+pub fn greeting(mechanism: &[u8]) -> Vec<u8> {
+    let mut g = vec![0u8; 64];
+    g[0] = 0xFF;
+    g[9] = 0x7F;
+    g[10] = 3;
+    g[11] = 1;
+    g[12..12 + mechanism.len()].copy_from_slice(mechanism);
+    g
+}
+
+/// A READY command frame, switching to the long form past 255 bytes.
+pub fn ready(socket_type: &[u8], identity: Option<&[u8]>) -> Vec<u8> {
+    let mut body = b"\x05READY".to_vec();
+    for (name, value) in [
+        (&b"Socket-Type"[..], Some(socket_type)),
+        (b"Identity", identity),
+    ] {
+        if let Some(value) = value {
+            body.push(name.len() as u8);
+            body.extend_from_slice(name);
+            body.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            body.extend_from_slice(value);
+        }
+    }
+    let mut frame = if body.len() > 255 {
+        let mut f = vec![0x06];
+        f.extend_from_slice(&(body.len() as u64).to_be_bytes());
+        f
+    } else {
+        vec![0x04, body.len() as u8]
+    };
+    frame.extend_from_slice(&body);
+    frame
+}
@@ -0,0 +1,112 @@
+//! Connection health and explicit `reconnect()` on the public sockets.
+//!
+//! Poisons a DEALER by cancelling a send that cannot finish, checks that
+//! `is_poisoned()` reports it and further I/O is refused, then recovers with
+//! `reconnect()` and resumes traffic on the new connection.
+
+use bytes::Bytes;
+use monocoque::rt::TcpListener;
+use monocoque::zmq::{DealerSocket, Endpoint, SocketEvent};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_reconnect_recovers_poisoned_dealer() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    // Peer side: the first connection never reads, so a large send stalls;
+    // the second echoes one message back.
+    let peer = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let (stalled, _) = listener.accept().await.unwrap();
+                let _stalled = DealerSocket::from_tcp(stalled).await.unwrap();
+
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut dealer = DealerSocket::from_tcp(stream).await.unwrap();
+                let msg = dealer.recv().await.unwrap().expect("peer EOF");
+                dealer.send(msg).await.unwrap();
+                done_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let events = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let mut dealer = DealerSocket::connect(&addr.to_string()).await.unwrap();
+                let monitor = dealer.monitor();
+                assert!(dealer.is_connected());
+                assert!(!dealer.is_poisoned());
+
+                // Far more than the socket buffers hold, so the write is
+                // still in flight when the timeout drops it.
+                let huge = Bytes::from(vec![0u8; 64 * 1024 * 1024]);
+                let cancelled =
+                    monocoque::rt::timeout(Duration::from_millis(100), dealer.send(vec![huge]))
+                        .await;
+                assert!(cancelled.is_err(), "send finished despite a stalled peer");
+                assert!(dealer.is_poisoned());
+                assert!(dealer.send(vec![Bytes::from("refused")]).await.is_err());
+
+                dealer.reconnect().await.unwrap();
+                assert!(dealer.is_connected());
+                assert!(!dealer.is_poisoned());
+
+                dealer.send(vec![Bytes::from("after")]).await.unwrap();
+                let echoed = monocoque::rt::timeout(Duration::from_secs(5), dealer.recv())
+                    .await
+                    .expect("echo timed out")
+                    .unwrap();
+                assert_eq!(echoed, Some(vec![Bytes::from("after")]));
+                done_tx.send(()).unwrap();
+
+                monitor.drain().collect::<Vec<_>>()
+            })
+    })
+    .join()
+    .expect("client thread panicked");
+    peer.join().expect("peer thread panicked");
+
+    let kinds: Vec<_> = events.iter().map(|e| &e.event).collect();
+    assert!(
+        matches!(
+            kinds.as_slice(),
+            [
+                SocketEvent::Connected(_),
+                SocketEvent::ConnectRetried { attempt: 1, .. },
+                SocketEvent::Connected(Endpoint::Tcp(a)),
+            ] if *a == addr
+        ),
+        "unexpected events: {kinds:?}"
+    );
+}
+
+#[test]
+fn test_reconnect_without_endpoint_is_unsupported() {
+    monocoque::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let peer = monocoque::rt::spawn(async move {
+                let (stream, _) = listener.accept().await.unwrap();
+                DealerSocket::from_tcp(stream).await.unwrap()
+            });
+
+            let stream = monocoque::rt::TcpStream::connect(addr).await.unwrap();
+            let mut dealer = DealerSocket::from_tcp(stream).await.unwrap();
+            let _peer = monocoque::rt::join(peer).await;
+
+            let err = dealer.reconnect().await.unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::Unsupported);
+        });
+}
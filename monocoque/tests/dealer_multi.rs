@@ -0,0 +1,225 @@
+//! One DEALER load-balancing across several ROUTER servers.
+//!
+//! `DealerSocket::connect_multiple` must hand messages to the servers in
+//! round-robin order and collect every reply, whichever server sends it, and
+//! keep going on the servers that are up while the others are redialed.
+
+use bytes::Bytes;
+use monocoque::rt::{LocalRuntime, TcpListener};
+use monocoque::zmq::{DealerSocket, MultiDealerSocket, RouterSocket, SocketOptions};
+use std::time::Duration;
+
+const REQUESTS: usize = 100;
+
+/// Bind an echo ROUTER on an ephemeral port. Returns the endpoint and a task
+/// that yields how many messages the server echoed before the DEALER left.
+async fn echo_server() -> (String, monocoque::rt::JoinHandle<usize>) {
+    echo_server_on("127.0.0.1:0").await
+}
+
+async fn echo_server_on(addr: &str) -> (String, monocoque::rt::JoinHandle<usize>) {
+    let listener = TcpListener::bind(addr).await.unwrap();
+    let endpoint = format!("tcp://{}", listener.local_addr().unwrap());
+    let task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+        let mut handled = 0;
+        while let Ok(Some(msg)) = router.recv().await {
+            router.send(msg).await.unwrap();
+            handled += 1;
+        }
+        handled
+    });
+    (endpoint, task)
+}
+
+async fn recv_within(dealer: &mut MultiDealerSocket) -> Option<Vec<Bytes>> {
+    monocoque::rt::timeout(Duration::from_secs(5), dealer.recv())
+        .await
+        .expect("recv timed out")
+        .unwrap()
+}
+
+#[test]
+fn two_routers_each_handle_half() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let (a, server_a) = echo_server().await;
+        let (b, server_b) = echo_server().await;
+        let mut dealer = DealerSocket::connect_multiple(&[&a, &b]).await.unwrap();
+        assert_eq!(dealer.len(), 2);
+
+        for i in 0..REQUESTS {
+            dealer
+                .send(vec![Bytes::from(format!("req-{i}"))])
+                .await
+                .unwrap();
+        }
+        let mut replies = Vec::with_capacity(REQUESTS);
+        for _ in 0..REQUESTS {
+            replies.push(recv_within(&mut dealer).await.expect("server closed"));
+        }
+        replies.sort();
+        let mut expected: Vec<_> = (0..REQUESTS)
+            .map(|i| vec![Bytes::from(format!("req-{i}"))])
+            .collect();
+        expected.sort();
+        assert_eq!(replies, expected);
+
+        dealer.close().await.unwrap();
+        assert_eq!(monocoque::rt::join(server_a).await, REQUESTS / 2);
+        assert_eq!(monocoque::rt::join(server_b).await, REQUESTS / 2);
+    });
+}
+
+#[test]
+fn added_connection_joins_the_rotation() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let (a, server_a) = echo_server().await;
+        let (b, server_b) = echo_server().await;
+        let mut dealer = DealerSocket::connect_multiple(&[&a]).await.unwrap();
+        dealer.add_connection(&b).await.unwrap();
+        assert_eq!(dealer.connected_endpoints().len(), 2);
+
+        for i in 0..4 {
+            dealer
+                .send(vec![Bytes::from(format!("req-{i}"))])
+                .await
+                .unwrap();
+            recv_within(&mut dealer).await.expect("server closed");
+        }
+        dealer.close().await.unwrap();
+        assert_eq!(monocoque::rt::join(server_a).await, 2);
+        assert_eq!(monocoque::rt::join(server_b).await, 2);
+    });
+}
+
+#[test]
+fn connect_multiple_rejects_empty_list() {
+    let err = LocalRuntime::new()
+        .unwrap()
+        .block_on(DealerSocket::connect_multiple(&[]))
+        .err()
+        .expect("an empty endpoint list must be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+/// An endpoint nothing listens on: bind an ephemeral port and release it.
+async fn dead_endpoint() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    listener.local_addr().unwrap().to_string()
+}
+
+#[test]
+fn sends_go_through_while_one_endpoint_is_down() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let (live, server) = echo_server().await;
+        let dead = format!("tcp://{}", dead_endpoint().await);
+        let mut dealer = DealerSocket::connect_multiple(&[&dead, &live])
+            .await
+            .unwrap();
+        assert_eq!(dealer.len(), 1);
+
+        for i in 0..4 {
+            dealer
+                .send(vec![Bytes::from(format!("req-{i}"))])
+                .await
+                .unwrap();
+            recv_within(&mut dealer).await.expect("server closed");
+        }
+        dealer.close().await.unwrap();
+        assert_eq!(monocoque::rt::join(server).await, 4);
+    });
+}
+
+#[test]
+fn down_endpoint_rejoins_the_rotation_once_it_is_up() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let (a, server_a) = echo_server().await;
+        let dead = dead_endpoint().await;
+        let mut dealer = DealerSocket::connect_multiple(&[&a, &format!("tcp://{dead}")])
+            .await
+            .unwrap();
+        assert_eq!(dealer.len(), 1);
+
+        let (_, server_b) = echo_server_on(&dead).await;
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while dealer.len() < 2 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "endpoint never rejoined"
+            );
+            monocoque::rt::sleep(Duration::from_millis(20)).await;
+            dealer
+                .send(vec![Bytes::from_static(b"probe")])
+                .await
+                .unwrap();
+            recv_within(&mut dealer).await.expect("server closed");
+        }
+
+        for _ in 0..2 {
+            dealer.send(vec![Bytes::from_static(b"req")]).await.unwrap();
+            recv_within(&mut dealer).await.expect("server closed");
+        }
+        dealer.close().await.unwrap();
+        assert!(monocoque::rt::join(server_b).await >= 1);
+        assert!(monocoque::rt::join(server_a).await >= 1);
+    });
+}
+
+#[test]
+fn receive_only_client_reconnects() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("tcp://{}", listener.local_addr().unwrap());
+        // Each accepted connection gets one message, then the server hangs up.
+        let server = monocoque::rt::spawn(async move {
+            for body in ["first", "second"] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut peer = DealerSocket::from_tcp(stream).await.unwrap();
+                peer.send(vec![Bytes::from(body)]).await.unwrap();
+                peer.close().await.unwrap();
+            }
+        });
+
+        let mut dealer = DealerSocket::connect_many(&[&endpoint]).await.unwrap();
+        assert_eq!(
+            recv_within(&mut dealer).await,
+            Some(vec![Bytes::from("first")])
+        );
+        assert_eq!(
+            recv_within(&mut dealer).await,
+            Some(vec![Bytes::from("second")])
+        );
+        monocoque::rt::join(server).await;
+        dealer.close().await.unwrap();
+    });
+}
+
+#[test]
+fn recv_ends_once_no_server_can_be_revived() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live = format!("tcp://{}", listener.local_addr().unwrap());
+        // Answer one request, then go away for good.
+        let server = monocoque::rt::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut router = RouterSocket::from_tcp(stream).await.unwrap();
+            let msg = router.recv().await.unwrap().unwrap();
+            router.send(msg).await.unwrap();
+            router.close().await.unwrap();
+        });
+        let dead = format!("tcp://{}", dead_endpoint().await);
+        let options = SocketOptions::default()
+            .with_reconnect_ivl(Duration::from_millis(10))
+            .with_max_reconnect_attempts(Some(2));
+        let mut dealer = MultiDealerSocket::connect_with_options(&[&live, &dead], options)
+            .await
+            .unwrap();
+
+        dealer.send(vec![Bytes::from_static(b"req")]).await.unwrap();
+        recv_within(&mut dealer).await.expect("server closed");
+        monocoque::rt::join(server).await;
+        assert_eq!(recv_within(&mut dealer).await, None);
+        assert!(dealer.is_empty());
+    });
+}
@@ -0,0 +1,75 @@
+//! Zero-copy and copied frame counts reported by a live socket.
+//!
+//! A DEALER sends small messages, then messages far larger than the ROUTER's
+//! 4 KiB read buffer. The small frames arrive within one read and are handed
+//! out as views of it; each large body spans many reads and must be copied.
+//! Both `buffer_stats()` and the diagnostics snapshot report the split.
+
+use bytes::Bytes;
+use monocoque::SocketOptions;
+use monocoque::rt::{LocalRuntime, TcpListener};
+use monocoque::zmq::{DealerSocket, RouterSocket};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const SMALL: u64 = 10;
+const LARGE: u64 = 4;
+const LARGE_SIZE: usize = 64 * 1024;
+
+#[test]
+fn router_reports_zero_copy_and_copied_frames() {
+    let (port_tx, port_rx) = mpsc::channel::<u16>();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let sender = thread::spawn(move || {
+        LocalRuntime::new().unwrap().block_on(async move {
+            let port = port_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+            let mut dealer = DealerSocket::connect(&format!("tcp://127.0.0.1:{port}"))
+                .await
+                .unwrap();
+            for _ in 0..SMALL {
+                dealer.send(vec![Bytes::from_static(b"hi")]).await.unwrap();
+            }
+            let body = Bytes::from(vec![0x5A; LARGE_SIZE]);
+            for _ in 0..LARGE {
+                dealer.send(vec![body.clone()]).await.unwrap();
+            }
+            // Keep the connection open until the ROUTER has read everything.
+            done_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        });
+    });
+
+    LocalRuntime::new().unwrap().block_on(async move {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        port_tx.send(listener.local_addr().unwrap().port()).unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        let options = SocketOptions::default().with_read_buffer_size(4096);
+        let mut router = RouterSocket::from_tcp_with_options(stream, options)
+            .await
+            .unwrap();
+
+        for _ in 0..SMALL {
+            let msg = router.recv().await.unwrap().expect("dealer left early");
+            assert_eq!(msg.last().unwrap().as_ref(), b"hi");
+        }
+        let stats = router.buffer_stats();
+        assert_eq!(stats.zero_copy_frames, SMALL);
+        assert_eq!(stats.copied_frames, 0);
+
+        for _ in 0..LARGE {
+            let msg = router.recv().await.unwrap().expect("dealer left early");
+            assert_eq!(msg.last().unwrap().len(), LARGE_SIZE);
+        }
+        let stats = router.buffer_stats();
+        assert_eq!(stats.zero_copy_frames, SMALL);
+        assert_eq!(stats.copied_frames, LARGE);
+
+        let buffers = router.snapshot().buffers;
+        assert_eq!(buffers.zero_copy_frames, SMALL);
+        assert_eq!(buffers.copied_frames, LARGE);
+
+        done_tx.send(()).unwrap();
+    });
+    sender.join().unwrap();
+}
@@ -0,0 +1,180 @@
+//! Failures of the public connect, bind, send and recv entry points map to
+//! the matching `monocoque::Error` variant.
+
+use bytes::Bytes;
+use monocoque::Error;
+use monocoque::rt::TcpListener;
+use monocoque::zmq::{
+    DealerSocket, PairSocket, RouterHubSocket, SocketOptions, StreamSocket, XSubSocket,
+};
+use std::net::SocketAddr;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+/// Run `peer` on its own runtime thread against a fresh listener and return
+/// the listener's address.
+fn spawn_peer<F, Fut>(peer: F) -> SocketAddr
+where
+    F: FnOnce(TcpListener) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = ()>,
+{
+    let (addr_tx, addr_rx) = mpsc::channel();
+    thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+                peer(listener).await;
+            });
+    });
+    addr_rx.recv_timeout(Duration::from_secs(5)).unwrap()
+}
+
+fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+    monocoque::rt::LocalRuntime::new().unwrap().block_on(fut)
+}
+
+#[test]
+fn refused_connect_is_io() {
+    // Bind and drop to find a port nothing listens on.
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let err = block_on(DealerSocket::connect(&addr.to_string()))
+        .err()
+        .expect("connect to a closed port should fail");
+    assert!(matches!(err, Error::Io(_)), "{err:?}");
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+}
+
+/// Start a peer that answers like an HTTP server and return its address.
+fn spawn_http_peer() -> SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    thread::spawn(move || {
+        use std::io::Write;
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reply = b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\n\r\n".to_vec();
+        reply.resize(64, b' ');
+        let _ = stream.write_all(&reply);
+        thread::sleep(Duration::from_millis(500));
+    });
+    addr
+}
+
+#[test]
+fn non_zmtp_peer_fails_the_handshake() {
+    let addr = spawn_http_peer();
+    let err = block_on(DealerSocket::connect(&addr.to_string()))
+        .err()
+        .expect("handshake with an HTTP server should fail");
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+}
+
+#[test]
+fn connect_with_options_classifies_handshake_failure() {
+    let addr = spawn_http_peer();
+    let err = block_on(DealerSocket::connect_with_options(
+        &addr.to_string(),
+        SocketOptions::default(),
+    ))
+    .err()
+    .expect("handshake with an HTTP server should fail");
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+}
+
+#[test]
+fn pair_and_xsub_connect_classify_handshake_failure() {
+    let addr = spawn_http_peer();
+    let err = block_on(PairSocket::connect(addr))
+        .err()
+        .expect("handshake with an HTTP server should fail");
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+
+    let addr = spawn_http_peer();
+    let err = block_on(XSubSocket::connect(&addr.to_string()))
+        .err()
+        .expect("handshake with an HTTP server should fail");
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+}
+
+#[test]
+fn recv_timeout_is_timeout() {
+    let addr = spawn_peer(|listener| async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let _peer = DealerSocket::from_tcp(stream).await.unwrap();
+        monocoque::rt::sleep(Duration::from_millis(500)).await;
+    });
+
+    let err = block_on(async move {
+        let options = SocketOptions::default().with_recv_timeout(Duration::from_millis(50));
+        let mut dealer = DealerSocket::connect_with_options(&addr.to_string(), options)
+            .await
+            .unwrap();
+        dealer.recv().await.unwrap_err()
+    });
+    assert!(matches!(err, Error::Timeout(_)), "{err:?}");
+}
+
+#[test]
+fn oversized_message_is_message_too_large() {
+    let addr = spawn_peer(|listener| async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut peer = DealerSocket::from_tcp(stream).await.unwrap();
+        peer.send(vec![Bytes::from(vec![0u8; 1024])]).await.unwrap();
+        monocoque::rt::sleep(Duration::from_millis(500)).await;
+    });
+
+    let err = block_on(async move {
+        let options = SocketOptions::default().with_max_msg_size(Some(16));
+        let mut dealer = DealerSocket::connect_with_options(&addr.to_string(), options)
+            .await
+            .unwrap();
+        dealer.recv().await.unwrap_err()
+    });
+    assert!(matches!(err, Error::MessageTooLarge(_)), "{err:?}");
+}
+
+#[test]
+fn oversized_message_through_recv_into_is_message_too_large() {
+    let addr = spawn_peer(|listener| async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut peer = DealerSocket::from_tcp(stream).await.unwrap();
+        peer.send(vec![Bytes::from(vec![0u8; 1024])]).await.unwrap();
+        monocoque::rt::sleep(Duration::from_millis(500)).await;
+    });
+
+    let err = block_on(async move {
+        let options = SocketOptions::default().with_max_msg_size(Some(16));
+        let mut dealer = DealerSocket::connect_with_options(&addr.to_string(), options)
+            .await
+            .unwrap();
+        let mut msg = Vec::new();
+        dealer.recv_into(&mut msg).await.unwrap_err()
+    });
+    assert!(matches!(err, Error::MessageTooLarge(_)), "{err:?}");
+}
+
+#[test]
+fn router_hub_and_stream_errors_are_classified() {
+    block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let (mut hub, driver) = RouterHubSocket::accept_loop(listener, SocketOptions::default());
+        monocoque::rt::spawn_detached(driver);
+        hub.set_router_mandatory(true);
+        let err: Error = hub
+            .send_to(b"nobody", vec![Bytes::from_static(b"hi")])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Io(_)), "{err:?}");
+        assert_eq!(err.kind(), std::io::ErrorKind::HostUnreachable);
+
+        let mut stream = StreamSocket::bind("127.0.0.1:0").await.unwrap();
+        let err: Error = stream.send(Vec::new()).await.unwrap_err();
+        assert!(matches!(err, Error::Io(_)), "{err:?}");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    });
+}
@@ -0,0 +1,32 @@
+//! Inproc PAIR round trip through the public `monocoque::zmq::PairSocket`.
+
+use bytes::Bytes;
+use monocoque::rt::LocalRuntime;
+use monocoque::zmq::PairSocket;
+
+#[test]
+fn inproc_pair_round_trip() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let mut server = PairSocket::bind_inproc("inproc://pair-round-trip").unwrap();
+        let mut client = PairSocket::connect_inproc("inproc://pair-round-trip").unwrap();
+
+        client
+            .send(vec![Bytes::from("ping"), Bytes::from("1")])
+            .await
+            .unwrap();
+        let request = server.recv().await.unwrap().expect("client hung up");
+        assert_eq!(request, vec![Bytes::from("ping"), Bytes::from("1")]);
+
+        server.send(vec![Bytes::from("pong")]).await.unwrap();
+        let reply = client.recv().await.unwrap().expect("server hung up");
+        assert_eq!(reply, vec![Bytes::from("pong")]);
+    });
+}
+
+#[test]
+fn connect_inproc_to_an_unbound_endpoint_fails() {
+    let err = PairSocket::connect_inproc("inproc://pair-nobody-home")
+        .err()
+        .expect("nothing is bound there");
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
@@ -0,0 +1,43 @@
+//! libzmq's ERROR command reaches the Monocoque caller with its reason.
+//!
+//! A libzmq server with a ZAP domain asks its handler about every NULL peer;
+//! when the handler says no, libzmq sends ERROR with the status code in place
+//! of READY and closes. The Monocoque client must fail with
+//! `ConnectionRefused` naming that reason instead of hanging.
+
+use monocoque::zmq::DealerSocket;
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn libzmq_zap_denial_is_reported_with_its_reason() {
+    let ctx = zmq::Context::new();
+    let zap = ctx.socket(zmq::REP).unwrap();
+    zap.bind("inproc://zeromq.zap.01").unwrap();
+    let handler = thread::spawn(move || {
+        // version, request id, domain, address, identity, mechanism
+        let request = zap.recv_multipart(0).unwrap();
+        assert_eq!(request[5], b"NULL");
+        zap.send_multipart([&b"1.0"[..], &request[1], b"400", b"denied", b"", b""], 0)
+            .unwrap();
+    });
+
+    let server = ctx.socket(zmq::DEALER).unwrap();
+    server.set_zap_domain("global").unwrap();
+    server.set_linger(0).unwrap();
+    server.bind("tcp://127.0.0.1:*").unwrap();
+    let endpoint = server.get_last_endpoint().unwrap().unwrap();
+
+    let err = monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+        monocoque::rt::timeout(Duration::from_secs(5), DealerSocket::connect(&endpoint))
+            .await
+            .expect("handshake hung after the ZAP denial")
+            .err()
+            .expect("libzmq should refuse the connection")
+    });
+    assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused, "{err}");
+    assert!(err.to_string().contains("400"), "{err}");
+
+    handler.join().unwrap();
+}
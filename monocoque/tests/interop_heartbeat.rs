@@ -0,0 +1,111 @@
+//! ZMTP heartbeats between monocoque and libzmq.
+//!
+//! Each side in turn runs `ZMQ_HEARTBEAT_IVL` with a short timeout and then
+//! goes idle for several intervals. The connection only survives if the other
+//! side answers every PING with a PONG, so a message sent after the idle
+//! period must still arrive on the original connection.
+
+use bytes::Bytes;
+use monocoque::zmq::{DealerSocket, SocketOptions};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const IVL: Duration = Duration::from_millis(50);
+const TIMEOUT: Duration = Duration::from_millis(200);
+const IDLE: Duration = Duration::from_millis(600);
+
+#[test]
+fn libzmq_heartbeats_are_answered() {
+    let (ready_tx, ready_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (result_tx, result_rx) = mpsc::channel::<Result<(), String>>();
+
+    thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+            let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0")
+                .await
+                .unwrap();
+            ready_tx.send(listener.local_addr().unwrap()).unwrap();
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut dealer = DealerSocket::from_tcp(stream).await.unwrap();
+
+            // libzmq PINGs throughout the idle period; a missing PONG makes
+            // it drop the connection and this recv sees EOF instead.
+            match dealer.recv().await {
+                Ok(Some(msg)) if msg[0] == b"late"[..] => {}
+                other => {
+                    result_tx
+                        .send(Err(format!("expected \"late\", got {other:?}")))
+                        .unwrap();
+                    return;
+                }
+            }
+            dealer.send(vec![Bytes::from_static(b"ack")]).await.unwrap();
+            result_tx.send(Ok(())).unwrap();
+        });
+    });
+
+    let addr = ready_rx.recv().unwrap();
+
+    let ctx = zmq::Context::new();
+    let sock = ctx.socket(zmq::DEALER).unwrap();
+    sock.set_heartbeat_ivl(IVL.as_millis() as i32).unwrap();
+    sock.set_heartbeat_timeout(TIMEOUT.as_millis() as i32)
+        .unwrap();
+    sock.set_rcvtimeo(5000).unwrap();
+    sock.set_linger(0).unwrap();
+    sock.connect(&format!("tcp://{addr}")).unwrap();
+
+    thread::sleep(IDLE);
+    sock.send("late", 0).unwrap();
+    assert_eq!(sock.recv_string(0).unwrap().unwrap(), "ack");
+
+    result_rx
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .unwrap();
+}
+
+#[test]
+fn libzmq_answers_monocoque_heartbeats() {
+    let ctx = zmq::Context::new();
+    let sock = ctx.socket(zmq::DEALER).unwrap();
+    sock.set_rcvtimeo(5000).unwrap();
+    sock.set_linger(0).unwrap();
+    sock.bind("tcp://127.0.0.1:*").unwrap();
+    let endpoint = sock.get_last_endpoint().unwrap().unwrap();
+
+    let (result_tx, result_rx) = mpsc::channel::<Result<(), String>>();
+    thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+            let options = SocketOptions::default()
+                .with_heartbeat_ivl(IVL)
+                .with_heartbeat_timeout(TIMEOUT);
+            let mut dealer = DealerSocket::connect_with_options(&endpoint, options)
+                .await
+                .unwrap();
+            dealer
+                .send(vec![Bytes::from_static(b"hello")])
+                .await
+                .unwrap();
+
+            // Idle for several intervals: every PING needs a PONG back from
+            // libzmq or this recv fails with TimedOut.
+            let result = match dealer.recv().await {
+                Ok(Some(msg)) if msg[0] == b"late"[..] => Ok(()),
+                other => Err(format!("expected \"late\", got {other:?}")),
+            };
+            result_tx.send(result).unwrap();
+        });
+    });
+
+    assert_eq!(sock.recv_string(0).unwrap().unwrap(), "hello");
+    thread::sleep(IDLE);
+    sock.send("late", 0).unwrap();
+
+    result_rx
+        .recv_timeout(Duration::from_secs(5))
+        .unwrap()
+        .unwrap();
+}
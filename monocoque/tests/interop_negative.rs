@@ -0,0 +1,219 @@
+//! Misbehaving peers, on both sides of the wire.
+//!
+//! A raw TCP actor speaks broken ZMTP to a Monocoque socket and to a libzmq
+//! socket. Both must close the connection promptly; Monocoque must also
+//! report the violation through a typed error and `protocol_violations()`.
+//!
+//! Run with:
+//! `cargo test --package monocoque-rs --features libzmq-interop --test interop_negative`
+
+use monocoque::Error;
+use monocoque::zmq::{DealerSocket, protocol_violations};
+use monocoque_zmtp::codec::ZmtpError;
+use monocoque_zmtp::{HandshakeFailed, ProbeKind};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+use common::{greeting, ready};
+
+/// How long either implementation may take to drop a violator.
+const CLOSE_WITHIN: Duration = Duration::from_secs(5);
+
+/// Read until the peer closes, failing if it is still open after
+/// [`CLOSE_WITHIN`]. Anything the peer sent first (greeting, READY) is
+/// discarded.
+fn assert_closed(stream: &mut TcpStream) {
+    let deadline = Instant::now() + CLOSE_WITHIN;
+    let mut buf = [0u8; 1024];
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        assert!(!left.is_zero(), "peer kept the connection open");
+        stream.set_read_timeout(Some(left)).unwrap();
+        match stream.read(&mut buf) {
+            Ok(0) => return,
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::ConnectionReset => return,
+            Err(e) => panic!("peer kept the connection open: {e}"),
+        }
+    }
+}
+
+/// Serve one connection with a Monocoque DEALER while `script` writes raw
+/// bytes to it, and return the error the DEALER reported.
+///
+/// The DEALER fails either during the handshake or on its first `recv`.
+fn against_monocoque_server(script: &[u8]) -> Error {
+    let (addr_tx, addr_rx) = mpsc::channel::<SocketAddr>();
+    let (err_tx, err_rx) = mpsc::channel::<Error>();
+
+    thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+            let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0")
+                .await
+                .unwrap();
+            addr_tx.send(listener.local_addr().unwrap()).unwrap();
+            let (stream, _) = listener.accept().await.unwrap();
+            let err = match DealerSocket::from_tcp(stream).await {
+                Err(e) => e,
+                Ok(mut dealer) => match dealer.recv().await {
+                    Err(e) => e,
+                    Ok(msg) => panic!("violation was accepted, recv returned {msg:?}"),
+                },
+            };
+            err_tx.send(err).unwrap();
+        });
+    });
+
+    let mut stream = TcpStream::connect(addr_rx.recv().unwrap()).unwrap();
+    stream.write_all(script).unwrap();
+    assert_closed(&mut stream);
+    err_rx.recv_timeout(CLOSE_WITHIN).unwrap()
+}
+
+/// Bind a libzmq socket of `kind`, play `script` against it and require that
+/// libzmq hangs up.
+fn against_libzmq(kind: zmq::SocketType, script: &[u8]) {
+    let ctx = zmq::Context::new();
+    let socket = ctx.socket(kind).unwrap();
+    socket.bind("tcp://127.0.0.1:*").unwrap();
+    let endpoint = socket.get_last_endpoint().unwrap().unwrap();
+    let addr = endpoint.trim_start_matches("tcp://");
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(script).unwrap();
+    assert_closed(&mut stream);
+}
+
+fn assert_handshake_rejected(err: &Error) {
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+    assert_eq!(err.kind(), io::ErrorKind::Other, "{err}");
+    assert_eq!(err.to_string(), "Handshake failed: Protocol violation");
+}
+
+fn assert_mechanism_mismatch(err: &Error) {
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+    let inner = err
+        .io_error()
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<ZmtpError>())
+        .expect("mechanism mismatch should carry a ZmtpError");
+    assert!(
+        matches!(inner, ZmtpError::MechanismMismatch { peer, .. } if peer == "FOO"),
+        "{inner:?}"
+    );
+}
+
+#[test]
+fn monocoque_drops_wrong_greeting_signature() {
+    let before = protocol_violations();
+    let mut script = greeting(b"NULL");
+    script[9] = 0x00;
+    let err = against_monocoque_server(&script);
+    assert!(matches!(err, Error::Handshake(_)), "{err:?}");
+    let inner = err
+        .io_error()
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<HandshakeFailed>())
+        .expect("greeting error should carry HandshakeFailed");
+    assert!(
+        matches!(
+            inner.0,
+            ZmtpError::NotZmtp {
+                kind: ProbeKind::Unknown
+            }
+        ),
+        "{inner:?}"
+    );
+    assert!(protocol_violations() > before);
+}
+
+#[test]
+fn monocoque_drops_data_frame_in_place_of_ready() {
+    let before = protocol_violations();
+    let mut script = greeting(b"NULL");
+    script.extend_from_slice(b"\x00\x05hello");
+    let err = against_monocoque_server(&script);
+    assert_handshake_rejected(&err);
+    assert!(protocol_violations() > before);
+}
+
+#[test]
+fn monocoque_drops_unknown_mechanism() {
+    let before = protocol_violations();
+    let err = against_monocoque_server(&greeting(b"FOO"));
+    assert_mechanism_mismatch(&err);
+    assert!(protocol_violations() > before);
+}
+
+#[test]
+fn monocoque_drops_oversized_identity() {
+    let before = protocol_violations();
+    let mut script = greeting(b"NULL");
+    script.extend_from_slice(&ready(b"DEALER", Some(&[b'x'; 256])));
+    let err = against_monocoque_server(&script);
+    assert_handshake_rejected(&err);
+    assert!(protocol_violations() > before);
+}
+
+#[test]
+fn monocoque_drops_command_frame_with_more_flag() {
+    let before = protocol_violations();
+    let mut script = greeting(b"NULL");
+    script.extend_from_slice(&ready(b"DEALER", None));
+    script.extend_from_slice(b"\x05\x04PING");
+    let err = against_monocoque_server(&script);
+    assert!(matches!(err, Error::Protocol(_)), "{err:?}");
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{err}");
+    let inner = err
+        .io_error()
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<ZmtpError>())
+        .expect("decoder error should carry a ZmtpError");
+    assert!(matches!(inner, ZmtpError::Protocol), "{inner:?}");
+    assert!(protocol_violations() > before);
+}
+
+#[test]
+fn monocoque_client_drops_misbehaving_server() {
+    let before = protocol_violations();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.write_all(&greeting(b"FOO")).unwrap();
+        assert_closed(&mut stream);
+    });
+
+    let err = monocoque::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(async { DealerSocket::connect(&format!("tcp://{addr}")).await })
+        .err()
+        .expect("handshake with a FOO server must fail");
+    assert_mechanism_mismatch(&err);
+    assert!(protocol_violations() > before);
+    server.join().unwrap();
+}
+
+#[test]
+fn libzmq_drops_unknown_mechanism() {
+    against_libzmq(zmq::ROUTER, &greeting(b"FOO"));
+}
+
+#[test]
+fn libzmq_drops_data_frame_in_place_of_ready() {
+    let mut script = greeting(b"NULL");
+    script.extend_from_slice(b"\x00\x05hello");
+    against_libzmq(zmq::ROUTER, &script);
+}
+
+#[test]
+fn libzmq_drops_incompatible_socket_type() {
+    let mut script = greeting(b"NULL");
+    script.extend_from_slice(&ready(b"PUB", None));
+    against_libzmq(zmq::REP, &script);
+}
@@ -0,0 +1,130 @@
+//! PLAIN authentication against libzmq, in both directions.
+//!
+//! Each server validates credentials through a ZAP handler: a libzmq REP
+//! socket on the libzmq side, `spawn_zap_server` on the Monocoque side.
+
+use bytes::Bytes;
+use monocoque::zmq::{DealerSocket, SocketOptions};
+use monocoque_zmtp::codec::ZmtpError;
+use monocoque_zmtp::security::plain::StaticPlainHandler;
+use monocoque_zmtp::security::zap_handler::{DefaultZapHandler, spawn_zap_server};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Answer `requests` ZAP requests on `ctx`, accepting only alice/secret.
+fn libzmq_zap_handler(ctx: &zmq::Context, requests: usize) -> thread::JoinHandle<()> {
+    let zap = ctx.socket(zmq::REP).unwrap();
+    zap.bind("inproc://zeromq.zap.01").unwrap();
+    thread::spawn(move || {
+        for _ in 0..requests {
+            // version, request id, domain, address, identity, mechanism,
+            // username, password
+            let request = zap.recv_multipart(0).unwrap();
+            assert_eq!(request[5], b"PLAIN");
+            let accepted = request[6] == b"alice" && request[7] == b"secret";
+            let (status, text) = if accepted {
+                (&b"200"[..], &b"OK"[..])
+            } else {
+                (&b"400"[..], &b"bad credentials"[..])
+            };
+            zap.send_multipart([&b"1.0"[..], &request[1], status, text, b"alice", b""], 0)
+                .unwrap();
+        }
+    })
+}
+
+/// Connect a Monocoque PLAIN DEALER to `endpoint` and run one round trip.
+fn monocoque_client(endpoint: &str, password: &str) -> std::io::Result<()> {
+    monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = SocketOptions::default()
+            .with_plain_credentials("alice", password)
+            .with_routing_id(Bytes::from_static(b"alice-dealer"));
+        let mut dealer = DealerSocket::connect_with_options(endpoint, options).await?;
+        dealer.send(vec![Bytes::from_static(b"Ping")]).await?;
+        let reply = monocoque::rt::timeout(Duration::from_secs(5), dealer.recv())
+            .await
+            .expect("reply timed out")?
+            .expect("libzmq closed the connection");
+        assert_eq!(reply, vec![Bytes::from_static(b"Pong")]);
+        Ok(())
+    })
+}
+
+#[test]
+fn monocoque_plain_client_to_libzmq_server() {
+    let ctx = zmq::Context::new();
+    let zap = libzmq_zap_handler(&ctx, 2);
+    let router = ctx.socket(zmq::ROUTER).unwrap();
+    router.set_plain_server(true).unwrap();
+    router.set_rcvtimeo(5000).unwrap();
+    router.bind("tcp://127.0.0.1:*").unwrap();
+    let endpoint = router.get_last_endpoint().unwrap().unwrap();
+
+    let client = {
+        let endpoint = endpoint.clone();
+        thread::spawn(move || monocoque_client(&endpoint, "secret"))
+    };
+    let msg = router.recv_multipart(0).unwrap();
+    assert_eq!(msg, vec![b"alice-dealer".to_vec(), b"Ping".to_vec()]);
+    router
+        .send_multipart([&b"alice-dealer"[..], b"Pong"], 0)
+        .unwrap();
+    client.join().unwrap().unwrap();
+
+    // libzmq answers a rejected HELLO with ERROR.
+    let err = monocoque_client(&endpoint, "wrong").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied, "{err}");
+    let inner = err.get_ref().and_then(|e| e.downcast_ref::<ZmtpError>());
+    assert!(
+        matches!(inner, Some(ZmtpError::AuthenticationFailed)),
+        "{err}"
+    );
+    zap.join().unwrap();
+}
+
+#[test]
+fn libzmq_plain_client_to_monocoque_server() {
+    let (addr_tx, addr_rx) = std::sync::mpsc::channel::<std::net::SocketAddr>();
+
+    let server = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+            let mut users = StaticPlainHandler::new();
+            users.add_user("alice", "secret");
+            spawn_zap_server(Arc::new(DefaultZapHandler::new(Arc::new(users), false))).unwrap();
+
+            let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0")
+                .await
+                .unwrap();
+            addr_tx.send(listener.local_addr().unwrap()).unwrap();
+            let (stream, _) = listener.accept().await.unwrap();
+            let options = SocketOptions::default().with_plain_server(true);
+            let mut dealer = DealerSocket::from_tcp_with_options(stream, options)
+                .await
+                .unwrap();
+            let msg = monocoque::rt::timeout(Duration::from_secs(5), dealer.recv())
+                .await
+                .expect("request timed out")
+                .unwrap()
+                .expect("libzmq closed the connection");
+            assert_eq!(msg, vec![Bytes::from_static(b"Ping")]);
+            dealer
+                .send(vec![Bytes::from_static(b"Pong")])
+                .await
+                .unwrap();
+        });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    let ctx = zmq::Context::new();
+    let dealer = ctx.socket(zmq::DEALER).unwrap();
+    dealer.set_plain_username(Some("alice")).unwrap();
+    dealer.set_plain_password(Some("secret")).unwrap();
+    dealer.set_rcvtimeo(5000).unwrap();
+    dealer.connect(&format!("tcp://{addr}")).unwrap();
+
+    dealer.send("Ping", 0).unwrap();
+    let reply = dealer.recv_string(0).unwrap().unwrap();
+    assert_eq!(reply, "Pong");
+    server.join().unwrap();
+}
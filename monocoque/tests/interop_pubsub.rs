@@ -1,5 +1,5 @@
 use bytes::Bytes;
-use monocoque::zmq::PubSocket;
+use monocoque::zmq::{PubSocket, SubSocket};
 use std::thread;
 use std::time::Duration;
 
@@ -74,3 +74,37 @@ fn test_pubsub_basic() {
     let _ = stop_tx.send(());
     publisher.join().unwrap();
 }
+
+/// libzmq 4.3 speaks ZMTP 3.1, so our SUB subscribes with SUBSCRIBE/CANCEL
+/// commands; libzmq's XPUB must hand them up as the usual `0x01`/`0x00`
+/// messages and filter its publications by them.
+#[test]
+fn test_libzmq_xpub_understands_subscribe_and_cancel_commands() {
+    let ctx = zmq::Context::new();
+    let xpub = ctx.socket(zmq::XPUB).unwrap();
+    xpub.set_rcvtimeo(5000).unwrap();
+    xpub.bind("tcp://127.0.0.1:*").unwrap();
+    let endpoint = xpub.get_last_endpoint().unwrap().unwrap();
+
+    let publisher = thread::spawn(move || {
+        let subscribe = xpub.recv_bytes(0).unwrap();
+        assert_eq!(subscribe, b"\x01weather");
+        xpub.send_multipart([&b"sport"[..], b"dropped"], 0).unwrap();
+        xpub.send_multipart([&b"weather.paris"[..], b"sunny"], 0)
+            .unwrap();
+        let cancel = xpub.recv_bytes(0).unwrap();
+        assert_eq!(cancel, b"\x00weather");
+    });
+
+    monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+        let mut sub = SubSocket::connect(&endpoint).await.unwrap();
+        sub.subscribe(b"weather").await.unwrap();
+        let msg = sub.recv().await.unwrap().expect("XPUB disconnected");
+        assert_eq!(
+            msg,
+            vec![Bytes::from("weather.paris"), Bytes::from("sunny")]
+        );
+        sub.unsubscribe(b"weather").await.unwrap();
+        publisher.join().unwrap();
+    });
+}
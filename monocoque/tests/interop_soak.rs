@@ -0,0 +1,281 @@
+//! Randomized legal traffic from Monocoque clients to libzmq servers.
+//!
+//! Monocoque DEALER, REQ and SUB sockets exchange randomly sized multipart
+//! messages (including frames past the 255-byte short-frame limit) and random
+//! subscribe/unsubscribe sequences with libzmq ROUTER, REP and XPUB sockets.
+//! libzmq silently drops peers that break the spec, so each server watches its
+//! own monitor and the test fails on any disconnect or handshake failure
+//! before the clients say goodbye.
+//!
+//! Runs for 60 seconds unless `MONOCOQUE_SOAK_SECS` says otherwise; set
+//! `MONOCOQUE_SOAK_SEED` to replay a failing run.
+//!
+//! Run with:
+//! `cargo test --package monocoque-rs --features libzmq-interop --test interop_soak`
+
+use bytes::Bytes;
+use monocoque::zmq::{DealerSocket, ReqSocket, SubSocket, protocol_violations};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const DONE: &[u8] = b"__done__";
+const TOPICS: usize = 8;
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn soak_duration() -> Duration {
+    let secs = std::env::var("MONOCOQUE_SOAK_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(60);
+    Duration::from_secs(secs)
+}
+
+fn soak_seed() -> u64 {
+    std::env::var("MONOCOQUE_SOAK_SEED")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_nanos() as u64
+                | 1
+        })
+}
+
+/// xorshift64: small, seedable, and good enough to vary traffic shape.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// One to four parts; a quarter of them need the long frame header.
+    fn message(&mut self) -> Vec<Bytes> {
+        (0..=self.below(4))
+            .map(|_| {
+                let len = if self.below(4) == 0 {
+                    256 + self.below(8 * 1024)
+                } else {
+                    self.below(256)
+                };
+                (0..len)
+                    .map(|_| self.next() as u8)
+                    .collect::<Vec<_>>()
+                    .into()
+            })
+            .collect()
+    }
+}
+
+/// Bind `socket` on an ephemeral port and attach a monitor for the events
+/// that mean libzmq gave up on a peer. Returns the endpoint and the monitor.
+fn bind_watched(ctx: &zmq::Context, socket: &zmq::Socket, name: &str) -> (String, zmq::Socket) {
+    let events = zmq::SocketEvent::DISCONNECTED.to_raw()
+        | zmq::SocketEvent::HANDSHAKE_FAILED_NO_DETAIL.to_raw()
+        | zmq::SocketEvent::HANDSHAKE_FAILED_PROTOCOL.to_raw()
+        | zmq::SocketEvent::HANDSHAKE_FAILED_AUTH.to_raw();
+    let monitor_endpoint = format!("inproc://soak-monitor-{name}");
+    socket
+        .monitor(&monitor_endpoint, i32::from(events))
+        .unwrap();
+    let monitor = ctx.socket(zmq::PAIR).unwrap();
+    monitor.connect(&monitor_endpoint).unwrap();
+
+    socket.set_rcvtimeo(10_000).unwrap();
+    socket.bind("tcp://127.0.0.1:*").unwrap();
+    (socket.get_last_endpoint().unwrap().unwrap(), monitor)
+}
+
+/// Events queued on `monitor` so far.
+fn drain_events(monitor: &zmq::Socket) -> Vec<zmq::SocketEvent> {
+    let mut seen = Vec::new();
+    while let Ok(frames) = monitor.recv_multipart(zmq::DONTWAIT) {
+        let raw = u16::from_le_bytes([frames[0][0], frames[0][1]]);
+        seen.push(zmq::SocketEvent::from_raw(raw));
+    }
+    seen
+}
+
+/// Echo every request back until the client sends [`DONE`], then report the
+/// monitor events observed during the run.
+fn echo_server(
+    kind: zmq::SocketType,
+    name: &'static str,
+) -> (String, thread::JoinHandle<Vec<zmq::SocketEvent>>) {
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let ctx = zmq::Context::new();
+        let socket = ctx.socket(kind).unwrap();
+        let (endpoint, monitor) = bind_watched(&ctx, &socket, name);
+        tx.send(endpoint).unwrap();
+        loop {
+            let msg = socket.recv_multipart(0).unwrap();
+            let done = msg.last().is_some_and(|part| part == DONE);
+            let seen = done.then(|| drain_events(&monitor));
+            socket.send_multipart(msg, 0).unwrap();
+            if let Some(seen) = seen {
+                return seen;
+            }
+        }
+    });
+    (rx.recv().unwrap(), handle)
+}
+
+/// Publish one random message on each newly subscribed topic, until the
+/// client subscribes to [`DONE`].
+fn xpub_server(seed: u64) -> (String, thread::JoinHandle<Vec<zmq::SocketEvent>>) {
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let mut rng = Rng(seed);
+        let ctx = zmq::Context::new();
+        let socket = ctx.socket(zmq::XPUB).unwrap();
+        let (endpoint, monitor) = bind_watched(&ctx, &socket, "xpub");
+        tx.send(endpoint).unwrap();
+        loop {
+            let event = socket.recv_bytes(0).unwrap();
+            if event.first() != Some(&1) {
+                continue;
+            }
+            let topic = event[1..].to_vec();
+            let seen = (topic == DONE).then(|| drain_events(&monitor));
+            let mut msg = vec![topic];
+            msg.extend(rng.message().into_iter().map(|b| b.to_vec()));
+            socket.send_multipart(msg, 0).unwrap();
+            if let Some(seen) = seen {
+                return seen;
+            }
+        }
+    });
+    (rx.recv().unwrap(), handle)
+}
+
+/// Run `client` on its own Monocoque runtime thread.
+fn on_runtime<F, Fut>(client: F) -> thread::JoinHandle<usize>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = usize>,
+{
+    thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(client())
+    })
+}
+
+async fn dealer_client(endpoint: String, mut rng: Rng, deadline: Instant) -> usize {
+    let mut dealer = DealerSocket::connect(&endpoint).await.unwrap();
+    let mut round_trips = 0;
+    while Instant::now() < deadline {
+        let msg = rng.message();
+        dealer.send(msg.clone()).await.unwrap();
+        let reply = monocoque::rt::timeout(REPLY_TIMEOUT, dealer.recv())
+            .await
+            .expect("ROUTER reply timed out")
+            .unwrap()
+            .expect("ROUTER disconnected");
+        assert_eq!(reply, msg);
+        round_trips += 1;
+    }
+    dealer.send(vec![Bytes::from_static(DONE)]).await.unwrap();
+    dealer.recv().await.unwrap().expect("ROUTER disconnected");
+    round_trips
+}
+
+async fn req_client(endpoint: String, mut rng: Rng, deadline: Instant) -> usize {
+    let mut req = ReqSocket::connect(&endpoint).await.unwrap();
+    let mut round_trips = 0;
+    while Instant::now() < deadline {
+        let msg = rng.message();
+        req.send(msg.clone()).await.unwrap();
+        let reply = monocoque::rt::timeout(REPLY_TIMEOUT, req.recv())
+            .await
+            .expect("REP reply timed out")
+            .unwrap()
+            .expect("REP disconnected");
+        assert_eq!(reply, msg);
+        round_trips += 1;
+    }
+    req.send(vec![Bytes::from_static(DONE)]).await.unwrap();
+    req.recv().await.unwrap().expect("REP disconnected");
+    round_trips
+}
+
+async fn sub_client(endpoint: String, mut rng: Rng, deadline: Instant) -> usize {
+    let mut sub = SubSocket::connect(&endpoint).await.unwrap();
+    let mut subscribed = [false; TOPICS];
+    let mut deliveries = 0;
+    while Instant::now() < deadline {
+        let i = rng.below(TOPICS);
+        let topic = format!("topic-{i}");
+        if subscribed[i] {
+            sub.unsubscribe(topic.as_bytes()).await.unwrap();
+        } else {
+            sub.subscribe(topic.as_bytes()).await.unwrap();
+            // The XPUB answers each new subscription with exactly one message
+            // on that topic, so it must be the next one we see.
+            let msg = monocoque::rt::timeout(REPLY_TIMEOUT, sub.recv())
+                .await
+                .expect("XPUB publish timed out")
+                .unwrap()
+                .expect("XPUB disconnected");
+            assert_eq!(msg[0], topic.as_bytes());
+            deliveries += 1;
+        }
+        subscribed[i] = !subscribed[i];
+    }
+    sub.subscribe(DONE).await.unwrap();
+    let msg = sub.recv().await.unwrap().expect("XPUB disconnected");
+    assert_eq!(msg[0], DONE);
+    deliveries
+}
+
+#[test]
+fn legal_traffic_is_never_dropped_by_libzmq() {
+    let seed = soak_seed();
+    let duration = soak_duration();
+    println!("soak seed {seed}, duration {duration:?}");
+    let violations_before = protocol_violations();
+    let deadline = Instant::now() + duration;
+
+    let (router_endpoint, router) = echo_server(zmq::ROUTER, "router");
+    let (rep_endpoint, rep) = echo_server(zmq::REP, "rep");
+    let (xpub_endpoint, xpub) = xpub_server(seed ^ 0x5EED);
+
+    let clients = [
+        (
+            "DEALER",
+            on_runtime(move || dealer_client(router_endpoint, Rng(seed), deadline)),
+        ),
+        (
+            "REQ",
+            on_runtime(move || req_client(rep_endpoint, Rng(seed.rotate_left(21)), deadline)),
+        ),
+        (
+            "SUB",
+            on_runtime(move || sub_client(xpub_endpoint, Rng(seed.rotate_left(42)), deadline)),
+        ),
+    ];
+    for (name, client) in clients {
+        let exchanges = client.join().unwrap();
+        assert!(exchanges > 0, "{name} client made no progress");
+    }
+
+    for (name, server) in [("ROUTER", router), ("REP", rep), ("XPUB", xpub)] {
+        let seen = server.join().unwrap();
+        assert!(
+            seen.is_empty(),
+            "libzmq {name} reported {seen:?} (seed {seed})"
+        );
+    }
+    assert_eq!(protocol_violations(), violations_before);
+}
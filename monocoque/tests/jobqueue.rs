@@ -0,0 +1,175 @@
+//! Job queue pattern end to end: producer → ROUTER/ROUTER broker → workers.
+//!
+//! Acked jobs leave the broker and reach the producer as ACKs; a job held by
+//! a worker that dies before acking goes to the surviving worker.
+
+use bytes::Bytes;
+use monocoque::rt::{self, TcpListener};
+use monocoque::zmq::patterns::jobqueue::{
+    JobBroker, JobBrokerOptions, JobProducer, JobQueueStats, JobWorker,
+};
+use monocoque::zmq::{RouterSocket, SocketOptions};
+use std::time::{Duration, Instant};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Start a broker and return its frontend and backend endpoints.
+async fn start_broker(options: JobBrokerOptions) -> (String, String) {
+    let frontend = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let backend = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoints = (
+        frontend.local_addr().unwrap().to_string(),
+        backend.local_addr().unwrap().to_string(),
+    );
+    let (frontend, driver) = RouterSocket::accept_loop(frontend, SocketOptions::default());
+    rt::spawn_detached(driver);
+    let (backend, driver) = RouterSocket::accept_loop(backend, SocketOptions::default());
+    rt::spawn_detached(driver);
+    let mut broker = JobBroker::new(frontend, backend, options);
+    rt::spawn_detached(async move {
+        broker.run().await.unwrap();
+    });
+    endpoints
+}
+
+async fn wait_for(options: &JobBrokerOptions, what: &str, ready: impl Fn(JobQueueStats) -> bool) {
+    let deadline = Instant::now() + TIMEOUT;
+    while !ready(options.stats()) {
+        assert!(Instant::now() < deadline, "timed out waiting for {what}");
+        rt::sleep(Duration::from_millis(5)).await;
+    }
+}
+
+#[test]
+fn acked_jobs_leave_the_queue() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = JobBrokerOptions::default();
+        let (frontend, backend) = start_broker(options.clone()).await;
+        let mut worker = JobWorker::connect(&backend).await.unwrap();
+        let mut producer = JobProducer::connect(&frontend).await.unwrap();
+
+        let mut submitted = Vec::new();
+        for i in 0..3 {
+            let payload = vec![Bytes::from(format!("job-{i}"))];
+            submitted.push(producer.submit(payload).await.unwrap());
+        }
+
+        for (i, id) in submitted.iter().enumerate() {
+            let job = rt::timeout(TIMEOUT, worker.recv())
+                .await
+                .expect("job not delivered")
+                .unwrap()
+                .unwrap();
+            assert_eq!(&job.id, id);
+            assert_eq!(job.attempt, 1);
+            assert_eq!(job.payload, vec![Bytes::from(format!("job-{i}"))]);
+            worker.ack(&job.id).await.unwrap();
+
+            let acked = rt::timeout(TIMEOUT, producer.recv_ack())
+                .await
+                .expect("ACK not delivered")
+                .unwrap();
+            assert_eq!(acked.as_ref(), Some(id));
+        }
+        assert_eq!(producer.pending(), 0);
+
+        wait_for(&options, "completions", |stats| stats.completed == 3).await;
+        let stats = options.stats();
+        assert_eq!((stats.queued, stats.in_flight), (0, 0));
+        assert_eq!(stats.redelivered, 0);
+    });
+}
+
+#[test]
+fn jobs_of_a_killed_worker_go_to_the_survivor() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        let options = JobBrokerOptions::default();
+        let (frontend, backend) = start_broker(options.clone()).await;
+
+        // The first worker to register gets the first job.
+        let mut doomed = JobWorker::connect(&backend).await.unwrap();
+        wait_for(&options, "the first worker", |stats| stats.workers == 1).await;
+        let mut survivor = JobWorker::connect(&backend).await.unwrap();
+        wait_for(&options, "the second worker", |stats| stats.workers == 2).await;
+
+        let mut producer = JobProducer::connect(&frontend).await.unwrap();
+        let id = producer
+            .submit(vec![Bytes::from_static(b"payload")])
+            .await
+            .unwrap();
+
+        let job = rt::timeout(TIMEOUT, doomed.recv())
+            .await
+            .expect("job not delivered")
+            .unwrap()
+            .unwrap();
+        assert_eq!(job.id, id);
+        wait_for(&options, "the job to be in flight", |stats| {
+            stats.in_flight == 1
+        })
+        .await;
+
+        // Die mid-job, without acking.
+        drop(doomed);
+
+        let job = rt::timeout(TIMEOUT, survivor.recv())
+            .await
+            .expect("job not redelivered")
+            .unwrap()
+            .unwrap();
+        assert_eq!(job.id, id);
+        assert_eq!(job.attempt, 2);
+        assert_eq!(job.payload, vec![Bytes::from_static(b"payload")]);
+        survivor.ack(&job.id).await.unwrap();
+
+        let acked = rt::timeout(TIMEOUT, producer.recv_ack())
+            .await
+            .expect("ACK not delivered")
+            .unwrap();
+        assert_eq!(acked, Some(id));
+
+        wait_for(&options, "the completion", |stats| stats.completed == 1).await;
+        let stats = options.stats();
+        assert_eq!(stats.redelivered, 1);
+        assert_eq!(stats.workers, 1);
+        assert_eq!((stats.queued, stats.in_flight), (0, 0));
+    });
+}
+
+#[test]
+fn unacked_jobs_are_resent_by_the_producer() {
+    rt::LocalRuntime::new().unwrap().block_on(async {
+        // A broker that never answers: the producer's timer has to fire.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = listener.local_addr().unwrap().to_string();
+        let (mut router, driver) = RouterSocket::accept_loop(listener, SocketOptions::default());
+        rt::spawn_detached(driver);
+
+        let mut producer = JobProducer::connect(&endpoint)
+            .await
+            .unwrap()
+            .with_redelivery(Duration::from_millis(50));
+        let id = producer
+            .submit(vec![Bytes::from_static(b"payload")])
+            .await
+            .unwrap();
+
+        let ack = async {
+            let first = router.recv().await.unwrap().unwrap();
+            let second = router.recv().await.unwrap().unwrap();
+            assert_eq!(first[2..], second[2..], "resend differs from the original");
+            assert_eq!(second[3].as_ref(), id.as_bytes());
+            router
+                .send(vec![
+                    second[0].clone(),
+                    Bytes::from_static(b"ACK"),
+                    second[3].clone(),
+                ])
+                .await
+                .unwrap();
+        };
+        let (acked, ()) = futures::join!(rt::timeout(TIMEOUT, producer.recv_ack()), ack);
+        assert_eq!(acked.expect("ACK not received").unwrap(), Some(id));
+        assert_eq!(producer.pending(), 0);
+    });
+}
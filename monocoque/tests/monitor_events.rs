@@ -0,0 +1,176 @@
+//! Socket monitor event stream.
+//!
+//! Checks that a connected DEALER reports its peer on `Connected` and
+//! `Disconnected`, that the events are timestamped in order, and that a
+//! graceful `close()` flushes buffered messages before reporting `Closed`.
+//! Also checks that reaching the send high-water mark is reported once per
+//! crossing rather than on every refused send.
+
+use bytes::Bytes;
+use monocoque::rt::TcpListener;
+use monocoque::zmq::{DealerSocket, Endpoint, SocketEvent, SocketOptions};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn test_connected_then_disconnected_timestamps_are_ordered() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+
+    // Peer side: accept, send one message, then close the connection.
+    let peer = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut dealer = DealerSocket::from_tcp(stream).await.unwrap();
+                dealer.send(vec![Bytes::from("bye")]).await.unwrap();
+            });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let events = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let mut dealer = DealerSocket::connect(&addr.to_string()).await.unwrap();
+                let monitor = dealer.monitor();
+
+                assert_eq!(dealer.recv().await.unwrap(), Some(vec![Bytes::from("bye")]));
+                assert!(dealer.recv().await.unwrap().is_none(), "expected EOF");
+
+                monitor.drain().collect::<Vec<_>>()
+            })
+    })
+    .join()
+    .expect("client thread panicked");
+    peer.join().expect("peer thread panicked");
+
+    assert_eq!(events.len(), 2, "unexpected events: {events:?}");
+    let (connected, disconnected) = (&events[0], &events[1]);
+
+    assert!(matches!(connected.event, SocketEvent::Connected(_)));
+    assert!(matches!(disconnected.event, SocketEvent::Disconnected(_)));
+    assert_eq!(connected.peer, Some(Endpoint::Tcp(addr)));
+    assert_eq!(disconnected.peer, Some(Endpoint::Tcp(addr)));
+    assert!(
+        connected.timestamp <= disconnected.timestamp,
+        "Disconnected must not be stamped before Connected"
+    );
+}
+
+#[test]
+fn test_close_flushes_buffered_messages_within_linger() {
+    const MESSAGES: usize = 50;
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+
+    // Peer side: count messages until the closing DEALER hangs up.
+    let peer = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut dealer = DealerSocket::from_tcp(stream).await.unwrap();
+                let mut received = Vec::new();
+                while let Some(msg) = dealer.recv().await.unwrap() {
+                    received.push(msg);
+                }
+                received
+            })
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let events = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let options = SocketOptions::default().with_linger(Some(Duration::from_secs(1)));
+                let mut dealer = DealerSocket::connect_with_options(&addr.to_string(), options)
+                    .await
+                    .unwrap();
+                let monitor = dealer.monitor();
+
+                for i in 0..MESSAGES {
+                    dealer
+                        .send_buffered(vec![Bytes::from(format!("msg-{i}"))])
+                        .unwrap();
+                }
+                dealer.close().await.unwrap();
+
+                monitor.drain().collect::<Vec<_>>()
+            })
+    })
+    .join()
+    .expect("client thread panicked");
+    let received = peer.join().expect("peer thread panicked");
+
+    assert_eq!(received.len(), MESSAGES);
+    for (i, msg) in received.iter().enumerate() {
+        assert_eq!(msg, &vec![Bytes::from(format!("msg-{i}"))]);
+    }
+    let closed = events.last().expect("no monitor events");
+    assert!(matches!(closed.event, SocketEvent::Closed(Endpoint::Tcp(a)) if a == addr));
+}
+
+#[test]
+fn test_send_hwm_reached_and_cleared_are_reported_once() {
+    const HWM: usize = 10;
+    monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let peer = monocoque::rt::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut dealer = DealerSocket::from_tcp(stream).await.unwrap();
+            for _ in 0..=HWM {
+                dealer.recv().await.unwrap().expect("peer EOF");
+            }
+        });
+
+        let options = SocketOptions::default().with_send_hwm(HWM);
+        let mut dealer = DealerSocket::connect_with_options(&addr.to_string(), options)
+            .await
+            .unwrap();
+        let monitor = dealer.monitor();
+
+        for i in 0..HWM {
+            dealer
+                .send_buffered(vec![Bytes::from(format!("{i}"))])
+                .unwrap();
+        }
+        // Sitting at the limit: every further attempt is refused, but
+        // only the crossing is reported.
+        for _ in 0..5 {
+            assert!(dealer.send_buffered(vec![Bytes::from("over")]).is_err());
+        }
+        dealer.flush().await.unwrap();
+        // Below the limit again: nothing more to report.
+        dealer.send_buffered(vec![Bytes::from("after")]).unwrap();
+        dealer.flush().await.unwrap();
+        monocoque::rt::join(peer).await;
+
+        let hwm_events: Vec<_> = monitor
+            .drain()
+            .map(|e| e.event)
+            .filter(|e| {
+                matches!(
+                    e,
+                    SocketEvent::SendHwmReached { .. } | SocketEvent::SendHwmCleared
+                )
+            })
+            .collect();
+        assert_eq!(hwm_events.len(), 2, "unexpected events: {hwm_events:?}");
+        assert!(matches!(
+            hwm_events[0],
+            SocketEvent::SendHwmReached { buffered: HWM }
+        ));
+        assert!(matches!(hwm_events[1], SocketEvent::SendHwmCleared));
+    });
+}
@@ -0,0 +1,173 @@
+//! Allocation gate for the vectored multipart send path.
+//!
+//! Sends 5 x 64 KiB messages PUSH -> PULL. Each frame is above the default
+//! `vectored_write_threshold`, so every message goes out as one vectored write
+//! of per-frame headers plus the caller's own `Bytes` bodies: no body is
+//! copied, and the header and iovec scratch is reused across messages.
+//!
+//! Only the sending thread is counted. The messages are built before the
+//! measured window, so the window holds nothing but `send` itself. The runtime
+//! may allocate for each write it submits, so the same test first counts a raw
+//! stream writing the identical iovec layout and bounds `send` by that
+//! baseline rather than by a fixed number.
+
+use bytes::Bytes;
+use compio_io::AsyncWriteExt;
+use monocoque::rt::{LocalRuntime, TcpListener, TcpStream};
+use monocoque::zmq::{PullSocket, PushSocket};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::Read;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Set on the sending thread for the measured window only.
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+struct Counting;
+
+fn counting() -> bool {
+    COUNTING.try_with(Cell::get).unwrap_or(false)
+}
+
+// SAFETY: delegates every operation to the system allocator unchanged; the
+// counter only observes, it never touches the returned pointers.
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if counting() {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+        }
+        unsafe { System.alloc(layout) }
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if counting() {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+        }
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const FRAMES: usize = 5;
+const FRAME_SIZE: usize = 64 * 1024;
+const WARM: usize = 200;
+const MEAS: usize = 2_000;
+
+/// Count the allocations of `MEAS` vectored writes with the layout `send`
+/// produces (a 9-byte header before each 64 KiB body) on a raw stream, with
+/// the iovec list reused as the send path reuses it.
+///
+/// Everything counted here belongs to the runtime's own write path.
+async fn raw_write_baseline() -> usize {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let drain = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = vec![0; FRAME_SIZE];
+        while stream.read(&mut buf).unwrap() > 0 {}
+    });
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let header = Bytes::from_static(&[0x03, 0, 0, 0, 0, 0, 1, 0, 0]);
+    let body = Bytes::from(vec![0xA5; FRAME_SIZE]);
+    let mut iov: Vec<Bytes> = Vec::with_capacity(FRAMES * 2);
+    let mut write = async |iov: &mut Vec<Bytes>| {
+        for _ in 0..FRAMES {
+            iov.push(header.clone());
+            iov.push(body.clone());
+        }
+        let compio_buf::BufResult(result, mut returned) =
+            stream.write_vectored_all(std::mem::take(iov)).await;
+        result.unwrap();
+        returned.clear();
+        *iov = returned;
+    };
+
+    for _ in 0..WARM {
+        write(&mut iov).await;
+    }
+    let before = ALLOCS.load(Ordering::Relaxed);
+    COUNTING.with(|c| c.set(true));
+    for _ in 0..MEAS {
+        write(&mut iov).await;
+    }
+    COUNTING.with(|c| c.set(false));
+    let allocs = ALLOCS.load(Ordering::Relaxed) - before;
+
+    drop(stream);
+    drain.join().unwrap();
+    allocs
+}
+
+/// Ceiling for allocations charged to `MEAS` sends, given the raw-write
+/// `baseline`.
+///
+/// Measured over 2000 messages, the baseline is 0 on tokio and smol and
+/// about 4300 on compio, whose `io_uring` driver allocates per submitted write
+/// and splits a 320 KiB write into a varying number of partial ones. A
+/// quarter of the baseline absorbs that run-to-run variance, and `MEAS / 4`
+/// covers the ~400 reactor wakeups smol charges to the socket's send. One more
+/// allocation per message from the send path (`MEAS` in total) exceeds both.
+const fn max_window_allocs(baseline: usize) -> usize {
+    baseline + baseline / 4 + MEAS / 4
+}
+
+#[test]
+fn vectored_multipart_send_makes_no_per_message_allocation() {
+    let (port_tx, port_rx) = mpsc::channel::<u16>();
+
+    let receiver = thread::spawn(move || {
+        LocalRuntime::new().unwrap().block_on(async move {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            port_tx.send(listener.local_addr().unwrap().port()).unwrap();
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut pull = PullSocket::from_tcp(stream).await.unwrap();
+            for _ in 0..(WARM + MEAS) {
+                let msg = pull.recv().await.unwrap().expect("sender left early");
+                assert_eq!(msg.len(), FRAMES);
+            }
+        });
+    });
+
+    LocalRuntime::new().unwrap().block_on(async move {
+        let baseline = raw_write_baseline().await;
+
+        let port = port_rx.recv().unwrap();
+        let mut push = PushSocket::connect(("127.0.0.1", port)).await.unwrap();
+        let body = Bytes::from(vec![0xA5; FRAME_SIZE]);
+        let mut messages: Vec<Vec<Bytes>> = (0..(WARM + MEAS))
+            .map(|_| vec![body.clone(); FRAMES])
+            .collect();
+        let measured = messages.split_off(WARM);
+
+        for msg in messages {
+            push.send(msg).await.unwrap();
+        }
+
+        let before = ALLOCS.load(Ordering::Relaxed);
+        COUNTING.with(|c| c.set(true));
+        for msg in measured {
+            push.send(msg).await.unwrap();
+        }
+        COUNTING.with(|c| c.set(false));
+
+        let allocs = ALLOCS.load(Ordering::Relaxed) - before;
+        let ceiling = max_window_allocs(baseline);
+        assert!(
+            allocs <= ceiling,
+            "vectored multipart send allocated {allocs} times over {MEAS} messages \
+             (ceiling {ceiling}, raw writes of the same layout: {baseline})"
+        );
+    });
+    receiver.join().unwrap();
+}
@@ -0,0 +1,55 @@
+//! A message cut off by a lost connection must not leak into the next one.
+//!
+//! A raw TCP peer sends the first frame of a two-frame message and hangs up.
+//! After `recv_with_reconnect` dials again, the first message on the new
+//! connection must arrive exactly as sent, without the stale frame in front.
+
+use bytes::Bytes;
+use monocoque::rt::LocalRuntime;
+use monocoque::zmq::PullSocket;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::thread;
+
+mod common;
+use common::{greeting, ready};
+
+/// Accept one connection, answer the handshake and consume the client's.
+fn accept(listener: &TcpListener) -> TcpStream {
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.write_all(&greeting(b"NULL")).unwrap();
+    stream.write_all(&ready(b"PUSH", None)).unwrap();
+    let mut peer_greeting = [0u8; 64];
+    stream.read_exact(&mut peer_greeting).unwrap();
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).unwrap();
+    let mut peer_ready = vec![0u8; header[1] as usize];
+    stream.read_exact(&mut peer_ready).unwrap();
+    stream
+}
+
+#[test]
+fn reconnect_drops_frames_of_the_interrupted_message() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let peer = thread::spawn(move || {
+        let mut first = accept(&listener);
+        // MORE-flagged "stale", then the connection goes away.
+        first.write_all(b"\x01\x05stale").unwrap();
+        first.shutdown(Shutdown::Write).unwrap();
+        let _ = first.read_to_end(&mut Vec::new());
+
+        let mut second = accept(&listener);
+        second.write_all(b"\x00\x05fresh").unwrap();
+        // Hold the connection open until the client is done with it.
+        let _ = second.read_to_end(&mut Vec::new());
+    });
+
+    LocalRuntime::new().unwrap().block_on(async move {
+        let mut pull = PullSocket::connect(("127.0.0.1", port)).await.unwrap();
+        let msg = pull.recv_with_reconnect().await.unwrap().unwrap();
+        assert_eq!(msg, vec![Bytes::from_static(b"fresh")]);
+    });
+    peer.join().unwrap();
+}
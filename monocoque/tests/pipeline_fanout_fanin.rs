@@ -159,6 +159,72 @@ fn test_fanin_merges_every_worker() {
     assert_eq!(count, TOTAL, "fan-in merged {count}/{TOTAL} results");
 }
 
+/// `close()` closes every worker connection: afterwards the sink reports end of
+/// stream, and each worker sees its connection go away.
+#[test]
+fn test_fanin_close_closes_every_worker() {
+    let (addr_tx, addr_rx) = mpsc::channel::<std::net::SocketAddr>();
+    let (closed_tx, closed_rx) = mpsc::channel::<()>();
+
+    let sink = thread::spawn(move || {
+        monocoque::rt::LocalRuntime::new()
+            .unwrap()
+            .block_on(async move {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                addr_tx.send(listener.local_addr().unwrap()).unwrap();
+
+                let mut sink =
+                    PullFanIn::accept_workers(&listener, WORKERS, SocketOptions::default())
+                        .await
+                        .unwrap();
+                assert!(sink.is_connected());
+
+                sink.close().await.unwrap();
+                assert!(!sink.is_connected());
+                assert!(sink.recv().await.unwrap().is_none());
+                // A second close has nothing left to do.
+                sink.close().await.unwrap();
+                for _ in 0..WORKERS {
+                    closed_tx.send(()).unwrap();
+                }
+            });
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let workers: Vec<_> = (0..WORKERS)
+        .map(|_| {
+            thread::spawn(move || {
+                monocoque::rt::LocalRuntime::new()
+                    .unwrap()
+                    .block_on(async move {
+                        let mut push = PushSocket::connect(addr).await.unwrap();
+                        // Writes to a closed peer fail once it has reset the
+                        // connection.
+                        for _ in 0..100 {
+                            if push.send(vec![Bytes::from("late")]).await.is_err() {
+                                return true;
+                            }
+                            monocoque::rt::sleep(Duration::from_millis(50)).await;
+                        }
+                        false
+                    })
+            })
+        })
+        .collect();
+
+    for _ in 0..WORKERS {
+        closed_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+    sink.join().expect("sink thread panicked");
+    for handle in workers {
+        assert!(
+            handle.join().expect("worker thread panicked"),
+            "worker never saw its connection closed"
+        );
+    }
+}
+
 /// Write coalescing and its options must reach the per-worker sockets through
 /// `PushFanOut`. With coalescing on and the flush threshold set far above the
 /// total payload, the only way the buffered bytes leave userspace is the explicit
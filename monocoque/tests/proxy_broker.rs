@@ -0,0 +1,109 @@
+//! Broker built from the public `monocoque::zmq` API.
+//!
+//! ROUTER frontend ↔ DEALER backend through `proxy_steerable`, with a PAIR
+//! capture socket that sees a copy of every forwarded message.
+
+use bytes::Bytes;
+use monocoque::rt::{self, TcpListener};
+use monocoque::zmq::proxy::{ProxyOptions, proxy_steerable};
+use monocoque::zmq::{DealerSocket, PairSocket, RouterSocket};
+
+const REQUESTS: usize = 10;
+
+#[test]
+fn test_router_dealer_broker_with_capture() {
+    monocoque::rt::LocalRuntime::new().unwrap().block_on(async {
+        let fe_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let be_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let cap_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ctl_listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let fe_addr = fe_listener.local_addr().unwrap();
+        let be_addr = be_listener.local_addr().unwrap();
+        let cap_addr = cap_listener.local_addr().unwrap();
+        let ctl_addr = ctl_listener.local_addr().unwrap();
+
+        // Echo worker behind the DEALER backend. Peers hand their sockets
+        // back so no connection closes while the proxy is still running.
+        let worker = rt::spawn(async move {
+            let mut worker = DealerSocket::connect(&be_addr.to_string()).await.unwrap();
+            for _ in 0..REQUESTS {
+                let msg = worker.recv().await.unwrap().expect("worker EOF");
+                worker.send(msg).await.unwrap();
+            }
+            worker
+        });
+
+        // Capture consumer: counts every message the proxy forwards.
+        let capture = rt::spawn(async move {
+            let mut pair = PairSocket::connect(cap_addr).await.unwrap();
+            let mut count = 0;
+            while count < 2 * REQUESTS {
+                pair.recv().await.unwrap().expect("capture EOF");
+                count += 1;
+            }
+            (count, pair)
+        });
+
+        // Client in front of the ROUTER frontend, one request in flight at a
+        // time.
+        let client = rt::spawn(async move {
+            let mut client = DealerSocket::connect(&fe_addr.to_string()).await.unwrap();
+            let mut replies = Vec::with_capacity(REQUESTS);
+            for i in 0..REQUESTS {
+                client
+                    .send(vec![Bytes::new(), Bytes::from(format!("req-{i}"))])
+                    .await
+                    .unwrap();
+                replies.push(client.recv().await.unwrap().expect("client EOF"));
+            }
+            (replies, client)
+        });
+
+        let controller = rt::spawn(async move { PairSocket::connect(ctl_addr).await.unwrap() });
+
+        let mut frontend = RouterSocket::from_tcp(fe_listener.accept().await.unwrap().0)
+            .await
+            .unwrap();
+        let mut backend = DealerSocket::from_tcp(be_listener.accept().await.unwrap().0)
+            .await
+            .unwrap();
+        let mut capture_sock = PairSocket::from_tcp(cap_listener.accept().await.unwrap().0)
+            .await
+            .unwrap();
+        let mut control = PairSocket::from_tcp(ctl_listener.accept().await.unwrap().0)
+            .await
+            .unwrap();
+        let mut controller = rt::join(controller).await;
+
+        let broker = rt::spawn(async move {
+            proxy_steerable(
+                &mut frontend,
+                &mut backend,
+                Some(&mut capture_sock),
+                &mut control,
+                ProxyOptions::default(),
+            )
+            .await
+        });
+
+        let (replies, _client) = rt::join(client).await;
+        for (i, reply) in replies.iter().enumerate() {
+            assert_eq!(
+                reply,
+                &vec![Bytes::new(), Bytes::from(format!("req-{i}"))],
+                "reply {i} out of order or mangled"
+            );
+        }
+        let _worker = rt::join(worker).await;
+
+        // One copy per request and one per reply.
+        let (captured, _capture) = rt::join(capture).await;
+        assert_eq!(captured, 2 * REQUESTS);
+
+        controller
+            .send(vec![Bytes::from_static(b"TERMINATE")])
+            .await
+            .unwrap();
+        rt::join(broker).await.unwrap();
+    });
+}
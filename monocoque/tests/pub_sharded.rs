@@ -0,0 +1,162 @@
+//! PUB sharded across worker runtimes with `PubSocket::bind_sharded`.
+//!
+//! Checks that subscribers are spread evenly across shards, that every shard
+//! filters by its own subscribers' topics, and that closing the socket stops
+//! every shard.
+
+use bytes::Bytes;
+use monocoque::zmq::{PubSocket, SubSocket};
+use std::net::SocketAddr;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+const SHARDS: usize = 4;
+const SUBSCRIBERS: usize = 8;
+
+/// Run `f` on its own runtime thread.
+fn on_runtime<F, Fut, T>(f: F) -> thread::JoinHandle<T>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = T>,
+    T: Send + 'static,
+{
+    thread::spawn(move || monocoque::rt::LocalRuntime::new().unwrap().block_on(f()))
+}
+
+/// Connect `n` subscribers to `addr`, subscriber `i` to `topics(i)`.
+async fn connect_subscribers(
+    addr: SocketAddr,
+    n: usize,
+    topics: impl Fn(usize) -> Option<String>,
+) -> Vec<SubSocket> {
+    let mut subs = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut sub = SubSocket::connect(&format!("tcp://{addr}")).await.unwrap();
+        if let Some(topic) = topics(i) {
+            sub.subscribe(topic.as_bytes()).await.unwrap();
+        }
+        subs.push(sub);
+    }
+    subs
+}
+
+async fn recv_within(sub: &mut SubSocket) -> Option<Vec<Bytes>> {
+    monocoque::rt::timeout(Duration::from_secs(5), sub.recv())
+        .await
+        .expect("recv timed out")
+        .unwrap()
+}
+
+#[test]
+fn bind_sharded_rejects_zero_workers() {
+    let err = monocoque::rt::LocalRuntime::new()
+        .unwrap()
+        .block_on(PubSocket::bind_sharded("127.0.0.1:0", 0))
+        .err()
+        .expect("zero workers must be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn subscribers_are_spread_evenly_and_filtered_per_shard() {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    let (ready_tx, ready_rx) = mpsc::channel::<()>();
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+
+    let publisher = on_runtime(move || async move {
+        let mut socket = PubSocket::bind_sharded("127.0.0.1:0", SHARDS)
+            .await
+            .unwrap();
+        addr_tx.send(socket.local_addr().unwrap()).unwrap();
+        for _ in 0..SUBSCRIBERS {
+            socket.accept_subscriber().await.unwrap();
+        }
+        let stats = socket.stats();
+        let snapshot = socket.snapshot();
+
+        // Let every shard's subscription reader apply the subscriptions.
+        ready_rx.recv().unwrap();
+        thread::sleep(Duration::from_millis(100));
+        for topic in ["t0", "t1"] {
+            socket
+                .send(vec![Bytes::from(topic), Bytes::from("first")])
+                .await
+                .unwrap();
+        }
+        for topic in ["t1", "t0"] {
+            socket
+                .send(vec![Bytes::from(topic), Bytes::from("second")])
+                .await
+                .unwrap();
+        }
+
+        done_rx.recv().unwrap();
+        socket.close().await.unwrap();
+        (stats, snapshot)
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    // Ties rotate, so subscriber i lands on shard i % SHARDS and every shard
+    // holds one `t0` and one `t1` subscriber.
+    let topic = |i: usize| format!("t{}", i / SHARDS);
+    let client = on_runtime(move || async move {
+        let mut subs = connect_subscribers(addr, SUBSCRIBERS, |i| Some(topic(i))).await;
+        ready_tx.send(()).unwrap();
+        for (i, sub) in subs.iter_mut().enumerate() {
+            let want = Bytes::from(topic(i));
+            for body in ["first", "second"] {
+                let msg = recv_within(sub).await.expect("publisher closed");
+                assert_eq!(msg, vec![want.clone(), Bytes::from(body)], "subscriber {i}");
+            }
+        }
+        done_tx.send(()).unwrap();
+    });
+
+    client.join().expect("client panicked");
+    let (stats, snapshot) = publisher.join().expect("publisher panicked");
+    assert_eq!(stats.per_worker, vec![SUBSCRIBERS / SHARDS; SHARDS]);
+    assert_eq!(stats.subscribers, SUBSCRIBERS);
+
+    assert_eq!(snapshot.socket_type, "PUB");
+    assert_eq!(snapshot.state, "connected");
+    assert_eq!(snapshot.endpoint, Some(format!("tcp://{addr}")));
+    let per_queue: Vec<_> = snapshot.queues.iter().map(|q| q.peers).collect();
+    assert_eq!(per_queue, stats.per_worker);
+    assert_eq!(snapshot.queues[0].name, "worker 0");
+    assert_eq!(snapshot.hwm_dropped, stats.dropped);
+}
+
+#[test]
+fn close_stops_every_shard() {
+    let (addr_tx, addr_rx) = mpsc::channel();
+    let (ready_tx, ready_rx) = mpsc::channel::<()>();
+
+    let publisher = on_runtime(move || async move {
+        let mut socket = PubSocket::bind_sharded("127.0.0.1:0", SHARDS)
+            .await
+            .unwrap();
+        addr_tx.send(socket.local_addr().unwrap()).unwrap();
+        for _ in 0..SHARDS {
+            socket.accept_subscriber().await.unwrap();
+        }
+        ready_rx.recv().unwrap();
+        socket.send(vec![Bytes::from("last")]).await.unwrap();
+        // Returns only once every worker thread has been joined.
+        socket.close().await.unwrap();
+    });
+
+    let addr = addr_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    let client = on_runtime(move || async move {
+        let mut subs = connect_subscribers(addr, SHARDS, |_| None).await;
+        ready_tx.send(()).unwrap();
+        for sub in &mut subs {
+            // Queued broadcasts are delivered before the shard stops.
+            assert_eq!(recv_within(sub).await, Some(vec![Bytes::from("last")]));
+            assert_eq!(recv_within(sub).await, None);
+        }
+    });
+
+    publisher.join().expect("publisher panicked");
+    client.join().expect("client panicked");
+}
@@ -4,8 +4,9 @@
 
 use bytes::Bytes;
 use monocoque_core::options::SocketOptions;
-use monocoque_zmtp::rep::RepSocket;
-use monocoque_zmtp::req::ReqSocket;
+use monocoque_zmtp::rep::{RepSocket, RepState};
+use monocoque_zmtp::req::{ReqSocket, ReqState};
+use monocoque_zmtp::router::RouterSocket;
 use std::io;
 
 /// Drive an async test body on whichever runtime backend is active.
@@ -117,8 +118,8 @@ async fn test_req_strict_recv_recv_fails_impl() -> io::Result<()> {
 /// Test relaxed REQ mode - send→send→recv→send→recv works
 ///
 /// Relaxed mode skips the strict enforcement that prevents send-after-send.
-/// Full request pipelining (N sends then N recvs) is not yet implemented  -
-/// each `recv()` still transitions to Idle, so send/recv still need to interleave.
+/// Without correlation there is no way to tell replies apart, so each `recv()`
+/// still transitions to Idle and send/recv need to interleave.
 #[test]
 fn test_req_relaxed_send_send_succeeds() -> io::Result<()> {
     block_on(test_req_relaxed_send_send_succeeds_impl())
@@ -260,3 +261,344 @@ async fn test_req_correlation_mode_impl() -> io::Result<()> {
     monocoque::rt::join(server_task).await?;
     Ok(())
 }
+
+/// Test relaxed + correlation mode - replies may arrive out of order
+#[test]
+fn test_req_relaxed_correlation_out_of_order() -> io::Result<()> {
+    block_on(test_req_relaxed_correlation_out_of_order_impl())
+}
+
+async fn test_req_relaxed_correlation_out_of_order_impl() -> io::Result<()> {
+    // A ROUTER peer collects all three requests before answering any
+    let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0").await?;
+    let server_addr = listener.local_addr()?;
+
+    let server_task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let mut router = RouterSocket::new(stream).await?;
+
+        // [identity, correlation ID, delimiter, body]
+        let mut requests = Vec::new();
+        for i in 1..=3 {
+            let req = router.recv().await?.expect("Should receive request");
+            assert_eq!(req.len(), 4);
+            assert_eq!(req[3], Bytes::from(format!("request{i}")));
+            requests.push(req);
+        }
+
+        // Answer 2, 3, 1, echoing each envelope
+        for i in [2, 3, 1] {
+            let mut reply = requests[i - 1][..3].to_vec();
+            reply.push(Bytes::from(format!("reply{i}")));
+            router.send(reply).await?;
+        }
+
+        Ok::<(), io::Error>(())
+    });
+
+    monocoque::rt::sleep(std::time::Duration::from_millis(50)).await;
+
+    let stream = monocoque::rt::TcpStream::connect(server_addr).await?;
+    let options = SocketOptions {
+        req_relaxed: true,
+        req_correlate: true,
+        ..Default::default()
+    };
+    let mut req_socket = ReqSocket::with_options(stream, options).await?;
+
+    for i in 1..=3 {
+        req_socket
+            .send(vec![Bytes::from(format!("request{i}"))])
+            .await?;
+    }
+
+    // Each reply is matched to its request and returned as it arrives
+    for i in [2, 3, 1] {
+        let reply = req_socket.recv().await?.expect("Should receive reply");
+        assert_eq!(reply, vec![Bytes::from(format!("reply{i}"))]);
+    }
+
+    // Every request has been answered, so the socket is idle again
+    let err = req_socket.recv().await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    monocoque::rt::join(server_task).await?;
+    Ok(())
+}
+
+/// Test relaxed + correlation mode - unanswered requests are tracked up to `send_hwm`
+#[test]
+fn test_req_relaxed_correlation_tracks_at_most_send_hwm() -> io::Result<()> {
+    block_on(test_req_relaxed_correlation_tracks_at_most_send_hwm_impl())
+}
+
+async fn test_req_relaxed_correlation_tracks_at_most_send_hwm_impl() -> io::Result<()> {
+    const HWM: usize = 4;
+    const REQUESTS: usize = 50;
+
+    // A ROUTER peer takes every request, then answers only the newest and
+    // the oldest
+    let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0").await?;
+    let server_addr = listener.local_addr()?;
+
+    let server_task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let mut router = RouterSocket::new(stream).await?;
+
+        let mut requests = Vec::new();
+        for _ in 0..REQUESTS {
+            requests.push(router.recv().await?.expect("Should receive request"));
+        }
+        for req in [&requests[REQUESTS - 1], &requests[0]] {
+            let mut reply = req[..3].to_vec();
+            reply.push(Bytes::from("reply"));
+            router.send(reply).await?;
+        }
+
+        Ok::<(), io::Error>(())
+    });
+
+    monocoque::rt::sleep(std::time::Duration::from_millis(50)).await;
+
+    let stream = monocoque::rt::TcpStream::connect(server_addr).await?;
+    let options = SocketOptions {
+        req_relaxed: true,
+        req_correlate: true,
+        send_hwm: HWM,
+        ..Default::default()
+    };
+    let mut req_socket = ReqSocket::with_options(stream, options).await?;
+
+    for i in 0..REQUESTS {
+        req_socket
+            .send(vec![Bytes::from(format!("request{i}"))])
+            .await?;
+    }
+
+    // The newest request is still tracked
+    let reply = req_socket.recv().await?.expect("Should receive reply");
+    assert_eq!(reply, vec![Bytes::from("reply")]);
+
+    // The oldest was forgotten long ago; only the last HWM - 1 remain
+    let err = req_socket.recv().await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(
+        err.to_string().contains("expected one of [47, 48, 49]"),
+        "unexpected error: {err}"
+    );
+
+    monocoque::rt::join(server_task).await?;
+    Ok(())
+}
+
+/// Test REP envelope tracking - each reply is routed back with its request's envelope
+#[test]
+fn test_rep_envelope_three_requests() -> io::Result<()> {
+    block_on(test_rep_envelope_three_requests_impl())
+}
+
+async fn test_rep_envelope_three_requests_impl() -> io::Result<()> {
+    let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0").await?;
+    let server_addr = listener.local_addr()?;
+
+    let server_task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let mut rep_socket = RepSocket::new(stream).await?;
+
+        // Reply before any request is a usage error
+        let err = rep_socket
+            .send(vec![Bytes::from("early")])
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+        for i in 0..3 {
+            // The envelope (correlation ID + delimiter) never reaches the app
+            let req = rep_socket.recv().await?.expect("Should receive request");
+            assert_eq!(req, vec![Bytes::from(format!("request{i}"))]);
+
+            if i == 0 {
+                let err = rep_socket.recv().await.unwrap_err();
+                assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            }
+
+            let mut reply = req.clone();
+            reply.push(Bytes::from(format!("reply{i}")));
+            rep_socket.send(reply).await?;
+        }
+
+        Ok::<(), io::Error>(())
+    });
+
+    monocoque::rt::sleep(std::time::Duration::from_millis(50)).await;
+
+    // Correlation puts a request ID in the envelope, so a reply carrying
+    // the wrong envelope would be rejected by the REQ side.
+    let stream = monocoque::rt::TcpStream::connect(server_addr).await?;
+    let options = SocketOptions {
+        req_correlate: true,
+        ..Default::default()
+    };
+    let mut req_socket = ReqSocket::with_options(stream, options).await?;
+
+    for i in 0..3 {
+        req_socket
+            .send(vec![Bytes::from(format!("request{i}"))])
+            .await?;
+        let reply = req_socket.recv().await?.expect("Should receive reply");
+        assert_eq!(
+            reply,
+            vec![
+                Bytes::from(format!("request{i}")),
+                Bytes::from(format!("reply{i}")),
+            ]
+        );
+    }
+
+    monocoque::rt::join(server_task).await?;
+    Ok(())
+}
+
+/// Test `send_recv` / `recv_send` - one call per round trip on each side
+#[test]
+fn test_send_recv_round_trips() -> io::Result<()> {
+    block_on(test_send_recv_round_trips_impl())
+}
+
+async fn test_send_recv_round_trips_impl() -> io::Result<()> {
+    let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0").await?;
+    let server_addr = listener.local_addr()?;
+
+    let server_task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let options = SocketOptions {
+            recv_timeout: Some(std::time::Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let mut rep_socket = RepSocket::with_options(stream, options).await?;
+
+        // Nothing has been sent yet: the timeout leaves the socket awaiting
+        // a request, so the next recv_send picks up the first one.
+        let err = rep_socket.recv_send(|req| req).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(rep_socket.state(), RepState::AwaitingRequest);
+        rep_socket
+            .update_options(|o| o.recv_timeout = None)
+            .map_err(io::Error::from)?;
+
+        for _ in 0..3 {
+            rep_socket
+                .recv_send(|mut req| {
+                    req.push(Bytes::from("reply"));
+                    req
+                })
+                .await?;
+        }
+
+        Ok::<(), io::Error>(())
+    });
+
+    let stream = monocoque::rt::TcpStream::connect(server_addr).await?;
+    let mut req_socket = ReqSocket::new(stream).await?;
+    monocoque::rt::sleep(std::time::Duration::from_millis(150)).await;
+
+    for i in 0..3 {
+        let reply = req_socket
+            .send_recv(vec![Bytes::from(format!("request{i}"))])
+            .await?;
+        assert_eq!(
+            reply,
+            vec![Bytes::from(format!("request{i}")), Bytes::from("reply")]
+        );
+        assert_eq!(req_socket.state(), ReqState::Idle);
+    }
+
+    monocoque::rt::join(server_task).await?;
+    Ok(())
+}
+
+/// Test `send_recv` recovery - a timed-out request leaves the socket idle
+#[test]
+fn test_send_recv_recovers_from_timeout() -> io::Result<()> {
+    block_on(test_send_recv_recovers_from_timeout_impl())
+}
+
+async fn test_send_recv_recovers_from_timeout_impl() -> io::Result<()> {
+    // A ROUTER peer that never answers the first request
+    let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0").await?;
+    let server_addr = listener.local_addr()?;
+
+    let server_task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let mut router = RouterSocket::new(stream).await?;
+
+        // [identity, delimiter, body]
+        let dropped = router.recv().await?.expect("Should receive request");
+        assert_eq!(dropped[2], Bytes::from("lost"));
+        let req = router.recv().await?.expect("Should receive request");
+        assert_eq!(req[2], Bytes::from("retry"));
+        let mut reply = req[..2].to_vec();
+        reply.push(Bytes::from("answer"));
+        router.send(reply).await?;
+
+        Ok::<(), io::Error>(())
+    });
+
+    monocoque::rt::sleep(std::time::Duration::from_millis(50)).await;
+
+    let stream = monocoque::rt::TcpStream::connect(server_addr).await?;
+    let options = SocketOptions {
+        recv_timeout: Some(std::time::Duration::from_millis(100)),
+        ..Default::default()
+    };
+    let mut req_socket = ReqSocket::with_options(stream, options).await?;
+
+    let err = req_socket
+        .send_recv(vec![Bytes::from("lost")])
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(req_socket.state(), ReqState::Idle);
+
+    // Strict mode would refuse a plain send() here; send_recv goes ahead.
+    let reply = req_socket.send_recv(vec![Bytes::from("retry")]).await?;
+    assert_eq!(reply, vec![Bytes::from("answer")]);
+
+    monocoque::rt::join(server_task).await?;
+    Ok(())
+}
+
+/// Test `send_recv` recovery - a peer that closes mid-request leaves the
+/// socket idle
+#[test]
+fn test_send_recv_recovers_from_peer_close() -> io::Result<()> {
+    block_on(test_send_recv_recovers_from_peer_close_impl())
+}
+
+async fn test_send_recv_recovers_from_peer_close_impl() -> io::Result<()> {
+    let listener = monocoque::rt::TcpListener::bind("127.0.0.1:0").await?;
+    let server_addr = listener.local_addr()?;
+
+    // A REP peer that takes the request and hangs up without replying
+    let server_task = monocoque::rt::spawn(async move {
+        let (stream, _) = listener.accept().await?;
+        let mut rep_socket = RepSocket::new(stream).await?;
+        let req = rep_socket.recv().await?.expect("Should receive request");
+        assert_eq!(req, vec![Bytes::from("unanswered")]);
+        drop(rep_socket);
+        Ok::<(), io::Error>(())
+    });
+
+    let stream = monocoque::rt::TcpStream::connect(server_addr).await?;
+    let mut req_socket = ReqSocket::new(stream).await?;
+
+    let err = req_socket
+        .send_recv(vec![Bytes::from("unanswered")])
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(req_socket.state(), ReqState::Idle);
+
+    monocoque::rt::join(server_task).await?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! The XPUB and XSUB wrappers forward the option and state accessors of the
+//! sockets they wrap.
+
+use monocoque::rt::LocalRuntime;
+use monocoque::zmq::{Endpoint, XPubSocket, XSubSocket};
+
+#[test]
+fn xpub_and_xsub_forward_options_state_and_close() {
+    LocalRuntime::new().unwrap().block_on(async {
+        let mut xpub = XPubSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = xpub.local_addr().unwrap();
+        assert_eq!(xpub.last_endpoint(), Some(&Endpoint::Tcp(addr)));
+        assert!(!xpub.is_connected());
+        assert!(!xpub.is_poisoned());
+
+        let subscriber =
+            monocoque::rt::spawn(async move { XSubSocket::connect(&addr.to_string()).await });
+        xpub.accept().await.unwrap();
+        let mut xsub = monocoque::rt::join(subscriber).await.unwrap();
+        assert!(xpub.is_connected());
+
+        xpub.update_options(|o| o.send_hwm = 5).unwrap();
+        assert_eq!(xpub.options().send_hwm, 5);
+        xsub.update_options(|o| o.recv_hwm = 7).unwrap();
+        assert_eq!(xsub.options().recv_hwm, 7);
+        assert!(!xsub.is_poisoned());
+
+        xsub.close().await.unwrap();
+        xpub.close().await.unwrap();
+    });
+}